@@ -60,8 +60,9 @@ fuzz_target!(|data: &[u8]| {
 
     with_runtime(|runtime| {
         let _ = runtime.block_on(async {
+            let expected = strest::args::ExpectedStatus::single(200);
             let result =
-                strest::metrics::read_metrics_log(&path, 200, &None, 10_000, None).await;
+                strest::metrics::read_metrics_log(&path, expected, &None, 10_000, None).await;
             if let Ok(log) = result {
                 let summary = &log.summary;
                 debug_assert_eq!(
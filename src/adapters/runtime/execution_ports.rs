@@ -67,7 +67,8 @@ impl DistributedRunPort<TesterArgs> for RuntimeDistributedPort {
         adapter_args: &TesterArgs,
         scenarios: Option<BTreeMap<String, ScenarioConfig>>,
     ) -> AppResult<()> {
-        crate::distributed::run_controller(adapter_args, scenarios).await
+        let local_port = RuntimeAgentLocalRunPort;
+        crate::distributed::run_controller(adapter_args, scenarios, &local_port).await
     }
 
     async fn run_agent(&self, adapter_args: &TesterArgs) -> AppResult<()> {
@@ -0,0 +1,181 @@
+use crate::{app::summary::SummaryStats, args::TesterArgs, metrics};
+
+const PERCENT_DIVISOR: u64 = 100;
+
+/// Result of one `--assert-*` threshold check on the final run results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AssertionResult {
+    pub(crate) name: &'static str,
+    pub(crate) expected: String,
+    pub(crate) observed: String,
+    pub(crate) passed: bool,
+}
+
+impl AssertionResult {
+    /// One-line description used in failure messages.
+    pub(crate) fn message(&self) -> String {
+        format!(
+            "{}: observed {}, expected {}",
+            self.name, self.observed, self.expected
+        )
+    }
+}
+
+/// Evaluates the configured thresholds, in a stable order.
+pub(crate) fn evaluate(
+    args: &TesterArgs,
+    summary: &metrics::MetricsSummary,
+    stats: &SummaryStats,
+    p99: u64,
+) -> Vec<AssertionResult> {
+    let mut results = Vec::new();
+    if let Some(min_rate) = args.assert_success_rate {
+        results.push(AssertionResult {
+            name: "success_rate",
+            expected: format!(">= {}", min_rate),
+            observed: format!(
+                "{}.{:02}%",
+                stats.success_rate_x100 / PERCENT_DIVISOR,
+                stats.success_rate_x100 % PERCENT_DIVISOR
+            ),
+            passed: summary.total_requests > 0 && stats.success_rate_x100 >= min_rate.x100(),
+        });
+    }
+    if let Some(max_p99) = args.assert_p99_ms {
+        results.push(AssertionResult {
+            name: "p99_latency",
+            expected: format!("<= {}ms", max_p99),
+            observed: format!("{}ms", p99),
+            passed: p99 <= max_p99,
+        });
+    }
+    if let Some(max_errors) = args.assert_max_errors {
+        results.push(AssertionResult {
+            name: "error_count",
+            expected: format!("<= {}", max_errors),
+            observed: summary.error_requests.to_string(),
+            passed: summary.error_requests <= max_errors,
+        });
+    }
+    results
+}
+
+/// Renders the results as a JUnit XML `<testsuite>`, one `<testcase>` per assertion.
+pub(crate) fn junit_xml(results: &[AssertionResult], duration: std::time::Duration) -> String {
+    let failures = results.iter().filter(|result| !result.passed).count();
+    let mut lines = vec![
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_owned(),
+        format!(
+            "<testsuite name=\"strest\" tests=\"{}\" failures=\"{}\" errors=\"0\" time=\"{}.{:03}\">",
+            results.len(),
+            failures,
+            duration.as_secs(),
+            duration.subsec_millis()
+        ),
+    ];
+    for result in results {
+        let name = xml_escape(result.name);
+        if result.passed {
+            lines.push(format!(
+                "  <testcase classname=\"strest.assertions\" name=\"{}\"/>",
+                name
+            ));
+            continue;
+        }
+        let message = xml_escape(&result.message());
+        lines.push(format!(
+            "  <testcase classname=\"strest.assertions\" name=\"{}\">",
+            name
+        ));
+        lines.push(format!(
+            "    <failure type=\"threshold\" message=\"{}\">{}</failure>",
+            message, message
+        ));
+        lines.push("  </testcase>".to_owned());
+    }
+    lines.push("</testsuite>".to_owned());
+    let mut xml = lines.join("\n");
+    xml.push('\n');
+    xml
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::parse_test_args;
+    use crate::error::{AppError, AppResult};
+
+    #[test]
+    fn junit_reports_breaches_as_failures() -> AppResult<()> {
+        let args = parse_test_args([
+            "strest",
+            "--url",
+            "http://localhost",
+            "--assert-success-rate",
+            "99.5",
+            "--assert-p99-ms",
+            "250",
+            "--assert-max-errors",
+            "3",
+        ])?;
+        let summary = metrics::MetricsSummary {
+            duration: std::time::Duration::from_millis(1500),
+            total_requests: 1000,
+            successful_requests: 998,
+            error_requests: 2,
+            timeout_requests: 0,
+            transport_errors: 0,
+            non_expected_status: 2,
+            min_latency_ms: 1,
+            max_latency_ms: 400,
+            avg_latency_ms: 20,
+            success_min_latency_ms: 1,
+            success_max_latency_ms: 400,
+            success_avg_latency_ms: 20,
+            http_versions: std::collections::BTreeMap::new(),
+            error_reasons: std::collections::BTreeMap::new(),
+            check_failures: crate::metrics::CheckFailures::default(),
+        };
+        let stats = SummaryStats {
+            success_rate_x100: 9980,
+            avg_rps_x100: 0,
+            avg_rpm_x100: 0,
+        };
+
+        let results = evaluate(&args, &summary, &stats, 310);
+        let passed: Vec<bool> = results.iter().map(|result| result.passed).collect();
+        if passed != [true, false, true] {
+            return Err(AppError::validation(format!(
+                "Unexpected assertion results: {:?}",
+                results
+            )));
+        }
+
+        let xml = junit_xml(&results, std::time::Duration::from_millis(1500));
+        if !xml.contains("tests=\"3\" failures=\"1\" errors=\"0\" time=\"1.500\"")
+            || !xml.contains("name=\"success_rate\"/>")
+            || !xml.contains("message=\"p99_latency: observed 310ms, expected &lt;= 250ms\"")
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected JUnit XML:\n{}",
+                xml
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 mod compare_output;
+mod verdict;
 
 use std::io::{self, IsTerminal};
 use std::path::Path;
@@ -10,7 +11,8 @@ use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use tokio::sync::watch;
 
 use crate::args::CompareArgs;
-use crate::error::{AppError, AppResult, MetricsError};
+use crate::error::{AppError, AppResult, MetricsError, ValidationError};
+use crate::metrics::LatencyHistogram;
 use crate::system::replay_compare::{
     PlaybackAction, PlaybackState, advance_playback, apply_playback_action,
     clamp_window_to_records, records_range, resolve_step_ms,
@@ -19,7 +21,8 @@ use crate::ui::model::{CompareOverlay, UiData};
 use crate::ui::render::setup_render_ui;
 
 use super::replay::{SnapshotMarkers, build_ui_data_with_config, read_records_from_path};
-use compare_output::print_compare_summary;
+use compare_output::{histogram_metrics, print_compare_summary, print_histogram_comparison};
+use verdict::{print_compare_deltas, regression_failures, report_verdict};
 
 /// Playback tick used when compare is in "playing" mode.
 const COMPARE_TICK_MS: u64 = 1000;
@@ -33,6 +36,15 @@ const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(0);
 pub(crate) async fn run_compare(args: &CompareArgs) -> AppResult<()> {
     let left_path = Path::new(&args.left);
     let right_path = Path::new(&args.right);
+    match (is_histogram_log(left_path), is_histogram_log(right_path)) {
+        (true, true) => return compare_histograms(args, left_path, right_path).await,
+        (false, false) => {}
+        _ => {
+            return Err(AppError::validation(
+                ValidationError::CompareHistogramMismatch,
+            ));
+        }
+    }
     let mut left_records = read_records_from_path(left_path).await?;
     let mut right_records = read_records_from_path(right_path).await?;
     if left_records.is_empty() || right_records.is_empty() {
@@ -49,8 +61,9 @@ pub(crate) async fn run_compare(args: &CompareArgs) -> AppResult<()> {
     let start_ms = left_min.min(right_min);
     let end_ms = left_max.max(right_max);
 
-    if !io::stdout().is_terminal() || args.no_ui {
-        print_compare_summary(
+    let gating = args.regress_p99.is_some() || args.regress_success.is_some();
+    if !io::stdout().is_terminal() || args.no_ui || gating {
+        let left = print_compare_summary(
             "left",
             &left_records,
             args.expected_status_code,
@@ -58,7 +71,7 @@ pub(crate) async fn run_compare(args: &CompareArgs) -> AppResult<()> {
             left_max,
             args,
         )?;
-        print_compare_summary(
+        let right = print_compare_summary(
             "right",
             &right_records,
             args.expected_status_code,
@@ -66,7 +79,13 @@ pub(crate) async fn run_compare(args: &CompareArgs) -> AppResult<()> {
             right_max,
             args,
         )?;
-        return Ok(());
+        print_compare_deltas(
+            args.left_label.as_deref().unwrap_or("left"),
+            &left,
+            args.right_label.as_deref().unwrap_or("right"),
+            &right,
+        );
+        return report_verdict(regression_failures(args, &left, &right)?);
     }
 
     let stop = Arc::new(AtomicBool::new(false));
@@ -85,7 +104,7 @@ pub(crate) async fn run_compare(args: &CompareArgs) -> AppResult<()> {
         ..UiData::default()
     };
     let (ui_tx, _) = watch::channel(initial_ui);
-    let render_ui_handle = setup_render_ui(&shutdown_tx, &ui_tx);
+    let render_ui_handle = setup_render_ui(&shutdown_tx, &ui_tx, false);
 
     let mut state = PlaybackState::new(start_ms, end_ms);
     let step_ms = resolve_step_ms(args.replay_step, DEFAULT_COMPARE_STEP);
@@ -185,6 +204,41 @@ fn resolve_label(path: &str, override_label: Option<&str>) -> String {
         .unwrap_or_else(|| "compare".to_owned())
 }
 
+/// HdrHistogram logs are recognised by extension, as written by `--export-histogram`.
+fn is_histogram_log(path: &Path) -> bool {
+    path.extension()
+        .and_then(|value| value.to_str())
+        .is_some_and(|value| matches!(value.to_ascii_lowercase().as_str(), "hlog" | "hdr"))
+}
+
+async fn compare_histograms(
+    args: &CompareArgs,
+    left_path: &Path,
+    right_path: &Path,
+) -> AppResult<()> {
+    let left = read_histogram_log(left_path).await?;
+    let right = read_histogram_log(right_path).await?;
+    print_histogram_comparison(
+        args.left_label.as_deref().unwrap_or("left"),
+        &left,
+        args.right_label.as_deref().unwrap_or("right"),
+        &right,
+    );
+    let failures =
+        regression_failures(args, &histogram_metrics(&left), &histogram_metrics(&right))?;
+    report_verdict(failures)
+}
+
+async fn read_histogram_log(path: &Path) -> AppResult<LatencyHistogram> {
+    let bytes = tokio::fs::read(path).await.map_err(|err| {
+        AppError::metrics(MetricsError::Io {
+            context: "read histogram log",
+            source: err,
+        })
+    })?;
+    LatencyHistogram::decode_hdr_log(&bytes)
+}
+
 const fn resolve_playback_action(key_code: KeyCode) -> Option<PlaybackAction> {
     if matches!(key_code, KeyCode::Char(' ')) {
         return Some(PlaybackAction::TogglePlayPause);
@@ -204,5 +258,11 @@ const fn resolve_playback_action(key_code: KeyCode) -> Option<PlaybackAction> {
     if matches!(key_code, KeyCode::Char('r')) {
         return Some(PlaybackAction::Restart);
     }
+    if matches!(key_code, KeyCode::Char('+' | '=')) {
+        return Some(PlaybackAction::SpeedUp);
+    }
+    if matches!(key_code, KeyCode::Char('-' | '_')) {
+        return Some(PlaybackAction::SpeedDown);
+    }
     None
 }
@@ -1,17 +1,28 @@
 use crate::app::replay::summary;
 use crate::app::summary::{PERCENT_DIVISOR, compute_percentiles, compute_summary_stats};
-use crate::args::CompareArgs;
+use crate::args::{CompareArgs, ExpectedStatus};
 use crate::error::AppResult;
-use crate::metrics::MetricRecord;
+use crate::metrics::{LatencyHistogram, MetricRecord};
+
+use super::verdict::{CompareMetrics, arrow, signed_delta};
+
+/// Quantiles shown when comparing two histogram logs.
+const HISTOGRAM_QUANTILES: [(&str, f64); 5] = [
+    ("p50", 0.5),
+    ("p90", 0.9),
+    ("p99", 0.99),
+    ("p99.9", 0.999),
+    ("p99.99", 0.9999),
+];
 
 pub(super) fn print_compare_summary(
     label: &str,
     records: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     start_ms: u64,
     end_ms: u64,
     _args: &CompareArgs,
-) -> AppResult<()> {
+) -> AppResult<CompareMetrics> {
     let summary_output = summary::summarize(records, expected_status_code, start_ms, end_ms)?;
     let stats = compute_summary_stats(&summary_output.summary);
     let (p50, p90, p99) = compute_percentiles(records);
@@ -69,5 +80,64 @@ pub(super) fn print_compare_summary(
         stats.avg_rpm_x100 % PERCENT_DIVISOR
     );
     println!();
-    Ok(())
+    Ok(CompareMetrics {
+        p50,
+        p90,
+        p99,
+        success_rate_x100: Some(stats.success_rate_x100),
+        avg_rps_x100: Some(stats.avg_rps_x100),
+    })
+}
+
+/// Latency percentiles of a histogram log; it has no status or rate data.
+pub(super) fn histogram_metrics(histogram: &LatencyHistogram) -> CompareMetrics {
+    let (p50, p90, p99) = histogram.percentiles();
+    CompareMetrics {
+        p50,
+        p90,
+        p99,
+        success_rate_x100: None,
+        avg_rps_x100: None,
+    }
+}
+
+pub(super) fn print_histogram_comparison(
+    left_label: &str,
+    left: &LatencyHistogram,
+    right_label: &str,
+    right: &LatencyHistogram,
+) {
+    println!(
+        "{:<8} {:>12} {:>12} {:>10}",
+        "", left_label, right_label, "delta"
+    );
+    println!(
+        "{:<8} {:>12} {:>12} {:>10}",
+        "count",
+        left.count(),
+        right.count(),
+        format!(
+            "{} {}",
+            arrow(left.count(), right.count()),
+            signed_delta(left.count(), right.count())
+        )
+    );
+    for (name, quantile) in HISTOGRAM_QUANTILES {
+        print_latency_row(name, left.percentile(quantile), right.percentile(quantile));
+    }
+    print_latency_row("max", left.max(), right.max());
+}
+
+fn print_latency_row(name: &str, left_ms: u64, right_ms: u64) {
+    println!(
+        "{:<8} {:>12} {:>12} {:>10}",
+        name,
+        format!("{left_ms}ms"),
+        format!("{right_ms}ms"),
+        format!(
+            "{} {}ms",
+            arrow(left_ms, right_ms),
+            signed_delta(left_ms, right_ms)
+        )
+    );
 }
@@ -0,0 +1,277 @@
+use crate::app::summary::PERCENT_DIVISOR;
+use crate::args::CompareArgs;
+use crate::error::{AppError, AppResult, ValidationError};
+
+/// One side of a comparison, as used for the delta table and the verdict.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct CompareMetrics {
+    pub(super) p50: u64,
+    pub(super) p90: u64,
+    pub(super) p99: u64,
+    /// `None` for histogram logs, which carry no status data.
+    pub(super) success_rate_x100: Option<u64>,
+    pub(super) avg_rps_x100: Option<u64>,
+}
+
+/// Prints each metric's change from `left` to `right` with a direction arrow.
+pub(super) fn print_compare_deltas(
+    left_label: &str,
+    left: &CompareMetrics,
+    right_label: &str,
+    right: &CompareMetrics,
+) {
+    println!("Change: {left_label} -> {right_label}");
+    for (name, left_ms, right_ms) in [
+        ("P50 Latency", left.p50, right.p50),
+        ("P90 Latency", left.p90, right.p90),
+        ("P99 Latency", left.p99, right.p99),
+    ] {
+        println!("{}", latency_delta_line(name, left_ms, right_ms));
+    }
+    if let (Some(left_rate), Some(right_rate)) = (left.success_rate_x100, right.success_rate_x100) {
+        println!(
+            "Success Rate: {}% -> {}% {} {}pp",
+            format_x100(left_rate),
+            format_x100(right_rate),
+            arrow(left_rate, right_rate),
+            signed_x100(left_rate, right_rate)
+        );
+    }
+    if let (Some(left_rps), Some(right_rps)) = (left.avg_rps_x100, right.avg_rps_x100) {
+        println!(
+            "Avg RPS: {} -> {} {} {}",
+            format_x100(left_rps),
+            format_x100(right_rps),
+            arrow(left_rps, right_rps),
+            signed_x100(left_rps, right_rps)
+        );
+    }
+    println!();
+}
+
+fn latency_delta_line(name: &str, left_ms: u64, right_ms: u64) -> String {
+    let line = format!(
+        "{name}: {left_ms}ms -> {right_ms}ms {} {}ms",
+        arrow(left_ms, right_ms),
+        signed_delta(left_ms, right_ms)
+    );
+    if left_ms == 0 {
+        return line;
+    }
+    let sign = if right_ms >= left_ms { '+' } else { '-' };
+    format!(
+        "{line} ({sign}{}%)",
+        format_x100(change_x100(left_ms, right_ms))
+    )
+}
+
+/// Checks `--regress-p99`/`--regress-success`; `None` when neither is set.
+///
+/// # Errors
+///
+/// Returns an error when `--regress-success` is set but a side has no
+/// success rate.
+pub(super) fn regression_failures(
+    args: &CompareArgs,
+    left: &CompareMetrics,
+    right: &CompareMetrics,
+) -> AppResult<Option<Vec<String>>> {
+    if args.regress_p99.is_none() && args.regress_success.is_none() {
+        return Ok(None);
+    }
+    let mut failures = Vec::new();
+    if let Some(limit) = args.regress_p99
+        && right.p99 > left.p99
+    {
+        let increase = change_x100(left.p99, right.p99);
+        if increase > limit.x100() {
+            failures.push(format!(
+                "p99 latency regressed {}ms -> {}ms (+{}%, limit {}%)",
+                left.p99,
+                right.p99,
+                format_x100(increase),
+                format_x100(limit.x100())
+            ));
+        }
+    }
+    if let Some(limit) = args.regress_success {
+        let (Some(left_rate), Some(right_rate)) = (left.success_rate_x100, right.success_rate_x100)
+        else {
+            return Err(AppError::validation(
+                ValidationError::RegressSuccessNeedsSnapshots,
+            ));
+        };
+        let drop = left_rate.saturating_sub(right_rate);
+        if drop > limit.x100() {
+            failures.push(format!(
+                "success rate dropped {}% -> {}% (-{}pp, limit {}pp)",
+                format_x100(left_rate),
+                format_x100(right_rate),
+                format_x100(drop),
+                format_x100(limit.x100())
+            ));
+        }
+    }
+    Ok(Some(failures))
+}
+
+/// Prints the verdict and turns failures into a `CompareRegressed` error (exit code 2).
+///
+/// # Errors
+///
+/// Returns an error when any regression threshold was exceeded.
+pub(super) fn report_verdict(failures: Option<Vec<String>>) -> AppResult<()> {
+    let Some(failures) = failures else {
+        return Ok(());
+    };
+    if failures.is_empty() {
+        println!("Verdict: PASS");
+        return Ok(());
+    }
+    println!("Verdict: FAIL");
+    for failure in &failures {
+        println!("  - {failure}");
+    }
+    Err(AppError::validation(ValidationError::CompareRegressed {
+        failures: failures.join("; "),
+    }))
+}
+
+/// Size of the change from `left` to `right` relative to `left`, in
+/// hundredths of a percent; saturates when `left` is 0 and `right` is not.
+fn change_x100(left: u64, right: u64) -> u64 {
+    let delta = right.abs_diff(left);
+    if delta == 0 {
+        return 0;
+    }
+    delta
+        .saturating_mul(PERCENT_DIVISOR.saturating_mul(PERCENT_DIVISOR))
+        .checked_div(left)
+        .unwrap_or(u64::MAX)
+}
+
+pub(super) const fn arrow(left: u64, right: u64) -> &'static str {
+    if right > left {
+        "↑"
+    } else if right < left {
+        "↓"
+    } else {
+        "="
+    }
+}
+
+pub(super) fn signed_delta(left: u64, right: u64) -> String {
+    if right >= left {
+        format!("+{}", right.saturating_sub(left))
+    } else {
+        format!("-{}", left.saturating_sub(right))
+    }
+}
+
+fn signed_x100(left: u64, right: u64) -> String {
+    let sign = if right >= left { '+' } else { '-' };
+    format!("{sign}{}", format_x100(right.abs_diff(left)))
+}
+
+fn format_x100(value: u64) -> String {
+    format!("{}.{:02}", value / PERCENT_DIVISOR, value % PERCENT_DIVISOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::args::{Command, TesterArgs};
+
+    fn compare_args(extra: &[&str]) -> AppResult<CompareArgs> {
+        let mut argv = vec!["strest", "compare", "base.json", "new.json"];
+        argv.extend_from_slice(extra);
+        match TesterArgs::try_parse_from(argv)?.command {
+            Some(Command::Compare(args)) => Ok(args),
+            _ => Err(AppError::validation("Expected the compare command")),
+        }
+    }
+
+    fn metrics(p99: u64, success_rate_x100: Option<u64>) -> CompareMetrics {
+        CompareMetrics {
+            p99,
+            success_rate_x100,
+            ..CompareMetrics::default()
+        }
+    }
+
+    #[test]
+    fn regression_thresholds_flag_p99_increase_and_success_drop() -> AppResult<()> {
+        let args = compare_args(&["--regress-p99", "10%", "--regress-success", "1"])?;
+        let base = metrics(100, Some(9_950));
+
+        let within = regression_failures(&args, &base, &metrics(110, Some(9_850)))?;
+        if within != Some(Vec::new()) {
+            return Err(AppError::validation(format!(
+                "Expected a pass at the limits, got {:?}",
+                within
+            )));
+        }
+
+        let failures = regression_failures(&args, &base, &metrics(111, Some(9_849)))?;
+        let expected = vec![
+            "p99 latency regressed 100ms -> 111ms (+11.00%, limit 10.00%)".to_owned(),
+            "success rate dropped 99.50% -> 98.49% (-1.01pp, limit 1.00pp)".to_owned(),
+        ];
+        if failures != Some(expected) {
+            return Err(AppError::validation(format!(
+                "Unexpected failures: {:?}",
+                failures
+            )));
+        }
+        match report_verdict(failures) {
+            Err(AppError::Validation(ValidationError::CompareRegressed { .. })) => {}
+            other => {
+                return Err(AppError::validation(format!(
+                    "Expected CompareRegressed, got {:?}",
+                    other
+                )));
+            }
+        }
+
+        let histograms = regression_failures(&args, &metrics(100, None), &metrics(90, None));
+        if !matches!(
+            histograms,
+            Err(AppError::Validation(
+                ValidationError::RegressSuccessNeedsSnapshots
+            ))
+        ) {
+            return Err(AppError::validation(
+                "Expected --regress-success to need snapshots",
+            ));
+        }
+        if regression_failures(&compare_args(&[])?, &base, &metrics(500, Some(0)))?.is_some() {
+            return Err(AppError::validation(
+                "Expected no verdict without thresholds",
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn latency_delta_line_shows_direction_and_relative_change() -> AppResult<()> {
+        let lines = [
+            latency_delta_line("P99 Latency", 120, 150),
+            latency_delta_line("P50 Latency", 40, 30),
+            latency_delta_line("P90 Latency", 0, 0),
+        ];
+        let expected = [
+            "P99 Latency: 120ms -> 150ms ↑ +30ms (+25.00%)",
+            "P50 Latency: 40ms -> 30ms ↓ -10ms (-25.00%)",
+            "P90 Latency: 0ms -> 0ms = +0ms",
+        ];
+        if lines != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected delta lines: {:?}",
+                lines
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+
+use crate::app::summary::SseReport;
 use crate::metrics;
 use tokio::io::{AsyncWriteExt, BufWriter};
 
@@ -44,18 +47,19 @@ pub(crate) async fn export_csv(
     let file = tokio::fs::File::create(path).await?;
     let mut writer = BufWriter::new(file);
     writer
-        .write_all(b"elapsed_ms,latency_ms,status_code,timed_out,transport_error,response_bytes,in_flight_ops\n")
+        .write_all(b"elapsed_ms,latency_ms,status_code,timed_out,transport_error,response_bytes,in_flight_ops,check_failed\n")
         .await?;
     for record in records {
         let line = format!(
-            "{},{},{},{},{},{},{}\n",
+            "{},{},{},{},{},{},{},{}\n",
             record.elapsed_ms,
             record.latency_ms,
             record.status_code,
             u8::from(record.timed_out),
             u8::from(record.transport_error),
             record.response_bytes,
-            record.in_flight_ops
+            record.in_flight_ops,
+            u8::from(record.check_failed)
         );
         writer.write_all(line.as_bytes()).await?;
     }
@@ -63,10 +67,30 @@ pub(crate) async fn export_csv(
     Ok(())
 }
 
+/// Histogram-derived latency percentiles written into the JSON summary.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExportPercentiles {
+    pub(crate) p50: u64,
+    pub(crate) p90: u64,
+    pub(crate) p99: u64,
+    pub(crate) p999: u64,
+    pub(crate) p9999: u64,
+}
+
+/// Optional sections added to the JSON and JSONL summaries when present.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExportExtras<'run> {
+    pub(crate) http_versions: Option<&'run BTreeMap<&'static str, u64>>,
+    pub(crate) tags: &'run BTreeMap<String, String>,
+    pub(crate) sse: Option<SseReport>,
+}
+
 pub(crate) async fn export_json(
     path: &str,
     summary: &metrics::MetricsSummary,
+    percentiles: ExportPercentiles,
     records: &[metrics::MetricRecord],
+    extras: ExportExtras<'_>,
 ) -> Result<(), std::io::Error> {
     let records_json: Vec<serde_json::Value> = records
         .iter()
@@ -78,14 +102,15 @@ pub(crate) async fn export_json(
                 "timed_out": record.timed_out,
                 "transport_error": record.transport_error,
                 "response_bytes": record.response_bytes,
-                "in_flight_ops": record.in_flight_ops
+                "in_flight_ops": record.in_flight_ops,
+                "check_failed": record.check_failed
             })
         })
         .collect();
 
     let (total_response_bytes, avg_response_bytes_per_sec, max_in_flight_ops, last_in_flight_ops) =
         flow_summary(records, summary.duration);
-    let summary_json = serde_json::json!({
+    let mut summary_json = serde_json::json!({
         "duration_ms": summary.duration.as_millis(),
         "total_requests": summary.total_requests,
         "successful_requests": summary.successful_requests,
@@ -99,11 +124,21 @@ pub(crate) async fn export_json(
         "min_latency_ms": summary.min_latency_ms,
         "max_latency_ms": summary.max_latency_ms,
         "avg_latency_ms": summary.avg_latency_ms,
+        "p50_latency_ms": percentiles.p50,
+        "p90_latency_ms": percentiles.p90,
+        "p99_latency_ms": percentiles.p99,
+        "p999_latency_ms": percentiles.p999,
+        "p9999_latency_ms": percentiles.p9999,
         "total_response_bytes": total_response_bytes,
         "avg_response_bytes_per_sec": avg_response_bytes_per_sec,
         "max_in_flight_ops": max_in_flight_ops,
         "last_in_flight_ops": last_in_flight_ops
     });
+    insert_http_versions(&mut summary_json, extras.http_versions);
+    insert_error_reasons(&mut summary_json, &summary.error_reasons);
+    insert_check_failures(&mut summary_json, summary.check_failures);
+    insert_sse(&mut summary_json, extras.sse);
+    insert_tags(&mut summary_json, extras.tags);
 
     let payload = serde_json::json!({
         "summary": summary_json,
@@ -122,13 +157,14 @@ pub(crate) async fn export_jsonl(
     path: &str,
     summary: &metrics::MetricsSummary,
     records: &[metrics::MetricRecord],
+    extras: ExportExtras<'_>,
 ) -> Result<(), std::io::Error> {
     let file = tokio::fs::File::create(path).await?;
     let mut writer = BufWriter::new(file);
 
     let (total_response_bytes, avg_response_bytes_per_sec, max_in_flight_ops, last_in_flight_ops) =
         flow_summary(records, summary.duration);
-    let summary_json = serde_json::json!({
+    let mut summary_json = serde_json::json!({
         "type": "summary",
         "duration_ms": summary.duration.as_millis(),
         "total_requests": summary.total_requests,
@@ -148,6 +184,11 @@ pub(crate) async fn export_jsonl(
         "max_in_flight_ops": max_in_flight_ops,
         "last_in_flight_ops": last_in_flight_ops
     });
+    insert_http_versions(&mut summary_json, extras.http_versions);
+    insert_error_reasons(&mut summary_json, &summary.error_reasons);
+    insert_check_failures(&mut summary_json, summary.check_failures);
+    insert_sse(&mut summary_json, extras.sse);
+    insert_tags(&mut summary_json, extras.tags);
     let summary_line = serde_json::to_vec(&summary_json).map_err(std::io::Error::other)?;
     writer.write_all(&summary_line).await?;
     writer.write_all(b"\n").await?;
@@ -161,7 +202,8 @@ pub(crate) async fn export_jsonl(
             "timed_out": record.timed_out,
             "transport_error": record.transport_error,
             "response_bytes": record.response_bytes,
-            "in_flight_ops": record.in_flight_ops
+            "in_flight_ops": record.in_flight_ops,
+            "check_failed": record.check_failed
         });
         let line_bytes = serde_json::to_vec(&line).map_err(std::io::Error::other)?;
         writer.write_all(&line_bytes).await?;
@@ -171,3 +213,118 @@ pub(crate) async fn export_jsonl(
     writer.flush().await?;
     Ok(())
 }
+
+/// Percentile bucket size for the TSV series, so each row covers one second.
+pub(crate) const TSV_BUCKET_MS: u64 = 1000;
+
+/// Writes the per-second series (elapsed, rps, p50, p90, p99, errors, bytes)
+/// as tab-separated rows for spreadsheet import. `data` must be built with
+/// [`TSV_BUCKET_MS`] buckets.
+pub(crate) async fn export_tsv(
+    path: &str,
+    data: &metrics::StreamingChartData,
+) -> Result<(), std::io::Error> {
+    let percentiles: BTreeMap<u64, (u64, u64, u64)> = data
+        .latency_buckets_ms
+        .iter()
+        .zip(data.p50.iter().zip(data.p90.iter().zip(data.p99.iter())))
+        .map(|(start_ms, (p50, (p90, p99)))| (start_ms / TSV_BUCKET_MS, (*p50, *p90, *p99)))
+        .collect();
+    let slot = |series: &[u32], sec: usize| u64::from(series.get(sec).copied().unwrap_or(0));
+
+    let file = tokio::fs::File::create(path).await?;
+    let mut writer = BufWriter::new(file);
+    writer
+        .write_all(b"elapsed_s\trps\tp50_ms\tp90_ms\tp99_ms\terrors\tbytes\n")
+        .await?;
+    for (sec, rps) in data.rps_counts.iter().enumerate() {
+        let elapsed_s = u64::try_from(sec).unwrap_or(u64::MAX);
+        let (p50, p90, p99) = percentiles.get(&elapsed_s).copied().unwrap_or((0, 0, 0));
+        let errors = slot(&data.timeouts, sec)
+            .saturating_add(slot(&data.transports, sec))
+            .saturating_add(slot(&data.non_expected, sec));
+        let bytes = data.response_bytes.get(sec).copied().unwrap_or(0);
+        let line = format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            elapsed_s, rps, p50, p90, p99, errors, bytes
+        );
+        writer.write_all(line.as_bytes()).await?;
+    }
+    writer.flush().await?;
+    Ok(())
+}
+
+pub(crate) async fn export_histogram(
+    path: &str,
+    histogram: &metrics::LatencyHistogram,
+    duration: std::time::Duration,
+) -> Result<(), std::io::Error> {
+    let start = std::time::SystemTime::now()
+        .checked_sub(duration)
+        .unwrap_or(std::time::UNIX_EPOCH);
+    let log = histogram
+        .encode_hdr_log(start, duration)
+        .map_err(std::io::Error::other)?;
+    tokio::fs::write(path, log).await
+}
+
+/// Adds the run's `--tag` labels as a `tags` object when any are set.
+fn insert_tags(summary_json: &mut serde_json::Value, tags: &BTreeMap<String, String>) {
+    if !tags.is_empty()
+        && let Some(object) = summary_json.as_object_mut()
+    {
+        object.insert("tags".to_owned(), serde_json::json!(tags));
+    }
+}
+
+/// Adds failed requests per error reason as an `error_reasons` object when
+/// any failure was classified.
+fn insert_error_reasons(
+    summary_json: &mut serde_json::Value,
+    error_reasons: &BTreeMap<&'static str, u64>,
+) {
+    if !error_reasons.is_empty()
+        && let Some(object) = summary_json.as_object_mut()
+    {
+        object.insert("error_reasons".to_owned(), serde_json::json!(error_reasons));
+    }
+}
+
+/// Adds one top-level counter per failed response check, e.g.
+/// `request_id_mismatches`, when it is non-zero.
+fn insert_check_failures(summary_json: &mut serde_json::Value, failures: metrics::CheckFailures) {
+    if let Some(object) = summary_json.as_object_mut() {
+        for (key, _, count) in failures.counts() {
+            object.insert(key.to_owned(), serde_json::json!(count));
+        }
+    }
+}
+
+/// Adds SSE event counts and average timing as an `sse` object for
+/// `--protocol sse` runs.
+fn insert_sse(summary_json: &mut serde_json::Value, sse: Option<SseReport>) {
+    if let Some(report) = sse
+        && let Some(object) = summary_json.as_object_mut()
+    {
+        object.insert(
+            "sse".to_owned(),
+            serde_json::json!({
+                "events": report.events,
+                "connections": report.connections,
+                "avg_first_event_us": report.avg_first_event_us,
+                "avg_inter_event_us": report.avg_inter_event_us
+            }),
+        );
+    }
+}
+
+fn insert_http_versions(
+    summary_json: &mut serde_json::Value,
+    http_versions: Option<&BTreeMap<&'static str, u64>>,
+) {
+    if let Some(versions) = http_versions
+        && let Some(object) = summary_json.as_object_mut()
+    {
+        object.insert("http_versions".to_owned(), serde_json::json!(versions));
+    }
+}
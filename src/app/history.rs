@@ -0,0 +1,347 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use crate::args::TesterArgs;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+const PERCENT_SCALE: i128 = 10_000;
+/// Oldest entries are dropped once the history file holds this many runs.
+const MAX_HISTORY_ENTRIES: usize = 1_000;
+
+/// One line of the run history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct HistoryEntry {
+    pub(crate) key: String,
+    pub(crate) timestamp_ms: u64,
+    pub(crate) target: String,
+    pub(crate) total_requests: u64,
+    pub(crate) error_requests: u64,
+    pub(crate) avg_rps_x100: u64,
+    pub(crate) success_rate_x100: u64,
+    pub(crate) p50_ms: u64,
+    pub(crate) p99_ms: u64,
+}
+
+/// The URL or scenario base URL that identifies the run in the history.
+pub(crate) fn run_target(args: &TesterArgs) -> String {
+    args.url
+        .clone()
+        .or_else(|| {
+            args.scenario
+                .as_ref()
+                .and_then(|scenario| scenario.base_url.clone())
+        })
+        .unwrap_or_default()
+}
+
+/// Groups runs that send the same traffic under the same load.
+///
+/// Hashes the parsed request config (target, protocol options, headers,
+/// body and data sources, scenario, expected status, timeouts, HTTP
+/// settings) and the load shape (mode, tasks, rate, schedule, duration).
+/// Credentials are left out so rotating a token keeps the series. Uses
+/// FNV-1a rather than `DefaultHasher` so keys stay stable across builds.
+pub(crate) fn run_key(args: &TesterArgs) -> String {
+    let fields = [
+        format!("{:?}", args.protocol),
+        format!("{:?}", args.method),
+        run_target(args),
+        format!("{:?}", args.scenario),
+        format!("{:?} {:?}", args.urls_from_file, args.rand_regex_url),
+        format!(
+            "{:?} {:?} {:?} {}",
+            args.headers, args.accept_header, args.content_type, args.no_ua
+        ),
+        format!("{:?} {:?} {:?}", args.data, args.form, args.form_file),
+        format!(
+            "{:?} {:?} {:?} {:?}",
+            args.json_template, args.data_file, args.data_lines, args.data_dir
+        ),
+        format!(
+            "{:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+            args.grpc_mode,
+            args.ws_messages_per_conn,
+            args.ws_message_interval_ms,
+            args.mqtt_mode,
+            args.mqtt_topic,
+            args.sse_events_per_conn,
+            args.sse_conn_duration
+        ),
+        format!(
+            "{:?} {:?} {}",
+            args.expected_status_code, args.request_timeout, args.redirect_limit
+        ),
+        format!(
+            "{:?} {} {} {:?} {}",
+            args.http_version,
+            args.http2,
+            args.disable_keepalive,
+            args.requests_per_connection,
+            args.disable_compression
+        ),
+        format!("{:?}", args.load_mode),
+        args.max_tasks.get().to_string(),
+        args.rate_limit
+            .map_or_else(String::new, |rate| rate.get().to_string()),
+        format!(
+            "{:?} {:?} {:?}",
+            args.rate_schedule, args.load_profile, args.arrival_distribution
+        ),
+        format!(
+            "{} {:?}",
+            args.target_duration.get(),
+            args.requests.map(|requests| requests.get())
+        ),
+    ];
+    let hash = fields
+        .join("\n")
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+    format!("{:016x}", hash)
+}
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| {
+            u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+        })
+}
+
+/// Appends `entry` to the history file and returns the previous run with the same key.
+///
+/// Lines that do not parse are skipped, so a damaged file does not block new runs.
+/// Once the file holds `MAX_HISTORY_ENTRIES` lines it is rewritten without the oldest ones.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, created, or written.
+pub(crate) async fn record(
+    path: &Path,
+    entry: &HistoryEntry,
+) -> Result<Option<HistoryEntry>, std::io::Error> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let previous = contents
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+        .find(|previous| previous.key == entry.key);
+    let lines: Vec<&str> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    line.push('\n');
+
+    if lines.len() >= MAX_HISTORY_ENTRIES {
+        let dropped = lines
+            .len()
+            .saturating_sub(MAX_HISTORY_ENTRIES.saturating_sub(1));
+        let mut rewritten = String::with_capacity(contents.len());
+        for kept in lines.iter().skip(dropped) {
+            rewritten.push_str(kept);
+            rewritten.push('\n');
+        }
+        rewritten.push_str(&line);
+        // Write a sibling file and rename it so a crash never leaves a half-written history.
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        tokio::fs::write(&tmp_path, rewritten).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        return Ok(previous);
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(line.as_bytes()).await?;
+    Ok(previous)
+}
+
+/// One-line comparison of this run against the previous matching run.
+pub(crate) fn delta_line(previous: &HistoryEntry, current: &HistoryEntry) -> String {
+    let rps_change = percent_change_x100(previous.avg_rps_x100, current.avg_rps_x100).map_or_else(
+        || "n/a".to_owned(),
+        |change| format_signed_x100(change, "%"),
+    );
+    let p99_change = i128::from(current.p99_ms).saturating_sub(i128::from(previous.p99_ms));
+    let error_change =
+        i128::from(current.error_requests).saturating_sub(i128::from(previous.error_requests));
+    format!(
+        "vs previous run ({} ago): rps {}.{:02} ({}), p99 {}ms ({:+}ms), errors {} ({:+})",
+        format_age(current.timestamp_ms.saturating_sub(previous.timestamp_ms)),
+        current.avg_rps_x100 / 100,
+        current.avg_rps_x100 % 100,
+        rps_change,
+        current.p99_ms,
+        p99_change,
+        current.error_requests,
+        error_change
+    )
+}
+
+fn percent_change_x100(previous: u64, current: u64) -> Option<i128> {
+    if previous == 0 {
+        return None;
+    }
+    i128::from(current)
+        .saturating_sub(i128::from(previous))
+        .saturating_mul(PERCENT_SCALE)
+        .checked_div(i128::from(previous))
+}
+
+fn format_signed_x100(value: i128, unit: &str) -> String {
+    let sign = if value < 0 { '-' } else { '+' };
+    let magnitude = value.unsigned_abs();
+    format!("{}{}.{:02}{}", sign, magnitude / 100, magnitude % 100, unit)
+}
+
+fn format_age(elapsed_ms: u64) -> String {
+    let secs = elapsed_ms / 1_000;
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3_600 => format!("{}m", secs / 60),
+        3_600..86_400 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::parse_test_args;
+    use crate::error::{AppError, AppResult};
+
+    fn entry(key: &str, timestamp_ms: u64, avg_rps_x100: u64, p99_ms: u64) -> HistoryEntry {
+        HistoryEntry {
+            key: key.to_owned(),
+            timestamp_ms,
+            target: "http://localhost".to_owned(),
+            total_requests: 1_000,
+            error_requests: 2,
+            avg_rps_x100,
+            success_rate_x100: 9_980,
+            p50_ms: 10,
+            p99_ms,
+        }
+    }
+
+    #[tokio::test]
+    async fn history_returns_previous_run_with_same_key() -> AppResult<()> {
+        let args = parse_test_args(["strest", "--url", "http://localhost", "--rate", "100"])?;
+        let other = parse_test_args(["strest", "--url", "http://localhost", "--rate", "200"])?;
+        let key = run_key(&args);
+        if key == run_key(&other) {
+            return Err(AppError::validation("Expected the rate to change the key"));
+        }
+        let with_header = parse_test_args([
+            "strest",
+            "--url",
+            "http://localhost",
+            "--rate",
+            "100",
+            "-H",
+            "X-Variant: b",
+        ])?;
+        let longer = parse_test_args([
+            "strest",
+            "--url",
+            "http://localhost",
+            "--rate",
+            "100",
+            "-t",
+            "120",
+        ])?;
+        if key == run_key(&with_header)
+            || key == run_key(&longer)
+            || key
+                != run_key(&parse_test_args([
+                    "strest",
+                    "--url",
+                    "http://localhost",
+                    "--rate",
+                    "100",
+                ])?)
+        {
+            return Err(AppError::validation(
+                "Expected headers and duration to change the key, and nothing else",
+            ));
+        }
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("nested").join("history.jsonl");
+        let first = record(&path, &entry(&key, 0, 100_000, 50)).await?;
+        record(&path, &entry(&run_key(&other), 1_000, 1, 1)).await?;
+        let current = entry(&key, 300_000, 103_250, 45);
+        let previous = record(&path, &current).await?;
+
+        let line = previous
+            .as_ref()
+            .map(|previous| delta_line(previous, &current));
+        if first.is_some()
+            || line.as_deref()
+                != Some(
+                    "vs previous run (5m ago): rps 1032.50 (+3.25%), p99 45ms (-5ms), errors 2 (+0)",
+                )
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected history delta: {:?}",
+                line
+            )));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn history_drops_oldest_entries_past_the_cap() -> AppResult<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("history.jsonl");
+        let mut contents = String::new();
+        for timestamp_ms in (0_u64..).take(MAX_HISTORY_ENTRIES) {
+            let line = serde_json::to_string(&entry("old", timestamp_ms, 1, 1))?;
+            contents.push_str(&line);
+            contents.push('\n');
+        }
+        tokio::fs::write(&path, contents).await?;
+
+        let previous = record(&path, &entry("old", 5_000_000, 1, 1)).await?;
+        let stored = tokio::fs::read_to_string(&path).await?;
+        let entries = stored
+            .lines()
+            .map(serde_json::from_str::<HistoryEntry>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let first = entries.first().map(|entry| entry.timestamp_ms);
+        let last = entries.last().map(|entry| entry.timestamp_ms);
+        if entries.len() != MAX_HISTORY_ENTRIES
+            || first != Some(1)
+            || last != Some(5_000_000)
+            || previous.map(|entry| entry.timestamp_ms) != Some(999)
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected capped history: {} entries, first {:?}, last {:?}",
+                entries.len(),
+                first,
+                last
+            )));
+        }
+        Ok(())
+    }
+}
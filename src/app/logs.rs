@@ -9,9 +9,11 @@ use std::sync::Arc;
 
 use tokio::time::Instant;
 
-use crate::args::TesterArgs;
+use crate::app::summary;
+use crate::args::{ExpectedStatus, TesterArgs};
 use crate::error::AppResult;
 use crate::metrics;
+use crate::sinks::checkpoint::ResumeState;
 
 pub(crate) struct LogSetup {
     pub(crate) log_sink: Option<Arc<metrics::LogSink>>,
@@ -45,9 +47,26 @@ pub(crate) fn merge_log_results(
     merge::merge_log_results(results, metrics_max)
 }
 
+pub(crate) fn merge_resume_state(
+    merged: LogMergeResult,
+    resume: &ResumeState,
+) -> AppResult<LogMergeResult> {
+    merge::merge_resume_state(merged, resume)
+}
+
+pub(crate) fn merge_response_totals(results: &[metrics::LogResult]) -> summary::ResponseTotals {
+    merge::merge_response_totals(results)
+}
+
+pub(crate) fn merge_status_latency(
+    results: &[metrics::LogResult],
+) -> AppResult<Vec<summary::StatusLatency>> {
+    merge::merge_status_latency(results)
+}
+
 pub(crate) async fn load_chart_data_streaming(
     paths: &[PathBuf],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     metrics_range: &Option<metrics::MetricsRange>,
     latency_bucket_ms: u64,
 ) -> AppResult<metrics::StreamingChartData> {
@@ -60,6 +79,14 @@ pub(crate) async fn load_chart_data_streaming(
     .await
 }
 
+pub(crate) fn chart_data_from_records(
+    records: &[metrics::MetricRecord],
+    expected_status_code: ExpectedStatus,
+    latency_bucket_ms: u64,
+) -> AppResult<metrics::StreamingChartData> {
+    streaming::chart_data_from_records(records, expected_status_code, latency_bucket_ms)
+}
+
 pub(crate) async fn load_log_records(
     paths: &[PathBuf],
     metrics_range: &Option<metrics::MetricsRange>,
@@ -1,7 +1,11 @@
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
 use std::time::Duration;
 
+use crate::app::summary::{ResponseTotals, StatusLatency};
 use crate::error::AppResult;
 use crate::metrics;
+use crate::sinks::checkpoint::ResumeState;
 
 use super::LogMergeResult;
 
@@ -25,6 +29,9 @@ pub(super) fn merge_log_results(
     let mut metrics_truncated = false;
     let mut histogram = metrics::LatencyHistogram::new()?;
     let mut success_histogram = metrics::LatencyHistogram::new()?;
+    let mut http_versions: BTreeMap<&'static str, u64> = BTreeMap::new();
+    let mut error_reasons: BTreeMap<&'static str, u64> = BTreeMap::new();
+    let mut check_failures = metrics::CheckFailures::default();
 
     for result in results {
         total_requests = total_requests.saturating_add(result.summary.total_requests);
@@ -52,6 +59,15 @@ pub(super) fn merge_log_results(
         records.extend(result.records);
         histogram.merge(&result.histogram)?;
         success_histogram.merge(&result.success_histogram)?;
+        for (version, count) in result.summary.http_versions {
+            let total = http_versions.entry(version).or_insert(0);
+            *total = total.saturating_add(count);
+        }
+        for (reason, count) in result.summary.error_reasons {
+            let total = error_reasons.entry(reason).or_insert(0);
+            *total = total.saturating_add(count);
+        }
+        check_failures.merge(result.summary.check_failures);
     }
 
     if metrics_max > 0 && records.len() > metrics_max {
@@ -109,6 +125,105 @@ pub(super) fn merge_log_results(
             success_min_latency_ms,
             success_max_latency_ms,
             success_avg_latency_ms,
+            http_versions,
+            error_reasons,
+            check_failures,
+        },
+        records,
+        metrics_truncated,
+        histogram,
+        latency_sum_ms,
+        success_latency_sum_ms,
+        success_histogram,
+    ))
+}
+
+/// Adds the totals of a resumed checkpoint to this run's merged logs.
+pub(super) fn merge_resume_state(
+    merged: LogMergeResult,
+    resume: &ResumeState,
+) -> AppResult<LogMergeResult> {
+    let (
+        summary,
+        records,
+        metrics_truncated,
+        mut histogram,
+        latency_sum_ms,
+        success_latency_sum_ms,
+        mut success_histogram,
+    ) = merged;
+    let (prior_histogram, prior_success_histogram) = resume.histograms()?;
+    histogram.merge(&prior_histogram)?;
+    success_histogram.merge(&prior_success_histogram)?;
+
+    let total_requests = summary.total_requests.saturating_add(resume.total_requests);
+    let successful_requests = summary
+        .successful_requests
+        .saturating_add(resume.successful_requests);
+    let latency_sum_ms = latency_sum_ms.saturating_add(resume.latency_sum_ms);
+    let success_latency_sum_ms =
+        success_latency_sum_ms.saturating_add(resume.success_latency_sum_ms);
+    let average = |sum: u128, count: u64| {
+        sum.checked_div(u128::from(count))
+            .map_or(0, |avg| u64::try_from(avg).unwrap_or(u64::MAX))
+    };
+    let combine_min = |current: u64, current_count: u64, prior: u64, prior_count: u64| match (
+        current_count > 0,
+        prior_count > 0,
+    ) {
+        (true, true) => current.min(prior),
+        (false, true) => prior,
+        _ => current,
+    };
+    let mut http_versions = summary.http_versions;
+    for (version, count) in resume.http_versions() {
+        let total = http_versions.entry(version).or_insert(0);
+        *total = total.saturating_add(count);
+    }
+    let mut error_reasons = summary.error_reasons;
+    for (reason, count) in resume.error_reasons() {
+        let total = error_reasons.entry(reason).or_insert(0);
+        *total = total.saturating_add(count);
+    }
+    let mut check_failures = summary.check_failures;
+    check_failures.merge(resume.check_failures);
+
+    Ok((
+        metrics::MetricsSummary {
+            duration: summary.duration.saturating_add(resume.duration()),
+            total_requests,
+            successful_requests,
+            error_requests: total_requests.saturating_sub(successful_requests),
+            timeout_requests: summary
+                .timeout_requests
+                .saturating_add(resume.timeout_requests),
+            transport_errors: summary
+                .transport_errors
+                .saturating_add(resume.transport_errors),
+            non_expected_status: summary
+                .non_expected_status
+                .saturating_add(resume.non_expected_status),
+            min_latency_ms: combine_min(
+                summary.min_latency_ms,
+                summary.total_requests,
+                resume.min_latency_ms,
+                resume.total_requests,
+            ),
+            max_latency_ms: summary.max_latency_ms.max(resume.max_latency_ms),
+            avg_latency_ms: average(latency_sum_ms, total_requests),
+            success_min_latency_ms: combine_min(
+                summary.success_min_latency_ms,
+                summary.successful_requests,
+                resume.success_min_latency_ms,
+                resume.successful_requests,
+            ),
+            success_max_latency_ms: summary
+                .success_max_latency_ms
+                .max(resume.success_max_latency_ms),
+            success_avg_latency_ms: average(success_latency_sum_ms, successful_requests),
+            http_versions,
+            error_reasons,
+            check_failures,
         },
         records,
         metrics_truncated,
@@ -118,3 +233,42 @@ pub(super) fn merge_log_results(
         success_histogram,
     ))
 }
+
+pub(super) fn merge_response_totals(results: &[metrics::LogResult]) -> ResponseTotals {
+    let mut totals = ResponseTotals::default();
+    for result in results {
+        totals.bytes = totals.bytes.saturating_add(result.response_bytes);
+        for (status, count) in &result.status_codes {
+            let total = totals.status_codes.entry(*status).or_insert(0);
+            *total = total.saturating_add(*count);
+        }
+    }
+    totals
+}
+
+pub(super) fn merge_status_latency(
+    results: &[metrics::LogResult],
+) -> AppResult<Vec<StatusLatency>> {
+    let mut merged: BTreeMap<metrics::StatusClass, metrics::LatencyHistogram> = BTreeMap::new();
+    for (class, histogram) in results.iter().flat_map(|result| &result.status_histograms) {
+        match merged.entry(*class) {
+            Entry::Occupied(mut entry) => entry.get_mut().merge(histogram)?,
+            Entry::Vacant(entry) => entry
+                .insert(metrics::LatencyHistogram::new()?)
+                .merge(histogram)?,
+        }
+    }
+    Ok(merged
+        .iter()
+        .map(|(class, histogram)| {
+            let (p50, p90, p99) = histogram.percentiles();
+            StatusLatency {
+                class: *class,
+                count: histogram.count(),
+                p50,
+                p90,
+                p99,
+            }
+        })
+        .collect())
+}
@@ -14,6 +14,9 @@ pub(super) struct LogRecord {
     pub(super) status_code: u16,
     pub(super) timed_out: bool,
     pub(super) transport_error: bool,
+    pub(super) response_bytes: u64,
+    pub(super) in_flight_ops: u64,
+    pub(super) check_failed: bool,
 }
 
 pub(super) fn parse_log_line(line: &str) -> Option<LogRecord> {
@@ -33,12 +36,27 @@ pub(super) fn parse_log_line(line: &str) -> Option<LogRecord> {
         .next()
         .and_then(|value| value.parse::<u8>().ok())
         .is_some_and(|value| value != 0);
+    let response_bytes = parts
+        .next()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(0);
+    let in_flight_ops = parts
+        .next()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(0);
+    let check_failed = parts
+        .next()
+        .and_then(|value| value.parse::<u8>().ok())
+        .is_some_and(|value| value != 0);
     Some(LogRecord {
         elapsed_ms,
         latency_ms,
         status_code,
         timed_out,
         transport_error,
+        response_bytes,
+        in_flight_ops,
+        check_failed,
     })
 }
 
@@ -68,12 +68,15 @@ pub(super) async fn load_log_records(
                 .next()
                 .and_then(|value| value.parse::<u64>().ok())
                 .unwrap_or(0);
+            let check_failed = parts
+                .next()
+                .and_then(|value| value.parse::<u8>().ok())
+                .is_some_and(|value| value != 0);
 
             let seconds_elapsed = elapsed_ms / 1000;
-            let in_range = match metrics_range {
-                Some(metrics::MetricsRange(range)) => range.contains(&seconds_elapsed),
-                None => true,
-            };
+            let in_range = metrics_range
+                .as_ref()
+                .is_none_or(|range| range.contains(seconds_elapsed));
             if !in_range {
                 continue;
             }
@@ -87,6 +90,7 @@ pub(super) async fn load_log_records(
                     transport_error,
                     response_bytes,
                     in_flight_ops,
+                    check_failed,
                 });
             } else {
                 metrics_truncated = true;
@@ -5,7 +5,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tokio::time::Instant;
 
-use crate::args::TesterArgs;
+use crate::args::{OutputFormat, TesterArgs};
 use crate::error::AppResult;
 use crate::metrics;
 
@@ -22,6 +22,7 @@ pub(super) async fn setup_log_sinks(
         || args.export_csv.is_some()
         || args.export_json.is_some()
         || args.export_jsonl.is_some()
+        || args.output_format == Some(OutputFormat::Tsv)
         || args.db_url.is_some();
 
     if !log_enabled {
@@ -59,6 +60,7 @@ pub(super) async fn setup_log_sinks(
             metrics_range: args.metrics_range.clone(),
             metrics_max: metrics_max_per_shard,
             db_url: if shard == 0 { db_url.clone() } else { None },
+            per_status_latency: args.per_status_latency,
         };
         let handle = metrics::setup_metrics_logger(log_path, logger_config, log_rx);
         handles.push(handle);
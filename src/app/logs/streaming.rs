@@ -3,16 +3,17 @@ use std::path::PathBuf;
 
 use tokio::io::BufReader;
 
+use crate::args::ExpectedStatus;
 use crate::error::{AppError, AppResult, MetricsError};
 use crate::metrics;
 
 use super::parsing::{
-    HeapItem, LogCursor, PercentileSeries, ensure_len, inc_slot, read_next_record,
+    HeapItem, LogCursor, LogRecord, PercentileSeries, ensure_len, inc_slot, read_next_record,
 };
 
 pub(super) async fn load_chart_data_streaming(
     paths: &[PathBuf],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     metrics_range: &Option<metrics::MetricsRange>,
     latency_bucket_ms: u64,
 ) -> AppResult<metrics::StreamingChartData> {
@@ -43,194 +44,287 @@ pub(super) async fn load_chart_data_streaming(
         }
     }
 
-    let mut avg_buckets: BTreeMap<u64, (u128, u64)> = BTreeMap::new();
-    let mut total_buckets: BTreeMap<u64, u64> = BTreeMap::new();
-    let mut success_buckets: BTreeMap<u64, u64> = BTreeMap::new();
-    let mut error_buckets: BTreeMap<u64, u64> = BTreeMap::new();
-    let mut rps_counts: Vec<u32> = Vec::new();
-    let mut timeouts: Vec<u32> = Vec::new();
-    let mut transports: Vec<u32> = Vec::new();
-    let mut non_expected: Vec<u32> = Vec::new();
-    let mut status_2xx: Vec<u32> = Vec::new();
-    let mut status_3xx: Vec<u32> = Vec::new();
-    let mut status_4xx: Vec<u32> = Vec::new();
-    let mut status_5xx: Vec<u32> = Vec::new();
-    let mut status_other: Vec<u32> = Vec::new();
-    let mut inflight_deltas: Vec<i64> = Vec::new();
-
-    let mut latency_buckets_ms: Vec<u64> = Vec::new();
-    let mut p50: Vec<u64> = Vec::new();
-    let mut p90: Vec<u64> = Vec::new();
-    let mut p99: Vec<u64> = Vec::new();
-    let mut p50_ok: Vec<u64> = Vec::new();
-    let mut p90_ok: Vec<u64> = Vec::new();
-    let mut p99_ok: Vec<u64> = Vec::new();
-
-    let bucket_ms = latency_bucket_ms.max(1);
-    let mut current_bucket: Option<u64> = None;
-    let mut latencies: Vec<u64> = Vec::new();
-    let mut latencies_ok: Vec<u64> = Vec::new();
-    let mut series = PercentileSeries {
-        latency_seconds: &mut latency_buckets_ms,
-        p50: &mut p50,
-        p90: &mut p90,
-        p99: &mut p99,
-        p50_ok: &mut p50_ok,
-        p90_ok: &mut p90_ok,
-        p99_ok: &mut p99_ok,
-    };
-
+    let mut accumulator = ChartAccumulator::new(expected_status_code, latency_bucket_ms);
     while let Some(std::cmp::Reverse(item)) = heap.pop() {
         let record = item.record;
         let sec = record.elapsed_ms / 1000;
-        let bucket = record.elapsed_ms.checked_div(bucket_ms).unwrap_or(0);
 
-        if let Some(metrics::MetricsRange(range)) = metrics_range.as_ref()
-            && !range.contains(&sec)
+        if metrics_range
+            .as_ref()
+            .is_none_or(|range| range.contains(sec))
         {
-            if let Some(cursor) = cursors.get_mut(item.idx)
-                && let Some(next) = read_next_record(cursor).await?
-            {
-                heap.push(std::cmp::Reverse(HeapItem {
-                    elapsed_ms: next.elapsed_ms,
-                    idx: item.idx,
-                    record: next,
-                }));
-            }
-            continue;
+            accumulator.push(&record)?;
+        }
+
+        if let Some(cursor) = cursors.get_mut(item.idx)
+            && let Some(next) = read_next_record(cursor).await?
+        {
+            heap.push(std::cmp::Reverse(HeapItem {
+                elapsed_ms: next.elapsed_ms,
+                idx: item.idx,
+                record: next,
+            }));
+        }
+    }
+
+    Ok(accumulator.finish())
+}
+
+/// Builds chart data from records that are already in memory, sorted by
+/// `elapsed_ms`.
+pub(super) fn chart_data_from_records(
+    records: &[metrics::MetricRecord],
+    expected_status_code: ExpectedStatus,
+    latency_bucket_ms: u64,
+) -> AppResult<metrics::StreamingChartData> {
+    let mut accumulator = ChartAccumulator::new(expected_status_code, latency_bucket_ms);
+    for record in records {
+        accumulator.push(&LogRecord {
+            elapsed_ms: record.elapsed_ms,
+            latency_ms: record.latency_ms,
+            status_code: record.status_code,
+            timed_out: record.timed_out,
+            transport_error: record.transport_error,
+            response_bytes: record.response_bytes,
+            in_flight_ops: record.in_flight_ops,
+            check_failed: record.check_failed,
+        })?;
+    }
+    Ok(accumulator.finish())
+}
+
+/// Folds records, in `elapsed_ms` order, into [`metrics::StreamingChartData`].
+struct ChartAccumulator {
+    expected_status_code: ExpectedStatus,
+    bucket_ms: u64,
+    avg_buckets: BTreeMap<u64, (u128, u64)>,
+    total_buckets: BTreeMap<u64, u64>,
+    success_buckets: BTreeMap<u64, u64>,
+    error_buckets: BTreeMap<u64, u64>,
+    rps_counts: Vec<u32>,
+    response_bytes: Vec<u64>,
+    timeouts: Vec<u32>,
+    transports: Vec<u32>,
+    non_expected: Vec<u32>,
+    status_2xx: Vec<u32>,
+    status_3xx: Vec<u32>,
+    status_4xx: Vec<u32>,
+    status_5xx: Vec<u32>,
+    status_other: Vec<u32>,
+    inflight_deltas: Vec<i64>,
+    inflight_latency: BTreeMap<u64, metrics::LatencyHistogram>,
+    latency_buckets_ms: Vec<u64>,
+    p50: Vec<u64>,
+    p90: Vec<u64>,
+    p99: Vec<u64>,
+    p50_ok: Vec<u64>,
+    p90_ok: Vec<u64>,
+    p99_ok: Vec<u64>,
+    current_bucket: Option<u64>,
+    latencies: Vec<u64>,
+    latencies_ok: Vec<u64>,
+}
+
+impl ChartAccumulator {
+    fn new(expected_status_code: ExpectedStatus, latency_bucket_ms: u64) -> Self {
+        Self {
+            expected_status_code,
+            bucket_ms: latency_bucket_ms.max(1),
+            avg_buckets: BTreeMap::new(),
+            total_buckets: BTreeMap::new(),
+            success_buckets: BTreeMap::new(),
+            error_buckets: BTreeMap::new(),
+            rps_counts: Vec::new(),
+            response_bytes: Vec::new(),
+            timeouts: Vec::new(),
+            transports: Vec::new(),
+            non_expected: Vec::new(),
+            status_2xx: Vec::new(),
+            status_3xx: Vec::new(),
+            status_4xx: Vec::new(),
+            status_5xx: Vec::new(),
+            status_other: Vec::new(),
+            inflight_deltas: Vec::new(),
+            inflight_latency: BTreeMap::new(),
+            latency_buckets_ms: Vec::new(),
+            p50: Vec::new(),
+            p90: Vec::new(),
+            p99: Vec::new(),
+            p50_ok: Vec::new(),
+            p90_ok: Vec::new(),
+            p99_ok: Vec::new(),
+            current_bucket: None,
+            latencies: Vec::new(),
+            latencies_ok: Vec::new(),
         }
+    }
 
-        match current_bucket {
+    fn flush_percentiles(&mut self, bucket: u64) {
+        let mut series = PercentileSeries {
+            latency_seconds: &mut self.latency_buckets_ms,
+            p50: &mut self.p50,
+            p90: &mut self.p90,
+            p99: &mut self.p99,
+            p50_ok: &mut self.p50_ok,
+            p90_ok: &mut self.p90_ok,
+            p99_ok: &mut self.p99_ok,
+        };
+        series.push_percentiles_for_sec(
+            bucket.saturating_mul(self.bucket_ms),
+            &mut self.latencies,
+            &mut self.latencies_ok,
+        );
+    }
+
+    fn push(&mut self, record: &LogRecord) -> AppResult<()> {
+        let expected_status_code = self.expected_status_code;
+        let sec = record.elapsed_ms / 1000;
+        let bucket = record.elapsed_ms.checked_div(self.bucket_ms).unwrap_or(0);
+
+        match self.current_bucket {
             Some(active) if bucket != active => {
-                series.push_percentiles_for_sec(
-                    active.saturating_mul(bucket_ms),
-                    &mut latencies,
-                    &mut latencies_ok,
-                );
-                current_bucket = Some(bucket);
+                self.flush_percentiles(active);
+                self.current_bucket = Some(bucket);
             }
-            None => current_bucket = Some(bucket),
+            None => self.current_bucket = Some(bucket),
             _ => {}
         }
 
         let bucket_100ms = record.elapsed_ms / 100;
-        let entry = avg_buckets.entry(bucket_100ms).or_insert((0, 0));
+        let entry = self.avg_buckets.entry(bucket_100ms).or_insert((0, 0));
         entry.0 = entry.0.saturating_add(u128::from(record.latency_ms));
         entry.1 = entry.1.saturating_add(1);
 
-        let total_entry = total_buckets.entry(bucket_100ms).or_insert(0);
+        let total_entry = self.total_buckets.entry(bucket_100ms).or_insert(0);
         *total_entry = total_entry.saturating_add(1);
 
-        if record.status_code == expected_status_code {
-            let success_entry = success_buckets.entry(bucket_100ms).or_insert(0);
+        let status_ok = expected_status_code.contains(record.status_code) && !record.check_failed;
+        if status_ok {
+            let success_entry = self.success_buckets.entry(bucket_100ms).or_insert(0);
             *success_entry = success_entry.saturating_add(1);
         }
-        if record.status_code != expected_status_code {
-            let error_entry = error_buckets.entry(bucket_100ms).or_insert(0);
+        if !status_ok {
+            let error_entry = self.error_buckets.entry(bucket_100ms).or_insert(0);
             *error_entry = error_entry.saturating_add(1);
         }
 
         let sec_idx = usize::try_from(sec).unwrap_or(usize::MAX);
         let sec_len = sec_idx.saturating_add(1);
-        ensure_len(&mut rps_counts, sec_len);
-        inc_slot(&mut rps_counts, sec_idx);
+        ensure_len(&mut self.rps_counts, sec_len);
+        inc_slot(&mut self.rps_counts, sec_idx);
+        if self.response_bytes.len() < sec_len {
+            self.response_bytes.resize(sec_len, 0);
+        }
+        if let Some(slot) = self.response_bytes.get_mut(sec_idx) {
+            *slot = slot.saturating_add(record.response_bytes);
+        }
 
-        ensure_len(&mut timeouts, sec_len);
-        ensure_len(&mut transports, sec_len);
-        ensure_len(&mut non_expected, sec_len);
+        ensure_len(&mut self.timeouts, sec_len);
+        ensure_len(&mut self.transports, sec_len);
+        ensure_len(&mut self.non_expected, sec_len);
 
         if record.timed_out {
-            inc_slot(&mut timeouts, sec_idx);
+            inc_slot(&mut self.timeouts, sec_idx);
         } else if record.transport_error {
-            inc_slot(&mut transports, sec_idx);
-        } else if record.status_code != expected_status_code {
-            inc_slot(&mut non_expected, sec_idx);
+            inc_slot(&mut self.transports, sec_idx);
+        } else if !expected_status_code.contains(record.status_code) {
+            inc_slot(&mut self.non_expected, sec_idx);
         }
 
-        ensure_len(&mut status_2xx, sec_len);
-        ensure_len(&mut status_3xx, sec_len);
-        ensure_len(&mut status_4xx, sec_len);
-        ensure_len(&mut status_5xx, sec_len);
-        ensure_len(&mut status_other, sec_len);
+        ensure_len(&mut self.status_2xx, sec_len);
+        ensure_len(&mut self.status_3xx, sec_len);
+        ensure_len(&mut self.status_4xx, sec_len);
+        ensure_len(&mut self.status_5xx, sec_len);
+        ensure_len(&mut self.status_other, sec_len);
 
         match record.status_code {
-            200..=299 => inc_slot(&mut status_2xx, sec_idx),
-            300..=399 => inc_slot(&mut status_3xx, sec_idx),
-            400..=499 => inc_slot(&mut status_4xx, sec_idx),
-            500..=599 => inc_slot(&mut status_5xx, sec_idx),
-            _ => inc_slot(&mut status_other, sec_idx),
+            200..=299 => inc_slot(&mut self.status_2xx, sec_idx),
+            300..=399 => inc_slot(&mut self.status_3xx, sec_idx),
+            400..=499 => inc_slot(&mut self.status_4xx, sec_idx),
+            500..=599 => inc_slot(&mut self.status_5xx, sec_idx),
+            _ => inc_slot(&mut self.status_other, sec_idx),
         }
 
         let start_sec = sec_idx;
         let end_total_ms = record.elapsed_ms.saturating_add(record.latency_ms);
         let end_sec = usize::try_from(end_total_ms / 1000).unwrap_or(usize::MAX);
         let end_idx = end_sec.saturating_add(1);
-        if inflight_deltas.len() <= end_idx {
-            inflight_deltas.resize(end_idx.saturating_add(1), 0);
+        if self.inflight_deltas.len() <= end_idx {
+            self.inflight_deltas.resize(end_idx.saturating_add(1), 0);
         }
-        if let Some(slot) = inflight_deltas.get_mut(start_sec) {
+        if let Some(slot) = self.inflight_deltas.get_mut(start_sec) {
             *slot = slot.saturating_add(1);
         }
-        if let Some(slot) = inflight_deltas.get_mut(end_idx) {
+        if let Some(slot) = self.inflight_deltas.get_mut(end_idx) {
             *slot = slot.saturating_sub(1);
         }
 
-        latencies.push(record.latency_ms);
-        if record.status_code == expected_status_code
-            && !record.timed_out
-            && !record.transport_error
-        {
-            latencies_ok.push(record.latency_ms);
-        }
+        let histogram = match self.inflight_latency.entry(record.in_flight_ops) {
+            std::collections::btree_map::Entry::Occupied(slot) => slot.into_mut(),
+            std::collections::btree_map::Entry::Vacant(slot) => {
+                slot.insert(metrics::LatencyHistogram::new()?)
+            }
+        };
+        histogram.record(record.latency_ms)?;
 
-        if let Some(cursor) = cursors.get_mut(item.idx)
-            && let Some(next) = read_next_record(cursor).await?
-        {
-            heap.push(std::cmp::Reverse(HeapItem {
-                elapsed_ms: next.elapsed_ms,
-                idx: item.idx,
-                record: next,
-            }));
+        self.latencies.push(record.latency_ms);
+        if status_ok && !record.timed_out && !record.transport_error {
+            self.latencies_ok.push(record.latency_ms);
         }
+        Ok(())
     }
 
-    if let Some(active) = current_bucket {
-        series.push_percentiles_for_sec(
-            active.saturating_mul(bucket_ms),
-            &mut latencies,
-            &mut latencies_ok,
-        );
-    }
+    fn finish(mut self) -> metrics::StreamingChartData {
+        if let Some(active) = self.current_bucket {
+            self.flush_percentiles(active);
+        }
 
-    let mut inflight: Vec<u32> = Vec::with_capacity(inflight_deltas.len());
-    let mut current: i64 = 0;
-    for delta in inflight_deltas {
-        current = current.saturating_add(delta);
-        inflight.push(u32::try_from(current.max(0)).unwrap_or(u32::MAX));
-    }
+        let mut inflight: Vec<u32> = Vec::with_capacity(self.inflight_deltas.len());
+        let mut current: i64 = 0;
+        for delta in self.inflight_deltas {
+            current = current.saturating_add(delta);
+            inflight.push(u32::try_from(current.max(0)).unwrap_or(u32::MAX));
+        }
+
+        let latency_by_inflight = self
+            .inflight_latency
+            .into_iter()
+            .map(|(in_flight, histogram)| {
+                let (level_p50, _, level_p99) = histogram.percentiles();
+                metrics::InflightLatencyPoint {
+                    in_flight,
+                    samples: histogram.count(),
+                    p50: level_p50,
+                    p99: level_p99,
+                }
+            })
+            .collect();
 
-    Ok(metrics::StreamingChartData {
-        avg_buckets,
-        total_buckets,
-        success_buckets,
-        error_buckets,
-        rps_counts,
-        timeouts,
-        transports,
-        non_expected,
-        status_2xx,
-        status_3xx,
-        status_4xx,
-        status_5xx,
-        status_other,
-        inflight,
-        latency_buckets_ms,
-        latency_bucket_ms: bucket_ms,
-        p50,
-        p90,
-        p99,
-        p50_ok,
-        p90_ok,
-        p99_ok,
-    })
+        metrics::StreamingChartData {
+            avg_buckets: self.avg_buckets,
+            total_buckets: self.total_buckets,
+            success_buckets: self.success_buckets,
+            error_buckets: self.error_buckets,
+            rps_counts: self.rps_counts,
+            response_bytes: self.response_bytes,
+            timeouts: self.timeouts,
+            transports: self.transports,
+            non_expected: self.non_expected,
+            status_2xx: self.status_2xx,
+            status_3xx: self.status_3xx,
+            status_4xx: self.status_4xx,
+            status_5xx: self.status_5xx,
+            status_other: self.status_other,
+            inflight,
+            latency_buckets_ms: self.latency_buckets_ms,
+            latency_bucket_ms: self.bucket_ms,
+            p50: self.p50,
+            p90: self.p90,
+            p99: self.p99,
+            p50_ok: self.p50_ok,
+            p90_ok: self.p90_ok,
+            p99_ok: self.p99_ok,
+            latency_by_inflight,
+        }
+    }
 }
@@ -1,13 +1,19 @@
+pub(crate) mod assertions;
 mod cleanup;
 mod compare;
 mod export;
+mod history;
 pub(crate) mod logs;
+mod notify;
 mod progress;
 mod replay;
+mod run_start;
 mod runner;
 pub(crate) mod summary;
 
 pub(crate) use cleanup::run_cleanup;
 pub(crate) use compare::run_compare;
 pub(crate) use replay::run_replay;
+pub(crate) use run_start::RunStartPayload;
+pub(crate) use run_start::{echo_run_config, redact_url};
 pub(crate) use runner::run_local;
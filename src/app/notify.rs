@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use crate::app::assertions::AssertionResult;
+use crate::app::summary::{PERCENT_DIVISOR, SummaryStats};
+use crate::args::{NotifyOn, TesterArgs, tag_map};
+use crate::error::{AppError, AppResult, HttpError};
+use crate::metrics;
+
+/// How long the webhook may take before the notification is given up.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Host of Slack incoming webhooks, which expect a `{"text": ...}` message.
+const SLACK_WEBHOOK_HOST: &str = "hooks.slack.com";
+
+/// End-of-run results sent to `--notify-webhook`.
+pub(crate) struct RunNotice<'run> {
+    pub(crate) summary: &'run metrics::MetricsSummary,
+    pub(crate) stats: &'run SummaryStats,
+    pub(crate) p99: u64,
+    pub(crate) assertions: &'run [AssertionResult],
+    /// Why the run fails; empty when it passes.
+    pub(crate) failures: Vec<String>,
+}
+
+impl RunNotice<'_> {
+    const fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    const fn status(&self) -> &'static str {
+        if self.passed() { "passed" } else { "failed" }
+    }
+}
+
+/// Posts `notice` to `--notify-webhook` when `--notify-on` asks for it.
+/// Failures are logged and never fail the run.
+pub(crate) async fn notify_run_end(args: &TesterArgs, notice: &RunNotice<'_>) {
+    let Some(url) = args.notify_webhook.as_deref() else {
+        return;
+    };
+    if args.notify_on == NotifyOn::Failure && notice.passed() {
+        return;
+    }
+    let payload = if is_slack_webhook(url) {
+        slack_payload(args, notice)
+    } else {
+        json_payload(args, notice)
+    };
+    match post_webhook(url, &payload).await {
+        Ok(()) => tracing::info!("Sent run notification to {}", url),
+        Err(err) => tracing::warn!("Run notification failed: {}", err),
+    }
+}
+
+async fn post_webhook(url: &str, payload: &serde_json::Value) -> AppResult<()> {
+    let notify_err = |err: reqwest::Error| {
+        AppError::http(HttpError::NotifyWebhook {
+            url: url.to_owned(),
+            source: err,
+        })
+    };
+    let client = reqwest::Client::builder()
+        .timeout(NOTIFY_TIMEOUT)
+        .build()
+        .map_err(notify_err)?;
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(notify_err)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::http(HttpError::NotifyWebhookStatus {
+            url: url.to_owned(),
+            status: status.as_u16(),
+        }));
+    }
+    Ok(())
+}
+
+fn is_slack_webhook(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|parsed| parsed.host_str() == Some(SLACK_WEBHOOK_HOST))
+}
+
+fn json_payload(args: &TesterArgs, notice: &RunNotice<'_>) -> serde_json::Value {
+    let summary = notice.summary;
+    let assertions: Vec<serde_json::Value> = notice
+        .assertions
+        .iter()
+        .map(|result| {
+            serde_json::json!({
+                "name": result.name,
+                "expected": result.expected,
+                "observed": result.observed,
+                "passed": result.passed,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "status": notice.status(),
+        "run_id": args.run_id,
+        "url": args.url,
+        "protocol": args.protocol.as_str(),
+        "tags": tag_map(&args.tags),
+        "duration_ms": summary.duration.as_millis(),
+        "total_requests": summary.total_requests,
+        "successful_requests": summary.successful_requests,
+        "error_requests": summary.error_requests,
+        "success_rate_x100": notice.stats.success_rate_x100,
+        "avg_rps_x100": notice.stats.avg_rps_x100,
+        "p99_latency_ms": notice.p99,
+        "assertions": assertions,
+        "failures": notice.failures,
+    })
+}
+
+fn slack_payload(args: &TesterArgs, notice: &RunNotice<'_>) -> serde_json::Value {
+    serde_json::json!({ "text": slack_text(args, notice) })
+}
+
+fn slack_text(args: &TesterArgs, notice: &RunNotice<'_>) -> String {
+    let success_rate = notice.stats.success_rate_x100;
+    let mut lines = vec![format!(
+        "strest run {} for {}: {} requests in {}s, {}.{:02}% success, p99 {}ms",
+        notice.status().to_uppercase(),
+        args.url.as_deref().unwrap_or("<scenario>"),
+        notice.summary.total_requests,
+        notice.summary.duration.as_secs(),
+        success_rate / PERCENT_DIVISOR,
+        success_rate % PERCENT_DIVISOR,
+        notice.p99
+    )];
+    if !notice.assertions.is_empty() {
+        let passed = notice
+            .assertions
+            .iter()
+            .filter(|result| result.passed)
+            .count();
+        lines.push(format!(
+            "Assertions: {}/{} passed",
+            passed,
+            notice.assertions.len()
+        ));
+    }
+    lines.extend(
+        notice
+            .failures
+            .iter()
+            .map(|failure| format!("• {}", failure)),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::error::AppError;
+
+    fn summary() -> metrics::MetricsSummary {
+        metrics::MetricsSummary {
+            duration: Duration::from_secs(60),
+            total_requests: 1_000,
+            successful_requests: 995,
+            error_requests: 5,
+            timeout_requests: 0,
+            transport_errors: 0,
+            non_expected_status: 5,
+            min_latency_ms: 1,
+            max_latency_ms: 300,
+            avg_latency_ms: 20,
+            success_min_latency_ms: 1,
+            success_max_latency_ms: 300,
+            success_avg_latency_ms: 20,
+            http_versions: std::collections::BTreeMap::new(),
+            error_reasons: std::collections::BTreeMap::new(),
+            check_failures: crate::metrics::CheckFailures::default(),
+        }
+    }
+
+    #[test]
+    fn notification_payloads_carry_status_and_assertions() -> AppResult<()> {
+        let args = TesterArgs::try_parse_from([
+            "strest",
+            "-u",
+            "http://localhost/api",
+            "--notify-webhook",
+            "https://hooks.slack.com/services/T0/B0/x",
+            "--notify-on",
+            "failure",
+        ])?;
+        let summary = summary();
+        let stats = SummaryStats {
+            success_rate_x100: 9_950,
+            avg_rps_x100: 1_666,
+            avg_rpm_x100: 99_960,
+        };
+        let assertions = [AssertionResult {
+            name: "p99_latency",
+            expected: "<= 100ms".to_owned(),
+            observed: "120ms".to_owned(),
+            passed: false,
+        }];
+        let notice = RunNotice {
+            summary: &summary,
+            stats: &stats,
+            p99: 120,
+            assertions: &assertions,
+            failures: assertions.iter().map(AssertionResult::message).collect(),
+        };
+
+        if !is_slack_webhook("https://hooks.slack.com/services/T0/B0/x")
+            || is_slack_webhook("https://example.com/hooks.slack.com")
+        {
+            return Err(AppError::validation("Unexpected Slack webhook detection"));
+        }
+        let expected_text = "strest run FAILED for http://localhost/api: 1000 requests in 60s, \
+             99.50% success, p99 120ms\n\
+             Assertions: 0/1 passed\n\
+             • p99_latency: observed 120ms, expected <= 100ms";
+        if slack_text(&args, &notice) != expected_text {
+            return Err(AppError::validation(format!(
+                "Unexpected Slack text: {}",
+                slack_text(&args, &notice)
+            )));
+        }
+
+        let payload = json_payload(&args, &notice);
+        let expected = [
+            ("/status", serde_json::json!("failed")),
+            ("/success_rate_x100", serde_json::json!(9_950)),
+            ("/p99_latency_ms", serde_json::json!(120)),
+            ("/assertions/0/passed", serde_json::json!(false)),
+        ];
+        if expected
+            .iter()
+            .any(|(pointer, value)| payload.pointer(pointer) != Some(value))
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected JSON payload: {}",
+                payload
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
 mod bounds;
+mod chart_data;
 mod records;
 mod runner;
 mod snapshots;
 pub(crate) mod state;
 pub(crate) mod summary;
 pub(crate) mod ui;
+#[cfg(feature = "replay-video")]
+mod video;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use crate::app::logs;
+use crate::args::TesterArgs;
+use crate::charts;
+use crate::error::AppResult;
+use crate::metrics::MetricRecord;
+
+use super::window_slice;
+
+/// Rebuilds the chart data for the replay window and writes it as pretty
+/// JSON, returning the number of records it covers.
+///
+/// This is the same `StreamingChartData` the end-of-run charts are drawn
+/// from, so external tools can re-plot a run without strest's renderer.
+pub(super) async fn write_chart_data(
+    records: &[MetricRecord],
+    args: &TesterArgs,
+    start_ms: u64,
+    end_ms: u64,
+    path: &str,
+) -> AppResult<usize> {
+    let window = window_slice(records, start_ms, end_ms);
+    let chart_data = logs::chart_data_from_records(
+        window,
+        args.expected_status_code,
+        args.charts_latency_bucket_ms.get(),
+    )?;
+    let json = serde_json::to_vec_pretty(&chart_data)?;
+    if let Some(parent) = Path::new(path).parent()
+        && !parent.as_os_str().is_empty()
+    {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, json).await?;
+    Ok(window.len())
+}
+
+/// Rebuilds the chart data for the replay window and plots it to
+/// `--charts-path`, returning the chart directory, or `None` when the window
+/// holds no records.
+pub(super) async fn write_charts(
+    records: &[MetricRecord],
+    args: &TesterArgs,
+    start_ms: u64,
+    end_ms: u64,
+) -> AppResult<Option<String>> {
+    let window = window_slice(records, start_ms, end_ms);
+    let chart_data = logs::chart_data_from_records(
+        window,
+        args.expected_status_code,
+        args.charts_latency_bucket_ms.get(),
+    )?;
+    charts::plot_streaming_metrics(&chart_data, args).await
+}
@@ -148,6 +148,7 @@ pub(super) async fn read_csv_records(path: &Path) -> AppResult<Vec<MetricRecord>
             .next()
             .and_then(|value| value.parse::<u64>().ok())
             .unwrap_or(0);
+        let check_failed = parts.next().map(parse_bool).unwrap_or(false);
         records.push(MetricRecord {
             elapsed_ms,
             latency_ms,
@@ -156,6 +157,7 @@ pub(super) async fn read_csv_records(path: &Path) -> AppResult<Vec<MetricRecord>
             transport_error,
             response_bytes,
             in_flight_ops,
+            check_failed,
         });
     }
 
@@ -186,6 +188,7 @@ pub(super) async fn read_json_records(path: &Path) -> AppResult<Vec<MetricRecord
             transport_error: record.transport_error,
             response_bytes: record.response_bytes.unwrap_or(0),
             in_flight_ops: record.in_flight_ops.unwrap_or(0),
+            check_failed: record.check_failed.unwrap_or(false),
         })
         .collect())
 }
@@ -244,6 +247,7 @@ pub(super) async fn read_jsonl_records(path: &Path) -> AppResult<Vec<MetricRecor
             transport_error: parsed.transport_error.unwrap_or(false),
             response_bytes: parsed.response_bytes.unwrap_or(0),
             in_flight_ops: parsed.in_flight_ops.unwrap_or(0),
+            check_failed: parsed.check_failed.unwrap_or(false),
         });
     }
 
@@ -269,6 +273,7 @@ struct ExportRecord {
     transport_error: bool,
     response_bytes: Option<u64>,
     in_flight_ops: Option<u64>,
+    check_failed: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -282,4 +287,5 @@ struct ExportJsonlLine {
     transport_error: Option<bool>,
     response_bytes: Option<u64>,
     in_flight_ops: Option<u64>,
+    check_failed: Option<bool>,
 }
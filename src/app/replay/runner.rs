@@ -3,8 +3,8 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use tokio::sync::watch;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tokio::sync::{mpsc, watch};
 
 use crate::args::TesterArgs;
 use crate::error::{AppError, AppResult, MetricsError, ValidationError};
@@ -22,7 +22,7 @@ use super::snapshots::{
 };
 use super::state::SnapshotMarkers;
 use super::ui::render_once;
-use super::{snapshots, ui};
+use super::{chart_data, snapshots, ui};
 
 /// Playback tick used when replay is in "playing" mode.
 const REPLAY_TICK_MS: u64 = 1000;
@@ -30,8 +30,6 @@ const REPLAY_TICK_MS: u64 = 1000;
 const DEFAULT_REPLAY_STEP: Duration = Duration::from_secs(1);
 /// UI refresh poll cadence for replay mode.
 const UI_POLL_INTERVAL: Duration = Duration::from_millis(100);
-/// Non-blocking poll interval for keyboard events.
-const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(0);
 
 pub(crate) async fn run_replay(args: &TesterArgs) -> AppResult<()> {
     let records = load_replay_records(args).await?;
@@ -75,6 +73,49 @@ pub(crate) async fn run_replay(args: &TesterArgs) -> AppResult<()> {
 
     let step_ms = resolve_step_ms(args.replay_step, DEFAULT_REPLAY_STEP);
 
+    if let Some(path) = args.replay_export_chart_data.as_deref() {
+        let exported = chart_data::write_chart_data(&records, args, start_ms, end_ms, path).await?;
+        println!("Wrote chart data for {} record(s) to {}", exported, path);
+        return Ok(());
+    }
+
+    if args.replay_export_charts {
+        match chart_data::write_charts(&records, args, start_ms, end_ms).await? {
+            Some(path) => println!("Charts saved in {}", path),
+            None => println!("No records in the replay window; no charts written."),
+        }
+        return Ok(());
+    }
+
+    if let Some(path) = args.replay_video.as_deref() {
+        #[cfg(feature = "replay-video")]
+        {
+            // Rendering and encoding every frame is CPU-bound; keep it off the runtime.
+            let video_args = args.clone();
+            let video_path = path.to_owned();
+            let frames = tokio::task::spawn_blocking(move || {
+                super::video::write_replay_video(
+                    &records,
+                    &video_args,
+                    start_ms,
+                    end_ms,
+                    step_ms,
+                    &video_path,
+                )
+            })
+            .await??;
+            println!("Wrote {} replay frame(s) to {}", frames, path);
+            return Ok(());
+        }
+        #[cfg(not(feature = "replay-video"))]
+        {
+            let _ = path;
+            return Err(AppError::validation(
+                ValidationError::ReplayVideoFeatureDisabled,
+            ));
+        }
+    }
+
     if !io::stdout().is_terminal() || args.no_ui {
         if snapshot_requested {
             if let Some(interval) = args.replay_snapshot_interval {
@@ -122,7 +163,10 @@ pub(crate) async fn run_replay(args: &TesterArgs) -> AppResult<()> {
         ..UiData::default()
     };
     let (ui_tx, _) = watch::channel(initial_ui);
-    let render_ui_handle = setup_render_ui(&shutdown_tx, &ui_tx);
+    let render_ui_handle = setup_render_ui(&shutdown_tx, &ui_tx, false);
+    let (key_tx, mut key_rx) = mpsc::unbounded_channel();
+    let key_reader_handle =
+        crate::system::shutdown_handlers::setup_key_event_reader(&shutdown_tx, key_tx);
 
     let mut state = PlaybackState::new(start_ms, end_ms);
     let mut last_tick = tokio::time::Instant::now();
@@ -139,15 +183,14 @@ pub(crate) async fn run_replay(args: &TesterArgs) -> AppResult<()> {
                 break;
             }
 
-            if event::poll(EVENT_POLL_INTERVAL)?
-                && let Event::Key(key) = event::read()?
-                && key.kind == KeyEventKind::Press
-            {
+            let mut quit = false;
+            while let Ok(key) = key_rx.try_recv() {
                 // Handle Ctrl+C and q/Esc for quitting
                 if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
                     || (key.code == KeyCode::Char('c')
-                        && key.modifiers.contains(event::KeyModifiers::CONTROL))
+                        && key.modifiers.contains(KeyModifiers::CONTROL))
                 {
+                    quit = true;
                     break;
                 }
                 if let Some(action) = resolve_playback_action(key.code) {
@@ -176,6 +219,9 @@ pub(crate) async fn run_replay(args: &TesterArgs) -> AppResult<()> {
                     dirty = true;
                 }
             }
+            if quit {
+                break;
+            }
 
             if state.playing {
                 if advance_playback(&mut state, last_tick.elapsed(), REPLAY_TICK_MS) {
@@ -233,6 +279,9 @@ pub(crate) async fn run_replay(args: &TesterArgs) -> AppResult<()> {
     if let Err(err) = render_ui_handle.await {
         eprintln!("Replay UI task failed: {}", err);
     }
+    if let Err(err) = key_reader_handle.await {
+        eprintln!("Replay key reader task failed: {}", err);
+    }
     result
 }
 
@@ -255,6 +304,12 @@ const fn resolve_playback_action(key_code: KeyCode) -> Option<PlaybackAction> {
     if matches!(key_code, KeyCode::Char('r')) {
         return Some(PlaybackAction::Restart);
     }
+    if matches!(key_code, KeyCode::Char('+' | '=')) {
+        return Some(PlaybackAction::SpeedUp);
+    }
+    if matches!(key_code, KeyCode::Char('-' | '_')) {
+        return Some(PlaybackAction::SpeedDown);
+    }
     None
 }
 
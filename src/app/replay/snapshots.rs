@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::args::TesterArgs;
+use crate::args::{TesterArgs, tag_map};
 use crate::error::{AppError, AppResult, ValidationError};
 use crate::metrics::MetricRecord;
 
@@ -174,12 +174,40 @@ pub(super) async fn write_snapshot(
         SnapshotFormat::Json => {
             let summary_output =
                 summary::summarize(slice, args.expected_status_code, start_ms, end_ms)?;
-            export::export_json(&path.to_string_lossy(), &summary_output.summary, slice).await?;
+            let (p50, p90, p99, p999, p9999) = summary_output.histogram.percentiles_extended();
+            export::export_json(
+                &path.to_string_lossy(),
+                &summary_output.summary,
+                export::ExportPercentiles {
+                    p50,
+                    p90,
+                    p99,
+                    p999,
+                    p9999,
+                },
+                slice,
+                export::ExportExtras {
+                    http_versions: None,
+                    tags: &tag_map(&args.tags),
+                    sse: None,
+                },
+            )
+            .await?;
         }
         SnapshotFormat::Jsonl => {
             let summary_output =
                 summary::summarize(slice, args.expected_status_code, start_ms, end_ms)?;
-            export::export_jsonl(&path.to_string_lossy(), &summary_output.summary, slice).await?;
+            export::export_jsonl(
+                &path.to_string_lossy(),
+                &summary_output.summary,
+                slice,
+                export::ExportExtras {
+                    http_versions: None,
+                    tags: &tag_map(&args.tags),
+                    sse: None,
+                },
+            )
+            .await?;
         }
     }
     Ok(path)
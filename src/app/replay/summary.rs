@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+use crate::args::ExpectedStatus;
 use crate::error::AppResult;
-use crate::metrics::{LatencyHistogram, MetricRecord, MetricsSummary};
+use crate::metrics::{CheckFailures, LatencyHistogram, MetricRecord, MetricsSummary};
 
 use super::super::summary as app_summary;
 
@@ -13,7 +15,7 @@ pub(crate) struct SummaryOutput {
 
 pub(crate) fn summarize(
     records: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     window_start_ms: u64,
     window_end_ms: u64,
 ) -> AppResult<SummaryOutput> {
@@ -41,10 +43,7 @@ pub(crate) fn summarize(
         if record.latency_ms > max_latency_ms {
             max_latency_ms = record.latency_ms;
         }
-        if record.status_code == expected_status_code
-            && !record.timed_out
-            && !record.transport_error
-        {
+        if record.is_success(expected_status_code) {
             successful_requests = successful_requests.saturating_add(1);
             success_latency_sum_ms =
                 success_latency_sum_ms.saturating_add(u128::from(record.latency_ms));
@@ -60,7 +59,7 @@ pub(crate) fn summarize(
             timeout_requests = timeout_requests.saturating_add(1);
         } else if record.transport_error {
             transport_errors = transport_errors.saturating_add(1);
-        } else if record.status_code != expected_status_code {
+        } else if !expected_status_code.contains(record.status_code) {
             non_expected_status = non_expected_status.saturating_add(1);
         }
         histogram.record(record.latency_ms)?;
@@ -121,6 +120,9 @@ pub(crate) fn summarize(
             success_min_latency_ms,
             success_max_latency_ms,
             success_avg_latency_ms,
+            http_versions: BTreeMap::new(),
+            error_reasons: BTreeMap::new(),
+            check_failures: CheckFailures::default(),
         },
         histogram,
         success_histogram,
@@ -130,7 +132,7 @@ pub(crate) fn summarize(
 pub(super) fn compute_replay_percentiles(
     summary_output: &SummaryOutput,
     slice: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
 ) -> (u64, u64, u64, u64, u64, u64) {
     let (mut p50, mut p90, mut p99) = summary_output.histogram.percentiles();
     let (mut success_p50, mut success_p90, mut success_p99) =
@@ -145,11 +147,7 @@ pub(super) fn compute_replay_percentiles(
         let success_records: Vec<MetricRecord> = slice
             .iter()
             .copied()
-            .filter(|record| {
-                record.status_code == expected_status_code
-                    && !record.timed_out
-                    && !record.transport_error
-            })
+            .filter(|record| record.is_success(expected_status_code))
             .collect();
         let (fallback_p50, fallback_p90, fallback_p99) =
             app_summary::compute_percentiles(&success_records);
@@ -3,8 +3,10 @@ use super::records::{read_csv_records, read_json_records, read_jsonl_records};
 use super::state::{ReplayWindow, SnapshotMarkers};
 use super::ui::build_ui_data_with_config;
 use super::window_slice;
+use crate::args::ExpectedStatus;
 use crate::error::{AppError, AppResult};
 use crate::metrics::MetricRecord;
+use crate::system::replay_compare::NORMAL_SPEED_PERCENT;
 use std::time::Duration;
 use tempfile::tempdir;
 
@@ -78,6 +80,7 @@ fn window_slice_handles_bounds() -> AppResult<()> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            check_failed: false,
         },
         MetricRecord {
             elapsed_ms: 1000,
@@ -87,6 +90,7 @@ fn window_slice_handles_bounds() -> AppResult<()> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            check_failed: false,
         },
     ];
     let first_slice = window_slice(&records, 0, 500);
@@ -233,6 +237,7 @@ fn replay_ui_excludes_trailing_partial_bucket_from_rate_series() -> AppResult<()
             transport_error: false,
             response_bytes: 100,
             in_flight_ops: 1,
+            check_failed: false,
         },
         MetricRecord {
             elapsed_ms: 1900,
@@ -242,6 +247,7 @@ fn replay_ui_excludes_trailing_partial_bucket_from_rate_series() -> AppResult<()
             transport_error: false,
             response_bytes: 100,
             in_flight_ops: 1,
+            check_failed: false,
         },
         MetricRecord {
             elapsed_ms: 2000,
@@ -251,6 +257,7 @@ fn replay_ui_excludes_trailing_partial_bucket_from_rate_series() -> AppResult<()
             transport_error: false,
             response_bytes: 100,
             in_flight_ops: 1,
+            check_failed: false,
         },
     ];
 
@@ -259,10 +266,11 @@ fn replay_ui_excludes_trailing_partial_bucket_from_rate_series() -> AppResult<()
         cursor_ms: 2300,
         end_ms: 2300,
         playing: false,
+        speed_percent: NORMAL_SPEED_PERCENT,
     };
     let data = build_ui_data_with_config(
         &records,
-        200,
+        ExpectedStatus::single(200),
         10_000,
         true,
         &state,
@@ -287,3 +295,168 @@ fn replay_ui_excludes_trailing_partial_bucket_from_rate_series() -> AppResult<()
     }
     Ok(())
 }
+
+#[test]
+fn replay_export_chart_data_writes_window_as_json() -> AppResult<()> {
+    use clap::Parser;
+
+    run_async_test(async {
+        let args = crate::args::TesterArgs::try_parse_from([
+            "strest",
+            "--url",
+            "http://localhost",
+            "--charts-latency-bucket-ms",
+            "1000",
+        ])
+        .map_err(|err| AppError::validation(format!("Failed to parse args: {}", err)))?;
+        let record = |elapsed_ms: u64, latency_ms: u64, status_code: u16| MetricRecord {
+            elapsed_ms,
+            latency_ms,
+            status_code,
+            timed_out: false,
+            transport_error: false,
+            response_bytes: 10,
+            in_flight_ops: 1,
+            check_failed: false,
+        };
+        let records = vec![
+            record(100, 10, 200),
+            record(1_200, 20, 200),
+            record(1_700, 40, 500),
+            record(3_500, 80, 200),
+        ];
+        let dir = tempdir().map_err(|err| AppError::validation(format!("tempdir: {}", err)))?;
+        let out = dir.path().join("charts").join("data.json");
+        let out_str = out.to_string_lossy();
+
+        let exported =
+            super::chart_data::write_chart_data(&records, &args, 1_000, 2_000, &out_str).await?;
+        if exported != 2 {
+            return Err(AppError::validation(format!(
+                "Expected 2 records in window, got {}",
+                exported
+            )));
+        }
+        let json: serde_json::Value = serde_json::from_slice(&std::fs::read(&out)?)?;
+        let field = |name: &str| json.get(name).cloned().unwrap_or_default();
+        let expected = [
+            ("rps_counts", serde_json::json!([0, 2])),
+            ("status_2xx", serde_json::json!([0, 1])),
+            ("status_5xx", serde_json::json!([0, 1])),
+            ("latency_buckets_ms", serde_json::json!([1000])),
+            ("latency_bucket_ms", serde_json::json!(1000)),
+            (
+                "avg_buckets",
+                serde_json::json!({"12": [20, 1], "17": [40, 1]}),
+            ),
+        ];
+        for (name, value) in expected {
+            if field(name) != value {
+                return Err(AppError::validation(format!(
+                    "Unexpected {}: {}",
+                    name,
+                    field(name)
+                )));
+            }
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn replay_export_charts_plots_window_without_ui() -> AppResult<()> {
+    use clap::Parser;
+
+    run_async_test(async {
+        let dir = tempdir().map_err(|err| AppError::validation(format!("tempdir: {}", err)))?;
+        let charts_path = dir.path().join("charts");
+        let charts_str = charts_path.to_string_lossy().to_string();
+        let args = crate::args::TesterArgs::try_parse_from([
+            "strest",
+            "--url",
+            "http://localhost",
+            "--replay",
+            "--no-ui",
+            "--export-charts",
+            "--charts-path",
+            &charts_str,
+        ])
+        .map_err(|err| AppError::validation(format!("Failed to parse args: {}", err)))?;
+        if crate::args::TesterArgs::try_parse_from(["strest", "--export-charts"]).is_ok() {
+            return Err(AppError::validation(
+                "Expected --export-charts to require --replay",
+            ));
+        }
+        let records: Vec<MetricRecord> = (0..40_u64)
+            .map(|idx| MetricRecord {
+                elapsed_ms: idx.saturating_mul(100),
+                latency_ms: idx.saturating_add(5),
+                status_code: 200,
+                timed_out: false,
+                transport_error: false,
+                response_bytes: 10,
+                in_flight_ops: 1,
+                check_failed: false,
+            })
+            .collect();
+
+        let written = super::chart_data::write_charts(&records, &args, 1_000, 3_000).await?;
+        let chart_dir =
+            written.ok_or_else(|| AppError::validation("Expected a chart directory"))?;
+        if !std::path::Path::new(&chart_dir).starts_with(&charts_path)
+            || !std::path::Path::new(&chart_dir)
+                .join("average_response_time.png")
+                .is_file()
+        {
+            return Err(AppError::validation(format!(
+                "Expected charts under {}, got {}",
+                charts_str, chart_dir
+            )));
+        }
+
+        if super::chart_data::write_charts(&records, &args, 10_000, 20_000)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::validation(
+                "Expected no charts for an empty window",
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(feature = "replay-video")]
+#[test]
+fn replay_video_writes_png_frames() -> AppResult<()> {
+    use clap::Parser;
+
+    let args = crate::args::TesterArgs::try_parse_from(["strest", "--url", "http://localhost"])
+        .map_err(|err| AppError::validation(format!("Failed to parse args: {}", err)))?;
+    let records = vec![MetricRecord {
+        elapsed_ms: 500,
+        latency_ms: 5,
+        status_code: 200,
+        timed_out: false,
+        transport_error: false,
+        response_bytes: 100,
+        in_flight_ops: 1,
+        check_failed: false,
+    }];
+    let dir = tempdir().map_err(|err| AppError::validation(format!("tempdir: {}", err)))?;
+    let out = dir.path().join("frames");
+    let out = out.to_string_lossy();
+
+    let frames = super::video::write_replay_video(&records, &args, 0, 1000, 400, &out)?;
+    if frames != 4 {
+        return Err(AppError::validation(format!(
+            "Expected 4 frames, got {}",
+            frames
+        )));
+    }
+    let last = dir.path().join("frames").join("frame_00004.png");
+    if !last.exists() {
+        return Err(AppError::validation("Expected final PNG frame to exist"));
+    }
+    Ok(())
+}
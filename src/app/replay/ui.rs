@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 use std::time::Duration;
 
-use crate::args::TesterArgs;
+use crate::args::{ExpectedStatus, TesterArgs};
 use crate::error::AppResult;
 use crate::metrics::MetricRecord;
 use crate::ui::model::{ReplayUi, StatusCounts, UiData};
@@ -48,12 +48,26 @@ pub(super) fn render_once(
     let extras = app_summary::SummaryExtras {
         metrics_truncated: false,
         charts_output_path: None,
+        stop_reason: None,
+        abort_reason: None,
         p50,
         p90,
         p99,
         success_p50,
         success_p90,
         success_p99,
+        http_versions: None,
+        status_latency: None,
+        slowest: None,
+        percentile_table: None,
+        drain: None,
+        latency_modes: summary_output.histogram.modes(),
+        max_rate: None,
+        connections: None,
+        http2_streams: None,
+        sse: None,
+        connection_split: None,
+        latency_stats: None,
     };
     for line in app_summary::summary_lines(&summary_output.summary, &extras, &stats, args) {
         println!("{line}");
@@ -82,7 +96,7 @@ pub(super) fn build_ui_data(
 #[expect(clippy::too_many_arguments)]
 pub(crate) fn build_ui_data_with_config(
     records: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     ui_window_ms: u64,
     no_color: bool,
     state: &ReplayWindow,
@@ -94,6 +108,7 @@ pub(crate) fn build_ui_data_with_config(
         summary::summarize(slice, expected_status_code, state.start_ms, state.cursor_ms)?;
     let (p50, p90, p99, p50_ok, p90_ok, p99_ok) =
         summary::compute_replay_percentiles(&summary_output, slice, expected_status_code);
+    let (_, _, _, p999, p9999) = summary_output.histogram.percentiles_extended();
 
     let chart_start = state.cursor_ms.saturating_sub(ui_window_ms);
     let chart_slice = window_slice(records, chart_start, state.cursor_ms);
@@ -198,9 +213,12 @@ pub(crate) fn build_ui_data_with_config(
             bytes_per_sec,
             series: data_series,
         }),
+        error_samples: Vec::new(),
         p50,
         p90,
         p99,
+        p999,
+        p9999,
         p50_ok,
         p90_ok,
         p99_ok,
@@ -211,6 +229,7 @@ pub(crate) fn build_ui_data_with_config(
             window_start_ms: state.start_ms,
             window_end_ms: state.end_ms,
             cursor_ms: state.cursor_ms,
+            speed_percent: state.speed_percent,
             snapshot_start_ms,
             snapshot_end_ms,
         }),
@@ -0,0 +1,221 @@
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color as CellColor;
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult};
+use crate::metrics::MetricRecord;
+use crate::system::replay_compare::{NORMAL_SPEED_PERCENT, PlaybackState};
+use crate::ui::model::UiRenderData;
+use crate::ui::render::{Ui, UiActions};
+
+use super::state::SnapshotMarkers;
+use super::ui::build_ui_data;
+
+/// Dashboard size, in terminal cells, rendered into every frame.
+const VIDEO_COLUMNS: u16 = 120;
+const VIDEO_ROWS: u16 = 36;
+/// Pixel size of one terminal cell.
+const CELL_WIDTH: u32 = 8;
+const CELL_HEIGHT: u32 = 16;
+/// Font size used for cell glyphs.
+const FONT_SIZE: u32 = 14;
+/// Delay between animated GIF frames.
+const GIF_FRAME_DELAY_MS: u32 = 200;
+/// First code point of the Unicode braille block used by ratatui charts.
+const BRAILLE_BASE: u32 = 0x2800;
+const BRAILLE_END: u32 = 0x28ff;
+/// Braille dot bit -> (column, row) inside the 2x4 dot grid.
+const BRAILLE_DOTS: [(u32, u32); 8] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (1, 0),
+    (1, 1),
+    (1, 2),
+    (0, 3),
+    (1, 3),
+];
+const DEFAULT_FG: RGBColor = RGBColor(0xff, 0xff, 0xff);
+const DEFAULT_BG: RGBColor = RGBColor(0x0a, 0x0a, 0x0a);
+
+/// Renders the replay dashboard every `step_ms` from `start_ms` to `end_ms`.
+///
+/// A `.gif` path produces one animated GIF; any other path is treated as a
+/// directory and receives numbered PNG frames. Returns the number of frames.
+pub(super) fn write_replay_video(
+    records: &[MetricRecord],
+    args: &TesterArgs,
+    start_ms: u64,
+    end_ms: u64,
+    step_ms: u64,
+    path: &str,
+) -> AppResult<usize> {
+    let mut terminal = Terminal::new(TestBackend::new(VIDEO_COLUMNS, VIDEO_ROWS))?;
+    let markers = SnapshotMarkers::default();
+    let size = (
+        u32::from(VIDEO_COLUMNS).saturating_mul(CELL_WIDTH),
+        u32::from(VIDEO_ROWS).saturating_mul(CELL_HEIGHT),
+    );
+    let is_gif = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+    let gif_root = if is_gif {
+        let backend = BitMapBackend::gif(path, size, GIF_FRAME_DELAY_MS).map_err(|err| {
+            AppError::from(std::io::Error::other(format!(
+                "Failed to create GIF '{}': {}",
+                path, err
+            )))
+        })?;
+        Some(backend.into_drawing_area())
+    } else {
+        std::fs::create_dir_all(path)?;
+        None
+    };
+
+    let mut frames = 0usize;
+    let mut cursor_ms = start_ms;
+    loop {
+        let state = PlaybackState {
+            start_ms,
+            cursor_ms,
+            end_ms,
+            playing: false,
+            speed_percent: NORMAL_SPEED_PERCENT,
+        };
+        let ui_data = build_ui_data(records, args, &state, &markers, None)?;
+        Ui::render(&mut terminal, &UiRenderData::from(&ui_data));
+        let buffer = terminal.backend().buffer();
+
+        match gif_root.as_ref() {
+            Some(root) => {
+                paint_buffer(root, buffer)?;
+                root.present()?;
+            }
+            None => {
+                let frame_path = Path::new(path)
+                    .join(format!("frame_{:05}.png", frames.saturating_add(1)))
+                    .to_string_lossy()
+                    .into_owned();
+                let root = BitMapBackend::new(&frame_path, size).into_drawing_area();
+                paint_buffer(&root, buffer)?;
+                root.present()?;
+            }
+        }
+        frames = frames.saturating_add(1);
+
+        if cursor_ms >= end_ms {
+            break;
+        }
+        cursor_ms = cursor_ms.saturating_add(step_ms.max(1)).min(end_ms);
+    }
+
+    Ok(frames)
+}
+
+fn paint_buffer(root: &DrawingArea<BitMapBackend<'_>, Shift>, buffer: &Buffer) -> AppResult<()> {
+    root.fill(&DEFAULT_BG)?;
+    let width = buffer.area.width;
+    for (index, cell) in buffer.content.iter().enumerate() {
+        let index = u16::try_from(index).unwrap_or(u16::MAX);
+        let column = i32::from(index.checked_rem(width).unwrap_or(0));
+        let row = i32::from(index.checked_div(width).unwrap_or(0));
+        let left = column.saturating_mul(cell_px(CELL_WIDTH));
+        let top = row.saturating_mul(cell_px(CELL_HEIGHT));
+        let right = left.saturating_add(cell_px(CELL_WIDTH));
+        let bottom = top.saturating_add(cell_px(CELL_HEIGHT));
+
+        if let Some(bg) = to_rgb(cell.bg) {
+            root.draw(&Rectangle::new([(left, top), (right, bottom)], bg.filled()))?;
+        }
+        let fg = to_rgb(cell.fg).unwrap_or(DEFAULT_FG);
+        let Some(glyph) = cell.symbol.chars().next() else {
+            continue;
+        };
+        if glyph == ' ' {
+            continue;
+        }
+        let code = u32::from(glyph);
+        if (BRAILLE_BASE..=BRAILLE_END).contains(&code) {
+            paint_braille(root, code.saturating_sub(BRAILLE_BASE), (left, top), fg)?;
+            continue;
+        }
+        let style = ("monospace", cell_px(FONT_SIZE)).into_font().color(&fg);
+        root.draw_text(&cell.symbol, &style, (left, top))?;
+    }
+    Ok(())
+}
+
+// Fonts rarely carry braille glyphs, so chart dots are painted as pixel blocks.
+fn paint_braille(
+    root: &DrawingArea<BitMapBackend<'_>, Shift>,
+    bits: u32,
+    origin: (i32, i32),
+    color: RGBColor,
+) -> AppResult<()> {
+    let dot_width = cell_px(CELL_WIDTH.checked_div(2).unwrap_or(1));
+    let dot_height = cell_px(CELL_HEIGHT.checked_div(4).unwrap_or(1));
+    for (bit, (dot_column, dot_row)) in BRAILLE_DOTS.iter().enumerate() {
+        let mask = 1u32
+            .checked_shl(u32::try_from(bit).unwrap_or(0))
+            .unwrap_or(0);
+        if bits & mask == 0 {
+            continue;
+        }
+        let left = origin
+            .0
+            .saturating_add(cell_px(*dot_column).saturating_mul(dot_width));
+        let top = origin
+            .1
+            .saturating_add(cell_px(*dot_row).saturating_mul(dot_height));
+        root.draw(&Rectangle::new(
+            [
+                (left, top),
+                (
+                    left.saturating_add(dot_width),
+                    top.saturating_add(dot_height),
+                ),
+            ],
+            color.filled(),
+        ))?;
+    }
+    Ok(())
+}
+
+fn cell_px(value: u32) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
+}
+
+/// Maps a ratatui color to pixels; `None` keeps the default background.
+///
+/// Named colors use a common terminal palette and indexed colors fall back to gray.
+const fn to_rgb(color: CellColor) -> Option<RGBColor> {
+    let (r, g, b) = match color {
+        CellColor::Reset => return None,
+        CellColor::Rgb(r, g, b) => (r, g, b),
+        CellColor::Black => (0x00, 0x00, 0x00),
+        CellColor::Red => (0xcd, 0x31, 0x31),
+        CellColor::Green => (0x0d, 0xbc, 0x79),
+        CellColor::Yellow => (0xe5, 0xe5, 0x10),
+        CellColor::Blue => (0x24, 0x72, 0xc8),
+        CellColor::Magenta => (0xbc, 0x3f, 0xbc),
+        CellColor::Cyan => (0x11, 0xa8, 0xcd),
+        CellColor::Gray => (0xe5, 0xe5, 0xe5),
+        CellColor::DarkGray => (0x66, 0x66, 0x66),
+        CellColor::LightRed => (0xf1, 0x4c, 0x4c),
+        CellColor::LightGreen => (0x23, 0xd1, 0x8b),
+        CellColor::LightYellow => (0xf5, 0xf5, 0x43),
+        CellColor::LightBlue => (0x3b, 0x8e, 0xea),
+        CellColor::LightMagenta => (0xd6, 0x70, 0xd6),
+        CellColor::LightCyan => (0x29, 0xb8, 0xdb),
+        CellColor::White => (0xff, 0xff, 0xff),
+        CellColor::Indexed(index) => (index, index, index),
+    };
+    Some(RGBColor(r, g, b))
+}
@@ -1,5 +1,7 @@
 mod alloc;
+mod baseline;
 mod core;
+mod diagnose;
 mod rss;
 
 pub(crate) use core::run_local;
@@ -1,5 +1,7 @@
+use tokio::sync::mpsc;
 use tracing::warn;
 
+use crate::metrics::LatencySpike;
 use crate::shutdown::ShutdownSender;
 
 #[cfg(feature = "alloc-profiler")]
@@ -77,35 +79,52 @@ pub(in crate::app::runner) fn setup_alloc_profiler_dump_task(
     shutdown_tx: &ShutdownSender,
     interval_ms: Option<&crate::args::PositiveU64>,
     dump_path: &str,
+    spike_rx: Option<mpsc::Receiver<LatencySpike>>,
 ) -> tokio::task::JoinHandle<()> {
-    let Some(interval_ms) = interval_ms.map(|value| value.get()) else {
+    let interval_ms = interval_ms.map(|value| value.get());
+    if interval_ms.is_none() && spike_rx.is_none() {
         return tokio::spawn(async {});
-    };
-    setup_alloc_profiler_dump_task_inner(shutdown_tx, interval_ms, dump_path)
+    }
+    setup_alloc_profiler_dump_task_inner(shutdown_tx, interval_ms, dump_path, spike_rx)
 }
 
 #[cfg(not(feature = "alloc-profiler"))]
 fn setup_alloc_profiler_dump_task_inner(
     _shutdown_tx: &ShutdownSender,
-    _interval_ms: u64,
+    interval_ms: Option<u64>,
     _dump_path: &str,
+    spike_rx: Option<mpsc::Receiver<LatencySpike>>,
 ) -> tokio::task::JoinHandle<()> {
-    warn!("alloc-profiler-dump-ms set but alloc-profiler feature is disabled.");
+    if interval_ms.is_some() {
+        warn!("alloc-profiler-dump-ms set but alloc-profiler feature is disabled.");
+    }
+    if let Some(_spike_rx) = spike_rx {
+        warn!("alloc-profiler-spike-* set but alloc-profiler feature is disabled.");
+    }
     tokio::spawn(async {})
 }
 
+/// Minimum gap between spike-triggered dumps, so a sustained spike does not
+/// flood the dump directory.
+#[cfg(feature = "alloc-profiler")]
+const SPIKE_DUMP_COOLDOWN: Duration = Duration::from_secs(10);
+
 #[cfg(feature = "alloc-profiler")]
 fn setup_alloc_profiler_dump_task_inner(
     shutdown_tx: &ShutdownSender,
-    interval_ms: u64,
+    interval_ms: Option<u64>,
     dump_path: &str,
+    mut spike_rx: Option<mpsc::Receiver<LatencySpike>>,
 ) -> tokio::task::JoinHandle<()> {
     let shutdown_tx = shutdown_tx.clone();
     let dump_path = dump_path.to_owned();
     tokio::spawn(async move {
         let mut shutdown_rx = shutdown_tx.subscribe();
-        let mut interval = tokio::time::interval(Duration::from_millis(interval_ms.max(1)));
+        let periodic = interval_ms.is_some();
+        let mut interval =
+            tokio::time::interval(Duration::from_millis(interval_ms.unwrap_or(1).max(1)));
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut last_spike_dump: Option<tokio::time::Instant> = None;
         if let Err(err) = tokio::fs::create_dir_all(&dump_path).await {
             warn!(
                 "alloc-profiler failed to create dump dir {}: {}",
@@ -114,10 +133,35 @@ fn setup_alloc_profiler_dump_task_inner(
             return;
         }
         loop {
+            let spike = async {
+                match spike_rx.as_mut() {
+                    Some(spike_rx) => spike_rx.recv().await,
+                    None => std::future::pending().await,
+                }
+            };
             tokio::select! {
                 _ = shutdown_rx.recv() => break,
-                _ = interval.tick() => {
-                    if let Err(err) = dump_alloc_profile(&dump_path) {
+                _ = interval.tick(), if periodic => {
+                    if let Err(err) = dump_alloc_profile(&dump_path, "heap") {
+                        warn!("alloc-profiler dump failed: {}", err);
+                        break;
+                    }
+                }
+                maybe_spike = spike => {
+                    let Some(spike_event) = maybe_spike else {
+                        // The collector has finished; keep serving periodic dumps.
+                        spike_rx = None;
+                        continue;
+                    };
+                    if last_spike_dump.is_some_and(|last| last.elapsed() < SPIKE_DUMP_COOLDOWN) {
+                        continue;
+                    }
+                    last_spike_dump = Some(tokio::time::Instant::now());
+                    info!(
+                        "alloc-profiler spike: latency_ms={},in_flight_ops={}",
+                        spike_event.latency_ms, spike_event.in_flight_ops
+                    );
+                    if let Err(err) = dump_alloc_profile(&dump_path, "heap-spike") {
                         warn!("alloc-profiler dump failed: {}", err);
                         break;
                     }
@@ -173,7 +217,7 @@ fn log_alloc_stats() -> AppResult<()> {
 }
 
 #[cfg(feature = "alloc-profiler")]
-fn dump_alloc_profile(dir: &str) -> AppResult<()> {
+fn dump_alloc_profile(dir: &str, prefix: &str) -> AppResult<()> {
     use std::ffi::CString;
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -186,7 +230,7 @@ fn dump_alloc_profile(dir: &str) -> AppResult<()> {
             })
         })?
         .as_millis();
-    let path = std::path::Path::new(dir).join(format!("heap-{}.prof", stamp));
+    let path = std::path::Path::new(dir).join(format!("{}-{}.prof", prefix, stamp));
     let path_cstr = CString::new(path.to_string_lossy().as_bytes()).map_err(|err| {
         AppError::metrics(MetricsError::External {
             context: "invalid dump path",
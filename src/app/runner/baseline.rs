@@ -0,0 +1,167 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::{
+    app::replay::{SnapshotMarkers, build_ui_data_with_config, read_records_from_path},
+    args::{ExpectedStatus, TesterArgs},
+    error::{AppError, AppResult, MetricsError},
+    metrics::MetricRecord,
+    shutdown::ShutdownSender,
+    system::replay_compare::{NORMAL_SPEED_PERCENT, PlaybackState, records_range},
+    ui::{
+        model::{CompareOverlay, UiData},
+        render::setup_render_ui,
+    },
+};
+
+/// How far the live run must advance before the baseline overlay is recomputed.
+const OVERLAY_REFRESH: Duration = Duration::from_millis(500);
+
+/// A recorded run played back in step with the live run as the TUI compare overlay.
+pub(super) struct BaselineOverlay {
+    label: String,
+    records: Vec<MetricRecord>,
+    start_ms: u64,
+    end_ms: u64,
+    expected_status_code: ExpectedStatus,
+    ui_window_ms: u64,
+    no_color: bool,
+}
+
+impl BaselineOverlay {
+    /// Loads the `--baseline` export, if one is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file cannot be read or holds no records.
+    pub(super) async fn load(args: &TesterArgs) -> AppResult<Option<Arc<Self>>> {
+        let Some(path) = args.baseline.as_deref() else {
+            return Ok(None);
+        };
+        let mut records = read_records_from_path(Path::new(path)).await?;
+        records.sort_by_key(|record| record.elapsed_ms);
+        let (start_ms, end_ms) = records_range(&records)
+            .ok_or_else(|| AppError::metrics(MetricsError::ReplayRecordsEmpty))?;
+        let label = args
+            .baseline_label
+            .as_deref()
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map_or_else(|| baseline_label(path), str::to_owned);
+        Ok(Some(Arc::new(Self {
+            label,
+            records,
+            start_ms,
+            end_ms,
+            expected_status_code: args.expected_status_code,
+            ui_window_ms: args.ui_window_ms.get(),
+            no_color: args.no_color,
+        })))
+    }
+
+    /// Baseline metrics up to the same offset into the run as the live `elapsed`.
+    fn overlay_at(&self, elapsed: Duration) -> AppResult<CompareOverlay> {
+        let elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        let state = PlaybackState {
+            start_ms: self.start_ms,
+            cursor_ms: self.start_ms.saturating_add(elapsed_ms).min(self.end_ms),
+            end_ms: self.end_ms,
+            playing: true,
+            speed_percent: NORMAL_SPEED_PERCENT,
+        };
+        let data = build_ui_data_with_config(
+            &self.records,
+            self.expected_status_code,
+            self.ui_window_ms,
+            self.no_color,
+            &state,
+            &SnapshotMarkers::default(),
+            None,
+        )?;
+        Ok(CompareOverlay::from_ui(self.label.clone(), &data))
+    }
+
+    /// Starts the TUI on a channel that mirrors `ui_tx` with the baseline attached as `compare`.
+    pub(super) fn setup_render_ui(
+        self: Arc<Self>,
+        shutdown_tx: &ShutdownSender,
+        ui_tx: &watch::Sender<UiData>,
+        hold_ui: bool,
+    ) -> tokio::task::JoinHandle<()> {
+        let mut live_rx = ui_tx.subscribe();
+        let (display_tx, _) = watch::channel(live_rx.borrow().clone());
+        let render_handle = setup_render_ui(shutdown_tx, &display_tx, hold_ui);
+        tokio::spawn(async move {
+            let mut overlay: Option<CompareOverlay> = None;
+            let mut overlay_elapsed: Option<Duration> = None;
+            while live_rx.changed().await.is_ok() {
+                let mut data = live_rx.borrow_and_update().clone();
+                let stale = overlay_elapsed.is_none_or(|built| {
+                    data.elapsed_time < built
+                        || data.elapsed_time.saturating_sub(built) >= OVERLAY_REFRESH
+                });
+                if stale {
+                    match self.overlay_at(data.elapsed_time) {
+                        Ok(next) => overlay = Some(next),
+                        Err(err) => warn!("Failed to build baseline overlay: {}", err),
+                    }
+                    overlay_elapsed = Some(data.elapsed_time);
+                }
+                data.compare.clone_from(&overlay);
+                if display_tx.send(data).is_err() {
+                    break;
+                }
+            }
+        });
+        render_handle
+    }
+}
+
+fn baseline_label(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .map_or_else(|| "baseline".to_owned(), str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::parse_test_args;
+
+    #[tokio::test]
+    async fn baseline_overlay_tracks_live_elapsed_time() -> AppResult<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("baseline-v1.csv");
+        let mut lines = vec![
+            "elapsed_ms,latency_ms,status_code,timed_out,transport_error,response_bytes,in_flight_ops"
+                .to_owned(),
+        ];
+        for second in 5..15_u64 {
+            lines.push(format!("{},10,200,0,0,0,1", second.saturating_mul(1_000)));
+        }
+        tokio::fs::write(&path, lines.join("\n")).await?;
+        let path = path.to_string_lossy().into_owned();
+        let args = parse_test_args(["strest", "--url", "http://localhost", "--baseline", &path])?;
+
+        let baseline = BaselineOverlay::load(&args)
+            .await?
+            .ok_or_else(|| AppError::validation("Expected a baseline to be loaded"))?;
+        let early = baseline.overlay_at(Duration::from_millis(2_500))?;
+        let late = baseline.overlay_at(Duration::from_secs(60))?;
+        if early.label != "baseline-v1"
+            || early.current_requests != 3
+            || late.current_requests != 10
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected overlay: label={} early={} late={}",
+                early.label, early.current_requests, late.current_requests
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,15 @@
 use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 
-use tracing::info;
+use tracing::{error, info, warn};
 
 use crate::{
-    app::{cleanup, export, logs, summary},
-    args::{OutputFormat, TesterArgs},
+    app::{assertions, cleanup, export, history, logs, notify, summary},
+    args::{OutputFormat, SummaryFormat, TesterArgs, tag_map},
     charts,
-    error::AppResult,
+    error::{AppError, AppResult},
     metrics,
+    run_context::RunContext,
     sinks::{config::SinkStats, writers},
 };
 
@@ -17,6 +19,7 @@ use crate::wasm_plugins::WasmPluginHost;
 
 pub(super) struct FinalizeContext<'args> {
     pub(super) args: &'args TesterArgs,
+    pub(super) run: &'args RunContext,
     pub(super) charts_enabled: bool,
     pub(super) summary_enabled: bool,
     pub(super) metrics_max: usize,
@@ -31,6 +34,7 @@ pub(super) struct FinalizeContext<'args> {
 pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutcome> {
     let FinalizeContext {
         args,
+        run,
         charts_enabled,
         summary_enabled,
         metrics_max,
@@ -43,6 +47,9 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
     } = ctx;
     #[cfg(feature = "wasm")]
     let mut plugin_host = plugin_host;
+    let stop_reason = report.stop_reason.clone();
+    let abort_reason = report.abort_reason.clone();
+    let slowest = args.slowest.is_some().then(|| report.slowest.clone());
     let mut log_results = Vec::new();
     for handle in log_handles {
         match handle.await {
@@ -56,6 +63,45 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         }
     }
 
+    let max_rate = run
+        .rate_feedback
+        .as_ref()
+        .zip(args.target_p99_ms)
+        .map(|(feedback, target)| summary::MaxRateReport {
+            target_p99_ms: target.get(),
+            sustainable_rps: feedback.sustainable_rps(),
+        });
+    let connections = run
+        .connection_counter
+        .as_ref()
+        .zip(args.requests_per_connection)
+        .map(|(counter, limit)| summary::ConnectionReport {
+            opened: counter.load(Ordering::Relaxed),
+            requests_per_connection: limit.get(),
+        });
+    // Unused when HTTP/2 was not enabled, so no streams were counted.
+    let http2_streams = run
+        .http2_multiplex
+        .as_ref()
+        .map(|multiplex| multiplex.report())
+        .filter(|http2| http2.streams > 0)
+        .map(|http2| summary::Http2StreamsReport {
+            parallel: http2.parallel,
+            connections: http2.connections,
+            streams: http2.streams,
+            avg_streams_x100: http2.avg_streams_x100,
+        });
+    let sse = run
+        .sse_stats
+        .as_deref()
+        .map(summary::SseReport::from_stats)
+        .filter(|timing| timing.events > 0);
+    let status_latency = if args.per_status_latency {
+        Some(logs::merge_status_latency(&log_results)?)
+    } else {
+        None
+    };
+    let response_totals = logs::merge_response_totals(&log_results);
     let (
         summary,
         _chart_records_unused,
@@ -65,7 +111,13 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         success_latency_sum_ms,
         success_histogram,
     ) = if !log_results.is_empty() {
-        logs::merge_log_results(log_results, metrics_max)?
+        let merged = logs::merge_log_results(log_results, metrics_max)?;
+        // The collector's report already starts from the checkpoint; the logs
+        // only cover this run.
+        match run.resume_state.as_deref() {
+            Some(resume) => logs::merge_resume_state(merged, resume)?,
+            None => merged,
+        }
     } else {
         (
             report.summary,
@@ -77,6 +129,9 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
             metrics::LatencyHistogram::new()?,
         )
     };
+    let http_versions = args
+        .protocol_version_report
+        .then(|| summary.http_versions.clone());
     let latency_sum_ms = if latency_sum_ms == 0 && summary.total_requests > 0 {
         u128::from(summary.avg_latency_ms).saturating_mul(u128::from(summary.total_requests))
     } else {
@@ -88,8 +143,10 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
     } else {
         success_latency_sum_ms
     };
-    let need_chart_records =
-        args.export_csv.is_some() || args.export_json.is_some() || args.export_jsonl.is_some();
+    let need_chart_records = args.export_csv.is_some()
+        || args.export_json.is_some()
+        || args.export_jsonl.is_some()
+        || args.compat.is_some();
     let (chart_records, metrics_truncated) = if need_chart_records && !log_paths.is_empty() {
         match logs::load_log_records(&log_paths, &args.metrics_range, metrics_max).await {
             Ok((records, truncated)) => (records, truncated),
@@ -102,7 +159,7 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         (Vec::new(), false)
     };
 
-    let (mut p50, mut p90, mut p99) = histogram.percentiles();
+    let (mut p50, mut p90, mut p99, mut p999, mut p9999) = histogram.percentiles_extended();
     let (mut success_p50, mut success_p90, mut success_p99) = success_histogram.percentiles();
     if histogram.count() == 0 && !chart_records.is_empty() {
         let (fallback_p50, fallback_p90, fallback_p99) =
@@ -110,13 +167,15 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         p50 = fallback_p50;
         p90 = fallback_p90;
         p99 = fallback_p99;
+        p999 = summary.max_latency_ms;
+        p9999 = summary.max_latency_ms;
     }
     if success_histogram.count() == 0 && summary.successful_requests > 0 {
         let expected_status = args.expected_status_code;
         let success_records: Vec<metrics::MetricRecord> = chart_records
             .iter()
             .copied()
-            .filter(|record| record.status_code == expected_status)
+            .filter(|record| record.is_success(expected_status))
             .collect();
         let (fallback_p50, fallback_p90, fallback_p99) =
             summary::compute_percentiles(&success_records);
@@ -126,10 +185,10 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
     }
 
     let mut charts_output_path: Option<String> = None;
-    if charts_enabled && !log_paths.is_empty() {
+    if charts_enabled && !args.no_final_charts && !log_paths.is_empty() {
         info!("Plotting charts...");
 
-        match logs::load_chart_data_streaming(
+        let chart_result = match logs::load_chart_data_streaming(
             &log_paths,
             args.expected_status_code,
             &args.metrics_range,
@@ -137,41 +196,132 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         )
         .await
         {
-            Ok(chart_data) => {
-                if let Some(path) = charts::plot_streaming_metrics(&chart_data, args).await? {
-                    info!("Charts saved in {}", path);
-                    #[cfg(feature = "wasm")]
-                    if let Some(host) = plugin_host.as_mut()
-                        && let Err(err) = host.on_artifact("charts", &path)
-                    {
-                        runtime_errors.push(format!("WASM plugin chart hook failed: {}", err));
-                    }
-                    charts_output_path = Some(path);
+            Ok(chart_data) => charts::plot_streaming_metrics(&chart_data, args).await,
+            Err(err) => Err(err),
+        };
+        match chart_result {
+            Ok(Some(path)) => {
+                info!("Charts saved in {}", path);
+                #[cfg(feature = "wasm")]
+                if let Some(host) = plugin_host.as_mut()
+                    && let Err(err) = host.on_artifact("charts", &path)
+                {
+                    runtime_errors.push(format!("WASM plugin chart hook failed: {}", err));
                 }
+                charts_output_path = Some(path);
             }
-            Err(err) => {
+            Ok(None) => {}
+            Err(err) if args.strict_charts => {
                 runtime_errors.push(format!("Failed to build charts: {}", err));
             }
+            Err(err) => error!("Failed to build charts: {}", err),
         }
     }
 
+    let percentile_table = args
+        .summary_percentile_table
+        .then(|| summary::percentile_table(&histogram, &success_histogram));
     let summary_stats = summary::compute_summary_stats(&summary);
+    let latency_modes = histogram.modes();
+    let latency_stats = args
+        .stats_extended
+        .then(|| summary::LatencyStats::from_histogram(&histogram));
+    let assertion_results = assertions::evaluate(args, &summary, &summary_stats, p99);
 
-    if summary_enabled
+    let extras = summary::SummaryExtras {
+        metrics_truncated,
+        charts_output_path,
+        stop_reason,
+        abort_reason: abort_reason.clone(),
+        p50,
+        p90,
+        p99,
+        success_p50,
+        success_p90,
+        success_p99,
+        http_versions,
+        status_latency,
+        slowest,
+        percentile_table,
+        drain: report.drain,
+        latency_modes,
+        max_rate,
+        connections,
+        http2_streams,
+        sse,
+        connection_split: report.connection_split,
+        latency_stats,
+    };
+
+    let print_summary = summary_enabled
         && !args.distributed_silent
-        && args.output_format != Some(OutputFormat::Quiet)
-    {
-        let extras = summary::SummaryExtras {
-            metrics_truncated,
-            charts_output_path: charts_output_path.clone(),
-            p50,
-            p90,
-            p99,
-            success_p50,
-            success_p90,
-            success_p99,
+        && args.output_format != Some(OutputFormat::Quiet);
+    if print_summary || args.summary_out.is_some() {
+        let rendered = match (args.compat, args.summary_template.as_deref()) {
+            (Some(format), _) => {
+                let input = summary::CompatInput {
+                    summary: &summary,
+                    stats: &summary_stats,
+                    histogram: &histogram,
+                    totals: &response_totals,
+                    records: &chart_records,
+                    args,
+                };
+                Some(summary::compat_lines(format, &input).join("\n"))
+            }
+            (None, Some(path)) => match summary::SummaryTemplate::load(path).await {
+                Ok(template) => Some(template.render(&summary, &extras, &summary_stats)),
+                Err(err) => {
+                    runtime_errors.push(format!("Failed to render summary template: {}", err));
+                    None
+                }
+            },
+            (None, None) if args.summary_format == Some(SummaryFormat::Plain) => {
+                Some(summary::plain_lines(&summary, &extras, &summary_stats).join("\n"))
+            }
+            (None, None) => {
+                Some(summary::summary_lines(&summary, &extras, &summary_stats, args).join("\n"))
+            }
+        };
+        if let Some(text) = rendered {
+            if print_summary {
+                println!("{}", text);
+            }
+            if let Some(path) = args.summary_out.as_deref()
+                && let Err(err) = write_summary_out(path, &text).await
+            {
+                runtime_errors.push(format!("Failed to write summary file: {}", err));
+            }
+        }
+    }
+
+    if !args.no_history && !args.distributed_silent {
+        let entry = history::HistoryEntry {
+            key: history::run_key(args),
+            timestamp_ms: history::now_ms(),
+            target: history::run_target(args),
+            total_requests: summary.total_requests,
+            error_requests: summary.error_requests,
+            avg_rps_x100: summary_stats.avg_rps_x100,
+            success_rate_x100: summary_stats.success_rate_x100,
+            p50_ms: p50,
+            p99_ms: p99,
         };
-        summary::print_summary(&summary, &extras, &summary_stats, args);
+        match history::record(Path::new(&args.history_file), &entry).await {
+            Ok(Some(previous)) => {
+                let line = history::delta_line(&previous, &entry);
+                if print_summary {
+                    println!("{}", line);
+                } else {
+                    info!("{}", line);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => warn!(
+                "Failed to update run history {}: {}",
+                args.history_file, err
+            ),
+        }
     }
 
     if let Some(path) = args.output.as_deref()
@@ -179,25 +329,41 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
             args.output_format,
             Some(OutputFormat::Text | OutputFormat::Quiet)
         )
-        && let Err(err) = export_text_summary(
+        && let Err(err) = export_text_summary(path, &summary, &summary_stats, args, &extras).await
+    {
+        runtime_errors.push(format!("Failed to write output: {}", err));
+    }
+
+    if let Some(path) = args.output.as_deref()
+        && args.output_format == Some(OutputFormat::Junit)
+        && let Err(err) = tokio::fs::write(
             path,
-            &summary,
-            &summary_stats,
-            args,
-            &summary::SummaryExtras {
-                metrics_truncated,
-                charts_output_path: charts_output_path.clone(),
-                p50,
-                p90,
-                p99,
-                success_p50,
-                success_p90,
-                success_p99,
-            },
+            assertions::junit_xml(&assertion_results, summary.duration),
         )
         .await
     {
-        runtime_errors.push(format!("Failed to write output: {}", err));
+        runtime_errors.push(format!("Failed to write JUnit report: {}", err));
+    }
+
+    if let Some(path) = args.output.as_deref()
+        && args.output_format == Some(OutputFormat::Tsv)
+    {
+        let tsv_result = match logs::load_chart_data_streaming(
+            &log_paths,
+            args.expected_status_code,
+            &args.metrics_range,
+            export::TSV_BUCKET_MS,
+        )
+        .await
+        {
+            Ok(series) => export::export_tsv(path, &series)
+                .await
+                .map_err(AppError::from),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = tsv_result {
+            runtime_errors.push(format!("Failed to export TSV: {}", err));
+        }
     }
 
     if let Some(path) = args.export_csv.as_deref()
@@ -214,8 +380,27 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         }
     }
 
+    let tags = tag_map(&args.tags);
+    let export_extras = export::ExportExtras {
+        http_versions: extras.http_versions.as_ref(),
+        tags: &tags,
+        sse,
+    };
     if let Some(path) = args.export_json.as_deref()
-        && let Err(err) = export::export_json(path, &summary, &chart_records).await
+        && let Err(err) = export::export_json(
+            path,
+            &summary,
+            export::ExportPercentiles {
+                p50,
+                p90,
+                p99,
+                p999,
+                p9999,
+            },
+            &chart_records,
+            export_extras,
+        )
+        .await
     {
         runtime_errors.push(format!("Failed to export JSON: {}", err));
     } else {
@@ -229,7 +414,7 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
     }
 
     if let Some(path) = args.export_jsonl.as_deref()
-        && let Err(err) = export::export_jsonl(path, &summary, &chart_records).await
+        && let Err(err) = export::export_jsonl(path, &summary, &chart_records, export_extras).await
     {
         runtime_errors.push(format!("Failed to export JSONL: {}", err));
     } else {
@@ -242,6 +427,20 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         }
     }
 
+    if let Some(path) = args.export_histogram.as_deref()
+        && let Err(err) = export::export_histogram(path, &histogram, summary.duration).await
+    {
+        runtime_errors.push(format!("Failed to export histogram: {}", err));
+    } else {
+        #[cfg(feature = "wasm")]
+        if let Some(path) = args.export_histogram.as_deref()
+            && let Some(host) = plugin_host.as_mut()
+            && let Err(err) = host.on_artifact("export_histogram", path)
+        {
+            runtime_errors.push(format!("WASM plugin histogram hook failed: {}", err));
+        }
+    }
+
     if let Some(sinks_config) = args.sinks.as_ref() {
         let sink_stats = SinkStats {
             duration: summary.duration,
@@ -255,11 +454,21 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
             p50_latency_ms: p50,
             p90_latency_ms: p90,
             p99_latency_ms: p99,
+            p999_latency_ms: p999,
+            p9999_latency_ms: p9999,
             success_rate_x100: summary_stats.success_rate_x100,
             avg_rps_x100: summary_stats.avg_rps_x100,
             avg_rpm_x100: summary_stats.avg_rpm_x100,
         };
-        if let Err(err) = writers::write_sinks(sinks_config, &sink_stats).await {
+        if let Err(err) = writers::write_sinks(
+            sinks_config,
+            &sink_stats,
+            &writers::sink_run_id(args.run_id.as_deref()),
+            args.protocol.as_str(),
+            &tag_map(&args.tags),
+        )
+        .await
+        {
             runtime_errors.push(format!("Failed to write sinks: {}", err));
         }
     }
@@ -282,6 +491,34 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         }
     }
 
+    let threshold_failures: Vec<String> = assertion_results
+        .iter()
+        .filter(|result| !result.passed)
+        .map(assertions::AssertionResult::message)
+        .collect();
+
+    if args.notify_webhook.is_some() {
+        let mut failures = Vec::new();
+        if !runtime_errors.is_empty() {
+            failures.push(format!("{} runtime error(s)", runtime_errors.len()));
+        }
+        if let Some(reason) = abort_reason.as_deref() {
+            failures.push(format!("aborted: {}", reason));
+        }
+        failures.extend(threshold_failures.iter().cloned());
+        if summary.transport_errors > 0 {
+            failures.push(format!("{} transport error(s)", summary.transport_errors));
+        }
+        let notice = notify::RunNotice {
+            summary: &summary,
+            stats: &summary_stats,
+            p99,
+            assertions: &assertion_results,
+            failures,
+        };
+        notify::notify_run_end(args, &notice).await;
+    }
+
     Ok(RunOutcome {
         summary,
         histogram,
@@ -289,9 +526,19 @@ pub(super) async fn finalize_run(ctx: FinalizeContext<'_>) -> AppResult<RunOutco
         latency_sum_ms,
         success_latency_sum_ms,
         runtime_errors,
+        abort_reason,
+        threshold_failures,
     })
 }
 
+async fn write_summary_out(path: &str, text: &str) -> Result<(), std::io::Error> {
+    let mut content = text.to_owned();
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    tokio::fs::write(path, content).await
+}
+
 async fn export_text_summary(
     path: &str,
     summary: &metrics::MetricsSummary,
@@ -303,7 +550,11 @@ async fn export_text_summary(
         tokio::fs::write(path, "").await?;
         return Ok(());
     }
-    let lines = summary::summary_lines(summary, extras, stats, args);
+    let lines = if args.summary_format == Some(SummaryFormat::Plain) {
+        summary::plain_lines(summary, extras, stats)
+    } else {
+        summary::summary_lines(summary, extras, stats, args)
+    };
     let content = lines.join("\n");
     tokio::fs::write(path, content).await?;
     Ok(())
@@ -1,22 +1,27 @@
 mod finalize;
 
 use std::io::IsTerminal;
-#[cfg(feature = "wasm")]
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
 
 use crate::{
-    app::{logs, progress},
+    app::{logs, progress, summary},
     application::local_run::{
         self, FinalizeRunInput, LocalRunExecutionCommand, LocalRunSettings, MetricsCollectorInput,
         MetricsPort, OutputPort, ShutdownPort, TrafficPort,
     },
-    args::TesterArgs,
+    args::{SummaryFormat, TesterArgs},
     domain::run::ProtocolKind,
-    error::{AppError, AppResult},
+    error::{AppError, AppResult, ValidationError},
+    http,
     metrics::{self, Metrics},
     protocol,
+    run_context::RunContext,
     shutdown::{ShutdownReceiver, ShutdownSender},
+    sinks::{
+        checkpoint::{self, ResumeState},
+        samples::SampleStream,
+    },
     system::shutdown_handlers,
     ui::{model::UiData, render::setup_render_ui},
 };
@@ -30,6 +35,8 @@ use crate::error::ScriptError;
 use crate::wasm_plugins::WasmPluginHost;
 
 use super::alloc::{setup_alloc_profiler_dump_task, setup_alloc_profiler_task};
+use super::baseline::BaselineOverlay;
+use super::diagnose::run_pre_run_diagnostics;
 use super::rss::setup_rss_log_task;
 use finalize::{FinalizeContext, finalize_run as finalize_local_run};
 
@@ -40,14 +47,47 @@ pub(crate) async fn run_local(
     stream_tx: Option<mpsc::UnboundedSender<metrics::StreamSnapshot>>,
     external_shutdown: Option<watch::Receiver<bool>>,
 ) -> AppResult<RunOutcome> {
+    let mut run = RunContext::default();
+    if urls_from_stdin(&args) {
+        run.stdin_urls = Some(Arc::from(http::read_stdin_urls().await?));
+    }
+    if args.diagnose || args.diagnose_strict {
+        run_pre_run_diagnostics(&args, &run).await?;
+    }
+    if let Some(path) = args.summary_template.as_deref() {
+        summary::SummaryTemplate::load(path).await?;
+    }
+    run.rate_feedback = resolve_rate_feedback(&args)?;
+    if args.requests_per_connection.is_some() {
+        run.connection_counter = Some(Arc::default());
+    }
+    if args.http2_parallel.get() > 1 {
+        run.http2_multiplex = Some(Arc::new(http::Http2Multiplex::new(
+            args.http2_parallel.get(),
+            args.max_tasks.get(),
+        )));
+    }
+    if matches!(args.protocol.to_domain(), ProtocolKind::Sse) {
+        run.sse_stats = Some(Arc::default());
+    }
+    run.resume_state = resolve_resume_state(&args).await?;
+    // Opened up front so an unwritable path fails the run before any traffic.
+    let sample_stream = match args.stream_samples.as_deref() {
+        Some(path) => Some(SampleStream::open(path).await?),
+        None => None,
+    };
+    let baseline = BaselineOverlay::load(&args).await?;
     let protocol = args.protocol.to_domain();
     let settings = local_run_settings(&args);
     let command =
         LocalRunExecutionCommand::new(protocol, settings, args, stream_tx, external_shutdown);
     let shutdown_adapter = RuntimeShutdownAdapter;
-    let traffic_adapter = RuntimeTrafficAdapter;
-    let metrics_adapter = RuntimeMetricsAdapter;
-    let output_adapter = RuntimeOutputAdapter::new();
+    let traffic_adapter = RuntimeTrafficAdapter { run: run.clone() };
+    let metrics_adapter = RuntimeMetricsAdapter {
+        run: run.clone(),
+        sample_stream: Mutex::new(sample_stream),
+    };
+    let output_adapter = RuntimeOutputAdapter::new(baseline, run);
 
     local_run::execute(
         command,
@@ -59,13 +99,66 @@ pub(crate) async fn run_local(
     .await
 }
 
+/// Creates the handle the collector and `--find-max-rate` controller share.
+fn resolve_rate_feedback(args: &TesterArgs) -> AppResult<Option<Arc<metrics::RateFeedback>>> {
+    if !args.find_max_rate {
+        return Ok(None);
+    }
+    if args.target_p99_ms.is_none() {
+        return Err(AppError::validation(
+            ValidationError::FindMaxRateRequiresTarget,
+        ));
+    }
+    if args.load_profile.is_some() {
+        return Err(AppError::validation(
+            ValidationError::FindMaxRateLoadProfileConflict,
+        ));
+    }
+    Ok(Some(Arc::new(metrics::RateFeedback::default())))
+}
+
+async fn resolve_resume_state(args: &TesterArgs) -> AppResult<Option<Arc<ResumeState>>> {
+    if !args.resume {
+        return Ok(None);
+    }
+    let Some(path) = args.checkpoint_out.as_deref() else {
+        return Err(AppError::validation(
+            ValidationError::ResumeRequiresCheckpoint,
+        ));
+    };
+    let state = checkpoint::load_resume_state(path).await?;
+    let target_secs = args.target_duration.get();
+    let elapsed_secs = state.duration().as_secs();
+    if elapsed_secs >= target_secs {
+        return Err(AppError::validation(
+            ValidationError::ResumeCheckpointCoversDuration {
+                elapsed_secs,
+                target_secs,
+            },
+        ));
+    }
+    tracing::info!(
+        "Resuming from checkpoint '{}': {} requests over {}s",
+        path,
+        state.total_requests,
+        elapsed_secs
+    );
+    Ok(Some(Arc::new(state)))
+}
+
 fn local_run_settings(args: &TesterArgs) -> LocalRunSettings {
     LocalRunSettings {
         no_color: args.no_color,
         no_ui: args.no_ui,
         no_splash: args.no_splash,
+        hold_ui: args.hold_ui,
+        stop_file: args.stop_file.as_ref().map(std::path::PathBuf::from),
         no_charts: args.no_charts,
-        summary: args.summary,
+        summary: args.summary
+            || args.summary_template.is_some()
+            || args.summary_out.is_some()
+            || args.compat.is_some()
+            || args.summary_format == Some(SummaryFormat::Plain),
         show_selections: args.show_selections,
         verbose: args.verbose,
         target_duration_secs: args.target_duration.get(),
@@ -77,10 +170,18 @@ fn local_run_settings(args: &TesterArgs) -> LocalRunSettings {
             .as_ref()
             .map(|value| value.get()),
         alloc_profiler_dump_path: args.alloc_profiler_dump_path.clone(),
+        alloc_profiler_spike: args.alloc_profiler_spike_ms.is_some()
+            || args.alloc_profiler_spike_inflight.is_some(),
         metrics_max: args.metrics_max.get(),
+        urls_from_stdin: urls_from_stdin(args),
     }
 }
 
+/// `--urls-from-file -u -`: the URL list is piped in on stdin.
+fn urls_from_stdin(args: &TesterArgs) -> bool {
+    args.urls_from_file && args.url.as_deref() == Some(http::STDIN_URL_PATH)
+}
+
 struct RuntimeShutdownAdapter;
 
 impl ShutdownPort for RuntimeShutdownAdapter {
@@ -122,9 +223,19 @@ impl ShutdownPort for RuntimeShutdownAdapter {
     ) -> tokio::task::JoinHandle<()> {
         shutdown_handlers::setup_signal_shutdown_handler(shutdown_tx)
     }
+
+    fn setup_stop_file_shutdown_handler(
+        &self,
+        shutdown_tx: &ShutdownSender,
+        path: std::path::PathBuf,
+    ) -> tokio::task::JoinHandle<()> {
+        shutdown_handlers::setup_stop_file_shutdown_handler(shutdown_tx, path)
+    }
 }
 
-struct RuntimeTrafficAdapter;
+struct RuntimeTrafficAdapter {
+    run: RunContext,
+}
 
 impl TrafficPort<TesterArgs> for RuntimeTrafficAdapter {
     fn setup_request_sender(
@@ -135,11 +246,21 @@ impl TrafficPort<TesterArgs> for RuntimeTrafficAdapter {
         metrics_tx: &mpsc::Sender<Metrics>,
         log_sink: Option<&std::sync::Arc<metrics::LogSink>>,
     ) -> AppResult<tokio::task::JoinHandle<()>> {
-        protocol::setup_request_sender(protocol, adapter_args, shutdown_tx, metrics_tx, log_sink)
+        protocol::setup_request_sender(
+            protocol,
+            adapter_args,
+            &self.run,
+            shutdown_tx,
+            metrics_tx,
+            log_sink,
+        )
     }
 }
 
-struct RuntimeMetricsAdapter;
+struct RuntimeMetricsAdapter {
+    run: RunContext,
+    sample_stream: Mutex<Option<SampleStream>>,
+}
 
 impl MetricsPort<TesterArgs> for RuntimeMetricsAdapter {
     fn setup_metrics_collector(
@@ -153,27 +274,40 @@ impl MetricsPort<TesterArgs> for RuntimeMetricsAdapter {
             metrics_rx,
             ui_tx,
             stream_tx,
+            spike_tx,
             ..
         } = input;
+        let sample_stream = self
+            .sample_stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
         metrics::setup_metrics_collector(
             adapter_args,
+            &self.run,
+            sample_stream,
             run_start,
             shutdown_tx,
             metrics_rx,
             ui_tx,
             stream_tx,
+            spike_tx,
         )
     }
 }
 
 struct RuntimeOutputAdapter {
+    baseline: Option<Arc<BaselineOverlay>>,
+    run: RunContext,
     #[cfg(feature = "wasm")]
     plugin_host: Mutex<Option<WasmPluginHost>>,
 }
 
 impl RuntimeOutputAdapter {
-    const fn new() -> Self {
+    const fn new(baseline: Option<Arc<BaselineOverlay>>, run: RunContext) -> Self {
         Self {
+            baseline,
+            run,
             #[cfg(feature = "wasm")]
             plugin_host: Mutex::new(None),
         }
@@ -183,6 +317,8 @@ impl RuntimeOutputAdapter {
 #[async_trait]
 impl OutputPort<TesterArgs> for RuntimeOutputAdapter {
     fn prepare_run(&self, adapter_args: &TesterArgs) -> AppResult<()> {
+        crate::app::echo_run_config(adapter_args);
+
         #[cfg(feature = "wasm")]
         {
             let mut plugin_host = WasmPluginHost::from_paths(&adapter_args.plugin)?;
@@ -249,9 +385,10 @@ impl OutputPort<TesterArgs> for RuntimeOutputAdapter {
         shutdown_tx: &ShutdownSender,
         interval_ms: Option<u64>,
         dump_path: &str,
+        spike_rx: Option<mpsc::Receiver<metrics::LatencySpike>>,
     ) -> tokio::task::JoinHandle<()> {
         let interval = interval_ms.and_then(|value| crate::args::PositiveU64::try_from(value).ok());
-        setup_alloc_profiler_dump_task(shutdown_tx, interval.as_ref(), dump_path)
+        setup_alloc_profiler_dump_task(shutdown_tx, interval.as_ref(), dump_path, spike_rx)
     }
 
     async fn setup_log_sinks(
@@ -274,8 +411,12 @@ impl OutputPort<TesterArgs> for RuntimeOutputAdapter {
         &self,
         shutdown_tx: &ShutdownSender,
         ui_tx: &watch::Sender<UiData>,
+        hold_ui: bool,
     ) -> tokio::task::JoinHandle<()> {
-        setup_render_ui(shutdown_tx, ui_tx)
+        self.baseline.clone().map_or_else(
+            || setup_render_ui(shutdown_tx, ui_tx, hold_ui),
+            |baseline| baseline.setup_render_ui(shutdown_tx, ui_tx, hold_ui),
+        )
     }
 
     fn setup_progress_indicator(
@@ -313,6 +454,7 @@ impl OutputPort<TesterArgs> for RuntimeOutputAdapter {
 
         let outcome = finalize_local_run(FinalizeContext {
             args: adapter_args,
+            run: &self.run,
             charts_enabled,
             summary_enabled,
             metrics_max,
@@ -0,0 +1,24 @@
+use crate::{
+    args::TesterArgs,
+    error::{AppError, AppResult, HttpError},
+    http,
+    run_context::RunContext,
+};
+
+/// Prints the pre-run connectivity report and enforces `--diagnose-strict`.
+pub(in crate::app::runner) async fn run_pre_run_diagnostics(
+    args: &TesterArgs,
+    run: &RunContext,
+) -> AppResult<()> {
+    let report = http::run_diagnostics(args, run).await;
+    for line in report.lines() {
+        println!("{}", line);
+    }
+    println!();
+
+    let failures = report.failures();
+    if args.diagnose_strict && failures > 0 {
+        return Err(AppError::http(HttpError::DiagnosticsFailed { failures }));
+    }
+    Ok(())
+}
@@ -1,11 +1,21 @@
+mod compat;
 mod lines;
 mod percentiles;
+mod plain;
+mod template;
+
+use std::collections::BTreeMap;
 
-use crate::args::TesterArgs;
 use crate::metrics;
 
-pub(crate) use lines::summary_lines;
-pub(crate) use percentiles::compute_percentiles;
+pub(crate) use compat::{CompatInput, ResponseTotals, compat_lines};
+pub(crate) use lines::{
+    check_failure_lines, error_reasons_line, http_versions_line, latency_stats_line, summary_lines,
+    tags_line,
+};
+pub(crate) use percentiles::{compute_percentiles, percentile_table};
+pub(crate) use plain::plain_lines;
+pub(crate) use template::SummaryTemplate;
 
 /// Minimum non-zero duration used to avoid divide-by-zero.
 const MIN_DURATION_MS: u128 = 1;
@@ -21,12 +31,121 @@ const RPM_PER_RPS: u64 = 60;
 pub(crate) struct SummaryExtras {
     pub(crate) metrics_truncated: bool,
     pub(crate) charts_output_path: Option<String>,
+    pub(crate) stop_reason: Option<String>,
+    /// Why an `--abort-on-*` circuit breaker ended the run.
+    pub(crate) abort_reason: Option<String>,
     pub(crate) p50: u64,
     pub(crate) p90: u64,
     pub(crate) p99: u64,
     pub(crate) success_p50: u64,
     pub(crate) success_p90: u64,
     pub(crate) success_p99: u64,
+    /// Responses per negotiated HTTP version, when `--protocol-version-report` is set.
+    pub(crate) http_versions: Option<BTreeMap<&'static str, u64>>,
+    /// Latency percentiles per status class, when `--per-status-latency` is set.
+    pub(crate) status_latency: Option<Vec<StatusLatency>>,
+    /// Highest-latency requests, slowest first, when `--slowest` is set.
+    pub(crate) slowest: Option<Vec<metrics::SlowRequest>>,
+    /// Fine-grained percentiles, when `--summary-percentile-table` is set.
+    pub(crate) percentile_table: Option<Vec<PercentileRow>>,
+    /// Late completions and abandoned requests, when
+    /// `--wait-ongoing-requests-after-deadline` is set.
+    pub(crate) drain: Option<metrics::DrainReport>,
+    /// Peaks of the latency histogram; two or more mean a single percentile
+    /// hides part of the distribution.
+    pub(crate) latency_modes: Vec<metrics::LatencyMode>,
+    /// Outcome of the `--find-max-rate` search.
+    pub(crate) max_rate: Option<MaxRateReport>,
+    /// Connections opened, when `--requests-per-connection` is set.
+    pub(crate) connections: Option<ConnectionReport>,
+    /// Streams per connection, when `--http2-parallel` multiplexed requests.
+    pub(crate) http2_streams: Option<Http2StreamsReport>,
+    /// Event timing, when `--protocol sse` received any event.
+    pub(crate) sse: Option<SseReport>,
+    /// Connect, first-byte and total latency by new vs reused connection,
+    /// when `--connection-timing` is set.
+    pub(crate) connection_split: Option<metrics::ConnectionSplit>,
+    /// Geometric mean and standard deviation, when `--stats-extended` is set.
+    pub(crate) latency_stats: Option<LatencyStats>,
+}
+
+/// Latency shape statistics from the histogram, in hundredths of a millisecond.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LatencyStats {
+    pub(crate) geomean_x100: u64,
+    pub(crate) stddev_x100: u64,
+}
+
+impl LatencyStats {
+    pub(crate) fn from_histogram(histogram: &metrics::LatencyHistogram) -> Self {
+        Self {
+            geomean_x100: histogram.geomean_x100(),
+            stddev_x100: histogram.stddev_x100(),
+        }
+    }
+}
+
+/// Connections the workers opened under `--requests-per-connection`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ConnectionReport {
+    pub(crate) opened: u64,
+    pub(crate) requests_per_connection: u64,
+}
+
+/// Streams per connection under `--http2-parallel`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Http2StreamsReport {
+    pub(crate) parallel: usize,
+    pub(crate) connections: u64,
+    pub(crate) streams: u64,
+    pub(crate) avg_streams_x100: u64,
+}
+
+/// Event counts and timing for `--protocol sse`, in microseconds.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SseReport {
+    pub(crate) events: u64,
+    pub(crate) connections: u64,
+    pub(crate) avg_first_event_us: u64,
+    pub(crate) avg_inter_event_us: u64,
+}
+
+impl SseReport {
+    pub(crate) fn from_stats(stats: &crate::protocol::SseStats) -> Self {
+        let micros =
+            |elapsed: std::time::Duration| u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        Self {
+            events: stats.events(),
+            connections: stats.connections(),
+            avg_first_event_us: micros(stats.avg_first_event()),
+            avg_inter_event_us: micros(stats.avg_inter_event()),
+        }
+    }
+}
+
+/// Highest rate that held `--target-p99-ms` during a `--find-max-rate` run.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MaxRateReport {
+    pub(crate) target_p99_ms: u64,
+    /// `None` when p99 breached the target at every rate tried.
+    pub(crate) sustainable_rps: Option<u64>,
+}
+
+/// One row of the `--summary-percentile-table` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PercentileRow {
+    pub(crate) label: &'static str,
+    pub(crate) all: u64,
+    pub(crate) ok: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StatusLatency {
+    pub(crate) class: metrics::StatusClass,
+    pub(crate) count: u64,
+    pub(crate) p50: u64,
+    pub(crate) p90: u64,
+    pub(crate) p99: u64,
 }
 
 pub(crate) struct SummaryStats {
@@ -67,14 +186,3 @@ pub(crate) fn compute_summary_stats(summary: &metrics::MetricsSummary) -> Summar
         avg_rpm_x100,
     }
 }
-
-pub(crate) fn print_summary(
-    summary: &metrics::MetricsSummary,
-    extras: &SummaryExtras,
-    stats: &SummaryStats,
-    args: &TesterArgs,
-) {
-    for line in summary_lines(summary, extras, stats, args) {
-        println!("{}", line);
-    }
-}
@@ -0,0 +1,587 @@
+use std::collections::BTreeMap;
+
+use crate::args::{CompatFormat, TesterArgs};
+use crate::metrics;
+
+use super::SummaryStats;
+
+/// Milliseconds per second.
+const MS_PER_SEC: u64 = 1_000;
+/// Microseconds per millisecond.
+const US_PER_MS: u128 = 1_000;
+/// Microseconds per second.
+const US_PER_SEC: u128 = 1_000_000;
+/// Scale for two-decimal fixed-point values.
+const X100: u128 = 100;
+/// Scale for percentages with two decimals (100% = 10_000).
+const PERCENT_X100: u128 = 10_000;
+/// Binary size units used by wrk.
+const KIB: u128 = 1_024;
+const MIB: u128 = 1_048_576;
+const GIB: u128 = 1_073_741_824;
+/// Percentiles printed by wrk's `--latency` block, as `(label, quantile)`.
+const WRK_PERCENTILES: [(u64, f64); 4] = [(50, 0.5), (75, 0.75), (90, 0.9), (99, 0.99)];
+/// Percentiles printed by hey's latency distribution, as `(label, quantile)`.
+const HEY_PERCENTILES: [(u64, f64); 7] = [
+    (10, 0.1),
+    (25, 0.25),
+    (50, 0.5),
+    (75, 0.75),
+    (90, 0.9),
+    (95, 0.95),
+    (99, 0.99),
+];
+/// Buckets in hey's response time histogram.
+const HEY_HISTOGRAM_BUCKETS: u64 = 10;
+/// Width of the longest hey histogram bar.
+const HEY_BAR_WIDTH: u64 = 40;
+
+/// Response totals over every logged request, unaffected by `--metrics-max`
+/// and `--metrics-range`.
+#[derive(Debug, Default)]
+pub(crate) struct ResponseTotals {
+    pub(crate) bytes: u128,
+    /// Responses per status code, excluding timeouts and transport errors.
+    pub(crate) status_codes: BTreeMap<u16, u64>,
+}
+
+/// Inputs shared by the compat formatters.
+pub(crate) struct CompatInput<'run> {
+    pub(crate) summary: &'run metrics::MetricsSummary,
+    pub(crate) stats: &'run SummaryStats,
+    /// Latency of every request; source of all latency figures.
+    pub(crate) histogram: &'run metrics::LatencyHistogram,
+    pub(crate) totals: &'run ResponseTotals,
+    /// Loaded records, which `--metrics-max` and `--metrics-range` may cut
+    /// short; only used for the wrk Req/Sec spread.
+    pub(crate) records: &'run [metrics::MetricRecord],
+    pub(crate) args: &'run TesterArgs,
+}
+
+/// Renders the final summary in the layout of the selected tool.
+pub(crate) fn compat_lines(format: CompatFormat, input: &CompatInput<'_>) -> Vec<String> {
+    match format {
+        CompatFormat::Wrk => wrk_lines(input),
+        CompatFormat::Hey => hey_lines(input),
+    }
+}
+
+/// Mean, standard deviation, max, and share of samples within one deviation.
+struct Spread {
+    mean: u128,
+    stdev: u128,
+    max: u128,
+    within_stdev_x100: u128,
+}
+
+fn spread(values: &[u128]) -> Spread {
+    let count = u128::try_from(values.len()).unwrap_or(u128::MAX);
+    let sum = values
+        .iter()
+        .fold(0u128, |acc, value| acc.saturating_add(*value));
+    let mean = sum.checked_div(count).unwrap_or(0);
+    let variance = values
+        .iter()
+        .fold(0u128, |acc, value| {
+            let diff = value.abs_diff(mean);
+            acc.saturating_add(diff.saturating_mul(diff))
+        })
+        .checked_div(count)
+        .unwrap_or(0);
+    let stdev = variance.isqrt();
+    let within = values
+        .iter()
+        .filter(|value| value.abs_diff(mean) <= stdev)
+        .count();
+    let within_stdev_x100 = u128::try_from(within)
+        .unwrap_or(u128::MAX)
+        .saturating_mul(PERCENT_X100)
+        .checked_div(count)
+        .unwrap_or(0);
+    Spread {
+        mean,
+        stdev,
+        max: values.iter().copied().max().unwrap_or(0),
+        within_stdev_x100,
+    }
+}
+
+/// Latency spread in microseconds: the mean comes from the summary, the
+/// deviation and the share within it from the histogram.
+fn latency_spread(
+    summary: &metrics::MetricsSummary,
+    histogram: &metrics::LatencyHistogram,
+) -> Spread {
+    let mean = u128::from(summary.avg_latency_ms).saturating_mul(US_PER_MS);
+    let stdev = u128::from(histogram.stddev_x100()).saturating_mul(US_PER_MS) / X100;
+    let low_ms = mean.saturating_sub(stdev).div_ceil(US_PER_MS);
+    let high_ms = mean.saturating_add(stdev) / US_PER_MS;
+    let within = histogram.count_between(
+        u64::try_from(low_ms).unwrap_or(u64::MAX),
+        u64::try_from(high_ms).unwrap_or(u64::MAX),
+    );
+    Spread {
+        mean,
+        stdev,
+        max: u128::from(summary.max_latency_ms).saturating_mul(US_PER_MS),
+        within_stdev_x100: u128::from(within)
+            .saturating_mul(PERCENT_X100)
+            .checked_div(u128::from(histogram.count()))
+            .unwrap_or(0),
+    }
+}
+
+fn per_second(value: u128, duration_ms: u128) -> u128 {
+    value
+        .saturating_mul(u128::from(MS_PER_SEC))
+        .checked_div(duration_ms.max(1))
+        .unwrap_or(0)
+}
+
+fn wrk_lines(input: &CompatInput<'_>) -> Vec<String> {
+    let CompatInput {
+        summary,
+        stats,
+        histogram,
+        totals,
+        records,
+        args,
+    } = input;
+    let duration_ms = summary.duration.as_millis();
+    let latency = latency_spread(summary, histogram);
+    let req_per_sec = spread(&requests_per_second_x100(records));
+    let bytes = totals.bytes;
+
+    let mut lines = vec![
+        format!(
+            "Running {}s test @ {}",
+            summary.duration.as_secs(),
+            args.url.as_deref().unwrap_or("<scenario>")
+        ),
+        format!("  1 threads and {} connections", args.max_tasks.get()),
+        "  Thread Stats   Avg      Stdev     Max   +/- Stdev".to_owned(),
+        format!(
+            "    Latency   {:>8} {:>8} {:>8} {:>8}",
+            format_wrk_latency(latency.mean),
+            format_wrk_latency(latency.stdev),
+            format_wrk_latency(latency.max),
+            format_x100_percent(latency.within_stdev_x100)
+        ),
+        format!(
+            "    Req/Sec   {:>8} {:>8} {:>8} {:>8}",
+            format_wrk_count_x100(req_per_sec.mean),
+            format_wrk_count_x100(req_per_sec.stdev),
+            format_wrk_count_x100(req_per_sec.max),
+            format_x100_percent(req_per_sec.within_stdev_x100)
+        ),
+    ];
+    let sampled = u64::try_from(records.len()).unwrap_or(u64::MAX);
+    if sampled < summary.total_requests {
+        lines.push(format!(
+            "    Req/Sec from {} of {} requests (--metrics-max/--metrics-range)",
+            sampled, summary.total_requests
+        ));
+    }
+    lines.push("  Latency Distribution".to_owned());
+    for (pct, quantile) in WRK_PERCENTILES {
+        let value_us = u128::from(histogram.percentile(quantile)).saturating_mul(US_PER_MS);
+        lines.push(format!("  {:>4}% {:>9}", pct, format_wrk_latency(value_us)));
+    }
+    lines.push(format!(
+        "  {} requests in {}s, {} read",
+        summary.total_requests,
+        format_x100(duration_ms / 10),
+        format_wrk_bytes(bytes)
+    ));
+    if summary.transport_errors > 0 || summary.timeout_requests > 0 {
+        lines.push(format!(
+            "  Socket errors: connect 0, read {}, write 0, timeout {}",
+            summary.transport_errors, summary.timeout_requests
+        ));
+    }
+    if summary.non_expected_status > 0 {
+        lines.push(format!(
+            "  Non-2xx or 3xx responses: {}",
+            summary.non_expected_status
+        ));
+    }
+    lines.push(format!(
+        "Requests/sec: {:>10}",
+        format_x100(u128::from(stats.avg_rps_x100))
+    ));
+    lines.push(format!(
+        "Transfer/sec: {:>10}",
+        format_wrk_bytes(per_second(bytes, duration_ms))
+    ));
+    lines
+}
+
+fn requests_per_second_x100(records: &[metrics::MetricRecord]) -> Vec<u128> {
+    let last_second = records
+        .iter()
+        .map(|record| record.elapsed_ms / MS_PER_SEC)
+        .max();
+    let Some(last_second) = last_second else {
+        return Vec::new();
+    };
+    let slots = usize::try_from(last_second)
+        .unwrap_or(usize::MAX)
+        .saturating_add(1);
+    let mut counts = vec![0u128; slots];
+    for record in records {
+        let slot = usize::try_from(record.elapsed_ms / MS_PER_SEC).unwrap_or(usize::MAX);
+        if let Some(count) = counts.get_mut(slot) {
+            *count = count.saturating_add(X100);
+        }
+    }
+    counts
+}
+
+fn hey_lines(input: &CompatInput<'_>) -> Vec<String> {
+    let CompatInput {
+        summary,
+        histogram,
+        totals,
+        ..
+    } = input;
+    let bytes = totals.bytes;
+    let rps_x10000 = u128::from(summary.total_requests)
+        .saturating_mul(PERCENT_X100)
+        .saturating_mul(u128::from(MS_PER_SEC))
+        .checked_div(summary.duration.as_millis().max(1))
+        .unwrap_or(0);
+    let size_per_request = bytes
+        .checked_div(u128::from(summary.total_requests))
+        .unwrap_or(0);
+
+    let mut lines = vec![
+        String::new(),
+        "Summary:".to_owned(),
+        format!(
+            "  Total:\t{} secs",
+            format_hey_secs_us(summary.duration.as_micros())
+        ),
+        format!(
+            "  Slowest:\t{} secs",
+            format_hey_secs_ms(summary.max_latency_ms)
+        ),
+        format!(
+            "  Fastest:\t{} secs",
+            format_hey_secs_ms(summary.min_latency_ms)
+        ),
+        format!(
+            "  Average:\t{} secs",
+            format_hey_secs_ms(summary.avg_latency_ms)
+        ),
+        format!("  Requests/sec:\t{}", format_x10000(rps_x10000)),
+        "  ".to_owned(),
+        format!("  Total data:\t{} bytes", bytes),
+        format!("  Size/request:\t{} bytes", size_per_request),
+        String::new(),
+        "Response time histogram:".to_owned(),
+    ];
+    lines.extend(hey_histogram(
+        histogram,
+        summary.min_latency_ms,
+        summary.max_latency_ms,
+    ));
+    lines.push(String::new());
+    lines.push(String::new());
+    lines.push("Latency distribution:".to_owned());
+    if histogram.count() > 0 {
+        for (pct, quantile) in HEY_PERCENTILES {
+            lines.push(format!(
+                "  {}% in {} secs",
+                pct,
+                format_hey_secs_ms(histogram.percentile(quantile))
+            ));
+        }
+    }
+    lines.push(String::new());
+    lines.push("Status code distribution:".to_owned());
+    for (status, count) in &totals.status_codes {
+        lines.push(format!("  [{}]\t{} responses", status, count));
+    }
+    if summary.timeout_requests > 0 || summary.transport_errors > 0 {
+        lines.push(String::new());
+        lines.push("Error distribution:".to_owned());
+        if summary.timeout_requests > 0 {
+            lines.push(format!("  [{}]\ttimeout", summary.timeout_requests));
+        }
+        if summary.transport_errors > 0 {
+            lines.push(format!("  [{}]\ttransport error", summary.transport_errors));
+        }
+    }
+    lines
+}
+
+/// Buckets latencies up to each mark between `fastest` and `slowest`; the
+/// last bucket also takes anything above its mark.
+fn hey_histogram(histogram: &metrics::LatencyHistogram, fastest: u64, slowest: u64) -> Vec<String> {
+    let total = histogram.count();
+    if total == 0 {
+        return Vec::new();
+    }
+    let step = slowest.saturating_sub(fastest) / HEY_HISTOGRAM_BUCKETS;
+    let marks: Vec<u64> = (0..=HEY_HISTOGRAM_BUCKETS)
+        .map(|index| fastest.saturating_add(step.saturating_mul(index)))
+        .collect();
+    let mut counted = 0u64;
+    let counts: Vec<u64> = marks
+        .iter()
+        .enumerate()
+        .map(|(index, mark)| {
+            let up_to_mark = if index.saturating_add(1) == marks.len() {
+                total
+            } else {
+                histogram.count_between(0, *mark)
+            };
+            let count = up_to_mark.saturating_sub(counted);
+            counted = counted.max(up_to_mark);
+            count
+        })
+        .collect();
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+    marks
+        .iter()
+        .zip(&counts)
+        .map(|(mark, count)| {
+            let bar_len = count
+                .saturating_mul(HEY_BAR_WIDTH)
+                .checked_div(max_count)
+                .unwrap_or(0);
+            let bars = "■".repeat(usize::try_from(bar_len).unwrap_or(0));
+            format!("  {} [{}]\t|{}", format_hey_mark_ms(*mark), count, bars)
+        })
+        .collect()
+}
+
+fn format_x100(value: u128) -> String {
+    format!("{}.{:02}", value / X100, value % X100)
+}
+
+fn format_x10000(value: u128) -> String {
+    format!("{}.{:04}", value / PERCENT_X100, value % PERCENT_X100)
+}
+
+fn format_x100_percent(value: u128) -> String {
+    format!("{}%", format_x100(value))
+}
+
+fn format_wrk_latency(value_us: u128) -> String {
+    if value_us < US_PER_MS {
+        format!("{}.00us", value_us)
+    } else if value_us < US_PER_SEC {
+        format!("{}ms", format_x100(value_us / 10))
+    } else {
+        format!("{}s", format_x100(value_us / 10_000))
+    }
+}
+
+fn format_wrk_count_x100(value_x100: u128) -> String {
+    if value_x100 >= 100_000_000 {
+        format!("{}M", format_x100(value_x100 / 1_000_000))
+    } else if value_x100 >= 100_000 {
+        format!("{}k", format_x100(value_x100 / 1_000))
+    } else {
+        format_x100(value_x100)
+    }
+}
+
+fn format_wrk_bytes(bytes: u128) -> String {
+    let (unit_size, unit) = if bytes >= GIB {
+        (GIB, "GB")
+    } else if bytes >= MIB {
+        (MIB, "MB")
+    } else if bytes >= KIB {
+        (KIB, "KB")
+    } else {
+        return format!("{}.00B", bytes);
+    };
+    let scaled = bytes
+        .saturating_mul(X100)
+        .checked_div(unit_size)
+        .unwrap_or(0);
+    format!("{}{}", format_x100(scaled), unit)
+}
+
+fn format_hey_secs_ms(value_ms: u64) -> String {
+    format_hey_secs_us(u128::from(value_ms).saturating_mul(US_PER_MS))
+}
+
+fn format_hey_secs_us(value_us: u128) -> String {
+    format_x10000(value_us / 100)
+}
+
+fn format_hey_mark_ms(value_ms: u64) -> String {
+    format!("{}.{:03}", value_ms / MS_PER_SEC, value_ms % MS_PER_SEC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AppError, AppResult};
+    use clap::Parser;
+    use std::time::Duration;
+
+    fn record(elapsed_ms: u64, latency_ms: u64, status_code: u16) -> metrics::MetricRecord {
+        metrics::MetricRecord {
+            elapsed_ms,
+            latency_ms,
+            status_code,
+            timed_out: false,
+            transport_error: false,
+            response_bytes: 1_024,
+            in_flight_ops: 1,
+            check_failed: false,
+        }
+    }
+
+    struct Fixture {
+        summary: metrics::MetricsSummary,
+        stats: SummaryStats,
+        histogram: metrics::LatencyHistogram,
+        totals: ResponseTotals,
+        records: Vec<metrics::MetricRecord>,
+        args: TesterArgs,
+    }
+
+    impl Fixture {
+        fn input(&self) -> CompatInput<'_> {
+            CompatInput {
+                summary: &self.summary,
+                stats: &self.stats,
+                histogram: &self.histogram,
+                totals: &self.totals,
+                records: &self.records,
+                args: &self.args,
+            }
+        }
+    }
+
+    fn fixture() -> AppResult<Fixture> {
+        let records = vec![
+            record(100, 10, 200),
+            record(600, 20, 200),
+            record(1_200, 30, 200),
+            record(1_700, 40, 503),
+        ];
+        let mut histogram = metrics::LatencyHistogram::new()?;
+        for record in &records {
+            histogram.record(record.latency_ms)?;
+        }
+        let totals = ResponseTotals {
+            bytes: 4_096,
+            status_codes: BTreeMap::from([(200, 3), (503, 1)]),
+        };
+        let summary = metrics::MetricsSummary {
+            duration: Duration::from_secs(2),
+            total_requests: 4,
+            successful_requests: 3,
+            error_requests: 1,
+            timeout_requests: 0,
+            transport_errors: 0,
+            non_expected_status: 1,
+            min_latency_ms: 10,
+            max_latency_ms: 40,
+            avg_latency_ms: 25,
+            success_min_latency_ms: 10,
+            success_max_latency_ms: 30,
+            success_avg_latency_ms: 20,
+            http_versions: std::collections::BTreeMap::new(),
+            error_reasons: std::collections::BTreeMap::new(),
+            check_failures: crate::metrics::CheckFailures::default(),
+        };
+        let stats = super::super::compute_summary_stats(&summary);
+        let args = TesterArgs::try_parse_from(["strest", "-u", "http://localhost/", "-m", "8"])
+            .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+        Ok(Fixture {
+            summary,
+            stats,
+            histogram,
+            totals,
+            records,
+            args,
+        })
+    }
+
+    fn expect_lines(lines: &[String], expected: &[&str]) -> AppResult<()> {
+        for line in expected {
+            if !lines.iter().any(|candidate| candidate == line) {
+                return Err(AppError::validation(format!(
+                    "Missing line {:?} in {:#?}",
+                    line, lines
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn wrk_layout_reports_latency_and_transfer() -> AppResult<()> {
+        let fixture = fixture()?;
+        let lines = compat_lines(CompatFormat::Wrk, &fixture.input());
+        if lines.iter().any(|line| line.contains("Req/Sec from")) {
+            return Err(AppError::validation(format!(
+                "Unexpected sampling note in {:#?}",
+                lines
+            )));
+        }
+        expect_lines(
+            &lines,
+            &[
+                "Running 2s test @ http://localhost/",
+                "  1 threads and 8 connections",
+                "    Latency    25.00ms  11.18ms  40.00ms   50.00%",
+                "    50%   20.00ms",
+                "  4 requests in 2.00s, 4.00KB read",
+                "  Non-2xx or 3xx responses: 1",
+                "Requests/sec:       2.00",
+                "Transfer/sec:     2.00KB",
+            ],
+        )
+    }
+
+    #[test]
+    fn wrk_layout_ignores_truncated_records_except_for_req_sec() -> AppResult<()> {
+        let mut fixture = fixture()?;
+        // `--metrics-max 2` keeps only the first two records.
+        fixture.records.truncate(2);
+        let lines = compat_lines(CompatFormat::Wrk, &fixture.input());
+        expect_lines(
+            &lines,
+            &[
+                "    Latency    25.00ms  11.18ms  40.00ms   50.00%",
+                "    Req/Sec from 2 of 4 requests (--metrics-max/--metrics-range)",
+                "    99%   40.00ms",
+                "  4 requests in 2.00s, 4.00KB read",
+            ],
+        )
+    }
+
+    #[test]
+    fn hey_layout_reports_histogram_and_status_codes() -> AppResult<()> {
+        let mut fixture = fixture()?;
+        // hey output never reads the records.
+        fixture.records.clear();
+        let lines = compat_lines(CompatFormat::Hey, &fixture.input());
+        expect_lines(
+            &lines,
+            &[
+                "  Total:\t2.0000 secs",
+                "  Slowest:\t0.0400 secs",
+                "  Requests/sec:\t2.0000",
+                "  Size/request:\t1024 bytes",
+                "  0.010 [1]\t|****************************************",
+                "  0.040 [1]\t|****************************************",
+                "  99% in 0.0400 secs",
+                "  [200]\t3 responses",
+                "  [503]\t1 responses",
+            ]
+            .map(|line| line.replace('*', "■"))
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>(),
+        )
+    }
+}
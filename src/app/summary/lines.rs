@@ -1,13 +1,19 @@
-use crate::args::{TesterArgs, TimeUnit};
+use std::collections::BTreeMap;
+
+use crate::args::{TesterArgs, TimeUnit, tag_map};
 use crate::metrics;
 use crate::system::{chart_status_line, selection_lines};
 
-use super::{PERCENT_DIVISOR, SummaryExtras, SummaryStats};
+use super::{
+    LatencyStats, PERCENT_DIVISOR, PercentileRow, StatusLatency, SummaryExtras, SummaryStats,
+};
 
 /// Milliseconds per second.
 const MS_PER_SEC_U64: u64 = 1_000;
 /// Microseconds per millisecond.
 const US_PER_MS: u128 = 1_000;
+/// Microseconds per millisecond, for `u64` figures.
+const US_PER_MS_U64: u64 = 1_000;
 /// Nanoseconds per millisecond.
 const NS_PER_MS: u128 = 1_000_000;
 /// Milliseconds per minute.
@@ -31,6 +37,8 @@ pub(crate) fn summary_lines(
     let errors = summary.error_requests;
     let time_unit = args.time_unit;
 
+    lines.extend(tags_line(&args.tags));
+
     if let Some(unit) = time_unit {
         let duration_ms = u64::try_from(summary.duration.as_millis()).unwrap_or(u64::MAX);
         lines.push(format!(
@@ -51,6 +59,8 @@ pub(crate) fn summary_lines(
             "Non-Expected Status: {}",
             summary.non_expected_status
         ));
+        lines.extend(error_reasons_line(&summary.error_reasons));
+        lines.extend(check_failure_lines(summary.check_failures));
         lines.push(format!(
             "Avg Latency (all): {}",
             format_duration_ms(summary.avg_latency_ms, unit)
@@ -97,6 +107,8 @@ pub(crate) fn summary_lines(
             "Non-Expected Status: {}",
             summary.non_expected_status
         ));
+        lines.extend(error_reasons_line(&summary.error_reasons));
+        lines.extend(check_failure_lines(summary.check_failures));
         lines.push(format!("Avg Latency (all): {}ms", summary.avg_latency_ms));
         lines.push(format!(
             "Avg Latency (ok): {}ms",
@@ -131,6 +143,107 @@ pub(crate) fn summary_lines(
         stats.avg_rpm_x100 % PERCENT_DIVISOR
     ));
 
+    if let Some(report) = extras.max_rate {
+        lines.push(report.sustainable_rps.map_or_else(
+            || {
+                format!(
+                    "Max Sustainable Rate: not found (p99 above {}ms at every rate tried)",
+                    report.target_p99_ms
+                )
+            },
+            |rps| {
+                format!(
+                    "Max Sustainable Rate: {} rps (p99 <= {}ms)",
+                    rps, report.target_p99_ms
+                )
+            },
+        ));
+    }
+
+    if let Some(report) = extras.connections {
+        let per_connection_x100 = total
+            .saturating_mul(PERCENT_DIVISOR)
+            .checked_div(report.opened)
+            .unwrap_or(0);
+        lines.push(format!(
+            "Connections: {} opened ({}.{:02} requests per connection, limit {})",
+            report.opened,
+            per_connection_x100 / PERCENT_DIVISOR,
+            per_connection_x100 % PERCENT_DIVISOR,
+            report.requests_per_connection
+        ));
+    }
+
+    if let Some(report) = extras.http2_streams {
+        lines.push(format!(
+            "HTTP/2 Streams: {}.{:02} concurrent per connection on average (target {}), {} requests over {} connections",
+            report.avg_streams_x100 / PERCENT_DIVISOR,
+            report.avg_streams_x100 % PERCENT_DIVISOR,
+            report.parallel,
+            report.streams,
+            report.connections
+        ));
+    }
+
+    if let Some(report) = extras.sse {
+        lines.push(format!(
+            "SSE: {} events over {} connections, avg time-to-first-event {}.{:03}ms, avg inter-event {}.{:03}ms",
+            report.events,
+            report.connections,
+            report.avg_first_event_us / US_PER_MS_U64,
+            report.avg_first_event_us % US_PER_MS_U64,
+            report.avg_inter_event_us / US_PER_MS_U64,
+            report.avg_inter_event_us % US_PER_MS_U64
+        ));
+    }
+
+    if let Some(split) = extras.connection_split.as_ref() {
+        lines.extend(connection_split_lines(split));
+    }
+
+    if let Some(latency_stats) = extras.latency_stats {
+        lines.push(latency_stats_line(latency_stats));
+    }
+
+    if let Some(versions) = extras.http_versions.as_ref() {
+        lines.push(http_versions_line(versions));
+    }
+
+    if let Some(rows) = extras.percentile_table.as_deref() {
+        lines.extend(percentile_table_lines(rows, time_unit));
+    }
+
+    if let Some(rows) = extras.status_latency.as_ref() {
+        lines.extend(status_latency_lines(rows, time_unit));
+    }
+
+    if let Some(slowest) = extras.slowest.as_deref() {
+        lines.extend(slowest_lines(
+            slowest,
+            args.url.as_deref().unwrap_or("-"),
+            time_unit,
+        ));
+    }
+
+    if let Some(drain) = extras.drain {
+        lines.push(format!(
+            "Deadline Drain: {} in flight, {} late completions, {} abandoned",
+            drain.in_flight_at_deadline, drain.late_completions, drain.abandoned
+        ));
+    }
+
+    if extras.latency_modes.len() > 1 {
+        lines.extend(latency_mode_lines(&extras.latency_modes, time_unit));
+    }
+
+    if let Some(reason) = extras.stop_reason.as_deref() {
+        lines.push(format!("Stop Reason: {}", reason));
+    }
+
+    if let Some(reason) = extras.abort_reason.as_deref() {
+        lines.push(format!("Aborted Early: {}", reason));
+    }
+
     lines.push(chart_status_line(
         args,
         extras.charts_output_path.as_deref(),
@@ -144,6 +257,214 @@ pub(crate) fn summary_lines(
     lines
 }
 
+/// `HTTP Versions: HTTP/2 1970 (98.50%), HTTP/1.1 30 (1.50%)`, most used first.
+/// `Tags: build=1234 env=staging`, sorted by key; `None` when the run has no tags.
+pub(crate) fn tags_line(tags: &[(String, String)]) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+    let parts: Vec<String> = tag_map(tags)
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    Some(format!("Tags: {}", parts.join(" ")))
+}
+
+pub(crate) fn latency_stats_line(stats: LatencyStats) -> String {
+    format!(
+        "Latency Geomean/Stddev (all): {}.{:02}ms / {}.{:02}ms",
+        stats.geomean_x100 / PERCENT_DIVISOR,
+        stats.geomean_x100 % PERCENT_DIVISOR,
+        stats.stddev_x100 / PERCENT_DIVISOR,
+        stats.stddev_x100 % PERCENT_DIVISOR
+    )
+}
+
+pub(crate) fn http_versions_line(versions: &BTreeMap<&'static str, u64>) -> String {
+    let total: u64 = versions
+        .values()
+        .fold(0, |sum, count| sum.saturating_add(*count));
+    if total == 0 {
+        return "HTTP Versions: none recorded".to_owned();
+    }
+    let mut counts: Vec<(&str, u64)> = versions
+        .iter()
+        .map(|(version, count)| (*version, *count))
+        .collect();
+    counts.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(right.0)));
+    let parts: Vec<String> = counts
+        .iter()
+        .map(|(version, count)| {
+            let share_x100 = u128::from(*count)
+                .saturating_mul(u128::from(PERCENT_DIVISOR) * 100)
+                .checked_div(u128::from(total))
+                .unwrap_or(0);
+            let share_x100 = u64::try_from(share_x100).unwrap_or(u64::MAX);
+            format!(
+                "{} {} ({}.{:02}%)",
+                version,
+                count,
+                share_x100 / PERCENT_DIVISOR,
+                share_x100 % PERCENT_DIVISOR
+            )
+        })
+        .collect();
+    format!("HTTP Versions: {}", parts.join(", "))
+}
+
+/// Failed requests per error reason, most frequent first; `None` when no
+/// failure was classified.
+pub(crate) fn error_reasons_line(reasons: &BTreeMap<&'static str, u64>) -> Option<String> {
+    if reasons.is_empty() {
+        return None;
+    }
+    let mut counts: Vec<(&str, u64)> = reasons
+        .iter()
+        .map(|(reason, count)| (*reason, *count))
+        .collect();
+    counts.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(right.0)));
+    let parts: Vec<String> = counts
+        .iter()
+        .map(|(reason, count)| format!("{} {}", reason, count))
+        .collect();
+    Some(format!("Error Reasons: {}", parts.join(", ")))
+}
+
+/// One line per failed response check that occurred, e.g.
+/// `Request ID Mismatches: 3`.
+pub(crate) fn check_failure_lines(failures: metrics::CheckFailures) -> Vec<String> {
+    failures
+        .counts()
+        .into_iter()
+        .map(|(_, title, count)| format!("{}: {}", title, count))
+        .collect()
+}
+
+/// Average connect, first-byte and total latency for requests that opened
+/// a connection and for those that reused one.
+fn connection_split_lines(split: &metrics::ConnectionSplit) -> Vec<String> {
+    let ms = |value_x100: u64| {
+        format!(
+            "{}.{:02}ms",
+            value_x100 / PERCENT_DIVISOR,
+            value_x100 % PERCENT_DIVISOR
+        )
+    };
+    let mut lines = vec!["Connection Timing (avg connect / first byte / total):".to_owned()];
+    for (label, bucket) in [("new", &split.new), ("reused", &split.reused)] {
+        lines.push(format!(
+            "  {:<6} {} requests, {} / {} / {}",
+            label,
+            bucket.requests,
+            ms(bucket.avg_connect_ms_x100()),
+            ms(bucket.avg_first_byte_ms_x100()),
+            ms(bucket.avg_total_ms_x100())
+        ));
+    }
+    lines
+}
+
+/// One row per status class seen, e.g. `  5xx: 120 requests, P50/P90/P99 2ms / 3ms / 9ms`.
+fn status_latency_lines(rows: &[StatusLatency], unit: Option<TimeUnit>) -> Vec<String> {
+    let format = |value_ms: u64| {
+        unit.map_or_else(
+            || format!("{}ms", value_ms),
+            |unit| format_duration_ms(value_ms, unit),
+        )
+    };
+    let mut lines = vec!["Latency by Status:".to_owned()];
+    if rows.is_empty() {
+        lines.push("  none recorded".to_owned());
+    }
+    lines.extend(rows.iter().map(|row| {
+        format!(
+            "  {}: {} requests, P50/P90/P99 {} / {} / {}",
+            row.class.label(),
+            row.count,
+            format(row.p50),
+            format(row.p90),
+            format(row.p99)
+        )
+    }));
+    lines
+}
+
+/// One row per slow request, e.g. `  1. 1500ms 503 https://host/path`.
+/// Renders the percentile table with the overall and success-only columns side by side.
+fn percentile_table_lines(rows: &[PercentileRow], unit: Option<TimeUnit>) -> Vec<String> {
+    let format = |value_ms: u64| {
+        unit.map_or_else(
+            || format!("{}ms", value_ms),
+            |unit| format_duration_ms(value_ms, unit),
+        )
+    };
+    let mut lines = vec![format!("Latency Percentiles: {:>12} {:>12}", "all", "ok")];
+    lines.extend(rows.iter().map(|row| {
+        format!(
+            "  {:<18} {:>12} {:>12}",
+            row.label,
+            format(row.all),
+            format(row.ok)
+        )
+    }));
+    lines
+}
+
+/// `Latency Modes: ~12ms (70.00%), ~180ms (30.00%)` plus a percentile warning.
+fn latency_mode_lines(modes: &[metrics::LatencyMode], unit: Option<TimeUnit>) -> Vec<String> {
+    let parts: Vec<String> = modes
+        .iter()
+        .map(|mode| {
+            let peak = unit.map_or_else(
+                || format!("{}ms", mode.peak_ms),
+                |unit| format_duration_ms(mode.peak_ms, unit),
+            );
+            format!(
+                "~{} ({}.{:02}%)",
+                peak,
+                mode.weight_x100 / PERCENT_DIVISOR,
+                mode.weight_x100 % PERCENT_DIVISOR
+            )
+        })
+        .collect();
+    vec![
+        format!("Latency Modes: {}", parts.join(", ")),
+        "Warning: latency is multimodal; a single percentile may be misleading.".to_owned(),
+    ]
+}
+
+fn slowest_lines(
+    slowest: &[metrics::SlowRequest],
+    default_url: &str,
+    unit: Option<TimeUnit>,
+) -> Vec<String> {
+    let mut lines = vec![format!("Slowest Requests ({}):", slowest.len())];
+    if slowest.is_empty() {
+        lines.push("  none recorded".to_owned());
+    }
+    lines.extend(slowest.iter().zip(1_usize..).map(|(request, rank)| {
+        let latency = unit.map_or_else(
+            || format!("{}ms", request.latency_ms),
+            |unit| format_duration_ms(request.latency_ms, unit),
+        );
+        let status = if request.timed_out {
+            "timeout".to_owned()
+        } else if request.transport_error {
+            "error".to_owned()
+        } else {
+            request.status_code.to_string()
+        };
+        format!(
+            "  {}. {} {} {}",
+            rank,
+            latency,
+            status,
+            request.url.as_deref().unwrap_or(default_url)
+        )
+    }));
+    lines
+}
+
 fn format_duration_ms(value_ms: u64, unit: TimeUnit) -> String {
     match unit {
         TimeUnit::Ns => format!("{}ns", u128::from(value_ms).saturating_mul(NS_PER_MS)),
@@ -165,3 +486,208 @@ fn format_fraction_ms(value_ms: u64, unit_ms: u64, suffix: &str) -> String {
         .unwrap_or(0);
     format!("{}.{:03}{}", whole, thousandths, suffix)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::error::{AppError, AppResult};
+
+    #[test]
+    fn http_versions_line_orders_by_count() -> AppResult<()> {
+        let versions = BTreeMap::from([("HTTP/1.1", 30), ("HTTP/2", 1_970)]);
+        let line = http_versions_line(&versions);
+        if line != "HTTP Versions: HTTP/2 1970 (98.50%), HTTP/1.1 30 (1.50%)" {
+            return Err(AppError::validation(format!("Unexpected line: {}", line)));
+        }
+        if http_versions_line(&BTreeMap::new()) != "HTTP Versions: none recorded" {
+            return Err(AppError::validation("Expected an empty report line"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn error_reasons_line_orders_by_count() -> AppResult<()> {
+        let reasons = BTreeMap::from([("connection_refused", 4_900), ("tls", 100)]);
+        let line = error_reasons_line(&reasons);
+        if line.as_deref() != Some("Error Reasons: connection_refused 4900, tls 100") {
+            return Err(AppError::validation(format!("Unexpected line: {:?}", line)));
+        }
+        if error_reasons_line(&BTreeMap::new()).is_some() {
+            return Err(AppError::validation("Expected no line without errors"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn connection_split_lines_average_each_bucket() -> AppResult<()> {
+        let mut split = metrics::ConnectionSplit::default();
+        for (new_connection, connect_us, first_byte_us, total_us) in [
+            (true, 30_000, 45_000, 50_000),
+            (true, 40_000, 55_000, 60_000),
+            (false, 0, 12_345, 15_000),
+        ] {
+            split.record(
+                &metrics::ConnectionTiming {
+                    new_connection,
+                    connect: std::time::Duration::from_micros(connect_us),
+                    first_byte: std::time::Duration::from_micros(first_byte_us),
+                },
+                std::time::Duration::from_micros(total_us),
+            );
+        }
+        let lines = connection_split_lines(&split);
+        let expected = [
+            "Connection Timing (avg connect / first byte / total):",
+            "  new    2 requests, 35.00ms / 50.00ms / 55.00ms",
+            "  reused 1 requests, 0.00ms / 12.34ms / 15.00ms",
+        ];
+        if lines != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected lines: {:?}",
+                lines
+            )));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn status_latency_lines_render_each_class() -> AppResult<()> {
+        let rows = [
+            StatusLatency {
+                class: metrics::StatusClass::Success,
+                count: 900,
+                p50: 40,
+                p90: 120,
+                p99: 300,
+            },
+            StatusLatency {
+                class: metrics::StatusClass::ServerError,
+                count: 100,
+                p50: 2,
+                p90: 3,
+                p99: 1_500,
+            },
+        ];
+        let lines = status_latency_lines(&rows, None);
+        let expected = [
+            "Latency by Status:",
+            "  2xx: 900 requests, P50/P90/P99 40ms / 120ms / 300ms",
+            "  5xx: 100 requests, P50/P90/P99 2ms / 3ms / 1500ms",
+        ];
+        if lines != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected lines: {:?}",
+                lines
+            )));
+        }
+        let seconds = status_latency_lines(&rows, Some(TimeUnit::S));
+        if seconds.get(2).map(String::as_str)
+            != Some("  5xx: 100 requests, P50/P90/P99 0.002s / 0.003s / 1.500s")
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected lines: {:?}",
+                lines
+            )));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_table_lines_show_all_and_ok_columns() -> AppResult<()> {
+        let mut histogram = metrics::LatencyHistogram::new()?;
+        let mut success_histogram = metrics::LatencyHistogram::new()?;
+        for latency_ms in 1..=100 {
+            histogram.record(latency_ms)?;
+            if latency_ms <= 50 {
+                success_histogram.record(latency_ms)?;
+            }
+        }
+        let rows = super::super::percentile_table(&histogram, &success_histogram);
+        let lines = percentile_table_lines(&rows, None);
+        let expected = [
+            "Latency Percentiles:          all           ok",
+            "  p50                        50ms         25ms",
+            "  p75                        75ms         38ms",
+            "  p90                        90ms         45ms",
+            "  p95                        95ms         48ms",
+            "  p99                        99ms         50ms",
+            "  p99.9                     100ms         50ms",
+            "  p99.99                    100ms         50ms",
+            "  max                       100ms         50ms",
+        ];
+        if lines != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected lines: {:?}",
+                lines
+            )));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn latency_mode_lines_list_peaks_with_weights() -> AppResult<()> {
+        let modes = [
+            metrics::LatencyMode {
+                peak_ms: 5,
+                low_ms: 4,
+                high_ms: 6,
+                count: 700,
+                weight_x100: 7_000,
+            },
+            metrics::LatencyMode {
+                peak_ms: 200,
+                low_ms: 180,
+                high_ms: 220,
+                count: 300,
+                weight_x100: 3_000,
+            },
+        ];
+        let lines = latency_mode_lines(&modes, None);
+        let expected = [
+            "Latency Modes: ~5ms (70.00%), ~200ms (30.00%)",
+            "Warning: latency is multimodal; a single percentile may be misleading.",
+        ];
+        if lines != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected lines: {:?}",
+                lines
+            )));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn slowest_lines_rank_requests_and_fall_back_to_target_url() -> AppResult<()> {
+        let slowest = [
+            metrics::SlowRequest {
+                latency_ms: 1_500,
+                status_code: 503,
+                timed_out: false,
+                transport_error: false,
+                url: Some(Arc::from("http://localhost/slow")),
+            },
+            metrics::SlowRequest {
+                latency_ms: 900,
+                status_code: 500,
+                timed_out: true,
+                transport_error: false,
+                url: None,
+            },
+        ];
+        let lines = slowest_lines(&slowest, "http://localhost/", None);
+        let expected = [
+            "Slowest Requests (2):",
+            "  1. 1500ms 503 http://localhost/slow",
+            "  2. 900ms timeout http://localhost/",
+        ];
+        if lines != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected lines: {:?}",
+                lines
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::metrics;
 
-use super::PERCENT_DIVISOR;
+use super::{PERCENT_DIVISOR, PercentileRow};
 
 /// Standard percentile labels.
 const PERCENTILE_P50: u64 = 50;
@@ -8,6 +8,16 @@ const PERCENTILE_P90: u64 = 90;
 const PERCENTILE_P99: u64 = 99;
 /// Rounding offset for percentile selection.
 const PERCENTILE_ROUNDING: u64 = 50;
+/// Rows of the `--summary-percentile-table` output, before the max row.
+const PERCENTILE_TABLE: [(&str, f64); 7] = [
+    ("p50", 0.5),
+    ("p75", 0.75),
+    ("p90", 0.9),
+    ("p95", 0.95),
+    ("p99", 0.99),
+    ("p99.9", 0.999),
+    ("p99.99", 0.9999),
+];
 
 pub(crate) fn compute_percentiles(records: &[metrics::MetricRecord]) -> (u64, u64, u64) {
     if records.is_empty() {
@@ -36,3 +46,23 @@ fn percentile(values: &[u64], percentile: u64) -> u64 {
     let idx = usize::try_from(index).unwrap_or_else(|_| values.len().saturating_sub(1));
     *values.get(idx).unwrap_or(&0)
 }
+
+/// Builds the percentile table from the overall and success-only histograms.
+pub(crate) fn percentile_table(
+    histogram: &metrics::LatencyHistogram,
+    success_histogram: &metrics::LatencyHistogram,
+) -> Vec<PercentileRow> {
+    PERCENTILE_TABLE
+        .iter()
+        .map(|&(label, quantile)| PercentileRow {
+            label,
+            all: histogram.percentile(quantile),
+            ok: success_histogram.percentile(quantile),
+        })
+        .chain(std::iter::once(PercentileRow {
+            label: "max",
+            all: histogram.max(),
+            ok: success_histogram.max(),
+        }))
+        .collect()
+}
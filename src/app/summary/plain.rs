@@ -0,0 +1,242 @@
+use crate::metrics;
+
+use super::{SummaryExtras, SummaryStats};
+
+/// Renders the `--summary-format plain` summary: one raw `key: value` per
+/// line in a fixed order, with no units or separators, so identical runs
+/// diff cleanly.
+pub(crate) fn plain_lines(
+    summary: &metrics::MetricsSummary,
+    extras: &SummaryExtras,
+    stats: &SummaryStats,
+) -> Vec<String> {
+    let mut lines = vec![
+        format!("duration_ms: {}", summary.duration.as_millis()),
+        format!("total_requests: {}", summary.total_requests),
+        format!("successful_requests: {}", summary.successful_requests),
+        format!("error_requests: {}", summary.error_requests),
+        format!("timeout_requests: {}", summary.timeout_requests),
+        format!("transport_errors: {}", summary.transport_errors),
+        format!("non_expected_status: {}", summary.non_expected_status),
+        format!("success_rate_x100: {}", stats.success_rate_x100),
+        format!("avg_rps_x100: {}", stats.avg_rps_x100),
+        format!("avg_rpm_x100: {}", stats.avg_rpm_x100),
+        format!("min_latency_ms: {}", summary.min_latency_ms),
+        format!("max_latency_ms: {}", summary.max_latency_ms),
+        format!("avg_latency_ms: {}", summary.avg_latency_ms),
+        format!("p50_latency_ms: {}", extras.p50),
+        format!("p90_latency_ms: {}", extras.p90),
+        format!("p99_latency_ms: {}", extras.p99),
+        format!("success_min_latency_ms: {}", summary.success_min_latency_ms),
+        format!("success_max_latency_ms: {}", summary.success_max_latency_ms),
+        format!("success_avg_latency_ms: {}", summary.success_avg_latency_ms),
+        format!("success_p50_latency_ms: {}", extras.success_p50),
+        format!("success_p90_latency_ms: {}", extras.success_p90),
+        format!("success_p99_latency_ms: {}", extras.success_p99),
+    ];
+    if let Some(latency_stats) = extras.latency_stats {
+        lines.push(format!(
+            "latency_geomean_ms_x100: {}",
+            latency_stats.geomean_x100
+        ));
+        lines.push(format!(
+            "latency_stddev_ms_x100: {}",
+            latency_stats.stddev_x100
+        ));
+    }
+    if let Some(versions) = extras.http_versions.as_ref() {
+        lines.extend(
+            versions
+                .iter()
+                .map(|(version, count)| format!("http_version.{}: {}", version, count)),
+        );
+    }
+    lines.extend(
+        summary
+            .error_reasons
+            .iter()
+            .map(|(reason, count)| format!("error_reason.{}: {}", reason, count)),
+    );
+    lines.extend(
+        summary
+            .check_failures
+            .counts()
+            .into_iter()
+            .map(|(key, _, count)| format!("{}: {}", key, count)),
+    );
+    if let Some(report) = extras.connections {
+        lines.push(format!("connections_opened: {}", report.opened));
+    }
+    if let Some(report) = extras.http2_streams {
+        lines.push(format!("http2_connections: {}", report.connections));
+        lines.push(format!(
+            "http2_avg_streams_x100: {}",
+            report.avg_streams_x100
+        ));
+    }
+    if let Some(report) = extras.sse {
+        lines.push(format!("sse_events: {}", report.events));
+        lines.push(format!("sse_connections: {}", report.connections));
+        lines.push(format!(
+            "sse_avg_first_event_us: {}",
+            report.avg_first_event_us
+        ));
+        lines.push(format!(
+            "sse_avg_inter_event_us: {}",
+            report.avg_inter_event_us
+        ));
+    }
+    if let Some(split) = extras.connection_split.as_ref() {
+        for (label, bucket) in [("new", &split.new), ("reused", &split.reused)] {
+            lines.push(format!(
+                "{}_connection_requests: {}",
+                label, bucket.requests
+            ));
+            lines.push(format!(
+                "{}_connection_connect_ms_x100: {}",
+                label,
+                bucket.avg_connect_ms_x100()
+            ));
+            lines.push(format!(
+                "{}_connection_first_byte_ms_x100: {}",
+                label,
+                bucket.avg_first_byte_ms_x100()
+            ));
+            lines.push(format!(
+                "{}_connection_total_ms_x100: {}",
+                label,
+                bucket.avg_total_ms_x100()
+            ));
+        }
+    }
+    if let Some(reason) = extras.stop_reason.as_deref() {
+        lines.push(format!("stop_reason: {}", reason));
+    }
+    if let Some(reason) = extras.abort_reason.as_deref() {
+        lines.push(format!("abort_reason: {}", reason));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::error::{AppError, AppResult};
+
+    fn extras() -> SummaryExtras {
+        SummaryExtras {
+            metrics_truncated: false,
+            charts_output_path: Some("charts/run-1234".to_owned()),
+            stop_reason: None,
+            abort_reason: None,
+            p50: 30,
+            p90: 40,
+            p99: 40,
+            success_p50: 20,
+            success_p90: 30,
+            success_p99: 30,
+            http_versions: Some(BTreeMap::from([("HTTP/1.1", 1), ("HTTP/2", 12_344)])),
+            status_latency: None,
+            slowest: None,
+            percentile_table: None,
+            drain: None,
+            latency_modes: Vec::new(),
+            max_rate: None,
+            connections: None,
+            http2_streams: Some(super::super::Http2StreamsReport {
+                parallel: 4,
+                connections: 2,
+                streams: 12345,
+                avg_streams_x100: 375,
+            }),
+            sse: Some(super::super::SseReport {
+                events: 40,
+                connections: 4,
+                avg_first_event_us: 12_500,
+                avg_inter_event_us: 250_000,
+            }),
+            connection_split: None,
+            latency_stats: None,
+        }
+    }
+
+    #[test]
+    fn plain_lines_print_raw_values_in_fixed_order() -> AppResult<()> {
+        let summary = metrics::MetricsSummary {
+            duration: Duration::from_millis(2_500),
+            total_requests: 12_345,
+            successful_requests: 12_000,
+            error_requests: 345,
+            timeout_requests: 5,
+            transport_errors: 40,
+            non_expected_status: 300,
+            min_latency_ms: 10,
+            max_latency_ms: 1_500,
+            avg_latency_ms: 25,
+            success_min_latency_ms: 10,
+            success_max_latency_ms: 900,
+            success_avg_latency_ms: 20,
+            http_versions: BTreeMap::new(),
+            error_reasons: BTreeMap::from([("connection_reset", 15), ("timeout", 5)]),
+            check_failures: metrics::CheckFailures {
+                request_id_mismatches: 7,
+                response_size_violations: 4,
+                too_few_messages: 3,
+                assertion_failures: 2,
+            },
+        };
+        let stats = super::super::compute_summary_stats(&summary);
+        let lines = plain_lines(&summary, &extras(), &stats);
+        let expected = [
+            "duration_ms: 2500",
+            "total_requests: 12345",
+            "successful_requests: 12000",
+            "error_requests: 345",
+            "timeout_requests: 5",
+            "transport_errors: 40",
+            "non_expected_status: 300",
+            "success_rate_x100: 9720",
+            "avg_rps_x100: 493800",
+            "avg_rpm_x100: 29628000",
+            "min_latency_ms: 10",
+            "max_latency_ms: 1500",
+            "avg_latency_ms: 25",
+            "p50_latency_ms: 30",
+            "p90_latency_ms: 40",
+            "p99_latency_ms: 40",
+            "success_min_latency_ms: 10",
+            "success_max_latency_ms: 900",
+            "success_avg_latency_ms: 20",
+            "success_p50_latency_ms: 20",
+            "success_p90_latency_ms: 30",
+            "success_p99_latency_ms: 30",
+            "http_version.HTTP/1.1: 1",
+            "http_version.HTTP/2: 12344",
+            "error_reason.connection_reset: 15",
+            "error_reason.timeout: 5",
+            "request_id_mismatches: 7",
+            "response_size_violations: 4",
+            "too_few_messages: 3",
+            "assertion_failures: 2",
+            "http2_connections: 2",
+            "http2_avg_streams_x100: 375",
+            "sse_events: 40",
+            "sse_connections: 4",
+            "sse_avg_first_event_us: 12500",
+            "sse_avg_inter_event_us: 250000",
+        ];
+        if lines != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected plain summary: {:#?}",
+                lines
+            )));
+        }
+        if plain_lines(&summary, &extras(), &stats) != lines {
+            return Err(AppError::validation("Expected identical output"));
+        }
+        Ok(())
+    }
+}
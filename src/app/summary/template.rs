@@ -0,0 +1,272 @@
+use crate::error::{AppError, AppResult, ValidationError};
+use crate::metrics;
+
+use super::{PERCENT_DIVISOR, SummaryExtras, SummaryStats};
+
+/// Placeholder opening delimiter.
+const OPEN: &str = "{{";
+/// Placeholder closing delimiter.
+const CLOSE: &str = "}}";
+
+/// Variables accepted inside `{{ }}` placeholders, in documentation order.
+pub(crate) const TEMPLATE_VARIABLES: &[&str] = &[
+    "duration_ms",
+    "duration_secs",
+    "total_requests",
+    "successful_requests",
+    "error_requests",
+    "timeout_requests",
+    "transport_errors",
+    "non_expected_status",
+    "success_rate",
+    "avg_rps",
+    "avg_rpm",
+    "min_latency_ms",
+    "max_latency_ms",
+    "avg_latency_ms",
+    "p50_latency_ms",
+    "p90_latency_ms",
+    "p99_latency_ms",
+    "success_min_latency_ms",
+    "success_max_latency_ms",
+    "success_avg_latency_ms",
+    "success_p50_latency_ms",
+    "success_p90_latency_ms",
+    "success_p99_latency_ms",
+];
+
+#[derive(Debug)]
+enum Segment {
+    Text(String),
+    Variable(&'static str),
+}
+
+/// Parsed `--summary-template` file with every placeholder already validated.
+#[derive(Debug)]
+pub(crate) struct SummaryTemplate {
+    segments: Vec<Segment>,
+}
+
+impl SummaryTemplate {
+    pub(crate) fn parse(source: &str) -> AppResult<Self> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+        let mut offset = 0usize;
+        while let Some(start) = rest.find(OPEN) {
+            let (text, tail) = rest.split_at(start);
+            if !text.is_empty() {
+                segments.push(Segment::Text(text.to_owned()));
+            }
+            let placeholder_offset = offset.saturating_add(start);
+            let inner = tail.get(OPEN.len()..).unwrap_or("");
+            let end = inner.find(CLOSE).ok_or_else(|| {
+                AppError::validation(ValidationError::SummaryTemplateUnclosed {
+                    offset: placeholder_offset,
+                })
+            })?;
+            let name = inner.get(..end).unwrap_or("").trim();
+            segments.push(Segment::Variable(lookup_variable(name)?));
+
+            let consumed = start
+                .saturating_add(OPEN.len())
+                .saturating_add(end)
+                .saturating_add(CLOSE.len());
+            rest = rest.get(consumed..).unwrap_or("");
+            offset = offset.saturating_add(consumed);
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_owned()));
+        }
+        Ok(Self { segments })
+    }
+
+    pub(crate) async fn load(path: &str) -> AppResult<Self> {
+        let source = tokio::fs::read_to_string(path).await.map_err(|err| {
+            AppError::validation(ValidationError::SummaryTemplateRead {
+                path: path.to_owned(),
+                source: err,
+            })
+        })?;
+        Self::parse(&source)
+    }
+
+    pub(crate) fn render(
+        &self,
+        summary: &metrics::MetricsSummary,
+        extras: &SummaryExtras,
+        stats: &SummaryStats,
+    ) -> String {
+        let mut output = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Variable(name) => {
+                    output.push_str(&variable_value(name, summary, extras, stats));
+                }
+            }
+        }
+        output
+    }
+}
+
+fn lookup_variable(name: &str) -> AppResult<&'static str> {
+    TEMPLATE_VARIABLES
+        .iter()
+        .copied()
+        .find(|variable| *variable == name)
+        .ok_or_else(|| {
+            AppError::validation(ValidationError::UnknownSummaryTemplateVariable {
+                name: name.to_owned(),
+                available: TEMPLATE_VARIABLES.join(", "),
+            })
+        })
+}
+
+fn variable_value(
+    name: &str,
+    summary: &metrics::MetricsSummary,
+    extras: &SummaryExtras,
+    stats: &SummaryStats,
+) -> String {
+    match name {
+        "duration_ms" => summary.duration.as_millis().to_string(),
+        "duration_secs" => summary.duration.as_secs().to_string(),
+        "total_requests" => summary.total_requests.to_string(),
+        "successful_requests" => summary.successful_requests.to_string(),
+        "error_requests" => summary.error_requests.to_string(),
+        "timeout_requests" => summary.timeout_requests.to_string(),
+        "transport_errors" => summary.transport_errors.to_string(),
+        "non_expected_status" => summary.non_expected_status.to_string(),
+        "success_rate" => format_x100(stats.success_rate_x100),
+        "avg_rps" => format_x100(stats.avg_rps_x100),
+        "avg_rpm" => format_x100(stats.avg_rpm_x100),
+        "min_latency_ms" => summary.min_latency_ms.to_string(),
+        "max_latency_ms" => summary.max_latency_ms.to_string(),
+        "avg_latency_ms" => summary.avg_latency_ms.to_string(),
+        "p50_latency_ms" => extras.p50.to_string(),
+        "p90_latency_ms" => extras.p90.to_string(),
+        "p99_latency_ms" => extras.p99.to_string(),
+        "success_min_latency_ms" => summary.success_min_latency_ms.to_string(),
+        "success_max_latency_ms" => summary.success_max_latency_ms.to_string(),
+        "success_avg_latency_ms" => summary.success_avg_latency_ms.to_string(),
+        "success_p50_latency_ms" => extras.success_p50.to_string(),
+        "success_p90_latency_ms" => extras.success_p90.to_string(),
+        "success_p99_latency_ms" => extras.success_p99.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn format_x100(value: u64) -> String {
+    format!("{}.{:02}", value / PERCENT_DIVISOR, value % PERCENT_DIVISOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sample() -> (metrics::MetricsSummary, SummaryExtras, SummaryStats) {
+        let summary = metrics::MetricsSummary {
+            duration: Duration::from_millis(2_500),
+            total_requests: 200,
+            successful_requests: 190,
+            error_requests: 10,
+            timeout_requests: 3,
+            transport_errors: 2,
+            non_expected_status: 5,
+            min_latency_ms: 1,
+            max_latency_ms: 90,
+            avg_latency_ms: 12,
+            success_min_latency_ms: 1,
+            success_max_latency_ms: 80,
+            success_avg_latency_ms: 11,
+            http_versions: std::collections::BTreeMap::new(),
+            error_reasons: std::collections::BTreeMap::new(),
+            check_failures: crate::metrics::CheckFailures::default(),
+        };
+        let extras = SummaryExtras {
+            metrics_truncated: false,
+            charts_output_path: None,
+            stop_reason: None,
+            abort_reason: None,
+            p50: 10,
+            p90: 40,
+            p99: 85,
+            success_p50: 9,
+            success_p90: 38,
+            success_p99: 79,
+            http_versions: None,
+            status_latency: None,
+            slowest: None,
+            percentile_table: None,
+            drain: None,
+            latency_modes: Vec::new(),
+            max_rate: None,
+            connections: None,
+            http2_streams: None,
+            sse: None,
+            connection_split: None,
+            latency_stats: None,
+        };
+        let stats = super::super::compute_summary_stats(&summary);
+        (summary, extras, stats)
+    }
+
+    #[test]
+    fn render_substitutes_variables() -> AppResult<()> {
+        let template = SummaryTemplate::parse(
+            "rps={{avg_rps}} ok={{ success_rate }}% p99={{p99_latency_ms}}ms\n",
+        )?;
+        let (summary, extras, stats) = sample();
+        let rendered = template.render(&summary, &extras, &stats);
+        if rendered != "rps=80.00 ok=95.00% p99=85ms\n" {
+            return Err(AppError::validation(format!(
+                "Unexpected render: {:?}",
+                rendered
+            )));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn every_variable_renders_a_value() -> AppResult<()> {
+        let (summary, extras, stats) = sample();
+        for name in TEMPLATE_VARIABLES {
+            let template = SummaryTemplate::parse(&format!("{{{{{}}}}}", name))?;
+            if template.render(&summary, &extras, &stats).is_empty() {
+                return Err(AppError::validation(format!(
+                    "Variable '{}' rendered empty",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rejects_unknown_variable_and_lists_available() -> AppResult<()> {
+        match SummaryTemplate::parse("{{p95}}") {
+            Err(AppError::Validation(ValidationError::UnknownSummaryTemplateVariable {
+                name,
+                available,
+            })) if name == "p95" && available.contains("p99_latency_ms") => Ok(()),
+            other => Err(AppError::validation(format!(
+                "Expected unknown variable error, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unclosed_placeholder() -> AppResult<()> {
+        match SummaryTemplate::parse("total={{total_requests") {
+            Err(AppError::Validation(ValidationError::SummaryTemplateUnclosed { offset: 6 })) => {
+                Ok(())
+            }
+            other => Err(AppError::validation(format!(
+                "Expected unclosed placeholder error, got {:?}",
+                other
+            ))),
+        }
+    }
+}
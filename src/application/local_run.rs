@@ -17,6 +17,8 @@ pub(crate) struct LocalRunSettings {
     pub no_color: bool,
     pub no_ui: bool,
     pub no_splash: bool,
+    pub hold_ui: bool,
+    pub stop_file: Option<PathBuf>,
     pub no_charts: bool,
     pub summary: bool,
     pub show_selections: bool,
@@ -27,7 +29,12 @@ pub(crate) struct LocalRunSettings {
     pub alloc_profiler_ms: Option<u64>,
     pub alloc_profiler_dump_ms: Option<u64>,
     pub alloc_profiler_dump_path: String,
+    /// Whether an `--alloc-profiler-spike-*` threshold is set.
+    pub alloc_profiler_spike: bool,
     pub metrics_max: usize,
+    /// Whether stdin carries the URL list, which rules out the keyboard
+    /// shutdown handler reading it.
+    pub urls_from_stdin: bool,
 }
 
 #[derive(Debug)]
@@ -38,6 +45,10 @@ pub(crate) struct RunOutcome {
     pub latency_sum_ms: u128,
     pub success_latency_sum_ms: u128,
     pub runtime_errors: Vec<String>,
+    /// Set when an `--abort-on-*` circuit breaker stopped the run.
+    pub abort_reason: Option<String>,
+    /// Messages for `--assert-*` thresholds the run did not meet.
+    pub threshold_failures: Vec<String>,
 }
 
 pub(crate) struct LocalRunLogSetup {
@@ -107,6 +118,11 @@ pub(crate) trait ShutdownPort {
         &self,
         shutdown_tx: &ShutdownSender,
     ) -> tokio::task::JoinHandle<()>;
+    fn setup_stop_file_shutdown_handler(
+        &self,
+        shutdown_tx: &ShutdownSender,
+        path: PathBuf,
+    ) -> tokio::task::JoinHandle<()>;
 }
 
 pub(crate) trait TrafficPort<TAdapterArgs> {
@@ -145,6 +161,7 @@ pub(crate) struct MetricsCollectorInput<'args, TAdapterArgs> {
     pub metrics_rx: mpsc::Receiver<Metrics>,
     pub ui_tx: &'args watch::Sender<UiData>,
     pub stream_tx: Option<mpsc::UnboundedSender<metrics::StreamSnapshot>>,
+    pub spike_tx: Option<mpsc::Sender<metrics::LatencySpike>>,
 }
 
 #[async_trait]
@@ -169,6 +186,7 @@ pub(crate) trait OutputPort<TAdapterArgs> {
         shutdown_tx: &ShutdownSender,
         interval_ms: Option<u64>,
         dump_path: &str,
+        spike_rx: Option<mpsc::Receiver<metrics::LatencySpike>>,
     ) -> tokio::task::JoinHandle<()>;
     async fn setup_log_sinks(
         &self,
@@ -181,6 +199,7 @@ pub(crate) trait OutputPort<TAdapterArgs> {
         &self,
         shutdown_tx: &ShutdownSender,
         ui_tx: &watch::Sender<UiData>,
+        hold_ui: bool,
     ) -> tokio::task::JoinHandle<()>;
     fn setup_progress_indicator(
         &self,
@@ -237,10 +256,17 @@ where
         output_port.setup_rss_log_task(&shutdown_tx, settings.no_ui, settings.rss_log_ms);
     let alloc_handle =
         output_port.setup_alloc_profiler_task(&shutdown_tx, settings.alloc_profiler_ms);
+    let (spike_tx, spike_rx) = if settings.alloc_profiler_spike {
+        let (spike_tx, spike_rx) = mpsc::channel::<metrics::LatencySpike>(1);
+        (Some(spike_tx), Some(spike_rx))
+    } else {
+        (None, None)
+    };
     let alloc_dump_handle = output_port.setup_alloc_profiler_dump_task(
         &shutdown_tx,
         settings.alloc_profiler_dump_ms,
         &settings.alloc_profiler_dump_path,
+        spike_rx,
     );
 
     if ui_enabled && !settings.no_splash {
@@ -279,14 +305,18 @@ where
     };
     drop(metrics_tx);
 
-    let keyboard_shutdown_handle = if ui_enabled {
+    let keyboard_shutdown_handle = if ui_enabled && !settings.urls_from_stdin {
         shutdown_port.setup_keyboard_shutdown_handler(&shutdown_tx)
     } else {
         tokio::spawn(async {})
     };
     let signal_shutdown_handle = shutdown_port.setup_signal_shutdown_handler(&shutdown_tx);
+    let stop_file_handle = settings.stop_file.clone().map_or_else(
+        || tokio::spawn(async {}),
+        |path| shutdown_port.setup_stop_file_shutdown_handler(&shutdown_tx, path),
+    );
     let render_ui_handle = if ui_enabled {
-        output_port.setup_render_ui(&shutdown_tx, &ui_tx)
+        output_port.setup_render_ui(&shutdown_tx, &ui_tx, settings.hold_ui)
     } else {
         tokio::spawn(async {})
     };
@@ -302,11 +332,13 @@ where
         metrics_rx,
         ui_tx: &ui_tx,
         stream_tx,
+        spike_tx,
     });
     let metrics_max = settings.metrics_max;
-    let (_, _, _, _, _, _, _, metrics_result, request_result) = tokio::join!(
+    let (_, _, _, _, _, _, _, _, metrics_result, request_result) = tokio::join!(
         keyboard_shutdown_handle,
         signal_shutdown_handle,
+        stop_file_handle,
         render_ui_handle,
         progress_handle,
         rss_handle,
@@ -329,6 +361,11 @@ where
             runtime_errors.push(format!("Metrics collector task failed: {}", err));
             metrics::MetricsReport {
                 summary: empty_summary(),
+                stop_reason: None,
+                abort_reason: None,
+                slowest: Vec::new(),
+                drain: None,
+                connection_split: None,
             }
         }
     };
@@ -362,6 +399,14 @@ const fn empty_summary() -> metrics::MetricsSummary {
         success_min_latency_ms: 0,
         success_max_latency_ms: 0,
         success_avg_latency_ms: 0,
+        http_versions: std::collections::BTreeMap::new(),
+        error_reasons: std::collections::BTreeMap::new(),
+        check_failures: metrics::CheckFailures {
+            request_id_mismatches: 0,
+            response_size_violations: 0,
+            too_few_messages: 0,
+            assertion_failures: 0,
+        },
     }
 }
 
@@ -405,6 +450,14 @@ mod tests {
         ) -> tokio::task::JoinHandle<()> {
             tokio::spawn(async {})
         }
+
+        fn setup_stop_file_shutdown_handler(
+            &self,
+            _shutdown_tx: &ShutdownSender,
+            _path: PathBuf,
+        ) -> tokio::task::JoinHandle<()> {
+            tokio::spawn(async {})
+        }
     }
 
     struct FakeTrafficPort;
@@ -432,6 +485,11 @@ mod tests {
             tokio::spawn(async {
                 metrics::MetricsReport {
                     summary: empty_summary(),
+                    stop_reason: None,
+                    abort_reason: None,
+                    slowest: Vec::new(),
+                    drain: None,
+                    connection_split: None,
                 }
             })
         }
@@ -494,6 +552,7 @@ mod tests {
             _shutdown_tx: &ShutdownSender,
             _interval_ms: Option<u64>,
             _dump_path: &str,
+            _spike_rx: Option<mpsc::Receiver<metrics::LatencySpike>>,
         ) -> tokio::task::JoinHandle<()> {
             tokio::spawn(async {})
         }
@@ -516,6 +575,7 @@ mod tests {
             &self,
             _shutdown_tx: &ShutdownSender,
             _ui_tx: &watch::Sender<UiData>,
+            _hold_ui: bool,
         ) -> tokio::task::JoinHandle<()> {
             tokio::spawn(async {})
         }
@@ -541,6 +601,8 @@ mod tests {
                 latency_sum_ms: 0,
                 success_latency_sum_ms: 0,
                 runtime_errors: Vec::new(),
+                abort_reason: None,
+                threshold_failures: Vec::new(),
             })
         }
     }
@@ -550,6 +612,8 @@ mod tests {
             no_color: false,
             no_ui: false,
             no_splash: false,
+            hold_ui: false,
+            stop_file: None,
             no_charts: false,
             summary: false,
             show_selections: false,
@@ -560,7 +624,9 @@ mod tests {
             alloc_profiler_ms: None,
             alloc_profiler_dump_ms: None,
             alloc_profiler_dump_path: "mem.prof".to_owned(),
+            alloc_profiler_spike: false,
             metrics_max: 10_000,
+            urls_from_stdin: false,
         }
     }
 
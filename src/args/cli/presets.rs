@@ -1,11 +1,12 @@
 use clap::{Args, Subcommand};
 use std::time::Duration;
 
-use crate::args::{PositiveU64, PositiveUsize};
+use crate::args::{ExpectedStatus, Percentage, PositiveU64, PositiveUsize};
 
 use super::super::defaults::{default_charts_path, default_tmp_path};
 use super::super::parsers::{
-    parse_bool_env, parse_duration_arg, parse_positive_u64, parse_positive_usize,
+    parse_bool_env, parse_duration_arg, parse_duration_secs, parse_expected_status,
+    parse_percentage, parse_positive_u64, parse_positive_usize,
 };
 
 #[derive(Debug, Subcommand, Clone)]
@@ -30,8 +31,8 @@ pub struct PresetQuickArgs {
     #[arg(long, short)]
     pub url: String,
 
-    /// Duration of test (seconds)
-    #[arg(long = "duration", short = 't', default_value = "30", value_parser = parse_positive_u64)]
+    /// Duration of test (e.g., 30, 90s, 5m, 1h30m)
+    #[arg(long = "duration", short = 't', default_value = "30", value_parser = parse_duration_secs)]
     pub target_duration: PositiveU64,
 
     /// Max number of concurrent request tasks
@@ -49,8 +50,8 @@ pub struct PresetSoakArgs {
     #[arg(long, short)]
     pub url: String,
 
-    /// Duration of test (seconds)
-    #[arg(long = "duration", short = 't', default_value = "1800", value_parser = parse_positive_u64)]
+    /// Duration of test (e.g., 30, 90s, 5m, 1h30m)
+    #[arg(long = "duration", short = 't', default_value = "1800", value_parser = parse_duration_secs)]
     pub target_duration: PositiveU64,
 
     /// Max number of concurrent request tasks
@@ -68,8 +69,8 @@ pub struct PresetSpikeArgs {
     #[arg(long, short)]
     pub url: String,
 
-    /// Duration of test (seconds)
-    #[arg(long = "duration", short = 't', default_value = "120", value_parser = parse_positive_u64)]
+    /// Duration of test (e.g., 30, 90s, 5m, 1h30m)
+    #[arg(long = "duration", short = 't', default_value = "120", value_parser = parse_duration_secs)]
     pub target_duration: PositiveU64,
 
     /// Max number of concurrent request tasks
@@ -95,8 +96,8 @@ pub struct PresetDistributedArgs {
     #[arg(long = "agents", default_value = "3", value_parser = parse_positive_usize)]
     pub agents: PositiveUsize,
 
-    /// Duration of test (seconds)
-    #[arg(long = "duration", short = 't', default_value = "300", value_parser = parse_positive_u64)]
+    /// Duration of test (e.g., 30, 90s, 5m, 1h30m)
+    #[arg(long = "duration", short = 't', default_value = "300", value_parser = parse_duration_secs)]
     pub target_duration: PositiveU64,
 
     /// Controller listen address
@@ -137,15 +138,21 @@ pub struct CleanupArgs {
 
 #[derive(Debug, Args, Clone)]
 pub struct CompareArgs {
-    /// Left snapshot file (csv/json/jsonl)
+    /// Left snapshot file (csv/json/jsonl, or an hlog/hdr histogram log)
     pub left: String,
 
-    /// Right snapshot file (csv/json/jsonl)
+    /// Right snapshot file (csv/json/jsonl, or an hlog/hdr histogram log)
     pub right: String,
 
-    /// Expected HTTP status code
-    #[arg(long = "status", short = 's', default_value = "200")]
-    pub expected_status_code: u16,
+    /// Expected HTTP status codes (e.g., 200,204,301-302)
+    #[arg(
+        long = "status",
+        short = 's',
+        visible_alias = "expected-status",
+        default_value = "200",
+        value_parser = parse_expected_status
+    )]
+    pub expected_status_code: ExpectedStatus,
 
     /// Replay step size for compare mode (supports ms/s/m/h)
     #[arg(long = "replay-step", value_parser = parse_duration_arg)]
@@ -170,4 +177,12 @@ pub struct CompareArgs {
     /// Label for the right series
     #[arg(long = "right-label")]
     pub right_label: Option<String>,
+
+    /// Fail (exit code 2) when the right p99 latency exceeds the left by more than this percentage
+    #[arg(long = "regress-p99", value_parser = parse_percentage)]
+    pub regress_p99: Option<Percentage>,
+
+    /// Fail (exit code 2) when the right success rate is more than this many points below the left
+    #[arg(long = "regress-success", value_parser = parse_percentage)]
+    pub regress_success: Option<Percentage>,
 }
@@ -4,14 +4,18 @@ use std::time::Duration;
 use crate::metrics::MetricsRange;
 use crate::sinks::config::SinksConfig;
 
-use super::super::defaults::{default_charts_path, default_tmp_path};
+use super::super::defaults::{default_charts_path, default_history_path, default_tmp_path};
 use super::super::parsers::{
-    parse_bool_env, parse_connect_to, parse_duration_arg, parse_header, parse_positive_u64,
-    parse_positive_usize, parse_tls_version,
+    parse_bandwidth, parse_bool_env, parse_byte_size, parse_chart_dpi, parse_chart_height,
+    parse_chart_width, parse_connect_to, parse_duration_arg, parse_duration_secs,
+    parse_expected_status, parse_header, parse_link_rate, parse_percentage, parse_positive_u64,
+    parse_positive_usize, parse_slowest, parse_tag, parse_tls_version,
 };
 use super::super::types::{
-    ConnectToMapping, ControllerMode, HttpMethod, HttpVersion, LoadMode, LoadProfile, OutputFormat,
-    PositiveU64, PositiveUsize, Protocol, Scenario, TimeUnit, TlsVersion,
+    ArrivalDistribution, BandwidthLimit, ByteSize, CompatFormat, ConnectToMapping, ControllerMode,
+    DeadlineFormat, EchoConfigFormat, ExpectedStatus, GrpcMode, HttpMethod, HttpVersion,
+    JsonAssertion, LinkRate, LoadMode, LoadProfile, MqttMode, NotifyOn, OutputFormat, Percentage,
+    PositiveU64, PositiveUsize, Protocol, Scenario, SummaryFormat, TimeUnit, TlsVersion,
 };
 use super::presets::Command;
 
@@ -44,7 +48,53 @@ pub struct TesterArgs {
     )]
     pub protocol: Protocol,
 
-    /// Load model intent (for presets/workflows and metadata)
+    /// gRPC call shape; defaults to unary for grpc-unary and server-stream for grpc-streaming
+    #[arg(long = "grpc-mode", value_enum)]
+    pub grpc_mode: Option<GrpcMode>,
+
+    /// Keep each WebSocket connection open for N messages (cycling through --data-lines), counting each round-trip as a request
+    #[arg(long = "ws-messages-per-conn", value_name = "N", value_parser = parse_positive_usize)]
+    pub ws_messages_per_conn: Option<PositiveUsize>,
+
+    /// Delay between messages on a WebSocket connection (ms)
+    #[arg(
+        long = "ws-message-interval-ms",
+        value_parser = parse_positive_u64,
+        requires = "ws_messages_per_conn"
+    )]
+    pub ws_message_interval_ms: Option<PositiveU64>,
+
+    /// MQTT measurement: publish only, or publish and time delivery to a subscriber
+    #[arg(long = "mqtt-mode", value_enum, default_value = "publish")]
+    pub mqtt_mode: MqttMode,
+
+    /// MQTT topic to publish (and subscribe) on; defaults to the URL path
+    #[arg(long = "mqtt-topic", value_name = "TOPIC")]
+    pub mqtt_topic: Option<String>,
+
+    /// Reconnect each SSE stream after N events (default: keep it until the server closes it)
+    #[arg(long = "sse-events-per-conn", value_name = "N", value_parser = parse_positive_usize)]
+    pub sse_events_per_conn: Option<PositiveUsize>,
+
+    /// Reconnect each SSE stream once it has been open this long (supports ms/s/m/h)
+    #[arg(long = "sse-conn-duration", value_parser = parse_duration_arg)]
+    pub sse_conn_duration: Option<Duration>,
+
+    /// Send the last seen event id as Last-Event-ID when an SSE stream reconnects
+    #[arg(long = "sse-resume")]
+    pub sse_resume: bool,
+
+    /// Count a streaming connection (sse, websocket, grpc-streaming) as successful only after N messages
+    #[arg(
+        long = "min-events",
+        visible_alias = "min-messages",
+        value_name = "N",
+        value_parser = parse_positive_u64,
+        conflicts_with = "ws_messages_per_conn"
+    )]
+    pub min_events: Option<PositiveU64>,
+
+    /// Load model intent (for presets/workflows and metadata); `concurrency` runs closed-loop workers
     #[arg(
         long = "load-mode",
         default_value = "arrival",
@@ -57,7 +107,7 @@ pub struct TesterArgs {
     #[arg(long, short, help_heading = "Common Options")]
     pub url: Option<String>,
 
-    /// Read URLs from file (newline-delimited)
+    /// Read URLs from file (newline-delimited, optional leading weight per line); `-u -` reads stdin
     #[arg(
         long = "urls-from-file",
         conflicts_with = "rand_regex_url",
@@ -81,6 +131,10 @@ pub struct TesterArgs {
     #[arg(long = "dump-urls", value_parser = parse_positive_usize, requires = "rand_regex_url")]
     pub dump_urls: Option<PositiveUsize>,
 
+    /// Validate the config, print the resolved run plan, and exit without sending traffic
+    #[arg(long = "dry-run", conflicts_with_all = ["dump_urls", "replay"])]
+    pub dry_run: bool,
+
     /// HTTP headers in 'Key: Value' format (repeatable)
     #[arg(long, short = 'H', value_parser = parse_header, help_heading = "Common Options")]
     pub headers: Vec<(String, String)>,
@@ -109,11 +163,60 @@ pub struct TesterArgs {
     #[arg(long = "form", short = 'F', conflicts_with_all = ["data", "data_file", "data_lines"])]
     pub form: Vec<String>,
 
-    /// Basic authentication (username:password), or AWS credentials (access_key:secret_key)
+    /// Multipart file part as name=@path (repeatable); files are read once at startup
+    #[arg(
+        long = "form-file",
+        value_name = "NAME=@PATH",
+        conflicts_with_all = ["data", "data_file", "data_lines"]
+    )]
+    pub form_file: Vec<String>,
+
+    /// Basic authentication (username:password), or AWS credentials (access_key:secret_key); accepts @file or env:VAR
     #[arg(long = "basic-auth", short = 'a')]
     pub basic_auth: Option<String>,
 
-    /// AWS session token
+    /// Read --basic-auth (username:password) from a file; trailing newlines are trimmed
+    #[arg(long = "basic-auth-file", conflicts_with = "basic_auth")]
+    pub basic_auth_file: Option<String>,
+
+    /// Bearer token sent as Authorization: Bearer <token>; accepts @file or env:VAR
+    #[arg(long = "bearer-token", conflicts_with_all = ["basic_auth", "basic_auth_file", "aws_sigv4"])]
+    pub bearer_token: Option<String>,
+
+    /// Read the bearer token from a file; trailing newlines are trimmed
+    #[arg(
+        long = "bearer-token-file",
+        conflicts_with_all = ["bearer_token", "basic_auth", "basic_auth_file", "aws_sigv4"]
+    )]
+    pub bearer_token_file: Option<String>,
+
+    /// HTTP Digest authentication (username:password), answered after the server's 401 challenge; accepts @file or env:VAR
+    #[arg(
+        long = "digest-auth",
+        conflicts_with_all = ["basic_auth", "basic_auth_file", "bearer_token", "bearer_token_file", "aws_sigv4"]
+    )]
+    pub digest_auth: Option<String>,
+
+    /// OAuth2 token endpoint; strest fetches a client-credentials token before the run and refreshes it
+    #[arg(
+        long = "oauth2-token-url",
+        conflicts_with_all = ["basic_auth", "basic_auth_file", "bearer_token", "bearer_token_file", "digest_auth", "aws_sigv4"]
+    )]
+    pub oauth2_token_url: Option<String>,
+
+    /// OAuth2 client ID
+    #[arg(long = "oauth2-client-id", requires = "oauth2_token_url")]
+    pub oauth2_client_id: Option<String>,
+
+    /// OAuth2 client secret; accepts @file or env:VAR
+    #[arg(long = "oauth2-client-secret", requires = "oauth2_token_url")]
+    pub oauth2_client_secret: Option<String>,
+
+    /// OAuth2 scope sent with the token request
+    #[arg(long = "oauth2-scope", requires = "oauth2_token_url")]
+    pub oauth2_scope: Option<String>,
+
+    /// AWS session token; accepts @file or env:VAR
     #[arg(long = "aws-session")]
     pub aws_session: Option<String>,
 
@@ -121,6 +224,18 @@ pub struct TesterArgs {
     #[arg(long = "aws-sigv4")]
     pub aws_sigv4: Option<String>,
 
+    /// Seed for fake body data ({{name}}, {{email}}, {{lorem:N}}, ...) to make payloads reproducible
+    #[arg(long = "seed")]
+    pub seed: Option<u64>,
+
+    /// Treat the --data/--data-file body as a JSON template; whole-string placeholders keep their type ("{{int:1:9}}" becomes a number)
+    #[arg(long = "json-template", conflicts_with_all = ["data_lines", "form", "form_file"])]
+    pub json_template: bool,
+
+    /// Send unknown {{...}} text in the request body as written instead of failing at startup
+    #[arg(long = "literal-placeholders")]
+    pub literal_placeholders: bool,
+
     /// Request body from file
     #[arg(long = "data-file", short = 'D', conflicts_with_all = ["data", "data_lines"])]
     pub data_file: Option<String>,
@@ -129,12 +244,24 @@ pub struct TesterArgs {
     #[arg(long = "data-lines", short = 'Z', conflicts_with_all = ["data", "data_file"])]
     pub data_lines: Option<String>,
 
-    /// Duration of test (seconds)
+    /// Rotate request bodies through the files in a directory; the extension sets Content-Type unless one is given
+    #[arg(
+        long = "data-dir",
+        value_name = "PATH",
+        conflicts_with_all = ["data", "data_file", "data_lines", "form", "form_file", "json_template"]
+    )]
+    pub data_dir: Option<String>,
+
+    /// Fail when the --data-dir files add up to more than this (default: 256MiB)
+    #[arg(long = "data-dir-max-bytes", value_parser = parse_byte_size, requires = "data_dir")]
+    pub data_dir_max_bytes: Option<ByteSize>,
+
+    /// Duration of test (e.g., 30, 90s, 5m, 1h30m)
     #[arg(
         long = "duration",
         short = 't',
         default_value = "30",
-        value_parser = parse_positive_u64,
+        value_parser = parse_duration_secs,
         help_heading = "Common Options"
     )]
     pub target_duration: PositiveU64,
@@ -143,18 +270,28 @@ pub struct TesterArgs {
     #[arg(long = "wait-ongoing-requests-after-deadline")]
     pub wait_ongoing_requests_after_deadline: bool,
 
+    /// Start traffic on the next whole wall-clock second and pace rate ticks on second boundaries
+    #[arg(long = "align-to-second")]
+    pub align_to_second: bool,
+
+    /// Space requests around --rate or a load profile by this distribution instead of releasing whole-second batches
+    #[arg(long = "arrival-distribution", value_enum)]
+    pub arrival_distribution: Option<ArrivalDistribution>,
+
     /// Stop after N total requests
     #[arg(long = "requests", short = 'n', value_parser = parse_positive_u64, help_heading = "Common Options")]
     pub requests: Option<PositiveU64>,
 
-    /// Expected HTTP status code
+    /// Expected HTTP status codes: a code or a comma list of codes and ranges (200,204,301-302)
     #[arg(
         long = "status",
         short = 's',
+        visible_alias = "expected-status",
         default_value = "200",
+        value_parser = parse_expected_status,
         help_heading = "Common Options"
     )]
-    pub expected_status_code: u16,
+    pub expected_status_code: ExpectedStatus,
 
     /// Request timeout (supports ms/s/m/h)
     #[arg(
@@ -173,10 +310,35 @@ pub struct TesterArgs {
     #[arg(long = "disable-keepalive")]
     pub disable_keepalive: bool,
 
+    /// Close each worker's connection after N requests and open a fresh one (HTTP only)
+    #[arg(
+        long = "requests-per-connection",
+        value_name = "N",
+        value_parser = parse_positive_u64,
+        conflicts_with = "disable_keepalive"
+    )]
+    pub requests_per_connection: Option<PositiveU64>,
+
     /// Disable compression (gzip, brotli, deflate)
     #[arg(long = "disable-compression")]
     pub disable_compression: bool,
 
+    /// Keep a per-worker cookie jar so Set-Cookie from one scenario step is sent on later steps
+    #[arg(long = "cookies")]
+    pub cookies: bool,
+
+    /// Keep this sticky-session cookie per virtual user across iterations and report which backends served it
+    #[arg(long = "session-cookie", value_name = "NAME")]
+    pub session_cookie: Option<String>,
+
+    /// Response header naming the serving backend for --session-cookie (falls back to Server)
+    #[arg(
+        long = "backend-header",
+        default_value = "X-Backend",
+        requires = "session_cookie"
+    )]
+    pub backend_header: String,
+
     /// Max idle connections per host in the HTTP pool (0 disables idle pooling)
     #[arg(long = "pool-max-idle-per-host", value_parser = parse_positive_usize)]
     pub pool_max_idle_per_host: Option<PositiveUsize>,
@@ -205,10 +367,30 @@ pub struct TesterArgs {
     #[arg(long, help_heading = "Common Options")]
     pub no_charts: bool,
 
+    /// Skip end-of-run chart rendering but keep metric logs and live sink/stream updates
+    #[arg(long = "no-final-charts")]
+    pub no_final_charts: bool,
+
     /// Latency percentile chart bucket size in milliseconds
     #[arg(long = "charts-latency-bucket-ms", default_value = "100", value_parser = parse_positive_u64)]
     pub charts_latency_bucket_ms: PositiveU64,
 
+    /// Chart image width in pixels (320-7680)
+    #[arg(long = "chart-width", default_value = "1600", value_parser = parse_chart_width)]
+    pub chart_width: u32,
+
+    /// Chart image height in pixels (200-4320)
+    #[arg(long = "chart-height", default_value = "600", value_parser = parse_chart_height)]
+    pub chart_height: u32,
+
+    /// Chart DPI (72-600); scales text, margins, and markers relative to 96
+    #[arg(long = "chart-dpi", default_value = "96", value_parser = parse_chart_dpi)]
+    pub chart_dpi: u32,
+
+    /// Also plot achieved throughput against latency (USL capacity curve)
+    #[arg(long = "usl-chart")]
+    pub usl_chart: bool,
+
     /// Enable verbose logging (sets log level to debug unless overridden by STREST_LOG/RUST_LOG)
     #[arg(long, short = 'v', alias = "debug", help_heading = "Common Options")]
     pub verbose: bool,
@@ -217,6 +399,10 @@ pub struct TesterArgs {
     #[arg(long, help_heading = "Common Options")]
     pub config: Option<String>,
 
+    /// Named `[profiles.<name>]` section merged over the base config
+    #[arg(long = "config-profile", help_heading = "Common Options")]
+    pub config_profile: Option<String>,
+
     /// Path to store temporary run data
     #[arg(long = "tmp-path", default_value_t = default_tmp_path())]
     pub tmp_path: String,
@@ -229,6 +415,18 @@ pub struct TesterArgs {
     #[arg(long = "warmup", value_parser = parse_duration_arg)]
     pub warmup: Option<Duration>,
 
+    /// File of requests (`URL` or `METHOD URL` per line) sent before measurement to warm caches
+    #[arg(long = "warmup-requests-file")]
+    pub warmup_requests_file: Option<String>,
+
+    /// Log the key run parameters (secrets redacted) when the run starts
+    #[arg(long = "echo-config-on-start")]
+    pub echo_config_on_start: bool,
+
+    /// Format of the --echo-config-on-start record (text or json)
+    #[arg(long = "echo-config-format", default_value = "text", value_enum)]
+    pub echo_config_format: EchoConfigFormat,
+
     /// Output file to write results to
     #[arg(long = "output", short = 'o', help_heading = "Common Options")]
     pub output: Option<String>,
@@ -253,10 +451,38 @@ pub struct TesterArgs {
     #[arg(long = "export-jsonl")]
     pub export_jsonl: Option<String>,
 
+    /// Export the final latency histogram as an HdrHistogram log
+    #[arg(long = "export-histogram", value_name = "PATH")]
+    pub export_histogram: Option<String>,
+
     /// Write per-request metrics to a sqlite database
     #[arg(long = "db-url")]
     pub db_url: Option<String>,
 
+    /// Periodically write the running summary to this JSON file (crash-safe partial results)
+    #[arg(
+        long = "checkpoint-out",
+        alias = "checkpoint-path",
+        value_name = "PATH"
+    )]
+    pub checkpoint_out: Option<String>,
+
+    /// Seconds between checkpoint writes (default: 30)
+    #[arg(
+        long = "checkpoint-interval-secs",
+        value_parser = parse_positive_u64,
+        requires = "checkpoint_out"
+    )]
+    pub checkpoint_interval_secs: Option<PositiveU64>,
+
+    /// Append one aggregated sample per sink tick to this JSONL file while the run progresses
+    #[arg(long = "stream-samples", value_name = "PATH")]
+    pub stream_samples: Option<String>,
+
+    /// Continue the run saved in --checkpoint-out instead of starting from zero
+    #[arg(long = "resume")]
+    pub resume: bool,
+
     /// Number of log shards to use for metrics logging (default: 1)
     #[arg(long = "log-shards", default_value = "1", value_parser = parse_positive_usize)]
     pub log_shards: PositiveUsize,
@@ -269,6 +495,14 @@ pub struct TesterArgs {
     #[arg(long = "no-splash")]
     pub no_splash: bool,
 
+    /// Keep the final TUI frame on screen after the run until a key is pressed
+    #[arg(long = "hold-ui", conflicts_with = "no_ui")]
+    pub hold_ui: bool,
+
+    /// Stop the run gracefully (drain and summarize) once this file exists
+    #[arg(long = "stop-file", alias = "stop-signal-file", value_name = "PATH")]
+    pub stop_file: Option<String>,
+
     /// UI chart window length in milliseconds (default: 10000)
     #[arg(
         long = "ui-window-ms",
@@ -278,10 +512,87 @@ pub struct TesterArgs {
     )]
     pub ui_window_ms: PositiveU64,
 
+    /// Overlay a recorded run (CSV/JSON/JSONL export) on the live TUI charts
+    #[arg(long = "baseline", value_name = "FILE", conflicts_with = "no_ui")]
+    pub baseline: Option<String>,
+
+    /// Label for the --baseline series (defaults to the file name)
+    #[arg(long = "baseline-label", requires = "baseline")]
+    pub baseline_label: Option<String>,
+
     /// Print summary at the end of the run (implied by --no-tui)
     #[arg(long = "summary", help_heading = "Common Options")]
     pub summary: bool,
 
+    /// Render the end-of-run summary through a `{{variable}}` template file (implies --summary)
+    #[arg(long = "summary-template", value_name = "FILE")]
+    pub summary_template: Option<String>,
+
+    /// Also write the end-of-run summary to this file (implies --summary)
+    #[arg(long = "summary-out", visible_alias = "tee", value_name = "FILE")]
+    pub summary_out: Option<String>,
+
+    /// Append each run's summary to this history file and print the delta vs the previous matching run
+    #[arg(long = "history-file", value_name = "FILE", default_value_t = default_history_path())]
+    pub history_file: String,
+
+    /// Do not record the run in the history file or print the previous-run delta
+    #[arg(long = "no-history")]
+    pub no_history: bool,
+
+    /// POST a run summary to this URL when the run ends (Slack incoming webhooks get a Slack message)
+    #[arg(long = "notify-webhook", value_name = "URL")]
+    pub notify_webhook: Option<String>,
+
+    /// When to call --notify-webhook
+    #[arg(
+        long = "notify-on",
+        default_value = "always",
+        value_enum,
+        requires = "notify_webhook"
+    )]
+    pub notify_on: NotifyOn,
+
+    /// Report how many responses used each negotiated HTTP version in the summary and JSON export
+    #[arg(long = "protocol-version-report")]
+    pub protocol_version_report: bool,
+
+    /// Split average connect, first-byte and total latency by new vs reused connections in the summary
+    #[arg(long = "connection-timing")]
+    pub connection_timing: bool,
+
+    /// Fail the run when any chart cannot be rendered, instead of logging the error
+    #[arg(long = "strict-charts")]
+    pub strict_charts: bool,
+
+    /// Print p50/p90/p99 latency separately for 2xx, 3xx, 4xx and 5xx responses in the summary
+    #[arg(long = "per-status-latency")]
+    pub per_status_latency: bool,
+
+    /// Print the N highest-latency requests (url, status, latency) at the end of the run (max 1000)
+    #[arg(long = "slowest", value_name = "N", value_parser = parse_slowest)]
+    pub slowest: Option<PositiveUsize>,
+
+    /// Print a p50-p99.99 and max latency table for all and successful requests in the summary
+    #[arg(long = "summary-percentile-table")]
+    pub summary_percentile_table: bool,
+
+    /// Add the geometric mean and standard deviation of latency to the summary
+    #[arg(long = "stats-extended")]
+    pub stats_extended: bool,
+
+    /// Print the end-of-run summary in a wrk- or hey-compatible layout (implies --summary)
+    #[arg(long = "compat", value_enum, conflicts_with = "summary_template")]
+    pub compat: Option<CompatFormat>,
+
+    /// End-of-run summary layout; `plain` prints raw `key: value` lines for diffing (implies --summary)
+    #[arg(
+        long = "summary-format",
+        value_enum,
+        conflicts_with_all = ["summary_template", "compat"]
+    )]
+    pub summary_format: Option<SummaryFormat>,
+
     /// Include a full selection summary in the final output
     #[arg(long = "show-selections")]
     pub show_selections: bool,
@@ -326,6 +637,34 @@ pub struct TesterArgs {
     )]
     pub replay_snapshot_format: String,
 
+    /// Render replay dashboard frames to an animated GIF (.gif) or a PNG frame directory
+    #[arg(
+        long = "replay-video",
+        value_name = "PATH",
+        requires = "replay",
+        help_heading = "Advanced Options"
+    )]
+    pub replay_video: Option<String>,
+
+    /// Write the replay window's chart data as JSON instead of opening the replay UI
+    #[arg(
+        long = "replay-export-chart-data",
+        value_name = "PATH",
+        requires = "replay",
+        conflicts_with = "replay_video",
+        help_heading = "Advanced Options"
+    )]
+    pub replay_export_chart_data: Option<String>,
+
+    /// Render the replay window's charts to --charts-path and exit, without the replay UI
+    #[arg(
+        long = "export-charts",
+        requires = "replay",
+        conflicts_with_all = ["replay_video", "replay_export_chart_data"],
+        help_heading = "Advanced Options"
+    )]
+    pub replay_export_charts: bool,
+
     /// Minimum TLS version (1.0, 1.1, 1.2, 1.3)
     #[arg(long = "tls-min", value_parser = parse_tls_version)]
     pub tls_min: Option<TlsVersion>,
@@ -411,6 +750,10 @@ pub struct TesterArgs {
     #[arg(long = "rate", short = 'q', value_parser = parse_positive_u64, required = false, help_heading = "Common Options")]
     pub rate_limit: Option<PositiveU64>,
 
+    /// Replay a rate timeline from a CSV of `second,rpm` rows, ramping between rows; overrides --rate and [load]
+    #[arg(long = "rate-schedule")]
+    pub rate_schedule: Option<String>,
+
     /// Burst delay (ignored if --rate is set)
     #[arg(long = "burst-delay", value_parser = parse_duration_arg)]
     pub burst_delay: Option<Duration>,
@@ -419,11 +762,128 @@ pub struct TesterArgs {
     #[arg(long = "burst-rate", default_value = "1", value_parser = parse_positive_usize)]
     pub burst_rate: PositiveUsize,
 
+    /// Search for the highest rate whose rolling p99 stays under --target-p99-ms, starting from --rate
+    #[arg(
+        long = "find-max-rate",
+        requires = "target_p99_ms",
+        conflicts_with = "burst_delay"
+    )]
+    pub find_max_rate: bool,
+
+    /// p99 latency target in milliseconds for --find-max-rate
+    #[arg(long = "target-p99-ms", value_parser = parse_positive_u64, requires = "find_max_rate")]
+    pub target_p99_ms: Option<PositiveU64>,
+
     /// Correct latency to avoid coordinated omission (ignored if --rate is not set)
     #[arg(long = "latency-correction")]
     pub latency_correction: bool,
 
-    /// Override DNS resolution and port for a host (repeatable)
+    /// Print a DNS/TCP/TLS/first-byte connectivity report before the run
+    #[arg(long = "diagnose")]
+    pub diagnose: bool,
+
+    /// Like --diagnose, but abort the run when any diagnostic step fails
+    #[arg(long = "diagnose-strict")]
+    pub diagnose_strict: bool,
+
+    /// Cap bytes sent + received per second across all tasks (e.g. 10MB/s, 512KiB/s; http only)
+    #[arg(long = "max-bandwidth", value_parser = parse_bandwidth)]
+    pub max_bandwidth: Option<BandwidthLimit>,
+
+    /// Throttle each task's response reads to this link speed (e.g. 1mbit, 512kbit; per task, http only)
+    #[arg(long = "sim-downlink", value_parser = parse_link_rate)]
+    pub sim_downlink: Option<LinkRate>,
+
+    /// Delay each request send by this fixed latency (e.g. 100ms; per task, http only)
+    #[arg(long = "sim-latency", value_parser = parse_duration_arg)]
+    pub sim_latency: Option<Duration>,
+
+    /// Cap new connections per second across all tasks, separate from --rate (http only)
+    #[arg(long = "connect-rate", value_parser = parse_positive_u64)]
+    pub connect_rate: Option<PositiveU64>,
+
+    /// Stop the run once this many response bytes have been received (e.g. 10GB, 512MiB)
+    #[arg(long = "max-bytes", value_parser = parse_byte_size)]
+    pub max_bytes: Option<ByteSize>,
+
+    /// Abort the run once this many requests have failed (exit code 5)
+    #[arg(long = "abort-on-errors", value_parser = parse_positive_u64)]
+    pub abort_on_errors: Option<PositiveU64>,
+
+    /// Abort the run once the error rate exceeds this percentage, e.g. 5 or 2.5% (exit code 5)
+    #[arg(long = "abort-on-error-rate", value_parser = parse_percentage)]
+    pub abort_on_error_rate: Option<Percentage>,
+
+    /// Requests to observe before --abort-on-error-rate can trip
+    #[arg(
+        long = "abort-min-requests",
+        default_value = "100",
+        value_parser = parse_positive_u64
+    )]
+    pub abort_min_requests: PositiveU64,
+
+    /// Verify responses echo the request-id header sent with each request (http only)
+    #[arg(long = "verify-request-id-echo")]
+    pub verify_request_id_echo: bool,
+
+    /// Correlation header checked by --verify-request-id-echo
+    #[arg(long = "request-id-header", default_value = "X-Request-Id")]
+    pub request_id_header: String,
+
+    /// Send the remaining --timeout budget in this header on each request (http only)
+    #[arg(long = "deadline-header")]
+    pub deadline_header: Option<String>,
+
+    /// Format of the --deadline-header value
+    #[arg(
+        long = "deadline-format",
+        default_value = "relative-ms",
+        value_enum,
+        requires = "deadline_header"
+    )]
+    pub deadline_format: DeadlineFormat,
+
+    /// Send a fresh UUID in this header on each request (http only)
+    #[arg(long = "correlation-header")]
+    pub correlation_header: Option<String>,
+
+    /// Write each request's generated header values and outcome to this CSV file (http only)
+    #[arg(long = "correlation-log")]
+    pub correlation_log: Option<String>,
+
+    /// Fail responses whose body is N bytes or larger (http only)
+    #[arg(long = "assert-response-bytes-below", value_parser = parse_positive_u64)]
+    pub assert_response_bytes_below: Option<PositiveU64>,
+
+    /// Fail responses whose body is N bytes or smaller (http only)
+    #[arg(long = "assert-response-bytes-above")]
+    pub assert_response_bytes_above: Option<u64>,
+
+    /// Fail responses whose body does not contain this text (reads the body; http only)
+    #[arg(long = "assert-body-contains", value_name = "TEXT")]
+    pub assert_body_contains: Option<String>,
+
+    /// Fail responses whose body does not match this regex (reads the body; http only)
+    #[arg(long = "assert-body-regex", value_name = "PATTERN")]
+    pub assert_body_regex: Option<String>,
+
+    /// Fail responses whose JSON body does not have this value, e.g. $.status=ok (http only)
+    #[arg(long = "assert-json", value_name = "PATH=VALUE")]
+    pub assert_json: Option<JsonAssertion>,
+
+    /// Fail the run (exit code 2) when the success rate is below this percentage (e.g. 99.5)
+    #[arg(long = "assert-success-rate", value_parser = parse_percentage)]
+    pub assert_success_rate: Option<Percentage>,
+
+    /// Fail the run (exit code 2) when p99 latency exceeds this many milliseconds
+    #[arg(long = "assert-p99-ms")]
+    pub assert_p99_ms: Option<u64>,
+
+    /// Fail the run (exit code 2) when more than N requests failed
+    #[arg(long = "assert-max-errors")]
+    pub assert_max_errors: Option<u64>,
+
+    /// Override DNS resolution and port for a host (repeatable); a trailing `:<ca.pem>` trusts that CA for the destination
     #[arg(long = "connect-to", value_parser = parse_connect_to)]
     pub connect_to: Vec<ConnectToMapping>,
 
@@ -439,6 +899,10 @@ pub struct TesterArgs {
     #[arg(long = "ipv4")]
     pub ipv4_only: bool,
 
+    /// Resolve hostnames through this DNS-over-HTTPS endpoint instead of the system resolver (http only)
+    #[arg(long = "doh-url", value_name = "URL")]
+    pub doh_url: Option<String>,
+
     /// Do not perform a DNS pre-lookup
     #[arg(long = "no-pre-lookup")]
     pub no_pre_lookup: bool,
@@ -470,6 +934,10 @@ pub struct TesterArgs {
     #[arg(long = "controller-mode", default_value = "auto", value_enum)]
     pub controller_mode: ControllerMode,
 
+    /// Also run a local agent on the controller host that contributes load
+    #[arg(long = "controller-also-runs")]
+    pub controller_also_runs: bool,
+
     /// Control-plane HTTP listen address (e.g. 127.0.0.1:9010)
     #[arg(long = "control-listen")]
     pub control_listen: Option<String>,
@@ -526,6 +994,10 @@ pub struct TesterArgs {
     )]
     pub agent_heartbeat_timeout_ms: PositiveU64,
 
+    /// Serve read-only JSON agent liveness on this address (e.g. :9100)
+    #[arg(long = "agent-health-listen")]
+    pub agent_health_listen: Option<String>,
+
     /// Stream summary interval in milliseconds for distributed mode (optional)
     /// Only applies when distributed stream summaries are enabled.
     #[arg(long = "stream-interval-ms", value_parser = parse_positive_u64)]
@@ -535,11 +1007,19 @@ pub struct TesterArgs {
     #[arg(long = "stream-summaries")]
     pub distributed_stream_summaries: bool,
 
+    /// Print per-agent achieved vs assigned rate after a distributed run
+    #[arg(long = "agent-breakdown")]
+    pub agent_breakdown: bool,
+
+    /// Print requests, success rate, and p99 per agent before the merged distributed summary
+    #[arg(long = "per-agent-summary")]
+    pub per_agent_summary: bool,
+
     /// Enable HTTP/3 (requires rustls + http3 support)
     #[arg(long = "http3")]
     pub http3: bool,
 
-    /// Range, in seconds, of metrics to collect for charts (e.g., 10-30)
+    /// Ranges, in seconds, of metrics to collect for charts (e.g., 10-30 or 10-20,60-70)
     #[arg(long = "metrics-range", short = 'M', value_parser, required = false)]
     pub metrics_range: Option<MetricsRange>,
 
@@ -567,6 +1047,14 @@ pub struct TesterArgs {
     #[arg(long = "alloc-profiler-dump-path", default_value = "./alloc-prof")]
     pub alloc_profiler_dump_path: String,
 
+    /// Dump a heap profile when a request takes at least this long (requires alloc-profiler feature, ms)
+    #[arg(long = "alloc-profiler-spike-ms", value_parser = parse_positive_u64)]
+    pub alloc_profiler_spike_ms: Option<PositiveU64>,
+
+    /// Dump a heap profile when in-flight requests reach this count (requires alloc-profiler feature)
+    #[arg(long = "alloc-profiler-spike-inflight", value_parser = parse_positive_u64)]
+    pub alloc_profiler_spike_inflight: Option<PositiveU64>,
+
     #[arg(skip)]
     pub scenario: Option<Scenario>,
 
@@ -590,9 +1078,17 @@ pub struct TesterArgs {
     #[arg(long = "service-name")]
     pub service_name: Option<String>,
 
+    /// Label the run with a key=value tag for sinks, exports, and the summary (repeatable)
+    #[arg(long = "tag", value_parser = parse_tag)]
+    pub tags: Vec<(String, String)>,
+
     #[arg(skip)]
     pub sinks: Option<SinksConfig>,
 
     #[arg(skip)]
     pub distributed_silent: bool,
+
+    /// Distributed run id when executing as an agent; labels pushed sink metrics.
+    #[arg(skip)]
+    pub run_id: Option<String>,
 }
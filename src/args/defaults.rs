@@ -20,6 +20,13 @@ pub(crate) fn default_tmp_path() -> String {
         .into_owned()
 }
 
+pub(crate) fn default_history_path() -> String {
+    default_base_dir()
+        .join("history.jsonl")
+        .to_string_lossy()
+        .into_owned()
+}
+
 fn default_base_dir() -> PathBuf {
     if let Some(home) = user_home_dir() {
         return home.join(".strest");
@@ -11,14 +11,16 @@ mod tests;
 
 pub use cli::{CleanupArgs, Command, CompareArgs, TesterArgs};
 pub use types::{
-    ConnectToMapping, ControllerMode, HttpMethod, HttpVersion, LoadMode, LoadProfile, LoadStage,
-    OutputFormat, PositiveU64, PositiveUsize, Protocol, Scenario, ScenarioStep, TimeUnit,
-    TlsVersion,
+    ArrivalDistribution, BandwidthLimit, ByteSize, ChartSetting, CompatFormat, ConnectToMapping,
+    ControllerMode, DeadlineFormat, EchoConfigFormat, ExpectedStatus, ExtractSource, GrpcMode,
+    HttpMethod, HttpVersion, JsonAssertion, JsonPathSegment, LinkRate, LoadMode, LoadProfile,
+    LoadStage, MqttMode, NotifyOn, OutputFormat, Percentage, PositiveU64, PositiveUsize, Protocol,
+    Scenario, ScenarioStep, SineWave, SummaryFormat, ThinkTime, TimeUnit, TlsVersion,
 };
 
 pub(crate) use defaults::DEFAULT_USER_AGENT;
 #[cfg(test)]
-pub(crate) use defaults::{default_charts_path, default_tmp_path};
-pub(crate) use parsers::{parse_connect_to, parse_header};
+pub(crate) use defaults::{default_charts_path, default_history_path, default_tmp_path};
+pub(crate) use parsers::{parse_connect_to, parse_header, parse_tag, tag_map};
 #[cfg(test)]
 pub(crate) use test_support::parse_test_args;
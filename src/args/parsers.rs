@@ -1,8 +1,44 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
-use super::types::{ConnectToMapping, PositiveU64, PositiveUsize, TlsVersion};
+use super::types::{
+    BandwidthLimit, ByteSize, ChartSetting, ConnectToMapping, ExpectedStatus, LinkRate, Percentage,
+    PositiveU64, PositiveUsize, TlsVersion,
+};
 use crate::error::{AppError, AppResult, ConnectToPortKind, ValidationError};
 
+/// Tag keys the sinks already use as labels, so a tag cannot shadow them.
+const RESERVED_TAG_KEYS: [&str; 6] = ["run", "run_id", "protocol", "job", "stat", "quantile"];
+
+/// Parses a `key=value` run tag. Keys are label-safe identifiers
+/// (`[A-Za-z_][A-Za-z0-9_]*`) so every sink can carry them; values are kept verbatim.
+pub(crate) fn parse_tag(s: &str) -> Result<(String, String), ValidationError> {
+    let invalid = || ValidationError::InvalidTag {
+        value: s.to_owned(),
+    };
+    let (key, value) = s.split_once('=').ok_or_else(invalid)?;
+    let key = key.trim();
+    let mut chars = key.chars();
+    let valid_key = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if !valid_key || value.is_empty() {
+        return Err(invalid());
+    }
+    if RESERVED_TAG_KEYS.contains(&key) {
+        return Err(ValidationError::ReservedTagKey {
+            key: key.to_owned(),
+        });
+    }
+    Ok((key.to_owned(), value.to_owned()))
+}
+
+/// Collects run tags into a sorted map; a repeated key keeps its last value.
+pub(crate) fn tag_map(tags: &[(String, String)]) -> BTreeMap<String, String> {
+    tags.iter().cloned().collect()
+}
+
 pub(crate) fn parse_header(s: &str) -> Result<(String, String), ValidationError> {
     match s.split_once(':') {
         Some((key, value)) => Ok((key.trim().to_owned(), value.trim().to_owned())),
@@ -20,6 +56,66 @@ pub(super) fn parse_positive_usize(s: &str) -> AppResult<PositiveUsize> {
     s.parse::<PositiveUsize>().map_err(AppError::from)
 }
 
+/// Upper bound for `--slowest`, keeping the collector's heap small.
+pub(crate) const MAX_SLOWEST: usize = 1_000;
+
+pub(super) fn parse_slowest(s: &str) -> AppResult<PositiveUsize> {
+    let value: usize = s
+        .parse()
+        .map_err(|err| AppError::validation(ValidationError::InvalidNumber { source: err }))?;
+    ensure_slowest(value).map_err(AppError::from)
+}
+
+pub(crate) fn ensure_slowest(value: usize) -> Result<PositiveUsize, ValidationError> {
+    if value > MAX_SLOWEST {
+        return Err(ValidationError::SlowestOutOfRange {
+            value,
+            max: MAX_SLOWEST,
+        });
+    }
+    PositiveUsize::try_from(value).map_err(|_err| ValidationError::SlowestOutOfRange {
+        value,
+        max: MAX_SLOWEST,
+    })
+}
+
+pub(super) fn parse_bandwidth(s: &str) -> AppResult<BandwidthLimit> {
+    s.parse::<BandwidthLimit>().map_err(AppError::from)
+}
+
+pub(super) fn parse_link_rate(s: &str) -> AppResult<LinkRate> {
+    s.parse::<LinkRate>().map_err(AppError::from)
+}
+
+pub(super) fn parse_byte_size(s: &str) -> AppResult<ByteSize> {
+    s.parse::<ByteSize>().map_err(AppError::from)
+}
+
+pub(super) fn parse_percentage(s: &str) -> AppResult<Percentage> {
+    s.parse::<Percentage>().map_err(AppError::from)
+}
+
+pub(super) fn parse_expected_status(s: &str) -> AppResult<ExpectedStatus> {
+    s.parse::<ExpectedStatus>().map_err(AppError::from)
+}
+
+pub(super) fn parse_chart_width(s: &str) -> AppResult<u32> {
+    parse_chart_setting(s, ChartSetting::Width)
+}
+
+pub(super) fn parse_chart_height(s: &str) -> AppResult<u32> {
+    parse_chart_setting(s, ChartSetting::Height)
+}
+
+pub(super) fn parse_chart_dpi(s: &str) -> AppResult<u32> {
+    parse_chart_setting(s, ChartSetting::Dpi)
+}
+
+fn parse_chart_setting(s: &str, setting: ChartSetting) -> AppResult<u32> {
+    let value = s.trim().parse::<u32>()?;
+    Ok(setting.check(value)?)
+}
+
 pub(super) fn parse_tls_version(s: &str) -> AppResult<TlsVersion> {
     s.parse::<TlsVersion>()
 }
@@ -35,8 +131,9 @@ pub(crate) fn parse_bool_env(s: &str) -> AppResult<bool> {
 }
 
 pub(crate) fn parse_connect_to(s: &str) -> Result<ConnectToMapping, ValidationError> {
-    let parts: Vec<&str> = s.split(':').collect();
-    if parts.len() != 4 {
+    // The optional CA path is the remainder, so it may contain `:` itself.
+    let parts: Vec<&str> = s.splitn(5, ':').collect();
+    if parts.len() < 4 {
         return Err(ValidationError::InvalidConnectToFormat {
             value: s.to_owned(),
         });
@@ -82,47 +179,77 @@ pub(crate) fn parse_connect_to(s: &str) -> Result<ConnectToMapping, ValidationEr
             value: s.to_owned(),
         });
     }
+    let cacert = match parts.get(4).map(|path| path.trim()) {
+        Some("") => {
+            return Err(ValidationError::InvalidConnectToFormat {
+                value: s.to_owned(),
+            });
+        }
+        path => path.map(str::to_owned),
+    };
     Ok(ConnectToMapping {
         source_host: source_host.to_owned(),
         source_port,
         target_host: target_host.to_owned(),
         target_port,
+        cacert,
     })
 }
 
+/// Parses a duration such as `500ms`, `30s`, `5m` or `1h30m`; a bare number
+/// is seconds.
 pub(crate) fn parse_duration_arg(s: &str) -> AppResult<Duration> {
     let value = s.trim();
     if value.is_empty() {
         return Err(AppError::validation(ValidationError::DurationEmpty));
     }
+    let invalid_format = || {
+        AppError::validation(ValidationError::InvalidDurationFormat {
+            value: value.to_owned(),
+        })
+    };
 
-    let mut digits_len = 0usize;
-    for ch in value.chars() {
-        if ch.is_ascii_digit() {
-            digits_len = digits_len.saturating_add(1);
-        } else {
-            break;
+    let mut duration = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits_len = rest
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits_len == 0 {
+            return Err(invalid_format());
         }
-    }
-    if digits_len == 0 {
-        return Err(AppError::validation(
-            ValidationError::InvalidDurationFormat {
+        let (num_part, tail) = rest.split_at(digits_len);
+        let unit_len = tail
+            .find(|ch: char| ch.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        // Only a lone number may omit its unit: `1h30` is ambiguous.
+        if unit.is_empty() && num_part.len() != value.len() {
+            return Err(invalid_format());
+        }
+        let number: u64 = num_part.parse().map_err(|err| {
+            AppError::validation(ValidationError::InvalidDurationNumber {
                 value: value.to_owned(),
-            },
-        ));
+                source: err,
+            })
+        })?;
+        duration = duration
+            .checked_add(duration_part(number, unit)?)
+            .ok_or_else(|| AppError::validation(ValidationError::DurationOverflow))?;
+        rest = tail;
     }
-    let (num_part, unit_part) = value.split_at(digits_len);
-    let number: u64 = num_part.parse().map_err(|err| {
-        AppError::validation(ValidationError::InvalidDurationNumber {
-            value: value.to_owned(),
-            source: err,
-        })
-    })?;
 
-    let unit = if unit_part.is_empty() { "s" } else { unit_part };
+    if duration.as_millis() == 0 {
+        return Err(AppError::validation(ValidationError::DurationZero));
+    }
+
+    Ok(duration)
+}
+
+fn duration_part(number: u64, unit: &str) -> AppResult<Duration> {
     let duration = match unit {
         "ms" => Duration::from_millis(number),
-        "s" => Duration::from_secs(number),
+        "" | "s" => Duration::from_secs(number),
         "m" => {
             let secs = number
                 .checked_mul(60)
@@ -142,10 +269,18 @@ pub(crate) fn parse_duration_arg(s: &str) -> AppResult<Duration> {
             }));
         }
     };
+    Ok(duration)
+}
 
-    if duration.as_millis() == 0 {
-        return Err(AppError::validation(ValidationError::DurationZero));
+/// Parses a run length like [`parse_duration_arg`], in whole seconds.
+pub(crate) fn parse_duration_secs(s: &str) -> AppResult<PositiveU64> {
+    let duration = parse_duration_arg(s)?;
+    if duration.subsec_nanos() != 0 {
+        return Err(AppError::validation(
+            ValidationError::DurationNotWholeSeconds {
+                value: s.trim().to_owned(),
+            },
+        ));
     }
-
-    Ok(duration)
+    Ok(PositiveU64::try_from(duration.as_secs())?)
 }
@@ -11,6 +11,7 @@ fn parse_args_defaults() -> AppResult<()> {
 
     let expected_charts = default_charts_path();
     let expected_tmp = default_tmp_path();
+    let expected_history = default_history_path();
 
     let checks = [
         (
@@ -47,7 +48,7 @@ fn parse_args_defaults() -> AppResult<()> {
         ),
         (args.requests.is_none(), "Expected requests to be None"),
         (
-            args.expected_status_code == 200,
+            args.expected_status_code == ExpectedStatus::single(200),
             "Unexpected expected_status_code",
         ),
         (
@@ -98,6 +99,11 @@ fn parse_args_defaults() -> AppResult<()> {
         (!args.verbose, "Expected verbose to be false"),
         (args.config.is_none(), "Expected config to be None"),
         (args.tmp_path == expected_tmp, "Unexpected tmp_path"),
+        (
+            args.history_file == expected_history,
+            "Unexpected history_file",
+        ),
+        (!args.no_history, "Expected no_history to be false"),
         (!args.no_ui, "Expected no_ui to be false"),
         (args.ui_window_ms.get() == 10_000, "Unexpected ui_window_ms"),
         (!args.summary, "Expected summary to be false"),
@@ -160,10 +160,39 @@ fn parse_args_form_fields() -> AppResult<()> {
         "name=demo",
         "--form",
         "file=@payload.txt",
+        "--form-file",
+        "avatar=@avatar.png",
     ])
     .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
     if args.form.len() != 2 {
         return Err(AppError::validation("Unexpected form length"));
     }
+    if args.form_file != ["avatar=@avatar.png"] {
+        return Err(AppError::validation("Unexpected form file entries"));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_duration_accepts_human_units() -> AppResult<()> {
+    for (value, expected_secs) in [("45", 45), ("90s", 90), ("5m", 300), ("1h30m", 5_400)] {
+        let args = TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "-t", value])
+            .map_err(|err| AppError::validation(format!("Expected parse success: {}", err)))?;
+        if args.target_duration.get() != expected_secs {
+            return Err(AppError::validation(format!(
+                "Unexpected duration for '{}': {}",
+                value,
+                args.target_duration.get()
+            )));
+        }
+    }
+    for value in ["0", "0s", "10x", "1h30", "1500ms", "m5"] {
+        if TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "-t", value]).is_ok() {
+            return Err(AppError::validation(format!(
+                "Expected '{}' to be rejected",
+                value
+            )));
+        }
+    }
     Ok(())
 }
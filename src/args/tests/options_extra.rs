@@ -137,3 +137,450 @@ fn parse_args_metrics_range() -> AppResult<()> {
     }
     Ok(())
 }
+
+#[test]
+fn parse_args_multiple_metrics_ranges() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--metrics-range",
+        "10:20,60:70",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    let Some(range) = args.metrics_range else {
+        return Err(AppError::validation("Expected metrics_range to be Some"));
+    };
+    if range.0 != vec![10..=20, 60..=70]
+        || !range.contains(15)
+        || range.contains(40)
+        || !range.contains(70)
+    {
+        return Err(AppError::validation(format!(
+            "Unexpected metrics ranges: {}",
+            range
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_metrics_ranges_reject_overlap_and_descending() -> AppResult<()> {
+    for value in ["10-20,20-30", "60-70,10-20"] {
+        if TesterArgs::try_parse_from([
+            "strest",
+            "-u",
+            "http://localhost",
+            "--metrics-range",
+            value,
+        ])
+        .is_ok()
+        {
+            return Err(AppError::validation(format!(
+                "Expected '{}' to be rejected",
+                value
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_max_bandwidth() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--max-bandwidth",
+        "10MB/s",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    let bytes = args.max_bandwidth.map(BandwidthLimit::bytes_per_sec);
+    if bytes != Some(10_000_000) {
+        return Err(AppError::validation(format!(
+            "Unexpected max_bandwidth: {:?}",
+            bytes
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_bandwidth_units() -> AppResult<()> {
+    let cases = [
+        ("512KiB/s", 524_288),
+        ("1gb/s", 1_000_000_000),
+        ("60MB/m", 1_000_000),
+        ("100/s", 100),
+    ];
+    for (input, expected) in cases {
+        let limit: BandwidthLimit = input.parse()?;
+        if limit.bytes_per_sec() != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected bytes/s for {}: {}",
+                input,
+                limit.bytes_per_sec()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_bandwidth_rejects_invalid_formats() -> AppResult<()> {
+    for input in ["10MB", "MB/s", "10XB/s", "10MB/d", "0MB/s", "1B/h"] {
+        if input.parse::<BandwidthLimit>().is_ok() {
+            return Err(AppError::validation(format!(
+                "Expected error for '{}'",
+                input
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_sim_downlink_and_latency() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--sim-downlink",
+        "1mbit",
+        "--sim-latency",
+        "100ms",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    let bytes = args.sim_downlink.map(LinkRate::bytes_per_sec);
+    if bytes != Some(125_000) || args.sim_latency != Some(Duration::from_millis(100)) {
+        return Err(AppError::validation(format!(
+            "Unexpected sim options: {:?} {:?}",
+            bytes, args.sim_latency
+        )));
+    }
+    for (input, expected) in [("512kbit", 64_000), ("2Gbps", 250_000_000), ("8bit", 1)] {
+        let rate: LinkRate = input.parse()?;
+        if rate.bytes_per_sec() != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected bytes/s for {}: {}",
+                input,
+                rate.bytes_per_sec()
+            )));
+        }
+    }
+    for input in ["1mb/s", "mbit", "10xbit", "0mbit", "7bit"] {
+        if input.parse::<LinkRate>().is_ok() {
+            return Err(AppError::validation(format!(
+                "Expected error for '{}'",
+                input
+            )));
+        }
+    }
+    if TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--sim-latency", "fast"])
+        .is_ok()
+    {
+        return Err(AppError::validation(
+            "Expected invalid --sim-latency to fail",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_body_assertions() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--assert-body-contains",
+        "\"ok\"",
+        "--assert-json",
+        "data.items[0].state=ready",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    if args.assert_body_contains.as_deref() != Some("\"ok\"") {
+        return Err(AppError::validation("Unexpected assert_body_contains"));
+    }
+    let rendered = args.assert_json.as_ref().map(ToString::to_string);
+    if rendered.as_deref() != Some("$.data.items[0].state=ready") {
+        return Err(AppError::validation(format!(
+            "Unexpected assert_json: {:?}",
+            rendered
+        )));
+    }
+    let with_equals: JsonAssertion = "token=a=b".parse()?;
+    if with_equals.expected != "a=b" {
+        return Err(AppError::validation(format!(
+            "Expected value after the first '=', got '{}'",
+            with_equals.expected
+        )));
+    }
+    for input in ["status", "=ok", "data..id=1", "$items[x]=1"] {
+        if input.parse::<JsonAssertion>().is_ok() {
+            return Err(AppError::validation(format!(
+                "Expected error for '{}'",
+                input
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_connect_to_accepts_optional_ca_bundle() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "https://api.internal/",
+        "--connect-to",
+        "api.internal:443:10.0.0.5:8443:/etc/pki/internal-ca.pem",
+        "--connect-to",
+        "auth.internal:443:10.0.0.6:8443",
+    ])?;
+    let cacerts: Vec<Option<&str>> = args
+        .connect_to
+        .iter()
+        .map(|mapping| mapping.cacert.as_deref())
+        .collect();
+    if cacerts != [Some("/etc/pki/internal-ca.pem"), None] {
+        return Err(AppError::validation(format!(
+            "Unexpected connect-to CA bundles: {:?}",
+            cacerts
+        )));
+    }
+    if TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "https://api.internal/",
+        "--connect-to",
+        "api.internal:443:10.0.0.5:8443:",
+    ])
+    .is_ok()
+    {
+        return Err(AppError::validation(
+            "Expected an empty CA path to be rejected",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_max_bytes_sizes() -> AppResult<()> {
+    let cases = [
+        ("10GB", 10_000_000_000),
+        ("512MiB", 536_870_912),
+        ("1tb", 1_000_000_000_000),
+        ("2048", 2_048),
+    ];
+    for (input, expected) in cases {
+        let size: ByteSize = input.parse()?;
+        if size.bytes() != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected bytes for {}: {}",
+                input,
+                size.bytes()
+            )));
+        }
+    }
+    for input in ["GB", "10XB", "0MB", "10GB/s", "99999999999999999999GB"] {
+        if input.parse::<ByteSize>().is_ok() {
+            return Err(AppError::validation(format!(
+                "Expected error for '{}'",
+                input
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_diagnose_flags() -> AppResult<()> {
+    let args =
+        TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--diagnose-strict"])
+            .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    if args.diagnose || !args.diagnose_strict {
+        return Err(AppError::validation(format!(
+            "Unexpected diagnose flags: diagnose={} diagnose_strict={}",
+            args.diagnose, args.diagnose_strict
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_request_id_echo_defaults_header() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--verify-request-id-echo",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    if !args.verify_request_id_echo || args.request_id_header != "X-Request-Id" {
+        return Err(AppError::validation(format!(
+            "Unexpected request id echo args: verify={} header={}",
+            args.verify_request_id_echo, args.request_id_header
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_hold_ui_conflicts_with_no_tui() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--hold-ui"])
+        .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    if !args.hold_ui {
+        return Err(AppError::validation("Expected hold_ui to be set"));
+    }
+    if TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--hold-ui", "--no-tui"])
+        .is_ok()
+    {
+        return Err(AppError::validation(
+            "Expected --hold-ui with --no-tui to be rejected",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_summary_out_tee_alias() -> AppResult<()> {
+    let args =
+        TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--tee", "summary.txt"])
+            .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    if args.summary_out.as_deref() != Some("summary.txt") {
+        return Err(AppError::validation(format!(
+            "Unexpected summary_out: {:?}",
+            args.summary_out
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_assert_success_rate_percentage() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--assert-success-rate",
+        "99.95",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    if args.assert_success_rate.map(|rate| rate.x100()) != Some(9995) {
+        return Err(AppError::validation(format!(
+            "Unexpected assert_success_rate: {:?}",
+            args.assert_success_rate
+        )));
+    }
+    for invalid in ["100.01", "99.999", "-1", "abc"] {
+        if TesterArgs::try_parse_from([
+            "strest",
+            "-u",
+            "http://localhost",
+            "--assert-success-rate",
+            invalid,
+        ])
+        .is_ok()
+        {
+            return Err(AppError::validation(format!(
+                "Expected --assert-success-rate {} to be rejected",
+                invalid
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_slowest_is_bounded() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--slowest", "10"])
+        .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    if args.slowest.map(PositiveUsize::get) != Some(10) {
+        return Err(AppError::validation(format!(
+            "Unexpected slowest: {:?}",
+            args.slowest
+        )));
+    }
+    for invalid in ["0", "1001", "abc"] {
+        if TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--slowest", invalid])
+            .is_ok()
+        {
+            return Err(AppError::validation(format!(
+                "Expected --slowest {} to be rejected",
+                invalid
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_expected_status_list_and_ranges() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--expected-status",
+        "200, 204,301-302",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    let codes: Vec<u16> = args.expected_status_code.codes().collect();
+    if codes != [200, 204, 301, 302] || args.expected_status_code.to_string() != "200,204,301-302" {
+        return Err(AppError::validation(format!(
+            "Unexpected expected_status_code: {:?}",
+            codes
+        )));
+    }
+    for invalid in ["", "200,", "302-301", "1000", "abc", "0", "0-599", "99"] {
+        if TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "-s", invalid]).is_ok() {
+            return Err(AppError::validation(format!(
+                "Expected --status '{}' to be rejected",
+                invalid
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_tags_are_repeatable_key_value_pairs() -> AppResult<()> {
+    let args = TesterArgs::try_parse_from([
+        "strest",
+        "-u",
+        "http://localhost",
+        "--tag",
+        "env=staging",
+        "--tag",
+        "build=a=b c",
+    ])
+    .map_err(|err| AppError::validation(format!("Expected Ok, got Err: {}", err)))?;
+    let expected = [
+        ("env".to_owned(), "staging".to_owned()),
+        ("build".to_owned(), "a=b c".to_owned()),
+    ];
+    if args.tags != expected {
+        return Err(AppError::validation(format!(
+            "Unexpected tags: {:?}",
+            args.tags
+        )));
+    }
+    for invalid in [
+        "env",
+        "env=",
+        "=x",
+        "1env=x",
+        "my-env=x",
+        "run=x",
+        "quantile=x",
+    ] {
+        if TesterArgs::try_parse_from(["strest", "-u", "http://localhost", "--tag", invalid])
+            .is_ok()
+        {
+            return Err(AppError::validation(format!(
+                "Expected --tag '{}' to be rejected",
+                invalid
+            )));
+        }
+    }
+    Ok(())
+}
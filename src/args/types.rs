@@ -60,9 +60,106 @@ pub enum OutputFormat {
     Json,
     Jsonl,
     Csv,
+    Tsv,
+    Junit,
     Quiet,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompatFormat {
+    Wrk,
+    Hey,
+}
+
+/// How `--arrival-distribution` spaces requests around the target rate.
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ArrivalDistribution {
+    /// Evenly spaced requests, one every `1/rate`.
+    Uniform,
+    /// Poisson arrivals: exponentially distributed gaps with mean `1/rate`.
+    Poisson,
+    /// Same process as `poisson`, named after its gap distribution.
+    Exponential,
+}
+
+/// Layout of the end-of-run text summary.
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryFormat {
+    /// Human-oriented summary with compact counts and units.
+    Pretty,
+    /// One raw `key: value` per line in a fixed order, for diffs and golden files.
+    Plain,
+}
+
+/// How `--deadline-header` expresses the remaining request budget.
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeadlineFormat {
+    /// Milliseconds left until the deadline.
+    RelativeMs,
+    /// Deadline as Unix epoch milliseconds.
+    AbsoluteMs,
+}
+
+/// When `--notify-webhook` is called at the end of a run.
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyOn {
+    /// After every run.
+    Always,
+    /// Only when the run fails: an assertion, abort, transport or runtime error.
+    Failure,
+}
+
+/// Call shape used by the gRPC protocols.
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GrpcMode {
+    /// One request message, one response message.
+    Unary,
+    /// Several request messages, one response message.
+    ClientStream,
+    /// One request message, a stream of response messages.
+    ServerStream,
+    /// Several request messages, a stream of response messages.
+    Bidi,
+}
+
+impl GrpcMode {
+    /// Whether the request body carries one message per `--data` line.
+    #[must_use]
+    pub const fn streams_requests(self) -> bool {
+        matches!(self, Self::ClientStream | Self::Bidi)
+    }
+
+    /// Whether the response may carry any number of messages.
+    #[must_use]
+    pub const fn streams_responses(self) -> bool {
+        matches!(self, Self::ServerStream | Self::Bidi)
+    }
+}
+
+/// What the MQTT adapter measures.
+#[derive(Debug, Clone, Copy, Default, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MqttMode {
+    /// Connect and publish; latency covers the connect and the write.
+    #[default]
+    Publish,
+    /// Publish on one session and receive on a subscribed one; latency is publish to delivery.
+    Subscribe,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EchoConfigFormat {
+    Text,
+    Json,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum TimeUnit {
@@ -112,6 +209,7 @@ pub enum Protocol {
     Enet,
     Kcp,
     Raknet,
+    Sse,
 }
 
 impl Protocol {
@@ -129,6 +227,7 @@ impl Protocol {
             Protocol::Enet => "enet",
             Protocol::Kcp => "kcp",
             Protocol::Raknet => "raknet",
+            Protocol::Sse => "sse",
         }
     }
 
@@ -146,6 +245,7 @@ impl Protocol {
             Protocol::Enet => crate::domain::run::ProtocolKind::Enet,
             Protocol::Kcp => crate::domain::run::ProtocolKind::Kcp,
             Protocol::Raknet => crate::domain::run::ProtocolKind::Raknet,
+            Protocol::Sse => crate::domain::run::ProtocolKind::Sse,
         }
     }
 }
@@ -159,6 +259,8 @@ pub enum LoadMode {
     Jitter,
     Burst,
     Soak,
+    /// Closed loop: each `--max-tasks` worker sends its next request as soon as the previous one completes.
+    Concurrency,
 }
 
 impl LoadMode {
@@ -171,6 +273,7 @@ impl LoadMode {
             LoadMode::Jitter => "jitter",
             LoadMode::Burst => "burst",
             LoadMode::Soak => "soak",
+            LoadMode::Concurrency => "concurrency",
         }
     }
 
@@ -183,6 +286,7 @@ impl LoadMode {
             LoadMode::Jitter => crate::domain::run::LoadMode::Jitter,
             LoadMode::Burst => crate::domain::run::LoadMode::Burst,
             LoadMode::Soak => crate::domain::run::LoadMode::Soak,
+            LoadMode::Concurrency => crate::domain::run::LoadMode::Concurrency,
         }
     }
 }
@@ -276,6 +380,126 @@ impl From<PositiveU64> for u64 {
     }
 }
 
+/// Chart image settings with bounded values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartSetting {
+    Width,
+    Height,
+    Dpi,
+}
+
+impl ChartSetting {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Width => "chart-width",
+            Self::Height => "chart-height",
+            Self::Dpi => "chart-dpi",
+        }
+    }
+
+    /// Inclusive `(min, max)`; up to 8K images and print-quality DPI.
+    #[must_use]
+    pub const fn bounds(self) -> (u32, u32) {
+        match self {
+            Self::Width => (320, 7_680),
+            Self::Height => (200, 4_320),
+            Self::Dpi => (72, 600),
+        }
+    }
+
+    /// Returns `value` when it is within [`Self::bounds`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `value` is out of bounds.
+    pub const fn check(self, value: u32) -> Result<u32, ValidationError> {
+        let (min, max) = self.bounds();
+        if value < min || value > max {
+            return Err(ValidationError::ChartSettingOutOfRange {
+                name: self.name(),
+                value,
+                min,
+                max,
+            });
+        }
+        Ok(value)
+    }
+}
+
+/// A percentage in `0..=100` with up to two decimals, stored in hundredths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percentage(u64);
+
+impl Percentage {
+    /// Value in hundredths of a percent (`99.5%` is `9950`).
+    #[must_use]
+    pub const fn x100(self) -> u64 {
+        self.0
+    }
+}
+
+impl std::str::FromStr for Percentage {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let invalid = || ValidationError::InvalidPercentage {
+            value: value.to_owned(),
+        };
+        let number = value.strip_suffix('%').unwrap_or(value);
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty()
+            || fraction.len() > 2
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|ch| ch.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let whole: u64 = whole.parse().map_err(|_err| invalid())?;
+        let fraction: u64 = format!("{:0<2}", fraction)
+            .parse()
+            .map_err(|_err| invalid())?;
+        let hundredths = whole
+            .checked_mul(100)
+            .and_then(|scaled| scaled.checked_add(fraction))
+            .filter(|hundredths| *hundredths <= 10_000)
+            .ok_or_else(invalid)?;
+        Ok(Self(hundredths))
+    }
+}
+
+impl std::fmt::Display for Percentage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{:02}%", self.0 / 100, self.0 % 100)
+    }
+}
+
+impl<'de> Deserialize<'de> for Percentage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Integer(u64),
+            Float(f64),
+            Text(String),
+        }
+        let value = match Raw::deserialize(deserializer)? {
+            Raw::Integer(value) => value.to_string(),
+            Raw::Float(value) => value.to_string(),
+            Raw::Text(value) => value,
+        };
+        value
+            .parse::<Percentage>()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PositiveUsize(NonZeroUsize);
 
@@ -313,6 +537,388 @@ impl From<PositiveUsize> for usize {
     }
 }
 
+/// Client-side bandwidth cap, normalized to bytes per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BandwidthLimit(NonZeroU64);
+
+impl BandwidthLimit {
+    #[must_use]
+    pub const fn bytes_per_sec(self) -> u64 {
+        self.0.get()
+    }
+}
+
+impl std::str::FromStr for BandwidthLimit {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let Some((size_part, time_part)) = value.split_once('/') else {
+            return Err(ValidationError::InvalidBandwidthFormat {
+                value: value.to_owned(),
+            });
+        };
+        let size_part = size_part.trim();
+        let digits_len = size_part.chars().take_while(char::is_ascii_digit).count();
+        if digits_len == 0 {
+            return Err(ValidationError::InvalidBandwidthFormat {
+                value: value.to_owned(),
+            });
+        }
+        let (num_part, unit_part) = size_part.split_at(digits_len);
+        let number: u64 = num_part
+            .parse()
+            .map_err(|_err| ValidationError::BandwidthOverflow)?;
+
+        let multiplier = size_unit_multiplier(unit_part).ok_or_else(|| {
+            ValidationError::InvalidBandwidthSizeUnit {
+                unit: unit_part.trim().to_owned(),
+            }
+        })?;
+        let per_secs: u64 = match time_part.trim().to_ascii_lowercase().as_str() {
+            "s" | "sec" => 1,
+            "m" | "min" => 60,
+            "h" => 3_600,
+            _ => {
+                return Err(ValidationError::InvalidBandwidthTimeUnit {
+                    unit: time_part.trim().to_owned(),
+                });
+            }
+        };
+
+        let bytes = number
+            .checked_mul(multiplier)
+            .ok_or(ValidationError::BandwidthOverflow)?;
+        let bytes_per_sec = bytes.checked_div(per_secs).unwrap_or(0);
+        NonZeroU64::new(bytes_per_sec)
+            .map(BandwidthLimit)
+            .ok_or(ValidationError::BandwidthTooSmall)
+    }
+}
+
+impl std::fmt::Display for BandwidthLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes = self.bytes_per_sec();
+        if bytes.is_multiple_of(1_000_000_000) {
+            write!(f, "{}GB/s", bytes / 1_000_000_000)
+        } else if bytes.is_multiple_of(1_000_000) {
+            write!(f, "{}MB/s", bytes / 1_000_000)
+        } else if bytes.is_multiple_of(1_000) {
+            write!(f, "{}KB/s", bytes / 1_000)
+        } else {
+            write!(f, "{}B/s", bytes)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BandwidthLimit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value
+            .parse::<BandwidthLimit>()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Simulated link speed, given in bits per second and normalized to bytes per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkRate(NonZeroU64);
+
+impl LinkRate {
+    #[must_use]
+    pub const fn bytes_per_sec(self) -> u64 {
+        self.0.get()
+    }
+}
+
+impl std::str::FromStr for LinkRate {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let digits_len = value.chars().take_while(char::is_ascii_digit).count();
+        if digits_len == 0 {
+            return Err(ValidationError::InvalidLinkRateFormat {
+                value: value.to_owned(),
+            });
+        }
+        let (num_part, unit_part) = value.split_at(digits_len);
+        let number: u64 = num_part
+            .parse()
+            .map_err(|_err| ValidationError::BandwidthOverflow)?;
+        let multiplier: u64 = match unit_part.trim().to_ascii_lowercase().as_str() {
+            "bit" | "bps" => 1,
+            "kbit" | "kbps" => 1_000,
+            "mbit" | "mbps" => 1_000_000,
+            "gbit" | "gbps" => 1_000_000_000,
+            _ => {
+                return Err(ValidationError::InvalidLinkRateUnit {
+                    unit: unit_part.trim().to_owned(),
+                });
+            }
+        };
+        let bits = number
+            .checked_mul(multiplier)
+            .ok_or(ValidationError::BandwidthOverflow)?;
+        NonZeroU64::new(bits.checked_div(8).unwrap_or(0))
+            .map(LinkRate)
+            .ok_or(ValidationError::BandwidthTooSmall)
+    }
+}
+
+impl std::fmt::Display for LinkRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bits = self.bytes_per_sec().saturating_mul(8);
+        if bits.is_multiple_of(1_000_000_000) {
+            write!(f, "{}gbit", bits / 1_000_000_000)
+        } else if bits.is_multiple_of(1_000_000) {
+            write!(f, "{}mbit", bits / 1_000_000)
+        } else if bits.is_multiple_of(1_000) {
+            write!(f, "{}kbit", bits / 1_000)
+        } else {
+            write!(f, "{}bit", bits)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LinkRate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse::<LinkRate>().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Bytes per size unit; decimal units are powers of 1000, `*iB` powers of 1024.
+fn size_unit_multiplier(unit: &str) -> Option<u64> {
+    match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => Some(1),
+        "k" | "kb" => Some(1_000),
+        "m" | "mb" => Some(1_000_000),
+        "g" | "gb" => Some(1_000_000_000),
+        "t" | "tb" => Some(1_000_000_000_000),
+        "kib" => Some(1_024),
+        "mib" => Some(1_048_576),
+        "gib" => Some(1_073_741_824),
+        "tib" => Some(1_099_511_627_776),
+        _ => None,
+    }
+}
+
+/// Non-zero byte count parsed from a size such as `10GB` or `512MiB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteSize(NonZeroU64);
+
+impl ByteSize {
+    #[must_use]
+    pub const fn bytes(self) -> u64 {
+        self.0.get()
+    }
+}
+
+impl std::str::FromStr for ByteSize {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let digits_len = value.chars().take_while(char::is_ascii_digit).count();
+        if digits_len == 0 {
+            return Err(ValidationError::InvalidByteSizeFormat {
+                value: value.to_owned(),
+            });
+        }
+        let (num_part, unit_part) = value.split_at(digits_len);
+        let number: u64 = num_part
+            .parse()
+            .map_err(|_err| ValidationError::ByteSizeOverflow)?;
+        let multiplier = size_unit_multiplier(unit_part).ok_or_else(|| {
+            ValidationError::InvalidByteSizeUnit {
+                unit: unit_part.trim().to_owned(),
+            }
+        })?;
+        let bytes = number
+            .checked_mul(multiplier)
+            .ok_or(ValidationError::ByteSizeOverflow)?;
+        NonZeroU64::new(bytes)
+            .map(ByteSize)
+            .ok_or(ValidationError::ByteSizeZero)
+    }
+}
+
+impl std::fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes = self.bytes();
+        if bytes.is_multiple_of(1_000_000_000) {
+            write!(f, "{}GB", bytes / 1_000_000_000)
+        } else if bytes.is_multiple_of(1_000_000) {
+            write!(f, "{}MB", bytes / 1_000_000)
+        } else if bytes.is_multiple_of(1_000) {
+            write!(f, "{}KB", bytes / 1_000)
+        } else {
+            write!(f, "{}B", bytes)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse::<ByteSize>().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Lowest status code `--status` accepts; status 0 marks failed checks and
+/// shortfalls, which must never count as success.
+const MIN_EXPECTED_STATUS: u16 = 100;
+/// Highest status code `--status` accepts.
+const MAX_EXPECTED_STATUS: u16 = 999;
+/// Bitset words covering `0..=MAX_EXPECTED_STATUS`.
+const EXPECTED_STATUS_WORDS: usize = 16;
+
+/// Status codes that count as success, parsed from a comma list of codes and
+/// ranges such as `200,204,301-302`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpectedStatus([u64; EXPECTED_STATUS_WORDS]);
+
+impl ExpectedStatus {
+    /// A set holding only `code`.
+    #[must_use]
+    pub fn single(code: u16) -> Self {
+        let mut set = Self([0; EXPECTED_STATUS_WORDS]);
+        set.insert(code);
+        set
+    }
+
+    #[must_use]
+    pub fn contains(&self, code: u16) -> bool {
+        let (word, mask) = Self::slot(code);
+        self.0.get(word).is_some_and(|bits| bits & mask != 0)
+    }
+
+    /// Lowest code in the set, reported where one status stands for success.
+    #[must_use]
+    pub fn first(&self) -> u16 {
+        self.codes().next().unwrap_or(200)
+    }
+
+    /// Codes in the set, ascending.
+    pub fn codes(&self) -> impl Iterator<Item = u16> + '_ {
+        (0..=MAX_EXPECTED_STATUS).filter(|code| self.contains(*code))
+    }
+
+    fn insert(&mut self, code: u16) {
+        let (word, mask) = Self::slot(code);
+        if let Some(bits) = self.0.get_mut(word) {
+            *bits |= mask;
+        }
+    }
+
+    fn slot(code: u16) -> (usize, u64) {
+        let word = usize::from(code / 64);
+        let mask = 1u64.checked_shl(u32::from(code % 64)).unwrap_or(0);
+        (word, mask)
+    }
+}
+
+impl Default for ExpectedStatus {
+    fn default() -> Self {
+        Self::single(200)
+    }
+}
+
+impl std::str::FromStr for ExpectedStatus {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ValidationError::InvalidExpectedStatus {
+            value: s.trim().to_owned(),
+        };
+        let parse_code = |text: &str| {
+            text.trim()
+                .parse::<u16>()
+                .ok()
+                .filter(|code| (MIN_EXPECTED_STATUS..=MAX_EXPECTED_STATUS).contains(code))
+                .ok_or_else(invalid)
+        };
+        let mut set = Self([0; EXPECTED_STATUS_WORDS]);
+        for part in s.split(',') {
+            let (start, end) = match part.split_once('-') {
+                Some((start, end)) => (parse_code(start)?, parse_code(end)?),
+                None => {
+                    let code = parse_code(part)?;
+                    (code, code)
+                }
+            };
+            if start > end {
+                return Err(invalid());
+            }
+            for code in start..=end {
+                set.insert(code);
+            }
+        }
+        Ok(set)
+    }
+}
+
+impl std::fmt::Display for ExpectedStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut runs: Vec<(u16, u16)> = Vec::new();
+        for code in self.codes() {
+            match runs.last_mut() {
+                Some((_, end)) if end.checked_add(1) == Some(code) => *end = code,
+                _ => runs.push((code, code)),
+            }
+        }
+        for (index, (start, end)) in runs.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            if start == end {
+                write!(f, "{}", start)?;
+            } else {
+                write!(f, "{}-{}", start, end)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for ExpectedStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ExpectedStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Code(u16),
+            Text(String),
+        }
+        let value = match Raw::deserialize(deserializer)? {
+            Raw::Code(code) => code.to_string(),
+            Raw::Text(value) => value,
+        };
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LoadProfile {
     pub initial_rpm: u64,
@@ -322,7 +928,19 @@ pub struct LoadProfile {
 #[derive(Debug, Clone)]
 pub struct LoadStage {
     pub duration: Duration,
+    /// Rate the stage ramps to; the wave's low point for a sine stage.
     pub target_rpm: u64,
+    /// Oscillate between two rates instead of ramping to `target_rpm`.
+    pub sine: Option<SineWave>,
+}
+
+/// A `kind = "sine"` stage: the rate starts at `min_rpm`, peaks at `max_rpm`
+/// half a period later and is back at `min_rpm` after each full `period`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SineWave {
+    pub min_rpm: u64,
+    pub max_rpm: u64,
+    pub period: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -342,8 +960,163 @@ pub struct ScenarioStep {
     pub body: Option<String>,
     pub assert_status: Option<u16>,
     pub assert_body_contains: Option<String>,
-    pub think_time: Option<Duration>,
+    pub think_time: Option<ThinkTime>,
     pub vars: BTreeMap<String, String>,
+    /// Variables captured from this step's response, keyed by variable name.
+    pub extract: BTreeMap<String, ExtractSource>,
+    /// PEM client certificate overriding `--cert` for this step.
+    pub cert: Option<String>,
+    /// PEM private key paired with `cert`.
+    pub key: Option<String>,
+}
+
+/// Pause after a scenario step, drawn uniformly from `min..=max`.
+///
+/// A fixed think time has `min == max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThinkTime {
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl ThinkTime {
+    #[must_use]
+    pub const fn fixed(duration: Duration) -> Self {
+        Self {
+            min: duration,
+            max: duration,
+        }
+    }
+}
+
+/// Where a scenario step reads an extracted variable from.
+///
+/// Sources starting with `$` are a JSONPath subset (`$.token`,
+/// `$.data[0].id`); anything else names a response header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractSource {
+    Header(String),
+    JsonPath(Vec<JsonPathSegment>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl std::str::FromStr for ExtractSource {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let invalid = || ValidationError::InvalidExtractSource {
+            value: value.to_owned(),
+        };
+        let Some(mut rest) = value.strip_prefix('$') else {
+            if value.is_empty() || value.chars().any(|ch| ch.is_whitespace() || ch == ':') {
+                return Err(invalid());
+            }
+            return Ok(Self::Header(value.to_ascii_lowercase()));
+        };
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+                let (key, remaining) = after_dot.split_at(end);
+                if key.is_empty() {
+                    return Err(invalid());
+                }
+                segments.push(JsonPathSegment::Key(key.to_owned()));
+                rest = remaining;
+            } else if let Some(after_bracket) = rest.strip_prefix('[') {
+                let (index, remaining) = after_bracket.split_once(']').ok_or_else(invalid)?;
+                let index = index.trim().parse::<usize>().map_err(|_err| invalid())?;
+                segments.push(JsonPathSegment::Index(index));
+                rest = remaining;
+            } else {
+                return Err(invalid());
+            }
+        }
+        Ok(Self::JsonPath(segments))
+    }
+}
+
+impl std::fmt::Display for ExtractSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Header(name) => f.write_str(name),
+            Self::JsonPath(segments) => {
+                f.write_str("$")?;
+                for segment in segments {
+                    match segment {
+                        JsonPathSegment::Key(key) => write!(f, ".{}", key)?,
+                        JsonPathSegment::Index(index) => write!(f, "[{}]", index)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `--assert-json <path>=<value>`: the value at a JSONPath must equal `expected`.
+///
+/// The leading `$.` of the path is optional (`status=ok` is `$.status=ok`).
+///
+/// Strings compare by their text, other JSON values by their compact JSON
+/// (`true`, `3`, `null`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonAssertion {
+    pub path: Vec<JsonPathSegment>,
+    pub expected: String,
+}
+
+impl std::str::FromStr for JsonAssertion {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ValidationError::InvalidJsonAssertion {
+            value: s.trim().to_owned(),
+        };
+        let (path, expected) = s.split_once('=').ok_or_else(invalid)?;
+        let path = path.trim();
+        let source = if path.starts_with('$') {
+            path.parse::<ExtractSource>()
+        } else {
+            format!("$.{}", path).parse::<ExtractSource>()
+        };
+        match source {
+            Ok(ExtractSource::JsonPath(segments)) => Ok(Self {
+                path: segments,
+                expected: expected.to_owned(),
+            }),
+            Ok(ExtractSource::Header(_)) | Err(_) => Err(invalid()),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonAssertion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}={}",
+            ExtractSource::JsonPath(self.path.clone()),
+            self.expected
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonAssertion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value
+            .parse::<JsonAssertion>()
+            .map_err(serde::de::Error::custom)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -352,4 +1125,6 @@ pub struct ConnectToMapping {
     pub source_port: u16,
     pub target_host: String,
     pub target_port: u16,
+    /// PEM CA bundle trusted for this destination instead of `--cacert`.
+    pub cacert: Option<String>,
 }
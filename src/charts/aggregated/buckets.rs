@@ -1,5 +1,6 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::AggregatedMetricSample;
 
@@ -8,6 +9,7 @@ use super::util::bucket_last_value_u64;
 pub fn plot_aggregated_average_response_time(
     samples: &[AggregatedMetricSample],
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if samples.is_empty() {
         return Ok(());
@@ -21,14 +23,17 @@ pub fn plot_aggregated_average_response_time(
         .unwrap_or(1)
         .saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Average Response Time", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "Average Response Time",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -49,6 +54,7 @@ pub fn plot_aggregated_average_response_time(
 pub fn plot_aggregated_cumulative_successful_requests(
     samples: &[AggregatedMetricSample],
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if samples.is_empty() {
         return Ok(());
@@ -57,14 +63,17 @@ pub fn plot_aggregated_cumulative_successful_requests(
     let x_max = data.last().map(|(x, _)| x.saturating_add(1)).unwrap_or(1);
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Successful Requests", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption(
+            "Cumulative Successful Requests",
+            ("sans-serif", size.scale(30)),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -84,6 +93,7 @@ pub fn plot_aggregated_cumulative_successful_requests(
 pub fn plot_aggregated_cumulative_error_rate(
     samples: &[AggregatedMetricSample],
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if samples.is_empty() {
         return Ok(());
@@ -92,14 +102,14 @@ pub fn plot_aggregated_cumulative_error_rate(
     let x_max = data.last().map(|(x, _)| x.saturating_add(1)).unwrap_or(1);
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Error Rate", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption("Cumulative Error Rate", ("sans-serif", size.scale(30)))
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -119,6 +129,7 @@ pub fn plot_aggregated_cumulative_error_rate(
 pub fn plot_aggregated_cumulative_total_requests(
     samples: &[AggregatedMetricSample],
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if samples.is_empty() {
         return Ok(());
@@ -127,14 +138,14 @@ pub fn plot_aggregated_cumulative_total_requests(
     let x_max = data.last().map(|(x, _)| x.saturating_add(1)).unwrap_or(1);
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Total Requests", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption("Cumulative Total Requests", ("sans-serif", size.scale(30)))
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -1,5 +1,6 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::AggregatedMetricSample;
 
@@ -8,6 +9,7 @@ use super::util::bucket_last_value_u64;
 pub fn plot_aggregated_latency_percentiles(
     samples: &[AggregatedMetricSample],
     base_path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if samples.is_empty() {
         return Ok(());
@@ -48,14 +50,14 @@ pub fn plot_aggregated_latency_percentiles(
                       file_path: &str,
                       y_max: u64|
      -> AppResult<()> {
-        let root = BitMapBackend::new(file_path, (1600, 600)).into_drawing_area();
+        let root = size.root(file_path);
         root.fill(&WHITE)?;
 
         let mut chart = ChartBuilder::on(&root)
-            .caption(title, ("sans-serif", 30))
-            .margin(10)
-            .x_label_area_size(30)
-            .y_label_area_size(50)
+            .caption(title, ("sans-serif", size.scale(30)))
+            .margin(size.scale(10))
+            .x_label_area_size(size.scale(30))
+            .y_label_area_size(size.scale(50))
             .build_cartesian_2d(x_min..x_max, 0u64..y_max)?;
 
         chart
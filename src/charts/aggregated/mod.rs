@@ -9,3 +9,4 @@ pub use buckets::{
 };
 pub use latency::plot_aggregated_latency_percentiles;
 pub use rps::plot_aggregated_requests_per_second;
+pub(in crate::charts) use util::exclude_warmup;
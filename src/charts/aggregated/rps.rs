@@ -1,5 +1,6 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::AggregatedMetricSample;
 
@@ -8,6 +9,7 @@ use super::util::compute_rps_series;
 pub fn plot_aggregated_requests_per_second(
     samples: &[AggregatedMetricSample],
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     let data = compute_rps_series(samples);
     if data.is_empty() {
@@ -21,14 +23,17 @@ pub fn plot_aggregated_requests_per_second(
         .unwrap_or(1)
         .saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Requests per Second", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "Requests per Second",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -8,6 +8,63 @@ fn sorted_samples(samples: &[AggregatedMetricSample]) -> Vec<AggregatedMetricSam
     out
 }
 
+/// Drops samples taken during warmup and rebases the rest on the warmup end.
+///
+/// Elapsed time restarts at zero, and cumulative counts and the running
+/// average are taken relative to the last warmup sample. Percentiles come
+/// from cumulative histograms and are kept as reported.
+pub(in crate::charts) fn exclude_warmup(
+    samples: &[AggregatedMetricSample],
+    warmup_ms: u64,
+) -> Vec<AggregatedMetricSample> {
+    let sorted = sorted_samples(samples);
+    if warmup_ms == 0 {
+        return sorted;
+    }
+    let baseline = sorted
+        .iter()
+        .rev()
+        .find(|sample| sample.elapsed_ms < warmup_ms)
+        .cloned();
+    sorted
+        .into_iter()
+        .filter(|sample| sample.elapsed_ms >= warmup_ms)
+        .map(|sample| match baseline.as_ref() {
+            Some(base) => rebase_sample(&sample, base, warmup_ms),
+            None => AggregatedMetricSample {
+                elapsed_ms: sample.elapsed_ms.saturating_sub(warmup_ms),
+                ..sample
+            },
+        })
+        .collect()
+}
+
+fn rebase_sample(
+    sample: &AggregatedMetricSample,
+    base: &AggregatedMetricSample,
+    warmup_ms: u64,
+) -> AggregatedMetricSample {
+    let total_requests = sample.total_requests.saturating_sub(base.total_requests);
+    let latency_sum = u128::from(sample.avg_latency_ms)
+        .saturating_mul(u128::from(sample.total_requests))
+        .saturating_sub(
+            u128::from(base.avg_latency_ms).saturating_mul(u128::from(base.total_requests)),
+        );
+    let avg_latency_ms = latency_sum
+        .checked_div(u128::from(total_requests))
+        .map_or(0, |avg| u64::try_from(avg).unwrap_or(u64::MAX));
+    AggregatedMetricSample {
+        elapsed_ms: sample.elapsed_ms.saturating_sub(warmup_ms),
+        total_requests,
+        successful_requests: sample
+            .successful_requests
+            .saturating_sub(base.successful_requests),
+        error_requests: sample.error_requests.saturating_sub(base.error_requests),
+        avg_latency_ms,
+        ..sample.clone()
+    }
+}
+
 fn bucket_last_value(
     samples: &[AggregatedMetricSample],
     bucket_ms: u64,
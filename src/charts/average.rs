@@ -2,11 +2,16 @@ use std::collections::BTreeMap;
 
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::MetricRecord;
 
-pub fn plot_average_response_time(metrics: &[MetricRecord], path: &str) -> AppResult<()> {
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+pub fn plot_average_response_time(
+    metrics: &[MetricRecord],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut buckets: BTreeMap<u64, Vec<u128>> = BTreeMap::new();
@@ -43,10 +48,13 @@ pub fn plot_average_response_time(metrics: &[MetricRecord], path: &str) -> AppRe
         .saturating_add(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Average Response Time", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "Average Response Time",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -2,21 +2,24 @@ use std::collections::BTreeMap;
 
 use plotters::prelude::*;
 
+use crate::args::ExpectedStatus;
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::MetricRecord;
 
 pub fn plot_cumulative_successful_requests(
     metrics: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut success_buckets: BTreeMap<u64, u64> = BTreeMap::new();
 
     for metric in metrics {
-        if metric.status_code == expected_status_code {
+        if expected_status_code.contains(metric.status_code) && !metric.check_failed {
             let elapsed_ms = metric.elapsed_ms;
             let bucket = elapsed_ms / 100; // 100ms buckets
             let entry = success_buckets.entry(bucket).or_insert(0);
@@ -38,10 +41,13 @@ pub fn plot_cumulative_successful_requests(
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Successful Requests", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption(
+            "Cumulative Successful Requests",
+            ("sans-serif", size.scale(30)),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -61,16 +67,17 @@ pub fn plot_cumulative_successful_requests(
 
 pub fn plot_cumulative_error_rate(
     metrics: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut error_buckets: BTreeMap<u64, u64> = BTreeMap::new();
 
     for metric in metrics {
-        if metric.status_code != expected_status_code {
+        if !expected_status_code.contains(metric.status_code) || metric.check_failed {
             let elapsed_ms = metric.elapsed_ms;
             let bucket = elapsed_ms / 100; // 100ms buckets
             let entry = error_buckets.entry(bucket).or_insert(0);
@@ -92,10 +99,10 @@ pub fn plot_cumulative_error_rate(
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Error Rate", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption("Cumulative Error Rate", ("sans-serif", size.scale(30)))
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -113,8 +120,12 @@ pub fn plot_cumulative_error_rate(
     Ok(())
 }
 
-pub fn plot_cumulative_total_requests(metrics: &[MetricRecord], path: &str) -> AppResult<()> {
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+pub fn plot_cumulative_total_requests(
+    metrics: &[MetricRecord],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut buckets: BTreeMap<u64, u64> = BTreeMap::new();
@@ -140,10 +151,10 @@ pub fn plot_cumulative_total_requests(metrics: &[MetricRecord], path: &str) -> A
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Total Requests", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption("Cumulative Total Requests", ("sans-serif", size.scale(30)))
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -7,6 +7,8 @@ use crate::args::TesterArgs;
 use crate::error::AppResult;
 use crate::metrics::{AggregatedMetricSample, StreamingChartData};
 
+use super::super::ChartSize;
+use super::super::aggregated::exclude_warmup;
 use super::super::{
     LatencyPercentilesSeries, plot_aggregated_average_response_time,
     plot_aggregated_cumulative_error_rate, plot_aggregated_cumulative_successful_requests,
@@ -14,9 +16,9 @@ use super::super::{
     plot_aggregated_requests_per_second, plot_average_response_time_from_buckets,
     plot_cumulative_error_rate_from_buckets, plot_cumulative_successful_requests_from_buckets,
     plot_cumulative_total_requests_from_buckets, plot_error_rate_breakdown_from_counts,
-    plot_inflight_requests_from_counts, plot_latency_percentiles_series,
+    plot_inflight_requests_from_counts, plot_latency_percentiles_series, plot_latency_vs_inflight,
     plot_requests_per_second_from_counts, plot_status_code_distribution_from_counts,
-    plot_timeouts_per_second_from_counts,
+    plot_throughput_vs_latency, plot_timeouts_per_second_from_counts, throughput_latency_points,
 };
 use super::naming::resolve_chart_output_dir;
 
@@ -40,6 +42,7 @@ pub async fn plot_metrics(
     }
     let output_dir = resolve_chart_output_dir(args);
     let path = output_dir.to_string_lossy().to_string();
+    let size = ChartSize::from_args(args);
     let expected_status_code = args.expected_status_code;
 
     if let Err(e) = fs::create_dir_all(Path::new(&path)).await {
@@ -49,7 +52,11 @@ pub async fn plot_metrics(
 
     info!("Plotting average response time...");
 
-    plot_average_response_time(metrics, &format!("{}/average_response_time.png", path))?;
+    plot_average_response_time(
+        metrics,
+        &format!("{}/average_response_time.png", path),
+        size,
+    )?;
 
     info!("Plotting cumulative successful requests...");
 
@@ -57,6 +64,7 @@ pub async fn plot_metrics(
         metrics,
         expected_status_code,
         &format!("{}/cumulative_successful_requests.png", path),
+        size,
     )?;
 
     info!("Plotting cumulative error rate...");
@@ -65,6 +73,7 @@ pub async fn plot_metrics(
         metrics,
         expected_status_code,
         &format!("{}/cumulative_error_rate.png", path),
+        size,
     )?;
 
     info!("Plotting latency percentiles...");
@@ -73,15 +82,16 @@ pub async fn plot_metrics(
         metrics,
         expected_status_code,
         &format!("{}/latency_percentiles", path),
+        size,
     )?;
 
     info!("Plotting requests per second...");
 
-    plot_requests_per_second(metrics, &format!("{}/requests_per_second.png", path))?;
+    plot_requests_per_second(metrics, &format!("{}/requests_per_second.png", path), size)?;
 
     info!("Plotting timeouts per second...");
 
-    plot_timeouts_per_second(metrics, &format!("{}/timeouts_per_second.png", path))?;
+    plot_timeouts_per_second(metrics, &format!("{}/timeouts_per_second.png", path), size)?;
 
     info!("Plotting error rate breakdown...");
 
@@ -89,19 +99,28 @@ pub async fn plot_metrics(
         metrics,
         expected_status_code,
         &format!("{}/error_rate_breakdown.png", path),
+        size,
     )?;
 
     info!("Plotting status code distribution...");
 
-    plot_status_code_distribution(metrics, &format!("{}/status_code_distribution.png", path))?;
+    plot_status_code_distribution(
+        metrics,
+        &format!("{}/status_code_distribution.png", path),
+        size,
+    )?;
 
     info!("Plotting in-flight requests...");
 
-    plot_inflight_requests(metrics, &format!("{}/inflight_requests.png", path))?;
+    plot_inflight_requests(metrics, &format!("{}/inflight_requests.png", path), size)?;
 
     info!("Plotting cumulative total requests...");
 
-    plot_cumulative_total_requests(metrics, &format!("{}/cumulative_total_requests.png", path))?;
+    plot_cumulative_total_requests(
+        metrics,
+        &format!("{}/cumulative_total_requests.png", path),
+        size,
+    )?;
 
     Ok(Some(path))
 }
@@ -110,11 +129,16 @@ pub async fn plot_aggregated_metrics(
     samples: &[AggregatedMetricSample],
     args: &TesterArgs,
 ) -> AppResult<Option<String>> {
+    let warmup_ms = args.warmup.map_or(0, |warmup| {
+        u64::try_from(warmup.as_millis()).unwrap_or(u64::MAX)
+    });
+    let samples = &exclude_warmup(samples, warmup_ms);
     if samples.is_empty() {
         return Ok(None);
     }
     let output_dir = resolve_chart_output_dir(args);
     let path = output_dir.to_string_lossy().to_string();
+    let size = ChartSize::from_args(args);
 
     if let Err(e) = fs::create_dir_all(Path::new(&path)).await {
         error!("Failed to create output directory '{}': {}", path, e);
@@ -122,27 +146,41 @@ pub async fn plot_aggregated_metrics(
     }
 
     info!("Plotting average response time (aggregated)...");
-    plot_aggregated_average_response_time(samples, &format!("{}/average_response_time.png", path))?;
+    plot_aggregated_average_response_time(
+        samples,
+        &format!("{}/average_response_time.png", path),
+        size,
+    )?;
 
     info!("Plotting cumulative successful requests (aggregated)...");
     plot_aggregated_cumulative_successful_requests(
         samples,
         &format!("{}/cumulative_successful_requests.png", path),
+        size,
     )?;
 
     info!("Plotting cumulative error rate (aggregated)...");
-    plot_aggregated_cumulative_error_rate(samples, &format!("{}/cumulative_error_rate.png", path))?;
+    plot_aggregated_cumulative_error_rate(
+        samples,
+        &format!("{}/cumulative_error_rate.png", path),
+        size,
+    )?;
 
     info!("Plotting latency percentiles (aggregated)...");
-    plot_aggregated_latency_percentiles(samples, &format!("{}/latency_percentiles", path))?;
+    plot_aggregated_latency_percentiles(samples, &format!("{}/latency_percentiles", path), size)?;
 
     info!("Plotting requests per second (aggregated)...");
-    plot_aggregated_requests_per_second(samples, &format!("{}/requests_per_second.png", path))?;
+    plot_aggregated_requests_per_second(
+        samples,
+        &format!("{}/requests_per_second.png", path),
+        size,
+    )?;
 
     info!("Plotting cumulative total requests (aggregated)...");
     plot_aggregated_cumulative_total_requests(
         samples,
         &format!("{}/cumulative_total_requests.png", path),
+        size,
     )?;
 
     Ok(Some(path))
@@ -157,6 +195,7 @@ pub async fn plot_streaming_metrics(
     }
     let output_dir = resolve_chart_output_dir(args);
     let path = output_dir.to_string_lossy().to_string();
+    let size = ChartSize::from_args(args);
 
     if let Err(e) = fs::create_dir_all(Path::new(&path)).await {
         error!("Failed to create output directory '{}': {}", path, e);
@@ -167,18 +206,21 @@ pub async fn plot_streaming_metrics(
     plot_average_response_time_from_buckets(
         &data.avg_buckets,
         &format!("{}/average_response_time.png", path),
+        size,
     )?;
 
     info!("Plotting cumulative successful requests...");
     plot_cumulative_successful_requests_from_buckets(
         &data.success_buckets,
         &format!("{}/cumulative_successful_requests.png", path),
+        size,
     )?;
 
     info!("Plotting cumulative error rate...");
     plot_cumulative_error_rate_from_buckets(
         &data.error_buckets,
         &format!("{}/cumulative_error_rate.png", path),
+        size,
     )?;
 
     info!("Plotting latency percentiles...");
@@ -192,18 +234,20 @@ pub async fn plot_streaming_metrics(
         p90_ok: &data.p90_ok,
         p99_ok: &data.p99_ok,
     };
-    plot_latency_percentiles_series(&percentiles, &format!("{}/latency_percentiles", path))?;
+    plot_latency_percentiles_series(&percentiles, &format!("{}/latency_percentiles", path), size)?;
 
     info!("Plotting requests per second...");
     plot_requests_per_second_from_counts(
         &data.rps_counts,
         &format!("{}/requests_per_second.png", path),
+        size,
     )?;
 
     info!("Plotting timeouts per second...");
     plot_timeouts_per_second_from_counts(
         &data.timeouts,
         &format!("{}/timeouts_per_second.png", path),
+        size,
     )?;
 
     info!("Plotting error rate breakdown...");
@@ -212,6 +256,7 @@ pub async fn plot_streaming_metrics(
         &data.transports,
         &data.non_expected,
         &format!("{}/error_rate_breakdown.png", path),
+        size,
     )?;
 
     info!("Plotting status code distribution...");
@@ -222,15 +267,39 @@ pub async fn plot_streaming_metrics(
         &data.status_5xx,
         &data.status_other,
         &format!("{}/status_code_distribution.png", path),
+        size,
     )?;
 
     info!("Plotting in-flight requests...");
-    plot_inflight_requests_from_counts(&data.inflight, &format!("{}/inflight_requests.png", path))?;
+    plot_inflight_requests_from_counts(
+        &data.inflight,
+        &format!("{}/inflight_requests.png", path),
+        size,
+    )?;
+
+    info!("Plotting latency vs in-flight requests...");
+    if !plot_latency_vs_inflight(
+        &data.latency_by_inflight,
+        &format!("{}/latency_vs_inflight.png", path),
+        size,
+    )? {
+        info!("Skipped latency vs in-flight chart: concurrency did not vary enough.");
+    }
+
+    if args.usl_chart {
+        info!("Plotting throughput vs latency...");
+        plot_throughput_vs_latency(
+            &throughput_latency_points(&data.rps_counts, &data.avg_buckets),
+            &format!("{}/throughput_vs_latency.png", path),
+            size,
+        )?;
+    }
 
     info!("Plotting cumulative total requests...");
     plot_cumulative_total_requests_from_buckets(
         &data.total_buckets,
         &format!("{}/cumulative_total_requests.png", path),
+        size,
     )?;
 
     Ok(Some(path))
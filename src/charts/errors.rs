@@ -1,12 +1,15 @@
 use plotters::prelude::*;
 
+use crate::args::ExpectedStatus;
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::MetricRecord;
 
 pub fn plot_error_rate_breakdown(
     metrics: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if metrics.is_empty() {
         return Ok(());
@@ -40,7 +43,7 @@ pub fn plot_error_rate_breakdown(
             continue;
         }
 
-        if metric.status_code != expected_status_code
+        if !expected_status_code.contains(metric.status_code)
             && let Some(slot) = non_expected.get_mut(sec)
         {
             *slot = slot.saturating_add(1);
@@ -58,17 +61,17 @@ pub fn plot_error_rate_breakdown(
         .unwrap_or(1);
     let y_range = 0u32..y_max.saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
         .caption(
             "Error Rate per Second (Breakdown)",
-            ("sans-serif", 30).into_font(),
+            ("sans-serif", size.scale(30)).into_font(),
         )
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(x_range, y_range)?;
 
     chart
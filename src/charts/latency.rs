@@ -2,13 +2,16 @@ use std::collections::BTreeMap;
 
 use plotters::prelude::*;
 
+use crate::args::ExpectedStatus;
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::MetricRecord;
 
 pub fn plot_latency_percentiles(
     metrics: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     base_path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if metrics.is_empty() {
         return Ok(());
@@ -19,10 +22,7 @@ pub fn plot_latency_percentiles(
     for metric in metrics {
         let sec = metric.elapsed_ms / 1000;
         grouped.entry(sec).or_default().push(metric.latency_ms);
-        if metric.status_code == expected_status_code
-            && !metric.timed_out
-            && !metric.transport_error
-        {
+        if metric.is_success(expected_status_code) {
             grouped_ok.entry(sec).or_default().push(metric.latency_ms);
         }
     }
@@ -61,18 +61,18 @@ pub fn plot_latency_percentiles(
         y_max: u64,
     }
 
-    fn draw_chart(seconds: &[u64], series: &LatencySeries) -> AppResult<()> {
-        let root = BitMapBackend::new(&series.file_path, (1600, 600)).into_drawing_area();
+    fn draw_chart(seconds: &[u64], series: &LatencySeries, size: ChartSize) -> AppResult<()> {
+        let root = size.root(&series.file_path);
         root.fill(&WHITE)?;
 
         let x_min = *seconds.first().unwrap_or(&0);
         let x_max = seconds.last().copied().unwrap_or(0).saturating_add(1);
 
         let mut chart = ChartBuilder::on(&root)
-            .caption(series.title, ("sans-serif", 30))
-            .margin(10)
-            .x_label_area_size(30)
-            .y_label_area_size(50)
+            .caption(series.title, ("sans-serif", size.scale(30)))
+            .margin(size.scale(10))
+            .x_label_area_size(size.scale(30))
+            .y_label_area_size(size.scale(50))
             .build_cartesian_2d(x_min..x_max, 0u64..series.y_max)?;
 
         chart
@@ -140,7 +140,7 @@ pub fn plot_latency_percentiles(
     ];
 
     for item in &series {
-        draw_chart(&seconds, item)?;
+        draw_chart(&seconds, item, size)?;
     }
 
     Ok(())
@@ -12,6 +12,7 @@ mod inflight;
 mod latency;
 #[cfg(feature = "legacy-charts")]
 mod rps;
+mod size;
 #[cfg(feature = "legacy-charts")]
 mod status;
 mod streaming;
@@ -46,13 +47,15 @@ pub use streaming::{
     LatencyPercentilesSeries, plot_average_response_time_from_buckets,
     plot_cumulative_error_rate_from_buckets, plot_cumulative_successful_requests_from_buckets,
     plot_cumulative_total_requests_from_buckets, plot_error_rate_breakdown_from_counts,
-    plot_inflight_requests_from_counts, plot_latency_percentiles_series,
+    plot_inflight_requests_from_counts, plot_latency_percentiles_series, plot_latency_vs_inflight,
     plot_requests_per_second_from_counts, plot_status_code_distribution_from_counts,
-    plot_timeouts_per_second_from_counts,
+    plot_throughput_vs_latency, plot_timeouts_per_second_from_counts, throughput_latency_points,
 };
 #[cfg(feature = "legacy-charts")]
 pub use timeouts::plot_timeouts_per_second;
 
+pub use size::ChartSize;
+
 pub(crate) use driver::is_chart_run_dir_name;
 #[cfg(feature = "legacy-charts")]
 pub use driver::plot_metrics;
@@ -1,9 +1,14 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::MetricRecord;
 
-pub fn plot_requests_per_second(metrics: &[MetricRecord], path: &str) -> AppResult<()> {
+pub fn plot_requests_per_second(
+    metrics: &[MetricRecord],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
     if metrics.is_empty() {
         return Ok(());
     }
@@ -27,14 +32,17 @@ pub fn plot_requests_per_second(metrics: &[MetricRecord], path: &str) -> AppResu
     let y_max = *counts.iter().max().unwrap_or(&1);
     let y_range = 0u32..y_max.saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Requests per Second", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "Requests per Second",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(x_range, y_range)?;
 
     chart
@@ -0,0 +1,51 @@
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::args::TesterArgs;
+
+/// DPI at which chart text, margins, and markers keep their base sizes.
+const BASE_DPI: u32 = 96;
+
+/// Image size shared by every chart of a run.
+///
+/// `width` and `height` are the PNG size in pixels; `dpi` scales captions,
+/// margins, label areas, and markers relative to 96 DPI so text stays legible
+/// on large images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartSize {
+    width: u32,
+    height: u32,
+    dpi: u32,
+}
+
+impl Default for ChartSize {
+    fn default() -> Self {
+        Self::new(1_600, 600, BASE_DPI)
+    }
+}
+
+impl ChartSize {
+    #[must_use]
+    pub const fn new(width: u32, height: u32, dpi: u32) -> Self {
+        Self { width, height, dpi }
+    }
+
+    #[must_use]
+    pub const fn from_args(args: &TesterArgs) -> Self {
+        Self::new(args.chart_width, args.chart_height, args.chart_dpi)
+    }
+
+    /// Scales a size given at 96 DPI to this chart's DPI.
+    #[must_use]
+    pub fn scale(self, value: u32) -> u32 {
+        let scaled = u64::from(value)
+            .saturating_mul(u64::from(self.dpi))
+            .checked_div(u64::from(BASE_DPI))
+            .unwrap_or(0);
+        u32::try_from(scaled).unwrap_or(u32::MAX).max(1)
+    }
+
+    pub(crate) fn root(self, path: &str) -> DrawingArea<BitMapBackend<'_>, Shift> {
+        BitMapBackend::new(path, (self.width, self.height)).into_drawing_area()
+    }
+}
@@ -1,9 +1,14 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 use crate::metrics::MetricRecord;
 
-pub fn plot_status_code_distribution(metrics: &[MetricRecord], path: &str) -> AppResult<()> {
+pub fn plot_status_code_distribution(
+    metrics: &[MetricRecord],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
     if metrics.is_empty() {
         return Ok(());
     }
@@ -54,18 +59,18 @@ pub fn plot_status_code_distribution(metrics: &[MetricRecord], path: &str) -> Ap
         .max()
         .unwrap_or(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let x_max = u32::try_from(max_sec.saturating_add(1)).unwrap_or(u32::MAX);
     let mut chart = ChartBuilder::on(&root)
         .caption(
             "HTTP Status Code Distribution",
-            ("sans-serif", 30).into_font(),
+            ("sans-serif", size.scale(30)).into_font(),
         )
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(50)
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(50))
         .build_cartesian_2d(0u32..x_max, 0u32..y_max.saturating_add(1))?;
 
     chart
@@ -2,16 +2,18 @@ use std::collections::BTreeMap;
 
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 
 pub fn plot_average_response_time_from_buckets(
     buckets: &BTreeMap<u64, (u128, u64)>,
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if buckets.is_empty() {
         return Ok(());
     }
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut data: Vec<(u64, u64)> = buckets
@@ -33,10 +35,13 @@ pub fn plot_average_response_time_from_buckets(
         .saturating_add(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Average Response Time", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "Average Response Time",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -57,11 +62,12 @@ pub fn plot_average_response_time_from_buckets(
 pub fn plot_cumulative_successful_requests_from_buckets(
     success_buckets: &BTreeMap<u64, u64>,
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if success_buckets.is_empty() {
         return Ok(());
     }
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let max_bucket = *success_buckets.keys().max().unwrap_or(&0);
@@ -78,10 +84,13 @@ pub fn plot_cumulative_successful_requests_from_buckets(
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Successful Requests", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption(
+            "Cumulative Successful Requests",
+            ("sans-serif", size.scale(30)),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -102,11 +111,12 @@ pub fn plot_cumulative_successful_requests_from_buckets(
 pub fn plot_cumulative_error_rate_from_buckets(
     error_buckets: &BTreeMap<u64, u64>,
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if error_buckets.is_empty() {
         return Ok(());
     }
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let max_bucket = *error_buckets.keys().max().unwrap_or(&0);
@@ -123,10 +133,10 @@ pub fn plot_cumulative_error_rate_from_buckets(
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Error Rate", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption("Cumulative Error Rate", ("sans-serif", size.scale(30)))
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
@@ -147,11 +157,12 @@ pub fn plot_cumulative_error_rate_from_buckets(
 pub fn plot_cumulative_total_requests_from_buckets(
     total_buckets: &BTreeMap<u64, u64>,
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if total_buckets.is_empty() {
         return Ok(());
     }
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let max_bucket = *total_buckets.keys().max().unwrap_or(&0);
@@ -168,10 +179,10 @@ pub fn plot_cumulative_total_requests_from_buckets(
     let y_max = data.last().map(|(_, y)| y.saturating_add(1)).unwrap_or(1);
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Cumulative Total Requests", ("sans-serif", 30))
-        .margin(10)
-        .x_label_area_size(50)
-        .y_label_area_size(60)
+        .caption("Cumulative Total Requests", ("sans-serif", size.scale(30)))
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(50))
+        .y_label_area_size(size.scale(60))
         .build_cartesian_2d(0u64..x_max, 0u64..y_max)?;
 
     chart
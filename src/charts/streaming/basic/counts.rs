@@ -1,8 +1,13 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 
-pub fn plot_requests_per_second_from_counts(counts: &[u32], path: &str) -> AppResult<()> {
+pub fn plot_requests_per_second_from_counts(
+    counts: &[u32],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
     if counts.is_empty() {
         return Ok(());
     }
@@ -12,14 +17,17 @@ pub fn plot_requests_per_second_from_counts(counts: &[u32], path: &str) -> AppRe
     let y_max = *counts.iter().max().unwrap_or(&1);
     let y_range = 0u32..y_max.saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Requests per Second", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "Requests per Second",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(x_range, y_range)?;
 
     chart
@@ -40,7 +48,11 @@ pub fn plot_requests_per_second_from_counts(counts: &[u32], path: &str) -> AppRe
     Ok(())
 }
 
-pub fn plot_timeouts_per_second_from_counts(counts: &[u32], path: &str) -> AppResult<()> {
+pub fn plot_timeouts_per_second_from_counts(
+    counts: &[u32],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
     if counts.is_empty() {
         return Ok(());
     }
@@ -50,14 +62,17 @@ pub fn plot_timeouts_per_second_from_counts(counts: &[u32], path: &str) -> AppRe
     let y_max = *counts.iter().max().unwrap_or(&1);
     let y_range = 0u32..y_max.saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("Timeouts per Second", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "Timeouts per Second",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(x_range, y_range)?;
 
     chart
@@ -78,7 +93,11 @@ pub fn plot_timeouts_per_second_from_counts(counts: &[u32], path: &str) -> AppRe
     Ok(())
 }
 
-pub fn plot_inflight_requests_from_counts(inflight: &[u32], path: &str) -> AppResult<()> {
+pub fn plot_inflight_requests_from_counts(
+    inflight: &[u32],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
     if inflight.is_empty() {
         return Ok(());
     }
@@ -88,14 +107,17 @@ pub fn plot_inflight_requests_from_counts(inflight: &[u32], path: &str) -> AppRe
     let y_max = inflight.iter().copied().max().unwrap_or(1);
     let y_range = 0u32..y_max.saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption("In-Flight Requests", ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .caption(
+            "In-Flight Requests",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(x_range, y_range)?;
 
     chart
@@ -1,5 +1,6 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 
 pub fn plot_error_rate_breakdown_from_counts(
@@ -7,6 +8,7 @@ pub fn plot_error_rate_breakdown_from_counts(
     transports: &[u32],
     non_expected: &[u32],
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if timeouts.is_empty() && transports.is_empty() && non_expected.is_empty() {
         return Ok(());
@@ -26,17 +28,17 @@ pub fn plot_error_rate_breakdown_from_counts(
         .unwrap_or(1);
     let y_range = 0u32..y_max.saturating_add(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let mut chart = ChartBuilder::on(&root)
         .caption(
             "Error Rate per Second (Breakdown)",
-            ("sans-serif", 30).into_font(),
+            ("sans-serif", size.scale(30)).into_font(),
         )
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(40))
         .build_cartesian_2d(x_range, y_range)?;
 
     chart
@@ -95,6 +97,7 @@ pub fn plot_error_rate_breakdown_from_counts(
     Ok(())
 }
 
+#[expect(clippy::too_many_arguments)]
 pub fn plot_status_code_distribution_from_counts(
     counts_2xx: &[u32],
     counts_3xx: &[u32],
@@ -102,6 +105,7 @@ pub fn plot_status_code_distribution_from_counts(
     counts_5xx: &[u32],
     counts_other: &[u32],
     path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     let len = counts_2xx
         .len()
@@ -152,18 +156,18 @@ pub fn plot_status_code_distribution_from_counts(
         .max()
         .unwrap_or(1);
 
-    let root = BitMapBackend::new(path, (1600, 600)).into_drawing_area();
+    let root = size.root(path);
     root.fill(&WHITE)?;
 
     let x_max = u32::try_from(len.saturating_add(1)).unwrap_or(u32::MAX);
     let mut chart = ChartBuilder::on(&root)
         .caption(
             "HTTP Status Code Distribution",
-            ("sans-serif", 30).into_font(),
+            ("sans-serif", size.scale(30)).into_font(),
         )
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(50)
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(50))
         .build_cartesian_2d(0u32..x_max, 0u32..y_max.saturating_add(1))?;
 
     chart
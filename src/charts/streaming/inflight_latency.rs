@@ -0,0 +1,88 @@
+use plotters::prelude::*;
+
+use crate::charts::ChartSize;
+use crate::error::AppResult;
+use crate::metrics::InflightLatencyPoint;
+
+/// Minimum samples an in-flight level needs before it is plotted.
+const MIN_SAMPLES_PER_LEVEL: u64 = 5;
+/// Minimum distinct in-flight levels for the curve to show a trend.
+const MIN_INFLIGHT_LEVELS: usize = 3;
+
+/// Keeps the in-flight levels with enough samples to produce stable percentiles.
+fn meaningful_inflight_levels(points: &[InflightLatencyPoint]) -> Vec<InflightLatencyPoint> {
+    let levels: Vec<InflightLatencyPoint> = points
+        .iter()
+        .copied()
+        .filter(|point| point.samples >= MIN_SAMPLES_PER_LEVEL)
+        .collect();
+    if levels.len() < MIN_INFLIGHT_LEVELS {
+        return Vec::new();
+    }
+    levels
+}
+
+/// Plots p50/p99 latency against in-flight concurrency (the saturation "hockey stick").
+///
+/// Returns `false` without writing a file when concurrency did not vary enough.
+pub fn plot_latency_vs_inflight(
+    points: &[InflightLatencyPoint],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<bool> {
+    let levels = meaningful_inflight_levels(points);
+    let (Some(first), Some(last)) = (levels.first(), levels.last()) else {
+        return Ok(false);
+    };
+
+    let x_range = first.in_flight..last.in_flight.saturating_add(1);
+    let y_max = levels
+        .iter()
+        .map(|point| point.p99.max(point.p50))
+        .max()
+        .unwrap_or(1)
+        .saturating_add(1);
+
+    let root = size.root(path);
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            "Latency vs In-Flight Requests",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(50))
+        .build_cartesian_2d(x_range, 0u64..y_max)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("In-Flight Requests")
+        .y_desc("Latency (ms)")
+        .draw()?;
+
+    chart
+        .draw_series(LineSeries::new(
+            levels.iter().map(|point| (point.in_flight, point.p50)),
+            &BLUE,
+        ))?
+        .label("P50")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x.saturating_add(20), y)], BLUE));
+    chart
+        .draw_series(LineSeries::new(
+            levels.iter().map(|point| (point.in_flight, point.p99)),
+            &RED,
+        ))?
+        .label("P99")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x.saturating_add(20), y)], RED));
+
+    chart
+        .configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()?;
+
+    root.present()?;
+    Ok(true)
+}
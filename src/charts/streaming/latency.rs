@@ -1,5 +1,6 @@
 use plotters::prelude::*;
 
+use crate::charts::ChartSize;
 use crate::error::AppResult;
 
 pub struct LatencyPercentilesSeries<'series> {
@@ -16,6 +17,7 @@ pub struct LatencyPercentilesSeries<'series> {
 pub fn plot_latency_percentiles_series(
     series: &LatencyPercentilesSeries<'_>,
     base_path: &str,
+    size: ChartSize,
 ) -> AppResult<()> {
     if series.buckets_ms.is_empty() {
         return Ok(());
@@ -28,8 +30,13 @@ pub fn plot_latency_percentiles_series(
         file_path: String,
     }
 
-    fn draw_chart(buckets_ms: &[u64], series: &LatencySeries<'_>, bucket_ms: u64) -> AppResult<()> {
-        let root = BitMapBackend::new(&series.file_path, (1600, 600)).into_drawing_area();
+    fn draw_chart(
+        buckets_ms: &[u64],
+        series: &LatencySeries<'_>,
+        bucket_ms: u64,
+        size: ChartSize,
+    ) -> AppResult<()> {
+        let root = size.root(&series.file_path);
         root.fill(&WHITE)?;
 
         let mut combined: Vec<(u64, u64)> = buckets_ms
@@ -53,10 +60,10 @@ pub fn plot_latency_percentiles_series(
             .saturating_add(1);
 
         let mut chart = ChartBuilder::on(&root)
-            .caption(series.title, ("sans-serif", 30))
-            .margin(10)
-            .x_label_area_size(30)
-            .y_label_area_size(50)
+            .caption(series.title, ("sans-serif", size.scale(30)))
+            .margin(size.scale(10))
+            .x_label_area_size(size.scale(30))
+            .y_label_area_size(size.scale(50))
             .build_cartesian_2d(x_min..x_max, 0u64..y_max)?;
 
         chart
@@ -146,7 +153,7 @@ pub fn plot_latency_percentiles_series(
     ];
 
     for item in &chart_series {
-        draw_chart(series.buckets_ms, item, series.bucket_ms)?;
+        draw_chart(series.buckets_ms, item, series.bucket_ms, size)?;
     }
 
     Ok(())
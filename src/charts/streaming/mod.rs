@@ -1,6 +1,8 @@
 mod basic;
 mod breakdown;
+mod inflight_latency;
 mod latency;
+mod usl;
 
 pub use basic::{
     plot_average_response_time_from_buckets, plot_cumulative_error_rate_from_buckets,
@@ -11,4 +13,6 @@ pub use basic::{
 pub use breakdown::{
     plot_error_rate_breakdown_from_counts, plot_status_code_distribution_from_counts,
 };
+pub use inflight_latency::plot_latency_vs_inflight;
 pub use latency::{LatencyPercentilesSeries, plot_latency_percentiles_series};
+pub use usl::{plot_throughput_vs_latency, throughput_latency_points};
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use plotters::prelude::*;
+
+use crate::charts::ChartSize;
+use crate::error::AppResult;
+
+/// Number of 100ms average-latency buckets per second.
+const BUCKETS_PER_SEC: u64 = 10;
+
+/// Pairs each second's achieved throughput with its average latency.
+///
+/// Seconds without completed requests are dropped so idle gaps do not pin
+/// points to the origin.
+#[must_use]
+pub fn throughput_latency_points(
+    rps_counts: &[u32],
+    avg_buckets: &BTreeMap<u64, (u128, u64)>,
+) -> Vec<(u32, u64)> {
+    let mut per_second: BTreeMap<u64, (u128, u64)> = BTreeMap::new();
+    for (bucket, (sum_ms, count)) in avg_buckets {
+        let entry = per_second.entry(bucket / BUCKETS_PER_SEC).or_insert((0, 0));
+        entry.0 = entry.0.saturating_add(*sum_ms);
+        entry.1 = entry.1.saturating_add(*count);
+    }
+
+    rps_counts
+        .iter()
+        .enumerate()
+        .filter(|(_, rps)| **rps > 0)
+        .filter_map(|(sec, rps)| {
+            let (sum_ms, count) = per_second.get(&u64::try_from(sec).ok()?)?;
+            let avg_ms = sum_ms.checked_div(u128::from(*count))?;
+            Some((*rps, u64::try_from(avg_ms).unwrap_or(u64::MAX)))
+        })
+        .collect()
+}
+
+/// Plots achieved throughput (x) against average latency (y), one point per second.
+pub fn plot_throughput_vs_latency(
+    points: &[(u32, u64)],
+    path: &str,
+    size: ChartSize,
+) -> AppResult<()> {
+    if points.is_empty() {
+        return Ok(());
+    }
+
+    let x_max = points
+        .iter()
+        .map(|(rps, _)| *rps)
+        .max()
+        .unwrap_or(1)
+        .saturating_add(1);
+    let y_max = points
+        .iter()
+        .map(|(_, latency)| *latency)
+        .max()
+        .unwrap_or(1)
+        .saturating_add(1);
+
+    let root = size.root(path);
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            "Throughput vs Latency",
+            ("sans-serif", size.scale(30)).into_font(),
+        )
+        .margin(size.scale(10))
+        .x_label_area_size(size.scale(40))
+        .y_label_area_size(size.scale(50))
+        .build_cartesian_2d(0u32..x_max, 0u64..y_max)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Throughput (requests/second)")
+        .y_desc("Avg Latency (ms)")
+        .draw()?;
+
+    chart.draw_series(
+        points
+            .iter()
+            .map(|(rps, latency)| Circle::new((*rps, *latency), size.scale(3), BLUE.filled())),
+    )?;
+
+    root.present()?;
+    Ok(())
+}
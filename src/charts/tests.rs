@@ -5,11 +5,21 @@ use std::time::Duration;
 use tempfile::tempdir;
 use tokio::io::AsyncWriteExt;
 
-use super::{LatencyPercentilesSeries, is_chart_run_dir_name, plot_streaming_metrics};
+use super::aggregated::exclude_warmup;
+use super::{
+    ChartSize, LatencyPercentilesSeries, is_chart_run_dir_name, plot_latency_vs_inflight,
+    plot_streaming_metrics, throughput_latency_points,
+};
 use crate::app::logs;
-use crate::args::{HttpMethod, LoadMode, PositiveU64, PositiveUsize, Protocol, TesterArgs};
+use crate::args::{
+    ChartSetting, ExpectedStatus, HttpMethod, LoadMode, PositiveU64, PositiveUsize, Protocol,
+    TesterArgs,
+};
 use crate::error::{AppError, AppResult};
-use crate::metrics::{MetricRecord, StreamingChartData};
+use crate::metrics::{
+    AggregatedMetricSample, MetricRecord, Metrics, MetricsLoggerConfig, StreamingChartData,
+    setup_metrics_logger,
+};
 
 fn sample_metrics() -> Vec<MetricRecord> {
     vec![
@@ -21,6 +31,7 @@ fn sample_metrics() -> Vec<MetricRecord> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            check_failed: false,
         },
         MetricRecord {
             elapsed_ms: 100,
@@ -30,6 +41,7 @@ fn sample_metrics() -> Vec<MetricRecord> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            check_failed: false,
         },
         MetricRecord {
             elapsed_ms: 200,
@@ -39,6 +51,7 @@ fn sample_metrics() -> Vec<MetricRecord> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            check_failed: false,
         },
         MetricRecord {
             elapsed_ms: 400,
@@ -48,6 +61,7 @@ fn sample_metrics() -> Vec<MetricRecord> {
             transport_error: true,
             response_bytes: 0,
             in_flight_ops: 0,
+            check_failed: false,
         },
     ]
 }
@@ -56,7 +70,7 @@ fn sample_metrics() -> Vec<MetricRecord> {
 fn plot_latency_percentiles_single_second() -> AppResult<()> {
     run_async_test(async {
         let metrics = sample_metrics();
-        let (dir, data) = build_streaming_data(&metrics, 200).await?;
+        let (dir, data) = build_streaming_data(&metrics, ExpectedStatus::single(200)).await?;
 
         let base_path = dir.path().join("latency_percentiles");
         let base_path_str = match base_path.to_str() {
@@ -74,7 +88,12 @@ fn plot_latency_percentiles_single_second() -> AppResult<()> {
             p99_ok: &data.p99_ok,
         };
 
-        super::plot_latency_percentiles_series(&series, base_path_str).map_err(|err| {
+        super::plot_latency_percentiles_series(
+            &series,
+            base_path_str,
+            ChartSize::new(800, 300, 192),
+        )
+        .map_err(|err| {
             AppError::metrics(format!("plot_latency_percentiles_series failed: {}", err))
         })?;
 
@@ -85,8 +104,14 @@ fn plot_latency_percentiles_single_second() -> AppResult<()> {
         let p99_path = format!("{}_P99_all.png", base_path_str);
         let p99_ok_path = format!("{}_P99_ok.png", base_path_str);
 
-        if std::fs::metadata(p50_path).is_err() {
-            return Err(AppError::metrics("Missing P50 output"));
+        // PNG stores width and height big-endian right after the IHDR tag.
+        let png = std::fs::read(&p50_path)
+            .map_err(|err| AppError::metrics(format!("Missing P50 output: {}", err)))?;
+        if png.get(16..24) != Some(&[0, 0, 3, 32, 0, 0, 1, 44][..]) {
+            return Err(AppError::metrics(format!(
+                "Expected an 800x300 chart, got header {:?}",
+                png.get(16..24)
+            )));
         }
         if std::fs::metadata(p50_ok_path).is_err() {
             return Err(AppError::metrics("Missing P50 ok output"));
@@ -121,7 +146,7 @@ where
 
 async fn build_streaming_data(
     metrics: &[MetricRecord],
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
 ) -> AppResult<(tempfile::TempDir, StreamingChartData)> {
     let dir = tempdir()
         .map_err(|err| AppError::metrics(format!("Failed to create temp dir: {}", err)))?;
@@ -133,12 +158,14 @@ async fn build_streaming_data(
     for metric in metrics {
         writeln!(
             &mut content,
-            "{},{},{},{},{}",
+            "{},{},{},{},{},{},{}",
             metric.elapsed_ms,
             metric.latency_ms,
             metric.status_code,
             u8::from(metric.timed_out),
-            u8::from(metric.transport_error)
+            u8::from(metric.transport_error),
+            metric.response_bytes,
+            metric.in_flight_ops
         )
         .map_err(|err| AppError::metrics(format!("Failed to format log line: {}", err)))?;
     }
@@ -153,6 +180,252 @@ async fn build_streaming_data(
     Ok((dir, data))
 }
 
+#[test]
+fn streaming_data_sums_response_bytes_per_second() -> AppResult<()> {
+    run_async_test(async {
+        let metrics: Vec<MetricRecord> = [(100u64, 300u64), (900, 200), (1_500, 1_000)]
+            .into_iter()
+            .map(|(elapsed_ms, response_bytes)| MetricRecord {
+                elapsed_ms,
+                latency_ms: 5,
+                status_code: 200,
+                timed_out: false,
+                transport_error: false,
+                response_bytes,
+                in_flight_ops: 1,
+                check_failed: false,
+            })
+            .collect();
+        let (_dir, data) = build_streaming_data(&metrics, ExpectedStatus::single(200)).await?;
+        if data.response_bytes != vec![500, 1_000] {
+            return Err(AppError::metrics(format!(
+                "Unexpected bytes per second: {:?}",
+                data.response_bytes
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn latency_vs_inflight_requires_varied_concurrency() -> AppResult<()> {
+    run_async_test(async {
+        let mut metrics = Vec::new();
+        for level in 0u64..4 {
+            for offset in 0u64..5 {
+                metrics.push(MetricRecord {
+                    elapsed_ms: level.saturating_mul(1_000).saturating_add(offset),
+                    latency_ms: 10u64
+                        .saturating_add(level.saturating_mul(level).saturating_mul(20)),
+                    status_code: 200,
+                    timed_out: false,
+                    transport_error: false,
+                    response_bytes: 0,
+                    in_flight_ops: level.saturating_mul(8),
+                    check_failed: false,
+                });
+            }
+        }
+        let (dir, data) = build_streaming_data(&metrics, ExpectedStatus::single(200)).await?;
+
+        let levels: Vec<(u64, u64)> = data
+            .latency_by_inflight
+            .iter()
+            .map(|point| (point.in_flight, point.p50))
+            .collect();
+        if levels != vec![(0, 10), (8, 30), (16, 90), (24, 190)] {
+            return Err(AppError::metrics(format!(
+                "Unexpected latency by in-flight: {:?}",
+                levels
+            )));
+        }
+
+        let varied = dir.path().join("varied.png");
+        let varied_path = varied
+            .to_str()
+            .ok_or_else(|| AppError::metrics("Failed to convert path to string"))?;
+        if !plot_latency_vs_inflight(&data.latency_by_inflight, varied_path, ChartSize::default())?
+            || !varied.exists()
+        {
+            return Err(AppError::metrics("Expected latency vs in-flight chart"));
+        }
+
+        let (_flat_dir, flat) =
+            build_streaming_data(&sample_metrics(), ExpectedStatus::single(200)).await?;
+        let flat_path = dir.path().join("flat.png");
+        let flat_path_str = flat_path
+            .to_str()
+            .ok_or_else(|| AppError::metrics("Failed to convert path to string"))?;
+        if plot_latency_vs_inflight(
+            &flat.latency_by_inflight,
+            flat_path_str,
+            ChartSize::default(),
+        )? || flat_path.exists()
+        {
+            return Err(AppError::metrics(
+                "Chart should be skipped when concurrency is constant",
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn warmup_shifts_streaming_chart_origin() -> AppResult<()> {
+    run_async_test(async {
+        let dir = tempdir()
+            .map_err(|err| AppError::metrics(format!("Failed to create temp dir: {}", err)))?;
+        let log_path = dir.path().join("metrics.log");
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+        let run_start = tokio::time::Instant::now();
+        let logger_config = MetricsLoggerConfig {
+            run_start,
+            warmup: Some(Duration::from_secs(5)),
+            expected_status_code: ExpectedStatus::single(200),
+            metrics_range: None,
+            metrics_max: 0,
+            db_url: None,
+            per_status_latency: false,
+        };
+        let handle = setup_metrics_logger(log_path.clone(), logger_config, rx);
+
+        // Two requests per second over a 15s run; the first 10 fall in warmup.
+        for step in 0u64..30 {
+            let start = run_start
+                .checked_add(Duration::from_millis(step.saturating_mul(500)))
+                .ok_or_else(|| AppError::metrics("Failed to add duration"))?;
+            tx.send(Metrics {
+                start,
+                response_time: Duration::from_millis(if step < 10 { 900 } else { 10 }),
+                status_code: 200,
+                timed_out: false,
+                transport_error: false,
+                response_bytes: 0,
+                in_flight_ops: 1,
+                error_message: None,
+                http_version: None,
+                url: None,
+                connection: None,
+                error_reason: None,
+                check_failure: None,
+            })
+            .await
+            .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+        }
+        drop(tx);
+        handle
+            .await
+            .map_err(|err| AppError::metrics(format!("Logger task failed: {}", err)))??;
+
+        let data =
+            logs::load_chart_data_streaming(&[log_path], ExpectedStatus::single(200), &None, 1_000)
+                .await?;
+        if data.rps_counts != vec![2; 10] {
+            return Err(AppError::metrics(format!(
+                "Expected 10 post-warmup seconds of 2 rps, got {:?}",
+                data.rps_counts
+            )));
+        }
+        if data.total_buckets.first_key_value() != Some((&0, &1)) {
+            return Err(AppError::metrics(format!(
+                "Expected the first bucket at the warmup end, got {:?}",
+                data.total_buckets.first_key_value()
+            )));
+        }
+        if data.total_buckets.values().sum::<u64>() != 20 {
+            return Err(AppError::metrics("Expected warmup requests to be excluded"));
+        }
+        if data.latency_buckets_ms.first() != Some(&0) || data.p99.first() != Some(&10) {
+            return Err(AppError::metrics(format!(
+                "Expected post-warmup latency in the first bucket, got {:?} / {:?}",
+                data.latency_buckets_ms.first(),
+                data.p99.first()
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn warmup_rebases_aggregated_samples() -> AppResult<()> {
+    let samples: Vec<AggregatedMetricSample> = (1u64..=15)
+        .map(|sec| AggregatedMetricSample {
+            elapsed_ms: sec.saturating_mul(1_000),
+            total_requests: sec.saturating_mul(10),
+            successful_requests: sec.saturating_mul(9),
+            error_requests: sec,
+            avg_latency_ms: if sec < 5 { 100 } else { 60 },
+            p50_latency_ms: 50,
+            p90_latency_ms: 90,
+            p99_latency_ms: 99,
+        })
+        .collect();
+
+    let trimmed = exclude_warmup(&samples, 5_000);
+    let first = trimmed
+        .first()
+        .ok_or_else(|| AppError::metrics("Expected post-warmup samples"))?;
+    if first.elapsed_ms != 0 || first.total_requests != 10 || first.error_requests != 1 {
+        return Err(AppError::metrics(format!(
+            "Expected the first sample at the warmup end, got {:?}",
+            first
+        )));
+    }
+    let last = trimmed
+        .last()
+        .ok_or_else(|| AppError::metrics("Expected post-warmup samples"))?;
+    if last.elapsed_ms != 10_000 || last.total_requests != 110 || last.successful_requests != 99 {
+        return Err(AppError::metrics(format!(
+            "Unexpected last sample after warmup: {:?}",
+            last
+        )));
+    }
+    if trimmed.len() != 11 {
+        return Err(AppError::metrics(format!(
+            "Expected 11 samples, got {}",
+            trimmed.len()
+        )));
+    }
+    if exclude_warmup(&samples, 0).len() != samples.len() {
+        return Err(AppError::metrics("Expected no warmup to keep every sample"));
+    }
+    Ok(())
+}
+
+#[test]
+fn chart_settings_are_bounded_and_scale_with_dpi() -> AppResult<()> {
+    if ChartSetting::Width.check(7_680).ok() != Some(7_680)
+        || ChartSetting::Width.check(7_681).is_ok()
+        || ChartSetting::Height.check(199).is_ok()
+        || ChartSetting::Dpi.check(71).is_ok()
+        || ChartSetting::Dpi.check(600).ok() != Some(600)
+    {
+        return Err(AppError::metrics("Unexpected chart setting bounds"));
+    }
+    let print = ChartSize::new(3_200, 1_200, 300);
+    if print.scale(30) != 93 || ChartSize::default().scale(30) != 30 {
+        return Err(AppError::metrics(format!(
+            "Unexpected DPI scaling: {}",
+            print.scale(30)
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn throughput_latency_points_pair_rps_with_average_latency() -> AppResult<()> {
+    let rps_counts = [2, 0, 3];
+    let avg_buckets = std::collections::BTreeMap::from([(0, (20, 1)), (5, (40, 1)), (21, (90, 3))]);
+    let points = throughput_latency_points(&rps_counts, &avg_buckets);
+    if points != vec![(2, 30), (3, 30)] {
+        return Err(AppError::metrics(format!(
+            "Unexpected throughput/latency points: {:?}",
+            points
+        )));
+    }
+    Ok(())
+}
+
 #[test]
 fn plot_metrics_creates_files() -> AppResult<()> {
     run_async_test(async {
@@ -176,14 +449,27 @@ fn plot_metrics_creates_files() -> AppResult<()> {
             replay_snapshot_end: None,
             replay_snapshot_out: None,
             replay_snapshot_format: "json".to_owned(),
+            replay_video: None,
+            replay_export_chart_data: None,
+            replay_export_charts: false,
             method: HttpMethod::Get,
             protocol: Protocol::Http,
+            grpc_mode: None,
+            ws_messages_per_conn: None,
+            ws_message_interval_ms: None,
+            mqtt_mode: crate::args::MqttMode::Publish,
+            mqtt_topic: None,
+            sse_events_per_conn: None,
+            sse_conn_duration: None,
+            sse_resume: false,
+            min_events: None,
             load_mode: LoadMode::Arrival,
             url: Some("http://localhost".to_owned()),
             urls_from_file: false,
             rand_regex_url: false,
             max_repeat: PositiveUsize::try_from(4)?,
             dump_urls: None,
+            dry_run: false,
             headers: vec![],
             accept_header: None,
             content_type: None,
@@ -191,32 +477,59 @@ fn plot_metrics_creates_files() -> AppResult<()> {
             authorized: false,
             data: String::new(),
             form: vec![],
+            form_file: vec![],
             basic_auth: None,
+            basic_auth_file: None,
+            bearer_token: None,
+            bearer_token_file: None,
+            digest_auth: None,
+            oauth2_token_url: None,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_scope: None,
             aws_session: None,
             aws_sigv4: None,
             data_file: None,
             data_lines: None,
+            data_dir: None,
+            data_dir_max_bytes: None,
+            seed: None,
+            json_template: false,
+            literal_placeholders: false,
             target_duration: PositiveU64::try_from(1)?,
             wait_ongoing_requests_after_deadline: false,
+            align_to_second: false,
+            arrival_distribution: None,
             requests: None,
-            expected_status_code: 200,
+            expected_status_code: ExpectedStatus::single(200),
             request_timeout: Duration::from_secs(10),
             redirect_limit: 10,
             disable_keepalive: false,
+            requests_per_connection: None,
             disable_compression: false,
+            cookies: false,
+            session_cookie: None,
+            backend_header: "X-Backend".to_owned(),
             pool_max_idle_per_host: None,
             pool_idle_timeout_ms: None,
             http_version: None,
             connect_timeout: Duration::from_secs(5),
             charts_path: charts_path.clone(),
             no_charts: false,
+            no_final_charts: false,
             charts_latency_bucket_ms: PositiveU64::try_from(100)?,
+            chart_width: 1_600,
+            chart_height: 600,
+            chart_dpi: 96,
+            usl_chart: true,
             verbose: false,
             config: None,
+            config_profile: None,
             tmp_path: "./tmp".to_owned(),
             load_profile: None,
             controller_listen: None,
             controller_mode: crate::args::ControllerMode::Auto,
+            controller_also_runs: false,
             control_listen: None,
             control_auth_token: None,
             agent_join: None,
@@ -229,20 +542,48 @@ fn plot_metrics_creates_files() -> AppResult<()> {
             agent_reconnect_ms: PositiveU64::try_from(1000)?,
             agent_heartbeat_interval_ms: PositiveU64::try_from(1000)?,
             agent_heartbeat_timeout_ms: PositiveU64::try_from(3000)?,
+            agent_health_listen: None,
             keep_tmp: false,
             warmup: None,
+            warmup_requests_file: None,
+            echo_config_on_start: false,
+            echo_config_format: crate::args::EchoConfigFormat::Text,
             output: None,
             output_format: None,
             time_unit: None,
             export_csv: None,
             export_json: None,
             export_jsonl: None,
+            export_histogram: None,
             db_url: None,
+            checkpoint_out: None,
+            checkpoint_interval_secs: None,
+            stream_samples: None,
+            resume: false,
             log_shards: PositiveUsize::try_from(1)?,
             no_ui: true,
             no_splash: true,
+            hold_ui: false,
+            stop_file: None,
             ui_window_ms: PositiveU64::try_from(10_000)?,
+            baseline: None,
+            baseline_label: None,
             summary: false,
+            summary_template: None,
+            summary_out: None,
+            history_file: "./history.jsonl".to_owned(),
+            no_history: true,
+            notify_webhook: None,
+            notify_on: crate::args::NotifyOn::Always,
+            protocol_version_report: false,
+            connection_timing: false,
+            strict_charts: false,
+            per_status_latency: false,
+            slowest: None,
+            summary_percentile_table: false,
+            stats_extended: false,
+            compat: None,
+            summary_format: None,
             show_selections: false,
             tls_min: None,
             tls_max: None,
@@ -264,11 +605,39 @@ fn plot_metrics_creates_files() -> AppResult<()> {
             rate_limit: None,
             burst_delay: None,
             burst_rate: PositiveUsize::try_from(1)?,
+            find_max_rate: false,
+            target_p99_ms: None,
             latency_correction: false,
+            max_bandwidth: None,
+            sim_downlink: None,
+            sim_latency: None,
+            connect_rate: None,
+            max_bytes: None,
+            abort_on_errors: None,
+            abort_on_error_rate: None,
+            abort_min_requests: PositiveU64::try_from(100)?,
+            verify_request_id_echo: false,
+            request_id_header: "X-Request-Id".to_owned(),
+            deadline_header: None,
+            deadline_format: crate::args::DeadlineFormat::RelativeMs,
+            correlation_header: None,
+            correlation_log: None,
+            rate_schedule: None,
+            assert_response_bytes_below: None,
+            assert_response_bytes_above: None,
+            assert_body_contains: None,
+            assert_body_regex: None,
+            assert_json: None,
+            assert_success_rate: None,
+            assert_p99_ms: None,
+            assert_max_errors: None,
+            diagnose: false,
+            diagnose_strict: false,
             connect_to: vec![],
             host_header: None,
             ipv6_only: false,
             ipv4_only: false,
+            doh_url: None,
             no_pre_lookup: false,
             no_color: false,
             ui_fps: 16,
@@ -280,6 +649,8 @@ fn plot_metrics_creates_files() -> AppResult<()> {
             alloc_profiler_ms: None,
             alloc_profiler_dump_ms: None,
             alloc_profiler_dump_path: "./alloc-prof".to_owned(),
+            alloc_profiler_spike_ms: None,
+            alloc_profiler_spike_inflight: None,
             scenario: None,
             script: None,
             plugin: vec![],
@@ -287,8 +658,12 @@ fn plot_metrics_creates_files() -> AppResult<()> {
             uninstall_service: false,
             service_name: None,
             sinks: None,
+            tags: Vec::new(),
             distributed_silent: false,
+            run_id: None,
             distributed_stream_summaries: false,
+            agent_breakdown: false,
+            per_agent_summary: false,
             distributed_stream_interval_ms: None,
         };
 
@@ -315,6 +690,7 @@ fn plot_metrics_creates_files() -> AppResult<()> {
             "status_code_distribution.png",
             "inflight_requests.png",
             "cumulative_total_requests.png",
+            "throughput_vs_latency.png",
         ];
 
         for file in expected {
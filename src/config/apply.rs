@@ -61,11 +61,23 @@ fn validate_config_conflicts(config: &ConfigFile) -> AppResult<()> {
             right: "data_lines",
         }));
     }
-    if config.form.is_some()
+    if config.data_dir.is_some()
+        && (config.data.is_some()
+            || config.data_file.is_some()
+            || config.data_lines.is_some()
+            || config.form.is_some()
+            || config.form_file.is_some())
+    {
+        return Err(AppError::config(ConfigError::Conflict {
+            left: "data_dir",
+            right: "data/data_file/data_lines/form/form_file",
+        }));
+    }
+    if (config.form.is_some() || config.form_file.is_some())
         && (config.data.is_some() || config.data_file.is_some() || config.data_lines.is_some())
     {
         return Err(AppError::config(ConfigError::Conflict {
-            left: "form",
+            left: "form/form_file",
             right: "data/data_file/data_lines",
         }));
     }
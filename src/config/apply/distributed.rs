@@ -62,6 +62,12 @@ pub(super) fn apply_distributed_config(
         }
     }
 
+    if !is_cli(matches, "controller_also_runs")
+        && let Some(also_runs) = config.controller_also_runs
+    {
+        args.controller_also_runs = also_runs;
+    }
+
     if !is_cli(matches, "control_listen")
         && let Some(listen) = config.control_listen.clone()
     {
@@ -134,6 +140,12 @@ pub(super) fn apply_distributed_config(
             ensure_positive_u64(timeout_ms, "distributed.agent_heartbeat_timeout_ms")?;
     }
 
+    if !is_cli(matches, "agent_health_listen")
+        && let Some(listen) = config.agent_health_listen.clone()
+    {
+        args.agent_health_listen = Some(listen);
+    }
+
     if !is_cli(matches, "distributed_stream_summaries")
         && let Some(stream_summaries) = config.stream_summaries
     {
@@ -149,5 +161,17 @@ pub(super) fn apply_distributed_config(
         )?);
     }
 
+    if !is_cli(matches, "agent_breakdown")
+        && let Some(agent_breakdown) = config.agent_breakdown
+    {
+        args.agent_breakdown = agent_breakdown;
+    }
+
+    if !is_cli(matches, "per_agent_summary")
+        && let Some(per_agent_summary) = config.per_agent_summary
+    {
+        args.per_agent_summary = per_agent_summary;
+    }
+
     Ok(())
 }
@@ -1,7 +1,9 @@
-use crate::args::{LoadProfile, LoadStage};
+use std::time::Duration;
+
+use crate::args::{LoadProfile, LoadStage, SineWave};
 use crate::error::{AppError, AppResult, ConfigError};
 
-use super::super::types::{LoadConfig, LoadStageConfig};
+use super::super::types::{LoadConfig, LoadStageConfig, LoadStageKind};
 
 pub(super) fn parse_load_profile(load: &LoadConfig) -> AppResult<LoadProfile> {
     let initial_rpm = resolve_rpm(load.rate, load.rpm, "load")?.unwrap_or(0);
@@ -10,10 +12,15 @@ pub(super) fn parse_load_profile(load: &LoadConfig) -> AppResult<LoadProfile> {
     if let Some(stage_configs) = load.stages.as_ref() {
         for (idx, stage) in stage_configs.iter().enumerate() {
             let duration = super::super::parse_duration_value(&stage.duration)?;
-            let target_rpm = resolve_stage_rpm(stage, idx)?;
+            let sine = resolve_stage_sine(stage, idx)?;
+            let target_rpm = match sine {
+                Some(sine) => sine.min_rpm,
+                None => resolve_stage_rpm(stage, idx)?,
+            };
             stages.push(LoadStage {
                 duration,
                 target_rpm,
+                sine,
             });
         }
     }
@@ -42,6 +49,42 @@ pub(super) fn parse_simple_load(rate: Option<u64>, rpm: Option<u64>) -> AppResul
     })
 }
 
+fn resolve_stage_sine(stage: &LoadStageConfig, idx: usize) -> AppResult<Option<SineWave>> {
+    let index = idx.saturating_add(1);
+    if stage.kind.unwrap_or_default() != LoadStageKind::Sine {
+        if stage.min_rpm.is_some() || stage.max_rpm.is_some() || stage.period_secs.is_some() {
+            return Err(AppError::config(ConfigError::StageSineFieldsWithoutKind {
+                index,
+            }));
+        }
+        return Ok(None);
+    }
+    if stage.target.is_some() || stage.rate.is_some() || stage.rpm.is_some() {
+        return Err(AppError::config(ConfigError::StageSineConflictingRate {
+            index,
+        }));
+    }
+    let missing = |field| AppError::config(ConfigError::StageSineMissingField { index, field });
+    let min_rpm = stage.min_rpm.ok_or_else(|| missing("min_rpm"))?;
+    let max_rpm = stage.max_rpm.ok_or_else(|| missing("max_rpm"))?;
+    let period_secs = stage.period_secs.ok_or_else(|| missing("period_secs"))?;
+    if min_rpm > max_rpm {
+        return Err(AppError::config(ConfigError::StageSineRangeInvalid {
+            index,
+            min_rpm,
+            max_rpm,
+        }));
+    }
+    if period_secs == 0 {
+        return Err(AppError::config(ConfigError::StageSinePeriodZero { index }));
+    }
+    Ok(Some(SineWave {
+        min_rpm,
+        max_rpm,
+        period: Duration::from_secs(period_secs),
+    }))
+}
+
 fn resolve_stage_rpm(stage: &LoadStageConfig, idx: usize) -> AppResult<u64> {
     let mut configured = 0u8;
     if stage.target.is_some() {
@@ -1,7 +1,12 @@
-use crate::args::{HttpMethod, Scenario, ScenarioStep, parse_header};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::args::{ExtractSource, HttpMethod, Scenario, ScenarioStep, ThinkTime, parse_header};
 use crate::error::{AppError, AppResult, ConfigError};
 
-use super::super::types::{SCENARIO_SCHEMA_VERSION, ScenarioConfig};
+use super::super::types::{
+    SCENARIO_SCHEMA_VERSION, ScenarioConfig, ScenarioStepConfig, ThinkTimeValue,
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct ScenarioDefaults {
@@ -80,10 +85,7 @@ pub(crate) fn parse_scenario(
             }
         }
 
-        let think_time = match step.think_time.as_ref() {
-            Some(value) => Some(value.to_duration()?),
-            None => None,
-        };
+        let think_time = step_think_time(step, idx.saturating_add(1))?;
 
         let url = step.url.clone();
         let path = step.path.clone();
@@ -95,6 +97,26 @@ pub(crate) fn parse_scenario(
             ));
         }
 
+        if step.cert.is_some() != step.key.is_some() {
+            return Err(AppError::config(
+                ConfigError::ScenarioStepIncompleteIdentity {
+                    index: idx.saturating_add(1),
+                },
+            ));
+        }
+
+        let mut extract = BTreeMap::new();
+        for (var, source) in step.extract.iter().flatten() {
+            let parsed = source.parse::<ExtractSource>().map_err(|err| {
+                AppError::config(ConfigError::InvalidScenarioExtract {
+                    index: idx.saturating_add(1),
+                    var: var.clone(),
+                    source: err,
+                })
+            })?;
+            extract.insert(var.clone(), parsed);
+        }
+
         steps.push(ScenarioStep {
             name: step.name.clone(),
             method,
@@ -115,6 +137,9 @@ pub(crate) fn parse_scenario(
             assert_body_contains: step.assert_body_contains.clone(),
             think_time,
             vars: step.vars.clone().unwrap_or_default(),
+            extract,
+            cert: step.cert.clone(),
+            key: step.key.clone(),
         });
     }
 
@@ -124,3 +149,30 @@ pub(crate) fn parse_scenario(
         steps,
     })
 }
+
+/// Resolves `think_time` / `think_time_ms` for the 1-based step `index`.
+fn step_think_time(step: &ScenarioStepConfig, index: usize) -> AppResult<Option<ThinkTime>> {
+    match (step.think_time.as_ref(), step.think_time_ms) {
+        (Some(_), Some(_)) => Err(AppError::config(
+            ConfigError::ScenarioStepThinkTimeConflict { index },
+        )),
+        (None, Some(0)) => Err(AppError::config(ConfigError::DurationZero)),
+        (None, Some(ms)) => Ok(Some(ThinkTime::fixed(Duration::from_millis(ms)))),
+        (Some(ThinkTimeValue::Fixed(value)), None) => {
+            Ok(Some(ThinkTime::fixed(value.to_duration()?)))
+        }
+        (Some(ThinkTimeValue::Range { min, max }), None) => {
+            let think_time = ThinkTime {
+                min: min.to_duration()?,
+                max: max.to_duration()?,
+            };
+            if think_time.min > think_time.max {
+                return Err(AppError::config(
+                    ConfigError::ScenarioStepThinkTimeMinAboveMax { index },
+                ));
+            }
+            Ok(Some(think_time))
+        }
+        (None, None) => Ok(None),
+    }
+}
@@ -1,6 +1,6 @@
 use clap::ArgMatches;
 
-use crate::args::{PositiveU64, TesterArgs};
+use crate::args::{ChartSetting, PositiveU64, TesterArgs};
 use crate::error::{AppError, AppResult, ConfigError};
 
 use super::super::types::ConfigFile;
@@ -77,12 +77,69 @@ pub(super) fn apply_basic_config(
         args.form = form;
     }
 
+    if !is_cli(matches, "form_file")
+        && let Some(form_file) = config.form_file.clone()
+    {
+        args.form_file = form_file;
+    }
+
     if !is_cli(matches, "basic_auth")
         && let Some(auth) = config.basic_auth.clone()
     {
         args.basic_auth = Some(auth);
     }
 
+    if !is_cli(matches, "basic_auth")
+        && !is_cli(matches, "basic_auth_file")
+        && let Some(path) = config.basic_auth_file.clone()
+    {
+        args.basic_auth_file = Some(path);
+    }
+
+    if !is_cli(matches, "bearer_token")
+        && !is_cli(matches, "bearer_token_file")
+        && let Some(token) = config.bearer_token.clone()
+    {
+        args.bearer_token = Some(token);
+    }
+
+    if !is_cli(matches, "bearer_token")
+        && !is_cli(matches, "bearer_token_file")
+        && let Some(path) = config.bearer_token_file.clone()
+    {
+        args.bearer_token_file = Some(path);
+    }
+
+    if !is_cli(matches, "digest_auth")
+        && let Some(auth) = config.digest_auth.clone()
+    {
+        args.digest_auth = Some(auth);
+    }
+
+    if !is_cli(matches, "oauth2_token_url")
+        && let Some(url) = config.oauth2_token_url.clone()
+    {
+        args.oauth2_token_url = Some(url);
+    }
+
+    if !is_cli(matches, "oauth2_client_id")
+        && let Some(client_id) = config.oauth2_client_id.clone()
+    {
+        args.oauth2_client_id = Some(client_id);
+    }
+
+    if !is_cli(matches, "oauth2_client_secret")
+        && let Some(secret) = config.oauth2_client_secret.clone()
+    {
+        args.oauth2_client_secret = Some(secret);
+    }
+
+    if !is_cli(matches, "oauth2_scope")
+        && let Some(scope) = config.oauth2_scope.clone()
+    {
+        args.oauth2_scope = Some(scope);
+    }
+
     if !is_cli(matches, "aws_session")
         && let Some(session) = config.aws_session.clone()
     {
@@ -107,10 +164,40 @@ pub(super) fn apply_basic_config(
         args.data_lines = Some(path);
     }
 
+    if !is_cli(matches, "data_dir")
+        && let Some(path) = config.data_dir.clone()
+    {
+        args.data_dir = Some(path);
+    }
+
+    if !is_cli(matches, "data_dir_max_bytes")
+        && let Some(max_bytes) = config.data_dir_max_bytes
+    {
+        args.data_dir_max_bytes = Some(max_bytes);
+    }
+
+    if !is_cli(matches, "seed")
+        && let Some(seed) = config.seed
+    {
+        args.seed = Some(seed);
+    }
+
+    if !is_cli(matches, "json_template")
+        && let Some(enabled) = config.json_template
+    {
+        args.json_template = enabled;
+    }
+
+    if !is_cli(matches, "literal_placeholders")
+        && let Some(enabled) = config.literal_placeholders
+    {
+        args.literal_placeholders = enabled;
+    }
+
     if !is_cli(matches, "target_duration")
-        && let Some(duration) = config.duration
+        && let Some(duration) = config.duration.as_ref()
     {
-        args.target_duration = ensure_positive_u64(duration, "duration")?;
+        args.target_duration = ensure_positive_u64(duration.to_whole_secs()?, "duration")?;
     }
 
     if !is_cli(matches, "wait_ongoing_requests_after_deadline")
@@ -119,6 +206,18 @@ pub(super) fn apply_basic_config(
         args.wait_ongoing_requests_after_deadline = value;
     }
 
+    if !is_cli(matches, "align_to_second")
+        && let Some(value) = config.align_to_second
+    {
+        args.align_to_second = value;
+    }
+
+    if !is_cli(matches, "arrival_distribution")
+        && let Some(distribution) = config.arrival_distribution
+    {
+        args.arrival_distribution = Some(distribution);
+    }
+
     if !is_cli(matches, "requests")
         && let Some(requests) = config.requests
     {
@@ -143,12 +242,36 @@ pub(super) fn apply_basic_config(
         args.disable_keepalive = disable;
     }
 
+    if !is_cli(matches, "requests_per_connection")
+        && let Some(limit) = config.requests_per_connection
+    {
+        args.requests_per_connection = Some(ensure_positive_u64(limit, "requests_per_connection")?);
+    }
+
     if !is_cli(matches, "disable_compression")
         && let Some(disable) = config.disable_compression
     {
         args.disable_compression = disable;
     }
 
+    if !is_cli(matches, "cookies")
+        && let Some(cookies) = config.cookies
+    {
+        args.cookies = cookies;
+    }
+
+    if !is_cli(matches, "session_cookie")
+        && let Some(name) = config.session_cookie.clone()
+    {
+        args.session_cookie = Some(name);
+    }
+
+    if !is_cli(matches, "backend_header")
+        && let Some(header) = config.backend_header.clone()
+    {
+        args.backend_header = header;
+    }
+
     if !is_cli(matches, "pool_max_idle_per_host")
         && let Some(value) = config.pool_max_idle_per_host
     {
@@ -173,6 +296,24 @@ pub(super) fn apply_basic_config(
         args.warmup = Some(warmup.to_duration()?);
     }
 
+    if !is_cli(matches, "warmup_requests_file")
+        && let Some(path) = config.warmup_requests_file.clone()
+    {
+        args.warmup_requests_file = Some(path);
+    }
+
+    if !is_cli(matches, "echo_config_on_start")
+        && let Some(value) = config.echo_config_on_start
+    {
+        args.echo_config_on_start = value;
+    }
+
+    if !is_cli(matches, "echo_config_format")
+        && let Some(format) = config.echo_config_format
+    {
+        args.echo_config_format = format;
+    }
+
     if !is_cli(matches, "expected_status_code")
         && let Some(status) = config.status
     {
@@ -191,6 +332,12 @@ pub(super) fn apply_basic_config(
         args.no_charts = no_charts;
     }
 
+    if !is_cli(matches, "no_final_charts")
+        && let Some(no_final_charts) = config.no_final_charts
+    {
+        args.no_final_charts = no_final_charts;
+    }
+
     if !is_cli(matches, "charts_latency_bucket_ms")
         && let Some(bucket_ms) = config.charts_latency_bucket_ms
     {
@@ -199,6 +346,36 @@ pub(super) fn apply_basic_config(
         })?;
     }
 
+    if !is_cli(matches, "chart_width")
+        && let Some(value) = config.chart_width
+    {
+        args.chart_width = ChartSetting::Width
+            .check(value)
+            .map_err(|err| AppError::config(ConfigError::InvalidChartSetting { source: err }))?;
+    }
+
+    if !is_cli(matches, "chart_height")
+        && let Some(value) = config.chart_height
+    {
+        args.chart_height = ChartSetting::Height
+            .check(value)
+            .map_err(|err| AppError::config(ConfigError::InvalidChartSetting { source: err }))?;
+    }
+
+    if !is_cli(matches, "chart_dpi")
+        && let Some(value) = config.chart_dpi
+    {
+        args.chart_dpi = ChartSetting::Dpi
+            .check(value)
+            .map_err(|err| AppError::config(ConfigError::InvalidChartSetting { source: err }))?;
+    }
+
+    if !is_cli(matches, "usl_chart")
+        && let Some(usl_chart) = config.usl_chart
+    {
+        args.usl_chart = usl_chart;
+    }
+
     if !is_cli(matches, "no_ua")
         && let Some(no_ua) = config.no_ua
     {
@@ -43,6 +43,12 @@ pub(super) fn apply_runtime_network_config(
         }
     }
 
+    if !is_cli(matches, "rate_schedule")
+        && let Some(path) = config.rate_schedule.clone()
+    {
+        args.rate_schedule = Some(path);
+    }
+
     if !is_cli(matches, "burst_delay")
         && let Some(delay) = config.burst_delay.as_ref()
     {
@@ -55,12 +61,224 @@ pub(super) fn apply_runtime_network_config(
         args.burst_rate = ensure_positive_usize(rate, "burst_rate")?;
     }
 
+    if !is_cli(matches, "find_max_rate")
+        && let Some(enabled) = config.find_max_rate
+    {
+        args.find_max_rate = enabled;
+    }
+
+    if !is_cli(matches, "target_p99_ms")
+        && let Some(target) = config.target_p99_ms
+    {
+        args.target_p99_ms = Some(ensure_positive_u64(target, "target_p99_ms")?);
+    }
+
     if !is_cli(matches, "latency_correction")
         && let Some(value) = config.latency_correction
     {
         args.latency_correction = value;
     }
 
+    if !is_cli(matches, "max_bandwidth")
+        && let Some(limit) = config.max_bandwidth
+    {
+        args.max_bandwidth = Some(limit);
+    }
+
+    if !is_cli(matches, "sim_downlink")
+        && let Some(rate) = config.sim_downlink
+    {
+        args.sim_downlink = Some(rate);
+    }
+
+    if !is_cli(matches, "sim_latency")
+        && let Some(latency) = config.sim_latency.as_ref()
+    {
+        args.sim_latency = Some(latency.to_duration()?);
+    }
+
+    if !is_cli(matches, "connect_rate")
+        && let Some(rate) = config.connect_rate
+    {
+        args.connect_rate = Some(ensure_positive_u64(rate, "connect_rate")?);
+    }
+
+    if !is_cli(matches, "max_bytes")
+        && let Some(cap) = config.max_bytes
+    {
+        args.max_bytes = Some(cap);
+    }
+
+    if !is_cli(matches, "abort_on_errors")
+        && let Some(count) = config.abort_on_errors
+    {
+        args.abort_on_errors = Some(ensure_positive_u64(count, "abort_on_errors")?);
+    }
+
+    if !is_cli(matches, "abort_on_error_rate")
+        && let Some(rate) = config.abort_on_error_rate
+    {
+        args.abort_on_error_rate = Some(rate);
+    }
+
+    if !is_cli(matches, "abort_min_requests")
+        && let Some(count) = config.abort_min_requests
+    {
+        args.abort_min_requests = ensure_positive_u64(count, "abort_min_requests")?;
+    }
+
+    if !is_cli(matches, "verify_request_id_echo")
+        && let Some(value) = config.verify_request_id_echo
+    {
+        args.verify_request_id_echo = value;
+    }
+
+    if !is_cli(matches, "request_id_header")
+        && let Some(header) = config.request_id_header.clone()
+    {
+        args.request_id_header = header;
+    }
+
+    if !is_cli(matches, "deadline_header")
+        && let Some(header) = config.deadline_header.clone()
+    {
+        args.deadline_header = Some(header);
+    }
+
+    if !is_cli(matches, "deadline_format")
+        && let Some(format) = config.deadline_format
+    {
+        args.deadline_format = format;
+    }
+
+    if !is_cli(matches, "correlation_header")
+        && let Some(header) = config.correlation_header.clone()
+    {
+        args.correlation_header = Some(header);
+    }
+
+    if !is_cli(matches, "correlation_log")
+        && let Some(path) = config.correlation_log.clone()
+    {
+        args.correlation_log = Some(path);
+    }
+
+    if !is_cli(matches, "grpc_mode")
+        && let Some(mode) = config.grpc_mode
+    {
+        args.grpc_mode = Some(mode);
+    }
+
+    if !is_cli(matches, "ws_messages_per_conn")
+        && let Some(count) = config.ws_messages_per_conn
+    {
+        args.ws_messages_per_conn = Some(ensure_positive_usize(count, "ws_messages_per_conn")?);
+    }
+
+    if !is_cli(matches, "ws_message_interval_ms")
+        && let Some(interval_ms) = config.ws_message_interval_ms
+    {
+        args.ws_message_interval_ms =
+            Some(ensure_positive_u64(interval_ms, "ws_message_interval_ms")?);
+    }
+
+    if !is_cli(matches, "mqtt_mode")
+        && let Some(mode) = config.mqtt_mode
+    {
+        args.mqtt_mode = mode;
+    }
+
+    if !is_cli(matches, "mqtt_topic")
+        && let Some(topic) = config.mqtt_topic.clone()
+    {
+        args.mqtt_topic = Some(topic);
+    }
+
+    if !is_cli(matches, "sse_events_per_conn")
+        && let Some(count) = config.sse_events_per_conn
+    {
+        args.sse_events_per_conn = Some(ensure_positive_usize(count, "sse_events_per_conn")?);
+    }
+
+    if !is_cli(matches, "sse_conn_duration")
+        && let Some(duration) = config.sse_conn_duration.as_ref()
+    {
+        args.sse_conn_duration = Some(duration.to_duration()?);
+    }
+
+    if !is_cli(matches, "sse_resume")
+        && let Some(resume) = config.sse_resume
+    {
+        args.sse_resume = resume;
+    }
+
+    if !is_cli(matches, "min_events")
+        && let Some(count) = config.min_events
+    {
+        args.min_events = Some(ensure_positive_u64(count, "min_events")?);
+    }
+
+    if !is_cli(matches, "assert_response_bytes_below")
+        && let Some(bytes) = config.assert_response_bytes_below
+    {
+        args.assert_response_bytes_below =
+            Some(ensure_positive_u64(bytes, "assert_response_bytes_below")?);
+    }
+
+    if !is_cli(matches, "assert_response_bytes_above")
+        && let Some(bytes) = config.assert_response_bytes_above
+    {
+        args.assert_response_bytes_above = Some(bytes);
+    }
+
+    if !is_cli(matches, "assert_body_contains")
+        && let Some(text) = config.assert_body_contains.as_ref()
+    {
+        args.assert_body_contains = Some(text.clone());
+    }
+
+    if !is_cli(matches, "assert_body_regex")
+        && let Some(pattern) = config.assert_body_regex.as_ref()
+    {
+        args.assert_body_regex = Some(pattern.clone());
+    }
+
+    if !is_cli(matches, "assert_json")
+        && let Some(assertion) = config.assert_json.as_ref()
+    {
+        args.assert_json = Some(assertion.clone());
+    }
+
+    if !is_cli(matches, "assert_success_rate")
+        && let Some(rate) = config.assert_success_rate
+    {
+        args.assert_success_rate = Some(rate);
+    }
+
+    if !is_cli(matches, "assert_p99_ms")
+        && let Some(ms) = config.assert_p99_ms
+    {
+        args.assert_p99_ms = Some(ms);
+    }
+
+    if !is_cli(matches, "assert_max_errors")
+        && let Some(count) = config.assert_max_errors
+    {
+        args.assert_max_errors = Some(count);
+    }
+
+    if !is_cli(matches, "diagnose")
+        && let Some(value) = config.diagnose
+    {
+        args.diagnose = value;
+    }
+
+    if !is_cli(matches, "diagnose_strict")
+        && let Some(value) = config.diagnose_strict
+    {
+        args.diagnose_strict = value;
+    }
+
     if !is_cli(matches, "connect_to")
         && let Some(entries) = config.connect_to.as_ref()
     {
@@ -85,6 +303,12 @@ pub(super) fn apply_runtime_network_config(
         args.ipv4_only = ipv4;
     }
 
+    if !is_cli(matches, "doh_url")
+        && let Some(url) = config.doh_url.clone()
+    {
+        args.doh_url = Some(url);
+    }
+
     if !is_cli(matches, "no_pre_lookup")
         && let Some(no_pre_lookup) = config.no_pre_lookup
     {
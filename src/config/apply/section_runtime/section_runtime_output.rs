@@ -1,7 +1,8 @@
 use clap::ArgMatches;
 
 use crate::args::TesterArgs;
-use crate::error::AppResult;
+use crate::args::parsers::ensure_slowest;
+use crate::error::{AppError, AppResult};
 
 use super::super::super::types::ConfigFile;
 use super::super::util::{ensure_positive_u64, ensure_positive_usize, is_cli, parse_headers};
@@ -59,12 +60,43 @@ pub(super) fn apply_runtime_output_config(
         args.export_jsonl = Some(path);
     }
 
+    if !is_cli(matches, "export_histogram")
+        && let Some(path) = config.export_histogram.clone()
+    {
+        args.export_histogram = Some(path);
+    }
+
     if !is_cli(matches, "db_url")
         && let Some(db_url) = config.db_url.clone()
     {
         args.db_url = Some(db_url);
     }
 
+    if !is_cli(matches, "checkpoint_out")
+        && let Some(path) = config.checkpoint_out.clone()
+    {
+        args.checkpoint_out = Some(path);
+    }
+
+    if !is_cli(matches, "checkpoint_interval_secs")
+        && let Some(secs) = config.checkpoint_interval_secs
+    {
+        args.checkpoint_interval_secs =
+            Some(ensure_positive_u64(secs, "checkpoint_interval_secs")?);
+    }
+
+    if !is_cli(matches, "stream_samples")
+        && let Some(path) = config.stream_samples.clone()
+    {
+        args.stream_samples = Some(path);
+    }
+
+    if !is_cli(matches, "resume")
+        && let Some(resume) = config.resume
+    {
+        args.resume = resume;
+    }
+
     if !is_cli(matches, "log_shards")
         && let Some(log_shards) = config.log_shards
     {
@@ -77,18 +109,131 @@ pub(super) fn apply_runtime_output_config(
         args.no_ui = no_ui;
     }
 
+    if !is_cli(matches, "hold_ui")
+        && let Some(hold_ui) = config.hold_ui
+    {
+        args.hold_ui = hold_ui;
+    }
+
+    if !is_cli(matches, "stop_file")
+        && let Some(path) = config.stop_file.clone()
+    {
+        args.stop_file = Some(path);
+    }
+
     if !is_cli(matches, "ui_window_ms")
         && let Some(window_ms) = config.ui_window_ms
     {
         args.ui_window_ms = ensure_positive_u64(window_ms, "ui_window_ms")?;
     }
 
+    if !is_cli(matches, "baseline")
+        && let Some(path) = config.baseline.clone()
+    {
+        args.baseline = Some(path);
+    }
+
+    if !is_cli(matches, "baseline_label")
+        && let Some(label) = config.baseline_label.clone()
+    {
+        args.baseline_label = Some(label);
+    }
+
     if !is_cli(matches, "summary")
         && let Some(summary) = config.summary
     {
         args.summary = summary;
     }
 
+    if !is_cli(matches, "summary_template")
+        && let Some(path) = config.summary_template.clone()
+    {
+        args.summary_template = Some(path);
+    }
+
+    if !is_cli(matches, "summary_out")
+        && let Some(path) = config.summary_out.clone()
+    {
+        args.summary_out = Some(path);
+    }
+
+    if !is_cli(matches, "history_file")
+        && let Some(path) = config.history_file.clone()
+    {
+        args.history_file = path;
+    }
+
+    if !is_cli(matches, "no_history")
+        && let Some(disabled) = config.no_history
+    {
+        args.no_history = disabled;
+    }
+
+    if !is_cli(matches, "notify_webhook")
+        && let Some(url) = config.notify_webhook.clone()
+    {
+        args.notify_webhook = Some(url);
+    }
+
+    if !is_cli(matches, "notify_on")
+        && let Some(notify_on) = config.notify_on
+    {
+        args.notify_on = notify_on;
+    }
+
+    if !is_cli(matches, "protocol_version_report")
+        && let Some(enabled) = config.protocol_version_report
+    {
+        args.protocol_version_report = enabled;
+    }
+    if !is_cli(matches, "connection_timing")
+        && let Some(enabled) = config.connection_timing
+    {
+        args.connection_timing = enabled;
+    }
+
+    if !is_cli(matches, "strict_charts")
+        && let Some(strict) = config.strict_charts
+    {
+        args.strict_charts = strict;
+    }
+
+    if !is_cli(matches, "per_status_latency")
+        && let Some(enabled) = config.per_status_latency
+    {
+        args.per_status_latency = enabled;
+    }
+
+    if !is_cli(matches, "slowest")
+        && let Some(count) = config.slowest
+    {
+        args.slowest = Some(ensure_slowest(count).map_err(AppError::from)?);
+    }
+
+    if !is_cli(matches, "summary_percentile_table")
+        && let Some(enabled) = config.summary_percentile_table
+    {
+        args.summary_percentile_table = enabled;
+    }
+
+    if !is_cli(matches, "stats_extended")
+        && let Some(enabled) = config.stats_extended
+    {
+        args.stats_extended = enabled;
+    }
+
+    if !is_cli(matches, "compat")
+        && let Some(format) = config.compat
+    {
+        args.compat = Some(format);
+    }
+
+    if !is_cli(matches, "summary_format")
+        && let Some(format) = config.summary_format
+    {
+        args.summary_format = Some(format);
+    }
+
     if !is_cli(matches, "tls_min")
         && let Some(version) = config.tls_min
     {
@@ -1,7 +1,7 @@
 use clap::ArgMatches;
 
-use crate::args::TesterArgs;
-use crate::error::AppResult;
+use crate::args::{TesterArgs, parse_tag};
+use crate::error::{AppError, AppResult, ConfigError};
 
 use super::super::types::ConfigFile;
 use super::distributed::apply_distributed_config;
@@ -44,6 +44,19 @@ pub(super) fn apply_tail_config(
         args.alloc_profiler_dump_path = value.clone();
     }
 
+    if !is_cli(matches, "alloc_profiler_spike_ms")
+        && let Some(value) = config.alloc_profiler_spike_ms
+    {
+        args.alloc_profiler_spike_ms = Some(ensure_positive_u64(value, "alloc_profiler_spike_ms")?);
+    }
+
+    if !is_cli(matches, "alloc_profiler_spike_inflight")
+        && let Some(value) = config.alloc_profiler_spike_inflight
+    {
+        args.alloc_profiler_spike_inflight =
+            Some(ensure_positive_u64(value, "alloc_profiler_spike_inflight")?);
+    }
+
     if !is_cli(matches, "script")
         && let Some(script) = config.script.clone()
     {
@@ -60,7 +73,27 @@ pub(super) fn apply_tail_config(
         args.scenario = Some(parse_scenario(scenario, scenario_defaults)?);
     }
 
+    if !is_cli(matches, "tags")
+        && let Some(tags) = config.tags.as_ref()
+    {
+        args.tags = tags
+            .iter()
+            .map(|(key, value)| {
+                parse_tag(&format!("{}={}", key, value))
+                    .map_err(|err| AppError::config(ConfigError::InvalidTag { source: err }))
+            })
+            .collect::<AppResult<_>>()?;
+    }
+
     if let Some(sinks) = config.sinks.as_ref() {
+        if let Some(influx) = sinks.influx.as_ref() {
+            if influx.path.is_none() && influx.url.is_none() {
+                return Err(AppError::config(ConfigError::InfluxSinkMissingTarget));
+            }
+            if influx.url.is_some() && influx.bucket.is_none() {
+                return Err(AppError::config(ConfigError::InfluxSinkMissingBucket));
+            }
+        }
         args.sinks = Some(sinks.clone());
     }
 
@@ -0,0 +1,156 @@
+//! `${VAR}` and `${VAR:-default}` expansion in config string values.
+
+use crate::error::{AppError, AppResult, ConfigError};
+
+/// Per-request header placeholders filled in at send time, never from the
+/// environment.
+const REQUEST_PLACEHOLDERS: [&str; 2] = ["uuid", "seq"];
+
+/// Expands every string value in a TOML document, recursing into tables and arrays.
+pub(super) fn expand_toml(
+    table: &mut toml::Table,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> AppResult<()> {
+    for (key, value) in table.iter_mut() {
+        expand_toml_value(value, key, lookup)?;
+    }
+    Ok(())
+}
+
+fn expand_toml_value(
+    value: &mut toml::Value,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> AppResult<()> {
+    match value {
+        toml::Value::String(text) => *text = expand_str(text, path, lookup)?,
+        toml::Value::Table(table) => {
+            for (key, nested) in table.iter_mut() {
+                expand_toml_value(nested, &format!("{path}.{key}"), lookup)?;
+            }
+        }
+        toml::Value::Array(items) => {
+            for (idx, item) in items.iter_mut().enumerate() {
+                expand_toml_value(item, &format!("{path}[{idx}]"), lookup)?;
+            }
+        }
+        toml::Value::Integer(_)
+        | toml::Value::Float(_)
+        | toml::Value::Boolean(_)
+        | toml::Value::Datetime(_) => {}
+    }
+    Ok(())
+}
+
+/// Expands every string value in a JSON document, recursing into objects and arrays.
+pub(super) fn expand_json(
+    map: &mut serde_json::Map<String, serde_json::Value>,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> AppResult<()> {
+    for (key, value) in map.iter_mut() {
+        expand_json_value(value, key, lookup)?;
+    }
+    Ok(())
+}
+
+fn expand_json_value(
+    value: &mut serde_json::Value,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> AppResult<()> {
+    match value {
+        serde_json::Value::String(text) => *text = expand_str(text, path, lookup)?,
+        serde_json::Value::Object(map) => {
+            for (key, nested) in map.iter_mut() {
+                expand_json_value(nested, &format!("{path}.{key}"), lookup)?;
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (idx, item) in items.iter_mut().enumerate() {
+                expand_json_value(item, &format!("{path}[{idx}]"), lookup)?;
+            }
+        }
+        serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+/// Expands `${VAR}` and `${VAR:-default}` in `text`; `$$` is a literal `$`.
+///
+/// The default applies when the variable is unset or empty. A `$` that starts
+/// neither form is kept as is, and so are the `${uuid}`/`${seq}` header
+/// placeholders. `key` names the config value in errors.
+pub(super) fn expand_str(
+    text: &str,
+    key: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> AppResult<String> {
+    if !text.contains('$') {
+        return Ok(text.to_owned());
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('$') {
+        let (literal, tail) = rest.split_at(pos);
+        out.push_str(literal);
+        if let Some(after) = tail.strip_prefix("$$") {
+            out.push('$');
+            rest = after;
+        } else if let Some(body) = tail.strip_prefix("${") {
+            let Some(end) = body.find('}') else {
+                return Err(AppError::config(ConfigError::InvalidInterpolation {
+                    key: key.to_owned(),
+                    reason: "missing closing '}'".to_owned(),
+                }));
+            };
+            let (expr, after) = body.split_at(end);
+            if REQUEST_PLACEHOLDERS.contains(&expr) {
+                out.push_str("${");
+                out.push_str(expr);
+                out.push('}');
+            } else {
+                out.push_str(&resolve(expr, key, lookup)?);
+            }
+            rest = after.strip_prefix('}').unwrap_or(after);
+        } else {
+            out.push('$');
+            rest = tail.strip_prefix('$').unwrap_or(tail);
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn resolve(expr: &str, key: &str, lookup: &impl Fn(&str) -> Option<String>) -> AppResult<String> {
+    let (name, default) = match expr.split_once(":-") {
+        Some((name, default)) => (name, Some(default)),
+        None => (expr, None),
+    };
+    let valid_name = name
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if !valid_name {
+        return Err(AppError::config(ConfigError::InvalidInterpolation {
+            key: key.to_owned(),
+            reason: format!("invalid variable name '{name}'"),
+        }));
+    }
+    match (lookup(name), default) {
+        (Some(value), Some(default)) if value.is_empty() => Ok(default.to_owned()),
+        (Some(value), _) => Ok(value),
+        (None, Some(default)) => Ok(default.to_owned()),
+        (None, None) => Err(AppError::config(ConfigError::MissingEnvVar {
+            var: name.to_owned(),
+            key: key.to_owned(),
+        })),
+    }
+}
+
+/// Reads a variable from the process environment; non-UTF-8 values count as unset.
+pub(super) fn env_lookup(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
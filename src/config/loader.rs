@@ -1,53 +1,82 @@
 use std::path::{Path, PathBuf};
 
+use tracing::debug;
+
 use crate::error::{AppError, AppResult, ConfigError};
 
+use super::interpolate::{env_lookup, expand_json, expand_toml};
+use super::profile::{select_json_profile, select_toml_profile};
 use super::types::ConfigFile;
 
 /// Loads a configuration file from the provided path or default locations.
 ///
+/// When `profile` is set, `[profiles.<profile>]` is merged over the base config.
+/// String values then have `${VAR}` and `${VAR:-default}` expanded from the
+/// environment, with `$$` as a literal `$`.
+///
 /// # Errors
 ///
-/// Returns an error when the config file cannot be read or parsed.
-pub fn load_config(path: Option<&str>) -> AppResult<Option<ConfigFile>> {
+/// Returns an error when the config file cannot be read or parsed, when the
+/// named profile does not exist, or when a referenced environment variable is
+/// unset and has no default.
+pub fn load_config(path: Option<&str>, profile: Option<&str>) -> AppResult<Option<ConfigFile>> {
     if let Some(path) = path {
         let path = PathBuf::from(path);
-        return Ok(Some(load_config_file(&path)?));
+        return Ok(Some(load_config_file(&path, profile)?));
     }
 
     let toml_path = PathBuf::from("strest.toml");
     if toml_path.exists() {
-        return Ok(Some(load_config_file(&toml_path)?));
+        return Ok(Some(load_config_file(&toml_path, profile)?));
     }
 
     let json_path = PathBuf::from("strest.json");
     if json_path.exists() {
-        return Ok(Some(load_config_file(&json_path)?));
+        return Ok(Some(load_config_file(&json_path, profile)?));
     }
 
+    if profile.is_some() {
+        return Err(AppError::config(ConfigError::ProfileWithoutConfig));
+    }
     Ok(None)
 }
 
-pub(crate) fn load_config_file(path: &Path) -> AppResult<ConfigFile> {
+pub(crate) fn load_config_file(path: &Path, profile: Option<&str>) -> AppResult<ConfigFile> {
     let content = std::fs::read_to_string(path).map_err(|err| {
         AppError::config(ConfigError::ReadConfig {
             path: path.to_path_buf(),
             source: err,
         })
     })?;
+    let parse_toml = |err| {
+        AppError::config(ConfigError::ParseToml {
+            path: path.to_path_buf(),
+            source: err,
+        })
+    };
+    let parse_json = |err| {
+        AppError::config(ConfigError::ParseJson {
+            path: path.to_path_buf(),
+            source: err,
+        })
+    };
+    if let Some(name) = profile {
+        debug!("Using config profile '{}' from {}", name, path.display());
+    }
     match path.extension().and_then(|ext| ext.to_str()) {
-        Some("toml") => toml::from_str(&content).map_err(|err| {
-            AppError::config(ConfigError::ParseToml {
-                path: path.to_path_buf(),
-                source: err,
-            })
-        }),
-        Some("json") => serde_json::from_str(&content).map_err(|err| {
-            AppError::config(ConfigError::ParseJson {
-                path: path.to_path_buf(),
-                source: err,
-            })
-        }),
+        Some("toml") => {
+            let root: toml::Table = toml::from_str(&content).map_err(parse_toml)?;
+            let mut table = select_toml_profile(root, profile)?;
+            expand_toml(&mut table, &env_lookup)?;
+            toml::Value::Table(table).try_into().map_err(parse_toml)
+        }
+        Some("json") => {
+            let root: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(&content).map_err(parse_json)?;
+            let mut map = select_json_profile(root, profile)?;
+            expand_json(&mut map, &env_lookup)?;
+            serde_json::from_value(serde_json::Value::Object(map)).map_err(parse_json)
+        }
         Some(ext) => Err(AppError::config(ConfigError::UnsupportedExtension {
             ext: ext.to_owned(),
         })),
@@ -1,7 +1,10 @@
 //! Configuration loading and application.
 pub(crate) mod apply;
+mod interpolate;
 mod loader;
 mod parse;
+mod profile;
+mod rate_schedule;
 pub mod types;
 
 #[cfg(any(test, feature = "fuzzing"))]
@@ -12,6 +15,9 @@ mod tests;
 pub use apply::apply_config;
 pub use loader::load_config;
 pub(crate) use parse::parse_duration_value;
+pub use rate_schedule::load_rate_schedule;
 
 #[cfg(any(test, feature = "fuzzing"))]
 pub(crate) use test_support::load_config_file;
+#[cfg(test)]
+pub(crate) use test_support::load_config_profile;
@@ -2,37 +2,59 @@ use std::time::Duration;
 
 use crate::error::{AppError, AppResult, ConfigError};
 
+/// Parses a config duration such as `500ms`, `30s`, `5m` or `1h30m`; a bare
+/// number is seconds.
 pub(crate) fn parse_duration_value(value: &str) -> AppResult<Duration> {
     let value = value.trim();
     if value.is_empty() {
         return Err(AppError::config(ConfigError::DurationEmpty));
     }
+    let invalid_format = || {
+        AppError::config(ConfigError::InvalidDurationFormat {
+            value: value.to_owned(),
+        })
+    };
 
-    let mut digits_len = 0usize;
-    for ch in value.chars() {
-        if ch.is_ascii_digit() {
-            digits_len = digits_len.saturating_add(1);
-        } else {
-            break;
+    let mut duration = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits_len = rest
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits_len == 0 {
+            return Err(invalid_format());
         }
+        let (num_part, tail) = rest.split_at(digits_len);
+        let unit_len = tail
+            .find(|ch: char| ch.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        if unit.is_empty() && num_part.len() != value.len() {
+            return Err(invalid_format());
+        }
+        let number: u64 = num_part.parse().map_err(|err| {
+            AppError::config(ConfigError::InvalidDurationNumber {
+                value: value.to_owned(),
+                source: err,
+            })
+        })?;
+        duration = duration
+            .checked_add(duration_part(number, unit)?)
+            .ok_or_else(|| AppError::config(ConfigError::DurationOverflow))?;
+        rest = tail;
     }
-    if digits_len == 0 {
-        return Err(AppError::config(ConfigError::InvalidDurationFormat {
-            value: value.to_owned(),
-        }));
+
+    if duration.as_millis() == 0 {
+        return Err(AppError::config(ConfigError::DurationZero));
     }
-    let (num_part, unit_part) = value.split_at(digits_len);
-    let number: u64 = num_part.parse().map_err(|err| {
-        AppError::config(ConfigError::InvalidDurationNumber {
-            value: value.to_owned(),
-            source: err,
-        })
-    })?;
 
-    let unit = if unit_part.is_empty() { "s" } else { unit_part };
+    Ok(duration)
+}
+
+fn duration_part(number: u64, unit: &str) -> AppResult<Duration> {
     let duration = match unit {
         "ms" => Duration::from_millis(number),
-        "s" => Duration::from_secs(number),
+        "" | "s" => Duration::from_secs(number),
         "m" => {
             let secs = number
                 .checked_mul(60)
@@ -52,10 +74,5 @@ pub(crate) fn parse_duration_value(value: &str) -> AppResult<Duration> {
             }));
         }
     };
-
-    if duration.as_millis() == 0 {
-        return Err(AppError::config(ConfigError::DurationZero));
-    }
-
     Ok(duration)
 }
@@ -0,0 +1,100 @@
+//! Named `[profiles.<name>]` sections merged over the base config.
+
+use crate::error::{AppError, AppResult, ConfigError};
+
+/// Top-level key holding named profiles.
+const PROFILES_KEY: &str = "profiles";
+
+/// Removes `profiles` from a TOML document and merges the selected profile over the rest.
+pub(super) fn select_toml_profile(
+    mut root: toml::Table,
+    profile: Option<&str>,
+) -> AppResult<toml::Table> {
+    let profiles = root.remove(PROFILES_KEY);
+    let Some(name) = profile else {
+        return Ok(root);
+    };
+    let mut profiles = match profiles {
+        Some(toml::Value::Table(profiles)) => profiles,
+        _ => toml::Table::new(),
+    };
+    match profiles.remove(name) {
+        Some(toml::Value::Table(overlay)) => {
+            merge_toml(&mut root, overlay);
+            Ok(root)
+        }
+        Some(_) => Err(AppError::config(ConfigError::ProfileNotTable {
+            name: name.to_owned(),
+        })),
+        None => Err(unknown_profile(name, profiles.keys())),
+    }
+}
+
+/// Removes `profiles` from a JSON document and merges the selected profile over the rest.
+pub(super) fn select_json_profile(
+    mut root: serde_json::Map<String, serde_json::Value>,
+    profile: Option<&str>,
+) -> AppResult<serde_json::Map<String, serde_json::Value>> {
+    let profiles = root.remove(PROFILES_KEY);
+    let Some(name) = profile else {
+        return Ok(root);
+    };
+    let mut profiles = match profiles {
+        Some(serde_json::Value::Object(profiles)) => profiles,
+        _ => serde_json::Map::new(),
+    };
+    match profiles.remove(name) {
+        Some(serde_json::Value::Object(overlay)) => {
+            merge_json(&mut root, overlay);
+            Ok(root)
+        }
+        Some(_) => Err(AppError::config(ConfigError::ProfileNotTable {
+            name: name.to_owned(),
+        })),
+        None => Err(unknown_profile(name, profiles.keys())),
+    }
+}
+
+// Tables merge key by key so a profile can override a single field of
+// `[distributed]` or `[load]`; every other value replaces the base value.
+fn merge_toml(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_toml(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn merge_json(
+    base: &mut serde_json::Map<String, serde_json::Value>,
+    overlay: serde_json::Map<String, serde_json::Value>,
+) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(serde_json::Value::Object(base_map)), serde_json::Value::Object(overlay_map)) => {
+                merge_json(base_map, overlay_map);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn unknown_profile<'names>(name: &str, names: impl Iterator<Item = &'names String>) -> AppError {
+    let available: Vec<&str> = names.map(String::as_str).collect();
+    let available = if available.is_empty() {
+        "none".to_owned()
+    } else {
+        available.join(", ")
+    };
+    AppError::config(ConfigError::UnknownProfile {
+        name: name.to_owned(),
+        available,
+    })
+}
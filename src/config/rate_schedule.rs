@@ -0,0 +1,82 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::args::{LoadProfile, LoadStage};
+use crate::error::{AppError, AppResult, ConfigError};
+
+/// Reads a `--rate-schedule` CSV and turns it into a load profile.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read or holds no valid rows.
+pub fn load_rate_schedule(path: &str) -> AppResult<LoadProfile> {
+    let path = Path::new(path);
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        AppError::config(ConfigError::ReadRateSchedule {
+            path: path.to_path_buf(),
+            source: err,
+        })
+    })?;
+    parse_rate_schedule(path, &content)
+}
+
+/// Parses `second,rpm` rows into ramp stages between consecutive points.
+///
+/// The rate holds the first point's rpm until that second and the last
+/// point's rpm after it. Blank lines, `#` comments, and a leading header row
+/// are skipped.
+pub(crate) fn parse_rate_schedule(path: &Path, content: &str) -> AppResult<LoadProfile> {
+    let mut points: Vec<(u64, u64)> = Vec::new();
+    for (idx, raw) in content.lines().enumerate() {
+        let line = idx.saturating_add(1);
+        let value = raw.trim();
+        if value.is_empty() || value.starts_with('#') {
+            continue;
+        }
+        let invalid = || {
+            AppError::config(ConfigError::InvalidRateScheduleRow {
+                line,
+                value: value.to_owned(),
+            })
+        };
+        let (second, rpm) = value.split_once(',').ok_or_else(invalid)?;
+        let (Ok(second), Ok(rpm)) = (second.trim().parse::<u64>(), rpm.trim().parse::<u64>())
+        else {
+            if points.is_empty() && !value.starts_with(|ch: char| ch.is_ascii_digit()) {
+                continue;
+            }
+            return Err(invalid());
+        };
+        if let Some((previous, _)) = points.last()
+            && second <= *previous
+        {
+            return Err(AppError::config(ConfigError::RateScheduleNotAscending {
+                line,
+                second,
+            }));
+        }
+        points.push((second, rpm));
+    }
+
+    let Some(&(_, initial_rpm)) = points.first() else {
+        return Err(AppError::config(ConfigError::RateScheduleEmpty {
+            path: path.to_path_buf(),
+        }));
+    };
+    let mut stages = Vec::with_capacity(points.len());
+    let mut previous_second = 0;
+    for (second, rpm) in points {
+        if second > previous_second {
+            stages.push(LoadStage {
+                duration: Duration::from_secs(second.saturating_sub(previous_second)),
+                target_rpm: rpm,
+                sine: None,
+            });
+        }
+        previous_second = second;
+    }
+    Ok(LoadProfile {
+        initial_rpm,
+        stages,
+    })
+}
@@ -6,5 +6,10 @@ use super::loader;
 use super::types::ConfigFile;
 
 pub(crate) fn load_config_file(path: &Path) -> AppResult<ConfigFile> {
-    loader::load_config_file(path)
+    loader::load_config_file(path, None)
+}
+
+#[cfg(test)]
+pub(crate) fn load_config_profile(path: &Path, profile: &str) -> AppResult<ConfigFile> {
+    loader::load_config_file(path, Some(profile))
 }
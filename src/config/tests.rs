@@ -1,16 +1,19 @@
+use super::interpolate::expand_str;
 use super::{
-    apply_config, load_config_file, parse_duration_value,
+    apply_config, load_config_file, load_config_profile, parse_duration_value,
+    rate_schedule::parse_rate_schedule,
     types::{
-        ConfigFile, DistributedConfig, DurationValue, LoadConfig, LoadStageConfig, ScenarioConfig,
-        ScenarioStepConfig,
+        ConfigFile, DistributedConfig, DurationValue, LoadConfig, LoadStageConfig, LoadStageKind,
+        ScenarioConfig, ScenarioStepConfig, ThinkTimeValue,
     },
 };
 use clap::{CommandFactory, FromArgMatches};
+use std::collections::BTreeMap;
 use std::time::Duration;
 use tempfile::tempdir;
 
-use crate::args::TesterArgs;
-use crate::error::{AppError, AppResult};
+use crate::args::{TesterArgs, ThinkTime};
+use crate::error::{AppError, AppResult, ConfigError};
 
 #[test]
 fn parse_toml_config_with_load_stages() -> AppResult<()> {
@@ -319,6 +322,30 @@ fn apply_config_respects_cli_overrides() -> AppResult<()> {
     Ok(())
 }
 
+#[test]
+fn apply_config_no_final_charts_keeps_charts_enabled() -> AppResult<()> {
+    let config = ConfigFile {
+        no_final_charts: Some(true),
+        ..ConfigFile::default()
+    };
+
+    let cmd = TesterArgs::command();
+    let matches = cmd.get_matches_from(["strest", "--url", "http://localhost"]);
+    let args = TesterArgs::from_arg_matches(&matches)
+        .map_err(|err| AppError::config(format!("parse args failed: {}", err)))?;
+
+    let args = apply_config(args, &matches, config)?.0;
+
+    if !args.no_final_charts || args.no_charts {
+        return Err(AppError::config(format!(
+            "Unexpected chart flags: no_final_charts={} no_charts={}",
+            args.no_final_charts, args.no_charts
+        )));
+    }
+
+    Ok(())
+}
+
 #[test]
 fn apply_config_load_profile_rate_to_rpm() -> AppResult<()> {
     let config = ConfigFile {
@@ -328,8 +355,7 @@ fn apply_config_load_profile_rate_to_rpm() -> AppResult<()> {
             stages: Some(vec![LoadStageConfig {
                 duration: "5s".to_owned(),
                 target: Some(20),
-                rate: None,
-                rpm: None,
+                ..LoadStageConfig::default()
             }]),
         }),
         ..ConfigFile::default()
@@ -366,6 +392,112 @@ fn apply_config_load_profile_rate_to_rpm() -> AppResult<()> {
     Ok(())
 }
 
+#[test]
+fn apply_config_parses_sine_load_stage() -> AppResult<()> {
+    let dir = tempdir().map_err(|err| AppError::config(format!("tempdir failed: {}", err)))?;
+    let path = dir.path().join("strest.toml");
+    let content = r#"
+[load]
+rpm = 300
+
+[[load.stages]]
+duration = "10m"
+kind = "sine"
+min_rpm = 600
+max_rpm = 3000
+period_secs = 120
+
+[[load.stages]]
+duration = "30s"
+rpm = 0
+"#;
+    std::fs::write(&path, content)
+        .map_err(|err| AppError::config(format!("write failed: {}", err)))?;
+    let config = load_config_file(&path)?;
+
+    let cmd = TesterArgs::command();
+    let matches = cmd.get_matches_from(["strest"]);
+    let args = TesterArgs::from_arg_matches(&matches)
+        .map_err(|err| AppError::config(format!("parse args failed: {}", err)))?;
+    let args = apply_config(args, &matches, config)?.0;
+
+    let load = args
+        .load_profile
+        .ok_or_else(|| AppError::config("Expected load_profile"))?;
+    let stages: Vec<(Duration, u64, Option<crate::args::SineWave>)> = load
+        .stages
+        .iter()
+        .map(|stage| (stage.duration, stage.target_rpm, stage.sine))
+        .collect();
+    let expected = vec![
+        (
+            Duration::from_secs(600),
+            600,
+            Some(crate::args::SineWave {
+                min_rpm: 600,
+                max_rpm: 3000,
+                period: Duration::from_secs(120),
+            }),
+        ),
+        (Duration::from_secs(30), 0, None),
+    ];
+    if stages != expected {
+        return Err(AppError::config(format!(
+            "Unexpected sine stages: {:?}",
+            stages
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn apply_config_rejects_invalid_sine_stages() -> AppResult<()> {
+    let sine = |min_rpm, max_rpm, period_secs| LoadStageConfig {
+        duration: "1m".to_owned(),
+        kind: Some(LoadStageKind::Sine),
+        min_rpm,
+        max_rpm,
+        period_secs,
+        ..LoadStageConfig::default()
+    };
+    let cases = vec![
+        sine(Some(10), None, Some(60)),
+        sine(Some(100), Some(10), Some(60)),
+        sine(Some(10), Some(100), Some(0)),
+        LoadStageConfig {
+            rpm: Some(5),
+            ..sine(Some(10), Some(100), Some(60))
+        },
+        LoadStageConfig {
+            duration: "1m".to_owned(),
+            min_rpm: Some(10),
+            ..LoadStageConfig::default()
+        },
+    ];
+    for stage in cases {
+        let label = format!("{:?}", stage);
+        let config = ConfigFile {
+            load: Some(LoadConfig {
+                rate: None,
+                rpm: None,
+                stages: Some(vec![stage]),
+            }),
+            ..ConfigFile::default()
+        };
+        let cmd = TesterArgs::command();
+        let matches = cmd.get_matches_from(["strest"]);
+        let args = TesterArgs::from_arg_matches(&matches)
+            .map_err(|err| AppError::config(format!("parse args failed: {}", err)))?;
+        if apply_config(args, &matches, config).is_ok() {
+            return Err(AppError::config(format!(
+                "Expected sine stage to be rejected: {}",
+                label
+            )));
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn apply_config_rejects_load_and_rate_conflict() -> AppResult<()> {
     let config = ConfigFile {
@@ -411,6 +543,52 @@ fn parse_duration_value_accepts_units() -> AppResult<()> {
     if hours != Duration::from_secs(3600) {
         return Err(AppError::config("Unexpected hours duration"));
     }
+    let compound = parse_duration_value("1h30m15s")?;
+    if compound != Duration::from_secs(5_415) {
+        return Err(AppError::config("Unexpected compound duration"));
+    }
+    if !matches!(
+        parse_duration_value("1h30"),
+        Err(AppError::Config(ConfigError::InvalidDurationFormat { .. }))
+    ) {
+        return Err(AppError::config("Expected a trailing bare number to fail"));
+    }
+    if !matches!(
+        parse_duration_value("2d"),
+        Err(AppError::Config(ConfigError::InvalidDurationUnit { .. }))
+    ) {
+        return Err(AppError::config("Expected an unknown unit to fail"));
+    }
+    if !matches!(
+        parse_duration_value("0m0s"),
+        Err(AppError::Config(ConfigError::DurationZero))
+    ) {
+        return Err(AppError::config("Expected a zero duration to fail"));
+    }
+    Ok(())
+}
+
+#[test]
+fn config_duration_accepts_seconds_and_human_units() -> AppResult<()> {
+    let parse = |toml: &str| -> AppResult<u64> {
+        let config: ConfigFile = toml::from_str(toml)
+            .map_err(|err| AppError::config(format!("parse failed: {}", err)))?;
+        let duration = config
+            .duration
+            .ok_or_else(|| AppError::config("Expected a duration"))?;
+        duration.to_whole_secs()
+    };
+    if parse("duration = 90")? != 90 || parse("duration = \"1h30m\"")? != 5_400 {
+        return Err(AppError::config("Unexpected config duration"));
+    }
+    if !matches!(
+        parse("duration = \"1500ms\""),
+        Err(AppError::Config(
+            ConfigError::DurationNotWholeSeconds { .. }
+        ))
+    ) {
+        return Err(AppError::config("Expected a sub-second run length to fail"));
+    }
     Ok(())
 }
 
@@ -443,6 +621,32 @@ fn apply_config_sets_warmup_and_tls() -> AppResult<()> {
     Ok(())
 }
 
+#[test]
+fn parse_toml_config_with_max_bandwidth() -> AppResult<()> {
+    let dir = tempdir().map_err(|err| AppError::config(format!("tempdir failed: {}", err)))?;
+    let path = dir.path().join("strest.toml");
+    std::fs::write(&path, "max_bandwidth = \"2MiB/s\"\n")
+        .map_err(|err| AppError::config(format!("write failed: {}", err)))?;
+
+    let config = load_config_file(&path)?;
+    let cmd = TesterArgs::command();
+    let matches = cmd.get_matches_from(["strest"]);
+    let args = TesterArgs::from_arg_matches(&matches)
+        .map_err(|err| AppError::config(format!("parse args failed: {}", err)))?;
+    let args = apply_config(args, &matches, config)?.0;
+
+    let bytes = args
+        .max_bandwidth
+        .map(crate::args::BandwidthLimit::bytes_per_sec);
+    if bytes != Some(2_097_152) {
+        return Err(AppError::config(format!(
+            "Expected max_bandwidth 2MiB/s, got {:?}",
+            bytes
+        )));
+    }
+    Ok(())
+}
+
 #[test]
 fn apply_config_parses_scenario() -> AppResult<()> {
     let config = ConfigFile {
@@ -463,8 +667,15 @@ fn apply_config_parses_scenario() -> AppResult<()> {
                 data: None,
                 assert_status: Some(201),
                 assert_body_contains: Some("ok".to_owned()),
-                think_time: Some(DurationValue::Text("1s".to_owned())),
+                think_time: Some(ThinkTimeValue::Fixed(DurationValue::Text("1s".to_owned()))),
+                think_time_ms: None,
                 vars: None,
+                extract: Some(BTreeMap::from([
+                    ("token".to_owned(), "$.data[0].token".to_owned()),
+                    ("session".to_owned(), "X-Session".to_owned()),
+                ])),
+                cert: Some("client.pem".to_owned()),
+                key: Some("client.key".to_owned()),
             }],
         }),
         ..ConfigFile::default()
@@ -497,13 +708,135 @@ fn apply_config_parses_scenario() -> AppResult<()> {
     if step.assert_status != Some(201) {
         return Err(AppError::config("Unexpected step assert_status"));
     }
-    if step.think_time != Some(Duration::from_secs(1)) {
+    if step.think_time != Some(ThinkTime::fixed(Duration::from_secs(1))) {
         return Err(AppError::config("Unexpected step think_time"));
     }
+    let token = step.extract.get("token").map(ToString::to_string);
+    let session = step.extract.get("session").map(ToString::to_string);
+    if token.as_deref() != Some("$.data[0].token") || session.as_deref() != Some("x-session") {
+        return Err(AppError::config("Unexpected step extract"));
+    }
+    if step.cert.as_deref() != Some("client.pem") || step.key.as_deref() != Some("client.key") {
+        return Err(AppError::config("Unexpected step identity"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn apply_config_rejects_scenario_step_cert_without_key() -> AppResult<()> {
+    let config = ConfigFile {
+        scenario: Some(ScenarioConfig {
+            schema_version: None,
+            base_url: Some("http://example.com".to_owned()),
+            method: None,
+            headers: None,
+            data: None,
+            vars: None,
+            steps: vec![ScenarioStepConfig {
+                path: Some("/test".to_owned()),
+                cert: Some("client.pem".to_owned()),
+                ..ScenarioStepConfig::default()
+            }],
+        }),
+        ..ConfigFile::default()
+    };
+
+    let cmd = TesterArgs::command();
+    let matches = cmd.get_matches_from(["strest"]);
+    let args = TesterArgs::from_arg_matches(&matches)
+        .map_err(|err| AppError::config(format!("parse args failed: {}", err)))?;
+
+    match apply_config(args, &matches, config) {
+        Err(err) if err.to_string().contains("must set both cert and key") => Ok(()),
+        Err(err) => Err(AppError::config(format!("Unexpected error: {}", err))),
+        Ok(_) => Err(AppError::config("Expected incomplete identity error")),
+    }
+}
+
+#[test]
+fn scenario_think_time_accepts_ms_and_ranges() -> AppResult<()> {
+    let dir = tempdir().map_err(|err| AppError::config(format!("tempdir failed: {}", err)))?;
+    let path = dir.path().join("strest.toml");
+    std::fs::write(
+        &path,
+        r#"
+[scenario]
+base_url = "http://example.com"
+
+[[scenario.steps]]
+path = "/browse"
+think_time = { min = "200ms", max = "2s" }
 
+[[scenario.steps]]
+path = "/cart"
+think_time_ms = 750
+"#,
+    )
+    .map_err(|err| AppError::config(format!("write failed: {}", err)))?;
+    let config = load_config_file(&path)?;
+
+    let cmd = TesterArgs::command();
+    let matches = cmd.get_matches_from(["strest"]);
+    let args = TesterArgs::from_arg_matches(&matches)
+        .map_err(|err| AppError::config(format!("parse args failed: {}", err)))?;
+    let scenario = apply_config(args, &matches, config)?
+        .0
+        .scenario
+        .ok_or_else(|| AppError::config("Expected scenario"))?;
+    let think_times: Vec<_> = scenario.steps.iter().map(|step| step.think_time).collect();
+    let expected = [
+        Some(ThinkTime {
+            min: Duration::from_millis(200),
+            max: Duration::from_secs(2),
+        }),
+        Some(ThinkTime::fixed(Duration::from_millis(750))),
+    ];
+    if think_times != expected {
+        return Err(AppError::config(format!(
+            "Unexpected think times: {:?}",
+            think_times
+        )));
+    }
     Ok(())
 }
 
+#[test]
+fn scenario_think_time_rejects_min_above_max() -> AppResult<()> {
+    let config = ConfigFile {
+        scenario: Some(ScenarioConfig {
+            schema_version: None,
+            base_url: Some("http://example.com".to_owned()),
+            method: None,
+            headers: None,
+            data: None,
+            vars: None,
+            steps: vec![ScenarioStepConfig {
+                path: Some("/test".to_owned()),
+                think_time: Some(ThinkTimeValue::Range {
+                    min: DurationValue::Text("2s".to_owned()),
+                    max: DurationValue::Text("500ms".to_owned()),
+                }),
+                ..ScenarioStepConfig::default()
+            }],
+        }),
+        ..ConfigFile::default()
+    };
+
+    let cmd = TesterArgs::command();
+    let matches = cmd.get_matches_from(["strest"]);
+    let args = TesterArgs::from_arg_matches(&matches)
+        .map_err(|err| AppError::config(format!("parse args failed: {}", err)))?;
+
+    match apply_config(args, &matches, config) {
+        Err(AppError::Config(ConfigError::ScenarioStepThinkTimeMinAboveMax { index: 1 })) => Ok(()),
+        Err(err) => Err(AppError::config(format!("Unexpected error: {}", err))),
+        Ok(_) => Err(AppError::config(
+            "Expected think_time min > max to be rejected",
+        )),
+    }
+}
+
 #[test]
 fn apply_config_sets_distributed_fields() -> AppResult<()> {
     let config = ConfigFile {
@@ -523,6 +856,7 @@ fn apply_config_sets_distributed_fields() -> AppResult<()> {
             agent_reconnect_ms: Some(1500),
             agent_heartbeat_interval_ms: Some(900),
             agent_heartbeat_timeout_ms: Some(3200),
+            agent_breakdown: Some(true),
             ..DistributedConfig::default()
         }),
         ..ConfigFile::default()
@@ -583,6 +917,219 @@ fn apply_config_sets_distributed_fields() -> AppResult<()> {
     if args.agent_heartbeat_timeout_ms.get() != 3200 {
         return Err(AppError::config("Unexpected agent_heartbeat_timeout_ms"));
     }
+    if !args.agent_breakdown {
+        return Err(AppError::config("Unexpected agent_breakdown"));
+    }
+
+    Ok(())
+}
+
+const PROFILED_TOML: &str = r#"
+url = "http://localhost:3000"
+duration = 30
+
+[distributed]
+role = "controller"
+min_agents = 2
+
+[profiles.staging]
+url = "https://staging.example.com"
+
+[profiles.prod]
+url = "https://prod.example.com"
+duration = 120
+
+[profiles.prod.distributed]
+min_agents = 8
+"#;
+
+#[test]
+fn load_config_profile_merges_over_base() -> AppResult<()> {
+    let dir = tempdir().map_err(|err| AppError::config(format!("tempdir failed: {}", err)))?;
+    let path = dir.path().join("strest.toml");
+    std::fs::write(&path, PROFILED_TOML)
+        .map_err(|err| AppError::config(format!("write failed: {}", err)))?;
+
+    let config = load_config_profile(&path, "prod")?;
+    if config.url.as_deref() != Some("https://prod.example.com") {
+        return Err(AppError::config("Expected profile url to override base"));
+    }
+    if config.duration != Some(DurationValue::Seconds(120)) {
+        return Err(AppError::config(
+            "Expected profile duration to override base",
+        ));
+    }
+    let distributed = config
+        .distributed
+        .ok_or_else(|| AppError::config("Expected distributed section"))?;
+    if distributed.role.as_deref() != Some("controller") || distributed.min_agents != Some(8) {
+        return Err(AppError::config(
+            "Expected profile to merge into the distributed table",
+        ));
+    }
+
+    let base = load_config_file(&path)?;
+    if base.url.as_deref() != Some("http://localhost:3000") {
+        return Err(AppError::config("Expected base url without a profile"));
+    }
+    Ok(())
+}
+
+#[test]
+fn load_config_profile_rejects_unknown_name() -> AppResult<()> {
+    let dir = tempdir().map_err(|err| AppError::config(format!("tempdir failed: {}", err)))?;
+    let path = dir.path().join("strest.json");
+    std::fs::write(
+        &path,
+        r#"{"url": "http://localhost:3000", "profiles": {"staging": {"url": "http://staging"}}}"#,
+    )
+    .map_err(|err| AppError::config(format!("write failed: {}", err)))?;
+
+    if load_config_profile(&path, "staging")?.url.as_deref() != Some("http://staging") {
+        return Err(AppError::config(
+            "Expected JSON profile url to override base",
+        ));
+    }
+    match load_config_profile(&path, "qa") {
+        Err(AppError::Config(ConfigError::UnknownProfile { name, available }))
+            if name == "qa" && available == "staging" =>
+        {
+            Ok(())
+        }
+        other => Err(AppError::config(format!(
+            "Expected unknown profile error, got {:?}",
+            other.map(|config| config.url)
+        ))),
+    }
+}
 
+#[test]
+fn expand_str_substitutes_env_vars_defaults_and_escapes() -> AppResult<()> {
+    let lookup = |name: &str| match name {
+        "API_TOKEN" => Some("s3cret".to_owned()),
+        "EMPTY" => Some(String::new()),
+        _ => None,
+    };
+    let cases = [
+        ("Bearer ${API_TOKEN}", "Bearer s3cret"),
+        ("${MISSING:-fallback}", "fallback"),
+        ("${EMPTY:-fallback}", "fallback"),
+        ("${EMPTY}", ""),
+        ("cost: $$5 and $${API_TOKEN}", "cost: $5 and ${API_TOKEN}"),
+        ("plain $ sign", "plain $ sign"),
+        ("load-${seq} ${uuid}", "load-${seq} ${uuid}"),
+        ("${seq:-1}", "1"),
+    ];
+    for (input, expected) in cases {
+        let expanded = expand_str(input, "headers[0]", &lookup)?;
+        if expanded != expected {
+            return Err(AppError::config(format!(
+                "Expected '{}' for '{}', got '{}'",
+                expected, input, expanded
+            )));
+        }
+    }
+    match expand_str("${API_TOKEN", "auth", &lookup) {
+        Err(AppError::Config(ConfigError::InvalidInterpolation { key, .. })) if key == "auth" => {}
+        other => {
+            return Err(AppError::config(format!(
+                "Expected unterminated interpolation error, got {:?}",
+                other
+            )));
+        }
+    }
     Ok(())
 }
+
+#[test]
+fn load_config_expands_env_vars_and_names_missing_ones() -> AppResult<()> {
+    let dir = tempdir().map_err(|err| AppError::config(format!("tempdir failed: {}", err)))?;
+    let toml_path = dir.path().join("strest.toml");
+    std::fs::write(
+        &toml_path,
+        "url = \"${STREST_TEST_UNSET_URL:-http://localhost:3000}\"\nheaders = [\"X-Price: $$5\"]\n",
+    )
+    .map_err(|err| AppError::config(format!("write failed: {}", err)))?;
+    let config = load_config_file(&toml_path)?;
+    if config.url.as_deref() != Some("http://localhost:3000")
+        || config.headers.as_deref() != Some(&["X-Price: $5".to_owned()][..])
+    {
+        return Err(AppError::config(format!(
+            "Unexpected expansion: {:?} {:?}",
+            config.url, config.headers
+        )));
+    }
+
+    let json_path = dir.path().join("strest.json");
+    std::fs::write(
+        &json_path,
+        r#"{"headers": ["Authorization: Bearer ${STREST_TEST_UNSET_TOKEN}"]}"#,
+    )
+    .map_err(|err| AppError::config(format!("write failed: {}", err)))?;
+    match load_config_file(&json_path) {
+        Err(AppError::Config(ConfigError::MissingEnvVar { var, key }))
+            if var == "STREST_TEST_UNSET_TOKEN" && key == "headers[0]" =>
+        {
+            Ok(())
+        }
+        other => Err(AppError::config(format!(
+            "Expected missing env var error, got {:?}",
+            other.map(|loaded| loaded.headers)
+        ))),
+    }
+}
+
+#[test]
+fn rate_schedule_ramps_between_points_and_holds_the_ends() -> AppResult<()> {
+    let path = std::path::Path::new("schedule.csv");
+    let stages = |content: &str| -> AppResult<(u64, Vec<(u64, u64)>)> {
+        let profile = parse_rate_schedule(path, content)?;
+        let stages = profile
+            .stages
+            .iter()
+            .map(|stage| (stage.duration.as_secs(), stage.target_rpm))
+            .collect();
+        Ok((profile.initial_rpm, stages))
+    };
+
+    let from_zero = stages("second,rpm\n# warmup\n0,600\n30,1200\n\n90,300\n")?;
+    if from_zero != (600, vec![(30, 1200), (60, 300)]) {
+        return Err(AppError::config(format!(
+            "Unexpected schedule from zero: {:?}",
+            from_zero
+        )));
+    }
+    let delayed = stages("10,120\n20,240\n")?;
+    if delayed != (120, vec![(10, 120), (10, 240)]) {
+        return Err(AppError::config(format!(
+            "Unexpected delayed schedule: {:?}",
+            delayed
+        )));
+    }
+
+    match parse_rate_schedule(path, "0,100\n0,200\n") {
+        Err(AppError::Config(ConfigError::RateScheduleNotAscending { line: 2, second: 0 })) => {}
+        other => {
+            return Err(AppError::config(format!(
+                "Expected RateScheduleNotAscending, got {:?}",
+                other.map(|profile| profile.initial_rpm)
+            )));
+        }
+    }
+    match parse_rate_schedule(path, "0,100\n5,fast\n") {
+        Err(AppError::Config(ConfigError::InvalidRateScheduleRow { line: 2, .. })) => {}
+        other => {
+            return Err(AppError::config(format!(
+                "Expected InvalidRateScheduleRow, got {:?}",
+                other.map(|profile| profile.initial_rpm)
+            )));
+        }
+    }
+    match parse_rate_schedule(path, "second,rpm\n") {
+        Err(AppError::Config(ConfigError::RateScheduleEmpty { .. })) => Ok(()),
+        other => Err(AppError::config(format!(
+            "Expected RateScheduleEmpty, got {:?}",
+            other.map(|profile| profile.initial_rpm)
+        ))),
+    }
+}
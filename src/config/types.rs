@@ -3,7 +3,7 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
-use crate::args::{HttpMethod, OutputFormat};
+use crate::args::{ExpectedStatus, HttpMethod, OutputFormat};
 use crate::error::{AppResult, ConfigError};
 use crate::sinks::config::SinksConfig;
 
@@ -20,26 +20,54 @@ pub struct ConfigFile {
     pub content_type: Option<String>,
     pub data: Option<String>,
     pub form: Option<Vec<String>>,
+    pub form_file: Option<Vec<String>>,
     pub data_file: Option<String>,
     pub data_lines: Option<String>,
+    pub data_dir: Option<String>,
+    pub data_dir_max_bytes: Option<crate::args::ByteSize>,
+    pub seed: Option<u64>,
+    pub json_template: Option<bool>,
+    pub literal_placeholders: Option<bool>,
     pub basic_auth: Option<String>,
+    pub basic_auth_file: Option<String>,
+    pub bearer_token: Option<String>,
+    pub bearer_token_file: Option<String>,
+    pub digest_auth: Option<String>,
+    pub oauth2_token_url: Option<String>,
+    pub oauth2_client_id: Option<String>,
+    pub oauth2_client_secret: Option<String>,
+    pub oauth2_scope: Option<String>,
     pub aws_session: Option<String>,
     pub aws_sigv4: Option<String>,
-    pub duration: Option<u64>,
+    pub duration: Option<DurationValue>,
     pub wait_ongoing_requests_after_deadline: Option<bool>,
+    pub align_to_second: Option<bool>,
+    pub arrival_distribution: Option<crate::args::ArrivalDistribution>,
     pub requests: Option<u64>,
     pub timeout: Option<DurationValue>,
     pub connect_timeout: Option<DurationValue>,
     pub warmup: Option<DurationValue>,
-    pub status: Option<u16>,
+    pub warmup_requests_file: Option<String>,
+    pub echo_config_on_start: Option<bool>,
+    pub echo_config_format: Option<crate::args::EchoConfigFormat>,
+    pub status: Option<ExpectedStatus>,
     pub redirect: Option<u32>,
     pub disable_keepalive: Option<bool>,
+    pub requests_per_connection: Option<u64>,
     pub disable_compression: Option<bool>,
+    pub cookies: Option<bool>,
+    pub session_cookie: Option<String>,
+    pub backend_header: Option<String>,
     pub pool_max_idle_per_host: Option<usize>,
     pub pool_idle_timeout_ms: Option<u64>,
     pub charts_path: Option<String>,
     pub no_charts: Option<bool>,
+    pub no_final_charts: Option<bool>,
     pub charts_latency_bucket_ms: Option<u64>,
+    pub chart_width: Option<u32>,
+    pub chart_height: Option<u32>,
+    pub chart_dpi: Option<u32>,
+    pub usl_chart: Option<bool>,
     pub no_ua: Option<bool>,
     pub authorized: Option<bool>,
     pub tmp_path: Option<String>,
@@ -50,11 +78,35 @@ pub struct ConfigFile {
     pub export_csv: Option<String>,
     pub export_json: Option<String>,
     pub export_jsonl: Option<String>,
+    pub export_histogram: Option<String>,
     pub db_url: Option<String>,
+    pub checkpoint_out: Option<String>,
+    pub checkpoint_interval_secs: Option<u64>,
+    pub stream_samples: Option<String>,
+    pub resume: Option<bool>,
     pub log_shards: Option<usize>,
     pub no_ui: Option<bool>,
+    pub hold_ui: Option<bool>,
+    pub stop_file: Option<String>,
     pub ui_window_ms: Option<u64>,
+    pub baseline: Option<String>,
+    pub baseline_label: Option<String>,
     pub summary: Option<bool>,
+    pub summary_template: Option<String>,
+    pub summary_out: Option<String>,
+    pub history_file: Option<String>,
+    pub no_history: Option<bool>,
+    pub notify_webhook: Option<String>,
+    pub notify_on: Option<crate::args::NotifyOn>,
+    pub protocol_version_report: Option<bool>,
+    pub connection_timing: Option<bool>,
+    pub strict_charts: Option<bool>,
+    pub per_status_latency: Option<bool>,
+    pub slowest: Option<usize>,
+    pub summary_percentile_table: Option<bool>,
+    pub stats_extended: Option<bool>,
+    pub compat: Option<crate::args::CompatFormat>,
+    pub summary_format: Option<crate::args::SummaryFormat>,
     pub tls_min: Option<crate::args::TlsVersion>,
     pub tls_max: Option<crate::args::TlsVersion>,
     pub cacert: Option<String>,
@@ -79,28 +131,68 @@ pub struct ConfigFile {
     pub rpm: Option<u64>,
     pub burst_delay: Option<DurationValue>,
     pub burst_rate: Option<usize>,
+    pub find_max_rate: Option<bool>,
+    pub target_p99_ms: Option<u64>,
     pub latency_correction: Option<bool>,
+    pub max_bandwidth: Option<crate::args::BandwidthLimit>,
+    pub sim_downlink: Option<crate::args::LinkRate>,
+    pub sim_latency: Option<DurationValue>,
+    pub connect_rate: Option<u64>,
+    pub max_bytes: Option<crate::args::ByteSize>,
+    pub abort_on_errors: Option<u64>,
+    pub abort_on_error_rate: Option<crate::args::Percentage>,
+    pub abort_min_requests: Option<u64>,
+    pub verify_request_id_echo: Option<bool>,
+    pub request_id_header: Option<String>,
+    pub deadline_header: Option<String>,
+    pub deadline_format: Option<crate::args::DeadlineFormat>,
+    pub correlation_header: Option<String>,
+    pub correlation_log: Option<String>,
+    pub grpc_mode: Option<crate::args::GrpcMode>,
+    pub ws_messages_per_conn: Option<usize>,
+    pub ws_message_interval_ms: Option<u64>,
+    pub mqtt_mode: Option<crate::args::MqttMode>,
+    pub mqtt_topic: Option<String>,
+    pub sse_events_per_conn: Option<usize>,
+    pub sse_conn_duration: Option<DurationValue>,
+    pub sse_resume: Option<bool>,
+    pub min_events: Option<u64>,
+    pub assert_response_bytes_below: Option<u64>,
+    pub assert_response_bytes_above: Option<u64>,
+    pub assert_body_contains: Option<String>,
+    pub assert_body_regex: Option<String>,
+    pub assert_json: Option<crate::args::JsonAssertion>,
+    pub assert_success_rate: Option<crate::args::Percentage>,
+    pub assert_p99_ms: Option<u64>,
+    pub assert_max_errors: Option<u64>,
+    pub diagnose: Option<bool>,
+    pub diagnose_strict: Option<bool>,
     pub connect_to: Option<Vec<String>>,
     pub host: Option<String>,
     pub ipv6: Option<bool>,
     pub ipv4: Option<bool>,
+    pub doh_url: Option<String>,
     pub no_pre_lookup: Option<bool>,
     pub no_color: Option<bool>,
     pub fps: Option<u32>,
     pub stats_success_breakdown: Option<bool>,
     pub unix_socket: Option<String>,
     pub load: Option<LoadConfig>,
+    pub rate_schedule: Option<String>,
     pub metrics_range: Option<String>,
     pub metrics_max: Option<usize>,
     pub rss_log_ms: Option<u64>,
     pub alloc_profiler_ms: Option<u64>,
     pub alloc_profiler_dump_ms: Option<u64>,
     pub alloc_profiler_dump_path: Option<String>,
+    pub alloc_profiler_spike_ms: Option<u64>,
+    pub alloc_profiler_spike_inflight: Option<u64>,
     pub scenario: Option<ScenarioConfig>,
     pub scenarios: Option<BTreeMap<String, ScenarioConfig>>,
     pub script: Option<String>,
     pub plugin: Option<Vec<String>>,
     pub sinks: Option<SinksConfig>,
+    pub tags: Option<BTreeMap<String, String>>,
     pub distributed: Option<DistributedConfig>,
 }
 
@@ -114,9 +206,21 @@ pub struct LoadConfig {
 #[derive(Debug, Default, Deserialize)]
 pub struct LoadStageConfig {
     pub duration: String,
+    pub kind: Option<LoadStageKind>,
     pub target: Option<u64>,
     pub rate: Option<u64>,
     pub rpm: Option<u64>,
+    pub min_rpm: Option<u64>,
+    pub max_rpm: Option<u64>,
+    pub period_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadStageKind {
+    #[default]
+    Ramp,
+    Sine,
 }
 
 #[derive(Debug, Default, Deserialize, Clone)]
@@ -142,8 +246,12 @@ pub struct ScenarioStepConfig {
     pub data: Option<String>,
     pub assert_status: Option<u16>,
     pub assert_body_contains: Option<String>,
-    pub think_time: Option<DurationValue>,
+    pub think_time: Option<ThinkTimeValue>,
+    pub think_time_ms: Option<u64>,
     pub vars: Option<BTreeMap<String, String>>,
+    pub extract: Option<BTreeMap<String, String>>,
+    pub cert: Option<String>,
+    pub key: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -151,6 +259,7 @@ pub struct DistributedConfig {
     pub role: Option<String>,
     pub controller_mode: Option<crate::args::ControllerMode>,
     pub listen: Option<String>,
+    pub controller_also_runs: Option<bool>,
     pub control_listen: Option<String>,
     pub control_auth_token: Option<String>,
     pub join: Option<String>,
@@ -163,17 +272,32 @@ pub struct DistributedConfig {
     pub agent_reconnect_ms: Option<u64>,
     pub agent_heartbeat_interval_ms: Option<u64>,
     pub agent_heartbeat_timeout_ms: Option<u64>,
+    pub agent_health_listen: Option<String>,
     pub stream_summaries: Option<bool>,
     pub stream_interval_ms: Option<u64>,
+    pub agent_breakdown: Option<bool>,
+    pub per_agent_summary: Option<bool>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 #[serde(untagged)]
 pub enum DurationValue {
     Seconds(u64),
     Text(String),
 }
 
+/// A step's `think_time`: one duration, or `{ min, max }` for a uniform
+/// random pause.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ThinkTimeValue {
+    Fixed(DurationValue),
+    Range {
+        min: DurationValue,
+        max: DurationValue,
+    },
+}
+
 impl DurationValue {
     pub(crate) fn to_duration(&self) -> AppResult<Duration> {
         match self {
@@ -187,4 +311,19 @@ impl DurationValue {
             DurationValue::Text(text) => super::parse_duration_value(text),
         }
     }
+
+    /// Like [`Self::to_duration`], for settings kept in whole seconds.
+    pub(crate) fn to_whole_secs(&self) -> AppResult<u64> {
+        let duration = self.to_duration()?;
+        if let DurationValue::Text(text) = self
+            && duration.subsec_nanos() != 0
+        {
+            return Err(crate::error::AppError::config(
+                ConfigError::DurationNotWholeSeconds {
+                    value: text.trim().to_owned(),
+                },
+            ));
+        }
+        Ok(duration.as_secs())
+    }
 }
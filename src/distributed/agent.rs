@@ -1,14 +1,17 @@
 mod command;
+mod health;
 mod run_exec;
 mod session;
 mod wire;
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use tracing::{info, warn};
 
 use crate::args::TesterArgs;
 use crate::error::AppResult;
+use health::{AgentHealth, spawn_health_server};
 pub(crate) use run_exec::{AgentLocalRunPort, AgentRunOutcome};
 
 /// Runs the distributed agent loop.
@@ -31,21 +34,40 @@ where
         reconnect_delay.as_millis()
     );
 
-    loop {
-        let result = session::run_agent_session(&args, local_run_port).await;
+    let (health, health_handle) = match args.agent_health_listen.as_deref() {
+        Some(listen) => {
+            let health = Arc::new(AgentHealth::new(
+                wire::build_agent_id(&args),
+                args.agent_join.clone().unwrap_or_default(),
+            ));
+            let handle = spawn_health_server(listen, Arc::clone(&health)).await?;
+            (Some(health), Some(handle))
+        }
+        None => (None, None),
+    };
+
+    let outcome = loop {
+        let result = session::run_agent_session(&args, local_run_port, health.as_deref()).await;
+        if let Some(health) = health.as_deref() {
+            health.set_connected(false);
+        }
         match result {
             Ok(()) => {
                 if !standby {
-                    return Ok(());
+                    break Ok(());
                 }
             }
             Err(err) => {
                 if !standby {
-                    return Err(err);
+                    break Err(err);
                 }
                 warn!("Agent session error: {}", err);
             }
         }
         tokio::time::sleep(reconnect_delay).await;
+    };
+    if let Some(handle) = health_handle {
+        handle.abort();
     }
+    outcome
 }
@@ -0,0 +1,225 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde::Serialize;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::error::{AppError, AppResult, DistributedError};
+
+use crate::distributed::http::{read_http_request, write_error_response, write_json_response};
+
+/// Liveness shared between the agent session and `--agent-health-listen`.
+#[derive(Debug)]
+pub(super) struct AgentHealth {
+    agent_id: String,
+    controller: String,
+    started_at: Instant,
+    state: Mutex<AgentHealthState>,
+}
+
+#[derive(Debug, Default)]
+struct AgentHealthState {
+    connected: bool,
+    run_id: Option<String>,
+    in_flight_requests: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct AgentHealthSnapshot {
+    pub(super) status: &'static str,
+    pub(super) agent_id: String,
+    pub(super) controller: String,
+    pub(super) connected: bool,
+    pub(super) run_id: Option<String>,
+    pub(super) in_flight_requests: u64,
+    pub(super) uptime_ms: u64,
+}
+
+impl AgentHealth {
+    pub(super) fn new(agent_id: String, controller: String) -> Self {
+        Self {
+            agent_id,
+            controller,
+            started_at: Instant::now(),
+            state: Mutex::new(AgentHealthState::default()),
+        }
+    }
+
+    pub(super) fn set_connected(&self, connected: bool) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.connected = connected;
+        if !connected {
+            state.run_id = None;
+            state.in_flight_requests = 0;
+        }
+    }
+
+    pub(super) fn set_run(&self, run_id: Option<String>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.run_id = run_id;
+        state.in_flight_requests = 0;
+    }
+
+    pub(super) fn set_in_flight(&self, in_flight_requests: u64) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.in_flight_requests = in_flight_requests;
+    }
+
+    pub(super) fn snapshot(&self) -> AgentHealthSnapshot {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        AgentHealthSnapshot {
+            status: "ok",
+            agent_id: self.agent_id.clone(),
+            controller: self.controller.clone(),
+            connected: state.connected,
+            run_id: state.run_id.clone(),
+            in_flight_requests: state.in_flight_requests,
+            uptime_ms: u64::try_from(self.started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Expands a bare `:port` into an all-interfaces bind address.
+pub(super) fn resolve_health_listen(listen: &str) -> String {
+    if listen.starts_with(':') {
+        format!("0.0.0.0{}", listen)
+    } else {
+        listen.to_owned()
+    }
+}
+
+/// Binds the read-only health endpoint and serves it in the background.
+///
+/// `GET /health` (or `/`) returns the current [`AgentHealthSnapshot`] as JSON.
+pub(super) async fn spawn_health_server(
+    listen: &str,
+    health: Arc<AgentHealth>,
+) -> AppResult<JoinHandle<()>> {
+    let addr = resolve_health_listen(listen);
+    let listener = TcpListener::bind(&addr).await.map_err(|err| {
+        AppError::distributed(DistributedError::Bind {
+            addr: addr.clone(),
+            source: err,
+        })
+    })?;
+    info!("Agent health endpoint listening on {}", addr);
+    Ok(tokio::spawn(async move {
+        loop {
+            let socket = match listener.accept().await {
+                Ok((socket, _)) => socket,
+                Err(err) => {
+                    warn!("Agent health accept failed: {}", err);
+                    continue;
+                }
+            };
+            let health = Arc::clone(&health);
+            tokio::spawn(handle_health_connection(socket, health));
+        }
+    }))
+}
+
+async fn handle_health_connection(mut socket: TcpStream, health: Arc<AgentHealth>) {
+    let result = match read_http_request(&mut socket).await {
+        Ok(request) => match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/health" | "/") => {
+                write_json_response(&mut socket, 200, &health.snapshot()).await
+            }
+            _ => write_error_response(&mut socket, 404, "Not found").await,
+        },
+        Err(err) => write_error_response(&mut socket, err.status, &err.message).await,
+    };
+    if result.is_err() {
+        // Client went away before the response was written.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn health_endpoint_reports_run_state() -> AppResult<()> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| AppError::validation(format!("Failed to build runtime: {}", err)))?;
+        runtime.block_on(async {
+            if resolve_health_listen(":9100") != "0.0.0.0:9100" {
+                return Err(AppError::validation(
+                    "Expected :9100 to bind all interfaces",
+                ));
+            }
+
+            let health = Arc::new(AgentHealth::new(
+                "agent-1".to_owned(),
+                "10.0.0.5:9009".to_owned(),
+            ));
+            health.set_connected(true);
+            health.set_run(Some("run-42".to_owned()));
+            health.set_in_flight(7);
+
+            let listener = TcpListener::bind("127.0.0.1:0")
+                .await
+                .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+            let addr = listener
+                .local_addr()
+                .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+            let server_health = Arc::clone(&health);
+            let server = tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    handle_health_connection(socket, server_health).await;
+                }
+            });
+
+            let mut client = TcpStream::connect(addr)
+                .await
+                .map_err(|err| AppError::validation(format!("Failed to connect: {}", err)))?;
+            client
+                .write_all(b"GET /health HTTP/1.1\r\nHost: agent\r\n\r\n")
+                .await
+                .map_err(|err| AppError::validation(format!("Failed to write: {}", err)))?;
+            let mut response = String::new();
+            client
+                .read_to_string(&mut response)
+                .await
+                .map_err(|err| AppError::validation(format!("Failed to read: {}", err)))?;
+            server
+                .await
+                .map_err(|err| AppError::validation(format!("Server task failed: {}", err)))?;
+
+            let body = response
+                .split_once("\r\n\r\n")
+                .map(|(_, body)| body)
+                .ok_or_else(|| AppError::validation("Missing response body"))?;
+            let json: serde_json::Value = serde_json::from_str(body)
+                .map_err(|err| AppError::validation(format!("Invalid JSON: {}", err)))?;
+            let connected = json.get("connected").and_then(serde_json::Value::as_bool);
+            let run_id = json.get("run_id").and_then(serde_json::Value::as_str);
+            let in_flight = json
+                .get("in_flight_requests")
+                .and_then(serde_json::Value::as_u64);
+            if !response.starts_with("HTTP/1.1 200")
+                || connected != Some(true)
+                || run_id != Some("run-42")
+                || in_flight != Some(7)
+            {
+                return Err(AppError::validation(format!(
+                    "Unexpected health response: {}",
+                    response
+                )));
+            }
+
+            health.set_connected(false);
+            let snapshot = health.snapshot();
+            if snapshot.connected || snapshot.run_id.is_some() || snapshot.in_flight_requests != 0 {
+                return Err(AppError::validation(
+                    "Expected disconnect to clear run state",
+                ));
+            }
+            Ok(())
+        })
+    }
+}
@@ -7,7 +7,8 @@ use crate::error::{AppError, AppResult, DistributedError};
 use crate::metrics::StreamSnapshot;
 
 use super::command::AgentCommand;
-use super::wire::{send_wire, snapshot_to_wire_summary};
+use super::health::AgentHealth;
+use super::wire::{send_wire, snapshot_to_wire_summary, wire_counts};
 use crate::distributed::protocol::{
     ConfigMessage, ErrorMessage, ReportMessage, StreamMessage, WireMessage, WireSummary,
 };
@@ -34,6 +35,7 @@ pub(crate) trait AgentLocalRunPort {
     ) -> AppResult<AgentRunOutcome>;
 }
 
+#[expect(clippy::too_many_arguments)]
 pub(super) async fn run_agent_run<TLocalRunPort>(
     base_args: &TesterArgs,
     config: ConfigMessage,
@@ -41,6 +43,39 @@ pub(super) async fn run_agent_run<TLocalRunPort>(
     out_tx: &mpsc::UnboundedSender<WireMessage>,
     cmd_rx: &mut mpsc::UnboundedReceiver<AgentCommand>,
     local_run_port: &TLocalRunPort,
+    health: Option<&AgentHealth>,
+) -> AppResult<()>
+where
+    TLocalRunPort: AgentLocalRunPort + Sync,
+{
+    if let Some(health) = health {
+        health.set_run(Some(config.run_id.clone()));
+    }
+    let result = execute_agent_run(
+        base_args,
+        config,
+        agent_id,
+        out_tx,
+        cmd_rx,
+        local_run_port,
+        health,
+    )
+    .await;
+    if let Some(health) = health {
+        health.set_run(None);
+    }
+    result
+}
+
+#[expect(clippy::too_many_arguments)]
+async fn execute_agent_run<TLocalRunPort>(
+    base_args: &TesterArgs,
+    config: ConfigMessage,
+    agent_id: String,
+    out_tx: &mpsc::UnboundedSender<WireMessage>,
+    cmd_rx: &mut mpsc::UnboundedReceiver<AgentCommand>,
+    local_run_port: &TLocalRunPort,
+    health: Option<&AgentHealth>,
 ) -> AppResult<()>
 where
     TLocalRunPort: AgentLocalRunPort + Sync,
@@ -56,11 +91,14 @@ where
     }
     info!("Starting run {} on agent {}", run_id, agent_id);
     run_args.distributed_silent = true;
+    run_args.run_id = Some(run_id.clone());
     let streaming_enabled = run_args.distributed_stream_summaries;
 
     let (stop_tx, stop_rx) = watch::channel(false);
 
-    let (stream_tx, mut stream_rx) = if streaming_enabled {
+    // The health endpoint reads in-flight counts from the same snapshots.
+    let snapshots_enabled = streaming_enabled || health.is_some();
+    let (stream_tx, mut stream_rx) = if snapshots_enabled {
         let (stream_tx, stream_rx) = mpsc::unbounded_channel::<StreamSnapshot>();
         (Some(stream_tx), Some(stream_rx))
     } else {
@@ -84,8 +122,14 @@ where
                 } else {
                     None
                 }
-            }, if streaming_enabled => {
+            }, if snapshots_enabled => {
                 if let Some(snapshot) = snapshot {
+                    if let Some(health) = health {
+                        health.set_in_flight(snapshot.in_flight_ops);
+                    }
+                    if !streaming_enabled {
+                        continue;
+                    }
                     let message = WireMessage::Stream(Box::new(StreamMessage {
                         run_id: run_id.clone(),
                         agent_id: agent_id.clone(),
@@ -143,6 +187,9 @@ where
         min_latency_ms: run_outcome.summary.min_latency_ms,
         max_latency_ms: run_outcome.summary.max_latency_ms,
         latency_sum_ms: run_outcome.latency_sum_ms,
+        http_versions: wire_counts(&run_outcome.summary.http_versions),
+        error_reasons: wire_counts(&run_outcome.summary.error_reasons),
+        check_failures: run_outcome.summary.check_failures,
     };
 
     let report = ReportMessage {
@@ -1,23 +1,29 @@
 use std::time::Duration;
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use tokio::io::BufReader;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
-use tokio::time::MissedTickBehavior;
 use tracing::{debug, info};
 
 use crate::args::TesterArgs;
 use crate::error::{AppError, AppResult, DistributedError};
 
 use super::command::AgentCommand;
+use super::health::AgentHealth;
 use super::run_exec::{AgentLocalRunPort, run_agent_run};
 use super::wire::{build_agent_id, build_hello, send_wire};
 use crate::distributed::protocol::{HeartbeatMessage, WireMessage, read_message, send_message};
 use crate::distributed::utils::current_time_ms;
 
+/// Fraction of the heartbeat interval (1/N) that may be shaved off each delay.
+const HEARTBEAT_JITTER_DIVISOR: u32 = 5;
+
 pub(super) async fn run_agent_session<TLocalRunPort>(
     base_args: &TesterArgs,
     local_run_port: &TLocalRunPort,
+    health: Option<&AgentHealth>,
 ) -> AppResult<()>
 where
     TLocalRunPort: AgentLocalRunPort + Sync,
@@ -36,6 +42,9 @@ where
         })
     })?;
     info!("Connected to controller {}", join);
+    if let Some(health) = health {
+        health.set_connected(true);
+    }
     let (read_half, mut write_half) = stream.into_split();
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<WireMessage>();
     let writer_handle = tokio::spawn(async move {
@@ -91,15 +100,14 @@ where
     let heartbeat_interval = Duration::from_millis(base_args.agent_heartbeat_interval_ms.get());
     let heartbeat_tx = out_tx.clone();
     let heartbeat_handle = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(heartbeat_interval);
-        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut rng = StdRng::from_entropy();
         loop {
-            interval.tick().await;
             let sent_at_ms = u64::try_from(current_time_ms()).unwrap_or(u64::MAX);
             let message = WireMessage::Heartbeat(HeartbeatMessage { sent_at_ms });
             if send_wire(&heartbeat_tx, message).is_err() {
                 break;
             }
+            tokio::time::sleep(jittered_heartbeat_delay(heartbeat_interval, &mut rng)).await;
         }
     });
 
@@ -136,6 +144,7 @@ where
             &out_tx,
             &mut cmd_rx,
             local_run_port,
+            health,
         )
         .await;
 
@@ -212,3 +221,45 @@ async fn wait_for_start(
         DistributedError::ControllerConnectionClosed,
     ))
 }
+
+/// Picks the delay before the next heartbeat.
+///
+/// Jitter only ever shortens the delay, so agents that connected together drift
+/// apart while the gap between heartbeats never exceeds the configured interval
+/// and `--agent-heartbeat-timeout-ms` keeps its meaning.
+fn jittered_heartbeat_delay<R: Rng>(interval: Duration, rng: &mut R) -> Duration {
+    let window = interval
+        .checked_div(HEARTBEAT_JITTER_DIVISOR)
+        .unwrap_or(Duration::ZERO);
+    if window.is_zero() {
+        return interval;
+    }
+    let shave = rng.gen_range(Duration::ZERO..=window);
+    interval.saturating_sub(shave)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jittered_heartbeat_delay_stays_within_interval() -> AppResult<()> {
+        let interval = Duration::from_millis(1_000);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut distinct = std::collections::BTreeSet::new();
+        for _ in 0..64 {
+            let delay = jittered_heartbeat_delay(interval, &mut rng);
+            if delay > interval || delay < Duration::from_millis(800) {
+                return Err(AppError::validation(format!(
+                    "Heartbeat delay {:?} outside jitter window",
+                    delay
+                )));
+            }
+            distinct.insert(delay);
+        }
+        if distinct.len() < 2 {
+            return Err(AppError::validation("Expected jittered heartbeat delays"));
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use tokio::sync::mpsc;
 
 use crate::args::TesterArgs;
@@ -30,9 +32,20 @@ pub(super) fn snapshot_to_wire_summary(snapshot: &StreamSnapshot) -> WireSummary
         min_latency_ms: snapshot.min_latency_ms,
         max_latency_ms: snapshot.max_latency_ms,
         latency_sum_ms: snapshot.latency_sum_ms,
+        http_versions: wire_counts(&snapshot.http_versions),
+        error_reasons: wire_counts(&snapshot.error_reasons),
+        check_failures: snapshot.check_failures,
     }
 }
 
+/// Converts a label-keyed count map (HTTP versions, error reasons) to its wire form.
+pub(super) fn wire_counts(counts: &BTreeMap<&'static str, u64>) -> BTreeMap<String, u64> {
+    counts
+        .iter()
+        .map(|(label, count)| ((*label).to_owned(), *count))
+        .collect()
+}
+
 pub(super) fn build_hello(args: &TesterArgs, agent_id: &str) -> HelloMessage {
     HelloMessage {
         agent_id: agent_id.to_owned(),
@@ -1,7 +1,7 @@
 mod agent;
 mod auto;
+mod breakdown;
 mod control;
-mod http;
 mod load;
 mod manual;
 mod output;
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::args::TesterArgs;
-use crate::error::{AppError, AppResult, DistributedError};
+use crate::error::{AppError, AppResult, DistributedError, ValidationError};
 
 use super::super::output::finalize_output;
 use super::events::AutoRunOutcome;
@@ -17,7 +17,8 @@ pub(super) async fn finalize_auto_run(args: &TesterArgs, outcome: AutoRunOutcome
     } = outcome;
 
     append_channel_closure_errors(channel_closed, &pending_agents, &mut runtime_errors);
-    finalize_output(args, &mut output_state, &agent_states, &mut runtime_errors).await;
+    let threshold_failures =
+        finalize_output(args, &mut output_state, &agent_states, &mut runtime_errors).await;
 
     if !runtime_errors.is_empty() {
         eprintln!("Runtime errors:");
@@ -28,6 +29,11 @@ pub(super) async fn finalize_auto_run(args: &TesterArgs, outcome: AutoRunOutcome
             DistributedError::RunCompletedWithErrors,
         ));
     }
+    if !threshold_failures.is_empty() {
+        return Err(AppError::validation(ValidationError::ThresholdsFailed {
+            failures: threshold_failures.join("; "),
+        }));
+    }
 
     Ok(())
 }
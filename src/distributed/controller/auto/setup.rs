@@ -7,6 +7,7 @@ use crate::args::TesterArgs;
 use crate::error::{AppError, AppResult, DistributedError};
 
 use super::super::agent::{AgentConn, accept_agent};
+use super::super::breakdown::AgentAssignment;
 use super::super::load::apply_load_share;
 use super::super::output::{DistributedOutputState, setup_output_state};
 use super::super::shared::{DEFAULT_START_AFTER_MS, REPORT_GRACE_SECS, resolve_agent_wait_timeout};
@@ -35,9 +36,15 @@ pub(super) async fn prepare_auto_run(args: &TesterArgs) -> AppResult<AutoRunSetu
         agents.len()
     );
 
+    let mut assignments = Vec::with_capacity(agents.len());
     for (idx, agent) in agents.iter_mut().enumerate() {
         let mut agent_args = base_args.clone();
         apply_load_share(&mut agent_args, args, &weights, idx);
+        assignments.push(AgentAssignment {
+            agent_id: agent.agent_id.clone(),
+            weight: agent.weight,
+            rate_limit: agent_args.rate_limit,
+        });
         debug!(
             "Sending config to agent {} for run {}",
             agent.agent_id, run_id
@@ -67,7 +74,7 @@ pub(super) async fn prepare_auto_run(args: &TesterArgs) -> AppResult<AutoRunSetu
         .await?;
     }
 
-    let output_state = setup_output_state(args);
+    let output_state = setup_output_state(args, assignments, run_id.clone());
     let heartbeat_timeout = Duration::from_millis(args.agent_heartbeat_timeout_ms.get());
     let report_deadline = Instant::now()
         .checked_add(
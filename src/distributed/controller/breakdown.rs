@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use super::shared::AgentSnapshot;
+
+/// Achieved rate below this percentage of the assigned rate flags an agent.
+const UNDERPERFORM_PERCENT: u128 = 90;
+/// Scale for rates carried as `rps * 100`.
+const RATE_SCALE: u128 = 100;
+const MS_PER_SEC: u128 = 1_000;
+const PERCENT_DIVISOR: u128 = 100;
+
+/// Load share handed to one agent when the run was configured.
+#[derive(Debug, Clone)]
+pub(in crate::distributed::controller) struct AgentAssignment {
+    pub(in crate::distributed::controller) agent_id: String,
+    pub(in crate::distributed::controller) weight: u64,
+    /// Per-agent `--rate` after `apply_load_share`; `None` when unthrottled or
+    /// driven by a load profile, in which case the weighted share of the
+    /// combined achieved rate is used instead.
+    pub(in crate::distributed::controller) rate_limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(in crate::distributed::controller) struct AgentRateRow {
+    pub(in crate::distributed::controller) agent_id: String,
+    pub(in crate::distributed::controller) weight: u64,
+    pub(in crate::distributed::controller) total_requests: u64,
+    pub(in crate::distributed::controller) achieved_rps_x100: u128,
+    pub(in crate::distributed::controller) expected_rps_x100: u128,
+    pub(in crate::distributed::controller) rate_limited: bool,
+    pub(in crate::distributed::controller) reported: bool,
+    pub(in crate::distributed::controller) underperforming: bool,
+}
+
+/// Compares each agent's achieved rps against its assigned share.
+pub(in crate::distributed::controller) fn compute_agent_breakdown(
+    assignments: &[AgentAssignment],
+    agent_states: &HashMap<String, AgentSnapshot>,
+) -> Vec<AgentRateRow> {
+    let achieved: Vec<Option<(u64, u128)>> = assignments
+        .iter()
+        .map(|assignment| {
+            agent_states.get(&assignment.agent_id).map(|snapshot| {
+                let summary = &snapshot.summary;
+                let duration_ms = u128::from(summary.duration_ms.max(1));
+                let rps_x100 = u128::from(summary.total_requests)
+                    .saturating_mul(MS_PER_SEC)
+                    .saturating_mul(RATE_SCALE)
+                    .checked_div(duration_ms)
+                    .unwrap_or(0);
+                (summary.total_requests, rps_x100)
+            })
+        })
+        .collect();
+    let combined_rps_x100: u128 = achieved
+        .iter()
+        .flatten()
+        .map(|(_, rps_x100)| *rps_x100)
+        .fold(0, u128::saturating_add);
+    let total_weight: u128 = assignments
+        .iter()
+        .map(|assignment| u128::from(assignment.weight))
+        .fold(0, u128::saturating_add);
+
+    assignments
+        .iter()
+        .zip(achieved)
+        .map(|(assignment, achieved)| {
+            let expected_rps_x100 = assignment.rate_limit.map_or_else(
+                || {
+                    combined_rps_x100
+                        .saturating_mul(u128::from(assignment.weight))
+                        .checked_div(total_weight)
+                        .unwrap_or(0)
+                },
+                |rate| u128::from(rate).saturating_mul(RATE_SCALE),
+            );
+            let (total_requests, achieved_rps_x100) = achieved.unwrap_or((0, 0));
+            let underperforming = expected_rps_x100 > 0
+                && achieved_rps_x100.saturating_mul(PERCENT_DIVISOR)
+                    < expected_rps_x100.saturating_mul(UNDERPERFORM_PERCENT);
+            AgentRateRow {
+                agent_id: assignment.agent_id.clone(),
+                weight: assignment.weight,
+                total_requests,
+                achieved_rps_x100,
+                expected_rps_x100,
+                rate_limited: assignment.rate_limit.is_some(),
+                reported: achieved.is_some(),
+                underperforming,
+            }
+        })
+        .collect()
+}
+
+pub(in crate::distributed::controller) fn agent_breakdown_lines(
+    rows: &[AgentRateRow],
+) -> Vec<String> {
+    let mut lines = Vec::with_capacity(rows.len().saturating_add(1));
+    lines.push("Agent Breakdown:".to_owned());
+    let id_width = rows.iter().map(|row| row.agent_id.len()).max().unwrap_or(0);
+    for row in rows {
+        let basis = if row.rate_limited {
+            "assigned"
+        } else {
+            "share"
+        };
+        let percent = row
+            .achieved_rps_x100
+            .saturating_mul(PERCENT_DIVISOR)
+            .checked_div(row.expected_rps_x100)
+            .map_or_else(|| "-".to_owned(), |value| format!("{}%", value));
+        let status = if !row.reported {
+            "NO REPORT"
+        } else if row.underperforming {
+            "UNDER"
+        } else {
+            "ok"
+        };
+        lines.push(format!(
+            "  {:<id_width$}  weight={}  requests={}  achieved={} rps  {}={} rps  ({})  {}",
+            row.agent_id,
+            row.weight,
+            row.total_requests,
+            format_rate(row.achieved_rps_x100),
+            basis,
+            format_rate(row.expected_rps_x100),
+            percent,
+            status,
+        ));
+    }
+    lines
+}
+
+/// One agent's totals for `--per-agent-summary`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(in crate::distributed::controller) struct AgentSummaryRow {
+    pub(in crate::distributed::controller) agent_id: String,
+    pub(in crate::distributed::controller) total_requests: u64,
+    pub(in crate::distributed::controller) success_rate_x100: u128,
+    pub(in crate::distributed::controller) p99_ms: u64,
+}
+
+/// Summarizes each reporting agent, busiest first.
+pub(in crate::distributed::controller) fn compute_agent_summaries(
+    agent_states: &HashMap<String, AgentSnapshot>,
+) -> Vec<AgentSummaryRow> {
+    let mut rows: Vec<AgentSummaryRow> = agent_states
+        .iter()
+        .map(|(agent_id, snapshot)| {
+            let summary = &snapshot.summary;
+            let success_rate_x100 = u128::from(summary.successful_requests)
+                .saturating_mul(PERCENT_DIVISOR)
+                .saturating_mul(RATE_SCALE)
+                .checked_div(u128::from(summary.total_requests))
+                .unwrap_or(0);
+            let (_, _, p99_ms) = snapshot.histogram.percentiles();
+            AgentSummaryRow {
+                agent_id: agent_id.clone(),
+                total_requests: summary.total_requests,
+                success_rate_x100,
+                p99_ms,
+            }
+        })
+        .collect();
+    rows.sort_by(|left, right| {
+        right
+            .total_requests
+            .cmp(&left.total_requests)
+            .then_with(|| left.agent_id.cmp(&right.agent_id))
+    });
+    rows
+}
+
+pub(in crate::distributed::controller) fn agent_summary_lines(
+    rows: &[AgentSummaryRow],
+) -> Vec<String> {
+    let mut lines = Vec::with_capacity(rows.len().saturating_add(1));
+    lines.push("Per-Agent Summary:".to_owned());
+    let id_width = rows.iter().map(|row| row.agent_id.len()).max().unwrap_or(0);
+    for row in rows {
+        lines.push(format!(
+            "  {:<id_width$}  requests={}  success={}%  p99={}ms",
+            row.agent_id,
+            row.total_requests,
+            format_rate(row.success_rate_x100),
+            row.p99_ms,
+        ));
+    }
+    lines
+}
+
+fn format_rate(value_x100: u128) -> String {
+    format!(
+        "{}.{:02}",
+        value_x100.checked_div(RATE_SCALE).unwrap_or(0),
+        value_x100.checked_rem(RATE_SCALE).unwrap_or(0)
+    )
+}
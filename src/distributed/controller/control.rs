@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use tokio::sync::oneshot;
 
+use crate::metrics::AggregatedMetricSample;
+
 #[derive(Debug, Deserialize, Default)]
 pub(super) struct ControlStartRequest {
     pub(super) scenario_name: Option<String>,
@@ -15,20 +17,7 @@ pub(super) struct ControlResponse {
     pub(super) run_id: Option<String>,
 }
 
-#[derive(Debug)]
-pub(super) struct ControlError {
-    pub(super) status: u16,
-    pub(super) message: String,
-}
-
-impl ControlError {
-    pub(super) fn new(status: u16, message: impl Into<String>) -> Self {
-        Self {
-            status,
-            message: message.into(),
-        }
-    }
-}
+pub(super) use crate::distributed::http::ControlError;
 
 pub(super) enum ControlCommand {
     Start {
@@ -38,4 +27,8 @@ pub(super) enum ControlCommand {
     Stop {
         respond_to: oneshot::Sender<Result<ControlResponse, ControlError>>,
     },
+    /// Current aggregated metrics; `None` when no run is active.
+    Metrics {
+        respond_to: oneshot::Sender<Option<AggregatedMetricSample>>,
+    },
 }
@@ -36,8 +36,15 @@ fn split_load_profile(
 ) -> Vec<super::super::protocol::WireLoadProfile> {
     let initial_shares = split_total(profile.initial_rpm, weights);
     let mut stage_shares: Vec<Vec<u64>> = Vec::new();
+    let mut sine_shares: Vec<Option<(Vec<u64>, Vec<u64>)>> = Vec::new();
     for stage in &profile.stages {
         stage_shares.push(split_total(stage.target_rpm, weights));
+        sine_shares.push(stage.sine.map(|sine| {
+            (
+                split_total(sine.min_rpm, weights),
+                split_total(sine.max_rpm, weights),
+            )
+        }));
     }
 
     let mut per_agent = Vec::with_capacity(weights.len());
@@ -49,9 +56,18 @@ fn split_load_profile(
                 .and_then(|values| values.get(idx))
                 .copied()
                 .unwrap_or(0);
+            let sine = stage.sine.and_then(|sine| {
+                let (min_shares, max_shares) = sine_shares.get(stage_idx)?.as_ref()?;
+                Some(super::super::protocol::WireSineWave {
+                    min_rpm: min_shares.get(idx).copied().unwrap_or(0),
+                    max_rpm: max_shares.get(idx).copied().unwrap_or(0),
+                    period_secs: sine.period.as_secs(),
+                })
+            });
             stages.push(super::super::protocol::WireLoadStage {
                 duration_secs: stage.duration.as_secs(),
                 target_rpm: share,
+                sine,
             });
         }
         let initial_rpm = initial_shares.get(idx).copied().unwrap_or(0);
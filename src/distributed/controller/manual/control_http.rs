@@ -4,7 +4,9 @@ use tokio::net::TcpStream;
 use tokio::sync::{mpsc, oneshot};
 
 use super::super::control::{ControlCommand, ControlError, ControlStartRequest};
-use super::super::http::{read_http_request, write_error_response, write_json_response};
+use crate::distributed::http::{
+    read_http_request, write_empty_response, write_error_response, write_json_response,
+};
 
 pub(super) async fn handle_control_connection(
     mut socket: TcpStream,
@@ -152,6 +154,35 @@ pub(super) async fn handle_control_connection(
                 }
             }
         }
+        ("GET", "/metrics") => {
+            let (respond_to, response_rx) = oneshot::channel();
+            if control_tx
+                .send(ControlCommand::Metrics { respond_to })
+                .is_err()
+            {
+                if write_error_response(&mut socket, 503, "Controller unavailable")
+                    .await
+                    .is_err()
+                {
+                    // Socket closed while writing error response.
+                }
+                return;
+            }
+
+            let written = match tokio::time::timeout(Duration::from_secs(5), response_rx).await {
+                Ok(Ok(Some(sample))) => write_json_response(&mut socket, 200, &sample).await,
+                Ok(Ok(None)) => write_empty_response(&mut socket, 204).await,
+                Ok(Err(_)) => {
+                    write_error_response(&mut socket, 503, "Controller unavailable").await
+                }
+                Err(_) => {
+                    write_error_response(&mut socket, 504, "Controller response timed out").await
+                }
+            };
+            if written.is_err() {
+                // Socket closed while writing response.
+            }
+        }
         _ => {
             if write_error_response(&mut socket, 404, "Not found")
                 .await
@@ -162,3 +193,126 @@ pub(super) async fn handle_control_connection(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AppError, AppResult};
+    use crate::metrics::AggregatedMetricSample;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    async fn metrics_roundtrip(
+        request: &'static [u8],
+        sample: Option<AggregatedMetricSample>,
+    ) -> AppResult<String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let (control_tx, mut control_rx) = mpsc::unbounded_channel();
+        let server = tokio::spawn(async move {
+            if let Ok((socket, _)) = listener.accept().await {
+                handle_control_connection(socket, Some("secret"), control_tx).await;
+            }
+        });
+        let responder = tokio::spawn(async move {
+            if let Some(ControlCommand::Metrics { respond_to }) = control_rx.recv().await
+                && respond_to.send(sample).is_err()
+            {
+                // Handler dropped the response channel.
+            }
+        });
+
+        let mut client = TcpStream::connect(addr)
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to connect: {}", err)))?;
+        client
+            .write_all(request)
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to write: {}", err)))?;
+        let mut response = String::new();
+        client
+            .read_to_string(&mut response)
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to read: {}", err)))?;
+        server
+            .await
+            .map_err(|err| AppError::validation(format!("Server task failed: {}", err)))?;
+        responder
+            .await
+            .map_err(|err| AppError::validation(format!("Responder task failed: {}", err)))?;
+        Ok(response)
+    }
+
+    #[test]
+    fn metrics_route_reports_aggregate_and_requires_auth() -> AppResult<()> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| AppError::validation(format!("Failed to build runtime: {}", err)))?;
+        runtime.block_on(async {
+            let sample = AggregatedMetricSample {
+                elapsed_ms: 2000,
+                total_requests: 40,
+                successful_requests: 38,
+                error_requests: 2,
+                avg_latency_ms: 12,
+                p50_latency_ms: 10,
+                p90_latency_ms: 20,
+                p99_latency_ms: 30,
+            };
+
+            let unauthorized =
+                metrics_roundtrip(b"GET /metrics HTTP/1.1\r\nHost: c\r\n\r\n", None).await?;
+            if !unauthorized.starts_with("HTTP/1.1 401") {
+                return Err(AppError::validation(format!(
+                    "Expected 401 without a token, got {}",
+                    unauthorized
+                )));
+            }
+
+            let active = metrics_roundtrip(
+                b"GET /metrics HTTP/1.1\r\nHost: c\r\nAuthorization: Bearer secret\r\n\r\n",
+                Some(sample),
+            )
+            .await?;
+            let body = active
+                .split_once("\r\n\r\n")
+                .map(|(_, body)| body)
+                .ok_or_else(|| AppError::validation("Missing response body"))?;
+            let json: serde_json::Value = serde_json::from_str(body)
+                .map_err(|err| AppError::validation(format!("Invalid JSON: {}", err)))?;
+            if !active.starts_with("HTTP/1.1 200")
+                || json
+                    .get("total_requests")
+                    .and_then(serde_json::Value::as_u64)
+                    != Some(40)
+                || json
+                    .get("p99_latency_ms")
+                    .and_then(serde_json::Value::as_u64)
+                    != Some(30)
+            {
+                return Err(AppError::validation(format!(
+                    "Unexpected metrics response: {}",
+                    active
+                )));
+            }
+
+            let idle = metrics_roundtrip(
+                b"GET /metrics HTTP/1.1\r\nHost: c\r\nAuthorization: Bearer secret\r\n\r\n",
+                None,
+            )
+            .await?;
+            if !idle.starts_with("HTTP/1.1 204") {
+                return Err(AppError::validation(format!(
+                    "Expected 204 while idle, got {}",
+                    idle
+                )));
+            }
+            Ok(())
+        })
+    }
+}
@@ -10,7 +10,9 @@ use crate::error::{AppError, AppResult, DistributedError};
 
 use super::super::control::{ControlCommand, ControlError, ControlResponse};
 use super::super::output::{OutputEvent, handle_output_event};
-use super::super::shared::{AgentEvent, event_agent_id, handle_agent_event};
+use super::super::shared::{
+    AgentEvent, build_aggregated_sample, event_agent_id, handle_agent_event,
+};
 use super::run_finalize::finalize_manual_run;
 use super::run_lifecycle::request_stop;
 use super::state::{ManualAgent, ManualRunState};
@@ -55,6 +57,11 @@ pub(super) async fn handle_active_run(
                         // Requester dropped the response channel.
                     }
                 }
+                ControlCommand::Metrics { respond_to } => {
+                    if respond_to.send(build_aggregated_sample(&state.agent_states)).is_err() {
+                        // Requester dropped the response channel.
+                    }
+                }
             }
         }
         event = event_rx.recv() => {
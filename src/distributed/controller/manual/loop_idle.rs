@@ -91,6 +91,11 @@ pub(super) async fn handle_idle(
                         // Requester dropped the response channel.
                     }
                 }
+                ControlCommand::Metrics { respond_to } => {
+                    if respond_to.send(None).is_err() {
+                        // Requester dropped the response channel.
+                    }
+                }
             }
         }
         event = event_rx.recv() => {
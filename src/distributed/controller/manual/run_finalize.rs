@@ -1,5 +1,5 @@
 use crate::args::TesterArgs;
-use crate::error::{AppError, AppResult, DistributedError};
+use crate::error::{AppError, AppResult, DistributedError, ValidationError};
 
 use super::super::output::finalize_output;
 use super::state::ManualRunState;
@@ -8,7 +8,7 @@ pub(super) async fn finalize_manual_run(
     args: &TesterArgs,
     state: &mut ManualRunState,
 ) -> AppResult<()> {
-    finalize_output(
+    let threshold_failures = finalize_output(
         args,
         &mut state.output_state,
         &state.agent_states,
@@ -25,6 +25,11 @@ pub(super) async fn finalize_manual_run(
             DistributedError::RunCompletedWithErrors,
         ));
     }
+    if !threshold_failures.is_empty() {
+        return Err(AppError::validation(ValidationError::ThresholdsFailed {
+            failures: threshold_failures.join("; "),
+        }));
+    }
 
     Ok(())
 }
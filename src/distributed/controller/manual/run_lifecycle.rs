@@ -7,6 +7,7 @@ use tokio::time::{Instant, MissedTickBehavior};
 use crate::args::{Scenario, TesterArgs};
 use crate::config::apply::scenario::{ScenarioDefaults, parse_scenario};
 
+use super::super::breakdown::AgentAssignment;
 use super::super::control::{ControlError, ControlStartRequest};
 use super::super::load::apply_load_share;
 use super::super::output::setup_output_state;
@@ -53,10 +54,12 @@ pub(super) async fn start_manual_run(
     let weights: Vec<u64> = agents.iter().map(|agent| agent.weight).collect();
     let mut pending_agents = HashSet::new();
     let mut failed_agents = Vec::new();
+    let mut assignments = Vec::with_capacity(agents.len());
 
     for (idx, agent) in agents.iter().enumerate() {
         let mut agent_args = base_args.clone();
         apply_load_share(&mut agent_args, args, &weights, idx);
+        let rate_limit = agent_args.rate_limit;
         if agent
             .sender
             .send(WireMessage::Config(Box::new(ConfigMessage {
@@ -75,6 +78,11 @@ pub(super) async fn start_manual_run(
             failed_agents.push(agent.agent_id.clone());
             continue;
         }
+        assignments.push(AgentAssignment {
+            agent_id: agent.agent_id.clone(),
+            weight: agent.weight,
+            rate_limit,
+        });
         pending_agents.insert(agent.agent_id.clone());
     }
 
@@ -107,7 +115,7 @@ pub(super) async fn start_manual_run(
         ));
     }
 
-    let output_state = setup_output_state(args);
+    let output_state = setup_output_state(args, assignments, run_id.clone());
     let mut sink_interval = tokio::time::interval(resolve_sink_interval(args.sinks.as_ref()));
     sink_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
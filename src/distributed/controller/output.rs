@@ -4,7 +4,9 @@ use std::time::Duration;
 
 use tokio::sync::watch;
 
-use crate::args::TesterArgs;
+use crate::app::assertions;
+use crate::app::summary::{LatencyStats, latency_stats_line};
+use crate::args::{OutputFormat, TesterArgs, tag_map};
 use crate::charts;
 use crate::distributed::summary::{
     Percentiles, SummaryPercentiles, compute_summary_stats, print_summary,
@@ -16,6 +18,10 @@ use crate::sinks::config::SinkStats;
 use crate::sinks::writers::write_sinks;
 use crate::ui::{model::UiData, render::setup_render_ui};
 
+use super::breakdown::{
+    AgentAssignment, agent_breakdown_lines, agent_summary_lines, compute_agent_breakdown,
+    compute_agent_summaries,
+};
 use super::shared::{AgentSnapshot, aggregate_snapshots, record_aggregated_sample, update_ui};
 
 pub(in crate::distributed::controller) enum OutputEvent {
@@ -32,10 +38,14 @@ pub(in crate::distributed::controller) struct DistributedOutputState {
     shutdown_tx: Option<ShutdownSender>,
     ui_latency_window: VecDeque<(u64, u64)>,
     ui_rps_window: VecDeque<(u64, u64)>,
+    assignments: Vec<AgentAssignment>,
+    run_id: String,
 }
 
 pub(in crate::distributed::controller) fn setup_output_state(
     args: &TesterArgs,
+    assignments: Vec<AgentAssignment>,
+    run_id: String,
 ) -> DistributedOutputState {
     let streaming_enabled = args.distributed_stream_summaries;
     let ui_enabled = streaming_enabled && !args.no_ui && std::io::stdout().is_terminal();
@@ -48,7 +58,7 @@ pub(in crate::distributed::controller) fn setup_output_state(
             no_color: args.no_color,
             ..UiData::default()
         });
-        let _ui_handle = setup_render_ui(&shutdown_tx, &ui_tx);
+        let _ui_handle = setup_render_ui(&shutdown_tx, &ui_tx, false);
         (Some(ui_tx), Some(shutdown_tx))
     } else {
         (None, None)
@@ -63,6 +73,8 @@ pub(in crate::distributed::controller) fn setup_output_state(
         shutdown_tx,
         ui_latency_window: VecDeque::new(),
         ui_rps_window: VecDeque::new(),
+        assignments,
+        run_id,
     }
 }
 
@@ -93,7 +105,7 @@ pub(in crate::distributed::controller) async fn handle_output_event(
         }
         OutputEvent::SinkTick => {
             if state.sink_updates_enabled && state.sink_dirty {
-                if let Err(err) = write_streaming_sinks(args, agent_states).await {
+                if let Err(err) = write_streaming_sinks(args, agent_states, &state.run_id).await {
                     runtime_errors.push(err.to_string());
                 } else {
                     state.sink_dirty = false;
@@ -103,32 +115,36 @@ pub(in crate::distributed::controller) async fn handle_output_event(
     }
 }
 
+/// Prints the aggregated report and writes the final outputs.
+///
+/// Returns the `--assert-*` thresholds the aggregated results missed.
 pub(in crate::distributed::controller) async fn finalize_output(
     args: &TesterArgs,
     state: &mut DistributedOutputState,
     agent_states: &HashMap<String, AgentSnapshot>,
     runtime_errors: &mut Vec<String>,
-) {
+) -> Vec<String> {
     if agent_states.is_empty() {
         runtime_errors.push("No successful agent reports received.".to_owned());
         send_shutdown_signal(state);
-        return;
+        return Vec::new();
     }
 
     let Ok((summary, merged_hist, success_hist)) = aggregate_snapshots(agent_states) else {
         runtime_errors.push("Failed to aggregate agent summaries.".to_owned());
         send_shutdown_signal(state);
-        return;
+        return Vec::new();
     };
 
-    let (p50, p90, p99) = merged_hist.percentiles();
+    let (p50, p90, p99, p999, p9999) = merged_hist.percentiles_extended();
     let (success_p50, success_p90, success_p99) = success_hist.percentiles();
     let stats = compute_summary_stats(&summary);
     let mut charts_output_path: Option<String> = None;
     if state.charts_enabled {
         match write_aggregated_charts(&state.aggregated_samples, args).await {
             Ok(path) => charts_output_path = path,
-            Err(err) => runtime_errors.push(err.to_string()),
+            Err(err) if args.strict_charts => runtime_errors.push(err.to_string()),
+            Err(err) => tracing::error!("Failed to build charts: {}", err),
         }
     }
 
@@ -141,7 +157,27 @@ pub(in crate::distributed::controller) async fn finalize_output(
         },
     };
 
+    if args.per_agent_summary {
+        for line in agent_summary_lines(&compute_agent_summaries(agent_states)) {
+            println!("{}", line);
+        }
+    }
+
     print_summary(&summary, percentiles, args, charts_output_path.as_deref());
+    if args.stats_extended {
+        println!(
+            "{}",
+            latency_stats_line(LatencyStats::from_histogram(&merged_hist))
+        );
+    }
+
+    if args.agent_breakdown {
+        for line in
+            agent_breakdown_lines(&compute_agent_breakdown(&state.assignments, agent_states))
+        {
+            println!("{}", line);
+        }
+    }
 
     if let Some(sinks) = args.sinks.as_ref() {
         let sink_stats = SinkStats {
@@ -156,16 +192,48 @@ pub(in crate::distributed::controller) async fn finalize_output(
             p50_latency_ms: p50,
             p90_latency_ms: p90,
             p99_latency_ms: p99,
+            p999_latency_ms: p999,
+            p9999_latency_ms: p9999,
             success_rate_x100: stats.success_rate_x100,
             avg_rps_x100: stats.avg_rps_x100,
             avg_rpm_x100: stats.avg_rpm_x100,
         };
-        if let Err(err) = write_sinks(sinks, &sink_stats).await {
+        if let Err(err) = write_sinks(
+            sinks,
+            &sink_stats,
+            &state.run_id,
+            args.protocol.as_str(),
+            &tag_map(&args.tags),
+        )
+        .await
+        {
             runtime_errors.push(format!("Sinks: {}", err));
         }
     }
 
+    let assertion_results = assertions::evaluate(
+        args,
+        &summary,
+        &crate::app::summary::compute_summary_stats(&summary),
+        p99,
+    );
+    if let Some(path) = args.output.as_deref()
+        && args.output_format == Some(OutputFormat::Junit)
+        && let Err(err) = tokio::fs::write(
+            path,
+            assertions::junit_xml(&assertion_results, summary.duration),
+        )
+        .await
+    {
+        runtime_errors.push(format!("Failed to write JUnit report: {}", err));
+    }
+
     send_shutdown_signal(state);
+    assertion_results
+        .iter()
+        .filter(|result| !result.passed)
+        .map(assertions::AssertionResult::message)
+        .collect()
 }
 
 fn send_shutdown_signal(state: &DistributedOutputState) {
@@ -177,12 +245,13 @@ fn send_shutdown_signal(state: &DistributedOutputState) {
 async fn write_streaming_sinks(
     args: &TesterArgs,
     agent_states: &HashMap<String, AgentSnapshot>,
+    run_id: &str,
 ) -> AppResult<()> {
     if agent_states.is_empty() {
         return Ok(());
     }
     let (summary, merged_hist, _success_hist) = aggregate_snapshots(agent_states)?;
-    let (p50, p90, p99) = merged_hist.percentiles();
+    let (p50, p90, p99, p999, p9999) = merged_hist.percentiles_extended();
     let stats = compute_summary_stats(&summary);
 
     if let Some(sinks) = args.sinks.as_ref() {
@@ -198,11 +267,20 @@ async fn write_streaming_sinks(
             p50_latency_ms: p50,
             p90_latency_ms: p90,
             p99_latency_ms: p99,
+            p999_latency_ms: p999,
+            p9999_latency_ms: p9999,
             success_rate_x100: stats.success_rate_x100,
             avg_rps_x100: stats.avg_rps_x100,
             avg_rpm_x100: stats.avg_rpm_x100,
         };
-        write_sinks(sinks, &sink_stats).await?;
+        write_sinks(
+            sinks,
+            &sink_stats,
+            run_id,
+            args.protocol.as_str(),
+            &tag_map(&args.tags),
+        )
+        .await?;
     }
     Ok(())
 }
@@ -211,7 +289,7 @@ async fn write_aggregated_charts(
     samples: &[AggregatedMetricSample],
     args: &TesterArgs,
 ) -> AppResult<Option<String>> {
-    if args.no_charts || samples.len() < 2 {
+    if args.no_charts || args.no_final_charts || samples.len() < 2 {
         return Ok(None);
     }
     charts::plot_aggregated_metrics(samples, args).await
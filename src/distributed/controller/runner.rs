@@ -1,25 +1,96 @@
 use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use tracing::info;
 
 use crate::args::{ControllerMode, TesterArgs};
 use crate::config::types::ScenarioConfig;
 use crate::error::AppResult;
 
+use super::super::agent::{AgentLocalRunPort, run_agent};
 use super::{auto, manual};
 
+/// Agent id the controller's own agent registers with.
+const LOCAL_AGENT_ID: &str = "controller-local";
+
 /// Runs the distributed controller in auto or manual mode.
 ///
+/// With `--controller-also-runs`, a local agent joins the controller over
+/// loopback and generates load through `local_run_port`, so its results are
+/// aggregated like any other agent's.
+///
 /// # Errors
 ///
 /// Returns an error if the controller cannot bind, validate configuration,
 /// or complete the distributed run.
-pub async fn run_controller(
+pub async fn run_controller<TLocalRunPort>(
     args: &TesterArgs,
     scenarios: Option<BTreeMap<String, ScenarioConfig>>,
-) -> AppResult<()> {
-    match args.controller_mode {
-        ControllerMode::Auto => auto::run_controller_auto(args).await,
-        ControllerMode::Manual => {
-            manual::run_controller_manual(args, scenarios.unwrap_or_default()).await
+    local_run_port: &TLocalRunPort,
+) -> AppResult<()>
+where
+    TLocalRunPort: AgentLocalRunPort + Sync,
+{
+    crate::app::echo_run_config(args);
+    let controller = async {
+        match args.controller_mode {
+            ControllerMode::Auto => auto::run_controller_auto(args).await,
+            ControllerMode::Manual => {
+                manual::run_controller_manual(args, scenarios.unwrap_or_default()).await
+            }
         }
+    };
+    if !args.controller_also_runs {
+        return controller.await;
+    }
+
+    let local_args = build_local_agent_args(args);
+    info!(
+        "Controller also runs a local agent joining {}",
+        local_args.agent_join.as_deref().unwrap_or_default()
+    );
+    // The local agent is in standby, so it retries until the listener is up and
+    // rejoins between manual runs; it only returns early on a setup error.
+    tokio::select! {
+        result = controller => result,
+        result = run_agent(local_args, local_run_port) => result,
+    }
+}
+
+fn build_local_agent_args(args: &TesterArgs) -> TesterArgs {
+    let mut local_args = args.clone();
+    local_args.agent_join = args.controller_listen.as_deref().map(loopback_join_addr);
+    local_args.controller_listen = None;
+    local_args.control_listen = None;
+    local_args.agent_id = Some(LOCAL_AGENT_ID.to_owned());
+    local_args.agent_standby = true;
+    local_args.agent_health_listen = None;
+    local_args
+}
+
+/// Turns a wildcard listen address into one the local agent can connect to.
+fn loopback_join_addr(listen: &str) -> String {
+    match listen.parse::<SocketAddr>() {
+        Ok(addr) if addr.ip().is_unspecified() => {
+            let loopback = match addr.ip() {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            };
+            SocketAddr::new(loopback, addr.port()).to_string()
+        }
+        Ok(_) | Err(_) => listen.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::loopback_join_addr;
+
+    #[test]
+    fn loopback_join_addr_rewrites_wildcards_only() {
+        assert_eq!(loopback_join_addr("0.0.0.0:9009"), "127.0.0.1:9009");
+        assert_eq!(loopback_join_addr("[::]:9009"), "[::1]:9009");
+        assert_eq!(loopback_join_addr("10.0.0.5:9009"), "10.0.0.5:9009");
+        assert_eq!(loopback_join_addr("controller:9009"), "controller:9009");
     }
 }
@@ -3,7 +3,9 @@ mod events;
 mod timing;
 mod ui;
 
-pub(super) use aggregation::{aggregate_snapshots, record_aggregated_sample};
+pub(super) use aggregation::{
+    aggregate_snapshots, build_aggregated_sample, record_aggregated_sample,
+};
 pub(super) use events::{AgentEvent, AgentSnapshot, event_agent_id, handle_agent_event};
 pub(super) use timing::{
     DEFAULT_START_AFTER_MS, REPORT_GRACE_SECS, resolve_agent_wait_timeout,
@@ -6,6 +6,10 @@ use crate::metrics::{AggregatedMetricSample, LatencyHistogram};
 use super::super::super::summary::merge_summaries;
 use super::events::AgentSnapshot;
 
+/// Merges every agent's summary and histograms.
+///
+/// Percentiles (including p99.9/p99.99) must be read from the merged
+/// histograms; per-agent percentiles cannot be combined.
 pub(in crate::distributed::controller) fn aggregate_snapshots(
     agent_states: &HashMap<String, AgentSnapshot>,
 ) -> AppResult<(
@@ -28,16 +32,14 @@ pub(in crate::distributed::controller) fn aggregate_snapshots(
     ))
 }
 
-pub(in crate::distributed::controller) fn record_aggregated_sample(
-    samples: &mut Vec<AggregatedMetricSample>,
+/// Builds the current aggregated sample; `None` if the histograms cannot be merged.
+pub(in crate::distributed::controller) fn build_aggregated_sample(
     agent_states: &HashMap<String, AgentSnapshot>,
-) {
-    let Ok((summary, merged_hist, _success_hist)) = aggregate_snapshots(agent_states) else {
-        return;
-    };
+) -> Option<AggregatedMetricSample> {
+    let (summary, merged_hist, _success_hist) = aggregate_snapshots(agent_states).ok()?;
     let (p50, p90, p99) = merged_hist.percentiles();
     let elapsed_ms = u64::try_from(summary.duration.as_millis()).unwrap_or(u64::MAX);
-    let sample = AggregatedMetricSample {
+    Some(AggregatedMetricSample {
         elapsed_ms,
         total_requests: summary.total_requests,
         successful_requests: summary.successful_requests,
@@ -46,6 +48,15 @@ pub(in crate::distributed::controller) fn record_aggregated_sample(
         p50_latency_ms: p50,
         p90_latency_ms: p90,
         p99_latency_ms: p99,
+    })
+}
+
+pub(in crate::distributed::controller) fn record_aggregated_sample(
+    samples: &mut Vec<AggregatedMetricSample>,
+    agent_states: &HashMap<String, AgentSnapshot>,
+) {
+    let Some(sample) = build_aggregated_sample(agent_states) else {
+        return;
     };
 
     if let Some(last) = samples.last()
@@ -20,7 +20,7 @@ pub(in crate::distributed::controller) fn update_ui(
     let Ok((summary, merged_hist, success_hist)) = aggregate_snapshots(agent_states) else {
         return;
     };
-    let (p50, p90, p99) = merged_hist.percentiles();
+    let (p50, p90, p99, p999, p9999) = merged_hist.percentiles_extended();
     let (p50_ok, p90_ok, p99_ok) = success_hist.percentiles();
     let stats = compute_summary_stats(&summary);
     let elapsed_ms = summary.duration.as_millis().min(u128::from(u64::MAX)) as u64;
@@ -58,9 +58,12 @@ pub(in crate::distributed::controller) fn update_ui(
         rps_series,
         status_counts: None,
         data_usage: None,
+        error_samples: Vec::new(),
         p50,
         p90,
         p99,
+        p999,
+        p9999,
         p50_ok,
         p90_ok,
         p99_ok,
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::error::{AppError, AppResult};
 
@@ -22,6 +22,14 @@ fn aggregate_snapshots_merges_summary() -> AppResult<()> {
         min_latency_ms: 10,
         max_latency_ms: 50,
         latency_sum_ms: 1000,
+        http_versions: BTreeMap::from([("HTTP/2".to_owned(), 8), ("HTTP/1.1".to_owned(), 2)]),
+        error_reasons: BTreeMap::from([("timeout".to_owned(), 1)]),
+        check_failures: crate::metrics::CheckFailures {
+            request_id_mismatches: 2,
+            response_size_violations: 2,
+            too_few_messages: 1,
+            assertion_failures: 3,
+        },
     };
     let summary_b = WireSummary {
         duration_ms: 1500,
@@ -37,6 +45,17 @@ fn aggregate_snapshots_merges_summary() -> AppResult<()> {
         min_latency_ms: 5,
         max_latency_ms: 40,
         latency_sum_ms: 600,
+        http_versions: BTreeMap::from([("HTTP/2".to_owned(), 20)]),
+        error_reasons: BTreeMap::from([
+            ("timeout".to_owned(), 2),
+            ("connection_refused".to_owned(), 1),
+        ]),
+        check_failures: crate::metrics::CheckFailures {
+            request_id_mismatches: 1,
+            response_size_violations: 1,
+            too_few_messages: 0,
+            assertion_failures: 1,
+        },
     };
 
     let hist_a = build_hist(&[10, 20])?;
@@ -69,6 +88,38 @@ fn aggregate_snapshots_merges_summary() -> AppResult<()> {
             summary.total_requests
         )));
     }
+    let versions: Vec<(&str, u64)> = summary
+        .http_versions
+        .iter()
+        .map(|(version, count)| (*version, *count))
+        .collect();
+    if versions != [("HTTP/1.1", 2), ("HTTP/2", 28)] {
+        return Err(AppError::distributed(format!(
+            "Unexpected http_versions: {:?}",
+            versions
+        )));
+    }
+    let reasons: Vec<(&str, u64)> = summary
+        .error_reasons
+        .iter()
+        .map(|(reason, count)| (*reason, *count))
+        .collect();
+    if reasons != [("connection_refused", 1), ("timeout", 3)] {
+        return Err(AppError::distributed(format!(
+            "Unexpected error_reasons: {:?}",
+            reasons
+        )));
+    }
+    if summary.check_failures.request_id_mismatches != 3
+        || summary.check_failures.response_size_violations != 3
+        || summary.check_failures.too_few_messages != 1
+        || summary.check_failures.assertion_failures != 4
+    {
+        return Err(AppError::distributed(format!(
+            "Unexpected check_failures: {:?}",
+            summary.check_failures
+        )));
+    }
     if summary.successful_requests != 28 {
         return Err(AppError::distributed(format!(
             "Unexpected successful_requests: {}",
@@ -136,6 +187,9 @@ fn record_aggregated_sample_deduplicates_identical_snapshots() -> AppResult<()>
         min_latency_ms: 10,
         max_latency_ms: 20,
         latency_sum_ms: 180,
+        http_versions: BTreeMap::new(),
+        error_reasons: BTreeMap::new(),
+        check_failures: crate::metrics::CheckFailures::default(),
     };
     let hist = build_hist(&[10, 20])?;
     let success_hist = build_hist(&[10, 20])?;
@@ -173,6 +227,9 @@ fn record_aggregated_sample_deduplicates_identical_snapshots() -> AppResult<()>
         min_latency_ms: 10,
         max_latency_ms: 25,
         latency_sum_ms: 250,
+        http_versions: BTreeMap::new(),
+        error_reasons: BTreeMap::new(),
+        check_failures: crate::metrics::CheckFailures::default(),
     };
     agent_states.insert(
         "a".to_owned(),
@@ -210,6 +267,9 @@ fn aggregate_snapshots_handles_many_agents() -> AppResult<()> {
             min_latency_ms: 10 + idx,
             max_latency_ms: 10 + idx,
             latency_sum_ms: u128::from(10 + idx),
+            http_versions: BTreeMap::new(),
+            error_reasons: BTreeMap::new(),
+            check_failures: crate::metrics::CheckFailures::default(),
         };
         agent_states.insert(
             format!("agent-{}", idx),
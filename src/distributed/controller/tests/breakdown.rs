@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use crate::error::{AppError, AppResult};
+
+use super::super::breakdown::{
+    AgentAssignment, agent_breakdown_lines, agent_summary_lines, compute_agent_breakdown,
+    compute_agent_summaries,
+};
+use super::{AgentSnapshot, WireSummary, build_hist};
+
+fn snapshot(duration_ms: u64, total_requests: u64) -> AppResult<AgentSnapshot> {
+    Ok(AgentSnapshot {
+        summary: WireSummary {
+            duration_ms,
+            total_requests,
+            successful_requests: total_requests,
+            error_requests: 0,
+            timeout_requests: 0,
+            transport_errors: 0,
+            non_expected_status: 0,
+            success_min_latency_ms: 1,
+            success_max_latency_ms: 1,
+            success_latency_sum_ms: u128::from(total_requests),
+            min_latency_ms: 1,
+            max_latency_ms: 1,
+            latency_sum_ms: u128::from(total_requests),
+            http_versions: std::collections::BTreeMap::new(),
+            error_reasons: std::collections::BTreeMap::new(),
+            check_failures: crate::metrics::CheckFailures::default(),
+        },
+        histogram: build_hist(&[1])?,
+        success_histogram: build_hist(&[1])?,
+    })
+}
+
+fn assignment(agent_id: &str, weight: u64, rate_limit: Option<u64>) -> AgentAssignment {
+    AgentAssignment {
+        agent_id: agent_id.to_owned(),
+        weight,
+        rate_limit,
+    }
+}
+
+#[test]
+fn agent_breakdown_flags_agent_below_assigned_rate() -> AppResult<()> {
+    let mut agent_states = HashMap::new();
+    agent_states.insert("fast".to_owned(), snapshot(10_000, 1_000)?);
+    agent_states.insert("slow".to_owned(), snapshot(10_000, 600)?);
+    let assignments = [
+        assignment("fast", 1, Some(100)),
+        assignment("slow", 1, Some(100)),
+    ];
+
+    let rows = compute_agent_breakdown(&assignments, &agent_states);
+    let flags: Vec<(&str, u128, bool)> = rows
+        .iter()
+        .map(|row| {
+            (
+                row.agent_id.as_str(),
+                row.achieved_rps_x100,
+                row.underperforming,
+            )
+        })
+        .collect();
+    if flags != [("fast", 10_000, false), ("slow", 6_000, true)] {
+        return Err(AppError::distributed(format!(
+            "Unexpected breakdown: {:?}",
+            flags
+        )));
+    }
+
+    let lines = agent_breakdown_lines(&rows);
+    let slow_line = lines.get(2).map(String::as_str).unwrap_or("");
+    if !slow_line.contains("achieved=60.00 rps  assigned=100.00 rps  (60%)  UNDER") {
+        return Err(AppError::distributed(format!(
+            "Unexpected breakdown line: {}",
+            slow_line
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn agent_breakdown_uses_weighted_share_without_rate_limit() -> AppResult<()> {
+    let mut agent_states = HashMap::new();
+    agent_states.insert("heavy".to_owned(), snapshot(1_000, 300)?);
+    agent_states.insert("light".to_owned(), snapshot(1_000, 100)?);
+    let assignments = [
+        assignment("heavy", 3, None),
+        assignment("light", 1, None),
+        assignment("missing", 1, None),
+    ];
+
+    let rows = compute_agent_breakdown(&assignments, &agent_states);
+    let expected: Vec<(u128, bool, bool)> = rows
+        .iter()
+        .map(|row| (row.expected_rps_x100, row.reported, row.underperforming))
+        .collect();
+    if expected
+        != [
+            (24_000, true, false),
+            (8_000, true, false),
+            (8_000, false, true),
+        ]
+    {
+        return Err(AppError::distributed(format!(
+            "Unexpected weighted breakdown: {:?}",
+            expected
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn per_agent_summary_sorts_by_request_count() -> AppResult<()> {
+    let mut agent_states = HashMap::new();
+    let mut degraded = snapshot(1_000, 200)?;
+    degraded.summary.successful_requests = 190;
+    degraded.histogram = build_hist(&[10, 10, 10, 250])?;
+    agent_states.insert("agent-b".to_owned(), degraded);
+    agent_states.insert("agent-a".to_owned(), snapshot(1_000, 500)?);
+    agent_states.insert("agent-c".to_owned(), snapshot(1_000, 200)?);
+
+    let rows = compute_agent_summaries(&agent_states);
+    let order: Vec<(&str, u128)> = rows
+        .iter()
+        .map(|row| (row.agent_id.as_str(), row.success_rate_x100))
+        .collect();
+    if order != [("agent-a", 10_000), ("agent-b", 9_500), ("agent-c", 10_000)] {
+        return Err(AppError::distributed(format!(
+            "Unexpected per-agent order: {:?}",
+            order
+        )));
+    }
+
+    let lines = agent_summary_lines(&rows);
+    let degraded_line = lines.get(2).map(String::as_str).unwrap_or("");
+    if lines.first().map(String::as_str) != Some("Per-Agent Summary:")
+        || degraded_line != "  agent-b  requests=200  success=95.00%  p99=250ms"
+    {
+        return Err(AppError::distributed(format!(
+            "Unexpected per-agent lines: {:?}",
+            lines
+        )));
+    }
+    Ok(())
+}
@@ -22,6 +22,9 @@ fn summary_fixture() -> WireSummary {
         min_latency_ms: 10,
         max_latency_ms: 30,
         latency_sum_ms: 200,
+        http_versions: std::collections::BTreeMap::new(),
+        error_reasons: std::collections::BTreeMap::new(),
+        check_failures: crate::metrics::CheckFailures::default(),
     }
 }
 
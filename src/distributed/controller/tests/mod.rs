@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crate::args::TesterArgs;
+use crate::args::{ExpectedStatus, TesterArgs};
 use crate::error::AppResult;
 use crate::metrics::LatencyHistogram;
 
@@ -10,6 +10,7 @@ use super::shared::{
 };
 
 mod aggregation;
+mod breakdown;
 mod events;
 mod ui;
 
@@ -33,14 +34,27 @@ fn base_args() -> AppResult<TesterArgs> {
         replay_snapshot_end: None,
         replay_snapshot_out: None,
         replay_snapshot_format: "json".to_owned(),
+        replay_video: None,
+        replay_export_chart_data: None,
+        replay_export_charts: false,
         method: crate::args::HttpMethod::Get,
         protocol: crate::args::Protocol::Http,
+        grpc_mode: None,
+        ws_messages_per_conn: None,
+        ws_message_interval_ms: None,
+        mqtt_mode: crate::args::MqttMode::Publish,
+        mqtt_topic: None,
+        sse_events_per_conn: None,
+        sse_conn_duration: None,
+        sse_resume: false,
+        min_events: None,
         load_mode: crate::args::LoadMode::Arrival,
         url: Some("http://localhost".to_owned()),
         urls_from_file: false,
         rand_regex_url: false,
         max_repeat: crate::args::PositiveUsize::try_from(4)?,
         dump_urls: None,
+        dry_run: false,
         headers: vec![],
         accept_header: None,
         content_type: None,
@@ -48,32 +62,59 @@ fn base_args() -> AppResult<TesterArgs> {
         authorized: false,
         data: String::new(),
         form: vec![],
+        form_file: vec![],
         basic_auth: None,
+        basic_auth_file: None,
+        bearer_token: None,
+        bearer_token_file: None,
+        digest_auth: None,
+        oauth2_token_url: None,
+        oauth2_client_id: None,
+        oauth2_client_secret: None,
+        oauth2_scope: None,
         aws_session: None,
         aws_sigv4: None,
         data_file: None,
         data_lines: None,
+        data_dir: None,
+        data_dir_max_bytes: None,
+        seed: None,
+        json_template: false,
+        literal_placeholders: false,
         target_duration: crate::args::PositiveU64::try_from(1)?,
         wait_ongoing_requests_after_deadline: false,
+        align_to_second: false,
+        arrival_distribution: None,
         requests: None,
-        expected_status_code: 200,
+        expected_status_code: ExpectedStatus::single(200),
         request_timeout: Duration::from_secs(2),
         redirect_limit: 10,
         disable_keepalive: false,
+        requests_per_connection: None,
         disable_compression: false,
+        cookies: false,
+        session_cookie: None,
+        backend_header: "X-Backend".to_owned(),
         pool_max_idle_per_host: None,
         pool_idle_timeout_ms: None,
         http_version: None,
         connect_timeout: Duration::from_secs(5),
         charts_path: "./charts".to_owned(),
         no_charts: true,
+        no_final_charts: false,
         charts_latency_bucket_ms: crate::args::PositiveU64::try_from(100)?,
+        chart_width: 1_600,
+        chart_height: 600,
+        chart_dpi: 96,
+        usl_chart: false,
         verbose: false,
         config: None,
+        config_profile: None,
         tmp_path: "./tmp".to_owned(),
         load_profile: None,
         controller_listen: None,
         controller_mode: crate::args::ControllerMode::Auto,
+        controller_also_runs: false,
         control_listen: None,
         control_auth_token: None,
         agent_join: None,
@@ -86,20 +127,48 @@ fn base_args() -> AppResult<TesterArgs> {
         agent_reconnect_ms: crate::args::PositiveU64::try_from(1000)?,
         agent_heartbeat_interval_ms: crate::args::PositiveU64::try_from(1000)?,
         agent_heartbeat_timeout_ms: crate::args::PositiveU64::try_from(3000)?,
+        agent_health_listen: None,
         keep_tmp: false,
         warmup: None,
+        warmup_requests_file: None,
+        echo_config_on_start: false,
+        echo_config_format: crate::args::EchoConfigFormat::Text,
         output: None,
         output_format: None,
         time_unit: None,
         export_csv: None,
         export_json: None,
         export_jsonl: None,
+        export_histogram: None,
         db_url: None,
+        checkpoint_out: None,
+        checkpoint_interval_secs: None,
+        stream_samples: None,
+        resume: false,
         log_shards: crate::args::PositiveUsize::try_from(1)?,
         no_ui: true,
         no_splash: true,
+        hold_ui: false,
+        stop_file: None,
         ui_window_ms: crate::args::PositiveU64::try_from(10_000)?,
+        baseline: None,
+        baseline_label: None,
         summary: false,
+        summary_template: None,
+        summary_out: None,
+        history_file: "./history.jsonl".to_owned(),
+        no_history: true,
+        notify_webhook: None,
+        notify_on: crate::args::NotifyOn::Always,
+        protocol_version_report: false,
+        connection_timing: false,
+        strict_charts: false,
+        per_status_latency: false,
+        slowest: None,
+        summary_percentile_table: false,
+        stats_extended: false,
+        compat: None,
+        summary_format: None,
         show_selections: false,
         tls_min: None,
         tls_max: None,
@@ -121,11 +190,39 @@ fn base_args() -> AppResult<TesterArgs> {
         rate_limit: None,
         burst_delay: None,
         burst_rate: crate::args::PositiveUsize::try_from(1)?,
+        find_max_rate: false,
+        target_p99_ms: None,
         latency_correction: false,
+        max_bandwidth: None,
+        sim_downlink: None,
+        sim_latency: None,
+        connect_rate: None,
+        max_bytes: None,
+        abort_on_errors: None,
+        abort_on_error_rate: None,
+        abort_min_requests: crate::args::PositiveU64::try_from(100)?,
+        verify_request_id_echo: false,
+        request_id_header: "X-Request-Id".to_owned(),
+        deadline_header: None,
+        deadline_format: crate::args::DeadlineFormat::RelativeMs,
+        correlation_header: None,
+        correlation_log: None,
+        rate_schedule: None,
+        assert_response_bytes_below: None,
+        assert_response_bytes_above: None,
+        assert_body_contains: None,
+        assert_body_regex: None,
+        assert_json: None,
+        assert_success_rate: None,
+        assert_p99_ms: None,
+        assert_max_errors: None,
+        diagnose: false,
+        diagnose_strict: false,
         connect_to: vec![],
         host_header: None,
         ipv6_only: false,
         ipv4_only: false,
+        doh_url: None,
         no_pre_lookup: false,
         no_color: false,
         ui_fps: 16,
@@ -137,6 +234,8 @@ fn base_args() -> AppResult<TesterArgs> {
         alloc_profiler_ms: None,
         alloc_profiler_dump_ms: None,
         alloc_profiler_dump_path: "./alloc-prof".to_owned(),
+        alloc_profiler_spike_ms: None,
+        alloc_profiler_spike_inflight: None,
         scenario: None,
         script: None,
         plugin: vec![],
@@ -144,8 +243,12 @@ fn base_args() -> AppResult<TesterArgs> {
         uninstall_service: false,
         service_name: None,
         sinks: None,
+        tags: Vec::new(),
         distributed_silent: false,
+        run_id: None,
         distributed_stream_summaries: false,
+        agent_breakdown: false,
+        per_agent_summary: false,
         distributed_stream_interval_ms: None,
     })
 }
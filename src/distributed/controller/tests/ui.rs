@@ -26,6 +26,9 @@ fn update_ui_emits_aggregated_stats() -> AppResult<()> {
         min_latency_ms: 10,
         max_latency_ms: 50,
         latency_sum_ms: 1000,
+        http_versions: std::collections::BTreeMap::new(),
+        error_reasons: std::collections::BTreeMap::new(),
+        check_failures: crate::metrics::CheckFailures::default(),
     };
     let hist = build_hist(&[10, 20, 30])?;
     let success_hist = build_hist(&[10, 20, 30])?;
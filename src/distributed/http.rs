@@ -1,3 +1,6 @@
+//! Minimal JSON-over-HTTP/1.1 handling shared by the controller control plane
+//! and the agent health endpoint.
+
 use std::collections::HashMap;
 
 use serde::Serialize;
@@ -6,7 +9,20 @@ use tokio::net::TcpStream;
 
 use crate::error::{AppError, AppResult, DistributedError};
 
-use super::control::{ControlError, ControlResponse};
+#[derive(Debug)]
+pub(super) struct ControlError {
+    pub(super) status: u16,
+    pub(super) message: String,
+}
+
+impl ControlError {
+    pub(super) fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
 
 pub(super) struct HttpRequest {
     pub(super) method: String,
@@ -111,6 +127,7 @@ fn find_header_end(buffer: &[u8]) -> Option<usize> {
 const fn status_text(status: u16) -> &'static str {
     match status {
         200 => "OK",
+        204 => "No Content",
         400 => "Bad Request",
         401 => "Unauthorized",
         404 => "Not Found",
@@ -122,10 +139,10 @@ const fn status_text(status: u16) -> &'static str {
     }
 }
 
-pub(super) async fn write_json_response(
+pub(super) async fn write_json_response<T: Serialize>(
     socket: &mut TcpStream,
     status: u16,
-    response: &ControlResponse,
+    response: &T,
 ) -> AppResult<()> {
     let body = serde_json::to_vec(response).map_err(|err| {
         AppError::distributed(DistributedError::Serialize {
@@ -136,6 +153,10 @@ pub(super) async fn write_json_response(
     write_response(socket, status, &body).await
 }
 
+pub(super) async fn write_empty_response(socket: &mut TcpStream, status: u16) -> AppResult<()> {
+    write_response(socket, status, &[]).await
+}
+
 pub(super) async fn write_error_response(
     socket: &mut TcpStream,
     status: u16,
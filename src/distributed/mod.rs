@@ -1,5 +1,6 @@
 mod agent;
 mod controller;
+mod http;
 mod protocol;
 mod summary;
 mod utils;
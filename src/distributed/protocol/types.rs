@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-use crate::args::{HttpMethod, LoadMode, Protocol, TlsVersion};
+use crate::args::{ExpectedStatus, HttpMethod, LoadMode, Protocol, TlsVersion};
+use crate::metrics::CheckFailures;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -84,7 +85,7 @@ pub(in crate::distributed) struct WireArgs {
     pub(in crate::distributed) headers: Vec<(String, String)>,
     pub(in crate::distributed) data: String,
     pub(in crate::distributed) target_duration: u64,
-    pub(in crate::distributed) expected_status_code: u16,
+    pub(in crate::distributed) expected_status_code: ExpectedStatus,
     pub(in crate::distributed) request_timeout_ms: u64,
     pub(in crate::distributed) charts_path: String,
     pub(in crate::distributed) no_charts: bool,
@@ -105,6 +106,8 @@ pub(in crate::distributed) struct WireArgs {
     pub(in crate::distributed) rate_limit: Option<u64>,
     pub(in crate::distributed) load_profile: Option<WireLoadProfile>,
     pub(in crate::distributed) metrics_range: Option<(u64, u64)>,
+    #[serde(default)]
+    pub(in crate::distributed) metrics_ranges: Vec<(u64, u64)>,
     pub(in crate::distributed) metrics_max: usize,
     pub(in crate::distributed) scenario: Option<WireScenario>,
     pub(in crate::distributed) tls_min: Option<TlsVersion>,
@@ -114,6 +117,8 @@ pub(in crate::distributed) struct WireArgs {
     pub(in crate::distributed) http3: bool,
     pub(in crate::distributed) alpn: Vec<String>,
     #[serde(default)]
+    pub(in crate::distributed) tags: BTreeMap<String, String>,
+    #[serde(default)]
     pub(in crate::distributed) stream_summaries: bool,
     #[serde(default)]
     pub(in crate::distributed) stream_interval_ms: Option<u64>,
@@ -137,6 +142,15 @@ pub(in crate::distributed) struct WireLoadProfile {
 pub(in crate::distributed) struct WireLoadStage {
     pub(in crate::distributed) duration_secs: u64,
     pub(in crate::distributed) target_rpm: u64,
+    #[serde(default)]
+    pub(in crate::distributed) sine: Option<WireSineWave>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub(in crate::distributed) struct WireSineWave {
+    pub(in crate::distributed) min_rpm: u64,
+    pub(in crate::distributed) max_rpm: u64,
+    pub(in crate::distributed) period_secs: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -157,7 +171,17 @@ pub(in crate::distributed) struct WireScenarioStep {
     pub(in crate::distributed) assert_status: Option<u16>,
     pub(in crate::distributed) assert_body_contains: Option<String>,
     pub(in crate::distributed) think_time_ms: Option<u64>,
+    #[serde(default)]
+    pub(in crate::distributed) think_time_max_ms: Option<u64>,
     pub(in crate::distributed) vars: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(in crate::distributed) extract: BTreeMap<String, String>,
+    /// Client certificate path, read on the agent.
+    #[serde(default)]
+    pub(in crate::distributed) cert: Option<String>,
+    /// Private key path paired with `cert`, read on the agent.
+    #[serde(default)]
+    pub(in crate::distributed) key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -182,6 +206,15 @@ pub(in crate::distributed) struct WireSummary {
     pub(in crate::distributed) max_latency_ms: u64,
     #[serde(with = "serde_u128")]
     pub(in crate::distributed) latency_sum_ms: u128,
+    /// Responses per negotiated HTTP version, keyed by label (`HTTP/2`).
+    #[serde(default)]
+    pub(in crate::distributed) http_versions: BTreeMap<String, u64>,
+    /// Failed requests per error reason label (`connection_refused`).
+    #[serde(default)]
+    pub(in crate::distributed) error_reasons: BTreeMap<String, u64>,
+    /// Delivered responses that failed a response check.
+    #[serde(default)]
+    pub(in crate::distributed) check_failures: CheckFailures,
 }
 
 mod serde_u128 {
@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+use crate::app::summary::{check_failure_lines, error_reasons_line, http_versions_line, tags_line};
 use crate::args::TesterArgs;
-use crate::metrics::MetricsSummary;
+use crate::metrics::{CheckFailures, ErrorReason, MetricsSummary};
 use crate::system::{chart_status_line, selection_lines};
 
 use super::protocol::WireSummary;
@@ -31,6 +33,9 @@ pub(super) fn merge_summaries(summaries: &[WireSummary]) -> MetricsSummary {
     let mut success_max_latency_ms = 0u64;
     let mut success_latency_sum_ms = 0u128;
     let mut duration_ms = 0u64;
+    let mut http_versions: BTreeMap<&'static str, u64> = BTreeMap::new();
+    let mut error_reasons: BTreeMap<&'static str, u64> = BTreeMap::new();
+    let mut check_failures = CheckFailures::default();
 
     for summary in summaries {
         total_requests = total_requests.saturating_add(summary.total_requests);
@@ -51,6 +56,19 @@ pub(super) fn merge_summaries(summaries: &[WireSummary]) -> MetricsSummary {
         success_latency_sum_ms =
             success_latency_sum_ms.saturating_add(summary.success_latency_sum_ms);
         duration_ms = duration_ms.max(summary.duration_ms);
+        for (version, count) in &summary.http_versions {
+            let total = http_versions
+                .entry(http_version_label(version))
+                .or_insert(0);
+            *total = total.saturating_add(*count);
+        }
+        for (reason, count) in &summary.error_reasons {
+            let total = error_reasons
+                .entry(ErrorReason::from_label(reason).label())
+                .or_insert(0);
+            *total = total.saturating_add(*count);
+        }
+        check_failures.merge(summary.check_failures);
     }
 
     let avg_latency_ms = if total_requests > 0 {
@@ -100,6 +118,21 @@ pub(super) fn merge_summaries(summaries: &[WireSummary]) -> MetricsSummary {
         success_min_latency_ms,
         success_max_latency_ms,
         success_avg_latency_ms,
+        http_versions,
+        error_reasons,
+        check_failures,
+    }
+}
+
+/// Maps an agent-reported version label back to the label the HTTP layer uses.
+fn http_version_label(label: &str) -> &'static str {
+    match label {
+        "HTTP/0.9" => "HTTP/0.9",
+        "HTTP/1.0" => "HTTP/1.0",
+        "HTTP/1.1" => "HTTP/1.1",
+        "HTTP/2" => "HTTP/2",
+        "HTTP/3" => "HTTP/3",
+        _ => "other",
     }
 }
 
@@ -163,6 +196,9 @@ pub(super) fn print_summary(
 ) {
     let stats = compute_summary_stats(summary);
 
+    if let Some(line) = tags_line(&args.tags) {
+        println!("{}", line);
+    }
     println!("Duration: {}s", summary.duration.as_secs());
     println!("Total Requests: {}", summary.total_requests);
     println!(
@@ -175,6 +211,12 @@ pub(super) fn print_summary(
     println!("Timeouts: {}", summary.timeout_requests);
     println!("Transport Errors: {}", summary.transport_errors);
     println!("Non-Expected Status: {}", summary.non_expected_status);
+    if let Some(line) = error_reasons_line(&summary.error_reasons) {
+        println!("{}", line);
+    }
+    for line in check_failure_lines(summary.check_failures) {
+        println!("{}", line);
+    }
     println!("Avg Latency (all): {}ms", summary.avg_latency_ms);
     println!("Avg Latency (ok): {}ms", summary.success_avg_latency_ms);
     println!(
@@ -204,6 +246,10 @@ pub(super) fn print_summary(
         stats.avg_rpm_x100 % PERCENT_DIVISOR
     );
 
+    if args.protocol_version_report {
+        println!("{}", http_versions_line(&summary.http_versions));
+    }
+
     println!("{}", chart_status_line(args, charts_output_path, false));
     if args.show_selections {
         for line in selection_lines(args, charts_output_path) {
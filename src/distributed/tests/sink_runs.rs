@@ -1,7 +1,12 @@
 use std::time::Duration;
 
 use crate::error::{AppError, AppResult};
-use crate::sinks::config::{PrometheusSinkConfig, SinksConfig};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::sinks::config::{PrometheusSinkConfig, PushgatewaySinkConfig, SinksConfig};
 
 use super::{
     allocate_port, base_args, positive_u64, run_async_test, run_distributed,
@@ -38,6 +43,7 @@ fn tcp_streaming_controller_writes_sink() -> AppResult<()> {
             }),
             otel: None,
             influx: None,
+            pushgateway: None,
         });
         controller_args.distributed_stream_summaries = true;
         controller_args.distributed_stream_interval_ms = Some(positive_u64(200)?);
@@ -106,6 +112,7 @@ fn tcp_non_streaming_writes_agent_and_controller_sinks() -> AppResult<()> {
             }),
             otel: None,
             influx: None,
+            pushgateway: None,
         });
 
         let mut agent_args = base_args(url, tmp_path)?;
@@ -117,6 +124,7 @@ fn tcp_non_streaming_writes_agent_and_controller_sinks() -> AppResult<()> {
             }),
             otel: None,
             influx: None,
+            pushgateway: None,
         });
 
         let run_result = tokio::time::timeout(
@@ -150,3 +158,124 @@ fn tcp_non_streaming_writes_agent_and_controller_sinks() -> AppResult<()> {
         Ok(())
     })
 }
+
+#[test]
+fn tcp_agent_pushes_sink_with_run_id() -> AppResult<()> {
+    run_async_test(async {
+        let Some((url, shutdown_tx)) = spawn_http_server_or_skip().await? else {
+            return Ok(());
+        };
+        let gateway = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::distributed(format!("Failed to bind gateway: {}", err)))?;
+        let gateway_addr = gateway.local_addr().map_err(|err| {
+            AppError::distributed(format!("Failed to read gateway addr: {}", err))
+        })?;
+        let pushes: Arc<Mutex<Vec<(String, String)>>> = Arc::new(Mutex::new(Vec::new()));
+        let gateway_pushes = Arc::clone(&pushes);
+        let gateway_task = tokio::spawn(async move {
+            while let Ok((mut socket, _)) = gateway.accept().await {
+                let mut buffer = Vec::new();
+                let mut chunk = [0_u8; 4096];
+                let request = loop {
+                    let Ok(read) = socket.read(&mut chunk).await else {
+                        break None;
+                    };
+                    if read == 0 {
+                        break None;
+                    }
+                    buffer.extend_from_slice(chunk.get(..read).unwrap_or_default());
+                    let text = String::from_utf8_lossy(&buffer).into_owned();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .and_then(|value| value.trim().parse::<usize>().ok())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            let line = head.lines().next().unwrap_or_default().to_owned();
+                            break Some((line, body.to_owned()));
+                        }
+                    }
+                };
+                if let Some(request) = request {
+                    gateway_pushes
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push(request);
+                }
+                drop(
+                    socket
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        )
+                        .await,
+                );
+            }
+        });
+
+        let controller_port = allocate_port()?;
+        let controller_addr = format!("127.0.0.1:{}", controller_port);
+        let tmp_dir = tempfile::tempdir()
+            .map_err(|err| AppError::distributed(format!("Failed to create temp dir: {}", err)))?;
+        let tmp_path = tmp_dir
+            .path()
+            .to_str()
+            .ok_or_else(|| AppError::distributed("Failed to convert tmp path"))?
+            .to_owned();
+
+        let mut controller_args = base_args(url.clone(), tmp_path.clone())?;
+        controller_args.controller_listen = Some(controller_addr.clone());
+
+        let mut agent_args = base_args(url, tmp_path)?;
+        agent_args.agent_join = Some(controller_addr);
+        agent_args.sinks = Some(SinksConfig {
+            update_interval_ms: None,
+            prometheus: None,
+            otel: None,
+            influx: None,
+            pushgateway: Some(PushgatewaySinkConfig {
+                url: format!("http://{}/", gateway_addr),
+                job: "strest".to_owned(),
+                timeout_ms: Some(2_000),
+            }),
+        });
+
+        let run_result = tokio::time::timeout(
+            Duration::from_secs(12),
+            run_distributed(controller_args, agent_args),
+        )
+        .await
+        .map_err(|err| {
+            AppError::distributed(format!("Timed out waiting for distributed run: {}", err))
+        })?;
+        run_result?;
+
+        shutdown_tx
+            .send(true)
+            .map_err(|err| AppError::distributed(format!("Failed to shutdown server: {}", err)))?;
+        gateway_task.abort();
+
+        let pushes = pushes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let Some((request_line, body)) = pushes.last() else {
+            return Err(AppError::distributed("Expected the agent to push metrics"));
+        };
+        let has_run_id = request_line
+            .strip_prefix("PUT /metrics/job/strest/run_id/")
+            .and_then(|rest| rest.split_whitespace().next())
+            .is_some_and(|run_id| !run_id.is_empty());
+        if !has_run_id || !body.contains("strest_requests_total") {
+            return Err(AppError::distributed(format!(
+                "Unexpected push: {} / {}",
+                request_line, body
+            )));
+        }
+        Ok(())
+    })
+}
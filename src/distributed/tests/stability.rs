@@ -1,9 +1,10 @@
 use std::time::Duration;
 
-use crate::error::{AppError, AppResult};
+use crate::args::{ExpectedStatus, OutputFormat};
+use crate::error::{AppError, AppResult, ValidationError};
 
 use super::{
-    allocate_port, base_args, positive_u64, run_async_test, run_distributed,
+    allocate_port, base_args, positive_u64, positive_usize, run_async_test, run_distributed,
     spawn_http_server_or_skip,
 };
 
@@ -124,3 +125,117 @@ fn distributed_auth_token_mismatch_fails_fast() -> AppResult<()> {
         Ok(())
     })
 }
+
+#[test]
+fn controller_also_runs_counts_local_agent_toward_min_agents() -> AppResult<()> {
+    run_async_test(async {
+        let Some((url, shutdown_tx)) = spawn_http_server_or_skip().await? else {
+            return Ok(());
+        };
+        let controller_port = allocate_port()?;
+        let controller_addr = format!("127.0.0.1:{}", controller_port);
+        let tmp_dir = tempfile::tempdir()
+            .map_err(|err| AppError::distributed(format!("Failed to create temp dir: {}", err)))?;
+        let tmp_path = tmp_dir
+            .path()
+            .to_str()
+            .ok_or_else(|| AppError::distributed("Failed to convert tmp path"))?
+            .to_owned();
+
+        // Only one remote agent joins; the run can start only once the
+        // controller's own agent has registered as the second.
+        let mut controller_args = base_args(url.clone(), tmp_path.clone())?;
+        controller_args.controller_listen = Some(controller_addr.clone());
+        controller_args.controller_also_runs = true;
+        controller_args.min_agents = positive_usize(2)?;
+        controller_args.target_duration = positive_u64(1)?;
+
+        let mut agent_args = base_args(url, tmp_path)?;
+        agent_args.agent_join = Some(controller_addr);
+        agent_args.target_duration = positive_u64(1)?;
+
+        let run_result = tokio::time::timeout(
+            Duration::from_secs(15),
+            run_distributed(controller_args, agent_args),
+        )
+        .await
+        .map_err(|err| {
+            AppError::distributed(format!("Timed out waiting for hybrid run: {}", err))
+        })?;
+        run_result?;
+
+        shutdown_tx
+            .send(true)
+            .map_err(|err| AppError::distributed(format!("Failed to shutdown server: {}", err)))?;
+        Ok(())
+    })
+}
+
+#[test]
+fn controller_checks_thresholds_on_aggregated_results() -> AppResult<()> {
+    run_async_test(async {
+        let Some((url, shutdown_tx)) = spawn_http_server_or_skip().await? else {
+            return Ok(());
+        };
+        let controller_port = allocate_port()?;
+        let controller_addr = format!("127.0.0.1:{}", controller_port);
+        let tmp_dir = tempfile::tempdir()
+            .map_err(|err| AppError::distributed(format!("Failed to create temp dir: {}", err)))?;
+        let tmp_path = tmp_dir
+            .path()
+            .to_str()
+            .ok_or_else(|| AppError::distributed("Failed to convert tmp path"))?
+            .to_owned();
+        // Agents remove their tmp directory on cleanup, so the report lives elsewhere.
+        let out_dir = tempfile::tempdir()
+            .map_err(|err| AppError::distributed(format!("Failed to create temp dir: {}", err)))?;
+        let junit_path = out_dir.path().join("junit.xml");
+
+        // The test server answers 200, so every request misses the expected 201.
+        let mut controller_args = base_args(url.clone(), tmp_path.clone())?;
+        controller_args.controller_listen = Some(controller_addr.clone());
+        controller_args.target_duration = positive_u64(1)?;
+        controller_args.expected_status_code = ExpectedStatus::single(201);
+        controller_args.assert_max_errors = Some(0);
+        controller_args.assert_p99_ms = Some(60_000);
+        controller_args.output = Some(junit_path.to_string_lossy().into_owned());
+        controller_args.output_format = Some(OutputFormat::Junit);
+
+        let mut agent_args = base_args(url, tmp_path)?;
+        agent_args.agent_join = Some(controller_addr);
+        agent_args.target_duration = positive_u64(1)?;
+
+        let run_result = tokio::time::timeout(
+            Duration::from_secs(15),
+            run_distributed(controller_args, agent_args),
+        )
+        .await
+        .map_err(|err| {
+            AppError::distributed(format!("Timed out waiting for threshold run: {}", err))
+        })?;
+        shutdown_tx
+            .send(true)
+            .map_err(|err| AppError::distributed(format!("Failed to shutdown server: {}", err)))?;
+
+        match run_result {
+            Err(AppError::Validation(ValidationError::ThresholdsFailed { failures }))
+                if failures.starts_with("error_count: observed ") => {}
+            other => {
+                return Err(AppError::distributed(format!(
+                    "Expected a failed error_count threshold, got {:?}",
+                    other
+                )));
+            }
+        }
+        let junit = tokio::fs::read_to_string(&junit_path)
+            .await
+            .map_err(|err| AppError::distributed(format!("Missing JUnit report: {}", err)))?;
+        if !junit.contains("tests=\"2\" failures=\"1\"") {
+            return Err(AppError::distributed(format!(
+                "Unexpected JUnit report: {}",
+                junit
+            )));
+        }
+        Ok(())
+    })
+}
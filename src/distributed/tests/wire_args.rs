@@ -58,3 +58,78 @@ fn wire_args_deserialize_missing_stream_interval() -> AppResult<()> {
     }
     Ok(())
 }
+
+#[test]
+fn wire_args_carry_tags_over_agent_tags() -> AppResult<()> {
+    let tmp_path = "./tmp".to_owned();
+    let mut args = base_args("http://localhost".to_owned(), tmp_path.clone())?;
+    args.tags = vec![
+        ("env".to_owned(), "staging".to_owned()),
+        ("build".to_owned(), "1234".to_owned()),
+    ];
+
+    let wire = build_wire_args(&args);
+    let mut applied = base_args("http://localhost".to_owned(), tmp_path)?;
+    applied.tags = vec![
+        ("env".to_owned(), "agent".to_owned()),
+        ("zone".to_owned(), "eu".to_owned()),
+    ];
+    apply_wire_args(&mut applied, wire)?;
+
+    let tags = crate::args::tag_map(&applied.tags);
+    let expected = [("build", "1234"), ("env", "staging"), ("zone", "eu")];
+    if !tags
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .eq(expected)
+    {
+        return Err(AppError::distributed(format!(
+            "Unexpected agent tags: {:?}",
+            tags
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn wire_args_carry_step_client_identities() -> AppResult<()> {
+    let tmp_path = "./tmp".to_owned();
+    let mut args = base_args("http://localhost".to_owned(), tmp_path.clone())?;
+    let step = crate::args::ScenarioStep {
+        name: Some("login".to_owned()),
+        method: crate::args::HttpMethod::Get,
+        url: None,
+        path: Some("/login".to_owned()),
+        headers: Vec::new(),
+        body: None,
+        assert_status: None,
+        assert_body_contains: None,
+        think_time: None,
+        vars: std::collections::BTreeMap::new(),
+        extract: std::collections::BTreeMap::new(),
+        cert: Some("/etc/pki/tenant.pem".to_owned()),
+        key: Some("/etc/pki/tenant.key".to_owned()),
+    };
+    args.scenario = Some(crate::args::Scenario {
+        base_url: Some("http://localhost".to_owned()),
+        vars: std::collections::BTreeMap::new(),
+        steps: vec![step],
+    });
+
+    let wire = build_wire_args(&args);
+    let mut applied = base_args("http://localhost".to_owned(), tmp_path)?;
+    apply_wire_args(&mut applied, wire)?;
+
+    let identity = applied
+        .scenario
+        .as_ref()
+        .and_then(|scenario| scenario.steps.first())
+        .map(|first| (first.cert.as_deref(), first.key.as_deref()));
+    if identity != Some((Some("/etc/pki/tenant.pem"), Some("/etc/pki/tenant.key"))) {
+        return Err(AppError::distributed(format!(
+            "Unexpected step identity: {:?}",
+            identity
+        )));
+    }
+    Ok(())
+}
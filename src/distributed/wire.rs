@@ -1,10 +1,16 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
-use crate::args::{LoadProfile, PositiveU64, PositiveUsize, Scenario, ScenarioStep, TesterArgs};
+use crate::args::{
+    ExtractSource, LoadProfile, PositiveU64, PositiveUsize, Scenario, ScenarioStep, TesterArgs,
+    ThinkTime, tag_map,
+};
 use crate::error::{AppError, AppResult, DistributedError, WireValueField};
 use crate::metrics::MetricsRange;
 
-use super::protocol::{WireArgs, WireLoadProfile, WireLoadStage, WireScenario, WireScenarioStep};
+use super::protocol::{
+    WireArgs, WireLoadProfile, WireLoadStage, WireScenario, WireScenarioStep, WireSineWave,
+};
 use super::utils::duration_to_ms;
 
 pub(super) fn build_wire_args(args: &TesterArgs) -> WireArgs {
@@ -35,11 +41,22 @@ pub(super) fn build_wire_args(args: &TesterArgs) -> WireArgs {
         tick_interval: args.tick_interval.get(),
         rate_limit: args.rate_limit.map(u64::from),
         load_profile: args.load_profile.as_ref().map(to_wire_load_profile),
-        metrics_range: args.metrics_range.as_ref().map(|range| {
-            let start = *range.0.start();
-            let end = *range.0.end();
-            (start, end)
-        }),
+        metrics_range: args
+            .metrics_range
+            .as_ref()
+            .and_then(|range| range.0.first())
+            .map(|range| (*range.start(), *range.end())),
+        metrics_ranges: args
+            .metrics_range
+            .as_ref()
+            .map(|range| {
+                range
+                    .0
+                    .iter()
+                    .map(|range| (*range.start(), *range.end()))
+                    .collect()
+            })
+            .unwrap_or_default(),
         metrics_max: 1,
         scenario: args.scenario.as_ref().map(to_wire_scenario),
         tls_min: args.tls_min,
@@ -47,6 +64,7 @@ pub(super) fn build_wire_args(args: &TesterArgs) -> WireArgs {
         http2: args.http2,
         http3: args.http3,
         alpn: args.alpn.clone(),
+        tags: tag_map(&args.tags),
         stream_summaries: args.distributed_stream_summaries,
         stream_interval_ms: args.distributed_stream_interval_ms.map(u64::from),
     }
@@ -113,21 +131,32 @@ pub(super) fn apply_wire_args(args: &mut TesterArgs, wire: WireArgs) -> AppResul
         None => None,
     };
     args.load_profile = wire.load_profile.map(from_wire_load_profile);
-    args.metrics_range = wire
-        .metrics_range
-        .map(|(start, end)| MetricsRange(start..=end));
+    // Older controllers only send the first window in `metrics_range`.
+    args.metrics_range = if wire.metrics_ranges.is_empty() {
+        wire.metrics_range
+            .map(|(start, end)| MetricsRange(vec![start..=end]))
+    } else {
+        Some(MetricsRange(
+            wire.metrics_ranges
+                .into_iter()
+                .map(|(start, end)| start..=end)
+                .collect(),
+        ))
+    };
     args.metrics_max = PositiveUsize::try_from(wire.metrics_max).map_err(|err| {
         AppError::distributed(DistributedError::WireValueTooSmall {
             field: WireValueField::MetricsMax,
             source: err,
         })
     })?;
-    args.scenario = wire.scenario.map(from_wire_scenario);
+    args.scenario = wire.scenario.map(from_wire_scenario).transpose()?;
     args.tls_min = wire.tls_min;
     args.tls_max = wire.tls_max;
     args.http2 = wire.http2;
     args.http3 = wire.http3;
     args.alpn = wire.alpn;
+    // Controller tags come last so they win over an agent's own `--tag`.
+    args.tags.extend(wire.tags);
     args.distributed_stream_summaries = wire.stream_summaries;
     args.distributed_stream_interval_ms = match wire.stream_interval_ms {
         Some(value) => Some(PositiveU64::try_from(value).map_err(|err| {
@@ -150,6 +179,11 @@ pub(super) fn to_wire_load_profile(profile: &LoadProfile) -> WireLoadProfile {
             .map(|stage| WireLoadStage {
                 duration_secs: stage.duration.as_secs(),
                 target_rpm: stage.target_rpm,
+                sine: stage.sine.map(|sine| WireSineWave {
+                    min_rpm: sine.min_rpm,
+                    max_rpm: sine.max_rpm,
+                    period_secs: sine.period.as_secs(),
+                }),
             })
             .collect(),
     }
@@ -164,6 +198,11 @@ pub(super) fn from_wire_load_profile(profile: WireLoadProfile) -> LoadProfile {
             .map(|stage| crate::args::LoadStage {
                 duration: Duration::from_secs(stage.duration_secs.max(1)),
                 target_rpm: stage.target_rpm,
+                sine: stage.sine.map(|sine| crate::args::SineWave {
+                    min_rpm: sine.min_rpm,
+                    max_rpm: sine.max_rpm,
+                    period: Duration::from_secs(sine.period_secs.max(1)),
+                }),
             })
             .collect(),
     }
@@ -185,32 +224,61 @@ pub(super) fn to_wire_scenario(scenario: &Scenario) -> WireScenario {
                 body: step.body.clone(),
                 assert_status: step.assert_status,
                 assert_body_contains: step.assert_body_contains.clone(),
-                think_time_ms: step.think_time.map(duration_to_ms),
+                think_time_ms: step
+                    .think_time
+                    .map(|think_time| duration_to_ms(think_time.min)),
+                think_time_max_ms: step
+                    .think_time
+                    .filter(|think_time| think_time.max > think_time.min)
+                    .map(|think_time| duration_to_ms(think_time.max)),
                 vars: step.vars.clone(),
+                extract: step
+                    .extract
+                    .iter()
+                    .map(|(var, source)| (var.clone(), source.to_string()))
+                    .collect(),
+                cert: step.cert.clone(),
+                key: step.key.clone(),
             })
             .collect(),
     }
 }
 
-pub(super) fn from_wire_scenario(scenario: WireScenario) -> Scenario {
-    Scenario {
+pub(super) fn from_wire_scenario(scenario: WireScenario) -> AppResult<Scenario> {
+    let mut steps = Vec::with_capacity(scenario.steps.len());
+    for step in scenario.steps {
+        let mut extract = BTreeMap::new();
+        for (var, source) in step.extract {
+            let parsed = source.parse::<ExtractSource>().map_err(|err| {
+                AppError::distributed(DistributedError::WireInvalidExtract {
+                    var: var.clone(),
+                    source: err,
+                })
+            })?;
+            extract.insert(var, parsed);
+        }
+        steps.push(ScenarioStep {
+            name: step.name,
+            method: step.method,
+            url: step.url,
+            path: step.path,
+            headers: step.headers,
+            body: step.body,
+            assert_status: step.assert_status,
+            assert_body_contains: step.assert_body_contains,
+            think_time: step.think_time_ms.map(|min_ms| ThinkTime {
+                min: Duration::from_millis(min_ms),
+                max: Duration::from_millis(step.think_time_max_ms.unwrap_or(min_ms).max(min_ms)),
+            }),
+            vars: step.vars,
+            extract,
+            cert: step.cert,
+            key: step.key,
+        });
+    }
+    Ok(Scenario {
         base_url: scenario.base_url,
         vars: scenario.vars,
-        steps: scenario
-            .steps
-            .into_iter()
-            .map(|step| ScenarioStep {
-                name: step.name,
-                method: step.method,
-                url: step.url,
-                path: step.path,
-                headers: step.headers,
-                body: step.body,
-                assert_status: step.assert_status,
-                assert_body_contains: step.assert_body_contains,
-                think_time: step.think_time_ms.map(Duration::from_millis),
-                vars: step.vars,
-            })
-            .collect(),
-    }
+        steps,
+    })
 }
@@ -11,6 +11,7 @@ pub enum ProtocolKind {
     Enet,
     Kcp,
     Raknet,
+    Sse,
 }
 
 impl ProtocolKind {
@@ -28,6 +29,7 @@ impl ProtocolKind {
             ProtocolKind::Enet => "enet",
             ProtocolKind::Kcp => "kcp",
             ProtocolKind::Raknet => "raknet",
+            ProtocolKind::Sse => "sse",
         }
     }
 }
@@ -40,6 +42,7 @@ pub enum LoadMode {
     Jitter,
     Burst,
     Soak,
+    Concurrency,
 }
 
 impl LoadMode {
@@ -52,6 +55,7 @@ impl LoadMode {
             LoadMode::Jitter => "jitter",
             LoadMode::Burst => "burst",
             LoadMode::Soak => "soak",
+            LoadMode::Concurrency => "concurrency",
         }
     }
 }
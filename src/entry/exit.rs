@@ -0,0 +1,186 @@
+use std::process::ExitCode;
+
+use crate::error::{AppError, DistributedError, ValidationError};
+
+/// Process exit status, documented under "Exit Codes" in `docs/guides/USAGE.md`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExitStatus {
+    /// Run finished (or command completed) without errors.
+    Success,
+    /// Unexpected failure outside the classes below (I/O, charts, service, ...).
+    Failure,
+    /// A pass/fail threshold on the run results was not met.
+    ThresholdFailed,
+    /// The run completed but recorded runtime or transport errors.
+    RuntimeErrors,
+    /// CLI arguments, config, or input validation was rejected before the run.
+    InvalidInput,
+    /// The run was aborted early by an error circuit breaker.
+    CircuitBreaker,
+}
+
+impl ExitStatus {
+    #[must_use]
+    pub(crate) const fn code(self) -> u8 {
+        match self {
+            Self::Success => 0,
+            Self::Failure => 1,
+            Self::ThresholdFailed => 2,
+            Self::RuntimeErrors => 3,
+            Self::InvalidInput => 4,
+            Self::CircuitBreaker => 5,
+        }
+    }
+
+    /// Classifies a top-level error.
+    ///
+    /// Every `AppError` variant is listed so a new variant has to pick a class.
+    #[must_use]
+    pub(crate) const fn from_error(err: &AppError) -> Self {
+        match err {
+            AppError::Validation(
+                ValidationError::ThresholdsFailed { .. } | ValidationError::CompareRegressed { .. },
+            ) => Self::ThresholdFailed,
+            AppError::Validation(ValidationError::RunAborted { .. }) => Self::CircuitBreaker,
+            AppError::Validation(
+                ValidationError::RuntimeErrors | ValidationError::TransportErrors { .. },
+            )
+            | AppError::Distributed(
+                DistributedError::RunCompletedWithErrors
+                | DistributedError::Io { .. }
+                | DistributedError::Connection { .. }
+                | DistributedError::Bind { .. }
+                | DistributedError::AgentWaitTimeout { .. }
+                | DistributedError::ConnectionClosed
+                | DistributedError::WireMessageTooLarge { .. }
+                | DistributedError::WireMessageInvalidUtf8 { .. }
+                | DistributedError::RunIdMismatch { .. }
+                | DistributedError::StopBeforeStart
+                | DistributedError::ConfigWhileWaitingForStart
+                | DistributedError::UnexpectedControllerMessageWhileRunning
+                | DistributedError::UnexpectedMessageFromController
+                | DistributedError::ControllerConnectionClosed
+                | DistributedError::StartBeforeConfig
+                | DistributedError::ControlChannelClosed
+                | DistributedError::AgentEventChannelClosed
+                | DistributedError::AgentHelloTimeout
+                | DistributedError::ExpectedHelloFromAgent
+                | DistributedError::InvalidAuthToken
+                | DistributedError::Serialize { .. }
+                | DistributedError::Deserialize { .. }
+                | DistributedError::Remote { .. },
+            )
+            | AppError::Reqwest { .. }
+            | AppError::Http(_) => Self::RuntimeErrors,
+            #[cfg(test)]
+            AppError::Distributed(
+                DistributedError::TestExpectation { .. }
+                | DistributedError::TestExpectationValue { .. },
+            ) => Self::Failure,
+            AppError::Validation(_)
+            | AppError::Distributed(
+                DistributedError::MissingOption { .. }
+                | DistributedError::MissingControllerListen
+                | DistributedError::MissingControlListen
+                | DistributedError::WireValueTooSmall { .. }
+                | DistributedError::WireInvalidExtract { .. },
+            )
+            | AppError::Config(_)
+            | AppError::Clap { .. }
+            | AppError::Toml { .. }
+            | AppError::Json { .. }
+            | AppError::ParseInt { .. }
+            | AppError::ParseFloat { .. } => Self::InvalidInput,
+            AppError::Io { .. }
+            | AppError::Join { .. }
+            | AppError::Utf8 { .. }
+            | AppError::SystemTime { .. }
+            | AppError::Plotters { .. }
+            | AppError::Metrics(_)
+            | AppError::Script(_)
+            | AppError::Service(_)
+            | AppError::Sink(_) => Self::Failure,
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        Self::from(status.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AppResult, ConfigError};
+
+    #[test]
+    fn error_classes_map_to_documented_codes() -> AppResult<()> {
+        let cases = [
+            (
+                AppError::validation(ValidationError::RuntimeErrors),
+                ExitStatus::RuntimeErrors,
+            ),
+            (
+                AppError::distributed(DistributedError::RunCompletedWithErrors),
+                ExitStatus::RuntimeErrors,
+            ),
+            (
+                AppError::validation(ValidationError::ThresholdsFailed {
+                    failures: "p99 latency".to_owned(),
+                }),
+                ExitStatus::ThresholdFailed,
+            ),
+            (
+                AppError::validation(ValidationError::CompareRegressed {
+                    failures: "p99 latency regressed +12.00% (limit 10.00%)".to_owned(),
+                }),
+                ExitStatus::ThresholdFailed,
+            ),
+            (
+                AppError::validation(ValidationError::RunAborted {
+                    reason: "12 errors reached --abort-on-errors 12".to_owned(),
+                }),
+                ExitStatus::CircuitBreaker,
+            ),
+            (
+                AppError::validation(ValidationError::MissingUrl),
+                ExitStatus::InvalidInput,
+            ),
+            (
+                AppError::config(ConfigError::ProfileWithoutConfig),
+                ExitStatus::InvalidInput,
+            ),
+            (
+                AppError::distributed(DistributedError::MissingOption { option: "--agents" }),
+                ExitStatus::InvalidInput,
+            ),
+            (
+                AppError::distributed(DistributedError::MissingControllerListen),
+                ExitStatus::InvalidInput,
+            ),
+            (
+                AppError::distributed(DistributedError::ConnectionClosed),
+                ExitStatus::RuntimeErrors,
+            ),
+            (
+                AppError::from(std::io::Error::other("disk full")),
+                ExitStatus::Failure,
+            ),
+        ];
+        for (err, expected) in cases {
+            let status = ExitStatus::from_error(&err);
+            if status != expected {
+                return Err(AppError::validation(format!(
+                    "Expected {:?} for '{}', got {:?}",
+                    expected, err, status
+                )));
+            }
+        }
+        if ExitStatus::Success.code() != 0 || ExitStatus::RuntimeErrors.code() != 3 {
+            return Err(AppError::validation("Unexpected exit code values"));
+        }
+        Ok(())
+    }
+}
@@ -1,18 +1,41 @@
+mod exit;
 mod plan;
 
 use std::ffi::OsString;
 use std::path::Path;
+use std::process::ExitCode;
 
 use clap::{ArgMatches, CommandFactory, FromArgMatches};
 
 use crate::args::TesterArgs;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
+use exit::ExitStatus;
 use plan::{build_plan, execute_plan};
 
 /// Default config filenames checked when no CLI args are provided.
 const DEFAULT_CONFIG_FILES: [&str; 2] = ["strest.toml", "strest.json"];
 
-pub(crate) fn run() -> AppResult<()> {
+/// Runs the CLI and maps the outcome to a documented exit code.
+pub(crate) fn run() -> ExitCode {
+    match run_cli() {
+        Ok(()) => ExitStatus::Success.into(),
+        Err(err) => {
+            report_error(&err);
+            ExitStatus::from_error(&err).into()
+        }
+    }
+}
+
+fn report_error(err: &AppError) {
+    if let AppError::Clap { source } = err {
+        // Clap renders its own usage hint and colors.
+        drop(source.print());
+        return;
+    }
+    eprintln!("Error: {}", err);
+}
+
+fn run_cli() -> AppResult<()> {
     let (args, matches) = match parse_args()? {
         Some(parsed) => parsed,
         None => return Ok(()),
@@ -37,7 +60,15 @@ fn parse_args() -> AppResult<Option<(TesterArgs, ArgMatches)>> {
         return Ok(None);
     }
 
-    let matches = cmd.get_matches_from(raw_args);
+    let matches = match cmd.try_get_matches_from(raw_args) {
+        Ok(matches) => matches,
+        Err(err) if !err.use_stderr() => {
+            // --help / --version
+            err.print()?;
+            return Ok(None);
+        }
+        Err(err) => return Err(err.into()),
+    };
     let args = TesterArgs::from_arg_matches(&matches)?;
 
     Ok(Some((args, matches)))
@@ -0,0 +1,209 @@
+use serde::Serialize;
+
+use crate::app::{RunStartPayload, redact_url};
+use crate::args::{HttpMethod, LoadMode, OutputFormat, Protocol, TesterArgs};
+use crate::error::{AppError, AppResult, ValidationError};
+use crate::http::{WorkloadDescription, describe_workload};
+
+/// Everything a run would do, resolved from CLI and config, for `--dry-run`.
+#[derive(Debug, Serialize)]
+pub(in crate::entry) struct DryRunPlan {
+    /// `local`, `controller`, or `agent`.
+    pub(super) mode: &'static str,
+    #[serde(flatten)]
+    pub(super) run: RunStartPayload,
+    pub(super) method: &'static str,
+    /// Effective request rate in words, e.g. `100 req/s`.
+    pub(super) rate: String,
+    /// Resolved HTTP workload; `None` for non-HTTP protocols.
+    pub(super) workload: Option<WorkloadDescription>,
+    /// Sinks and result files the run would write.
+    pub(super) outputs: Vec<String>,
+    #[serde(skip)]
+    pub(super) json: bool,
+}
+
+pub(super) fn build_dry_run_plan(args: &TesterArgs) -> AppResult<DryRunPlan> {
+    if args.url.is_none() && args.scenario.is_none() {
+        return Err(AppError::validation(ValidationError::MissingUrl));
+    }
+    let mode = if args.controller_listen.is_some() {
+        "controller"
+    } else if args.agent_join.is_some() {
+        "agent"
+    } else {
+        "local"
+    };
+    let workload = if args.protocol == Protocol::Http {
+        let mut workload = describe_workload(args)?;
+        for url in &mut workload.urls {
+            *url = redact_url(url);
+        }
+        Some(workload)
+    } else {
+        None
+    };
+    Ok(DryRunPlan {
+        mode,
+        run: RunStartPayload::from_args(args),
+        method: method_name(args.method),
+        rate: describe_rate(args),
+        workload,
+        outputs: describe_outputs(args),
+        json: args.output_format == Some(OutputFormat::Json),
+    })
+}
+
+impl DryRunPlan {
+    pub(super) fn to_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            "Dry run: no traffic will be sent.".to_owned(),
+            format!("Mode: {}", self.mode),
+            format!(
+                "Protocol: {} (load mode: {})",
+                self.run.protocol, self.run.load_mode
+            ),
+            format!("Method: {}", self.method),
+        ];
+        match self.workload.as_ref() {
+            Some(workload) => lines.extend(workload_lines(workload)),
+            None => lines.push(format!("URL: {}", self.run.url.as_deref().unwrap_or("-"))),
+        }
+        lines.push(format!("Rate: {}", self.rate));
+        lines.push(format!("Duration: {}s", self.run.duration_seconds));
+        lines.push(format!("Max Tasks: {}", self.run.max_tasks));
+        if let Some(requests) = self.run.requests {
+            lines.push(format!("Requests: {}", requests));
+        }
+        if let Some(agents) = self.run.agents {
+            lines.push(format!("Min Agents: {}", agents));
+        }
+        if self.outputs.is_empty() {
+            lines.push("Outputs: none".to_owned());
+        } else {
+            lines.push("Outputs:".to_owned());
+            lines.extend(self.outputs.iter().map(|output| format!("  {}", output)));
+        }
+        lines
+    }
+}
+
+fn workload_lines(workload: &WorkloadDescription) -> Vec<String> {
+    let mut lines = Vec::new();
+    match (workload.url_source, workload.url_count) {
+        ("static", _) => lines.push(format!(
+            "URL: {}",
+            workload.urls.first().map_or("-", String::as_str)
+        )),
+        ("regex", _) => lines.push(format!(
+            "URLs: generated from {}",
+            workload.urls.first().map_or("-", String::as_str)
+        )),
+        (source, count) => {
+            lines.push(format!("URLs: {} ({})", source, count.unwrap_or(0)));
+            lines.extend(workload.urls.iter().map(|url| format!("  {}", url)));
+            let shown = workload.urls.len();
+            if let Some(hidden) = count.and_then(|count| count.checked_sub(shown))
+                && hidden > 0
+            {
+                lines.push(format!("  ... and {} more", hidden));
+            }
+        }
+    }
+    if let Some(steps) = workload.scenario_steps {
+        lines.push(format!("Scenario Steps: {}", steps));
+    }
+    lines.push(workload.body_count.map_or_else(
+        || format!("Body: {}", workload.body),
+        |count| format!("Body: {} ({})", workload.body, count),
+    ));
+    lines.push(format!("Auth: {}", workload.auth.unwrap_or("none")));
+    if !workload.header_names.is_empty() {
+        lines.push(format!("Headers: {}", workload.header_names.join(", ")));
+    }
+    lines
+}
+
+const fn method_name(method: HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "GET",
+        HttpMethod::Post => "POST",
+        HttpMethod::Patch => "PATCH",
+        HttpMethod::Put => "PUT",
+        HttpMethod::Delete => "DELETE",
+    }
+}
+
+fn describe_rate(args: &TesterArgs) -> String {
+    let workers = args.max_tasks.get();
+    if args.load_mode == LoadMode::Concurrency {
+        return format!("closed loop, {} workers", workers);
+    }
+    if let Some(profile) = args.load_profile.as_ref() {
+        let peak_rpm = profile
+            .stages
+            .iter()
+            .map(|stage| {
+                stage
+                    .sine
+                    .map_or(stage.target_rpm, |wave| wave.max_rpm.max(wave.min_rpm))
+            })
+            .fold(profile.initial_rpm, u64::max);
+        return format!(
+            "load profile, {} stage(s), {} to peak {} req/min",
+            profile.stages.len(),
+            profile.initial_rpm,
+            peak_rpm
+        );
+    }
+    if let Some(rate) = args.rate_limit {
+        return format!("{} req/s", rate.get());
+    }
+    if let Some(delay) = args.burst_delay {
+        return format!(
+            "bursts of {} every {}ms",
+            args.burst_rate.get(),
+            delay.as_millis()
+        );
+    }
+    format!("unlimited, {} workers", workers)
+}
+
+fn describe_outputs(args: &TesterArgs) -> Vec<String> {
+    let mut outputs = Vec::new();
+    if let Some(sinks) = args.sinks.as_ref() {
+        if let Some(sink) = sinks.prometheus.as_ref() {
+            outputs.push(format!("prometheus sink: {}", sink.path));
+        }
+        if let Some(sink) = sinks.otel.as_ref() {
+            outputs.push(format!("otel sink: {}", sink.path));
+        }
+        if let Some(sink) = sinks.influx.as_ref() {
+            for target in [sink.path.as_deref(), sink.url.as_deref()]
+                .into_iter()
+                .flatten()
+            {
+                outputs.push(format!("influx sink: {}", target));
+            }
+        }
+        if let Some(sink) = sinks.pushgateway.as_ref() {
+            outputs.push(format!("pushgateway sink: {}", sink.url));
+        }
+    }
+    let files = [
+        ("csv export", args.export_csv.as_ref()),
+        ("json export", args.export_json.as_ref()),
+        ("jsonl export", args.export_jsonl.as_ref()),
+        ("sqlite log", args.db_url.as_ref()),
+        ("summary", args.summary_out.as_ref()),
+    ];
+    outputs.extend(
+        files
+            .into_iter()
+            .filter_map(|(label, path)| path.map(|path| format!("{}: {}", label, path))),
+    );
+    if !args.no_charts {
+        outputs.push(format!("charts: {}", args.charts_path));
+    }
+    outputs
+}
@@ -30,6 +30,16 @@ pub(crate) async fn execute_plan(plan: RunPlan) -> AppResult<()> {
             slice_execution::execute_replay(args, &replay_port).await
         }
         RunPlan::DumpUrls(plan) => dump_urls(plan),
+        RunPlan::DryRun(plan) => {
+            if plan.json {
+                println!("{}", serde_json::to_string_pretty(&plan)?);
+            } else {
+                for line in plan.to_lines() {
+                    println!("{}", line);
+                }
+            }
+            Ok(())
+        }
         RunPlan::Service(args) => {
             let service_port = RuntimeServicePort;
             slice_execution::execute_service(args, &service_port)
@@ -55,6 +65,19 @@ pub(crate) async fn execute_plan(plan: RunPlan) -> AppResult<()> {
                 print_runtime_errors(&outcome.runtime_errors);
                 return Err(AppError::validation(ValidationError::RuntimeErrors));
             }
+            if let Some(reason) = outcome.abort_reason {
+                return Err(AppError::validation(ValidationError::RunAborted { reason }));
+            }
+            if !outcome.threshold_failures.is_empty() {
+                return Err(AppError::validation(ValidationError::ThresholdsFailed {
+                    failures: outcome.threshold_failures.join("; "),
+                }));
+            }
+            if outcome.summary.transport_errors > 0 {
+                return Err(AppError::validation(ValidationError::TransportErrors {
+                    count: outcome.summary.transport_errors,
+                }));
+            }
             Ok(())
         }
     }
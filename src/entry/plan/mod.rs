@@ -1,4 +1,5 @@
 mod build;
+mod dry_run;
 mod execute;
 mod types;
 
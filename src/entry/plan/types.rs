@@ -1,6 +1,8 @@
 use crate::application::commands::{DistributedRunCommand, LocalRunCommand, ReplayRunCommand};
 use crate::args::{CleanupArgs, CompareArgs, TesterArgs};
 
+use super::dry_run::DryRunPlan;
+
 pub(in crate::entry) struct DumpUrlsPlan {
     pub(super) pattern: String,
     pub(super) count: usize,
@@ -15,6 +17,7 @@ pub(in crate::entry) enum RunPlan {
         args: TesterArgs,
     },
     DumpUrls(DumpUrlsPlan),
+    DryRun(Box<DryRunPlan>),
     Service(TesterArgs),
     Distributed {
         command: DistributedRunCommand,
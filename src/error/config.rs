@@ -26,6 +26,14 @@ pub enum ConfigError {
     UnsupportedExtension { ext: String },
     #[error("Config file must have .toml or .json extension.")]
     MissingExtension,
+    #[error("Environment variable '{var}' referenced by config key '{key}' is not set.")]
+    MissingEnvVar { var: String, key: String },
+    #[error("Invalid interpolation in config key '{key}': {reason}.")]
+    InvalidInterpolation { key: String, reason: String },
+    #[error("Config 'sinks.influx' needs a 'path' or a 'url'.")]
+    InfluxSinkMissingTarget,
+    #[error("Config 'sinks.influx.url' requires 'sinks.influx.bucket'.")]
+    InfluxSinkMissingBucket,
     #[error("Config cannot set both '{left}' and '{right}'.")]
     Conflict {
         left: &'static str,
@@ -36,6 +44,11 @@ pub enum ConfigError {
         #[source]
         source: ValidationError,
     },
+    #[error("Invalid tag: {source}")]
+    InvalidTag {
+        #[source]
+        source: ValidationError,
+    },
     #[error("Invalid connect-to entry: {source}")]
     InvalidConnectTo {
         #[source]
@@ -46,6 +59,11 @@ pub enum ConfigError {
         #[source]
         source: ValidationError,
     },
+    #[error("Invalid chart size: {source}")]
+    InvalidChartSetting {
+        #[source]
+        source: ValidationError,
+    },
     #[error("Invalid charts_latency_bucket_ms: {source}")]
     InvalidChartsLatencyBucket {
         #[source]
@@ -65,6 +83,32 @@ pub enum ConfigError {
     StageMissingTargetRateRpm { index: usize },
     #[error("Stage {index} cannot combine target, rate, and rpm.")]
     StageConflictingTargetRateRpm { index: usize },
+    #[error("Stage {index} sets min_rpm, max_rpm, or period_secs without kind = \"sine\".")]
+    StageSineFieldsWithoutKind { index: usize },
+    #[error("Sine stage {index} must define {field}.")]
+    StageSineMissingField { index: usize, field: &'static str },
+    #[error("Sine stage {index} cannot set target, rate, or rpm; use min_rpm and max_rpm.")]
+    StageSineConflictingRate { index: usize },
+    #[error("Sine stage {index} min_rpm ({min_rpm}) must not exceed max_rpm ({max_rpm}).")]
+    StageSineRangeInvalid {
+        index: usize,
+        min_rpm: u64,
+        max_rpm: u64,
+    },
+    #[error("Sine stage {index} period_secs must be >= 1.")]
+    StageSinePeriodZero { index: usize },
+    #[error("Failed to read rate schedule '{path}': {source}")]
+    ReadRateSchedule {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Rate schedule '{path}' had no rows.")]
+    RateScheduleEmpty { path: PathBuf },
+    #[error("Invalid rate schedule row on line {line}: '{value}' (expected second,rpm).")]
+    InvalidRateScheduleRow { line: usize, value: String },
+    #[error("Rate schedule line {line}: second {second} must be later than the previous row.")]
+    RateScheduleNotAscending { line: usize, second: u64 },
     #[error("Config '{context}' cannot define both rate and rpm.")]
     RateRpmConflict { context: String },
     #[error("Unsupported scenario schema_version {version}.")]
@@ -73,6 +117,19 @@ pub enum ConfigError {
     ScenarioMissingSteps,
     #[error("Scenario step {index} must define url/path or set scenario.base_url.")]
     ScenarioStepMissingUrlOrPath { index: usize },
+    #[error("Scenario step {index} must set both cert and key.")]
+    ScenarioStepIncompleteIdentity { index: usize },
+    #[error("Scenario step {index} sets both think_time and think_time_ms.")]
+    ScenarioStepThinkTimeConflict { index: usize },
+    #[error("Scenario step {index} think_time min must be <= max.")]
+    ScenarioStepThinkTimeMinAboveMax { index: usize },
+    #[error("Scenario step {index} extract '{var}': {source}")]
+    InvalidScenarioExtract {
+        index: usize,
+        var: String,
+        #[source]
+        source: ValidationError,
+    },
     #[error("Duration must not be empty.")]
     DurationEmpty,
     #[error("Invalid duration '{value}'.")]
@@ -89,8 +146,16 @@ pub enum ConfigError {
     InvalidDurationUnit { unit: String },
     #[error("Duration must be > 0.")]
     DurationZero,
+    #[error("Invalid duration '{value}'. Use whole seconds (e.g., 90s, 5m, 1h30m).")]
+    DurationNotWholeSeconds { value: String },
     #[error("Invalid distributed.role '{value}'. Use 'controller' or 'agent'.")]
     InvalidDistributedRole { value: String },
+    #[error("Config profile '{name}' not found. Available profiles: {available}.")]
+    UnknownProfile { name: String, available: String },
+    #[error("Config 'profiles.{name}' must be a table.")]
+    ProfileNotTable { name: String },
+    #[error("--config-profile requires a config file (--config, strest.toml, or strest.json).")]
+    ProfileWithoutConfig,
     #[cfg(test)]
     #[error("Test expectation failed: {message}")]
     TestExpectation { message: &'static str },
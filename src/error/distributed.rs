@@ -64,6 +64,12 @@ pub enum DistributedError {
         #[source]
         source: ValidationError,
     },
+    #[error("Wire scenario extract '{var}' is invalid: {source}")]
+    WireInvalidExtract {
+        var: String,
+        #[source]
+        source: ValidationError,
+    },
     #[error("Run id mismatch (expected {expected}, got {actual}).")]
     RunIdMismatch { expected: String, actual: String },
     #[error("Received stop before start.")]
@@ -14,6 +14,18 @@ pub enum HttpError {
         #[source]
         source: reqwest::Error,
     },
+    #[error("OAuth2 token request failed: {source}")]
+    OAuth2TokenRequest {
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("OAuth2 token endpoint returned status {status}.")]
+    OAuth2TokenStatus { status: u16 },
+    #[error("OAuth2 token response had no usable access_token: {source}")]
+    OAuth2TokenResponse {
+        #[source]
+        source: serde_json::Error,
+    },
     #[error("Scenario has no steps.")]
     ScenarioHasNoSteps,
     #[error("Scenario preflight failed: {source}")]
@@ -29,6 +41,38 @@ pub enum HttpError {
     },
     #[error("Body lines file was empty.")]
     BodyLinesEmpty,
+    #[error("Body directory {path} has no files.")]
+    BodyDirEmpty { path: PathBuf },
+    #[error(
+        "Body directory {path} holds more than {max_bytes} bytes; raise --data-dir-max-bytes to load it."
+    )]
+    BodyDirTooLarge { path: PathBuf, max_bytes: u64 },
+    #[error("Unknown body placeholder '{name}'. Available: {available}")]
+    UnknownFakePlaceholder {
+        name: String,
+        available: &'static str,
+    },
+    #[error(
+        "Unknown body placeholders {names}. Available: {available}. Pass --literal-placeholders to send them as written."
+    )]
+    UnknownBodyPlaceholders {
+        names: String,
+        available: &'static str,
+    },
+    #[error(
+        "Invalid body placeholder '{placeholder}' (use lorem:N with N in 1..=10000, or int:MIN:MAX with MIN <= MAX)"
+    )]
+    InvalidFakePlaceholder { placeholder: String },
+    #[error("JSON body template is not valid JSON: {source}")]
+    InvalidJsonTemplate {
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Failed to render JSON body template: {source}")]
+    RenderJsonTemplate {
+        #[source]
+        source: serde_json::Error,
+    },
     #[error("Failed to build request: {source}")]
     BuildRequestFailed {
         #[source]
@@ -91,12 +135,30 @@ pub enum HttpError {
     },
     #[error("No addresses resolved for {host}.")]
     NoAddressesResolved { host: String },
+    #[error("DNS-over-HTTPS query for {host} failed: {source}")]
+    DohRequest {
+        host: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("DNS-over-HTTPS endpoint returned status {status} for {host}.")]
+    DohStatus { host: String, status: u16 },
+    #[error("DNS-over-HTTPS response for {host} is not a valid DNS message.")]
+    DohMalformedResponse { host: String },
+    #[error("Cannot encode '{host}' as a DNS name.")]
+    DohInvalidHost { host: String },
     #[error("Failed to read cacert '{path}': {source}")]
     ReadCacert {
         path: PathBuf,
         #[source]
         source: std::io::Error,
     },
+    #[error("Invalid TLS probe {what}: {source}")]
+    TlsProbeSetup {
+        what: &'static str,
+        #[source]
+        source: tokio_native_tls::native_tls::Error,
+    },
     #[error("Invalid cacert '{path}': {source}")]
     InvalidCacert {
         path: PathBuf,
@@ -143,6 +205,50 @@ pub enum HttpError {
     },
     #[error("URL file '{path}' was empty.")]
     UrlFileEmpty { path: PathBuf },
+    #[error("Failed to read URLs from stdin: {source}")]
+    ReadUrlStdin {
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("No URLs were read from stdin.")]
+    UrlStdinEmpty,
+    #[error("URL file '{path}' has an invalid weight (expected a positive integer): '{line}'")]
+    InvalidUrlWeight { path: PathBuf, line: String },
+    #[error("Failed to build weighted URL list: {source}")]
+    UrlWeights {
+        #[source]
+        source: rand::distributions::WeightedError,
+    },
+    #[error("Failed to read warmup requests file '{path}': {source}")]
+    ReadWarmupFile {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to write correlation log '{path}': {source}")]
+    WriteCorrelationLog {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to notify webhook '{url}': {source}")]
+    NotifyWebhook {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Webhook '{url}' returned HTTP {status}")]
+    NotifyWebhookStatus { url: String, status: u16 },
+    #[error("Warmup requests file '{path}' had no requests.")]
+    WarmupFileEmpty { path: PathBuf },
+    #[error("Invalid warmup request on line {line} of '{path}': '{value}'")]
+    InvalidWarmupLine {
+        path: PathBuf,
+        line: usize,
+        value: String,
+    },
+    #[error("Pre-run diagnostics reported {failures} failed step(s) (--diagnose-strict).")]
+    DiagnosticsFailed { failures: usize },
     #[error("Failed to resolve {host}:{port} ({source})")]
     ResolveHost {
         host: String,
@@ -14,6 +14,10 @@ pub enum MetricsError {
         #[source]
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+    #[error("Failed to parse histogram log: {detail}")]
+    HistogramLogParse { detail: String },
+    #[error("Histogram log contains no intervals.")]
+    HistogramLogEmpty,
     #[error("{context}: {source}")]
     External {
         context: &'static str,
@@ -12,6 +12,16 @@ pub enum SinkError {
         #[source]
         source: std::io::Error,
     },
+    #[error("Invalid Pushgateway URL '{url}'")]
+    InvalidPushgatewayUrl { url: String },
+    #[error("Failed to push to Pushgateway '{url}': {source}")]
+    PushPushgateway {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Pushgateway '{url}' returned HTTP {status}")]
+    PushgatewayStatus { url: String, status: u16 },
     #[error("Failed to serialize OTel sink: {source}")]
     SerializeOtel {
         #[source]
@@ -27,6 +37,57 @@ pub enum SinkError {
         #[source]
         source: std::io::Error,
     },
+    #[error("Invalid Influx URL '{url}'")]
+    InvalidInfluxUrl { url: String },
+    #[error("Failed to write to Influx '{url}': {source}")]
+    PushInflux {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Influx '{url}' returned HTTP {status}")]
+    InfluxStatus { url: String, status: u16 },
+    #[error("{}", .messages.join("; "))]
+    Multiple { messages: Vec<String> },
+    #[error("Failed to serialize checkpoint: {source}")]
+    SerializeCheckpoint {
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Failed to write checkpoint '{path}': {source}")]
+    WriteCheckpoint {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to serialize stream sample: {source}")]
+    SerializeSample {
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Failed to write stream samples '{path}': {source}")]
+    WriteSamples {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to read checkpoint '{path}': {source}")]
+    ReadCheckpoint {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to parse checkpoint '{path}': {source}")]
+    ParseCheckpoint {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error(
+        "Checkpoint '{path}' has no resume state; it was written by an older strest or \
+without a latency histogram."
+    )]
+    CheckpointMissingResume { path: String },
     #[cfg(test)]
     #[error("Test expectation failed: {message}")]
     TestExpectation { message: &'static str },
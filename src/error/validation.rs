@@ -14,7 +14,7 @@ pub enum ValidationError {
     #[error("Invalid boolean '{value}'. Expected true/false, yes/no, on/off, or 1/0.")]
     InvalidBoolean { value: String },
     #[error(
-        "Invalid connect-to '{value}'. Expected 'source_host:source_port:target_host:target_port'."
+        "Invalid connect-to '{value}'. Expected 'source_host:source_port:target_host:target_port[:ca_path]'."
     )]
     InvalidConnectToFormat { value: String },
     #[error("Invalid connect-to '{value}'.")]
@@ -44,6 +44,48 @@ pub enum ValidationError {
     InvalidDurationUnit { unit: String },
     #[error("Duration must be > 0.")]
     DurationZero,
+    #[error("Invalid duration '{value}'. Use whole seconds (e.g., 90s, 5m, 1h30m).")]
+    DurationNotWholeSeconds { value: String },
+    #[error("Invalid bandwidth '{value}'. Expected <size>/<time> (e.g., 10MB/s).")]
+    InvalidBandwidthFormat { value: String },
+    #[error("Invalid bandwidth size unit '{unit}'. Use B, KB, MB, GB, KiB, MiB, or GiB.")]
+    InvalidBandwidthSizeUnit { unit: String },
+    #[error("Invalid bandwidth time unit '{unit}'. Use s, m, or h.")]
+    InvalidBandwidthTimeUnit { unit: String },
+    #[error("Bandwidth overflow.")]
+    BandwidthOverflow,
+    #[error("Bandwidth must be at least 1 byte per second.")]
+    BandwidthTooSmall,
+    #[error("Invalid link rate '{value}'. Expected <number><unit> (e.g., 1mbit).")]
+    InvalidLinkRateFormat { value: String },
+    #[error("Invalid link rate unit '{unit}'. Use bit, kbit, mbit, or gbit.")]
+    InvalidLinkRateUnit { unit: String },
+    #[error("Invalid byte size '{value}'. Expected <number><unit> (e.g., 10GB).")]
+    InvalidByteSizeFormat { value: String },
+    #[error("Invalid byte size unit '{unit}'. Use B, KB, MB, GB, TB, KiB, MiB, GiB, or TiB.")]
+    InvalidByteSizeUnit { unit: String },
+    #[error("Byte size overflow.")]
+    ByteSizeOverflow,
+    #[error("Byte size must be > 0.")]
+    ByteSizeZero,
+    #[error(
+        "Invalid tag '{value}'. Expected key=value with a key of letters, digits, or '_' (e.g., env=staging)."
+    )]
+    InvalidTag { value: String },
+    #[error("Tag key '{key}' is reserved for sink labels.")]
+    ReservedTagKey { key: String },
+    #[error(
+        "Invalid status list '{value}'. Expected codes or ranges in 100-999 (e.g., 200,204,301-302)."
+    )]
+    InvalidExpectedStatus { value: String },
+    #[error("Invalid percentage '{value}'. Expected 0-100 with up to two decimals (e.g., 99.5).")]
+    InvalidPercentage { value: String },
+    #[error("Invalid extract source '{value}'. Use a header name or a JSONPath like $.data[0].id.")]
+    InvalidExtractSource { value: String },
+    #[error("Invalid JSON assertion '{value}'. Expected <jsonpath>=<value> (e.g., $.status=ok).")]
+    InvalidJsonAssertion { value: String },
+    #[error("Invalid --assert-body-regex '{pattern}': {reason}")]
+    InvalidBodyRegex { pattern: String, reason: String },
     #[error("Invalid older-than duration.")]
     InvalidOlderThanDuration,
     #[error("Invalid HTTP version '{value}'. Use 0.9, 1.0, 1.1, 2, or 3.")]
@@ -59,10 +101,21 @@ pub enum ValidationError {
     UnsupportedProtocol { protocol: String, supported: String },
     #[error("Load mode '{load_mode}' is not supported by protocol '{protocol}'.")]
     UnsupportedLoadModeForProtocol { protocol: String, load_mode: String },
+    #[error(
+        "`--load-mode concurrency` sends requests back-to-back and cannot be combined with `--rate`, `--burst-delay`, or a load profile."
+    )]
+    ConcurrencyModeWithRate,
     #[error("Invalid TLS version '{value}'. Use 1.0, 1.1, 1.2, or 1.3.")]
     InvalidTlsVersion { value: String },
     #[error("Value must be >= {min}.")]
     ValueTooSmall { min: u64 },
+    #[error("--{name} must be between {min} and {max}, got {value}.")]
+    ChartSettingOutOfRange {
+        name: &'static str,
+        value: u32,
+        min: u32,
+        max: u32,
+    },
     #[error("Invalid value: {source}")]
     InvalidNumber {
         #[source]
@@ -82,6 +135,18 @@ pub enum ValidationError {
     RunCancelled,
     #[error("Runtime errors occurred.")]
     RuntimeErrors,
+    #[error("Run recorded {count} transport error(s).")]
+    TransportErrors { count: u64 },
+    #[error("Threshold assertion(s) failed: {failures}")]
+    ThresholdsFailed { failures: String },
+    #[error("Regression check(s) failed: {failures}")]
+    CompareRegressed { failures: String },
+    #[error(
+        "--regress-success needs csv/json/jsonl snapshots; histogram logs have no status data."
+    )]
+    RegressSuccessNeedsSnapshots,
+    #[error("Run aborted early: {reason}")]
+    RunAborted { reason: String },
     #[error("`--output-format` requires `--output`.")]
     OutputFormatRequiresOutput,
     #[error("`--output` cannot be combined with export flags.")]
@@ -90,6 +155,18 @@ pub enum ValidationError {
     DbUrlRequiresSingleShard,
     #[error("--dump-urls cannot be used with scenarios.")]
     DumpUrlsWithScenario,
+    #[error("--find-max-rate requires --target-p99-ms.")]
+    FindMaxRateRequiresTarget,
+    #[error(
+        "--find-max-rate cannot be combined with a load profile or config rate/rpm; use --rate for the starting rate."
+    )]
+    FindMaxRateLoadProfileConflict,
+    #[error("--resume requires --checkpoint-out pointing at the checkpoint to continue.")]
+    ResumeRequiresCheckpoint,
+    #[error(
+        "Checkpoint already covers {elapsed_secs}s of the {target_secs}s target duration; raise --duration to continue the run."
+    )]
+    ResumeCheckpointCoversDuration { elapsed_secs: u64, target_secs: u64 },
     #[error("--dump-urls requires --rand-regex-url.")]
     DumpUrlsRequiresRandRegex,
     #[error("--dump-urls requires a count.")]
@@ -115,6 +192,8 @@ pub enum ValidationError {
 RUSTFLAGS=\"--cfg reqwest_unstable\"."
     )]
     Http3NotEnabled,
+    #[error("HTTP/3 runs over QUIC and requires an https:// target.")]
+    Http3RequiresHttps,
     #[error("Cannot enable http2 while ALPN is set to http/1.1 only.")]
     Http2WithHttp1OnlyAlpn,
     #[error("Unsupported ALPN protocol '{protocol}'. Use h2, http/1.1, or h3.")]
@@ -139,6 +218,63 @@ RUSTFLAGS=\"--cfg reqwest_unstable\"."
         #[source]
         source: http::header::InvalidHeaderName,
     },
+    #[error("Invalid --request-id-header '{header}': {source}")]
+    InvalidRequestIdHeader {
+        header: String,
+        #[source]
+        source: http::header::InvalidHeaderName,
+    },
+    #[error("Invalid --session-cookie '{name}': expected a non-empty cookie name.")]
+    InvalidSessionCookie { name: String },
+    #[error("--slowest must be between 1 and {max}, got {value}.")]
+    SlowestOutOfRange { value: usize, max: usize },
+    #[error("Invalid --backend-header '{header}': {source}")]
+    InvalidBackendHeader {
+        header: String,
+        #[source]
+        source: http::header::InvalidHeaderName,
+    },
+    #[error("Invalid --deadline-header '{header}': {source}")]
+    InvalidDeadlineHeader {
+        header: String,
+        #[source]
+        source: http::header::InvalidHeaderName,
+    },
+    #[error("Invalid --correlation-header '{header}': {source}")]
+    InvalidCorrelationHeader {
+        header: String,
+        #[source]
+        source: http::header::InvalidHeaderName,
+    },
+    #[error("Invalid name for generated header '{header}': {source}")]
+    InvalidGeneratedHeaderName {
+        header: String,
+        #[source]
+        source: http::header::InvalidHeaderName,
+    },
+    #[error("Invalid value template for header '{header}': {source}")]
+    InvalidHeaderTemplate {
+        header: String,
+        #[source]
+        source: http::header::InvalidHeaderValue,
+    },
+    #[error(
+        "--correlation-log requires --correlation-header or a -H value with ${{uuid}} or ${{seq}}."
+    )]
+    CorrelationLogWithoutGeneratedHeader,
+    #[error(
+        "--assert-response-bytes-above {above} and --assert-response-bytes-below {below} leave no valid response size."
+    )]
+    ResponseBytesRangeEmpty { above: u64, below: u64 },
+    #[error(
+        "--min-events {min_events} exceeds --sse-events-per-conn {events_per_conn}; every stream would fall short."
+    )]
+    MinEventsExceedsSseEventsPerConn {
+        min_events: u64,
+        events_per_conn: usize,
+    },
+    #[error("--replay-video requires building strest with the `replay-video` feature.")]
+    ReplayVideoFeatureDisabled,
     #[error("Invalid proxy header value for '{header}': {source}")]
     InvalidProxyHeaderValue {
         header: String,
@@ -163,6 +299,12 @@ RUSTFLAGS=\"--cfg reqwest_unstable\"."
     },
     #[error("URL is missing host.")]
     UrlMissingHost,
+    #[error("Invalid --doh-url '{url}': {source}")]
+    InvalidDohUrl {
+        url: String,
+        #[source]
+        source: url::ParseError,
+    },
     #[error("Invalid base_url '{url}': {source}")]
     InvalidBaseUrl {
         url: String,
@@ -193,11 +335,38 @@ RUSTFLAGS=\"--cfg reqwest_unstable\"."
     AuthPairInvalidFormat,
     #[error("Auth username must not be empty.")]
     AuthUsernameEmpty,
+    #[error("Provide only one of --{flag} or --{flag}-file.")]
+    SecretSourceConflict { flag: &'static str },
+    #[error("Provide only one of --basic-auth or --bearer-token.")]
+    BasicAndBearerAuthConflict,
+    #[error("--aws-sigv4 cannot be combined with --bearer-token.")]
+    AwsSigv4WithBearerToken,
+    #[error("--digest-auth cannot be combined with --basic-auth, --bearer-token, or --aws-sigv4.")]
+    DigestAuthConflict,
+    #[error("--oauth2-token-url requires --{flag}.")]
+    OAuth2MissingField { flag: &'static str },
+    #[error("--oauth2-{flag} requires --oauth2-token-url.")]
+    OAuth2RequiresTokenUrl { flag: &'static str },
+    #[error(
+        "--oauth2-token-url cannot be combined with --basic-auth, --bearer-token, --digest-auth, or --aws-sigv4."
+    )]
+    OAuth2AuthConflict,
+    #[error("Failed to read --{flag} secret file '{path}': {source}")]
+    SecretFileRead {
+        flag: &'static str,
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Environment variable '{var}' for --{flag} is not set or not valid UTF-8.")]
+    SecretEnvMissing { flag: &'static str, var: String },
+    #[error("--{flag} resolved to an empty value.")]
+    SecretEmpty { flag: &'static str },
     #[error("Invalid aws-sigv4 format. Expected aws:amz:region:service.")]
     AwsSigv4InvalidFormat,
     #[error("aws-sigv4 region/service must not be empty.")]
     AwsSigv4EmptyRegionOrService,
-    #[error("Expected format start-end (e.g., 10-30)")]
+    #[error("Expected format start-end[,start-end...] (e.g., 10-30 or 10-20,60-70)")]
     MetricsRangeFormat,
     #[error("Invalid start value: {source}")]
     MetricsRangeInvalidStart {
@@ -211,6 +380,10 @@ RUSTFLAGS=\"--cfg reqwest_unstable\"."
     },
     #[error("Start must be <= end")]
     MetricsRangeStartAfterEnd,
+    #[error(
+        "Ranges must be ascending and non-overlapping (range starting at {start} does not begin after {previous_end})"
+    )]
+    MetricsRangeOverlap { start: u64, previous_end: u64 },
     #[error("Failed to build runtime: {source}")]
     RuntimeBuildFailed {
         #[source]
@@ -233,6 +406,20 @@ RUSTFLAGS=\"--cfg reqwest_unstable\"."
     ReplayExportSourceConflict,
     #[error("Unsupported snapshot format '{value}'. Expected json, jsonl, or csv.")]
     InvalidSnapshotFormat { value: String },
+    #[error(
+        "Compare needs two histogram logs (.hlog/.hdr) or two metrics exports, not one of each."
+    )]
+    CompareHistogramMismatch,
+    #[error("Failed to read summary template '{path}': {source}")]
+    SummaryTemplateRead {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Unclosed '{{{{' in summary template at byte {offset}.")]
+    SummaryTemplateUnclosed { offset: usize },
+    #[error("Unknown summary template variable '{name}'. Available variables: {available}.")]
+    UnknownSummaryTemplateVariable { name: String, available: String },
     #[error("Test expectation failed: {message}")]
     TestExpectation { message: &'static str },
     #[error("Test expectation failed: {message}: {value}")]
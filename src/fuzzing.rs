@@ -209,6 +209,8 @@ pub fn build_scenario_request_input(
             connect_to: &[],
             host_header: None,
             auth: None,
+            version: None,
+            fake: None,
         },
     )?;
     Ok(())
@@ -0,0 +1,129 @@
+use std::sync::{
+    Mutex, PoisonError,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::Duration;
+
+use tokio::time::{Instant, sleep};
+
+/// Nanoseconds per second for refill math.
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+/// Milliseconds per second for achieved-rate math.
+const MILLIS_PER_SEC: u128 = 1_000;
+
+/// Shared byte token bucket used to cap bytes sent and received across all workers.
+///
+/// The bucket holds up to one second of budget. Consumers may overdraw it; the
+/// caller then sleeps until the debt has been refilled, so single large
+/// payloads are throttled instead of being rejected.
+#[derive(Debug)]
+pub(crate) struct BandwidthLimiter {
+    bytes_per_sec: u64,
+    started: Instant,
+    total_bytes: AtomicU64,
+    bucket: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: i128,
+    last_refill: Instant,
+}
+
+impl BandwidthLimiter {
+    pub(crate) fn new(bytes_per_sec: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            bytes_per_sec,
+            started: now,
+            total_bytes: AtomicU64::new(0),
+            bucket: Mutex::new(BucketState {
+                tokens: i128::from(bytes_per_sec),
+                last_refill: now,
+            }),
+        }
+    }
+
+    pub(crate) const fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    pub(crate) fn total_bytes(&self) -> u64 {
+        self.total_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Takes `bytes` from the bucket and returns how long the caller must wait
+    /// before the bucket is back in balance.
+    pub(crate) fn reserve(&self, bytes: u64, now: Instant) -> Duration {
+        self.total_bytes.fetch_add(bytes, Ordering::Relaxed);
+        let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let elapsed_nanos = now.saturating_duration_since(bucket.last_refill).as_nanos();
+        let refill = elapsed_nanos
+            .saturating_mul(u128::from(self.bytes_per_sec))
+            .checked_div(NANOS_PER_SEC)
+            .unwrap_or(0);
+        if refill > 0 {
+            let capacity = i128::from(self.bytes_per_sec);
+            bucket.tokens = bucket
+                .tokens
+                .saturating_add(i128::try_from(refill).unwrap_or(i128::MAX))
+                .min(capacity);
+            bucket.last_refill = now;
+        }
+
+        bucket.tokens = bucket.tokens.saturating_sub(i128::from(bytes));
+        if bucket.tokens >= 0 {
+            return Duration::ZERO;
+        }
+        let deficit = bucket.tokens.unsigned_abs();
+        let wait_nanos = deficit
+            .saturating_mul(NANOS_PER_SEC)
+            .checked_div(u128::from(self.bytes_per_sec))
+            .unwrap_or(0);
+        Duration::from_nanos(u64::try_from(wait_nanos).unwrap_or(u64::MAX))
+    }
+
+    pub(crate) async fn consume(&self, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+        let wait = self.reserve(bytes, Instant::now());
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+    }
+
+    /// Average bytes per second since the limiter was created.
+    pub(crate) fn achieved_bytes_per_sec(&self, now: Instant) -> u64 {
+        let elapsed_ms = now.saturating_duration_since(self.started).as_millis();
+        let per_sec = u128::from(self.total_bytes())
+            .saturating_mul(MILLIS_PER_SEC)
+            .checked_div(elapsed_ms)
+            .unwrap_or(0);
+        u64::try_from(per_sec).unwrap_or(u64::MAX)
+    }
+}
+
+pub(crate) fn format_bytes_per_sec(bytes_per_sec: u64) -> String {
+    const KB: u64 = 1_000;
+    const MB: u64 = 1_000_000;
+    const GB: u64 = 1_000_000_000;
+
+    let (unit_size, unit) = if bytes_per_sec >= GB {
+        (GB, "GB")
+    } else if bytes_per_sec >= MB {
+        (MB, "MB")
+    } else if bytes_per_sec >= KB {
+        (KB, "KB")
+    } else {
+        return format!("{}B/s", bytes_per_sec);
+    };
+    let whole = bytes_per_sec.checked_div(unit_size).unwrap_or(0);
+    let frac = bytes_per_sec
+        .saturating_sub(whole.saturating_mul(unit_size))
+        .saturating_mul(100)
+        .checked_div(unit_size)
+        .unwrap_or(0);
+    format!("{whole}.{frac:02}{unit}/s")
+}
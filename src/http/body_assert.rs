@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use regex_automata::meta::Regex;
+use serde_json::Value;
+
+use crate::args::{ExpectedStatus, ExtractSource, JsonAssertion, TesterArgs};
+use crate::error::{AppError, AppResult, ValidationError};
+
+use super::workload::json_lookup;
+
+/// Response body checks from `--assert-body-contains`, `--assert-body-regex`
+/// and `--assert-json`.
+///
+/// Setting any of them makes the worker buffer each response body; without
+/// them bodies are drained and discarded. Only responses with an expected
+/// status are checked, so an error page keeps its real status.
+#[derive(Debug)]
+pub(crate) struct BodyAssert {
+    expected_status: ExpectedStatus,
+    contains: Option<String>,
+    regex: Option<(String, Regex)>,
+    json: Option<JsonAssertion>,
+}
+
+impl BodyAssert {
+    pub(crate) fn from_args(args: &TesterArgs) -> AppResult<Option<Arc<Self>>> {
+        if args.assert_body_contains.is_none()
+            && args.assert_body_regex.is_none()
+            && args.assert_json.is_none()
+        {
+            return Ok(None);
+        }
+        let regex = args
+            .assert_body_regex
+            .as_ref()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map(|regex| (pattern.clone(), regex))
+                    .map_err(|err| {
+                        AppError::validation(ValidationError::InvalidBodyRegex {
+                            pattern: pattern.clone(),
+                            reason: err.to_string(),
+                        })
+                    })
+            })
+            .transpose()?;
+        Ok(Some(Arc::new(Self {
+            expected_status: args.expected_status_code,
+            contains: args.assert_body_contains.clone(),
+            regex,
+            json: args.assert_json.clone(),
+        })))
+    }
+
+    /// Whether a response with `status` has its body checked.
+    pub(crate) fn applies_to(&self, status: u16) -> bool {
+        self.expected_status.contains(status)
+    }
+
+    /// Returns a message describing the first failed assertion, if any.
+    pub(crate) fn check(&self, body: &[u8]) -> Option<Arc<str>> {
+        if let Some(fragment) = self.contains.as_deref()
+            && !fragment.is_empty()
+            && !body
+                .windows(fragment.len())
+                .any(|slice| slice == fragment.as_bytes())
+        {
+            return Some(Arc::from(format!("body missing '{}'", fragment)));
+        }
+        if let Some((pattern, regex)) = self.regex.as_ref()
+            && !regex.is_match(body)
+        {
+            return Some(Arc::from(format!("body does not match /{}/", pattern)));
+        }
+        if let Some(assertion) = self.json.as_ref() {
+            return check_json(assertion, body);
+        }
+        None
+    }
+}
+
+fn check_json(assertion: &JsonAssertion, body: &[u8]) -> Option<Arc<str>> {
+    let path = ExtractSource::JsonPath(assertion.path.clone());
+    let Ok(root) = serde_json::from_slice::<Value>(body) else {
+        return Some(Arc::from(format!(
+            "body is not JSON, expected {}",
+            assertion
+        )));
+    };
+    let actual = match json_lookup(&root, &assertion.path) {
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+        None => return Some(Arc::from(format!("{} not found in body", path))),
+    };
+    if actual == assertion.expected {
+        return None;
+    }
+    Some(Arc::from(format!(
+        "{} was '{}', expected '{}'",
+        path, actual, assertion.expected
+    )))
+}
@@ -0,0 +1,121 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::metrics::ConnectionTiming;
+
+tokio::task_local! {
+    static TRACE: RequestTrace;
+}
+
+/// Phase timings of the request running in the current task.
+///
+/// The connector only runs inside the request's own task while the request
+/// waits for it, so a connection that finishes opening after the request
+/// went out on a pooled one is not attributed to it.
+struct RequestTrace {
+    started: Instant,
+    connect: Cell<Option<Duration>>,
+    first_byte: Cell<Option<Duration>>,
+}
+
+impl RequestTrace {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            connect: Cell::new(None),
+            first_byte: Cell::new(None),
+        }
+    }
+
+    fn timing(&self) -> Option<ConnectionTiming> {
+        let first_byte = self.first_byte.get()?;
+        let connect = self.connect.get();
+        Some(ConnectionTiming {
+            new_connection: connect.is_some(),
+            connect: connect.unwrap_or_default(),
+            first_byte,
+        })
+    }
+}
+
+/// Runs `request` with a fresh trace and returns its output with the
+/// recorded timing; `None` when no response headers arrived.
+pub(crate) async fn traced<F: Future>(request: F) -> (F::Output, Option<ConnectionTiming>) {
+    TRACE
+        .scope(RequestTrace::new(), async move {
+            let output = request.await;
+            let timing = TRACE.try_with(RequestTrace::timing).ok().flatten();
+            (output, timing)
+        })
+        .await
+}
+
+/// Marks the arrival of the response headers for the traced request, if any.
+pub(crate) fn mark_first_byte() {
+    TRACE
+        .try_with(|trace| {
+            trace.first_byte.set(Some(trace.started.elapsed()));
+        })
+        .ok();
+}
+
+fn mark_connected(elapsed: Duration) {
+    TRACE
+        .try_with(|trace| {
+            if trace.first_byte.get().is_none() {
+                trace.connect.set(Some(elapsed));
+            }
+        })
+        .ok();
+}
+
+/// Connector layer that reports how long each new connection took to the
+/// request that is waiting for it.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ConnectTimingLayer;
+
+impl<S> Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectTiming<S> {
+    inner: S,
+}
+
+impl<S, Request> Service<Request> for ConnectTiming<S>
+where
+    S: Service<Request>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            let started = Instant::now();
+            let result = connecting.await;
+            if result.is_ok() {
+                mark_connected(started.elapsed());
+            }
+            result
+        })
+    }
+}
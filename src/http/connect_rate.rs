@@ -0,0 +1,143 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{
+    Arc, Mutex, PoisonError,
+    atomic::{AtomicU64, Ordering},
+};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::time::{Instant, sleep};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Nanoseconds per second for slot spacing.
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+/// Scale for achieved-rate math (connections per second x100).
+const RATE_SCALE: u128 = 100_000;
+
+/// Paces new connections to `connects_per_sec` across all workers.
+///
+/// A token bucket holding a single token: each connection attempt takes the
+/// next free slot, so connections are spread evenly instead of arriving in
+/// per-second bursts. Requests on pooled connections are not affected.
+#[derive(Debug)]
+pub(crate) struct ConnectRateLimiter {
+    connects_per_sec: u64,
+    spacing: Duration,
+    started: Instant,
+    total_connects: AtomicU64,
+    next_slot: Mutex<Instant>,
+}
+
+impl ConnectRateLimiter {
+    pub(crate) fn new(connects_per_sec: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            connects_per_sec,
+            spacing: Duration::from_nanos(
+                NANOS_PER_SEC
+                    .checked_div(connects_per_sec)
+                    .unwrap_or(NANOS_PER_SEC),
+            ),
+            started: now,
+            total_connects: AtomicU64::new(0),
+            next_slot: Mutex::new(now),
+        }
+    }
+
+    pub(crate) const fn connects_per_sec(&self) -> u64 {
+        self.connects_per_sec
+    }
+
+    pub(crate) fn total_connects(&self) -> u64 {
+        self.total_connects.load(Ordering::Relaxed)
+    }
+
+    /// Claims the next connection slot and returns how long to wait for it.
+    pub(crate) fn reserve(&self, now: Instant) -> Duration {
+        self.total_connects.fetch_add(1, Ordering::Relaxed);
+        let mut next_slot = self
+            .next_slot
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let slot = (*next_slot).max(now);
+        *next_slot = slot.checked_add(self.spacing).unwrap_or(slot);
+        slot.saturating_duration_since(now)
+    }
+
+    async fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+    }
+
+    /// Achieved connections per second since the limiter was created, x100.
+    pub(crate) fn achieved_per_sec_x100(&self, now: Instant) -> u64 {
+        let elapsed_ms = now.saturating_duration_since(self.started).as_millis();
+        if elapsed_ms == 0 {
+            return 0;
+        }
+        let scaled = u128::from(self.total_connects())
+            .saturating_mul(RATE_SCALE)
+            .checked_div(elapsed_ms)
+            .unwrap_or(0);
+        u64::try_from(scaled).unwrap_or(u64::MAX)
+    }
+}
+
+/// Connector layer that waits for a [`ConnectRateLimiter`] slot before dialing.
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectRateLayer {
+    limiter: Arc<ConnectRateLimiter>,
+}
+
+impl ConnectRateLayer {
+    pub(crate) const fn new(limiter: Arc<ConnectRateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for ConnectRateLayer {
+    type Service = ConnectRate<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectRate {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectRate<S> {
+    inner: S,
+    limiter: Arc<ConnectRateLimiter>,
+}
+
+impl<S, Request> Service<Request> for ConnectRate<S>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    Request: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let limiter = self.limiter.clone();
+        // Keep the service that was polled ready; leave a fresh clone behind.
+        let ready = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, ready);
+        Box::pin(async move {
+            limiter.acquire().await;
+            inner.call(request).await
+        })
+    }
+}
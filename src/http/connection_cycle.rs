@@ -0,0 +1,52 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use reqwest::Client;
+
+use crate::error::AppResult;
+
+type BuildClient = dyn Fn() -> AppResult<Client> + Send + Sync;
+
+/// Closes each worker's connection after `--requests-per-connection` requests.
+///
+/// Each worker gets its own client, so its pool only holds the connection
+/// that worker uses. Once the quota is served the client is dropped with its
+/// pool and a fresh one is built, which forces a new TCP (and TLS) handshake.
+pub(crate) struct ConnectionCycle {
+    requests_per_connection: u64,
+    connections: Arc<AtomicU64>,
+    build: Box<BuildClient>,
+}
+
+impl ConnectionCycle {
+    pub(crate) fn new(
+        requests_per_connection: u64,
+        connections: Arc<AtomicU64>,
+        build: Box<BuildClient>,
+    ) -> Self {
+        Self {
+            requests_per_connection: requests_per_connection.max(1),
+            connections,
+            build,
+        }
+    }
+
+    pub(crate) const fn requests_per_connection(&self) -> u64 {
+        self.requests_per_connection
+    }
+
+    /// Connections opened by the worker clients so far.
+    pub(crate) fn connections(&self) -> u64 {
+        self.connections.load(Ordering::Relaxed)
+    }
+
+    /// Counts a request on a worker's connection and returns a fresh client
+    /// when the previous one has served its quota (and for the first request).
+    pub(crate) fn next_client(&self, served: &mut u64) -> Option<AppResult<Client>> {
+        let due = served.checked_rem(self.requests_per_connection) == Some(0);
+        *served = served.saturating_add(1);
+        due.then(|| (self.build)())
+    }
+}
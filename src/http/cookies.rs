@@ -0,0 +1,225 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, HeaderMap, HeaderValue, LOCATION,
+    PROXY_AUTHORIZATION, SET_COOKIE, TRANSFER_ENCODING,
+};
+use reqwest::{Method, Request, Response, StatusCode, Url};
+
+/// Cookies held by one worker for the duration of a scenario iteration.
+///
+/// Honours `Domain`, `Path`, `Secure`, `Expires` and `Max-Age`. The client
+/// does not follow redirects while a jar is in use; [`redirect_request`]
+/// builds each hop instead, so a `Set-Cookie` on a 3xx is stored and sent on
+/// the next hop.
+#[derive(Debug, Default)]
+pub(crate) struct CookieJar {
+    cookies: Vec<StoredCookie>,
+    max_redirects: u32,
+}
+
+#[derive(Debug)]
+struct StoredCookie {
+    name: String,
+    value: String,
+    domain: String,
+    host_only: bool,
+    path: String,
+    secure: bool,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl StoredCookie {
+    fn matches(&self, url: &Url, host: &str, now: DateTime<Utc>) -> bool {
+        if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return false;
+        }
+        if self.secure && !matches!(url.scheme(), "https" | "wss") {
+            return false;
+        }
+        let host_ok = if self.host_only {
+            host == self.domain
+        } else {
+            host == self.domain
+                || host
+                    .strip_suffix(self.domain.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        };
+        host_ok && path_matches(&self.path, url.path())
+    }
+}
+
+/// RFC 6265 path matching: `/docs` matches `/docs`, `/docs/` and `/docs/a`,
+/// but not `/docsearch`.
+fn path_matches(cookie_path: &str, request_path: &str) -> bool {
+    request_path
+        .strip_prefix(cookie_path)
+        .is_some_and(|rest| rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/'))
+}
+
+/// The directory of the request path, used when `Set-Cookie` has no `Path`.
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_owned(),
+        Some(index) => url.path().get(..index).unwrap_or("/").to_owned(),
+    }
+}
+
+impl CookieJar {
+    /// A jar that follows up to `max_redirects` redirects per step itself.
+    pub(crate) const fn new(max_redirects: u32) -> Self {
+        Self {
+            cookies: Vec::new(),
+            max_redirects,
+        }
+    }
+
+    pub(crate) const fn max_redirects(&self) -> u32 {
+        self.max_redirects
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.cookies.clear();
+    }
+
+    /// Stores every `Set-Cookie` header of a response from `url`.
+    pub(crate) fn store(&mut self, url: &Url, headers: &HeaderMap) {
+        let Some(host) = url.host_str() else {
+            return;
+        };
+        let now = Utc::now();
+        for header in headers.get_all(SET_COOKIE) {
+            if let Ok(value) = header.to_str() {
+                self.store_one(url, host, value, now);
+            }
+        }
+    }
+
+    fn store_one(&mut self, url: &Url, host: &str, set_cookie: &str, now: DateTime<Utc>) {
+        let mut parts = set_cookie.split(';');
+        let Some((name, value)) = parts.next().and_then(|pair| pair.split_once('=')) else {
+            return;
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        let mut domain = None;
+        let mut path = None;
+        let mut secure = false;
+        let mut max_age = None;
+        let mut expires = None;
+        for attribute in parts {
+            let (key, attr_value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let key = key.trim();
+            let attr_value = attr_value.trim();
+            if key.eq_ignore_ascii_case("domain") && !attr_value.is_empty() {
+                domain = Some(attr_value.trim_start_matches('.').to_ascii_lowercase());
+            } else if key.eq_ignore_ascii_case("path") && attr_value.starts_with('/') {
+                path = Some(attr_value.to_owned());
+            } else if key.eq_ignore_ascii_case("secure") {
+                secure = true;
+            } else if key.eq_ignore_ascii_case("max-age") {
+                max_age = attr_value.parse::<i64>().ok();
+            } else if key.eq_ignore_ascii_case("expires") {
+                expires = DateTime::parse_from_rfc2822(attr_value)
+                    .ok()
+                    .map(|date| date.with_timezone(&Utc));
+            }
+        }
+        // `Max-Age` wins over `Expires`; a non-positive value deletes the cookie.
+        let expires_at = match max_age {
+            Some(seconds) if seconds <= 0 => Some(DateTime::<Utc>::MIN_UTC),
+            Some(seconds) => TimeDelta::try_seconds(seconds)
+                .and_then(|delta| now.checked_add_signed(delta))
+                .or(Some(DateTime::<Utc>::MAX_UTC)),
+            None => expires,
+        };
+        let host_only = domain.is_none();
+        let domain = domain.unwrap_or_else(|| host.to_ascii_lowercase());
+        let path = path.unwrap_or_else(|| default_path(url));
+        self.cookies
+            .retain(|cookie| cookie.name != name || cookie.domain != domain || cookie.path != path);
+        if expires_at.is_none_or(|expires_at| expires_at > now) {
+            self.cookies.push(StoredCookie {
+                name: name.to_owned(),
+                value: value.trim().to_owned(),
+                domain,
+                host_only,
+                path,
+                secure,
+                expires_at,
+            });
+        }
+    }
+
+    /// Adds matching cookies to the request, after any `Cookie` header it already has.
+    pub(crate) fn apply(&self, request: &mut Request) {
+        let Some(host) = request.url().host_str().map(str::to_ascii_lowercase) else {
+            return;
+        };
+        let now = Utc::now();
+        let pairs: Vec<String> = self
+            .cookies
+            .iter()
+            .filter(|cookie| cookie.matches(request.url(), &host, now))
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        if pairs.is_empty() {
+            return;
+        }
+        let mut header = pairs.join("; ");
+        if let Some(existing) = request
+            .headers()
+            .get(COOKIE)
+            .and_then(|value| value.to_str().ok())
+        {
+            header = format!("{}; {}", existing, header);
+        }
+        if let Ok(value) = HeaderValue::from_str(&header) {
+            request.headers_mut().insert(COOKIE, value);
+        }
+    }
+}
+
+/// Builds the request for the redirect `response` points to, the way the
+/// client's own redirect policy would.
+///
+/// `previous` is a copy of the request that got `response`, and
+/// `base_cookie` its `Cookie` header before any jar cookies were added.
+/// Returns `None` when `response` is not a followable redirect.
+pub(crate) fn redirect_request(
+    mut previous: Request,
+    response: &Response,
+    base_cookie: Option<&HeaderValue>,
+) -> Option<Request> {
+    let status = response.status();
+    let keeps_method = match status {
+        StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT => true,
+        StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND => *previous.method() != Method::POST,
+        StatusCode::SEE_OTHER => false,
+        _ => return None,
+    };
+    let location = response.headers().get(LOCATION)?.to_str().ok()?;
+    let next_url = response.url().join(location).ok()?;
+    let cross_origin = next_url.origin() != previous.url().origin();
+    *previous.url_mut() = next_url;
+    let headers = previous.headers_mut();
+    headers.remove(COOKIE);
+    if cross_origin {
+        headers.remove(AUTHORIZATION);
+        headers.remove(PROXY_AUTHORIZATION);
+    } else if let Some(cookie) = base_cookie {
+        headers.insert(COOKIE, cookie.clone());
+    }
+    if !keeps_method {
+        if *previous.method() != Method::HEAD {
+            *previous.method_mut() = Method::GET;
+        }
+        *previous.body_mut() = None;
+        let body_headers = previous.headers_mut();
+        body_headers.remove(CONTENT_TYPE);
+        body_headers.remove(CONTENT_LENGTH);
+        body_headers.remove(TRANSFER_ENCODING);
+    }
+    Some(previous)
+}
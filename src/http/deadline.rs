@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::Request;
+use reqwest::header::{HeaderName, HeaderValue};
+use tokio::time::Instant;
+
+use crate::args::{DeadlineFormat, TesterArgs};
+use crate::error::{AppError, AppResult, ValidationError};
+
+/// Stamps each request with the time left before `--timeout` expires.
+///
+/// The budget runs from the request's scheduled start, so with
+/// `--latency-correction` time spent queued behind the rate limiter is
+/// already deducted when the request goes out.
+#[derive(Debug)]
+pub(crate) struct DeadlineHeader {
+    header: HeaderName,
+    format: DeadlineFormat,
+    budget: Duration,
+}
+
+impl DeadlineHeader {
+    pub(crate) fn from_args(args: &TesterArgs) -> AppResult<Option<Arc<Self>>> {
+        let Some(header) = args.deadline_header.as_deref() else {
+            return Ok(None);
+        };
+        let header = HeaderName::from_bytes(header.as_bytes()).map_err(|err| {
+            AppError::validation(ValidationError::InvalidDeadlineHeader {
+                header: header.to_owned(),
+                source: err,
+            })
+        })?;
+        Ok(Some(Arc::new(Self::new(
+            header,
+            args.deadline_format,
+            args.request_timeout,
+        ))))
+    }
+
+    pub(crate) const fn new(header: HeaderName, format: DeadlineFormat, budget: Duration) -> Self {
+        Self {
+            header,
+            format,
+            budget,
+        }
+    }
+
+    /// Sets the header on `request` for a request scheduled at `scheduled`.
+    pub(crate) fn apply(&self, request: &mut Request, scheduled: Option<Instant>) {
+        let elapsed = scheduled.map_or(Duration::ZERO, |start| start.elapsed());
+        let remaining = self.budget.saturating_sub(elapsed);
+        let value = match self.format {
+            DeadlineFormat::RelativeMs => remaining.as_millis(),
+            DeadlineFormat::AbsoluteMs => SystemTime::now()
+                .checked_add(remaining)
+                .and_then(|deadline| deadline.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |since_epoch| since_epoch.as_millis()),
+        };
+        request.headers_mut().insert(
+            self.header.clone(),
+            HeaderValue::from(u64::try_from(value).unwrap_or(u64::MAX)),
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use serde::Serialize;
+
+use crate::args::TesterArgs;
+use crate::error::AppResult;
+use crate::run_context::RunContext;
+
+use super::sender::build_client_and_workload;
+use super::workload::{AuthConfig, BodySource, UrlSource, Workload};
+
+/// URLs listed before the rest of a list is only counted.
+const URL_PREVIEW: usize = 5;
+
+/// What the HTTP sender would send, resolved without opening a connection.
+///
+/// Header values and credentials are left out; only header names and the
+/// auth scheme are reported.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkloadDescription {
+    /// `static`, `list`, `weighted`, `regex`, or `scenario`.
+    pub url_source: &'static str,
+    /// Number of distinct URLs; `None` for generated URLs.
+    pub url_count: Option<usize>,
+    /// Up to the first five URLs (the pattern for `regex`).
+    pub urls: Vec<String>,
+    /// `none`, `static`, `lines`, `fake`, `json-template`, `files`, or `form`.
+    pub body: &'static str,
+    /// Lines or files rotated through, for `lines` and `files` bodies.
+    pub body_count: Option<usize>,
+    pub auth: Option<&'static str>,
+    pub header_names: Vec<String>,
+    pub scenario_steps: Option<usize>,
+}
+
+/// Resolves the workload the same way the sender does, so `--dry-run`
+/// surfaces the same validation errors, and describes it.
+///
+/// # Errors
+///
+/// Returns the error the sender would fail with, e.g. conflicting flags or
+/// an unreadable URL or body file.
+pub fn describe_workload(args: &TesterArgs) -> AppResult<WorkloadDescription> {
+    let (_client, workload) = build_client_and_workload(args, &RunContext::default(), None)?;
+    let auth = workload.auth().map(auth_label);
+    let description = match &workload {
+        Workload::Single(request) => {
+            let has_body = request
+                .body()
+                .and_then(reqwest::Body::as_bytes)
+                .is_some_and(|body| !body.is_empty());
+            WorkloadDescription {
+                url_source: "static",
+                url_count: Some(1),
+                urls: vec![request.url().to_string()],
+                body: if has_body { "static" } else { "none" },
+                body_count: None,
+                auth,
+                header_names: request
+                    .headers()
+                    .keys()
+                    .map(|name| name.as_str().to_owned())
+                    .collect(),
+                scenario_steps: None,
+            }
+        }
+        Workload::SingleDynamic(spec) => {
+            let (url_source, url_count, urls) = match &spec.url {
+                UrlSource::Static(url) => ("static", Some(1), vec![url.clone()]),
+                UrlSource::List(list) => ("list", Some(list.items().len()), preview(list.items())),
+                UrlSource::Weighted(list) => {
+                    ("weighted", Some(list.items().len()), preview(list.items()))
+                }
+                UrlSource::Regex(_) => ("regex", None, args.url.clone().into_iter().collect()),
+            };
+            let (body, body_count) = match &spec.body {
+                _ if spec.form.is_some() => ("form", None),
+                BodySource::Static(body) if body.is_empty() => ("none", None),
+                BodySource::Static(_) => ("static", None),
+                BodySource::Lines(lines) => ("lines", Some(lines.items().len())),
+                BodySource::Fake(_) => ("fake", None),
+                BodySource::Json(_) => ("json-template", None),
+                BodySource::Files(files) => ("files", Some(files.file_count())),
+            };
+            WorkloadDescription {
+                url_source,
+                url_count,
+                urls,
+                body,
+                body_count,
+                auth,
+                header_names: spec.headers.iter().map(|(name, _)| name.clone()).collect(),
+                scenario_steps: None,
+            }
+        }
+        Workload::Scenario(scenario, ..) => {
+            let urls: Vec<String> = scenario
+                .steps
+                .iter()
+                .filter_map(|step| step.url.clone())
+                .collect();
+            WorkloadDescription {
+                url_source: "scenario",
+                url_count: Some(urls.len()),
+                urls: preview(&urls),
+                body: "scenario",
+                body_count: None,
+                auth,
+                header_names: args.headers.iter().map(|(name, _)| name.clone()).collect(),
+                scenario_steps: Some(scenario.steps.len()),
+            }
+        }
+    };
+    Ok(description)
+}
+
+fn preview(urls: &[String]) -> Vec<String> {
+    urls.iter().take(URL_PREVIEW).cloned().collect()
+}
+
+const fn auth_label(auth: &AuthConfig) -> &'static str {
+    match auth {
+        AuthConfig::Basic { .. } => "basic",
+        AuthConfig::Bearer { .. } => "bearer",
+        AuthConfig::Digest { .. } => "digest",
+        AuthConfig::OAuth2 { .. } => "oauth2",
+        AuthConfig::SigV4 { .. } => "sigv4",
+    }
+}
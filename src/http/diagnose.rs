@@ -0,0 +1,368 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio::time::{Instant, timeout};
+use tokio_native_tls::TlsConnector;
+use url::Url;
+
+use crate::args::{Protocol, TesterArgs};
+use crate::run_context::RunContext;
+
+use super::sender::{build_client_and_workload, resolve_addrs, resolve_primary_host};
+use super::tls::diagnose_tls_connector;
+use super::workload::probe_first_byte;
+
+/// Outcome of one diagnostic step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticStatus {
+    Ok,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticStep {
+    pub name: &'static str,
+    pub status: DiagnosticStatus,
+    pub elapsed: Option<Duration>,
+    pub detail: String,
+}
+
+/// Pre-run connectivity report: DNS, TCP connect, TLS handshake, and first byte.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticReport {
+    pub steps: Vec<DiagnosticStep>,
+}
+
+impl DiagnosticReport {
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status == DiagnosticStatus::Failed)
+            .count()
+    }
+
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::with_capacity(self.steps.len().saturating_add(1));
+        lines.push("Diagnostics:".to_owned());
+        for step in &self.steps {
+            let status = match step.status {
+                DiagnosticStatus::Ok => "ok",
+                DiagnosticStatus::Failed => "FAIL",
+                DiagnosticStatus::Skipped => "skip",
+            };
+            let elapsed = step.elapsed.map_or_else(|| "-".to_owned(), format_elapsed);
+            lines.push(format!(
+                "  {:<10} {:<4} {:>9}  {}",
+                step.name, status, elapsed, step.detail
+            ));
+        }
+        lines
+    }
+
+    fn push(
+        &mut self,
+        name: &'static str,
+        status: DiagnosticStatus,
+        elapsed: Option<Duration>,
+        detail: String,
+    ) {
+        self.steps.push(DiagnosticStep {
+            name,
+            status,
+            elapsed,
+            detail,
+        });
+    }
+}
+
+/// Runs DNS, TCP, TLS, and first-byte checks against the primary target once.
+///
+/// Every step is attempted independently where possible; failures are recorded
+/// in the report instead of aborting so the caller decides whether to proceed.
+pub async fn run_diagnostics(args: &TesterArgs, run: &RunContext) -> DiagnosticReport {
+    let mut report = DiagnosticReport::default();
+    let target = match resolve_primary_host(args) {
+        Ok(target) => target,
+        Err(err) => {
+            report.push("dns", DiagnosticStatus::Failed, None, err.to_string());
+            None
+        }
+    };
+
+    let addr = match (target.as_ref(), args.unix_socket.as_deref()) {
+        (_, Some(path)) => {
+            let detail = format!("unix socket {}", path);
+            report.push("dns", DiagnosticStatus::Skipped, None, detail.clone());
+            report.push("tcp", DiagnosticStatus::Skipped, None, detail);
+            None
+        }
+        (Some((host, port)), None) => {
+            let (dns_host, dns_port) = connect_target(args, host, *port);
+            diagnose_dns(&mut report, args, dns_host, dns_port).await
+        }
+        (None, None) => {
+            if report.steps.is_empty() {
+                report.push(
+                    "dns",
+                    DiagnosticStatus::Skipped,
+                    None,
+                    "no single target host (URL list or regex)".to_owned(),
+                );
+            }
+            None
+        }
+    };
+
+    let stream = match addr {
+        Some(addr) => diagnose_tcp(&mut report, args, addr).await,
+        None if args.unix_socket.is_none() => {
+            report.push(
+                "tcp",
+                DiagnosticStatus::Skipped,
+                None,
+                "no resolved address".to_owned(),
+            );
+            None
+        }
+        None => None,
+    };
+
+    match (stream, target.as_ref(), is_https_target(args)) {
+        (Some(stream), Some((host, _)), true) => {
+            diagnose_tls(&mut report, args, stream, host).await;
+        }
+        (_, _, false) => report.push(
+            "tls",
+            DiagnosticStatus::Skipped,
+            None,
+            "plain-text target".to_owned(),
+        ),
+        (_, _, true) => report.push(
+            "tls",
+            DiagnosticStatus::Skipped,
+            None,
+            "no TCP connection".to_owned(),
+        ),
+    }
+
+    diagnose_first_byte(&mut report, args, run).await;
+    report
+}
+
+fn connect_target<'args>(
+    args: &'args TesterArgs,
+    host: &'args str,
+    port: u16,
+) -> (&'args str, u16) {
+    args.connect_to
+        .iter()
+        .find(|mapping| mapping.source_host == host && mapping.source_port == port)
+        .map_or((host, port), |mapping| {
+            (mapping.target_host.as_str(), mapping.target_port)
+        })
+}
+
+async fn diagnose_dns(
+    report: &mut DiagnosticReport,
+    args: &TesterArgs,
+    host: &str,
+    port: u16,
+) -> Option<SocketAddr> {
+    let lookup_host = host.to_owned();
+    let (ipv4_only, ipv6_only) = (args.ipv4_only, args.ipv6_only);
+    let started = Instant::now();
+    let result = tokio::task::spawn_blocking(move || {
+        resolve_addrs(&lookup_host, port, ipv4_only, ipv6_only)
+    })
+    .await;
+    let elapsed = started.elapsed();
+    match result {
+        Ok(Ok(addrs)) => {
+            let Some(first) = addrs.first().copied() else {
+                report.push(
+                    "dns",
+                    DiagnosticStatus::Failed,
+                    Some(elapsed),
+                    format!("{}:{} resolved to no usable addresses", host, port),
+                );
+                return None;
+            };
+            let extra = addrs.len().saturating_sub(1);
+            let detail = if extra > 0 {
+                format!("{}:{} -> {} (+{} more)", host, port, first, extra)
+            } else {
+                format!("{}:{} -> {}", host, port, first)
+            };
+            report.push("dns", DiagnosticStatus::Ok, Some(elapsed), detail);
+            Some(first)
+        }
+        Ok(Err(err)) => {
+            report.push(
+                "dns",
+                DiagnosticStatus::Failed,
+                Some(elapsed),
+                err.to_string(),
+            );
+            None
+        }
+        Err(err) => {
+            report.push(
+                "dns",
+                DiagnosticStatus::Failed,
+                Some(elapsed),
+                format!("lookup task failed: {}", err),
+            );
+            None
+        }
+    }
+}
+
+async fn diagnose_tcp(
+    report: &mut DiagnosticReport,
+    args: &TesterArgs,
+    addr: SocketAddr,
+) -> Option<TcpStream> {
+    let started = Instant::now();
+    let result = timeout(args.connect_timeout, TcpStream::connect(addr)).await;
+    let elapsed = started.elapsed();
+    match result {
+        Ok(Ok(stream)) => {
+            report.push(
+                "tcp",
+                DiagnosticStatus::Ok,
+                Some(elapsed),
+                format!("connected to {}", addr),
+            );
+            Some(stream)
+        }
+        Ok(Err(err)) => {
+            report.push(
+                "tcp",
+                DiagnosticStatus::Failed,
+                Some(elapsed),
+                format!("{}: {}", addr, err),
+            );
+            None
+        }
+        Err(_) => {
+            report.push(
+                "tcp",
+                DiagnosticStatus::Failed,
+                Some(elapsed),
+                format!(
+                    "{}: timed out after {}ms",
+                    addr,
+                    args.connect_timeout.as_millis()
+                ),
+            );
+            None
+        }
+    }
+}
+
+async fn diagnose_tls(
+    report: &mut DiagnosticReport,
+    args: &TesterArgs,
+    stream: TcpStream,
+    host: &str,
+) {
+    let connector = match diagnose_tls_connector(args) {
+        Ok(connector) => TlsConnector::from(connector),
+        Err(err) => {
+            report.push("tls", DiagnosticStatus::Failed, None, err.to_string());
+            return;
+        }
+    };
+    let started = Instant::now();
+    let result = timeout(args.connect_timeout, connector.connect(host, stream)).await;
+    let elapsed = started.elapsed();
+    match result {
+        Ok(Ok(_)) => report.push(
+            "tls",
+            DiagnosticStatus::Ok,
+            Some(elapsed),
+            format!("handshake with {}", host),
+        ),
+        Ok(Err(err)) => report.push(
+            "tls",
+            DiagnosticStatus::Failed,
+            Some(elapsed),
+            err.to_string(),
+        ),
+        Err(_) => report.push(
+            "tls",
+            DiagnosticStatus::Failed,
+            Some(elapsed),
+            format!("timed out after {}ms", args.connect_timeout.as_millis()),
+        ),
+    }
+}
+
+async fn diagnose_first_byte(report: &mut DiagnosticReport, args: &TesterArgs, run: &RunContext) {
+    if args.protocol != Protocol::Http {
+        report.push(
+            "first_byte",
+            DiagnosticStatus::Skipped,
+            None,
+            format!("protocol {} has no HTTP probe", args.protocol.as_str()),
+        );
+        return;
+    }
+    let (client, workload) = match build_client_and_workload(args, run, None) {
+        Ok(built) => built,
+        Err(err) => {
+            report.push(
+                "first_byte",
+                DiagnosticStatus::Failed,
+                None,
+                err.to_string(),
+            );
+            return;
+        }
+    };
+    match probe_first_byte(&client, &workload).await {
+        Ok((status, elapsed)) => {
+            let (step_status, detail) = if args.expected_status_code.contains(status) {
+                (DiagnosticStatus::Ok, format!("status {}", status))
+            } else {
+                (
+                    DiagnosticStatus::Failed,
+                    format!("status {} (expected {})", status, args.expected_status_code),
+                )
+            };
+            report.push("first_byte", step_status, Some(elapsed), detail);
+        }
+        Err(err) => {
+            report.push(
+                "first_byte",
+                DiagnosticStatus::Failed,
+                None,
+                err.to_string(),
+            );
+        }
+    }
+}
+
+fn is_https_target(args: &TesterArgs) -> bool {
+    let raw = args.url.as_deref().or_else(|| {
+        args.scenario
+            .as_ref()
+            .and_then(|scenario| scenario.base_url.as_deref())
+    });
+    raw.and_then(|value| Url::parse(value).ok())
+        .is_some_and(|url| matches!(url.scheme(), "https" | "grpcs" | "wss"))
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let micros = elapsed.as_micros();
+    let millis = micros.checked_div(1_000).unwrap_or(0);
+    let tenths = micros
+        .checked_rem(1_000)
+        .and_then(|rem| rem.checked_div(100))
+        .unwrap_or(0);
+    format!("{}.{}ms", millis, tenths)
+}
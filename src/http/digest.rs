@@ -0,0 +1,323 @@
+use std::sync::{Mutex, PoisonError};
+
+use md5::Md5;
+use rand::Rng;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue, WWW_AUTHENTICATE};
+use reqwest::{Client, Request, Response, StatusCode};
+use sha2::{Digest, Sha256};
+
+use super::workload::AuthConfig;
+
+/// HTTP Digest authentication (RFC 7616) for one worker.
+///
+/// Requests go out unauthenticated until the server answers with a `401`
+/// Digest challenge; the request is then retried once with credentials.
+/// Later requests reuse the challenge with an increasing nonce count, so
+/// only the first request and requests after a stale nonce are sent twice.
+#[derive(Debug)]
+pub(crate) struct DigestAuth {
+    username: String,
+    password: String,
+    state: Mutex<Option<DigestState>>,
+}
+
+#[derive(Debug)]
+struct DigestState {
+    challenge: DigestChallenge,
+    nonce_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestAlgorithm {
+    Md5,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha256 => "SHA-256",
+        }
+    }
+
+    /// Hex digest of `parts` joined with `:`, without building the joined string.
+    fn hash(self, parts: &[&str]) -> String {
+        match self {
+            Self::Md5 => to_hex(&digest_parts::<Md5>(parts)),
+            Self::Sha256 => to_hex(&digest_parts::<Sha256>(parts)),
+        }
+    }
+}
+
+fn digest_parts<D: Digest>(parts: &[&str]) -> sha2::digest::Output<D> {
+    let mut hasher = D::new();
+    for (index, part) in parts.iter().enumerate() {
+        if index > 0 {
+            hasher.update(b":");
+        }
+        hasher.update(part.as_bytes());
+    }
+    hasher.finalize()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    qop_auth: bool,
+}
+
+impl DigestAuth {
+    pub(crate) fn from_auth(auth: Option<&AuthConfig>) -> Option<Self> {
+        match auth {
+            Some(AuthConfig::Digest { username, password }) => Some(Self {
+                username: username.clone(),
+                password: password.clone(),
+                state: Mutex::new(None),
+            }),
+            Some(
+                AuthConfig::Basic { .. }
+                | AuthConfig::Bearer { .. }
+                | AuthConfig::OAuth2 { .. }
+                | AuthConfig::SigV4 { .. },
+            )
+            | None => None,
+        }
+    }
+
+    /// Sends `request`, answering a Digest challenge with one authenticated retry.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport error of the request that was sent last.
+    pub(crate) async fn execute(
+        &self,
+        client: &Client,
+        mut request: Request,
+    ) -> Result<Response, reqwest::Error> {
+        let retry = request.try_clone();
+        self.authorize(&mut request);
+        let response = client.execute(request).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let Some(mut retry) = retry else {
+            return Ok(response);
+        };
+        if !self.accept_challenge(response.headers()) {
+            return Ok(response);
+        }
+        // Read the 401 body so the connection can be reused for the retry.
+        drop(response.bytes().await);
+        self.authorize(&mut retry);
+        client.execute(retry).await
+    }
+
+    fn accept_challenge(&self, headers: &HeaderMap) -> bool {
+        let challenge = headers
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(parse_challenge)
+            .max_by_key(|challenge| challenge.algorithm == DigestAlgorithm::Sha256);
+        let Some(challenge) = challenge else {
+            return false;
+        };
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner) = Some(DigestState {
+            challenge,
+            nonce_count: 0,
+        });
+        true
+    }
+
+    fn authorize(&self, request: &mut Request) {
+        let mut uri = request.url().path().to_owned();
+        if let Some(query) = request.url().query() {
+            uri.push('?');
+            uri.push_str(query);
+        }
+        let header = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            let Some(state) = state.as_mut() else {
+                return;
+            };
+            state.nonce_count = state.nonce_count.wrapping_add(1);
+            self.header_value(
+                &state.challenge,
+                state.nonce_count,
+                request.method().as_str(),
+                &uri,
+            )
+        };
+        if let Ok(value) = HeaderValue::from_str(&header) {
+            request.headers_mut().insert(AUTHORIZATION, value);
+        }
+    }
+
+    fn header_value(
+        &self,
+        challenge: &DigestChallenge,
+        nonce_count: u32,
+        method: &str,
+        uri: &str,
+    ) -> String {
+        let algorithm = challenge.algorithm;
+        let ha1 = algorithm.hash(&[&self.username, &challenge.realm, &self.password]);
+        let ha2 = algorithm.hash(&[method, uri]);
+        let mut header = String::with_capacity(
+            HEADER_OVERHEAD
+                .saturating_add(self.username.len())
+                .saturating_add(challenge.realm.len())
+                .saturating_add(challenge.nonce.len())
+                .saturating_add(uri.len())
+                .saturating_add(ha1.len())
+                .saturating_add(challenge.opaque.as_ref().map_or(0, String::len)),
+        );
+        header.push_str("Digest ");
+        push_quoted(&mut header, "username", &self.username);
+        header.push_str(", ");
+        push_quoted(&mut header, "realm", &challenge.realm);
+        header.push_str(", ");
+        push_quoted(&mut header, "nonce", &challenge.nonce);
+        header.push_str(", ");
+        push_quoted(&mut header, "uri", uri);
+        header.push_str(", algorithm=");
+        header.push_str(algorithm.name());
+        let response = if challenge.qop_auth {
+            let nc = format!("{:08x}", nonce_count);
+            let cnonce = format!("{:016x}", rand::thread_rng().r#gen::<u64>());
+            let response = algorithm.hash(&[&ha1, &challenge.nonce, &nc, &cnonce, "auth", &ha2]);
+            header.push_str(", qop=auth, nc=");
+            header.push_str(&nc);
+            header.push_str(", ");
+            push_quoted(&mut header, "cnonce", &cnonce);
+            response
+        } else {
+            algorithm.hash(&[&ha1, &challenge.nonce, &ha2])
+        };
+        header.push_str(", ");
+        push_quoted(&mut header, "response", &response);
+        if let Some(opaque) = challenge.opaque.as_deref() {
+            header.push_str(", ");
+            push_quoted(&mut header, "opaque", opaque);
+        }
+        header
+    }
+}
+
+/// Parses one `WWW-Authenticate: Digest ...` value; other schemes and
+/// unsupported algorithms or qop values return `None`.
+fn parse_challenge(value: &str) -> Option<DigestChallenge> {
+    let (scheme, params) = value.trim().split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("digest") {
+        return None;
+    }
+    let mut realm = None;
+    let mut nonce = None;
+    let mut opaque = None;
+    let mut algorithm = DigestAlgorithm::Md5;
+    let mut qop = None;
+    for (key, param) in parse_params(params) {
+        match key.to_ascii_lowercase().as_str() {
+            "realm" => realm = Some(param),
+            "nonce" => nonce = Some(param),
+            "opaque" => opaque = Some(param),
+            "qop" => qop = Some(param),
+            "algorithm" => {
+                algorithm = if param.eq_ignore_ascii_case("md5") {
+                    DigestAlgorithm::Md5
+                } else if param.eq_ignore_ascii_case("sha-256") {
+                    DigestAlgorithm::Sha256
+                } else {
+                    return None;
+                };
+            }
+            _ => {}
+        }
+    }
+    let qop_auth = match qop {
+        Some(qop) => {
+            if !qop.split(',').any(|option| option.trim() == "auth") {
+                return None;
+            }
+            true
+        }
+        None => false,
+    };
+    Some(DigestChallenge {
+        realm: realm?,
+        nonce: nonce?,
+        opaque,
+        algorithm,
+        qop_auth,
+    })
+}
+
+/// Splits `key=value, key="quoted, value"` auth parameters.
+fn parse_params(input: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = input.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_owned();
+        let after = after.trim_start();
+        let (value, remaining) = after.strip_prefix('"').map_or_else(
+            || {
+                let (value, remaining) = after.split_at(after.find(',').unwrap_or(after.len()));
+                (value.trim().to_owned(), remaining)
+            },
+            parse_quoted,
+        );
+        params.push((key, value));
+        rest = remaining.trim_start().trim_start_matches(',').trim_start();
+    }
+    params
+}
+
+/// Reads a quoted-string body up to its closing quote, unescaping `\x`.
+fn parse_quoted(quoted: &str) -> (String, &str) {
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    let mut end = quoted.len();
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            '"' => {
+                end = index.saturating_add(1);
+                break;
+            }
+            other => value.push(other),
+        }
+    }
+    (value, quoted.get(end..).unwrap_or(""))
+}
+
+/// Room for the field names, quotes, separators, qop fields, and the response hash.
+const HEADER_OVERHEAD: usize = 192;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+fn push_quoted(header: &mut String, key: &str, value: &str) {
+    header.push_str(key);
+    header.push_str("=\"");
+    header.push_str(value);
+    header.push('"');
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len().saturating_mul(2));
+    for byte in bytes {
+        for nibble in [byte >> 4, byte & 0x0f] {
+            hex.push(char::from(
+                HEX_DIGITS.get(usize::from(nibble)).copied().unwrap_or(b'0'),
+            ));
+        }
+    }
+    hex
+}
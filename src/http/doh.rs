@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{Client, Url};
+
+use crate::error::{AppError, AppResult, HttpError};
+
+const DNS_MESSAGE: &str = "application/dns-message";
+pub(in crate::http) const TYPE_A: u16 = 1;
+pub(in crate::http) const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+const HEADER_LEN: usize = 12;
+/// Top two bits of a label length byte mark a compression pointer.
+const POINTER_MASK: u8 = 0xC0;
+
+/// Resolves hostnames through a DNS-over-HTTPS endpoint (RFC 8484).
+///
+/// Installed with `ClientBuilder::dns_resolver`, so every host the client
+/// connects to is looked up here instead of through the system resolver.
+/// `--ipv4`/`--ipv6` decide which record types are queried. Answers are
+/// cached for their shortest TTL so new connections do not wait on the
+/// endpoint again.
+#[derive(Debug)]
+pub(in crate::http) struct DohResolver {
+    inner: Arc<DohQuery>,
+}
+
+#[derive(Debug)]
+struct DohQuery {
+    url: Url,
+    client: Client,
+    record_types: Vec<u16>,
+    cache: Mutex<HashMap<String, CachedAddrs>>,
+}
+
+#[derive(Debug)]
+struct CachedAddrs {
+    addrs: Vec<IpAddr>,
+    expires_at: Instant,
+}
+
+impl DohResolver {
+    pub(in crate::http) fn new(url: Url, client: Client, ipv4_only: bool, ipv6_only: bool) -> Self {
+        let record_types = match (ipv4_only, ipv6_only) {
+            (true, _) => vec![TYPE_A],
+            (false, true) => vec![TYPE_AAAA],
+            (false, false) => vec![TYPE_A, TYPE_AAAA],
+        };
+        Self {
+            inner: Arc::new(DohQuery {
+                url,
+                client,
+                record_types,
+                cache: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let query = self.inner.clone();
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs = match query.cached(host) {
+                Some(addrs) => addrs,
+                None => query.resolve_uncached(host).await?,
+            };
+            // The connector fills in the request port.
+            let addrs: Addrs = Box::new(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+impl DohQuery {
+    fn cached(&self, host: &str) -> Option<Vec<IpAddr>> {
+        let cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        cache
+            .get(host)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(|entry| entry.addrs.clone())
+    }
+
+    async fn resolve_uncached(&self, host: &str) -> AppResult<Vec<IpAddr>> {
+        let mut addrs = Vec::new();
+        let mut ttl = u32::MAX;
+        for record_type in &self.record_types {
+            let answers = self.lookup(host, *record_type).await?;
+            if let Some(record_ttl) = answers.ttl {
+                ttl = ttl.min(record_ttl);
+            }
+            addrs.extend(answers.addrs);
+        }
+        if addrs.is_empty() {
+            return Err(AppError::http(HttpError::NoAddressesResolved {
+                host: host.to_owned(),
+            }));
+        }
+        if ttl > 0
+            && let Some(expires_at) =
+                Instant::now().checked_add(Duration::from_secs(u64::from(ttl)))
+        {
+            let entry = CachedAddrs {
+                addrs: addrs.clone(),
+                expires_at,
+            };
+            self.cache
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(host.to_owned(), entry);
+        }
+        Ok(addrs)
+    }
+
+    async fn lookup(&self, host: &str, record_type: u16) -> AppResult<DohAnswers> {
+        let request_failed = |source| {
+            AppError::http(HttpError::DohRequest {
+                host: host.to_owned(),
+                source,
+            })
+        };
+        let response = self
+            .client
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, DNS_MESSAGE)
+            .header(ACCEPT, DNS_MESSAGE)
+            .body(encode_query(host, record_type)?)
+            .send()
+            .await
+            .map_err(request_failed)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AppError::http(HttpError::DohStatus {
+                host: host.to_owned(),
+                status: status.as_u16(),
+            }));
+        }
+        let body = response.bytes().await.map_err(request_failed)?;
+        decode_answers(&body, record_type).ok_or_else(|| {
+            AppError::http(HttpError::DohMalformedResponse {
+                host: host.to_owned(),
+            })
+        })
+    }
+}
+
+/// Builds a recursive query for `host` with ID 0, as RFC 8484 recommends for caching.
+pub(in crate::http) fn encode_query(host: &str, record_type: u16) -> AppResult<Vec<u8>> {
+    let mut message = vec![0, 0, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in host.trim_end_matches('.').split('.') {
+        let len = u8::try_from(label.len())
+            .ok()
+            .filter(|len| (1..=63).contains(len))
+            .ok_or_else(|| {
+                AppError::http(HttpError::DohInvalidHost {
+                    host: host.to_owned(),
+                })
+            })?;
+        message.push(len);
+        message.extend_from_slice(label.as_bytes());
+    }
+    message.push(0);
+    message.extend_from_slice(&record_type.to_be_bytes());
+    message.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(message)
+}
+
+/// Addresses of one record type from a DoH response.
+#[derive(Debug, PartialEq, Eq)]
+pub(in crate::http) struct DohAnswers {
+    pub(in crate::http) addrs: Vec<IpAddr>,
+    /// Shortest TTL of the returned records in seconds; `None` without records.
+    pub(in crate::http) ttl: Option<u32>,
+}
+
+/// Returns the `record_type` addresses in a response, or `None` when it cannot be parsed.
+///
+/// CNAME and other records in the answer section are skipped; the addresses
+/// the resolver chased them to follow in the same section.
+pub(in crate::http) fn decode_answers(message: &[u8], record_type: u16) -> Option<DohAnswers> {
+    let question_count = read_u16(message, 4)?;
+    let answer_count = read_u16(message, 6)?;
+    let mut offset = HEADER_LEN;
+    for _ in 0..question_count {
+        offset = skip_name(message, offset)?.checked_add(4)?;
+    }
+    let mut addrs = Vec::new();
+    let mut ttl: Option<u32> = None;
+    for _ in 0..answer_count {
+        offset = skip_name(message, offset)?;
+        let answer_type = read_u16(message, offset)?;
+        let record_ttl = read_u32(message, offset.checked_add(4)?)?;
+        let rdlength = usize::from(read_u16(message, offset.checked_add(8)?)?);
+        let start = offset.checked_add(10)?;
+        let end = start.checked_add(rdlength)?;
+        let rdata = message.get(start..end)?;
+        match (answer_type, rdata.len()) {
+            (TYPE_A, 4) if record_type == TYPE_A => {
+                let octets: [u8; 4] = rdata.try_into().ok()?;
+                addrs.push(IpAddr::V4(Ipv4Addr::from(octets)));
+                ttl = Some(ttl.map_or(record_ttl, |current| current.min(record_ttl)));
+            }
+            (TYPE_AAAA, 16) if record_type == TYPE_AAAA => {
+                let octets: [u8; 16] = rdata.try_into().ok()?;
+                addrs.push(IpAddr::V6(Ipv6Addr::from(octets)));
+                ttl = Some(ttl.map_or(record_ttl, |current| current.min(record_ttl)));
+            }
+            _ => {}
+        }
+        offset = end;
+    }
+    Some(DohAnswers { addrs, ttl })
+}
+
+fn read_u16(message: &[u8], offset: usize) -> Option<u16> {
+    let bytes = message.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u32(message: &[u8], offset: usize) -> Option<u32> {
+    let bytes = message.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn skip_name(message: &[u8], mut offset: usize) -> Option<usize> {
+    loop {
+        let len = *message.get(offset)?;
+        if len & POINTER_MASK == POINTER_MASK {
+            return offset.checked_add(2);
+        }
+        offset = offset.checked_add(1)?;
+        if len == 0 {
+            return Some(offset);
+        }
+        offset = offset.checked_add(usize::from(len))?;
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use crate::args::LinkRate;
+
+use super::bandwidth::BandwidthLimiter;
+
+/// Per-task link shaping for `--sim-downlink` and `--sim-latency`.
+///
+/// Each worker task owns its own instance, so the downlink budget models one
+/// client's connection rather than a cap shared across the run.
+#[derive(Debug)]
+pub(crate) struct EdgeSim {
+    downlink: Option<BandwidthLimiter>,
+    latency: Option<Duration>,
+}
+
+impl EdgeSim {
+    pub(crate) fn new(downlink: Option<LinkRate>, latency: Option<Duration>) -> Option<Self> {
+        if downlink.is_none() && latency.is_none() {
+            return None;
+        }
+        Some(Self {
+            downlink: downlink.map(|rate| BandwidthLimiter::new(rate.bytes_per_sec())),
+            latency,
+        })
+    }
+
+    /// Waits out the added latency before a request is sent.
+    pub(crate) async fn delay_send(&self) {
+        if let Some(latency) = self.latency {
+            sleep(latency).await;
+        }
+    }
+
+    /// Charges a received body chunk against the task's downlink budget.
+    pub(crate) async fn throttle_read(&self, bytes: usize) {
+        if let Some(downlink) = self.downlink.as_ref() {
+            downlink
+                .consume(u64::try_from(bytes).unwrap_or(u64::MAX))
+                .await;
+        }
+    }
+}
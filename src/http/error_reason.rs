@@ -0,0 +1,61 @@
+use std::error::Error as StdError;
+use std::io::ErrorKind;
+
+use crate::metrics::ErrorReason;
+
+/// I/O error kinds that mean the peer dropped an established connection.
+const RESET_KINDS: [ErrorKind; 4] = [
+    ErrorKind::ConnectionReset,
+    ErrorKind::ConnectionAborted,
+    ErrorKind::BrokenPipe,
+    ErrorKind::UnexpectedEof,
+];
+
+/// Buckets a failed request by the most specific cause found in its error chain.
+pub(crate) fn classify(err: &reqwest::Error) -> ErrorReason {
+    if err.is_timeout() {
+        return ErrorReason::Timeout;
+    }
+    let mut source: Option<&(dyn StdError + 'static)> = err.source();
+    while let Some(cause) = source {
+        if let Some(reason) = cause_reason(cause) {
+            return reason;
+        }
+        source = cause.source();
+    }
+    if err.is_connect() {
+        ErrorReason::Connect
+    } else if err.is_body() || err.is_decode() {
+        ErrorReason::Body
+    } else if err.is_redirect() {
+        ErrorReason::Redirect
+    } else {
+        ErrorReason::Other
+    }
+}
+
+fn cause_reason(cause: &(dyn StdError + 'static)) -> Option<ErrorReason> {
+    if let Some(io_err) = cause.downcast_ref::<std::io::Error>() {
+        let kind = io_err.kind();
+        if kind == ErrorKind::ConnectionRefused {
+            return Some(ErrorReason::ConnectionRefused);
+        }
+        if RESET_KINDS.contains(&kind) {
+            return Some(ErrorReason::ConnectionReset);
+        }
+    }
+    let message = cause.to_string().to_ascii_lowercase();
+    if message.starts_with("connection closed") {
+        // hyper's incomplete message: the peer hung up before responding.
+        Some(ErrorReason::ConnectionReset)
+    } else if message.starts_with("dns error") || message.contains("failed to lookup address") {
+        Some(ErrorReason::Dns)
+    } else if ["tls", "ssl", "certificate", "handshake"]
+        .iter()
+        .any(|needle| message.contains(needle))
+    {
+        Some(ErrorReason::Tls)
+    } else {
+        None
+    }
+}
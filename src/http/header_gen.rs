@@ -0,0 +1,283 @@
+use std::fs::File;
+use std::io::{BufWriter, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc, Mutex, PoisonError,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+};
+use std::time::Duration;
+
+use reqwest::Request;
+use reqwest::header::{HeaderName, HeaderValue};
+use tracing::warn;
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult, HttpError, ValidationError};
+
+use super::workload::write_uuid_v4;
+
+/// Placeholder replaced with a fresh random UUID (v4) on every request.
+const UUID_TOKEN: &str = "uuid}";
+/// Placeholder replaced with a per-run counter starting at 1.
+const SEQ_TOKEN: &str = "seq}";
+const PLACEHOLDER_START: &str = "${";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Uuid,
+    Seq,
+}
+
+/// A header value split into literal text and `${uuid}`/`${seq}` placeholders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HeaderTemplate {
+    segments: Vec<Segment>,
+}
+
+impl HeaderTemplate {
+    /// Parses `value`; unknown `${...}` sequences are kept as literal text.
+    fn parse(value: &str) -> Self {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = value;
+        while let Some((before, tail)) = rest.split_once(PLACEHOLDER_START) {
+            literal.push_str(before);
+            let placeholder = if let Some(after) = tail.strip_prefix(UUID_TOKEN) {
+                rest = after;
+                Segment::Uuid
+            } else if let Some(after) = tail.strip_prefix(SEQ_TOKEN) {
+                rest = after;
+                Segment::Seq
+            } else {
+                literal.push_str(PLACEHOLDER_START);
+                rest = tail;
+                continue;
+            };
+            if !literal.is_empty() {
+                segments.push(Segment::Literal(std::mem::take(&mut literal)));
+            }
+            segments.push(placeholder);
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Self { segments }
+    }
+
+    fn is_generated(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| !matches!(segment, Segment::Literal(_)))
+    }
+
+    fn uses_uuid(&self) -> bool {
+        self.segments.contains(&Segment::Uuid)
+    }
+
+    fn render(&self, uuid: &str, seq: u64) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Uuid => out.push_str(uuid),
+                Segment::Seq => out.push_str(&seq.to_string()),
+            }
+        }
+        out
+    }
+}
+
+/// Values generated for one request, in header order.
+#[derive(Debug)]
+pub(crate) struct GeneratedIds {
+    seq: u64,
+    values: Vec<HeaderValue>,
+}
+
+/// Headers whose value changes on every request, from `${uuid}`/`${seq}`
+/// templates in `-H` and from `--correlation-header`.
+///
+/// All placeholders of one request share the same UUID and sequence number,
+/// so an idempotency key and a request id can carry the same value.
+#[derive(Debug)]
+pub(crate) struct GeneratedHeaders {
+    headers: Vec<(HeaderName, HeaderTemplate)>,
+    needs_uuid: bool,
+    seq: AtomicU64,
+    log: Option<CorrelationLog>,
+}
+
+impl GeneratedHeaders {
+    /// Moves the templated entries out of `headers` and adds the
+    /// `--correlation-header`, replacing any static header of the same name.
+    pub(crate) fn from_args(
+        args: &TesterArgs,
+        headers: &mut Vec<(String, String)>,
+    ) -> AppResult<Option<Arc<Self>>> {
+        let mut templates = Vec::new();
+        let mut static_headers = Vec::with_capacity(headers.len());
+        for (name, value) in headers.drain(..) {
+            let template = HeaderTemplate::parse(&value);
+            if template.is_generated() {
+                templates.push((name, template));
+            } else {
+                static_headers.push((name, value));
+            }
+        }
+        if let Some(name) = args.correlation_header.as_ref() {
+            let header = HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+                AppError::validation(ValidationError::InvalidCorrelationHeader {
+                    header: name.clone(),
+                    source: err,
+                })
+            })?;
+            templates.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+            static_headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+            templates.push((header.as_str().to_owned(), HeaderTemplate::parse("${uuid}")));
+        }
+        *headers = static_headers;
+
+        if templates.is_empty() {
+            if args.correlation_log.is_some() {
+                return Err(AppError::validation(
+                    ValidationError::CorrelationLogWithoutGeneratedHeader,
+                ));
+            }
+            return Ok(None);
+        }
+
+        let mut parsed = Vec::with_capacity(templates.len());
+        for (name, template) in templates {
+            let header = HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+                AppError::validation(ValidationError::InvalidGeneratedHeaderName {
+                    header: name.clone(),
+                    source: err,
+                })
+            })?;
+            // Placeholders render to ASCII alphanumerics and dashes, so a
+            // sample rendering validates every value the run will send.
+            let sample = template.render("00000000-0000-0000-0000-000000000000", 0);
+            HeaderValue::from_str(&sample).map_err(|err| {
+                AppError::validation(ValidationError::InvalidHeaderTemplate {
+                    header: name.clone(),
+                    source: err,
+                })
+            })?;
+            parsed.push((header, template));
+        }
+        let log = args
+            .correlation_log
+            .as_deref()
+            .map(|path| CorrelationLog::create(Path::new(path), &parsed))
+            .transpose()?;
+        Ok(Some(Arc::new(Self::new(parsed, log))))
+    }
+
+    fn new(headers: Vec<(HeaderName, HeaderTemplate)>, log: Option<CorrelationLog>) -> Self {
+        let needs_uuid = headers.iter().any(|(_, template)| template.uses_uuid());
+        Self {
+            headers,
+            needs_uuid,
+            seq: AtomicU64::new(1),
+            log,
+        }
+    }
+
+    /// Renders a fresh set of values and sets them on `request`.
+    pub(crate) fn apply(&self, request: &mut Request) -> GeneratedIds {
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+        let mut uuid = String::new();
+        if self.needs_uuid {
+            write_uuid_v4(&mut rand::thread_rng(), &mut uuid);
+        }
+        let mut values = Vec::with_capacity(self.headers.len());
+        for (name, template) in &self.headers {
+            let Ok(value) = HeaderValue::from_str(&template.render(&uuid, seq)) else {
+                continue;
+            };
+            request.headers_mut().insert(name.clone(), value.clone());
+            values.push(value);
+        }
+        GeneratedIds { seq, values }
+    }
+
+    /// Appends the outcome of the request that carried `ids` to `--correlation-log`.
+    pub(crate) fn record(&self, ids: &GeneratedIds, status: u16, success: bool, latency: Duration) {
+        if let Some(log) = self.log.as_ref() {
+            log.record(ids, status, success, latency);
+        }
+    }
+}
+
+/// CSV file of generated header values and the outcome of each request.
+#[derive(Debug)]
+struct CorrelationLog {
+    path: PathBuf,
+    writer: Mutex<BufWriter<File>>,
+    failed: AtomicBool,
+}
+
+impl CorrelationLog {
+    fn create(path: &Path, headers: &[(HeaderName, HeaderTemplate)]) -> AppResult<Self> {
+        let map_err = |err| {
+            AppError::http(HttpError::WriteCorrelationLog {
+                path: path.to_path_buf(),
+                source: err,
+            })
+        };
+        let mut writer = BufWriter::new(File::create(path).map_err(map_err)?);
+        let mut header_row = String::from("seq,status,success,latency_ms");
+        for (name, _) in headers {
+            header_row.push(',');
+            header_row.push_str(&csv_field(name.as_str()));
+        }
+        writeln!(writer, "{}", header_row).map_err(map_err)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: Mutex::new(writer),
+            failed: AtomicBool::new(false),
+        })
+    }
+
+    fn record(&self, ids: &GeneratedIds, status: u16, success: bool, latency: Duration) {
+        let mut row = format!("{},{},{},{}", ids.seq, status, success, latency.as_millis());
+        for value in &ids.values {
+            row.push(',');
+            row.push_str(&csv_field(&String::from_utf8_lossy(value.as_bytes())));
+        }
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(err) = writeln!(writer, "{}", row)
+            && !self.failed.swap(true, Ordering::Relaxed)
+        {
+            warn!(
+                "Failed to write correlation log '{}': {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+impl Drop for CorrelationLog {
+    fn drop(&mut self) {
+        if let Ok(writer) = self.writer.get_mut()
+            && let Err(err) = writer.flush()
+        {
+            warn!(
+                "Failed to flush correlation log '{}': {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
@@ -1,16 +1,40 @@
 //! HTTP request execution and workload orchestration.
+mod bandwidth;
+mod body_assert;
+mod conn_timing;
+mod connect_rate;
+mod connection_cycle;
+mod cookies;
+mod deadline;
+mod describe;
+mod diagnose;
+mod digest;
+mod doh;
+mod edge_sim;
+mod error_reason;
+mod header_gen;
+mod multiplex;
+mod oauth2;
 mod rate;
+mod request_id;
+mod response_size;
 mod sender;
-mod tls;
-pub(crate) mod workload;
-
+mod sticky;
 #[cfg(test)]
 mod tests;
+mod tls;
+pub(crate) mod workload;
 
-pub(crate) use rate::build_rate_limiter;
+pub use describe::{WorkloadDescription, describe_workload};
+pub use diagnose::run_diagnostics;
+pub(crate) use multiplex::Http2Multiplex;
+pub(crate) use rate::{MaxRateSettings, RatePacing, build_rate_limiter, until_next_wall_second};
+pub(crate) use sender::apply_proxy_settings;
 pub use sender::setup_request_sender;
+pub use sender::{STDIN_URL_PATH, read_stdin_urls};
+pub(crate) use tls::apply_trust_settings;
 
 #[cfg(test)]
-pub(crate) use rate::{RateController, RatePlan, RateStage};
+pub(crate) use rate::{RateController, RatePlan, RateStage, SineStage, mean_gap_ns, next_gap_ns};
 #[cfg(test)]
 pub(crate) use tls::{AlpnChoice, resolve_alpn};
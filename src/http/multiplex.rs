@@ -0,0 +1,207 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+use std::task::{Context, Poll};
+
+use reqwest::Client;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Scale for the average streams per connection (x100).
+const AVG_SCALE: u64 = 100;
+
+/// Run-wide stream accounting for `--http2-parallel`.
+///
+/// Each `--max-tasks` slot gets its own client, and so its own HTTP/2
+/// connection, shared by `parallel` workers. The connector layer counts the
+/// connections actually opened; a client falls back to several connections
+/// when HTTP/2 is not negotiated or the server caps concurrent streams.
+#[derive(Debug)]
+pub(crate) struct Http2Multiplex {
+    parallel: usize,
+    tasks: usize,
+    connections: Arc<AtomicU64>,
+    streams: AtomicU64,
+    stream_sum: AtomicU64,
+}
+
+impl Http2Multiplex {
+    pub(crate) fn new(parallel: usize, tasks: usize) -> Self {
+        Self {
+            parallel,
+            tasks,
+            connections: Arc::new(AtomicU64::new(0)),
+            streams: AtomicU64::new(0),
+            stream_sum: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) const fn parallel(&self) -> usize {
+        self.parallel
+    }
+
+    pub(crate) fn connections(&self) -> u64 {
+        self.connections.load(Ordering::Relaxed)
+    }
+
+    /// Counter bumped by [`CountConnectionsLayer`] for the slot clients.
+    pub(crate) const fn connection_counter(&self) -> &Arc<AtomicU64> {
+        &self.connections
+    }
+
+    pub(crate) fn streams(&self) -> u64 {
+        self.streams.load(Ordering::Relaxed)
+    }
+
+    /// Average number of concurrent streams on a connection as seen by each
+    /// request when it started (itself included), x100.
+    pub(crate) fn avg_streams_x100(&self) -> u64 {
+        self.stream_sum
+            .load(Ordering::Relaxed)
+            .saturating_mul(AVG_SCALE)
+            .checked_div(self.streams())
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn report(&self) -> Http2Report {
+        Http2Report {
+            parallel: self.parallel,
+            tasks: self.tasks,
+            connections: self.connections(),
+            streams: self.streams(),
+            avg_streams_x100: self.avg_streams_x100(),
+        }
+    }
+}
+
+/// The client of one `--max-tasks` slot and its open streams.
+#[derive(Debug)]
+pub(crate) struct StreamGroup {
+    client: Client,
+    multiplex: Arc<Http2Multiplex>,
+    open: AtomicU64,
+}
+
+impl StreamGroup {
+    pub(crate) const fn new(client: Client, multiplex: Arc<Http2Multiplex>) -> Self {
+        Self {
+            client,
+            multiplex,
+            open: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) const fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Marks a request as in flight on this connection until the guard drops.
+    pub(crate) fn begin(&self) -> StreamGuard<'_> {
+        let open = self.open.fetch_add(1, Ordering::Relaxed).saturating_add(1);
+        self.multiplex.streams.fetch_add(1, Ordering::Relaxed);
+        self.multiplex.stream_sum.fetch_add(open, Ordering::Relaxed);
+        StreamGuard { group: self }
+    }
+}
+
+pub(crate) struct StreamGuard<'group> {
+    group: &'group StreamGroup,
+}
+
+impl Drop for StreamGuard<'_> {
+    fn drop(&mut self) {
+        self.group.open.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Connector layer that counts every connection a client opens.
+#[derive(Debug, Clone)]
+pub(crate) struct CountConnectionsLayer {
+    connections: Arc<AtomicU64>,
+}
+
+impl CountConnectionsLayer {
+    pub(crate) const fn new(connections: Arc<AtomicU64>) -> Self {
+        Self { connections }
+    }
+}
+
+impl<S> Layer<S> for CountConnectionsLayer {
+    type Service = CountConnections<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CountConnections {
+            inner,
+            connections: self.connections.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CountConnections<S> {
+    inner: S,
+    connections: Arc<AtomicU64>,
+}
+
+impl<S, Request> Service<Request> for CountConnections<S>
+where
+    S: Service<Request>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        self.connections.fetch_add(1, Ordering::Relaxed);
+        Box::pin(self.inner.call(request))
+    }
+}
+
+/// One [`StreamGroup`] per `--max-tasks` slot, each shared by `parallel` workers.
+#[derive(Debug)]
+pub(crate) struct Http2Streams {
+    multiplex: Arc<Http2Multiplex>,
+    groups: Vec<Arc<StreamGroup>>,
+}
+
+impl Http2Streams {
+    pub(crate) const fn new(multiplex: Arc<Http2Multiplex>, groups: Vec<Arc<StreamGroup>>) -> Self {
+        Self { multiplex, groups }
+    }
+
+    pub(crate) fn parallel(&self) -> usize {
+        self.multiplex.parallel()
+    }
+
+    pub(crate) fn group_for(&self, worker_index: usize) -> Option<&Arc<StreamGroup>> {
+        worker_index
+            .checked_div(self.parallel())
+            .and_then(|index| self.groups.get(index))
+    }
+
+    pub(crate) fn client(&self) -> Option<&Client> {
+        self.groups.first().map(|group| group.client())
+    }
+
+    pub(crate) fn report(&self) -> Http2Report {
+        self.multiplex.report()
+    }
+}
+
+/// End-of-run multiplexing figures for `--http2-parallel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Http2Report {
+    pub(crate) parallel: usize,
+    pub(crate) tasks: usize,
+    pub(crate) connections: u64,
+    pub(crate) streams: u64,
+    pub(crate) avg_streams_x100: u64,
+}
@@ -0,0 +1,149 @@
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::error::{AppError, AppResult, HttpError};
+use crate::shutdown::ShutdownSender;
+
+const DEFAULT_EXPIRES_IN: Duration = Duration::from_secs(3_600);
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+const MIN_REFRESH_DELAY: Duration = Duration::from_secs(1);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// OAuth2 client-credentials session shared by every worker.
+///
+/// Every clone shares one token slot, so a refresh is seen by all workers
+/// on their next request; the refresh task only takes the write lock for the
+/// pointer swap.
+#[derive(Debug, Clone)]
+pub(crate) struct OAuth2Session {
+    credentials: Arc<OAuth2Credentials>,
+    token: Arc<RwLock<Arc<str>>>,
+}
+
+#[derive(Debug)]
+struct OAuth2Credentials {
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl OAuth2Session {
+    pub(crate) fn new(
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        scope: Option<String>,
+    ) -> Self {
+        Self {
+            credentials: Arc::new(OAuth2Credentials {
+                token_url,
+                client_id,
+                client_secret,
+                scope,
+            }),
+            token: Arc::new(RwLock::new(Arc::from(""))),
+        }
+    }
+
+    /// `Authorization` header value for the current token.
+    pub(crate) fn bearer(&self) -> String {
+        format!("Bearer {}", self.current_token())
+    }
+
+    fn current_token(&self) -> Arc<str> {
+        Arc::clone(&self.token.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn set_token(&self, access_token: &str) {
+        *self.token.write().unwrap_or_else(PoisonError::into_inner) = Arc::from(access_token);
+    }
+
+    /// Fetches the first token, then keeps it fresh until shutdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the first token request fails.
+    pub(crate) async fn start(
+        &self,
+        client: &Client,
+        shutdown_tx: &ShutdownSender,
+    ) -> AppResult<()> {
+        let expires_in = self.fetch_token(client).await?;
+        info!(
+            "Fetched OAuth2 token from {} (expires in {}s).",
+            self.credentials.token_url,
+            expires_in.as_secs()
+        );
+
+        let session = self.clone();
+        let client = client.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            let mut delay = refresh_delay(expires_in);
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => return,
+                    () = tokio::time::sleep(delay) => {}
+                }
+                delay = match session.fetch_token(&client).await {
+                    Ok(next_expiry) => refresh_delay(next_expiry),
+                    Err(err) => {
+                        warn!("OAuth2 token refresh failed, retrying: {}", err);
+                        RETRY_DELAY
+                    }
+                };
+            }
+        });
+        Ok(())
+    }
+
+    async fn fetch_token(&self, client: &Client) -> AppResult<Duration> {
+        let credentials = &self.credentials;
+        let mut form = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", credentials.client_id.as_str()),
+            ("client_secret", credentials.client_secret.as_str()),
+        ];
+        if let Some(scope) = credentials.scope.as_deref() {
+            form.push(("scope", scope));
+        }
+        let request_failed = |source| AppError::http(HttpError::OAuth2TokenRequest { source });
+        let response = client
+            .post(&credentials.token_url)
+            .form(&form)
+            .send()
+            .await
+            .map_err(request_failed)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AppError::http(HttpError::OAuth2TokenStatus {
+                status: status.as_u16(),
+            }));
+        }
+        let body = response.bytes().await.map_err(request_failed)?;
+        let parsed: TokenResponse = serde_json::from_slice(&body)
+            .map_err(|source| AppError::http(HttpError::OAuth2TokenResponse { source }))?;
+        self.set_token(&parsed.access_token);
+        Ok(parsed
+            .expires_in
+            .map_or(DEFAULT_EXPIRES_IN, Duration::from_secs))
+    }
+}
+
+/// Refreshes 30s before expiry, but never more often than once a second.
+pub(crate) fn refresh_delay(expires_in: Duration) -> Duration {
+    expires_in
+        .saturating_sub(REFRESH_MARGIN)
+        .max(MIN_REFRESH_DELAY)
+}
@@ -1,11 +1,21 @@
+mod adaptive;
+mod arrival;
+
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use tokio::sync::Semaphore;
-use tokio::time::interval;
+use tokio::time::{Interval, interval, sleep};
 
 use crate::args::LoadProfile;
 
+pub(crate) use adaptive::MaxRateSettings;
+use adaptive::spawn_max_rate_controller;
+pub(crate) use arrival::RatePacing;
+use arrival::{RateSource, spawn_paced_rate_controller};
+#[cfg(test)]
+pub(crate) use arrival::{mean_gap_ns, next_gap_ns};
+
 /// Seconds per minute for RPM → RPS conversions.
 const SECS_PER_MIN: u64 = 60;
 /// Tick interval for rate control updates.
@@ -16,6 +26,8 @@ const MIN_STAGE_SECS: u64 = 1;
 const MIN_BURST_RATE: usize = 1;
 /// Minimum delay between burst ticks.
 const MIN_BURST_DELAY: Duration = Duration::from_millis(1);
+/// A timer that fires just before a second boundary waits for the following one.
+const MIN_ALIGN_GAP: Duration = Duration::from_millis(50);
 
 #[derive(Clone)]
 pub(crate) struct RatePlan {
@@ -27,6 +39,43 @@ pub(crate) struct RatePlan {
 pub(crate) struct RateStage {
     pub duration_secs: u64,
     pub target_rpm: u64,
+    pub sine: Option<SineStage>,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct SineStage {
+    pub min_rpm: u64,
+    pub max_rpm: u64,
+    pub period_secs: u64,
+}
+
+impl SineStage {
+    /// `min + (max - min) * sin²(π·t/period)`: starts at `min_rpm` and peaks
+    /// at `max_rpm` half a period in.
+    ///
+    /// `sin` uses Bhaskara's approximation `16x(π−x) / (5π² − 4x(π−x))`, which
+    /// stays within 0.2% of the true curve and keeps the controller in integer math.
+    pub(crate) fn rpm_at(&self, elapsed_secs: u64) -> u64 {
+        let period = u128::from(self.period_secs.max(MIN_STAGE_SECS));
+        let phase = u128::from(elapsed_secs).checked_rem(period).unwrap_or(0);
+        let product = phase.saturating_mul(period.saturating_sub(phase));
+        let numerator = product.saturating_mul(16);
+        let denominator = period
+            .saturating_mul(period)
+            .saturating_mul(5)
+            .saturating_sub(product.saturating_mul(4));
+        let span = u128::from(self.max_rpm.saturating_sub(self.min_rpm));
+        let offset = span
+            .saturating_mul(numerator)
+            .checked_div(denominator)
+            .unwrap_or(0)
+            .saturating_mul(numerator)
+            .checked_div(denominator)
+            .unwrap_or(0);
+        self.min_rpm
+            .saturating_add(u64::try_from(offset).unwrap_or(u64::MAX))
+            .min(self.max_rpm.max(self.min_rpm))
+    }
 }
 
 pub(crate) struct RateController {
@@ -38,6 +87,17 @@ pub(crate) struct RateController {
 }
 
 impl RateController {
+    const fn new(plan: RatePlan) -> Self {
+        let initial_rpm = plan.initial_rpm;
+        Self {
+            plan,
+            stage_idx: 0,
+            stage_elapsed_secs: 0,
+            stage_start_rpm: initial_rpm,
+            remainder: 0,
+        }
+    }
+
     pub(crate) fn next_tokens(&mut self) -> usize {
         let rpm = self.current_rpm();
         let (base, rem) = div_mod_u64(rpm, SECS_PER_MIN);
@@ -55,6 +115,17 @@ impl RateController {
 
         let stage_secs = stage.duration_secs.max(MIN_STAGE_SECS);
         let elapsed = self.stage_elapsed_secs.min(stage_secs);
+        if let Some(sine) = stage.sine {
+            let rpm = sine.rpm_at(elapsed);
+            self.stage_elapsed_secs = self.stage_elapsed_secs.saturating_add(1);
+            if self.stage_elapsed_secs >= stage_secs {
+                // The next stage ramps from wherever the wave stopped.
+                self.stage_start_rpm = sine.rpm_at(stage_secs);
+                self.stage_idx = self.stage_idx.saturating_add(1);
+                self.stage_elapsed_secs = 0;
+            }
+            return rpm;
+        }
 
         let start = i128::from(self.stage_start_rpm);
         let target = i128::from(stage.target_rpm);
@@ -84,22 +155,71 @@ impl RateController {
     }
 }
 
+/// Time left until the next whole wall-clock second.
+///
+/// Used by `--align-to-second`; never shorter than [`MIN_ALIGN_GAP`] so an
+/// early timer wakeup does not produce two ticks for the same second.
+pub(crate) fn until_next_wall_second() -> Duration {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let into_second = Duration::from_nanos(u64::from(since_epoch.subsec_nanos()));
+    let remaining = RATE_TICK_INTERVAL.saturating_sub(into_second);
+    if remaining < MIN_ALIGN_GAP {
+        remaining.saturating_add(RATE_TICK_INTERVAL)
+    } else {
+        remaining
+    }
+}
+
+async fn next_rate_tick(rate_tick: &mut Interval, align_to_second: bool) {
+    if align_to_second {
+        sleep(until_next_wall_second()).await;
+    } else {
+        rate_tick.tick().await;
+    }
+}
+
 pub(crate) fn build_rate_limiter(
     rate_limit: Option<u64>,
     load_profile: Option<&LoadProfile>,
     burst_delay: Option<Duration>,
     burst_rate: usize,
+    pacing: RatePacing,
+    max_rate: Option<MaxRateSettings>,
 ) -> Option<Arc<Semaphore>> {
+    if let Some(settings) = max_rate {
+        let limiter = Arc::new(Semaphore::new(0));
+        spawn_max_rate_controller(limiter.clone(), settings, pacing.align_to_second);
+        return Some(limiter);
+    }
+
     if let Some(profile) = load_profile {
         let plan = RatePlan::from(profile);
         let limiter = Arc::new(Semaphore::new(0));
-        spawn_rate_controller(limiter.clone(), plan);
+        match pacing.arrival {
+            Some(distribution) => spawn_paced_rate_controller(
+                limiter.clone(),
+                RateSource::Plan(RateController::new(plan)),
+                distribution,
+            ),
+            None => spawn_rate_controller(limiter.clone(), plan, pacing.align_to_second),
+        }
         return Some(limiter);
     }
 
     if let Some(rate) = rate_limit {
         let limiter = Arc::new(Semaphore::new(0));
-        spawn_fixed_rate_controller(limiter.clone(), rate);
+        match pacing.arrival {
+            Some(distribution) => spawn_paced_rate_controller(
+                limiter.clone(),
+                RateSource::Fixed {
+                    rpm: rate.saturating_mul(SECS_PER_MIN),
+                },
+                distribution,
+            ),
+            None => spawn_fixed_rate_controller(limiter.clone(), rate, pacing.align_to_second),
+        }
         return Some(limiter);
     }
 
@@ -112,13 +232,13 @@ pub(crate) fn build_rate_limiter(
     None
 }
 
-fn spawn_fixed_rate_controller(limiter: Arc<Semaphore>, rate: u64) {
+fn spawn_fixed_rate_controller(limiter: Arc<Semaphore>, rate: u64, align_to_second: bool) {
     tokio::spawn(async move {
         let rate_per_sec = usize::try_from(rate).unwrap_or(usize::MAX);
         limiter.add_permits(rate_per_sec);
         let mut rate_tick = interval(RATE_TICK_INTERVAL);
         loop {
-            rate_tick.tick().await;
+            next_rate_tick(&mut rate_tick, align_to_second).await;
             let available = limiter.available_permits();
             if available < rate_per_sec {
                 limiter.add_permits(rate_per_sec.saturating_sub(available));
@@ -127,22 +247,15 @@ fn spawn_fixed_rate_controller(limiter: Arc<Semaphore>, rate: u64) {
     });
 }
 
-fn spawn_rate_controller(limiter: Arc<Semaphore>, plan: RatePlan) {
+fn spawn_rate_controller(limiter: Arc<Semaphore>, plan: RatePlan, align_to_second: bool) {
     tokio::spawn(async move {
-        let initial_rpm = plan.initial_rpm;
-        let mut controller = RateController {
-            plan,
-            stage_idx: 0,
-            stage_elapsed_secs: 0,
-            stage_start_rpm: initial_rpm,
-            remainder: 0,
-        };
+        let mut controller = RateController::new(plan);
         let initial = controller.next_tokens();
         limiter.add_permits(initial);
 
         let mut rate_tick = interval(RATE_TICK_INTERVAL);
         loop {
-            rate_tick.tick().await;
+            next_rate_tick(&mut rate_tick, align_to_second).await;
             let available = limiter.available_permits();
             let target = controller.next_tokens();
             if available < target {
@@ -184,6 +297,11 @@ impl RatePlan {
             .map(|stage| RateStage {
                 duration_secs: stage.duration.as_secs().max(MIN_STAGE_SECS),
                 target_rpm: stage.target_rpm,
+                sine: stage.sine.map(|sine| SineStage {
+                    min_rpm: sine.min_rpm,
+                    max_rpm: sine.max_rpm,
+                    period_secs: sine.period.as_secs().max(MIN_STAGE_SECS),
+                }),
             })
             .collect();
         Self {
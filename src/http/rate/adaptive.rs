@@ -0,0 +1,182 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::time::{Instant, interval};
+use tracing::info;
+
+use crate::args::TesterArgs;
+use crate::metrics::RateFeedback;
+use crate::run_context::RunContext;
+
+use super::{RATE_TICK_INTERVAL, next_rate_tick};
+
+/// Starting rate when `--find-max-rate` runs without `--rate`.
+const DEFAULT_START_RPS: u64 = 10;
+/// Fewest latencies in the collector window before a step's p99 is trusted.
+const MIN_STEP_SAMPLES: u64 = 20;
+/// The search settles once the held and breached rates are within 1/20 of
+/// the held rate.
+const SETTLE_GAP_DIVISOR: u64 = 20;
+
+/// Settings for the `--find-max-rate` controller.
+#[derive(Clone)]
+pub(crate) struct MaxRateSettings {
+    start_rps: u64,
+    target_p99_ms: u64,
+    /// How long each rate is held before its p99 is judged; matches
+    /// `--ui-window-ms` so the collector window only covers the current step.
+    step: Duration,
+    feedback: Arc<RateFeedback>,
+}
+
+impl MaxRateSettings {
+    pub(crate) fn from_args(args: &TesterArgs, run: &RunContext) -> Option<Self> {
+        let feedback = run.rate_feedback.clone()?;
+        let target_p99_ms = args.target_p99_ms?.get();
+        Some(Self {
+            start_rps: args.rate_limit.map_or(DEFAULT_START_RPS, u64::from),
+            target_p99_ms,
+            step: Duration::from_millis(args.ui_window_ms.get()),
+            feedback,
+        })
+    }
+}
+
+/// Capacity search behind `--find-max-rate`.
+///
+/// Doubles the rate while p99 holds the target, then bisects between the
+/// highest rate that held and the lowest that breached. A breach at or below
+/// the held rate halves it, so the search backs off if the target degrades.
+#[derive(Debug)]
+pub(crate) struct MaxRateSearch {
+    target_p99_ms: u64,
+    rate: u64,
+    held: u64,
+    breached: Option<u64>,
+}
+
+impl MaxRateSearch {
+    pub(crate) fn new(start_rps: u64, target_p99_ms: u64) -> Self {
+        Self {
+            target_p99_ms,
+            rate: start_rps.max(1),
+            held: 0,
+            breached: None,
+        }
+    }
+
+    pub(crate) const fn rate(&self) -> u64 {
+        self.rate
+    }
+
+    /// Highest rate that held the target; zero until one has.
+    pub(crate) const fn sustainable_rps(&self) -> u64 {
+        self.held
+    }
+
+    /// Records the p99 seen at the current rate and returns the next rate.
+    pub(crate) fn step(&mut self, p99_ms: u64) -> u64 {
+        if p99_ms <= self.target_p99_ms {
+            self.held = self.held.max(self.rate);
+        } else {
+            self.breached = Some(
+                self.breached
+                    .map_or(self.rate, |upper| upper.min(self.rate)),
+            );
+            if self.held >= self.rate {
+                self.held = self.rate.checked_div(2).unwrap_or(0);
+            }
+        }
+        self.rate = match self.breached {
+            None => self.rate.saturating_mul(2),
+            Some(upper) => {
+                let gap = upper.saturating_sub(self.held);
+                let settle_gap = self
+                    .held
+                    .checked_div(SETTLE_GAP_DIVISOR)
+                    .unwrap_or(0)
+                    .max(1);
+                if gap <= settle_gap {
+                    self.held.max(1)
+                } else {
+                    self.held.saturating_add(gap.checked_div(2).unwrap_or(0))
+                }
+            }
+        };
+        self.rate
+    }
+}
+
+pub(super) fn spawn_max_rate_controller(
+    limiter: Arc<Semaphore>,
+    settings: MaxRateSettings,
+    align_to_second: bool,
+) {
+    tokio::spawn(async move {
+        let mut search = MaxRateSearch::new(settings.start_rps, settings.target_p99_ms);
+        let mut rate_per_sec = usize::try_from(search.rate()).unwrap_or(usize::MAX);
+        limiter.add_permits(rate_per_sec);
+        let mut rate_tick = interval(RATE_TICK_INTERVAL);
+        let mut step_started = Instant::now();
+        loop {
+            next_rate_tick(&mut rate_tick, align_to_second).await;
+            if step_started.elapsed() >= settings.step {
+                let (p99_ms, samples) = settings.feedback.p99();
+                if samples >= MIN_STEP_SAMPLES {
+                    let held_rps = search.rate();
+                    let next_rps = search.step(p99_ms);
+                    settings
+                        .feedback
+                        .set_sustainable_rps(search.sustainable_rps());
+                    info!(
+                        "--find-max-rate: p99 {}ms at {} rps (target {}ms); next {} rps.",
+                        p99_ms, held_rps, settings.target_p99_ms, next_rps
+                    );
+                    rate_per_sec = usize::try_from(next_rps).unwrap_or(usize::MAX);
+                    step_started = Instant::now();
+                }
+            }
+            let available = limiter.available_permits();
+            if available < rate_per_sec {
+                limiter.add_permits(rate_per_sec.saturating_sub(available));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AppError, AppResult};
+
+    #[test]
+    fn max_rate_search_doubles_then_bisects_to_the_knee() -> AppResult<()> {
+        // The target holds up to 100 rps.
+        let mut search = MaxRateSearch::new(10, 50);
+        let mut rates = vec![search.rate()];
+        for _ in 0..12 {
+            let p99_ms = if search.rate() <= 100 { 40 } else { 90 };
+            rates.push(search.step(p99_ms));
+        }
+        let expected = [10, 20, 40, 80, 160, 120, 100, 110, 105, 100, 100, 100, 100];
+        if rates != expected || search.sustainable_rps() != 100 {
+            return Err(AppError::validation(format!(
+                "Unexpected search path {:?} (held {})",
+                rates,
+                search.sustainable_rps()
+            )));
+        }
+
+        // A breach at the held rate backs off.
+        let next = search.step(90);
+        if search.sustainable_rps() != 50 || next != 75 {
+            return Err(AppError::validation(format!(
+                "Expected back-off to 50 held / 75 next, got {} / {}",
+                search.sustainable_rps(),
+                next
+            )));
+        }
+        Ok(())
+    }
+}
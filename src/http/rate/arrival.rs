@@ -0,0 +1,155 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tokio::sync::Semaphore;
+use tokio::time::{Instant, MissedTickBehavior, interval};
+use tracing::warn;
+
+use crate::args::{ArrivalDistribution, TesterArgs};
+use crate::metrics::{LOG_ONE, log2_fixed};
+
+use super::{RATE_TICK_INTERVAL, RateController, SECS_PER_MIN};
+
+/// How often due arrivals are released; gaps shorter than this are batched.
+const ARRIVAL_TICK_INTERVAL: Duration = Duration::from_millis(1);
+/// Nanoseconds per minute, for RPM → mean gap conversions.
+const NS_PER_MIN: u128 = 60_000_000_000;
+/// `ln 2` in the fixed-point format.
+const LN_2: u128 = 45_426;
+/// Bits of the uniform draw behind each exponential gap.
+const UNIFORM_BITS: u32 = 32;
+
+/// Timing options shared by the rate controllers.
+#[derive(Clone, Copy)]
+pub(crate) struct RatePacing {
+    pub(crate) align_to_second: bool,
+    pub(crate) arrival: Option<ArrivalDistribution>,
+}
+
+impl RatePacing {
+    pub(crate) fn from_args(args: &TesterArgs) -> Self {
+        let paced_rate = args.rate_limit.is_some() || args.load_profile.is_some();
+        let arrival = args.arrival_distribution.filter(|_| {
+            if !paced_rate || args.find_max_rate {
+                warn!(
+                    "--arrival-distribution is ignored unless --rate or a load profile is set \
+                     (and --find-max-rate is not)."
+                );
+            }
+            paced_rate && !args.find_max_rate
+        });
+        Self {
+            align_to_second: args.align_to_second,
+            arrival,
+        }
+    }
+}
+
+/// Where the paced controller reads its target rate from.
+pub(super) enum RateSource {
+    Fixed { rpm: u64 },
+    Plan(RateController),
+}
+
+impl RateSource {
+    /// Target rate for the next second.
+    fn next_rpm(&mut self) -> u64 {
+        match self {
+            Self::Fixed { rpm } => *rpm,
+            Self::Plan(controller) => controller.current_rpm(),
+        }
+    }
+}
+
+/// Mean gap between arrivals at `rpm`, or `None` when nothing should be sent.
+pub(crate) fn mean_gap_ns(rpm: u64) -> Option<u64> {
+    let gap = NS_PER_MIN.checked_div(u128::from(rpm))?;
+    Some(u64::try_from(gap).unwrap_or(u64::MAX).max(1))
+}
+
+/// Draws the gap until the next arrival.
+///
+/// Exponential gaps use inverse transform sampling, `-ln(U) * mean`, with the
+/// logarithm in fixed point so the controller stays in integer math. The
+/// mean of `-ln(U)` is 1, so the long-run rate matches the target.
+pub(crate) fn next_gap_ns<R: Rng>(
+    distribution: ArrivalDistribution,
+    mean_gap_ns: u64,
+    rng: &mut R,
+) -> u64 {
+    match distribution {
+        ArrivalDistribution::Uniform => mean_gap_ns,
+        ArrivalDistribution::Poisson | ArrivalDistribution::Exponential => {
+            // `U` in (0, 1], so `-ln(U)` is finite.
+            let draw = u64::from(rng.r#gen::<u32>()).saturating_add(1);
+            let scaled = u128::from(mean_gap_ns)
+                .saturating_mul(neg_ln_uniform(draw))
+                .checked_div(LOG_ONE)
+                .unwrap_or(0);
+            u64::try_from(scaled).unwrap_or(u64::MAX)
+        }
+    }
+}
+
+/// `-ln(draw / 2^32)` in fixed point, for `draw` in `1..=2^32`.
+fn neg_ln_uniform(draw: u64) -> u128 {
+    let log2_uniform = u128::from(UNIFORM_BITS).saturating_mul(LOG_ONE);
+    log2_uniform
+        .saturating_sub(log2_fixed(draw))
+        .saturating_mul(LN_2)
+        .checked_div(LOG_ONE)
+        .unwrap_or(0)
+}
+
+/// Releases one permit per arrival, with gaps drawn from `distribution`.
+///
+/// Arrivals are scheduled on an absolute timeline from the start, so timer
+/// slack never lowers the achieved rate. The target rate is re-read once per
+/// second; permits left unused are capped at one second of traffic, like the
+/// whole-second controllers.
+pub(super) fn spawn_paced_rate_controller(
+    limiter: Arc<Semaphore>,
+    mut source: RateSource,
+    distribution: ArrivalDistribution,
+) {
+    tokio::spawn(async move {
+        let mut rng = StdRng::from_entropy();
+        let start = Instant::now();
+        let mut rpm = source.next_rpm();
+        let mut next_rate_change = RATE_TICK_INTERVAL;
+        let mut next_arrival = Duration::ZERO;
+        let mut tick = interval(ARRIVAL_TICK_INTERVAL);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tick.tick().await;
+            let elapsed = start.elapsed();
+            let mut due: usize = 0;
+            while next_arrival <= elapsed {
+                if next_arrival >= next_rate_change {
+                    rpm = source.next_rpm();
+                    next_rate_change = next_rate_change.saturating_add(RATE_TICK_INTERVAL);
+                    continue;
+                }
+                let Some(mean) = mean_gap_ns(rpm) else {
+                    next_arrival = next_rate_change;
+                    continue;
+                };
+                due = due.saturating_add(1);
+                next_arrival = next_arrival.saturating_add(Duration::from_nanos(next_gap_ns(
+                    distribution,
+                    mean,
+                    &mut rng,
+                )));
+            }
+            let cap = usize::try_from(rpm.checked_div(SECS_PER_MIN).unwrap_or(0))
+                .unwrap_or(usize::MAX)
+                .max(1);
+            let available = limiter.available_permits();
+            if due > 0 && available < cap {
+                limiter.add_permits(due.min(cap.saturating_sub(available)));
+            }
+        }
+    });
+}
@@ -0,0 +1,78 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use reqwest::Request;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult, ValidationError};
+
+/// Verifies that responses echo the correlation header sent with each request.
+///
+/// Requests that do not carry the header are not checked, so the verifier only
+/// has an effect once the header is set via `-H` or a scenario step.
+#[derive(Debug)]
+pub(crate) struct RequestIdEcho {
+    header: HeaderName,
+    checked: AtomicU64,
+    mismatches: AtomicU64,
+}
+
+impl RequestIdEcho {
+    pub(crate) fn from_args(args: &TesterArgs) -> AppResult<Option<Arc<Self>>> {
+        if !args.verify_request_id_echo {
+            return Ok(None);
+        }
+        let header = HeaderName::from_bytes(args.request_id_header.as_bytes()).map_err(|err| {
+            AppError::validation(ValidationError::InvalidRequestIdHeader {
+                header: args.request_id_header.clone(),
+                source: err,
+            })
+        })?;
+        Ok(Some(Arc::new(Self::new(header))))
+    }
+
+    pub(crate) const fn new(header: HeaderName) -> Self {
+        Self {
+            header,
+            checked: AtomicU64::new(0),
+            mismatches: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) const fn header(&self) -> &HeaderName {
+        &self.header
+    }
+
+    pub(crate) fn checked(&self) -> u64 {
+        self.checked.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn mismatches(&self) -> u64 {
+        self.mismatches.load(Ordering::Relaxed)
+    }
+
+    /// Captures the correlation value sent with `request`, if any.
+    pub(crate) fn sent_value(&self, request: &Request) -> Option<HeaderValue> {
+        request.headers().get(&self.header).cloned()
+    }
+
+    /// Compares the echoed header against `sent` and returns an error message on mismatch.
+    pub(crate) fn verify(
+        &self,
+        sent: Option<&HeaderValue>,
+        response_headers: &HeaderMap,
+    ) -> Option<Arc<str>> {
+        let sent = sent?;
+        self.checked.fetch_add(1, Ordering::Relaxed);
+        let message = match response_headers.get(&self.header) {
+            Some(received) if received == sent => return None,
+            Some(_) => format!("{} echo mismatch", self.header),
+            None => format!("{} not echoed", self.header),
+        };
+        self.mismatches.fetch_add(1, Ordering::Relaxed);
+        Some(Arc::from(message))
+    }
+}
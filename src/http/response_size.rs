@@ -0,0 +1,83 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult, ValidationError};
+
+/// Flags responses whose body size falls outside the configured bounds.
+///
+/// Both bounds are exclusive: `below` fails bodies of `below` bytes or more,
+/// `above` fails bodies of `above` bytes or fewer.
+#[derive(Debug)]
+pub(crate) struct ResponseSizeAssert {
+    below: Option<u64>,
+    above: Option<u64>,
+    checked: AtomicU64,
+    violations: AtomicU64,
+}
+
+impl ResponseSizeAssert {
+    pub(crate) fn from_args(args: &TesterArgs) -> AppResult<Option<Arc<Self>>> {
+        let below = args
+            .assert_response_bytes_below
+            .as_ref()
+            .map(|value| value.get());
+        let above = args.assert_response_bytes_above;
+        if below.is_none() && above.is_none() {
+            return Ok(None);
+        }
+        if let (Some(below), Some(above)) = (below, above)
+            && above.saturating_add(1) >= below
+        {
+            return Err(AppError::validation(
+                ValidationError::ResponseBytesRangeEmpty { above, below },
+            ));
+        }
+        Ok(Some(Arc::new(Self::new(below, above))))
+    }
+
+    pub(crate) const fn new(below: Option<u64>, above: Option<u64>) -> Self {
+        Self {
+            below,
+            above,
+            checked: AtomicU64::new(0),
+            violations: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn checked(&self) -> u64 {
+        self.checked.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn violations(&self) -> u64 {
+        self.violations.load(Ordering::Relaxed)
+    }
+
+    /// Human-readable description of the accepted range, e.g. `> 0 and < 4096 bytes`.
+    pub(crate) fn bounds(&self) -> String {
+        match (self.above, self.below) {
+            (Some(above), Some(below)) => format!("> {} and < {} bytes", above, below),
+            (Some(above), None) => format!("> {} bytes", above),
+            (None, Some(below)) => format!("< {} bytes", below),
+            (None, None) => "any size".to_owned(),
+        }
+    }
+
+    /// Counts `response_bytes` and returns an error message when it is out of bounds.
+    pub(crate) fn check(&self, response_bytes: u64) -> Option<Arc<str>> {
+        self.checked.fetch_add(1, Ordering::Relaxed);
+        let too_large = self.below.is_some_and(|below| response_bytes >= below);
+        let too_small = self.above.is_some_and(|above| response_bytes <= above);
+        if !too_large && !too_small {
+            return None;
+        }
+        self.violations.fetch_add(1, Ordering::Relaxed);
+        Some(Arc::from(format!(
+            "response size {} bytes, expected {}",
+            response_bytes,
+            self.bounds()
+        )))
+    }
+}
@@ -1,16 +1,28 @@
 use std::net::ToSocketAddrs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use reqwest::Url;
-use tracing::warn;
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Proxy, Url, Version};
+use tokio::io::AsyncReadExt;
+use tracing::{error, warn};
 
 use crate::{
-    args::{HttpVersion, TesterArgs},
+    args::{ByteSize, HttpVersion, TesterArgs},
     error::{AppError, AppResult, HttpError, ValidationError},
+    run_context::RunContext,
 };
 
-use super::super::workload::{AuthConfig, BodySource, FormFieldSpec, UrlSource};
+use super::super::oauth2::OAuth2Session;
+use super::super::workload::{
+    AuthConfig, BodyFile, BodySource, FakeBody, FormFieldSpec, JsonBody, UrlSource,
+};
+
+/// `--url` value that makes `--urls-from-file` read the list from stdin.
+pub const STDIN_URL_PATH: &str = "-";
+/// `--data-dir-max-bytes` default: 256 MiB.
+const DEFAULT_DATA_DIR_MAX_BYTES: u64 = 256 * 1024 * 1024;
 
 pub(super) fn build_headers(args: &TesterArgs) -> Vec<(String, String)> {
     let mut headers = Vec::new();
@@ -36,6 +48,16 @@ fn has_host_header(headers: &[(String, String)]) -> bool {
 }
 
 pub(super) fn resolve_body_source(args: &TesterArgs) -> AppResult<BodySource> {
+    if let Some(path) = args.data_dir.as_ref() {
+        let max_bytes = args
+            .data_dir_max_bytes
+            .map_or(DEFAULT_DATA_DIR_MAX_BYTES, ByteSize::bytes);
+        return Ok(BodySource::from_files(load_body_dir(
+            Path::new(path),
+            max_bytes,
+        )?));
+    }
+
     if let Some(path) = args.data_lines.as_ref() {
         let content = std::fs::read_to_string(path).map_err(|err| {
             AppError::http(HttpError::ReadFile {
@@ -57,37 +79,134 @@ pub(super) fn resolve_body_source(args: &TesterArgs) -> AppResult<BodySource> {
                 source: err,
             })
         })?;
-        return Ok(BodySource::Static(content));
+        return templated_body(content, args);
     }
 
-    Ok(BodySource::Static(args.data.clone()))
+    templated_body(args.data.clone(), args)
+}
+
+/// Loads every regular, non-hidden file in `dir`, sorted by name.
+///
+/// The size check runs before any file is read, so an oversized directory
+/// fails fast instead of filling memory.
+pub(super) fn load_body_dir(dir: &Path, max_bytes: u64) -> AppResult<Vec<BodyFile>> {
+    let read_error = |path: &Path, source: std::io::Error| {
+        AppError::http(HttpError::ReadFile {
+            path: path.to_path_buf(),
+            source,
+        })
+    };
+    let mut paths = Vec::new();
+    let mut total_bytes: u64 = 0;
+    for entry in std::fs::read_dir(dir).map_err(|err| read_error(dir, err))? {
+        let entry = entry.map_err(|err| read_error(dir, err))?;
+        let path = entry.path();
+        let metadata = std::fs::metadata(&path).map_err(|err| read_error(&path, err))?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !metadata.is_file() || hidden {
+            continue;
+        }
+        total_bytes = total_bytes.saturating_add(metadata.len());
+        if total_bytes > max_bytes {
+            return Err(AppError::http(HttpError::BodyDirTooLarge {
+                path: dir.to_path_buf(),
+                max_bytes,
+            }));
+        }
+        paths.push(path);
+    }
+    if paths.is_empty() {
+        return Err(AppError::http(HttpError::BodyDirEmpty {
+            path: dir.to_path_buf(),
+        }));
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let bytes = std::fs::read(&path).map_err(|err| read_error(&path, err))?;
+            Ok(BodyFile {
+                bytes: Bytes::from(bytes),
+                content_type: content_type_for(&path),
+            })
+        })
+        .collect()
 }
 
-pub(super) fn resolve_url_source(args: &TesterArgs) -> AppResult<UrlSource> {
+fn content_type_for(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let content_type = match extension.as_str() {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "yaml" | "yml" => "application/yaml",
+        "pb" | "bin" => "application/octet-stream",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "gz" => "application/gzip",
+        "zip" => "application/zip",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+/// Bodies with `{{name}}`-style placeholders get fresh fake data per request.
+fn templated_body(body: String, args: &TesterArgs) -> AppResult<BodySource> {
+    if args.json_template {
+        return Ok(BodySource::Json(Arc::new(JsonBody::parse(
+            &body, args.seed,
+        )?)));
+    }
+    Ok(
+        FakeBody::parse(&body, args.seed, args.literal_placeholders)?.map_or_else(
+            || BodySource::Static(body),
+            |fake| BodySource::Fake(Arc::new(fake)),
+        ),
+    )
+}
+
+pub(super) fn resolve_url_source(args: &TesterArgs, run: &RunContext) -> AppResult<UrlSource> {
     let value = args
         .url
         .as_deref()
         .ok_or_else(|| AppError::validation(ValidationError::MissingUrl))?;
 
     if args.urls_from_file {
-        let content = std::fs::read_to_string(value).map_err(|err| {
-            AppError::http(HttpError::ReadUrlFile {
-                path: PathBuf::from(value),
-                source: err,
-            })
-        })?;
-        let urls: Vec<String> = content
+        let from_stdin = value == STDIN_URL_PATH;
+        let content = if from_stdin {
+            match run.stdin_urls.as_deref() {
+                Some(content) => content.to_owned(),
+                None => read_stdin_urls_blocking()?,
+            }
+        } else {
+            std::fs::read_to_string(value).map_err(|err| {
+                AppError::http(HttpError::ReadUrlFile {
+                    path: PathBuf::from(value),
+                    source: err,
+                })
+            })?
+        };
+        let urls = content
             .lines()
             .map(|line| line.trim())
             .filter(|line| !line.is_empty())
-            .map(|line| line.to_owned())
-            .collect();
+            .map(|line| parse_weighted_url(value, line))
+            .collect::<AppResult<Vec<_>>>()?;
         if urls.is_empty() {
-            return Err(AppError::http(HttpError::UrlFileEmpty {
-                path: PathBuf::from(value),
+            return Err(AppError::http(if from_stdin {
+                HttpError::UrlStdinEmpty
+            } else {
+                HttpError::UrlFileEmpty {
+                    path: PathBuf::from(value),
+                }
             }));
         }
-        return Ok(UrlSource::from_list(urls));
+        return UrlSource::from_weighted(urls);
     }
 
     if args.rand_regex_url {
@@ -104,34 +223,59 @@ pub(super) fn resolve_url_source(args: &TesterArgs) -> AppResult<UrlSource> {
     Ok(UrlSource::Static(value.to_owned()))
 }
 
+/// Reads the whole of stdin as a `--urls-from-file` list.
+///
+/// # Errors
+///
+/// Returns an error if stdin cannot be read as UTF-8.
+pub async fn read_stdin_urls() -> AppResult<String> {
+    let mut content = String::new();
+    tokio::io::stdin()
+        .read_to_string(&mut content)
+        .await
+        .map_err(|err| AppError::http(HttpError::ReadUrlStdin { source: err }))?;
+    Ok(content)
+}
+
+/// Blocking fallback for callers without a pre-read list; only `--dry-run`
+/// gets here, while the plan is built and before any tasks run.
+fn read_stdin_urls_blocking() -> AppResult<String> {
+    std::io::read_to_string(std::io::stdin())
+        .map_err(|err| AppError::http(HttpError::ReadUrlStdin { source: err }))
+}
+
+/// Splits an optional leading weight off a URL file line (`70 https://...`).
+///
+/// Lines without a weight count as weight 1.
+pub(super) fn parse_weighted_url(path: &str, line: &str) -> AppResult<(u32, String)> {
+    let Some((weight, url)) = line.split_once(char::is_whitespace) else {
+        return Ok((1, line.to_owned()));
+    };
+    if !weight.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Ok((1, line.to_owned()));
+    }
+    match weight.parse::<u32>() {
+        Ok(weight) if weight > 0 => Ok((weight, url.trim_start().to_owned())),
+        Ok(_) | Err(_) => Err(AppError::http(HttpError::InvalidUrlWeight {
+            path: PathBuf::from(path),
+            line: line.to_owned(),
+        })),
+    }
+}
+
+/// Builds the multipart fields from `--form` and `--form-file`.
+///
+/// File parts are read here so a missing file fails before the run starts
+/// instead of on every request.
 pub(super) fn parse_form_fields(args: &TesterArgs) -> AppResult<Option<Vec<FormFieldSpec>>> {
-    if args.form.is_empty() {
+    if args.form.is_empty() && args.form_file.is_empty() {
         return Ok(None);
     }
-    let mut fields = Vec::with_capacity(args.form.len());
+    let mut fields = Vec::with_capacity(args.form.len().saturating_add(args.form_file.len()));
     for entry in &args.form {
-        let (name, value) = entry.split_once('=').ok_or_else(|| {
-            AppError::validation(ValidationError::InvalidFormEntryFormat {
-                entry: entry.to_owned(),
-            })
-        })?;
-        let name = name.trim();
-        if name.is_empty() {
-            return Err(AppError::validation(ValidationError::FormEntryNameEmpty {
-                entry: entry.to_owned(),
-            }));
-        }
-        let value = value.trim();
+        let (name, value) = split_form_entry(entry)?;
         if let Some(path) = value.strip_prefix('@') {
-            if path.is_empty() {
-                return Err(AppError::validation(ValidationError::FormEntryPathEmpty {
-                    entry: entry.to_owned(),
-                }));
-            }
-            fields.push(FormFieldSpec::File {
-                name: name.to_owned(),
-                path: path.to_owned(),
-            });
+            fields.push(load_form_file(entry, name, path)?);
         } else {
             fields.push(FormFieldSpec::Text {
                 name: name.to_owned(),
@@ -139,9 +283,54 @@ pub(super) fn parse_form_fields(args: &TesterArgs) -> AppResult<Option<Vec<FormF
             });
         }
     }
+    for entry in &args.form_file {
+        let (name, value) = split_form_entry(entry)?;
+        let path = value.strip_prefix('@').unwrap_or(value);
+        fields.push(load_form_file(entry, name, path)?);
+    }
     Ok(Some(fields))
 }
 
+fn split_form_entry(entry: &str) -> AppResult<(&str, &str)> {
+    let (name, value) = entry.split_once('=').ok_or_else(|| {
+        AppError::validation(ValidationError::InvalidFormEntryFormat {
+            entry: entry.to_owned(),
+        })
+    })?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation(ValidationError::FormEntryNameEmpty {
+            entry: entry.to_owned(),
+        }));
+    }
+    Ok((name, value.trim()))
+}
+
+fn load_form_file(entry: &str, name: &str, path: &str) -> AppResult<FormFieldSpec> {
+    if path.is_empty() {
+        return Err(AppError::validation(ValidationError::FormEntryPathEmpty {
+            entry: entry.to_owned(),
+        }));
+    }
+    let bytes = std::fs::read(path).map_err(|err| {
+        AppError::http(HttpError::ReadFormFile {
+            path: path.to_owned(),
+            source: err,
+        })
+    })?;
+    let path = Path::new(path);
+    Ok(FormFieldSpec::File {
+        name: name.to_owned(),
+        file_name: path
+            .file_name()
+            .and_then(|value| value.to_str())
+            .unwrap_or("file")
+            .to_owned(),
+        content_type: content_type_for(path),
+        bytes: Bytes::from(bytes),
+    })
+}
+
 pub(super) fn resolve_http2_parallel(args: &TesterArgs) -> usize {
     let http2_enabled = args.http2 || matches!(args.http_version, Some(HttpVersion::V2));
     if !http2_enabled && args.http2_parallel.get() > 1 {
@@ -151,6 +340,85 @@ pub(super) fn resolve_http2_parallel(args: &TesterArgs) -> usize {
     args.http2_parallel.get()
 }
 
+/// Request-line version forced by `--http-version`.
+///
+/// HTTP/1.x versions are stamped on every request so the server sees the
+/// exact version (HTTP/1.0 also drops keep-alive unless the server opts in).
+/// HTTP/2 and HTTP/3 are selected by the client builder alone.
+pub(super) fn resolve_request_version(args: &TesterArgs) -> AppResult<Option<Version>> {
+    let Some(version) = args.http_version else {
+        return Ok(None);
+    };
+    if args.http2 || args.http3 || !args.alpn.is_empty() {
+        warn!("--http-version overrides --http2/--http3/--alpn.");
+    }
+    let cleartext = args
+        .url
+        .as_deref()
+        .is_some_and(|url| url.starts_with("http://"));
+    match version {
+        HttpVersion::V0_9 => {
+            warn!("HTTP/0.9 requests are not supported; sending HTTP/1.0 requests instead.");
+        }
+        HttpVersion::V2 if cleartext => {
+            warn!(
+                "--http-version 2 on an http:// target uses prior knowledge (h2c) without \
+                 negotiation; servers that only offer HTTP/2 via Upgrade will reject it."
+            );
+        }
+        HttpVersion::V3 if cleartext => {
+            return Err(AppError::validation(ValidationError::Http3RequiresHttps));
+        }
+        HttpVersion::V1_0 | HttpVersion::V1_1 | HttpVersion::V2 | HttpVersion::V3 => {}
+    }
+    Ok(forced_request_version(version))
+}
+
+pub(super) const fn forced_request_version(version: HttpVersion) -> Option<Version> {
+    match version {
+        HttpVersion::V0_9 | HttpVersion::V1_0 => Some(Version::HTTP_10),
+        HttpVersion::V1_1 => Some(Version::HTTP_11),
+        HttpVersion::V2 | HttpVersion::V3 => None,
+    }
+}
+
+/// Routes the client through `--proxy` with any `--proxy-header` values.
+pub(crate) fn apply_proxy_settings(
+    builder: reqwest::ClientBuilder,
+    args: &TesterArgs,
+) -> AppResult<reqwest::ClientBuilder> {
+    let Some(proxy_url) = args.proxy_url.as_deref() else {
+        return Ok(builder);
+    };
+    let mut proxy = Proxy::all(proxy_url).map_err(|err| {
+        error!("Invalid proxy URL '{}': {}", proxy_url, err);
+        AppError::validation(ValidationError::InvalidProxyUrl {
+            url: proxy_url.to_owned(),
+            source: err,
+        })
+    })?;
+    if !args.proxy_headers.is_empty() {
+        let mut headers = HeaderMap::new();
+        for (key, value) in &args.proxy_headers {
+            let name = HeaderName::from_bytes(key.as_bytes()).map_err(|err| {
+                AppError::validation(ValidationError::InvalidProxyHeaderName {
+                    header: key.clone(),
+                    source: err,
+                })
+            })?;
+            let val = HeaderValue::from_str(value).map_err(|err| {
+                AppError::validation(ValidationError::InvalidProxyHeaderValue {
+                    header: key.clone(),
+                    source: err,
+                })
+            })?;
+            headers.insert(name, val);
+        }
+        proxy = proxy.headers(headers);
+    }
+    Ok(builder.proxy(proxy))
+}
+
 pub(super) fn apply_proxy_http_version(
     mut builder: reqwest::ClientBuilder,
     version: HttpVersion,
@@ -171,7 +439,7 @@ pub(super) fn apply_proxy_http_version(
     Ok(builder)
 }
 
-pub(super) fn resolve_primary_host(args: &TesterArgs) -> AppResult<Option<(String, u16)>> {
+pub(in crate::http) fn resolve_primary_host(args: &TesterArgs) -> AppResult<Option<(String, u16)>> {
     if args.urls_from_file || args.rand_regex_url {
         return Ok(None);
     }
@@ -221,7 +489,7 @@ pub(super) fn resolve_primary_host(args: &TesterArgs) -> AppResult<Option<(Strin
     Ok(None)
 }
 
-pub(super) fn resolve_addrs(
+pub(in crate::http) fn resolve_addrs(
     host: &str,
     port: u16,
     ipv4_only: bool,
@@ -247,17 +515,82 @@ pub(super) fn resolve_addrs(
 }
 
 pub(super) fn resolve_auth(args: &TesterArgs) -> AppResult<Option<AuthConfig>> {
+    let basic = resolve_secret_arg(
+        "basic-auth",
+        args.basic_auth.as_deref(),
+        args.basic_auth_file.as_deref(),
+    )?;
+    let bearer = resolve_secret_arg(
+        "bearer-token",
+        args.bearer_token.as_deref(),
+        args.bearer_token_file.as_deref(),
+    )?;
+    if let Some(token_url) = args.oauth2_token_url.as_ref() {
+        if basic.is_some()
+            || bearer.is_some()
+            || args.digest_auth.is_some()
+            || args.aws_sigv4.is_some()
+        {
+            return Err(AppError::validation(ValidationError::OAuth2AuthConflict));
+        }
+        let client_id = args.oauth2_client_id.clone().ok_or_else(|| {
+            AppError::validation(ValidationError::OAuth2MissingField {
+                flag: "oauth2-client-id",
+            })
+        })?;
+        let client_secret = args.oauth2_client_secret.as_deref().ok_or_else(|| {
+            AppError::validation(ValidationError::OAuth2MissingField {
+                flag: "oauth2-client-secret",
+            })
+        })?;
+        let client_secret = resolve_secret("oauth2-client-secret", client_secret)?;
+        return Ok(Some(AuthConfig::OAuth2 {
+            session: OAuth2Session::new(
+                token_url.clone(),
+                client_id,
+                client_secret,
+                args.oauth2_scope.clone(),
+            ),
+        }));
+    }
+    for (flag, value) in [
+        ("client-id", &args.oauth2_client_id),
+        ("client-secret", &args.oauth2_client_secret),
+        ("scope", &args.oauth2_scope),
+    ] {
+        if value.is_some() {
+            return Err(AppError::validation(
+                ValidationError::OAuth2RequiresTokenUrl { flag },
+            ));
+        }
+    }
+    if let Some(digest) = args.digest_auth.as_deref() {
+        if basic.is_some() || bearer.is_some() || args.aws_sigv4.is_some() {
+            return Err(AppError::validation(ValidationError::DigestAuthConflict));
+        }
+        let (username, password) = parse_auth_pair(&resolve_secret("digest-auth", digest)?)?;
+        return Ok(Some(AuthConfig::Digest { username, password }));
+    }
     if let Some(sigv4) = args.aws_sigv4.as_ref() {
-        let basic = args
-            .basic_auth
-            .as_ref()
+        if bearer.is_some() {
+            return Err(AppError::validation(
+                ValidationError::AwsSigv4WithBearerToken,
+            ));
+        }
+        let basic = basic
+            .as_deref()
             .ok_or_else(|| AppError::validation(ValidationError::AwsSigv4RequiresBasicAuth))?;
         let (access_key, secret_key) = parse_auth_pair(basic)?;
         let (region, service) = parse_aws_sigv4(sigv4)?;
+        let session_token = args
+            .aws_session
+            .as_deref()
+            .map(|value| resolve_secret("aws-session", value))
+            .transpose()?;
         return Ok(Some(AuthConfig::SigV4 {
             access_key,
             secret_key,
-            session_token: args.aws_session.clone(),
+            session_token,
             region,
             service,
         }));
@@ -267,11 +600,68 @@ pub(super) fn resolve_auth(args: &TesterArgs) -> AppResult<Option<AuthConfig>> {
             ValidationError::AwsSessionRequiresSigv4,
         ));
     }
-    if let Some(basic) = args.basic_auth.as_ref() {
-        let (username, password) = parse_auth_pair(basic)?;
-        return Ok(Some(AuthConfig::Basic { username, password }));
+    match (basic, bearer) {
+        (Some(_), Some(_)) => Err(AppError::validation(
+            ValidationError::BasicAndBearerAuthConflict,
+        )),
+        (Some(basic), None) => {
+            let (username, password) = parse_auth_pair(&basic)?;
+            Ok(Some(AuthConfig::Basic { username, password }))
+        }
+        (None, Some(token)) => Ok(Some(AuthConfig::Bearer { token })),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Resolves a secret given inline or through its `--<flag>-file` companion.
+fn resolve_secret_arg(
+    flag: &'static str,
+    value: Option<&str>,
+    file: Option<&str>,
+) -> AppResult<Option<String>> {
+    match (value, file) {
+        (Some(_), Some(_)) => Err(AppError::validation(
+            ValidationError::SecretSourceConflict { flag },
+        )),
+        (Some(value), None) => resolve_secret(flag, value).map(Some),
+        (None, Some(path)) => read_secret_file(flag, path).map(Some),
+        (None, None) => Ok(None),
     }
-    Ok(None)
+}
+
+/// Dereferences `@path` and `env:VAR`; any other value is used as-is.
+fn resolve_secret(flag: &'static str, value: &str) -> AppResult<String> {
+    if let Some(path) = value.strip_prefix('@') {
+        return read_secret_file(flag, path);
+    }
+    let secret = match value.strip_prefix("env:") {
+        Some(var) => std::env::var(var).map_err(|_err| {
+            AppError::validation(ValidationError::SecretEnvMissing {
+                flag,
+                var: var.to_owned(),
+            })
+        })?,
+        None => value.to_owned(),
+    };
+    non_empty_secret(flag, secret)
+}
+
+fn read_secret_file(flag: &'static str, path: &str) -> AppResult<String> {
+    let contents = std::fs::read_to_string(path).map_err(|err| {
+        AppError::validation(ValidationError::SecretFileRead {
+            flag,
+            path: path.to_owned(),
+            source: err,
+        })
+    })?;
+    non_empty_secret(flag, contents.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+fn non_empty_secret(flag: &'static str, secret: String) -> AppResult<String> {
+    if secret.is_empty() {
+        return Err(AppError::validation(ValidationError::SecretEmpty { flag }));
+    }
+    Ok(secret)
 }
 
 fn parse_auth_pair(value: &str) -> AppResult<(String, String)> {
@@ -1,30 +1,49 @@
 mod config;
+mod warmup;
 mod worker;
 
-use std::sync::Arc;
+pub(crate) use config::apply_proxy_settings;
+pub use config::{STDIN_URL_PATH, read_stdin_urls};
+pub(in crate::http) use config::{resolve_addrs, resolve_primary_host};
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, atomic::AtomicU64};
 use std::time::Duration;
 
-use reqwest::{
-    Client, Proxy,
-    header::{HeaderMap, HeaderName, HeaderValue},
-    redirect,
-};
+use reqwest::{Client, Url, redirect};
 use tokio::sync::mpsc;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::{
-    args::{DEFAULT_USER_AGENT, HttpMethod, HttpVersion, TesterArgs},
+    args::{DEFAULT_USER_AGENT, HttpMethod, HttpVersion, Scenario, TesterArgs},
     error::{AppError, AppResult, HttpError, ValidationError},
     metrics::{LogSink, Metrics},
+    run_context::RunContext,
     shutdown::ShutdownSender,
 };
 
-use super::tls::apply_tls_settings;
-use super::workload::{AuthConfig, BodySource, SingleRequestSpec, UrlSource, Workload};
+use super::body_assert::BodyAssert;
+use super::conn_timing::ConnectTimingLayer;
+use super::connect_rate::{ConnectRateLayer, ConnectRateLimiter};
+use super::connection_cycle::ConnectionCycle;
+use super::deadline::DeadlineHeader;
+use super::doh::DohResolver;
+use super::header_gen::GeneratedHeaders;
+use super::multiplex::{CountConnectionsLayer, Http2Multiplex, Http2Streams, StreamGroup};
+use super::request_id::RequestIdEcho;
+use super::response_size::ResponseSizeAssert;
+use super::sticky::BackendTally;
+use super::tls::{apply_tls_settings, apply_trust_settings};
+use super::workload::{
+    AuthConfig, BodySource, ConnectToClients, FakeData, SingleRequestSpec, StepClients, UrlSource,
+    Workload, validate_scenario_placeholders,
+};
 use config::{
-    apply_proxy_http_version, build_headers, parse_form_fields, resolve_addrs, resolve_auth,
-    resolve_body_source, resolve_primary_host, resolve_url_source,
+    apply_proxy_http_version, build_headers, parse_form_fields, resolve_auth, resolve_body_source,
+    resolve_http2_parallel, resolve_request_version, resolve_url_source,
 };
+use warmup::WarmupRequests;
 use worker::create_sender_task;
 
 /// Creates the request sender task and validates the HTTP client/config.
@@ -34,13 +53,126 @@ use worker::create_sender_task;
 /// Returns an error when the HTTP client or request template cannot be built.
 pub fn setup_request_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<tokio::task::JoinHandle<()>> {
     let shutdown_tx = shutdown_tx.clone();
     let metrics_tx = metrics_tx.clone();
+    let connect_limiter = args
+        .connect_rate
+        .map(|rate| Arc::new(ConnectRateLimiter::new(rate.get())));
+    let (client, workload) = build_client_and_workload(args, run, connect_limiter.as_ref())?;
+    let http2_streams = build_http2_streams(args, run, connect_limiter.as_ref())?;
+    let connection_cycle = if http2_streams.is_some() {
+        if args.requests_per_connection.is_some() {
+            warn!("--requests-per-connection is ignored with --http2-parallel.");
+        }
+        None
+    } else {
+        build_connection_cycle(args, run, connect_limiter.as_ref())?
+    };
+    let client = http2_streams
+        .as_ref()
+        .and_then(Http2Streams::client)
+        .cloned()
+        .unwrap_or(client);
+    let request_id_echo = RequestIdEcho::from_args(args)?;
+    let deadline_header = DeadlineHeader::from_args(args)?;
+    let response_size = ResponseSizeAssert::from_args(args)?;
+    let body_assert = BodyAssert::from_args(args)?;
+    let backend_tally = BackendTally::from_args(args)?;
+    let warmup_requests = WarmupRequests::from_args(args, &client)?;
+
+    Ok(create_sender_task(
+        args,
+        run,
+        &shutdown_tx,
+        &metrics_tx,
+        log_sink.cloned(),
+        client,
+        workload,
+        request_id_echo,
+        deadline_header,
+        response_size,
+        body_assert,
+        backend_tally,
+        warmup_requests,
+        connect_limiter,
+        http2_streams,
+        connection_cycle,
+    ))
+}
+
+/// With `--requests-per-connection`, prepares the per-worker client factory
+/// that counts every connection it opens.
+pub(in crate::http) fn build_connection_cycle(
+    args: &TesterArgs,
+    run: &RunContext,
+    connect_limiter: Option<&Arc<ConnectRateLimiter>>,
+) -> AppResult<Option<Arc<ConnectionCycle>>> {
+    let Some(limit) = args.requests_per_connection else {
+        return Ok(None);
+    };
+    let connections = run.connection_counter.clone().unwrap_or_default();
+    let pinned_addrs = resolve_pinned_addrs(args)?;
+    let client_args = args.clone();
+    let connect_limiter = connect_limiter.cloned();
+    let counter = connections.clone();
+    let build = move || {
+        build_client(
+            &client_args,
+            pinned_addrs.as_ref(),
+            connect_limiter.as_ref(),
+            Some(&counter),
+        )
+    };
+    Ok(Some(Arc::new(ConnectionCycle::new(
+        limit.get(),
+        connections,
+        Box::new(build),
+    ))))
+}
+
+/// With `--http2-parallel` above 1, builds one client per `--max-tasks` slot
+/// so each slot multiplexes its workers' requests over its own connection.
+fn build_http2_streams(
+    args: &TesterArgs,
+    run: &RunContext,
+    connect_limiter: Option<&Arc<ConnectRateLimiter>>,
+) -> AppResult<Option<Http2Streams>> {
+    let parallel = resolve_http2_parallel(args);
+    if parallel <= 1 {
+        return Ok(None);
+    }
+    let multiplex = run
+        .http2_multiplex
+        .clone()
+        .unwrap_or_else(|| Arc::new(Http2Multiplex::new(parallel, args.max_tasks.get())));
+    let pinned_addrs = resolve_pinned_addrs(args)?;
+    let groups = (0..args.max_tasks.get())
+        .map(|_| {
+            build_client(
+                args,
+                pinned_addrs.as_ref(),
+                connect_limiter,
+                Some(multiplex.connection_counter()),
+            )
+            .map(|client| Arc::new(StreamGroup::new(client, multiplex.clone())))
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+    Ok(Some(Http2Streams::new(multiplex, groups)))
+}
 
+/// Builds the HTTP client and workload shared by the sender and diagnostics probe.
+///
+/// With a `connect_limiter`, new connections wait for a slot before dialing.
+pub(in crate::http) fn build_client_and_workload(
+    args: &TesterArgs,
+    run: &RunContext,
+    connect_limiter: Option<&Arc<ConnectRateLimiter>>,
+) -> AppResult<(Client, Workload)> {
     if args.ipv4_only && args.ipv6_only {
         return Err(AppError::validation(ValidationError::Ipv4Ipv6Conflict));
     }
@@ -52,6 +184,7 @@ pub fn setup_request_sender(
     }
 
     let auth_config = resolve_auth(args)?;
+    let request_version = resolve_request_version(args)?;
 
     if args.scenario.is_some()
         && (args.urls_from_file || args.rand_regex_url || args.dump_urls.is_some())
@@ -70,10 +203,208 @@ pub fn setup_request_sender(
             ValidationError::UrlsFromFileAndRandRegexConflict,
         ));
     }
-    if matches!(auth_config, Some(AuthConfig::SigV4 { .. })) && !args.form.is_empty() {
+    if matches!(auth_config, Some(AuthConfig::SigV4 { .. }))
+        && (!args.form.is_empty() || !args.form_file.is_empty())
+    {
         return Err(AppError::validation(ValidationError::SigV4FormUnsupported));
     }
 
+    let pinned_addrs = resolve_pinned_addrs(args)?;
+    let client = build_client(args, pinned_addrs.as_ref(), connect_limiter, None)?;
+
+    let workload = if let Some(scenario) = args.scenario.clone() {
+        if args.correlation_header.is_some() || args.correlation_log.is_some() {
+            warn!("--correlation-header and --correlation-log are ignored with scenarios.");
+        }
+        for body in scenario
+            .steps
+            .iter()
+            .filter_map(|step| step.body.as_deref())
+        {
+            validate_scenario_placeholders(body)?;
+        }
+        let step_clients =
+            build_step_clients(args, &scenario, pinned_addrs.as_ref(), connect_limiter)?;
+        let connect_to_clients =
+            build_connect_to_clients(args, pinned_addrs.as_ref(), connect_limiter)?;
+        Workload::Scenario(
+            Arc::new(scenario),
+            Arc::new(args.connect_to.clone()),
+            args.host_header.clone(),
+            auth_config,
+            request_version,
+            Arc::new(FakeData::new(args.seed)),
+            Arc::new(step_clients),
+            Arc::new(connect_to_clients),
+        )
+    } else {
+        let url_source = resolve_url_source(args, run)?;
+        let body_source = resolve_body_source(args)?;
+        let form_fields = parse_form_fields(args)?;
+        let mut headers = build_headers(args);
+        let generated = GeneratedHeaders::from_args(args, &mut headers)?;
+
+        let requires_dynamic = matches!(
+            body_source,
+            BodySource::Lines(_) | BodySource::Fake(_) | BodySource::Json(_) | BodySource::Files(_)
+        ) || matches!(
+            url_source,
+            UrlSource::List(_) | UrlSource::Weighted(_) | UrlSource::Regex(_)
+        ) || form_fields.is_some()
+            || !args.connect_to.is_empty()
+            || auth_config.is_some()
+            || generated.is_some();
+
+        if requires_dynamic {
+            Workload::SingleDynamic(Arc::new(SingleRequestSpec {
+                method: args.method,
+                url: url_source,
+                headers,
+                body: body_source,
+                form: form_fields,
+                connect_to: args.connect_to.clone(),
+                connect_to_clients: build_connect_to_clients(
+                    args,
+                    pinned_addrs.as_ref(),
+                    connect_limiter,
+                )?,
+                auth: auth_config,
+                version: request_version,
+                generated,
+            }))
+        } else {
+            let UrlSource::Static(url) = url_source else {
+                return Err(AppError::http(HttpError::InvalidUrlSourceForStaticWorkload));
+            };
+            let BodySource::Static(body) = body_source else {
+                return Err(AppError::http(
+                    HttpError::InvalidBodySourceForStaticWorkload,
+                ));
+            };
+
+            drop(auth_config);
+            let mut request_builder = match args.method {
+                HttpMethod::Get => client.get(&url),
+                HttpMethod::Post => client.post(&url),
+                HttpMethod::Patch => client.patch(&url),
+                HttpMethod::Put => client.put(&url),
+                HttpMethod::Delete => client.delete(&url),
+            };
+
+            if let Some(version) = request_version {
+                request_builder = request_builder.version(version);
+            }
+            for (key, value) in &headers {
+                request_builder = request_builder.header(key, value);
+            }
+
+            let request = match request_builder.body(body).build() {
+                Ok(req) => req,
+                Err(e) => {
+                    error!("Failed to build request: {}", e);
+                    return Err(AppError::http(HttpError::BuildRequestFailed { source: e }));
+                }
+            };
+
+            Workload::Single(Arc::new(request))
+        }
+    };
+
+    Ok((client, workload))
+}
+
+/// Addresses the client is pinned to by `--ipv4`/`--ipv6`, resolved once per run.
+///
+/// Without either flag the target host is only looked up to fail early,
+/// unless `--no-pre-lookup` is set.
+fn resolve_pinned_addrs(args: &TesterArgs) -> AppResult<Option<(String, Vec<SocketAddr>)>> {
+    if args.doh_url.is_some() {
+        return Ok(None);
+    }
+    let Some((host, port)) = resolve_primary_host(args)? else {
+        return Ok(None);
+    };
+    if args.ipv4_only || args.ipv6_only {
+        let addrs = resolve_addrs(&host, port, args.ipv4_only, args.ipv6_only)?;
+        if addrs.is_empty() {
+            return Err(AppError::http(HttpError::NoAddressesResolved { host }));
+        }
+        return Ok(Some((host, addrs)));
+    }
+    if !args.no_pre_lookup {
+        let _ = resolve_addrs(&host, port, false, false)?;
+    }
+    Ok(None)
+}
+
+/// Builds one client per distinct scenario step identity up front, so steps
+/// with their own cert/key avoid per-request client setup.
+fn build_step_clients(
+    args: &TesterArgs,
+    scenario: &Scenario,
+    pinned_addrs: Option<&(String, Vec<SocketAddr>)>,
+    connect_limiter: Option<&Arc<ConnectRateLimiter>>,
+) -> AppResult<StepClients> {
+    let mut by_identity: BTreeMap<(&str, &str), Client> = BTreeMap::new();
+    let mut step_clients = Vec::with_capacity(scenario.steps.len());
+    for step in &scenario.steps {
+        let (Some(cert), Some(key)) = (step.cert.as_deref(), step.key.as_deref()) else {
+            step_clients.push(None);
+            continue;
+        };
+        let client = match by_identity.get(&(cert, key)) {
+            Some(client) => client.clone(),
+            None => {
+                let mut step_args = args.clone();
+                step_args.cert = Some(cert.to_owned());
+                step_args.key = Some(key.to_owned());
+                let client = build_client(&step_args, pinned_addrs, connect_limiter, None)?;
+                by_identity.insert((cert, key), client.clone());
+                client
+            }
+        };
+        step_clients.push(Some(client));
+    }
+    Ok(step_clients)
+}
+
+/// Builds one client per distinct `--connect-to` CA bundle up front, which
+/// also fails the run early on an unreadable or invalid bundle.
+fn build_connect_to_clients(
+    args: &TesterArgs,
+    pinned_addrs: Option<&(String, Vec<SocketAddr>)>,
+    connect_limiter: Option<&Arc<ConnectRateLimiter>>,
+) -> AppResult<ConnectToClients> {
+    let mut by_cacert: BTreeMap<&str, Client> = BTreeMap::new();
+    let mut clients = Vec::with_capacity(args.connect_to.len());
+    for mapping in &args.connect_to {
+        let Some(cacert) = mapping.cacert.as_deref() else {
+            clients.push(None);
+            continue;
+        };
+        let client = match by_cacert.get(cacert) {
+            Some(client) => client.clone(),
+            None => {
+                let mut ca_args = args.clone();
+                ca_args.cacert = Some(cacert.to_owned());
+                let client = build_client(&ca_args, pinned_addrs, connect_limiter, None)?;
+                by_cacert.insert(cacert, client.clone());
+                client
+            }
+        };
+        clients.push(Some(client));
+    }
+    Ok(clients)
+}
+
+/// Builds one HTTP client; with `connection_counter`, every connection it
+/// opens is counted.
+fn build_client(
+    args: &TesterArgs,
+    pinned_addrs: Option<&(String, Vec<SocketAddr>)>,
+    connect_limiter: Option<&Arc<ConnectRateLimiter>>,
+    connection_counter: Option<&Arc<AtomicU64>>,
+) -> AppResult<Client> {
     let mut client_builder = Client::builder()
         .timeout(args.request_timeout)
         .connect_timeout(args.connect_timeout);
@@ -82,19 +413,28 @@ pub fn setup_request_sender(
         client_builder = client_builder.user_agent(DEFAULT_USER_AGENT);
     }
 
-    if let Some((host, port)) = resolve_primary_host(args)? {
-        if args.ipv4_only || args.ipv6_only {
-            let addrs = resolve_addrs(&host, port, args.ipv4_only, args.ipv6_only)?;
-            if addrs.is_empty() {
-                return Err(AppError::http(HttpError::NoAddressesResolved { host }));
-            }
-            client_builder = client_builder.resolve_to_addrs(&host, &addrs);
-        } else if !args.no_pre_lookup {
-            let _ = resolve_addrs(&host, port, false, false)?;
-        }
+    if args.connection_timing {
+        // Innermost, so waiting for a `--connect-rate` slot is not counted.
+        client_builder = client_builder.connector_layer(ConnectTimingLayer);
     }
 
-    if args.redirect_limit == 0 {
+    if let Some(limiter) = connect_limiter {
+        client_builder = client_builder.connector_layer(ConnectRateLayer::new(limiter.clone()));
+    }
+
+    if let Some(counter) = connection_counter {
+        client_builder =
+            client_builder.connector_layer(CountConnectionsLayer::new(counter.clone()));
+    }
+
+    if let Some(doh_url) = args.doh_url.as_deref() {
+        client_builder = client_builder.dns_resolver(Arc::new(build_doh_resolver(args, doh_url)?));
+    } else if let Some((host, addrs)) = pinned_addrs {
+        client_builder = client_builder.resolve_to_addrs(host, addrs);
+    }
+
+    // A scenario cookie jar follows redirects itself to see every hop's cookies.
+    if args.redirect_limit == 0 || (args.cookies && args.scenario.is_some()) {
         client_builder = client_builder.redirect(redirect::Policy::none());
     } else {
         client_builder = client_builder.redirect(redirect::Policy::limited(
@@ -123,87 +463,8 @@ pub fn setup_request_sender(
 
     client_builder = apply_tls_settings(client_builder, args)?;
 
-    if let Some(path) = args.cacert.as_ref() {
-        let bytes = std::fs::read(path).map_err(|err| {
-            AppError::http(HttpError::ReadCacert {
-                path: path.clone().into(),
-                source: err,
-            })
-        })?;
-        let cert = reqwest::Certificate::from_pem(&bytes).map_err(|err| {
-            AppError::http(HttpError::InvalidCacert {
-                path: path.clone().into(),
-                source: err,
-            })
-        })?;
-        client_builder = client_builder.add_root_certificate(cert);
-    }
-
-    if args.cert.is_some() || args.key.is_some() {
-        let cert_path = args
-            .cert
-            .as_ref()
-            .ok_or_else(|| AppError::validation(ValidationError::CertRequiresKey))?;
-        let key_path = args
-            .key
-            .as_ref()
-            .ok_or_else(|| AppError::validation(ValidationError::KeyRequiresCert))?;
-        let cert_bytes = std::fs::read(cert_path).map_err(|err| {
-            AppError::http(HttpError::ReadCert {
-                path: cert_path.clone().into(),
-                source: err,
-            })
-        })?;
-        let key_bytes = std::fs::read(key_path).map_err(|err| {
-            AppError::http(HttpError::ReadKey {
-                path: key_path.clone().into(),
-                source: err,
-            })
-        })?;
-        let identity = reqwest::Identity::from_pkcs8_pem(&cert_bytes, &key_bytes)
-            .map_err(|err| AppError::http(HttpError::InvalidIdentity { source: err }))?;
-        client_builder = client_builder.identity(identity);
-    }
-
-    if args.insecure {
-        client_builder = client_builder
-            .danger_accept_invalid_certs(true)
-            .danger_accept_invalid_hostnames(true);
-    }
-
-    if let Some(ref proxy_url) = args.proxy_url {
-        match Proxy::all(proxy_url) {
-            Ok(mut proxy) => {
-                if !args.proxy_headers.is_empty() {
-                    let mut headers = HeaderMap::new();
-                    for (key, value) in &args.proxy_headers {
-                        let name = HeaderName::from_bytes(key.as_bytes()).map_err(|err| {
-                            AppError::validation(ValidationError::InvalidProxyHeaderName {
-                                header: key.clone(),
-                                source: err,
-                            })
-                        })?;
-                        let val = HeaderValue::from_str(value).map_err(|err| {
-                            AppError::validation(ValidationError::InvalidProxyHeaderValue {
-                                header: key.clone(),
-                                source: err,
-                            })
-                        })?;
-                        headers.insert(name, val);
-                    }
-                    proxy = proxy.headers(headers);
-                }
-                client_builder = client_builder.proxy(proxy);
-            }
-            Err(e) => {
-                error!("Invalid proxy URL '{}': {}", proxy_url, e);
-                return Err(AppError::validation(ValidationError::InvalidProxyUrl {
-                    url: proxy_url.clone(),
-                    source: e,
-                }));
-            }
-        }
-    }
+    client_builder = apply_trust_settings(client_builder, args)?;
+    client_builder = apply_proxy_settings(client_builder, args)?;
 
     if args.proxy_http2 {
         client_builder = client_builder.http2_prior_knowledge();
@@ -226,84 +487,34 @@ pub fn setup_request_sender(
         }
     }
 
-    let client = match client_builder.build() {
-        Ok(c) => c,
+    match client_builder.build() {
+        Ok(client) => Ok(client),
         Err(e) => {
             error!("Failed to build HTTP client: {}", e);
-            return Err(AppError::http(HttpError::BuildClientFailed { source: e }));
-        }
-    };
-
-    let workload = if let Some(scenario) = args.scenario.clone() {
-        Workload::Scenario(
-            Arc::new(scenario),
-            Arc::new(args.connect_to.clone()),
-            args.host_header.clone(),
-            auth_config,
-        )
-    } else {
-        let url_source = resolve_url_source(args)?;
-        let body_source = resolve_body_source(args)?;
-        let form_fields = parse_form_fields(args)?;
-        let headers = build_headers(args);
-
-        let requires_dynamic = matches!(body_source, BodySource::Lines(_))
-            || matches!(url_source, UrlSource::List(_) | UrlSource::Regex(_))
-            || form_fields.is_some()
-            || !args.connect_to.is_empty()
-            || auth_config.is_some();
-
-        if requires_dynamic {
-            Workload::SingleDynamic(Arc::new(SingleRequestSpec {
-                method: args.method,
-                url: url_source,
-                headers,
-                body: body_source,
-                form: form_fields,
-                connect_to: args.connect_to.clone(),
-                auth: auth_config,
-            }))
-        } else {
-            let UrlSource::Static(url) = url_source else {
-                return Err(AppError::http(HttpError::InvalidUrlSourceForStaticWorkload));
-            };
-            let BodySource::Static(body) = body_source else {
-                return Err(AppError::http(
-                    HttpError::InvalidBodySourceForStaticWorkload,
-                ));
-            };
-
-            drop(auth_config);
-            let mut request_builder = match args.method {
-                HttpMethod::Get => client.get(&url),
-                HttpMethod::Post => client.post(&url),
-                HttpMethod::Patch => client.patch(&url),
-                HttpMethod::Put => client.put(&url),
-                HttpMethod::Delete => client.delete(&url),
-            };
-
-            for (key, value) in &headers {
-                request_builder = request_builder.header(key, value);
-            }
-
-            let request = match request_builder.body(body).build() {
-                Ok(req) => req,
-                Err(e) => {
-                    error!("Failed to build request: {}", e);
-                    return Err(AppError::http(HttpError::BuildRequestFailed { source: e }));
-                }
-            };
-
-            Workload::Single(Arc::new(request))
+            Err(AppError::http(HttpError::BuildClientFailed { source: e }))
         }
-    };
+    }
+}
 
-    Ok(create_sender_task(
-        args,
-        &shutdown_tx,
-        &metrics_tx,
-        log_sink.cloned(),
+/// Builds the DoH resolver with its own client, so the lookups skip the
+/// target's proxy, auth, and connect-rate settings.
+fn build_doh_resolver(args: &TesterArgs, doh_url: &str) -> AppResult<DohResolver> {
+    let url = Url::parse(doh_url).map_err(|err| {
+        AppError::validation(ValidationError::InvalidDohUrl {
+            url: doh_url.to_owned(),
+            source: err,
+        })
+    })?;
+    let client = Client::builder()
+        .timeout(args.request_timeout)
+        .connect_timeout(args.connect_timeout)
+        .danger_accept_invalid_certs(args.insecure)
+        .build()
+        .map_err(|err| AppError::http(HttpError::BuildClientFailed { source: err }))?;
+    Ok(DohResolver::new(
+        url,
         client,
-        workload,
+        args.ipv4_only,
+        args.ipv6_only,
     ))
 }
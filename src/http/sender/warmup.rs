@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use futures_util::StreamExt;
+use reqwest::{Client, Request, Url};
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::{
+    args::{HttpMethod, TesterArgs},
+    error::{AppError, AppResult, HttpError},
+    shutdown::ShutdownSender,
+};
+
+use super::super::workload::execute_request;
+use super::config::{build_headers, forced_request_version};
+
+/// Cache-warming requests issued before the measured workload starts.
+///
+/// Each non-empty line of the file is `URL` or `METHOD URL`; `#` starts a
+/// comment. Paths starting with `/` are resolved against `--url`. Requests
+/// carry the run's headers but never reach the metrics pipeline.
+pub(super) struct WarmupRequests {
+    path: String,
+    requests: Vec<Request>,
+    warmup_window: Option<Duration>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct WarmupReport {
+    pub(super) sent: usize,
+    pub(super) succeeded: usize,
+    pub(super) failed: usize,
+}
+
+impl WarmupRequests {
+    pub(super) fn from_args(args: &TesterArgs, client: &Client) -> AppResult<Option<Self>> {
+        let Some(path) = args.warmup_requests_file.as_deref() else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(path).map_err(|err| {
+            AppError::http(HttpError::ReadWarmupFile {
+                path: PathBuf::from(path),
+                source: err,
+            })
+        })?;
+        let base_url = args.url.as_deref().and_then(|value| Url::parse(value).ok());
+        let headers = build_headers(args);
+        let version = args.http_version.and_then(forced_request_version);
+
+        let mut requests = Vec::new();
+        for (index, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                AppError::http(HttpError::InvalidWarmupLine {
+                    path: PathBuf::from(path),
+                    line: index.saturating_add(1),
+                    value: line.to_owned(),
+                })
+            };
+            let (method, target) = match line.split_once(char::is_whitespace) {
+                Some((method, target)) => (
+                    HttpMethod::from_str(method, true).map_err(|_err| invalid())?,
+                    target.trim(),
+                ),
+                None => (args.method, line),
+            };
+            let url = if target.starts_with('/') {
+                base_url
+                    .as_ref()
+                    .and_then(|base| base.join(target).ok())
+                    .ok_or_else(invalid)?
+            } else {
+                Url::parse(target).map_err(|_err| invalid())?
+            };
+
+            let mut builder = match method {
+                HttpMethod::Get => client.get(url),
+                HttpMethod::Post => client.post(url),
+                HttpMethod::Patch => client.patch(url),
+                HttpMethod::Put => client.put(url),
+                HttpMethod::Delete => client.delete(url),
+            };
+            if let Some(version) = version {
+                builder = builder.version(version);
+            }
+            for (key, value) in &headers {
+                builder = builder.header(key, value);
+            }
+            let request = builder
+                .build()
+                .map_err(|err| AppError::http(HttpError::BuildRequestFailed { source: err }))?;
+            requests.push(request);
+        }
+        if requests.is_empty() {
+            return Err(AppError::http(HttpError::WarmupFileEmpty {
+                path: PathBuf::from(path),
+            }));
+        }
+
+        Ok(Some(Self {
+            path: path.to_owned(),
+            requests,
+            warmup_window: args.warmup,
+        }))
+    }
+
+    /// Sends every warmup request with up to `concurrency` in flight and logs the counts.
+    ///
+    /// Stops early when the run shuts down.
+    pub(super) async fn run(
+        &self,
+        client: &Client,
+        concurrency: usize,
+        shutdown_tx: &ShutdownSender,
+    ) -> WarmupReport {
+        let started = Instant::now();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+        let pending: Vec<_> = self
+            .requests
+            .iter()
+            .map(|request| {
+                let client = client.clone();
+                let request = request.try_clone();
+                async move {
+                    match request {
+                        Some(request) => execute_request(&client, request, true).await.is_ok(),
+                        None => false,
+                    }
+                }
+            })
+            .collect();
+        let mut results = futures_util::stream::iter(pending).buffer_unordered(concurrency.max(1));
+
+        let mut report = WarmupReport::default();
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => break,
+                next = results.next() => {
+                    let Some(ok) = next else {
+                        break;
+                    };
+                    report.sent = report.sent.saturating_add(1);
+                    if ok {
+                        report.succeeded = report.succeeded.saturating_add(1);
+                    } else {
+                        report.failed = report.failed.saturating_add(1);
+                    }
+                }
+            }
+        }
+
+        let elapsed = started.elapsed();
+        info!(
+            "Warmup requests: sent {} of {} from {} ({} ok, {} failed) in {}ms; not included in results.",
+            report.sent,
+            self.requests.len(),
+            self.path,
+            report.succeeded,
+            report.failed,
+            elapsed.as_millis()
+        );
+        if let Some(window) = self.warmup_window
+            && elapsed > window
+        {
+            warn!(
+                "Warmup requests took {}ms, longer than --warmup {}ms; the run started measuring before the cache was warm.",
+                elapsed.as_millis(),
+                window.as_millis()
+            );
+        }
+        report
+    }
+}
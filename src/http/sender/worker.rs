@@ -3,35 +3,59 @@ use std::time::Duration;
 
 use reqwest::Client;
 use tokio::sync::{Semaphore, mpsc};
-use tokio::time::{interval, sleep};
-use tracing::{error, warn};
+use tokio::time::{Instant, interval, sleep};
+use tracing::{error, info, warn};
 
 use crate::{
-    args::TesterArgs,
+    args::{LoadMode, TesterArgs},
     metrics::{LogSink, Metrics},
+    run_context::RunContext,
     shutdown::ShutdownSender,
 };
 
-use super::super::rate::build_rate_limiter;
+use super::super::bandwidth::{BandwidthLimiter, format_bytes_per_sec};
+use super::super::body_assert::BodyAssert;
+use super::super::connect_rate::ConnectRateLimiter;
+use super::super::connection_cycle::ConnectionCycle;
+use super::super::cookies::CookieJar;
+use super::super::deadline::DeadlineHeader;
+use super::super::digest::DigestAuth;
+use super::super::edge_sim::EdgeSim;
+use super::super::multiplex::Http2Streams;
+use super::super::rate::{MaxRateSettings, RatePacing, build_rate_limiter, until_next_wall_second};
+use super::super::request_id::RequestIdEcho;
+use super::super::response_size::ResponseSizeAssert;
+use super::super::sticky::{BackendTally, StickySession};
 use super::super::workload::{
-    RequestLimiter, ScenarioRunContext, WorkerContext, Workload, preflight_request,
+    AuthConfig, RequestLimiter, ScenarioRunContext, WorkerContext, Workload, preflight_request,
     run_scenario_iteration, run_single_dynamic_iteration, run_single_iteration,
 };
-use super::config::resolve_http2_parallel;
+use super::warmup::WarmupRequests;
 
+#[expect(clippy::too_many_arguments)]
 pub(super) fn create_sender_task(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<Arc<LogSink>>,
     client: Client,
     workload: Workload,
+    request_id_echo: Option<Arc<RequestIdEcho>>,
+    deadline_header: Option<Arc<DeadlineHeader>>,
+    response_size: Option<Arc<ResponseSizeAssert>>,
+    body_assert: Option<Arc<BodyAssert>>,
+    backend_tally: Option<Arc<BackendTally>>,
+    warmup_requests: Option<WarmupRequests>,
+    connect_limiter: Option<Arc<ConnectRateLimiter>>,
+    http2_streams: Option<Http2Streams>,
+    connection_cycle: Option<Arc<ConnectionCycle>>,
 ) -> tokio::task::JoinHandle<()> {
     let shutdown_tx = shutdown_tx.clone();
     let metrics_tx = metrics_tx.clone();
     let log_sink = log_sink;
 
-    let http2_parallel = resolve_http2_parallel(args);
+    let http2_parallel = http2_streams.as_ref().map_or(1, Http2Streams::parallel);
     let max_tasks = args.max_tasks.get().saturating_mul(http2_parallel);
     let spawn_rate = args
         .spawn_rate_per_tick
@@ -44,7 +68,27 @@ pub(super) fn create_sender_task(
     let request_limiter = RequestLimiter::new(args.requests.map(u64::from)).map(Arc::new);
     let burst_delay = args.burst_delay;
     let burst_rate = args.burst_rate.get();
+    let max_rate = MaxRateSettings::from_args(args, run);
     let wait_ongoing = args.wait_ongoing_requests_after_deadline;
+    let align_to_second = args.align_to_second;
+    let pacing = RatePacing::from_args(args);
+    let max_bandwidth = args.max_bandwidth;
+    let sim_downlink = args.sim_downlink;
+    let sim_latency = args.sim_latency;
+    let closed_loop = args.load_mode == LoadMode::Concurrency;
+    let cookies = args.cookies;
+    let redirect_limit = args.redirect_limit;
+    let record_urls = args.slowest.is_some();
+    let connection_timing = args.connection_timing;
+    if cookies && !matches!(workload, Workload::Scenario(..)) {
+        warn!("--cookies only applies to scenario steps.");
+    }
+    if connection_timing && matches!(workload, Workload::Scenario(..)) {
+        warn!("--connection-timing only applies to single-request workloads.");
+    }
+    if body_assert.is_some() && matches!(workload, Workload::Scenario(..)) {
+        warn!("--assert-body-*/--assert-json are ignored for scenarios; use step asserts.");
+    }
     let latency_correction = if args.rate_limit.is_some() {
         args.latency_correction
     } else {
@@ -61,30 +105,67 @@ pub(super) fn create_sender_task(
     }
 
     tokio::spawn(async move {
+        if let Some(AuthConfig::OAuth2 { session }) = workload.auth()
+            && let Err(err) = session.start(&client, &shutdown_tx).await
+        {
+            error!("Failed to fetch OAuth2 token: {}", err);
+            drop(shutdown_tx.send(()));
+            return;
+        }
         if let Err(err) = preflight_request(&client, &workload).await {
             error!("Test request failed: {}", err);
             drop(shutdown_tx.send(()));
             return;
         }
+        if let Some(warmup_requests) = warmup_requests.as_ref() {
+            warmup_requests.run(&client, max_tasks, &shutdown_tx).await;
+        }
 
         let mut shutdown_rx = shutdown_tx.subscribe();
+        if align_to_second {
+            tokio::select! {
+                _ = shutdown_rx.recv() => return,
+                () = sleep(until_next_wall_second()) => {}
+            }
+        }
         let mut spawn_interval = interval(Duration::from_millis(tick_interval));
         let mut total_spawned: usize = 0;
         let permits = Arc::new(Semaphore::new(0));
         let in_flight_counter = Arc::new(AtomicU64::new(0));
-        let rate_limiter =
-            build_rate_limiter(rate_limit, load_profile.as_ref(), burst_delay, burst_rate);
+        let rate_limiter = build_rate_limiter(
+            rate_limit,
+            load_profile.as_ref(),
+            burst_delay,
+            burst_rate,
+            pacing,
+            max_rate,
+        );
+        let bandwidth_limiter =
+            max_bandwidth.map(|limit| Arc::new(BandwidthLimiter::new(limit.bytes_per_sec())));
         let mut worker_handles = Vec::with_capacity(max_tasks);
 
-        for _ in 0..max_tasks {
+        for worker_index in 0..max_tasks {
             let permits = Arc::clone(&permits);
             let shutdown_tx = shutdown_tx.clone();
             let metrics_tx = metrics_tx.clone();
             let log_sink = log_sink.clone();
-            let client = client.clone();
+            let stream_group = http2_streams
+                .as_ref()
+                .and_then(|streams| streams.group_for(worker_index))
+                .cloned();
+            let mut client = stream_group
+                .as_ref()
+                .map_or_else(|| client.clone(), |group| group.client().clone());
+            let connection_cycle = connection_cycle.clone();
             let workload = workload.clone();
             let rate_limiter = rate_limiter.clone();
+            let bandwidth_limiter = bandwidth_limiter.clone();
             let request_limiter = request_limiter.clone();
+            let request_id_echo = request_id_echo.clone();
+            let deadline_header = deadline_header.clone();
+            let response_size = response_size.clone();
+            let body_assert = body_assert.clone();
+            let backend_tally = backend_tally.clone();
             let in_flight_counter = in_flight_counter.clone();
 
             let handle = tokio::spawn(async move {
@@ -99,12 +180,41 @@ pub(super) fn create_sender_task(
                 };
 
                 let mut request_seq: u64 = 0;
+                let mut cookie_jar = cookies.then(|| CookieJar::new(redirect_limit));
+                let digest = DigestAuth::from_auth(workload.auth());
+                let sticky = backend_tally.map(StickySession::new);
+                let edge_sim = EdgeSim::new(sim_downlink, sim_latency);
+                let mut connection_requests: u64 = 0;
                 loop {
+                    if let Some(fresh) = connection_cycle
+                        .as_ref()
+                        .and_then(|cycle| cycle.next_client(&mut connection_requests))
+                    {
+                        match fresh {
+                            Ok(fresh) => client = fresh,
+                            Err(err) => {
+                                error!("Failed to build a fresh connection client: {}", err);
+                                drop(shutdown_tx.send(()));
+                                break;
+                            }
+                        }
+                    }
                     let worker = WorkerContext {
                         shutdown_tx: &shutdown_tx,
                         rate_limiter: rate_limiter.as_ref(),
+                        bandwidth_limiter: bandwidth_limiter.as_ref(),
                         request_limiter: request_limiter.as_ref(),
+                        request_id_echo: request_id_echo.as_deref(),
+                        deadline_header: deadline_header.as_deref(),
+                        response_size: response_size.as_deref(),
+                        body_assert: body_assert.as_deref(),
                         in_flight_counter: &in_flight_counter,
+                        digest: digest.as_ref(),
+                        sticky: sticky.as_ref(),
+                        edge_sim: edge_sim.as_ref(),
+                        stream_group: stream_group.as_deref(),
+                        record_urls,
+                        connection_timing,
                         client: &client,
                         log_sink: &log_sink,
                         metrics_tx: &metrics_tx,
@@ -120,17 +230,31 @@ pub(super) fn create_sender_task(
                             run_single_dynamic_iteration(&mut shutdown_rx_worker, &worker, spec)
                                 .await
                         }
-                        Workload::Scenario(scenario, connect_to, host_header, auth) => {
+                        Workload::Scenario(
+                            scenario,
+                            connect_to,
+                            host_header,
+                            auth,
+                            version,
+                            fake,
+                            step_clients,
+                            connect_to_clients,
+                        ) => {
                             let mut context = ScenarioRunContext {
                                 client: &client,
                                 scenario,
                                 connect_to,
                                 host_header: host_header.as_deref(),
                                 auth: auth.as_ref(),
+                                version: *version,
+                                fake,
+                                step_clients,
+                                connect_to_clients,
                                 expected_status_code,
                                 log_sink: &log_sink,
                                 metrics_tx: &metrics_tx,
                                 request_seq: &mut request_seq,
+                                cookies: cookie_jar.as_mut(),
                             };
                             run_scenario_iteration(&mut shutdown_rx_worker, &worker, &mut context)
                                 .await
@@ -142,7 +266,7 @@ pub(super) fn create_sender_task(
                         break;
                     }
 
-                    if rate_limiter.is_none() {
+                    if rate_limiter.is_none() && !closed_loop {
                         sleep(Duration::from_millis(100)).await;
                     }
                 }
@@ -173,5 +297,96 @@ pub(super) fn create_sender_task(
                 break;
             }
         }
+
+        if let Some(limiter) = bandwidth_limiter {
+            info!(
+                "Bandwidth: achieved {} of configured {} ({} bytes sent+received).",
+                format_bytes_per_sec(limiter.achieved_bytes_per_sec(Instant::now())),
+                format_bytes_per_sec(limiter.bytes_per_sec()),
+                limiter.total_bytes()
+            );
+        }
+
+        if let Some(limiter) = connect_limiter {
+            let achieved_x100 = limiter.achieved_per_sec_x100(Instant::now());
+            info!(
+                "Connect rate: achieved {}.{:02}/s of configured {}/s ({} connections).",
+                achieved_x100 / 100,
+                achieved_x100 % 100,
+                limiter.connects_per_sec(),
+                limiter.total_connects()
+            );
+        }
+
+        if let Some(cycle) = connection_cycle {
+            info!(
+                "Connection cycling: {} connections opened ({} requests per connection).",
+                cycle.connections(),
+                cycle.requests_per_connection()
+            );
+        }
+
+        if let Some(streams) = http2_streams {
+            let report = streams.report();
+            info!(
+                "HTTP/2 multiplexing: {}.{:02} concurrent streams per connection on average (target {}), {} requests over {} connections for {} tasks.",
+                report.avg_streams_x100 / 100,
+                report.avg_streams_x100 % 100,
+                report.parallel,
+                report.streams,
+                report.connections,
+                report.tasks
+            );
+            if report.connections > u64::try_from(report.tasks).unwrap_or(u64::MAX) {
+                warn!(
+                    "HTTP/2 clients opened extra connections; the server may not speak HTTP/2 or may cap concurrent streams."
+                );
+            }
+        }
+
+        if let Some(echo) = request_id_echo {
+            if echo.checked() == 0 {
+                warn!(
+                    "--verify-request-id-echo checked no responses; no request carried a {} header.",
+                    echo.header()
+                );
+            } else {
+                info!(
+                    "Request ID echo: {} mismatches out of {} checked responses ({}).",
+                    echo.mismatches(),
+                    echo.checked(),
+                    echo.header()
+                );
+            }
+        }
+
+        if let Some(check) = response_size {
+            info!(
+                "Response size: {} of {} responses outside {}.",
+                check.violations(),
+                check.checked(),
+                check.bounds()
+            );
+        }
+
+        if let Some(tally) = backend_tally {
+            if tally.sticky_requests() == 0 {
+                warn!(
+                    "--session-cookie {} was never set by the target; no request was pinned.",
+                    tally.cookie_name()
+                );
+            } else {
+                info!(
+                    "Sticky sessions: {} session breaks out of {} requests carrying {}.",
+                    tally.breaks(),
+                    tally.sticky_requests(),
+                    tally.cookie_name()
+                );
+            }
+            match tally.distribution_line() {
+                Some(line) => info!("Backend distribution: {}.", line),
+                None => warn!("No backend header seen; backend distribution unavailable."),
+            }
+        }
     })
 }
@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+use std::sync::{
+    Arc, Mutex, PoisonError,
+    atomic::{AtomicU64, Ordering},
+};
+
+use reqwest::Request;
+use reqwest::header::{COOKIE, HeaderMap, HeaderName, HeaderValue, SERVER, SET_COOKIE};
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult, ValidationError};
+
+/// Run-wide backend distribution for `--session-cookie`.
+///
+/// A session break is a request that carried the session cookie but was
+/// served by a different backend than the one the virtual user was pinned to.
+#[derive(Debug)]
+pub(crate) struct BackendTally {
+    cookie_name: String,
+    backend_header: HeaderName,
+    counts: Mutex<BTreeMap<String, u64>>,
+    sticky_requests: AtomicU64,
+    breaks: AtomicU64,
+}
+
+impl BackendTally {
+    pub(crate) fn from_args(args: &TesterArgs) -> AppResult<Option<Arc<Self>>> {
+        let Some(name) = args.session_cookie.as_deref() else {
+            return Ok(None);
+        };
+        let name = name.trim();
+        if name.is_empty() || name.contains(['=', ';', ' ']) {
+            return Err(AppError::validation(
+                ValidationError::InvalidSessionCookie {
+                    name: name.to_owned(),
+                },
+            ));
+        }
+        let header = HeaderName::from_bytes(args.backend_header.as_bytes()).map_err(|err| {
+            AppError::validation(ValidationError::InvalidBackendHeader {
+                header: args.backend_header.clone(),
+                source: err,
+            })
+        })?;
+        Ok(Some(Arc::new(Self::new(name.to_owned(), header))))
+    }
+
+    pub(crate) const fn new(cookie_name: String, backend_header: HeaderName) -> Self {
+        Self {
+            cookie_name,
+            backend_header,
+            counts: Mutex::new(BTreeMap::new()),
+            sticky_requests: AtomicU64::new(0),
+            breaks: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn cookie_name(&self) -> &str {
+        &self.cookie_name
+    }
+
+    pub(crate) fn sticky_requests(&self) -> u64 {
+        self.sticky_requests.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn breaks(&self) -> u64 {
+        self.breaks.load(Ordering::Relaxed)
+    }
+
+    /// Requests per backend, most-used first.
+    pub(crate) fn distribution(&self) -> Vec<(String, u64)> {
+        let counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut entries: Vec<(String, u64)> = counts
+            .iter()
+            .map(|(backend, count)| (backend.clone(), *count))
+            .collect();
+        entries.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+        entries
+    }
+
+    /// Formats the distribution as `name count (pct%)` entries.
+    pub(crate) fn distribution_line(&self) -> Option<String> {
+        let entries = self.distribution();
+        let total = entries
+            .iter()
+            .fold(0_u64, |sum, (_, count)| sum.saturating_add(*count));
+        if total == 0 {
+            return None;
+        }
+        let parts: Vec<String> = entries
+            .iter()
+            .map(|(backend, count)| {
+                let pct_x100 = count.saturating_mul(10_000).checked_div(total).unwrap_or(0);
+                format!(
+                    "{} {} ({}.{:02}%)",
+                    backend,
+                    count,
+                    pct_x100 / 100,
+                    pct_x100 % 100
+                )
+            })
+            .collect();
+        Some(parts.join(", "))
+    }
+
+    fn backend(&self, headers: &HeaderMap) -> Option<String> {
+        headers
+            .get(&self.backend_header)
+            .or_else(|| headers.get(SERVER))
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn count(&self, backend: &str) {
+        let mut counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = counts.entry(backend.to_owned()).or_insert(0);
+        *entry = entry.saturating_add(1);
+    }
+
+    /// The session cookie value from `Set-Cookie`; `Some(None)` when it is expired.
+    fn session_cookie(&self, headers: &HeaderMap) -> Option<Option<String>> {
+        let mut found = None;
+        for header in headers.get_all(SET_COOKIE) {
+            let Ok(set_cookie) = header.to_str() else {
+                continue;
+            };
+            let mut parts = set_cookie.split(';');
+            let Some((name, value)) = parts.next().and_then(|pair| pair.split_once('=')) else {
+                continue;
+            };
+            if name.trim() != self.cookie_name {
+                continue;
+            }
+            let expired = parts.any(|attribute| {
+                let (key, attr_value) = attribute.split_once('=').unwrap_or((attribute, ""));
+                key.trim().eq_ignore_ascii_case("max-age")
+                    && attr_value
+                        .trim()
+                        .parse::<i64>()
+                        .is_ok_and(|seconds| seconds <= 0)
+            });
+            found = Some((!expired).then(|| value.trim().to_owned()));
+        }
+        found
+    }
+}
+
+/// One virtual user's sticky session, kept across iterations.
+#[derive(Debug)]
+pub(crate) struct StickySession {
+    tally: Arc<BackendTally>,
+    state: Mutex<StickyState>,
+}
+
+#[derive(Debug, Default)]
+struct StickyState {
+    cookie: Option<String>,
+    backend: Option<String>,
+}
+
+impl StickySession {
+    pub(crate) fn new(tally: Arc<BackendTally>) -> Self {
+        Self {
+            tally,
+            state: Mutex::new(StickyState::default()),
+        }
+    }
+
+    /// Sends the session cookie unless the request already carries one by that name.
+    pub(crate) fn apply(&self, request: &mut Request) {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(value) = state.cookie.as_deref() else {
+            return;
+        };
+        let name = self.tally.cookie_name();
+        let existing = request
+            .headers()
+            .get(COOKIE)
+            .and_then(|header| header.to_str().ok());
+        if existing.is_some_and(|header| {
+            header.split(';').any(|pair| {
+                pair.split_once('=')
+                    .is_some_and(|(key, _)| key.trim() == name)
+            })
+        }) {
+            return;
+        }
+        let header = existing.map_or_else(
+            || format!("{}={}", name, value),
+            |existing| format!("{}; {}={}", existing, name, value),
+        );
+        if let Ok(header) = HeaderValue::from_str(&header) {
+            request.headers_mut().insert(COOKIE, header);
+        }
+    }
+
+    /// Records which backend answered and picks up a new or expired session cookie.
+    pub(crate) fn observe(&self, headers: &HeaderMap) {
+        let backend = self.tally.backend(headers);
+        if let Some(backend) = backend.as_deref() {
+            self.tally.count(backend);
+        }
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.cookie.is_some() {
+            self.tally.sticky_requests.fetch_add(1, Ordering::Relaxed);
+            if let Some(backend) = backend.as_deref() {
+                if state
+                    .backend
+                    .as_deref()
+                    .is_some_and(|pinned| pinned != backend)
+                {
+                    self.tally.breaks.fetch_add(1, Ordering::Relaxed);
+                }
+                state.backend = Some(backend.to_owned());
+            }
+        }
+        if let Some(cookie) = self.tally.session_cookie(headers) {
+            // A new session starts on whichever backend issued it.
+            if cookie != state.cookie {
+                state.backend = cookie.as_ref().and(backend);
+            }
+            state.cookie = cookie;
+        }
+    }
+}
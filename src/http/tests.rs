@@ -1,9 +1,31 @@
-use super::workload::{RequestLimiter, render_template};
+use super::bandwidth::{BandwidthLimiter, format_bytes_per_sec};
+use super::body_assert::BodyAssert;
+use super::conn_timing::{ConnectTimingLayer, mark_first_byte, traced};
+use super::connect_rate::ConnectRateLimiter;
+use super::cookies::CookieJar;
+use super::deadline::DeadlineHeader;
+use super::diagnose::DiagnosticStatus;
+use super::edge_sim::EdgeSim;
+use super::error_reason::classify;
+use super::header_gen::GeneratedHeaders;
+use super::multiplex::{Http2Multiplex, Http2Streams, StreamGroup};
+use super::request_id::RequestIdEcho;
+use super::response_size::ResponseSizeAssert;
+use super::sticky::{BackendTally, StickySession};
+use super::workload::{
+    FakeBody, FakeData, JsonBody, RequestLimiter, Workload, build_request_from_spec, extract_vars,
+    render_template,
+};
 use super::*;
-use crate::args::{HttpMethod, LoadMode, PositiveU64, PositiveUsize, Protocol, TesterArgs};
-use crate::error::{AppError, AppResult};
-use crate::metrics::Metrics;
+use crate::args::{
+    ExpectedStatus, HttpMethod, HttpVersion, LoadMode, PositiveU64, PositiveUsize, Protocol,
+    TesterArgs,
+};
+use crate::error::{AppError, AppResult, HttpError, ValidationError};
+use crate::metrics::{ErrorReason, Metrics};
+use crate::run_context::RunContext;
 use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast;
 
@@ -29,14 +51,27 @@ fn base_args(url: String) -> AppResult<TesterArgs> {
         replay_snapshot_end: None,
         replay_snapshot_out: None,
         replay_snapshot_format: "json".to_owned(),
+        replay_video: None,
+        replay_export_chart_data: None,
+        replay_export_charts: false,
         method: HttpMethod::Get,
         protocol: Protocol::Http,
+        grpc_mode: None,
+        ws_messages_per_conn: None,
+        ws_message_interval_ms: None,
+        mqtt_mode: crate::args::MqttMode::Publish,
+        mqtt_topic: None,
+        sse_events_per_conn: None,
+        sse_conn_duration: None,
+        sse_resume: false,
+        min_events: None,
         load_mode: LoadMode::Arrival,
         url: Some(url),
         urls_from_file: false,
         rand_regex_url: false,
         max_repeat: positive_usize(4)?,
         dump_urls: None,
+        dry_run: false,
         headers: vec![],
         accept_header: None,
         content_type: None,
@@ -44,32 +79,59 @@ fn base_args(url: String) -> AppResult<TesterArgs> {
         authorized: false,
         data: String::new(),
         form: vec![],
+        form_file: vec![],
         basic_auth: None,
+        basic_auth_file: None,
+        bearer_token: None,
+        bearer_token_file: None,
+        digest_auth: None,
+        oauth2_token_url: None,
+        oauth2_client_id: None,
+        oauth2_client_secret: None,
+        oauth2_scope: None,
         aws_session: None,
         aws_sigv4: None,
         data_file: None,
         data_lines: None,
+        data_dir: None,
+        data_dir_max_bytes: None,
+        seed: None,
+        json_template: false,
+        literal_placeholders: false,
         target_duration: positive_u64(1)?,
         wait_ongoing_requests_after_deadline: false,
+        align_to_second: false,
+        arrival_distribution: None,
         requests: None,
-        expected_status_code: 200,
+        expected_status_code: ExpectedStatus::single(200),
         request_timeout: Duration::from_secs(10),
         redirect_limit: 10,
         disable_keepalive: false,
+        requests_per_connection: None,
         disable_compression: false,
+        cookies: false,
+        session_cookie: None,
+        backend_header: "X-Backend".to_owned(),
         pool_max_idle_per_host: None,
         pool_idle_timeout_ms: None,
         http_version: None,
         connect_timeout: Duration::from_secs(5),
         charts_path: "./charts".to_owned(),
         no_charts: true,
+        no_final_charts: false,
         charts_latency_bucket_ms: positive_u64(100)?,
+        chart_width: 1_600,
+        chart_height: 600,
+        chart_dpi: 96,
+        usl_chart: false,
         verbose: false,
         config: None,
+        config_profile: None,
         tmp_path: "./tmp".to_owned(),
         load_profile: None,
         controller_listen: None,
         controller_mode: crate::args::ControllerMode::Auto,
+        controller_also_runs: false,
         control_listen: None,
         control_auth_token: None,
         agent_join: None,
@@ -82,20 +144,48 @@ fn base_args(url: String) -> AppResult<TesterArgs> {
         agent_reconnect_ms: positive_u64(1000)?,
         agent_heartbeat_interval_ms: positive_u64(1000)?,
         agent_heartbeat_timeout_ms: positive_u64(3000)?,
+        agent_health_listen: None,
         keep_tmp: false,
         warmup: None,
+        warmup_requests_file: None,
+        echo_config_on_start: false,
+        echo_config_format: crate::args::EchoConfigFormat::Text,
         output: None,
         output_format: None,
         time_unit: None,
         export_csv: None,
         export_json: None,
         export_jsonl: None,
+        export_histogram: None,
         db_url: None,
+        checkpoint_out: None,
+        checkpoint_interval_secs: None,
+        stream_samples: None,
+        resume: false,
         log_shards: positive_usize(1)?,
         no_ui: true,
         no_splash: true,
+        hold_ui: false,
+        stop_file: None,
         ui_window_ms: positive_u64(10_000)?,
+        baseline: None,
+        baseline_label: None,
         summary: false,
+        summary_template: None,
+        summary_out: None,
+        history_file: "./history.jsonl".to_owned(),
+        no_history: true,
+        notify_webhook: None,
+        notify_on: crate::args::NotifyOn::Always,
+        protocol_version_report: false,
+        connection_timing: false,
+        strict_charts: false,
+        per_status_latency: false,
+        slowest: None,
+        summary_percentile_table: false,
+        stats_extended: false,
+        compat: None,
+        summary_format: None,
         show_selections: false,
         tls_min: None,
         tls_max: None,
@@ -117,11 +207,39 @@ fn base_args(url: String) -> AppResult<TesterArgs> {
         rate_limit: None,
         burst_delay: None,
         burst_rate: positive_usize(1)?,
+        find_max_rate: false,
+        target_p99_ms: None,
         latency_correction: false,
+        max_bandwidth: None,
+        sim_downlink: None,
+        sim_latency: None,
+        connect_rate: None,
+        max_bytes: None,
+        abort_on_errors: None,
+        abort_on_error_rate: None,
+        abort_min_requests: positive_u64(100)?,
+        verify_request_id_echo: false,
+        request_id_header: "X-Request-Id".to_owned(),
+        deadline_header: None,
+        deadline_format: crate::args::DeadlineFormat::RelativeMs,
+        correlation_header: None,
+        correlation_log: None,
+        rate_schedule: None,
+        assert_response_bytes_below: None,
+        assert_response_bytes_above: None,
+        assert_body_contains: None,
+        assert_body_regex: None,
+        assert_json: None,
+        assert_success_rate: None,
+        assert_p99_ms: None,
+        assert_max_errors: None,
+        diagnose: false,
+        diagnose_strict: false,
         connect_to: vec![],
         host_header: None,
         ipv6_only: false,
         ipv4_only: false,
+        doh_url: None,
         no_pre_lookup: false,
         no_color: false,
         ui_fps: 16,
@@ -133,6 +251,8 @@ fn base_args(url: String) -> AppResult<TesterArgs> {
         alloc_profiler_ms: None,
         alloc_profiler_dump_ms: None,
         alloc_profiler_dump_path: "./alloc-prof".to_owned(),
+        alloc_profiler_spike_ms: None,
+        alloc_profiler_spike_inflight: None,
         scenario: None,
         script: None,
         plugin: vec![],
@@ -140,8 +260,12 @@ fn base_args(url: String) -> AppResult<TesterArgs> {
         uninstall_service: false,
         service_name: None,
         sinks: None,
+        tags: Vec::new(),
         distributed_silent: false,
+        run_id: None,
         distributed_stream_summaries: false,
+        agent_breakdown: false,
+        per_agent_summary: false,
         distributed_stream_interval_ms: None,
     })
 }
@@ -165,7 +289,13 @@ fn invalid_proxy_sends_shutdown() -> AppResult<()> {
         let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
         let (metrics_tx, _metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(1);
 
-        let result = setup_request_sender(&args, &shutdown_tx, &metrics_tx, None);
+        let result = setup_request_sender(
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        );
         if result.is_ok() {
             return Err(AppError::validation("Expected error for invalid proxy"));
         }
@@ -181,7 +311,13 @@ fn invalid_url_sends_shutdown() -> AppResult<()> {
         let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
         let (metrics_tx, _metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
 
-        let result = setup_request_sender(&args, &shutdown_tx, &metrics_tx, None);
+        let result = setup_request_sender(
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        );
         if result.is_ok() {
             return Err(AppError::validation("Expected error for invalid URL"));
         }
@@ -189,6 +325,46 @@ fn invalid_url_sends_shutdown() -> AppResult<()> {
     })
 }
 
+#[test]
+fn warmup_requests_file_rejects_empty_and_invalid_lines() -> AppResult<()> {
+    run_async_test(async {
+        let dir = tempfile::tempdir()
+            .map_err(|err| AppError::validation(format!("Failed to create tempdir: {}", err)))?;
+        let cases = [
+            ("# only comments\n\n", false),
+            ("GET /warm\nFETCH /bad\n", false),
+            ("/relative\nPOST http://localhost/cache\n# comment\n", true),
+        ];
+        for (index, (content, expect_ok)) in cases.into_iter().enumerate() {
+            let path = dir.path().join(format!("warmup_{}.txt", index));
+            std::fs::write(&path, content)
+                .map_err(|err| AppError::validation(format!("Failed to write file: {}", err)))?;
+            let mut args = base_args("http://localhost".to_owned())?;
+            args.warmup_requests_file = Some(path.to_string_lossy().into_owned());
+            let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+            let (metrics_tx, _metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(1);
+
+            let result = setup_request_sender(
+                &args,
+                &RunContext::default(),
+                &shutdown_tx,
+                &metrics_tx,
+                None,
+            );
+            if let Ok(handle) = result.as_ref() {
+                handle.abort();
+            }
+            if result.is_ok() != expect_ok {
+                return Err(AppError::validation(format!(
+                    "Unexpected warmup file result for {:?}",
+                    content
+                )));
+            }
+        }
+        Ok(())
+    })
+}
+
 #[test]
 fn rate_controller_ramps_tokens() -> AppResult<()> {
     let plan = RatePlan {
@@ -196,6 +372,7 @@ fn rate_controller_ramps_tokens() -> AppResult<()> {
         stages: vec![RateStage {
             duration_secs: 2,
             target_rpm: 1200,
+            sine: None,
         }],
     };
     let initial_rpm = plan.initial_rpm;
@@ -232,6 +409,123 @@ fn rate_controller_ramps_tokens() -> AppResult<()> {
     Ok(())
 }
 
+#[test]
+fn arrival_distributions_keep_the_target_mean_rate() -> AppResult<()> {
+    use rand::SeedableRng;
+
+    const TARGET_RPS: u64 = 1_000;
+    const ARRIVALS: u64 = 200_000;
+    let mean = mean_gap_ns(TARGET_RPS.saturating_mul(60))
+        .ok_or_else(|| AppError::validation("Expected a mean gap"))?;
+    if mean != 1_000_000 {
+        return Err(AppError::validation(format!(
+            "Unexpected mean gap {}",
+            mean
+        )));
+    }
+    if mean_gap_ns(0).is_some() {
+        return Err(AppError::validation("Expected no arrivals at zero rpm"));
+    }
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    for distribution in [
+        crate::args::ArrivalDistribution::Uniform,
+        crate::args::ArrivalDistribution::Poisson,
+        crate::args::ArrivalDistribution::Exponential,
+    ] {
+        let mut total_ns: u64 = 0;
+        let mut longest_ns: u64 = 0;
+        for _ in 0..ARRIVALS {
+            let gap = next_gap_ns(distribution, mean, &mut rng);
+            total_ns = total_ns.saturating_add(gap);
+            longest_ns = longest_ns.max(gap);
+        }
+        // Achieved rate in thousandths of the target; must land within 2%.
+        let rate_permille = ARRIVALS
+            .saturating_mul(1_000_000_000_000)
+            .checked_div(total_ns.saturating_mul(TARGET_RPS))
+            .unwrap_or(0);
+        if !(980..=1_020).contains(&rate_permille) {
+            return Err(AppError::validation(format!(
+                "{:?} rate was {} permille of the target",
+                distribution, rate_permille
+            )));
+        }
+        let bursty = longest_ns > mean.saturating_mul(5);
+        if bursty == (distribution == crate::args::ArrivalDistribution::Uniform) {
+            return Err(AppError::validation(format!(
+                "{:?} longest gap {}ns does not match its distribution",
+                distribution, longest_ns
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn rate_controller_follows_sine_stage() -> AppResult<()> {
+    let plan = RatePlan {
+        initial_rpm: 0,
+        stages: vec![
+            RateStage {
+                duration_secs: 6,
+                target_rpm: 600,
+                sine: Some(SineStage {
+                    min_rpm: 600,
+                    max_rpm: 1800,
+                    period_secs: 4,
+                }),
+            },
+            RateStage {
+                duration_secs: 2,
+                target_rpm: 0,
+                sine: None,
+            },
+        ],
+    };
+    let mut controller = RateController {
+        plan,
+        stage_idx: 0,
+        stage_elapsed_secs: 0,
+        stage_start_rpm: 0,
+        remainder: 0,
+    };
+    let rpms: Vec<u64> = (0..8).map(|_| controller.current_rpm()).collect();
+    // min, ~midpoint, peak, ~midpoint, min, ~midpoint; the ramp then starts from
+    // where the wave stopped (peak at t=6).
+    let expected = vec![600, 1197, 1800, 1197, 600, 1197, 1800, 900];
+    if rpms != expected {
+        return Err(AppError::validation(format!(
+            "Unexpected sine rpms: {:?}",
+            rpms
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn until_next_wall_second_lands_on_a_boundary() -> AppResult<()> {
+    let delay = until_next_wall_second();
+    if delay < Duration::from_millis(50) || delay > Duration::from_millis(1050) {
+        return Err(AppError::validation(format!(
+            "Unexpected alignment delay {:?}",
+            delay
+        )));
+    }
+    let target = std::time::SystemTime::now()
+        .checked_add(delay)
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .ok_or_else(|| AppError::validation("Failed to compute aligned time"))?;
+    let offset_ms = target.subsec_millis();
+    if offset_ms > 5 && offset_ms < 995 {
+        return Err(AppError::validation(format!(
+            "Expected a second boundary, landed {}ms into the second",
+            offset_ms
+        )));
+    }
+    Ok(())
+}
+
 #[test]
 fn render_template_substitutes_vars() -> AppResult<()> {
     let vars = std::collections::BTreeMap::from([
@@ -248,6 +542,341 @@ fn render_template_substitutes_vars() -> AppResult<()> {
     Ok(())
 }
 
+#[test]
+fn extract_vars_reads_json_paths_and_headers() -> AppResult<()> {
+    let extract = std::collections::BTreeMap::from([
+        ("id".to_owned(), "$.data[1].id".parse()?),
+        ("token".to_owned(), "$.token".parse()?),
+        ("session".to_owned(), "X-Session".parse()?),
+    ]);
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        "x-session",
+        reqwest::header::HeaderValue::from_static("abc"),
+    );
+    let body = br#"{"token":"t-1","data":[{"id":1},{"id":2}]}"#;
+    let mut vars = std::collections::BTreeMap::new();
+    if let Err(message) = extract_vars(&extract, &headers, body, &mut vars) {
+        return Err(AppError::validation(format!(
+            "Unexpected extract miss: {}",
+            message
+        )));
+    }
+    let rendered = render_template("/items/{{id}}?t={{token}}&s={{session}}", &vars);
+    if rendered != "/items/2?t=t-1&s=abc" {
+        return Err(AppError::validation(format!(
+            "Unexpected render: {}",
+            rendered
+        )));
+    }
+
+    let missing = std::collections::BTreeMap::from([("id".to_owned(), "$.data[5].id".parse()?)]);
+    if extract_vars(&missing, &headers, body, &mut vars).is_ok() {
+        return Err(AppError::validation("Expected a missing JSONPath to fail"));
+    }
+    Ok(())
+}
+
+#[test]
+fn cookie_jar_replays_set_cookie_on_matching_hosts() -> AppResult<()> {
+    let client = reqwest::Client::new();
+    let login = client.post("http://app.example.com/login").build()?;
+    let mut headers = reqwest::header::HeaderMap::new();
+    for set_cookie in [
+        "session=abc; Path=/; HttpOnly",
+        "theme=dark; Domain=.example.com",
+        "stale=1; Max-Age=0",
+    ] {
+        headers.append(
+            reqwest::header::SET_COOKIE,
+            reqwest::header::HeaderValue::from_static(set_cookie),
+        );
+    }
+    let mut jar = CookieJar::default();
+    jar.store(login.url(), &headers);
+
+    let mut same_host = client
+        .get("http://app.example.com/profile")
+        .header(reqwest::header::COOKIE, "lang=en")
+        .build()?;
+    jar.apply(&mut same_host);
+    let mut sibling = client.get("http://api.example.com/items").build()?;
+    jar.apply(&mut sibling);
+
+    let cookie = |request: &reqwest::Request| {
+        request
+            .headers()
+            .get(reqwest::header::COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+    };
+    if cookie(&same_host).as_deref() != Some("lang=en; session=abc; theme=dark")
+        || cookie(&sibling).as_deref() != Some("theme=dark")
+    {
+        return Err(AppError::validation(format!(
+            "Unexpected cookies: {:?} / {:?}",
+            cookie(&same_host),
+            cookie(&sibling)
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn cookie_jar_honours_path_secure_and_expiry() -> AppResult<()> {
+    let client = reqwest::Client::new();
+    let login = client.get("http://app.example.com/account/login").build()?;
+    let mut headers = reqwest::header::HeaderMap::new();
+    for set_cookie in [
+        "scoped=1; Path=/account",
+        "dir=1",
+        "tls=1; Secure; Path=/",
+        "gone=1; Path=/; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+        "later=1; Path=/; Expires=Fri, 01 Jan 2100 00:00:00 GMT",
+        "aged=1; Path=/; Max-Age=0; Expires=Fri, 01 Jan 2100 00:00:00 GMT",
+    ] {
+        headers.append(
+            reqwest::header::SET_COOKIE,
+            reqwest::header::HeaderValue::from_static(set_cookie),
+        );
+    }
+    let mut jar = CookieJar::default();
+    jar.store(login.url(), &headers);
+
+    let cookie = |url: &str| -> AppResult<Option<String>> {
+        let mut request = client.get(url).build()?;
+        jar.apply(&mut request);
+        Ok(request
+            .headers()
+            .get(reqwest::header::COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned))
+    };
+    let cases = [
+        (
+            "http://app.example.com/account/settings",
+            Some("scoped=1; dir=1; later=1"),
+        ),
+        ("http://app.example.com/accounts", Some("later=1")),
+        ("https://app.example.com/", Some("tls=1; later=1")),
+    ];
+    for (url, expected) in cases {
+        let actual = cookie(url)?;
+        if actual.as_deref() != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected cookies for {}: {:?}",
+                url, actual
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn scenario_cookies_follow_set_cookie_on_redirects() -> AppResult<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        // The session cookie is only set on the 302, and /home requires it.
+        let server = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0_u8; 2048];
+                    while let Ok(read) = stream.read(&mut buf).await {
+                        if read == 0 {
+                            break;
+                        }
+                        let request = String::from_utf8_lossy(buf.get(..read).unwrap_or_default());
+                        let response: &[u8] = if request.starts_with("GET /login ") {
+                            b"HTTP/1.1 302 Found\r\nLocation: /home\r\nSet-Cookie: session=abc; Path=/\r\nContent-Length: 0\r\n\r\n"
+                        } else if request.to_ascii_lowercase().contains("cookie: session=abc") {
+                            b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok"
+                        } else {
+                            b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n"
+                        };
+                        if stream.write_all(response).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut args = base_args(format!("http://{}/", addr))?;
+        args.cookies = true;
+        args.max_tasks = positive_usize(1)?;
+        args.target_duration = positive_u64(30)?;
+        args.requests = Some(positive_u64(2)?);
+        args.scenario = Some(crate::args::Scenario {
+            base_url: Some(format!("http://{}", addr)),
+            vars: std::collections::BTreeMap::new(),
+            steps: vec![crate::args::ScenarioStep {
+                name: None,
+                method: HttpMethod::Get,
+                url: None,
+                path: Some("/login".to_owned()),
+                headers: Vec::new(),
+                body: None,
+                assert_status: None,
+                assert_body_contains: None,
+                think_time: None,
+                vars: std::collections::BTreeMap::new(),
+                extract: std::collections::BTreeMap::new(),
+                cert: None,
+                key: None,
+            }],
+        });
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(16);
+        let sender = setup_request_sender(
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        drop(metrics_tx);
+        for _ in 0..2 {
+            let metric = tokio::time::timeout(Duration::from_secs(10), metrics_rx.recv())
+                .await
+                .map_err(|_err| AppError::validation("Timed out waiting for metrics"))?
+                .ok_or_else(|| AppError::validation("Metrics channel closed early"))?;
+            if metric.status_code != 200 || metric.transport_error {
+                return Err(AppError::validation(format!(
+                    "Expected the redirect to carry the session cookie: {:?}",
+                    metric
+                )));
+            }
+        }
+        drop(shutdown_tx.send(()));
+        sender
+            .await
+            .map_err(|err| AppError::validation(format!("Sender task failed: {}", err)))?;
+        server.abort();
+        Ok(())
+    })
+}
+
+#[test]
+fn fake_body_is_reproducible_with_seed() -> AppResult<()> {
+    let template =
+        r#"{"name":"{{name}}","email":"{{ email }}","bio":"{{lorem:3}}","age":{{int:18:99}}}"#;
+    let first = FakeBody::parse(template, Some(7), false)?
+        .ok_or_else(|| AppError::validation("Expected fake placeholders"))?;
+    let second = FakeBody::parse(template, Some(7), false)?
+        .ok_or_else(|| AppError::validation("Expected fake placeholders"))?;
+    let first_bodies = [first.render(), first.render()];
+    let second_bodies = [second.render(), second.render()];
+    if first_bodies != second_bodies || first_bodies.first() == first_bodies.get(1) {
+        return Err(AppError::validation(format!(
+            "Expected seeded, varying bodies: {:?} / {:?}",
+            first_bodies, second_bodies
+        )));
+    }
+    let body = first_bodies.first().cloned().unwrap_or_default();
+    let json: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|err| AppError::validation(format!("Invalid JSON {}: {}", body, err)))?;
+    let bio_words = json
+        .get("bio")
+        .and_then(serde_json::Value::as_str)
+        .map_or(0, |bio| bio.split_whitespace().count());
+    let age = json
+        .get("age")
+        .and_then(serde_json::Value::as_i64)
+        .unwrap_or(0);
+    if bio_words != 3 || !(18..=99).contains(&age) || body.contains("{{") {
+        return Err(AppError::validation(format!("Unexpected body: {}", body)));
+    }
+
+    if FakeBody::parse("plain body", None, false)?.is_some() {
+        return Err(AppError::validation(
+            "Expected no fake body without placeholders",
+        ));
+    }
+    if FakeBody::parse("{{lorem:0}}", None, true).is_ok() {
+        return Err(AppError::validation(
+            "Expected invalid placeholder arguments to be rejected",
+        ));
+    }
+    // A typo is rejected at startup, naming every unknown placeholder once.
+    match FakeBody::parse("{{nmae}} {{ emial }} {{nmae}}", None, false) {
+        Err(AppError::Http(HttpError::UnknownBodyPlaceholders { names, .. }))
+            if names == "'nmae', 'emial'" => {}
+        other => {
+            return Err(AppError::validation(format!(
+                "Expected unknown placeholders to be rejected, got {:?}",
+                other.map(|fake| fake.is_some())
+            )));
+        }
+    }
+    // With --literal-placeholders, e.g. for a handlebars body, they are sent unchanged.
+    let literal = r#"{"greeting":"{{ nmae }} {{user}}","id":"{{uuid}}"}"#;
+    let rendered = FakeBody::parse(literal, Some(1), true)?
+        .ok_or_else(|| AppError::validation("Expected the uuid placeholder"))?
+        .render();
+    if !rendered.starts_with(r#"{"greeting":"{{ nmae }} {{user}}","id":""#)
+        || rendered.contains("{{uuid}}")
+    {
+        return Err(AppError::validation(format!(
+            "Unexpected literal body: {}",
+            rendered
+        )));
+    }
+    if FakeBody::parse("{{template}}", None, true)?.is_some() {
+        return Err(AppError::validation(
+            "Expected unknown placeholders to leave the body static",
+        ));
+    }
+
+    let expanded = FakeData::new(Some(1)).expand("{{user}} {{uuid}}");
+    let uuid = expanded.strip_prefix("{{user}} ").unwrap_or_default();
+    if uuid.len() != 36 || uuid.get(14..15) != Some("4") {
+        return Err(AppError::validation(format!(
+            "Unexpected scenario expansion: {}",
+            expanded
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn json_template_keeps_placeholder_types() -> AppResult<()> {
+    let template = r#"{"id":"{{int:1:100}}","ts":"{{now_ms}}","tags":["{{first_name}}",true],"note":"hi {{name}}!","nested":{"n":null}}"#;
+    let body = JsonBody::parse(template, Some(3))?.render()?;
+    let json: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|err| AppError::validation(format!("Invalid JSON {}: {}", body, err)))?;
+    let id = json.get("id").and_then(serde_json::Value::as_i64);
+    let ts = json.get("ts").and_then(serde_json::Value::as_i64);
+    let tag = json
+        .get("tags")
+        .and_then(|tags| tags.get(0))
+        .and_then(serde_json::Value::as_str);
+    let note = json.get("note").and_then(serde_json::Value::as_str);
+    if !id.is_some_and(|id| (1..=100).contains(&id))
+        || ts.is_none_or(|ts| ts <= 0)
+        || tag.is_none_or(str::is_empty)
+        || !note.is_some_and(|note| note.starts_with("hi ") && note.ends_with('!'))
+        || json.get("nested").and_then(|nested| nested.get("n")) != Some(&serde_json::Value::Null)
+    {
+        return Err(AppError::validation(format!("Unexpected body: {}", body)));
+    }
+
+    if JsonBody::parse(r#"{"id": {{int:1:2}}}"#, None).is_ok()
+        || JsonBody::parse(r#"{"id": "{{nope}}"}"#, None).is_ok()
+    {
+        return Err(AppError::validation(
+            "Expected invalid JSON and unknown placeholders to be rejected",
+        ));
+    }
+    Ok(())
+}
+
 #[test]
 fn resolve_alpn_detects_http2_only() -> AppResult<()> {
     let selection = resolve_alpn(&["h2".to_owned()])?;
@@ -282,3 +911,1803 @@ fn request_limiter_stops_at_limit() -> AppResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn bandwidth_limiter_allows_burst_within_budget() -> AppResult<()> {
+    run_async_test(async {
+        let limiter = BandwidthLimiter::new(1_000);
+        let now = tokio::time::Instant::now();
+        if !limiter.reserve(600, now).is_zero() || !limiter.reserve(400, now).is_zero() {
+            return Err(AppError::validation("Expected no wait within budget"));
+        }
+        if limiter.total_bytes() != 1_000 {
+            return Err(AppError::validation("Unexpected total bytes"));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn bandwidth_limiter_waits_for_debt_and_refills() -> AppResult<()> {
+    run_async_test(async {
+        let limiter = BandwidthLimiter::new(1_000);
+        let now = tokio::time::Instant::now();
+        let wait = limiter.reserve(1_500, now);
+        if wait != Duration::from_millis(500) {
+            return Err(AppError::validation(format!(
+                "Expected 500ms wait, got {:?}",
+                wait
+            )));
+        }
+        let later = now
+            .checked_add(Duration::from_secs(1))
+            .ok_or_else(|| AppError::validation("Instant overflow"))?;
+        if !limiter.reserve(500, later).is_zero() {
+            return Err(AppError::validation("Expected refill after one second"));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn edge_sim_delays_sends_and_throttles_reads_per_task() -> AppResult<()> {
+    run_async_test(async {
+        let rate: crate::args::LinkRate = "800kbit".parse()?;
+        if EdgeSim::new(None, None).is_some() {
+            return Err(AppError::validation("Expected no shaping without options"));
+        }
+        let edge = EdgeSim::new(Some(rate), Some(Duration::from_millis(50)))
+            .ok_or_else(|| AppError::validation("Expected edge simulation"))?;
+        let send_started = tokio::time::Instant::now();
+        edge.delay_send().await;
+        if send_started.elapsed() < Duration::from_millis(50) {
+            return Err(AppError::validation("Expected send delay of 50ms"));
+        }
+
+        // 100KB/s with a one-second bucket: 150KB overdraws by half a second.
+        let read_started = tokio::time::Instant::now();
+        edge.throttle_read(150_000).await;
+        if read_started.elapsed() < Duration::from_millis(450) {
+            return Err(AppError::validation(format!(
+                "Expected downlink throttling, read took {:?}",
+                read_started.elapsed()
+            )));
+        }
+
+        // A second task gets its own budget.
+        let other = EdgeSim::new(Some(rate), None)
+            .ok_or_else(|| AppError::validation("Expected edge simulation"))?;
+        let other_started = tokio::time::Instant::now();
+        other.throttle_read(100_000).await;
+        if other_started.elapsed() >= Duration::from_millis(100) {
+            return Err(AppError::validation(
+                "Expected an independent per-task budget",
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn connect_rate_limiter_spaces_connections_evenly() -> AppResult<()> {
+    let limiter = ConnectRateLimiter::new(10);
+    let start = tokio::time::Instant::now();
+    let waits: Vec<u128> = (0..3).map(|_| limiter.reserve(start).as_millis()).collect();
+    if waits != [0, 100, 200] {
+        return Err(AppError::validation(format!(
+            "Unexpected connect waits: {:?}",
+            waits
+        )));
+    }
+    let later = start + Duration::from_secs(2);
+    if !limiter.reserve(later).is_zero() {
+        return Err(AppError::validation(
+            "Expected an idle limiter to allow a connection immediately",
+        ));
+    }
+    let achieved = limiter.achieved_per_sec_x100(later);
+    if limiter.total_connects() != 4 || !(199..=200).contains(&achieved) {
+        return Err(AppError::validation(format!(
+            "Unexpected achieved rate: {} connects, {} x100/s",
+            limiter.total_connects(),
+            achieved
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn format_bytes_per_sec_uses_decimal_units() -> AppResult<()> {
+    let cases = [
+        (512, "512B/s"),
+        (1_500, "1.50KB/s"),
+        (10_000_000, "10.00MB/s"),
+        (2_250_000_000, "2.25GB/s"),
+    ];
+    for (value, expected) in cases {
+        let formatted = format_bytes_per_sec(value);
+        if formatted != expected {
+            return Err(AppError::validation(format!(
+                "Expected {}, got {}",
+                expected, formatted
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn http_version_1_0_is_sent_on_the_request_line() -> AppResult<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.ok()?;
+            let mut buf = [0_u8; 1024];
+            let read = stream.read(&mut buf).await.ok()?;
+            let request = String::from_utf8_lossy(buf.get(..read)?).into_owned();
+            let response = b"HTTP/1.0 200 OK\r\nContent-Length: 2\r\n\r\nok";
+            stream.write_all(response).await.ok()?;
+            request.lines().next().map(str::to_owned)
+        });
+
+        let mut args = base_args(format!("http://{}/legacy", addr))?;
+        args.http_version = Some(HttpVersion::V1_0);
+        let (client, workload) =
+            sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+        let workload::Workload::Single(template) = workload else {
+            return Err(AppError::validation("Expected a static workload"));
+        };
+        let request = template
+            .try_clone()
+            .ok_or_else(|| AppError::validation("Failed to clone request"))?;
+        client
+            .execute(request)
+            .await
+            .map_err(|err| AppError::validation(format!("Request failed: {}", err)))?;
+        let request_line = server
+            .await
+            .map_err(|err| AppError::validation(format!("Server task failed: {}", err)))?;
+        if request_line.as_deref() != Some("GET /legacy HTTP/1.0") {
+            return Err(AppError::validation(format!(
+                "Unexpected request line {:?}",
+                request_line
+            )));
+        }
+
+        args.http_version = Some(HttpVersion::V3);
+        if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+            return Err(AppError::validation(
+                "Expected HTTP/3 on an http:// target to be rejected",
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn connect_to_cacert_is_validated_at_startup() -> AppResult<()> {
+    let dir = tempfile::tempdir()?;
+    let missing = dir.path().join("missing.pem");
+    let invalid = dir.path().join("invalid.pem");
+    std::fs::write(&invalid, "not a certificate")?;
+    let mut args = base_args("https://localhost/".to_owned())?;
+    args.no_pre_lookup = true;
+    for (path, expect_read_error) in [(missing, true), (invalid, false)] {
+        args.connect_to = vec![crate::args::parse_connect_to(&format!(
+            "localhost:443:127.0.0.1:8443:{}",
+            path.display()
+        ))?];
+        match sender::build_client_and_workload(&args, &RunContext::default(), None) {
+            Err(AppError::Http(HttpError::ReadCacert { .. })) if expect_read_error => {}
+            Err(AppError::Http(HttpError::InvalidCacert { .. })) if !expect_read_error => {}
+            Err(err) => {
+                return Err(AppError::validation(format!("Unexpected error: {}", err)));
+            }
+            Ok(_) => {
+                return Err(AppError::validation(format!(
+                    "Expected CA bundle {} to be rejected",
+                    path.display()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Self-signed CA used only to build a per-mapping `--connect-to` client.
+const TEST_CA_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBijCCAS+gAwIBAgIUHGao3fBhykXpQ4s8uBzlSFWEltcwCgYIKoZIzj0EAwIw
+GTEXMBUGA1UEAwwOc3RyZXN0IHRlc3QgQ0EwIBcNMjYxMDE3MjMyMDI2WhgPMjEy
+NjA5MjMyMzIwMjZaMBkxFzAVBgNVBAMMDnN0cmVzdCB0ZXN0IENBMFkwEwYHKoZI
+zj0CAQYIKoZIzj0DAQcDQgAELiLPL1Vh2hGI5UIePN14piuEHq/UI9ubESQeG3BR
+Lu5eA7qsek3RHOVtbu9fKWRnFDO8381j+DXRl48JOFrS/aNTMFEwHQYDVR0OBBYE
+FIEed2419pkDG4w/9SaeZq1m8vpRMB8GA1UdIwQYMBaAFIEed2419pkDG4w/9Sae
+Zq1m8vpRMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSQAwRgIhAJzmnOAQ
+CEhh6q9TIlUh24HE4RpOO0d0EqF5Dxcpby+uAiEA3fTxmma8E9wy64ubIwrfH2QJ
+F2oMmLhzTvSwosSsvAk=
+-----END CERTIFICATE-----
+";
+
+#[test]
+fn connect_to_client_follows_the_matched_mapping() -> AppResult<()> {
+    let dir = tempfile::tempdir()?;
+    let ca = dir.path().join("ca.pem");
+    std::fs::write(&ca, TEST_CA_PEM)?;
+    let mut args = base_args("https://with-ca.test/".to_owned())?;
+    args.no_pre_lookup = true;
+    // Both mappings share a target; only the first carries a CA bundle.
+    args.connect_to = vec![
+        crate::args::parse_connect_to(&format!(
+            "with-ca.test:443:127.0.0.1:8443:{}",
+            ca.display()
+        ))?,
+        crate::args::parse_connect_to("no-ca.test:443:127.0.0.1:8443")?,
+    ];
+    let (client, workload) =
+        sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+    let Workload::SingleDynamic(spec) = &workload else {
+        return Err(AppError::validation("Expected a dynamic workload"));
+    };
+    let mapping_for = |url: &str| -> AppResult<Option<usize>> {
+        let mut spec = (**spec).clone();
+        spec.url = super::workload::UrlSource::Static(url.to_owned());
+        let (request, mapping) = build_request_from_spec(&client, &spec)?;
+        if request.url().as_str() != "https://127.0.0.1:8443/" {
+            return Err(AppError::validation(format!(
+                "Unexpected rewritten URL for {}: {}",
+                url,
+                request.url()
+            )));
+        }
+        Ok(mapping)
+    };
+
+    let with_ca = mapping_for("https://with-ca.test/")?;
+    let no_ca = mapping_for("https://no-ca.test/")?;
+    if with_ca != Some(0) || workload.connect_to_client(with_ca).is_none() {
+        return Err(AppError::validation(format!(
+            "Expected the CA client for mapping {:?}",
+            with_ca
+        )));
+    }
+    if no_ca != Some(1) || workload.connect_to_client(no_ca).is_some() {
+        return Err(AppError::validation(format!(
+            "Expected no CA client for mapping {:?}",
+            no_ca
+        )));
+    }
+    // A request sent straight to the target matched no mapping.
+    if workload.connect_to_client(None).is_some() {
+        return Err(AppError::validation(
+            "Expected no CA client for an unmapped request",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn requests_per_connection_opens_a_fresh_connection_after_the_limit() -> AppResult<()> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let accepted = Arc::new(AtomicU64::new(0));
+        let server_accepted = Arc::clone(&accepted);
+        let server = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                server_accepted.fetch_add(1, Ordering::Relaxed);
+                tokio::spawn(async move {
+                    let mut buf = [0_u8; 1024];
+                    while let Ok(read) = stream.read(&mut buf).await {
+                        if read == 0 {
+                            break;
+                        }
+                        let response = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+                        if stream.write_all(response).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut args = base_args(format!("http://{}/", addr))?;
+        args.requests_per_connection = Some(positive_u64(2)?);
+        let cycle = sender::build_connection_cycle(&args, &RunContext::default(), None)?
+            .ok_or_else(|| AppError::validation("Expected a connection cycle"))?;
+        let mut served: u64 = 0;
+        let mut client = None;
+        for _ in 0..6 {
+            if let Some(fresh) = cycle.next_client(&mut served) {
+                client = Some(fresh?);
+            }
+            let active = client
+                .as_ref()
+                .ok_or_else(|| AppError::validation("Expected a client"))?;
+            active
+                .get(format!("http://{}/", addr))
+                .send()
+                .await
+                .map_err(|err| AppError::validation(format!("Request failed: {}", err)))?;
+        }
+        server.abort();
+
+        let accepted = accepted.load(Ordering::Relaxed);
+        if cycle.connections() != 3 || accepted != 3 {
+            return Err(AppError::validation(format!(
+                "Expected 3 connections for 6 requests, counted {} and accepted {}",
+                cycle.connections(),
+                accepted
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn weighted_url_file_samples_by_weight() -> AppResult<()> {
+    let dir = tempfile::tempdir()?;
+    let urls_path = dir.path().join("urls.txt");
+    std::fs::write(
+        &urls_path,
+        "70 http://localhost/a\n20 http://localhost/b\nhttp://localhost/c\n",
+    )?;
+
+    let mut args = base_args(urls_path.to_string_lossy().into_owned())?;
+    args.urls_from_file = true;
+    let (_client, workload) =
+        sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+    let workload::Workload::SingleDynamic(spec) = workload else {
+        return Err(AppError::validation("Expected a dynamic workload"));
+    };
+    if !matches!(spec.url, workload::UrlSource::Weighted(_)) {
+        return Err(AppError::validation("Expected a weighted URL source"));
+    }
+    let mut counts = [0_u32; 3];
+    for _ in 0..9_100 {
+        let url = spec.url.next_url()?;
+        let slot = match url.as_str() {
+            "http://localhost/a" => counts.get_mut(0),
+            "http://localhost/b" => counts.get_mut(1),
+            "http://localhost/c" => counts.get_mut(2),
+            _ => None,
+        }
+        .ok_or_else(|| AppError::validation(format!("Unexpected URL: {}", url)))?;
+        *slot = slot.saturating_add(1);
+    }
+    let [a, b, c] = counts;
+    if !(6_000..=8_000).contains(&a) || !(1_400..=2_400).contains(&b) || !(30..=200).contains(&c) {
+        return Err(AppError::validation(format!(
+            "Unexpected weighted URL counts: {:?}",
+            counts
+        )));
+    }
+
+    std::fs::write(&urls_path, "http://localhost/a\nhttp://localhost/b\n")?;
+    let (_uniform_client, uniform_workload) =
+        sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+    let workload::Workload::SingleDynamic(uniform_spec) = uniform_workload else {
+        return Err(AppError::validation("Expected a dynamic workload"));
+    };
+    if !matches!(uniform_spec.url, workload::UrlSource::List(_)) {
+        return Err(AppError::validation(
+            "Expected an unweighted file to stay round-robin",
+        ));
+    }
+
+    std::fs::write(&urls_path, "0 http://localhost/a\n")?;
+    if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+        return Err(AppError::validation(
+            "Expected a zero weight to be rejected",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn stdin_url_list_feeds_the_url_source_and_must_not_be_empty() -> AppResult<()> {
+    let mut args = base_args(STDIN_URL_PATH.to_owned())?;
+    args.urls_from_file = true;
+    let mut run = RunContext {
+        stdin_urls: Some(Arc::from("http://localhost/a\n\nhttp://localhost/b\n")),
+        ..RunContext::default()
+    };
+    let (_client, workload) = sender::build_client_and_workload(&args, &run, None)?;
+    let workload::Workload::SingleDynamic(spec) = workload else {
+        return Err(AppError::validation("Expected a dynamic workload"));
+    };
+    if !matches!(spec.url, workload::UrlSource::List(_)) {
+        return Err(AppError::validation("Expected a URL list from stdin"));
+    }
+
+    run.stdin_urls = Some(Arc::from("\n  \n"));
+    match sender::build_client_and_workload(&args, &run, None) {
+        Err(AppError::Http(HttpError::UrlStdinEmpty)) => Ok(()),
+        Err(err) => Err(AppError::validation(format!(
+            "Expected UrlStdinEmpty, got {}",
+            err
+        ))),
+        Ok(_) => Err(AppError::validation("Expected empty stdin to be rejected")),
+    }
+}
+
+#[test]
+fn data_dir_rotates_files_and_enforces_max_bytes() -> AppResult<()> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("b.xml"), "<b/>")?;
+    std::fs::write(dir.path().join("a.json"), "{}")?;
+    std::fs::write(dir.path().join("c.dat"), "c")?;
+    std::fs::write(dir.path().join(".hidden"), "ignored")?;
+    std::fs::create_dir(dir.path().join("nested"))?;
+
+    let mut args = base_args("http://localhost/".to_owned())?;
+    args.data_dir = Some(dir.path().to_string_lossy().into_owned());
+    let (_client, workload) =
+        sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+    let workload::Workload::SingleDynamic(spec) = workload else {
+        return Err(AppError::validation("Expected a dynamic workload"));
+    };
+    let workload::BodySource::Files(files) = &spec.body else {
+        return Err(AppError::validation("Expected a file body source"));
+    };
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        let file = files
+            .next()
+            .ok_or_else(|| AppError::validation("Expected a body file"))?;
+        seen.push((
+            String::from_utf8_lossy(&file.bytes).into_owned(),
+            file.content_type,
+        ));
+    }
+    let expected = vec![
+        ("{}".to_owned(), Some("application/json")),
+        ("<b/>".to_owned(), Some("application/xml")),
+        ("c".to_owned(), None),
+        ("{}".to_owned(), Some("application/json")),
+    ];
+    if seen != expected {
+        return Err(AppError::validation(format!(
+            "Unexpected body rotation: {:?}",
+            seen
+        )));
+    }
+
+    args.data_dir_max_bytes = Some("6B".parse()?);
+    if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+        return Err(AppError::validation(
+            "Expected --data-dir-max-bytes to reject the directory",
+        ));
+    }
+
+    let empty = tempfile::tempdir()?;
+    args.data_dir = Some(empty.path().to_string_lossy().into_owned());
+    args.data_dir_max_bytes = None;
+    if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+        return Err(AppError::validation("Expected an empty directory error"));
+    }
+    Ok(())
+}
+
+#[test]
+fn form_files_are_loaded_at_startup_and_mixed_with_text_fields() -> AppResult<()> {
+    let dir = tempfile::tempdir()?;
+    let upload = dir.path().join("avatar.png");
+    std::fs::write(&upload, "png-bytes")?;
+
+    let mut args = base_args("http://localhost/".to_owned())?;
+    args.form = vec!["user=demo".to_owned()];
+    args.form_file = vec![format!("avatar=@{}", upload.to_string_lossy())];
+    let (_client, workload) =
+        sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+    let workload::Workload::SingleDynamic(spec) = workload else {
+        return Err(AppError::validation("Expected a dynamic workload"));
+    };
+    let fields = spec
+        .form
+        .as_ref()
+        .ok_or_else(|| AppError::validation("Expected form fields"))?;
+    match fields.as_slice() {
+        [
+            workload::FormFieldSpec::Text { name, value },
+            workload::FormFieldSpec::File {
+                name: file_field,
+                file_name,
+                content_type,
+                bytes,
+            },
+        ] if name == "user"
+            && value == "demo"
+            && file_field == "avatar"
+            && file_name == "avatar.png"
+            && *content_type == Some("image/png")
+            && bytes.as_ref() == b"png-bytes" => {}
+        _ => return Err(AppError::validation("Unexpected form fields")),
+    }
+
+    args.form_file = vec![format!(
+        "avatar=@{}",
+        dir.path().join("missing.png").to_string_lossy()
+    )];
+    if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+        return Err(AppError::validation(
+            "Expected a missing form file to fail at startup",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn auth_secrets_are_read_from_files_and_env() -> AppResult<()> {
+    let dir = tempfile::tempdir()?;
+    let token_path = dir.path().join("token");
+    std::fs::write(&token_path, "tok-123\r\n")?;
+
+    let mut args = base_args("http://localhost/".to_owned())?;
+    args.bearer_token_file = Some(token_path.to_string_lossy().into_owned());
+    let (_client, workload) =
+        sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+    let workload::Workload::SingleDynamic(spec) = workload else {
+        return Err(AppError::validation("Expected a dynamic workload"));
+    };
+    match spec.auth.as_ref() {
+        Some(workload::AuthConfig::Bearer { token }) if token == "tok-123" => {}
+        other => {
+            return Err(AppError::validation(format!(
+                "Unexpected auth config: {:?}",
+                other
+            )));
+        }
+    }
+
+    args.bearer_token_file = None;
+    args.basic_auth = Some("env:STREST_TEST_SECRET_THAT_IS_NOT_SET".to_owned());
+    if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+        return Err(AppError::validation(
+            "Expected a missing env:VAR secret to be rejected",
+        ));
+    }
+    args.basic_auth = Some(format!("@{}", dir.path().join("missing").display()));
+    if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+        return Err(AppError::validation(
+            "Expected a missing @file secret to be rejected",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn digest_auth_retries_after_challenge_and_reuses_nonce() -> AppResult<()> {
+    use sha2::{Digest, Sha256};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn sha256_hex(input: &str) -> String {
+        Sha256::digest(input.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn param<'header>(header: &'header str, key: &str) -> Option<&'header str> {
+        header.split(", ").find_map(|field| {
+            field
+                .trim_start_matches("Digest ")
+                .strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix('='))
+                .map(|value| value.trim_matches('"'))
+        })
+    }
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let (seen_tx, mut seen_rx) = tokio::sync::mpsc::unbounded_channel::<Option<String>>();
+        let server = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let seen_tx = seen_tx.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0_u8; 4096];
+                    while let Ok(read) = stream.read(&mut buf).await
+                        && read > 0
+                    {
+                        let request =
+                            String::from_utf8_lossy(buf.get(..read).unwrap_or(&[])).into_owned();
+                        let authorization = request.lines().find_map(|line| {
+                            line.strip_prefix("authorization: ").map(str::to_owned)
+                        });
+                        let response: &[u8] = if authorization.is_some() {
+                            b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok"
+                        } else {
+                            b"HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"x\"\r\nWWW-Authenticate: Digest realm=\"api\", qop=\"auth\", algorithm=SHA-256, nonce=\"n0nce\", opaque=\"op\"\r\nContent-Length: 6\r\n\r\ndenied"
+                        };
+                        if seen_tx.send(authorization).is_err()
+                            || stream.write_all(response).await.is_err()
+                        {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut args = base_args(format!("http://{}/secure?id=1", addr))?;
+        args.digest_auth = Some("user:pass".to_owned());
+        let (client, workload) =
+            sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+        let digest = digest::DigestAuth::from_auth(workload.auth())
+            .ok_or_else(|| AppError::validation("Expected digest auth"))?;
+        for _ in 0..2 {
+            let request = client
+                .get(format!("http://{}/secure?id=1", addr))
+                .build()
+                .map_err(|err| AppError::validation(format!("Failed to build request: {}", err)))?;
+            let response = digest
+                .execute(&client, request)
+                .await
+                .map_err(|err| AppError::validation(format!("Request failed: {}", err)))?;
+            if response.status() != reqwest::StatusCode::OK {
+                return Err(AppError::validation(format!(
+                    "Unexpected status {}",
+                    response.status()
+                )));
+            }
+        }
+        server.abort();
+
+        let mut seen = Vec::new();
+        while let Ok(authorization) = seen_rx.try_recv() {
+            seen.push(authorization);
+        }
+        let [None, Some(first), Some(second)] = seen.as_slice() else {
+            return Err(AppError::validation(format!(
+                "Expected one challenge then two authorized requests: {:?}",
+                seen
+            )));
+        };
+        let cnonce = param(first, "cnonce").unwrap_or_default();
+        let ha1 = sha256_hex("user:api:pass");
+        let ha2 = sha256_hex("GET:/secure?id=1");
+        let expected = sha256_hex(&format!("{}:n0nce:00000001:{}:auth:{}", ha1, cnonce, ha2));
+        if param(first, "response") != Some(expected.as_str())
+            || param(first, "algorithm") != Some("SHA-256")
+            || param(first, "opaque") != Some("op")
+            || param(second, "nc") != Some("00000002")
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected digest headers: {} / {}",
+                first, second
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn oauth2_session_fetches_client_credentials_token() -> AppResult<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.ok()?;
+            let mut request = String::new();
+            let mut buf = [0_u8; 4096];
+            while !request.contains("scope=") {
+                let read = stream.read(&mut buf).await.ok()?;
+                if read == 0 {
+                    break;
+                }
+                request.push_str(&String::from_utf8_lossy(buf.get(..read)?));
+            }
+            let response = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 41\r\n\r\n{\"access_token\":\"tok-1\",\"token_type\":\"x\"}";
+            stream.write_all(response).await.ok()?;
+            request.lines().last().map(str::to_owned)
+        });
+
+        let mut args = base_args("http://localhost/".to_owned())?;
+        args.oauth2_token_url = Some(format!("http://{}/token", addr));
+        args.oauth2_client_id = Some("client".to_owned());
+        args.oauth2_client_secret = Some("s3cret".to_owned());
+        args.oauth2_scope = Some("read write".to_owned());
+        let (client, workload) =
+            sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+        let Some(workload::AuthConfig::OAuth2 { session }) = workload.auth() else {
+            return Err(AppError::validation("Expected an OAuth2 auth config"));
+        };
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        session.start(&client, &shutdown_tx).await?;
+        drop(shutdown_tx.send(()));
+
+        let form = server
+            .await
+            .map_err(|err| AppError::validation(format!("Server task failed: {}", err)))?;
+        let expected_form =
+            "grant_type=client_credentials&client_id=client&client_secret=s3cret&scope=read+write";
+        if session.bearer() != "Bearer tok-1" || form.as_deref() != Some(expected_form) {
+            return Err(AppError::validation(format!(
+                "Unexpected token exchange: {} / {:?}",
+                session.bearer(),
+                form
+            )));
+        }
+        if oauth2::refresh_delay(Duration::from_secs(3_600)) != Duration::from_secs(3_570)
+            || oauth2::refresh_delay(Duration::from_secs(10)) != Duration::from_secs(1)
+        {
+            return Err(AppError::validation("Unexpected OAuth2 refresh delay"));
+        }
+
+        args.oauth2_token_url = None;
+        if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+            return Err(AppError::validation(
+                "Expected OAuth2 client flags without a token URL to be rejected",
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn oauth2_refresh_reaches_existing_worker_handles() -> AppResult<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        tokio::spawn(async move {
+            for body in [
+                "{\"access_token\":\"tok-1\",\"expires_in\":1}",
+                "{\"access_token\":\"tok-2\",\"expires_in\":3600}",
+            ] {
+                let (mut stream, _) = listener.accept().await.ok()?;
+                let mut request = String::new();
+                let mut buf = [0_u8; 4096];
+                while !request.contains("client_secret=") {
+                    let read = stream.read(&mut buf).await.ok()?;
+                    if read == 0 {
+                        break;
+                    }
+                    request.push_str(&String::from_utf8_lossy(buf.get(..read)?));
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.ok()?;
+            }
+            Some(())
+        });
+
+        let mut args = base_args("http://localhost/".to_owned())?;
+        args.oauth2_token_url = Some(format!("http://{}/token", addr));
+        args.oauth2_client_id = Some("client".to_owned());
+        args.oauth2_client_secret = Some("s3cret".to_owned());
+        let (client, workload) =
+            sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+        let Some(workload::AuthConfig::OAuth2 { session }) = workload.auth() else {
+            return Err(AppError::validation("Expected an OAuth2 auth config"));
+        };
+        let worker = session.clone();
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        session.start(&client, &shutdown_tx).await?;
+        if worker.bearer() != "Bearer tok-1" {
+            return Err(AppError::validation(format!(
+                "Unexpected first token: {}",
+                worker.bearer()
+            )));
+        }
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while worker.bearer() != "Bearer tok-2" && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        drop(shutdown_tx.send(()));
+        if worker.bearer() != "Bearer tok-2" {
+            return Err(AppError::validation(format!(
+                "Worker kept the old token after refresh: {}",
+                worker.bearer()
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn doh_message_round_trips_and_filters_record_types() -> AppResult<()> {
+    let query = doh::encode_query("api.example.com.", doh::TYPE_A)?;
+    let expected_query = b"\0\0\x01\0\0\x01\0\0\0\0\0\0\x03api\x07example\x03com\0\0\x01\0\x01";
+    if query != expected_query {
+        return Err(AppError::validation(format!(
+            "Unexpected query: {:?}",
+            query
+        )));
+    }
+    if doh::encode_query("a..b", doh::TYPE_A).is_ok() {
+        return Err(AppError::validation(
+            "Expected an empty label to be rejected",
+        ));
+    }
+
+    // Question, then a CNAME and an A record whose names point back at it.
+    let mut response = vec![0, 0, 0x81, 0x80, 0, 1, 0, 2, 0, 0, 0, 0];
+    response.extend_from_slice(query.get(12..).unwrap_or_default());
+    response.extend_from_slice(&[0xC0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xC0, 12]);
+    response.extend_from_slice(&[0xC0, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 10, 0, 0, 7]);
+    let addrs = doh::decode_answers(&response, doh::TYPE_A);
+    let expected = doh::DohAnswers {
+        addrs: vec![std::net::IpAddr::from([10, 0, 0, 7])],
+        ttl: Some(60),
+    };
+    if addrs.as_ref() != Some(&expected) {
+        return Err(AppError::validation(format!(
+            "Unexpected answers: {:?}",
+            addrs
+        )));
+    }
+    let empty = doh::DohAnswers {
+        addrs: Vec::new(),
+        ttl: None,
+    };
+    if doh::decode_answers(&response, doh::TYPE_AAAA).as_ref() != Some(&empty) {
+        return Err(AppError::validation(
+            "Expected A records to be ignored for AAAA",
+        ));
+    }
+    if doh::decode_answers(
+        response
+            .get(..response.len().saturating_sub(1))
+            .unwrap_or_default(),
+        doh::TYPE_A,
+    )
+    .is_some()
+    {
+        return Err(AppError::validation(
+            "Expected a truncated message to be rejected",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn doh_url_routes_name_resolution_through_the_endpoint() -> AppResult<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let bind = || async {
+            tokio::net::TcpListener::bind("127.0.0.1:0")
+                .await
+                .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))
+        };
+        let doh_listener = bind().await?;
+        let target_listener = bind().await?;
+        let doh_addr = doh_listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let target_addr = target_listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+
+        let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = doh_listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0_u8; 1024];
+                // Every query for a one-label host ends with its QTYPE and QCLASS.
+                while !request.ends_with(&[0, 1]) || !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(buf.get(..read).unwrap_or_default()),
+                    }
+                }
+                let Some(split) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+                    continue;
+                };
+                let query = request
+                    .get(split.saturating_add(4)..)
+                    .unwrap_or_default()
+                    .to_vec();
+                let qtype = query
+                    .get(query.len().saturating_sub(4)..query.len().saturating_sub(2))
+                    .unwrap_or_default()
+                    .to_vec();
+                let mut answer = vec![0, 0, 0x81, 0x80, 0, 0, 0, 1, 0, 0, 0, 0];
+                if qtype == [0, 1] {
+                    answer.extend_from_slice(&[0, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 127, 0, 0, 1]);
+                } else {
+                    answer = vec![0, 0, 0x81, 0x80, 0, 0, 0, 0, 0, 0, 0, 0];
+                }
+                drop(query_tx.send(query));
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/dns-message\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    answer.len()
+                );
+                drop(stream.write_all(head.as_bytes()).await);
+                drop(stream.write_all(&answer).await);
+            }
+        });
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = target_listener.accept().await {
+                let mut buf = [0_u8; 1024];
+                drop(stream.read(&mut buf).await);
+                drop(
+                    stream
+                        .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                        .await,
+                );
+            }
+        });
+
+        let url = format!("http://strest-doh:{}/", target_addr.port());
+        let mut args = base_args(url.clone())?;
+        args.doh_url = Some(format!("http://{}/dns-query", doh_addr));
+        args.ipv4_only = true;
+        let (client, _workload) =
+            sender::build_client_and_workload(&args, &RunContext::default(), None)?;
+        let status = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|err| AppError::validation(format!("Request failed: {}", err)))?
+            .status();
+        let query = query_rx.try_recv().ok();
+        if status.as_u16() != 204 || query != Some(doh::encode_query("strest-doh", doh::TYPE_A)?) {
+            return Err(AppError::validation(format!(
+                "Unexpected DoH exchange: {} / {:?}",
+                status, query
+            )));
+        }
+        if query_rx.try_recv().is_ok() {
+            return Err(AppError::validation(
+                "Expected --ipv4 to skip the AAAA query",
+            ));
+        }
+        let cached_status = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|err| AppError::validation(format!("Request failed: {}", err)))?
+            .status();
+        if cached_status.as_u16() != 204 || query_rx.try_recv().is_ok() {
+            return Err(AppError::validation(
+                "Expected the second connection to reuse the cached answer",
+            ));
+        }
+
+        args.doh_url = Some("not a url".to_owned());
+        if sender::build_client_and_workload(&args, &RunContext::default(), None).is_ok() {
+            return Err(AppError::validation(
+                "Expected an invalid --doh-url to be rejected",
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn scenario_step_identities_are_loaded_up_front() -> AppResult<()> {
+    let step = |cert: Option<&str>, key: Option<&str>| crate::args::ScenarioStep {
+        name: None,
+        method: HttpMethod::Get,
+        url: None,
+        path: Some("/".to_owned()),
+        headers: Vec::new(),
+        body: None,
+        assert_status: None,
+        assert_body_contains: None,
+        think_time: None,
+        vars: std::collections::BTreeMap::new(),
+        extract: std::collections::BTreeMap::new(),
+        cert: cert.map(str::to_owned),
+        key: key.map(str::to_owned),
+    };
+    let mut args = base_args("http://127.0.0.1:1/".to_owned())?;
+    args.scenario = Some(crate::args::Scenario {
+        base_url: Some("http://127.0.0.1:1".to_owned()),
+        vars: std::collections::BTreeMap::new(),
+        steps: vec![step(None, None)],
+    });
+    if let Err(err) = sender::build_client_and_workload(&args, &RunContext::default(), None) {
+        return Err(AppError::validation(format!(
+            "Unexpected error without step identities: {}",
+            err
+        )));
+    }
+
+    if let Some(scenario) = args.scenario.as_mut() {
+        scenario.steps.push(step(
+            Some("missing-step-cert.pem"),
+            Some("missing-step-key.pem"),
+        ));
+    }
+    match sender::build_client_and_workload(&args, &RunContext::default(), None) {
+        Err(err) if err.to_string().contains("missing-step-cert.pem") => Ok(()),
+        Err(err) => Err(AppError::validation(format!("Unexpected error: {}", err))),
+        Ok(_) => Err(AppError::validation(
+            "Expected a missing step cert to fail client setup",
+        )),
+    }
+}
+
+#[test]
+fn run_diagnostics_reports_each_step() -> AppResult<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let server = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0_u8; 1024];
+                    if let Ok(read) = stream.read(&mut buf).await
+                        && read > 0
+                    {
+                        let response = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+                        if stream.write_all(response).await.is_err() {
+                            // Client went away; nothing to report.
+                        }
+                    }
+                });
+            }
+        });
+
+        let args = base_args(format!("http://{}/", addr))?;
+        let report = run_diagnostics(&args, &RunContext::default()).await;
+        server.abort();
+
+        let statuses: Vec<(&str, DiagnosticStatus)> = report
+            .steps
+            .iter()
+            .map(|step| (step.name, step.status.clone()))
+            .collect();
+        let expected = vec![
+            ("dns", DiagnosticStatus::Ok),
+            ("tcp", DiagnosticStatus::Ok),
+            ("tls", DiagnosticStatus::Skipped),
+            ("first_byte", DiagnosticStatus::Ok),
+        ];
+        if statuses != expected || report.failures() != 0 {
+            return Err(AppError::validation(format!(
+                "Unexpected diagnostics: {:?}",
+                report.lines()
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn connection_timing_splits_new_and_reused_connections() -> AppResult<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let server = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0_u8; 1024];
+                    while let Ok(read) = stream.read(&mut buf).await
+                        && read > 0
+                    {
+                        let response = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+                        if stream.write_all(response).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        let client = reqwest::Client::builder()
+            .connector_layer(ConnectTimingLayer)
+            .build()
+            .map_err(|err| AppError::validation(format!("Failed to build client: {}", err)))?;
+        let url = format!("http://{}/", addr);
+        let mut timings = Vec::new();
+        for _ in 0..2 {
+            let (response, timing) = traced(async {
+                let response = client.get(&url).send().await;
+                if response.is_ok() {
+                    mark_first_byte();
+                }
+                match response {
+                    Ok(response) => response.bytes().await.map(|_| ()),
+                    Err(err) => Err(err),
+                }
+            })
+            .await;
+            response.map_err(|err| AppError::validation(format!("Request failed: {}", err)))?;
+            timings.push(timing);
+        }
+        server.abort();
+
+        let [Some(first), Some(second)] = timings.as_slice() else {
+            return Err(AppError::validation(format!(
+                "Expected both requests to be timed: {:?}",
+                timings
+            )));
+        };
+        if !first.new_connection
+            || first.connect > first.first_byte
+            || second.new_connection
+            || !second.connect.is_zero()
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected connection timings: {:?}",
+                timings
+            )));
+        }
+
+        let (output, untimed) = traced(async { 7 }).await;
+        if output != 7 || untimed.is_some() {
+            return Err(AppError::validation(
+                "Expected no timing without response headers",
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn classify_buckets_refused_and_dropped_connections() -> AppResult<()> {
+    run_async_test(async {
+        let client = reqwest::Client::new();
+        let closed = std::net::TcpListener::bind("127.0.0.1:0")
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let closed_addr = closed
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        drop(closed);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let hangup_addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let server = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                drop(stream);
+            }
+        });
+
+        let mut reasons = Vec::new();
+        for addr in [closed_addr, hangup_addr] {
+            match client.get(format!("http://{}/", addr)).send().await {
+                Ok(response) => {
+                    return Err(AppError::validation(format!(
+                        "Expected a failed request, got {}",
+                        response.status()
+                    )));
+                }
+                Err(err) => reasons.push(classify(&err)),
+            }
+        }
+        server.abort();
+
+        if reasons != [ErrorReason::ConnectionRefused, ErrorReason::ConnectionReset] {
+            return Err(AppError::validation(format!(
+                "Unexpected error reasons: {:?}",
+                reasons
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn run_diagnostics_records_connect_failure() -> AppResult<()> {
+    run_async_test(async {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        drop(listener);
+
+        let args = base_args(format!("http://{}/", addr))?;
+        let report = run_diagnostics(&args, &RunContext::default()).await;
+        let tcp_failed = report
+            .steps
+            .iter()
+            .any(|step| step.name == "tcp" && step.status == DiagnosticStatus::Failed);
+        if !tcp_failed || report.failures() < 2 {
+            return Err(AppError::validation(format!(
+                "Expected tcp and first_byte failures: {:?}",
+                report.lines()
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn run_diagnostics_tls_probe_uses_sender_cacert() -> AppResult<()> {
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let server = tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                held.push(stream);
+            }
+        });
+
+        let dir = tempfile::tempdir()?;
+        let mut args = base_args(format!("https://{}/", addr))?;
+        args.cacert = Some(
+            dir.path()
+                .join("missing-ca.pem")
+                .to_string_lossy()
+                .into_owned(),
+        );
+        args.request_timeout = Duration::from_millis(500);
+        args.connect_timeout = Duration::from_millis(500);
+        let report = run_diagnostics(&args, &RunContext::default()).await;
+        server.abort();
+
+        let tls = report.steps.iter().find(|step| step.name == "tls");
+        if !tls.is_some_and(|step| {
+            step.status == DiagnosticStatus::Failed && step.detail.contains("missing-ca.pem")
+        }) {
+            return Err(AppError::validation(format!(
+                "Expected the TLS probe to load --cacert: {:?}",
+                report.lines()
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn deadline_header_carries_remaining_timeout_budget() -> AppResult<()> {
+    let header = reqwest::header::HeaderName::from_static("x-deadline");
+    let client = reqwest::Client::new();
+    let header_ms = |request: &reqwest::Request| -> AppResult<u64> {
+        request
+            .headers()
+            .get(&header)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .ok_or_else(|| AppError::validation("Missing numeric deadline header"))
+    };
+    let mut request = client
+        .get("http://localhost/")
+        .build()
+        .map_err(|err| AppError::validation(format!("Failed to build request: {}", err)))?;
+
+    let relative = DeadlineHeader::new(
+        header.clone(),
+        crate::args::DeadlineFormat::RelativeMs,
+        Duration::from_secs(10),
+    );
+    relative.apply(&mut request, None);
+    if header_ms(&request)? != 10_000 {
+        return Err(AppError::validation(
+            "Expected the full budget without a scheduled start",
+        ));
+    }
+    let scheduled = tokio::time::Instant::now()
+        .checked_sub(Duration::from_secs(4))
+        .ok_or_else(|| AppError::validation("Failed to compute scheduled start"))?;
+    relative.apply(&mut request, Some(scheduled));
+    if !(5_000..=6_000).contains(&header_ms(&request)?) {
+        return Err(AppError::validation(
+            "Expected queueing time to be deducted from the budget",
+        ));
+    }
+
+    let absolute = DeadlineHeader::new(
+        header.clone(),
+        crate::args::DeadlineFormat::AbsoluteMs,
+        Duration::from_secs(10),
+    );
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|err| AppError::validation(format!("Clock before epoch: {}", err)))?
+        .as_millis();
+    let now_ms = u64::try_from(now_ms).unwrap_or(u64::MAX);
+    absolute.apply(&mut request, None);
+    let deadline_ms = header_ms(&request)?;
+    if !(now_ms.saturating_add(9_000)..=now_ms.saturating_add(11_000)).contains(&deadline_ms) {
+        return Err(AppError::validation(format!(
+            "Expected an epoch deadline about 10s out, got {}",
+            deadline_ms
+        )));
+    }
+
+    let mut args = base_args("http://localhost/".to_owned())?;
+    args.deadline_header = Some("bad header".to_owned());
+    if DeadlineHeader::from_args(&args).is_ok() {
+        return Err(AppError::validation(
+            "Expected an invalid header name to be rejected",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn generated_headers_render_fresh_values_and_log_outcomes() -> AppResult<()> {
+    let dir = tempfile::tempdir()?;
+    let log_path = dir.path().join("ids.csv");
+    let mut args = base_args("http://localhost/".to_owned())?;
+    args.correlation_header = Some("X-Request-Id".to_owned());
+    args.correlation_log = Some(log_path.to_string_lossy().into_owned());
+    let mut headers = vec![
+        ("X-Request-Id".to_owned(), "static".to_owned()),
+        ("Idempotency-Key".to_owned(), "load-${seq}".to_owned()),
+        ("Accept".to_owned(), "${other}".to_owned()),
+    ];
+    let generated = GeneratedHeaders::from_args(&args, &mut headers)?
+        .ok_or_else(|| AppError::validation("Expected generated headers"))?;
+    if headers != [("Accept".to_owned(), "${other}".to_owned())] {
+        return Err(AppError::validation(format!(
+            "Unexpected static headers: {:?}",
+            headers
+        )));
+    }
+
+    let client = reqwest::Client::new();
+    let mut sent = Vec::new();
+    for status in [200, 503] {
+        let mut request = client
+            .get("http://localhost/")
+            .build()
+            .map_err(|err| AppError::validation(format!("Failed to build request: {}", err)))?;
+        let ids = generated.apply(&mut request);
+        generated.record(&ids, status, status == 200, Duration::from_millis(7));
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+                .ok_or_else(|| AppError::validation(format!("Missing {}", name)))
+        };
+        sent.push((header("idempotency-key")?, header("x-request-id")?));
+    }
+    let [(first_key, first_id), (second_key, second_id)] = sent.as_slice() else {
+        return Err(AppError::validation("Expected two requests"));
+    };
+    if first_key != "load-1" || second_key != "load-2" || first_id == second_id {
+        return Err(AppError::validation(format!(
+            "Unexpected generated values: {:?}",
+            sent
+        )));
+    }
+    if first_id.len() != 36 || first_id.chars().nth(14) != Some('4') {
+        return Err(AppError::validation(format!(
+            "Expected a v4 UUID: {}",
+            first_id
+        )));
+    }
+
+    drop(generated);
+    let log = std::fs::read_to_string(&log_path)?;
+    let expected = format!(
+        "seq,status,success,latency_ms,idempotency-key,x-request-id\n\
+         1,200,true,7,load-1,{}\n\
+         2,503,false,7,load-2,{}\n",
+        first_id, second_id
+    );
+    if log != expected {
+        return Err(AppError::validation(format!("Unexpected log: {}", log)));
+    }
+
+    args.correlation_header = None;
+    let mut static_only = vec![("Accept".to_owned(), "text/plain".to_owned())];
+    match GeneratedHeaders::from_args(&args, &mut static_only) {
+        Err(AppError::Validation(ValidationError::CorrelationLogWithoutGeneratedHeader)) => Ok(()),
+        other => Err(AppError::validation(format!(
+            "Expected CorrelationLogWithoutGeneratedHeader, got {:?}",
+            other.map(|parsed| parsed.is_some())
+        ))),
+    }
+}
+
+#[test]
+fn request_id_echo_counts_mismatches_and_skips_untagged_requests() -> AppResult<()> {
+    let header = reqwest::header::HeaderName::from_static("x-request-id");
+    let echo = RequestIdEcho::new(header.clone());
+    let client = reqwest::Client::new();
+    let tagged = client
+        .get("http://localhost/")
+        .header(&header, "abc-123")
+        .build()
+        .map_err(|err| AppError::validation(format!("Failed to build request: {}", err)))?;
+    let untagged = client
+        .get("http://localhost/")
+        .build()
+        .map_err(|err| AppError::validation(format!("Failed to build request: {}", err)))?;
+
+    let sent = echo.sent_value(&tagged);
+    let mut echoed = reqwest::header::HeaderMap::new();
+    echoed.insert(
+        header.clone(),
+        reqwest::header::HeaderValue::from_static("abc-123"),
+    );
+    let mut altered = reqwest::header::HeaderMap::new();
+    altered.insert(header, reqwest::header::HeaderValue::from_static("other"));
+    let empty = reqwest::header::HeaderMap::new();
+
+    if echo.verify(sent.as_ref(), &echoed).is_some() {
+        return Err(AppError::validation("Matching echo reported a mismatch"));
+    }
+    let mismatch = echo.verify(sent.as_ref(), &altered);
+    if mismatch.as_deref() != Some("x-request-id echo mismatch") {
+        return Err(AppError::validation(format!(
+            "Unexpected mismatch message: {:?}",
+            mismatch
+        )));
+    }
+    let missing = echo.verify(sent.as_ref(), &empty);
+    if missing.as_deref() != Some("x-request-id not echoed") {
+        return Err(AppError::validation(format!(
+            "Unexpected missing message: {:?}",
+            missing
+        )));
+    }
+    if echo
+        .verify(echo.sent_value(&untagged).as_ref(), &empty)
+        .is_some()
+    {
+        return Err(AppError::validation("Untagged request was checked"));
+    }
+    if echo.checked() != 3 || echo.mismatches() != 2 {
+        return Err(AppError::validation(format!(
+            "Unexpected counters: checked={} mismatches={}",
+            echo.checked(),
+            echo.mismatches()
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn response_size_assert_flags_bodies_outside_bounds() -> AppResult<()> {
+    let check = ResponseSizeAssert::new(Some(1_024), Some(0));
+    if check.check(512).is_some() {
+        return Err(AppError::validation("In-range body was flagged"));
+    }
+    let too_large = check.check(1_024);
+    if too_large.as_deref() != Some("response size 1024 bytes, expected > 0 and < 1024 bytes") {
+        return Err(AppError::validation(format!(
+            "Unexpected oversize message: {:?}",
+            too_large
+        )));
+    }
+    if check.check(0).is_none() {
+        return Err(AppError::validation("Empty body was not flagged"));
+    }
+    if check.checked() != 3 || check.violations() != 2 {
+        return Err(AppError::validation(format!(
+            "Unexpected counters: checked={} violations={}",
+            check.checked(),
+            check.violations()
+        )));
+    }
+    Ok(())
+}
+
+#[test]
+fn response_size_assert_rejects_empty_range() -> AppResult<()> {
+    let mut args = base_args("http://localhost".to_owned())?;
+    args.assert_response_bytes_below = Some(positive_u64(10)?);
+    args.assert_response_bytes_above = Some(9);
+    match ResponseSizeAssert::from_args(&args) {
+        Err(AppError::Validation(ValidationError::ResponseBytesRangeEmpty {
+            above: 9,
+            below: 10,
+        })) => Ok(()),
+        other => Err(AppError::validation(format!(
+            "Expected empty range error, got {:?}",
+            other
+        ))),
+    }
+}
+
+#[test]
+fn body_assert_reports_first_failed_check() -> AppResult<()> {
+    let mut args = base_args("http://localhost".to_owned())?;
+    if BodyAssert::from_args(&args)?.is_some() {
+        return Err(AppError::validation("Expected no body checks by default"));
+    }
+    args.assert_body_contains = Some("\"id\"".to_owned());
+    args.assert_body_regex = Some(r#""state":\s*"\w+""#.to_owned());
+    args.assert_json = Some("state=ready".parse()?);
+    let check = BodyAssert::from_args(&args)?
+        .ok_or_else(|| AppError::validation("Expected body checks"))?;
+    // Unexpected statuses keep their real code instead of a failed check.
+    if !check.applies_to(200) || check.applies_to(503) {
+        return Err(AppError::validation(
+            "Expected body checks only for the expected status",
+        ));
+    }
+
+    let cases: [(&[u8], Option<&str>); 5] = [
+        (br#"{"id": 1, "state": "ready"}"#, None),
+        (br#"{"state": "ready"}"#, Some("body missing '\"id\"'")),
+        (
+            br#"{"id": 1, "state": 2}"#,
+            Some(r#"body does not match /"state":\s*"\w+"/"#),
+        ),
+        (
+            br#"{"id": 1, "state": "busy"}"#,
+            Some("$.state was 'busy', expected 'ready'"),
+        ),
+        (
+            br#""id" "state": "x""#,
+            Some("body is not JSON, expected $.state=ready"),
+        ),
+    ];
+    for (body, expected) in cases {
+        let message = check.check(body);
+        if message.as_deref() != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected result for {}: {:?}",
+                String::from_utf8_lossy(body),
+                message
+            )));
+        }
+    }
+
+    args.assert_body_regex = Some("(".to_owned());
+    match BodyAssert::from_args(&args) {
+        Err(AppError::Validation(ValidationError::InvalidBodyRegex { .. })) => Ok(()),
+        other => Err(AppError::validation(format!(
+            "Expected invalid regex error, got {:?}",
+            other
+        ))),
+    }
+}
+
+#[test]
+fn sticky_session_pins_cookie_and_counts_backend_breaks() -> AppResult<()> {
+    use reqwest::header::{COOKIE, HeaderMap, HeaderName, HeaderValue, SET_COOKIE};
+
+    let response = |backend: &'static str, set_cookie: Option<&'static str>| {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-backend"),
+            HeaderValue::from_static(backend),
+        );
+        if let Some(cookie) = set_cookie {
+            headers.insert(SET_COOKIE, HeaderValue::from_static(cookie));
+        }
+        headers
+    };
+    let client = reqwest::Client::new();
+    let build = || {
+        client
+            .get("http://localhost/")
+            .header(COOKIE, "theme=dark")
+            .build()
+            .map_err(|err| AppError::validation(format!("Failed to build request: {}", err)))
+    };
+
+    let tally = Arc::new(BackendTally::new(
+        "SERVERID".to_owned(),
+        HeaderName::from_static("x-backend"),
+    ));
+    let session = StickySession::new(Arc::clone(&tally));
+    let mut first = build()?;
+    session.apply(&mut first);
+    if first.headers().get(COOKIE) != Some(&HeaderValue::from_static("theme=dark")) {
+        return Err(AppError::validation(
+            "Expected no session cookie before it is set",
+        ));
+    }
+    session.observe(&response("app-1", Some("SERVERID=a1; Path=/")));
+
+    let mut second = build()?;
+    session.apply(&mut second);
+    if second.headers().get(COOKIE) != Some(&HeaderValue::from_static("theme=dark; SERVERID=a1")) {
+        return Err(AppError::validation(format!(
+            "Expected the session cookie to be appended, got {:?}",
+            second.headers().get(COOKIE)
+        )));
+    }
+    session.observe(&response("app-1", None));
+    session.observe(&response("app-2", None));
+    session.observe(&response("app-2", None));
+
+    let other = StickySession::new(Arc::clone(&tally));
+    other.observe(&response("app-2", None));
+
+    if tally.breaks() != 1 || tally.sticky_requests() != 3 {
+        return Err(AppError::validation(format!(
+            "Unexpected sticky counts: {} breaks / {} pinned",
+            tally.breaks(),
+            tally.sticky_requests()
+        )));
+    }
+    if tally.distribution_line().as_deref() != Some("app-2 3 (60.00%), app-1 2 (40.00%)") {
+        return Err(AppError::validation(format!(
+            "Unexpected distribution: {:?}",
+            tally.distribution_line()
+        )));
+    }
+
+    session.observe(&response("app-2", Some("SERVERID=; Max-Age=0")));
+    let mut expired = build()?;
+    session.apply(&mut expired);
+    if expired.headers().get(COOKIE) != Some(&HeaderValue::from_static("theme=dark")) {
+        return Err(AppError::validation(
+            "Expected an expired session cookie to be dropped",
+        ));
+    }
+
+    let mut args = base_args("http://localhost/".to_owned())?;
+    args.session_cookie = Some("bad=name".to_owned());
+    if BackendTally::from_args(&args).is_ok() {
+        return Err(AppError::validation(
+            "Expected an invalid cookie name to be rejected",
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn http2_parallel_multiplexes_streams_per_connection() -> AppResult<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    run_async_test(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| AppError::validation(format!("Failed to bind: {}", err)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|err| AppError::validation(format!("Failed to read addr: {}", err)))?;
+        let connections = Arc::new(AtomicUsize::new(0));
+        let peak_streams = Arc::new(AtomicUsize::new(0));
+        let server_connections = Arc::clone(&connections);
+        let server_peak = Arc::clone(&peak_streams);
+        let server = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                server_connections.fetch_add(1, Ordering::Relaxed);
+                let peak = Arc::clone(&server_peak);
+                tokio::spawn(async move {
+                    let Ok(mut connection) = h2::server::handshake(socket).await else {
+                        return;
+                    };
+                    let open = Arc::new(AtomicUsize::new(0));
+                    while let Some(Ok((_request, mut respond))) = connection.accept().await {
+                        let open = Arc::clone(&open);
+                        let peak = Arc::clone(&peak);
+                        tokio::spawn(async move {
+                            let now = open.fetch_add(1, Ordering::Relaxed).saturating_add(1);
+                            peak.fetch_max(now, Ordering::Relaxed);
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                            open.fetch_sub(1, Ordering::Relaxed);
+                            if let Ok(response) = http::Response::builder().status(200).body(()) {
+                                drop(respond.send_response(response, true));
+                            }
+                        });
+                    }
+                });
+            }
+        });
+
+        let mut args = base_args(format!("http://{}/", addr))?;
+        args.http2 = true;
+        args.http2_parallel = positive_usize(4)?;
+        args.max_tasks = positive_usize(2)?;
+        args.target_duration = positive_u64(30)?;
+        args.requests = Some(positive_u64(24)?);
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(64);
+        let sender = setup_request_sender(
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        drop(metrics_tx);
+        let mut completed = 0_u32;
+        while completed < 24 {
+            let metric = tokio::time::timeout(Duration::from_secs(10), metrics_rx.recv())
+                .await
+                .map_err(|_err| AppError::validation("Timed out waiting for metrics"))?
+                .ok_or_else(|| AppError::validation("Metrics channel closed early"))?;
+            if metric.status_code != 200 {
+                return Err(AppError::validation(format!(
+                    "Unexpected metric: {:?}",
+                    metric
+                )));
+            }
+            completed = completed.saturating_add(1);
+        }
+        drop(shutdown_tx.send(()));
+        sender
+            .await
+            .map_err(|err| AppError::validation(format!("Sender task failed: {}", err)))?;
+        server.abort();
+
+        let connections = connections.load(Ordering::Relaxed);
+        let peak_streams = peak_streams.load(Ordering::Relaxed);
+        if connections != 2 || peak_streams != 4 {
+            return Err(AppError::validation(format!(
+                "Expected 2 connections with 4 concurrent streams, got {} / {}",
+                connections, peak_streams
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn http2_streams_average_concurrent_streams_per_connection() -> AppResult<()> {
+    let multiplex = Arc::new(Http2Multiplex::new(3, 2));
+    let client = reqwest::Client::new();
+    let groups = vec![
+        Arc::new(StreamGroup::new(client.clone(), Arc::clone(&multiplex))),
+        Arc::new(StreamGroup::new(client, Arc::clone(&multiplex))),
+    ];
+    let streams = Http2Streams::new(multiplex, groups);
+    let first = streams
+        .group_for(0)
+        .ok_or_else(|| AppError::validation("Expected a group for worker 0"))?;
+    let second = streams
+        .group_for(5)
+        .ok_or_else(|| AppError::validation("Expected a group for worker 5"))?;
+    if streams.group_for(6).is_some() {
+        return Err(AppError::validation("Expected no group past the last slot"));
+    }
+
+    // Streams seen at start: 1, 2, 3 on the first connection, then 1 on the second.
+    let a = first.begin();
+    let b = first.begin();
+    let c = first.begin();
+    drop((a, b, c));
+    let d = second.begin();
+    drop(d);
+
+    let report = streams.report();
+    if report.streams != 4 || report.avg_streams_x100 != 175 || report.tasks != 2 {
+        return Err(AppError::validation(format!(
+            "Unexpected HTTP/2 report: {:?}",
+            report
+        )));
+    }
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use reqwest::ClientBuilder;
+use tokio_native_tls::native_tls;
 
 use crate::args::{HttpVersion, TesterArgs, TlsVersion};
-use crate::error::{AppError, AppResult, ValidationError};
+use crate::error::{AppError, AppResult, HttpError, ValidationError};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum AlpnChoice {
@@ -16,6 +17,110 @@ pub(crate) struct AlpnSelection {
     pub(crate) has_h3: bool,
 }
 
+/// Adds `--cacert`, the `--cert`/`--key` client identity, and `--insecure`.
+///
+/// Shared by every reqwest client that talks to the target, so the HTTP
+/// sender and the SSE runtime trust the same certificates.
+pub(crate) fn apply_trust_settings(
+    mut builder: ClientBuilder,
+    args: &TesterArgs,
+) -> AppResult<ClientBuilder> {
+    if let Some(path) = args.cacert.as_deref() {
+        let cert = reqwest::Certificate::from_pem(&read_cacert(path)?).map_err(|err| {
+            AppError::http(HttpError::InvalidCacert {
+                path: path.into(),
+                source: err,
+            })
+        })?;
+        builder = builder.add_root_certificate(cert);
+    }
+
+    if let Some((cert_bytes, key_bytes)) = read_identity(args)? {
+        let identity = reqwest::Identity::from_pkcs8_pem(&cert_bytes, &key_bytes)
+            .map_err(|err| AppError::http(HttpError::InvalidIdentity { source: err }))?;
+        builder = builder.identity(identity);
+    }
+
+    if args.insecure {
+        builder = builder
+            .danger_accept_invalid_certs(true)
+            .danger_accept_invalid_hostnames(true);
+    }
+    Ok(builder)
+}
+
+/// TLS connector for the `--diagnose` handshake probe, trusting the same
+/// `--cacert`, `--cert`/`--key`, and `--insecure` settings as the sender.
+pub(super) fn diagnose_tls_connector(args: &TesterArgs) -> AppResult<native_tls::TlsConnector> {
+    let mut builder = native_tls::TlsConnector::builder();
+    if let Some(path) = args.cacert.as_deref() {
+        let cert = native_tls::Certificate::from_pem(&read_cacert(path)?).map_err(|err| {
+            AppError::http(HttpError::TlsProbeSetup {
+                what: "--cacert",
+                source: err,
+            })
+        })?;
+        builder.add_root_certificate(cert);
+    }
+    if let Some((cert_bytes, key_bytes)) = read_identity(args)? {
+        let identity =
+            native_tls::Identity::from_pkcs8(&cert_bytes, &key_bytes).map_err(|err| {
+                AppError::http(HttpError::TlsProbeSetup {
+                    what: "--cert/--key",
+                    source: err,
+                })
+            })?;
+        builder.identity(identity);
+    }
+    builder
+        .danger_accept_invalid_certs(args.insecure)
+        .danger_accept_invalid_hostnames(args.insecure)
+        .build()
+        .map_err(|err| {
+            AppError::http(HttpError::TlsProbeSetup {
+                what: "settings",
+                source: err,
+            })
+        })
+}
+
+fn read_cacert(path: &str) -> AppResult<Vec<u8>> {
+    std::fs::read(path).map_err(|err| {
+        AppError::http(HttpError::ReadCacert {
+            path: path.into(),
+            source: err,
+        })
+    })
+}
+
+/// Reads the `--cert`/`--key` PEM pair; either one alone is rejected.
+fn read_identity(args: &TesterArgs) -> AppResult<Option<(Vec<u8>, Vec<u8>)>> {
+    if args.cert.is_none() && args.key.is_none() {
+        return Ok(None);
+    }
+    let cert_path = args
+        .cert
+        .as_deref()
+        .ok_or_else(|| AppError::validation(ValidationError::CertRequiresKey))?;
+    let key_path = args
+        .key
+        .as_deref()
+        .ok_or_else(|| AppError::validation(ValidationError::KeyRequiresCert))?;
+    let cert_bytes = std::fs::read(cert_path).map_err(|err| {
+        AppError::http(HttpError::ReadCert {
+            path: cert_path.into(),
+            source: err,
+        })
+    })?;
+    let key_bytes = std::fs::read(key_path).map_err(|err| {
+        AppError::http(HttpError::ReadKey {
+            path: key_path.into(),
+            source: err,
+        })
+    })?;
+    Ok(Some((cert_bytes, key_bytes)))
+}
+
 pub(super) fn apply_tls_settings(
     mut builder: ClientBuilder,
     args: &TesterArgs,
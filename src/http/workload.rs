@@ -2,16 +2,28 @@ mod builders;
 mod builders_auth;
 mod data;
 mod execution;
+mod extract;
+mod faker;
+mod json_template;
 mod runner;
 mod runner_common;
 mod template;
 
+#[cfg(test)]
+pub(super) use builders::build_request_from_spec;
 pub(super) use data::{
-    AuthConfig, BodySource, FormFieldSpec, RequestLimiter, ScenarioRunContext, SingleRequestSpec,
-    UrlSource, WorkerContext, Workload,
+    AuthConfig, BodyFile, BodySource, ConnectToClients, FormFieldSpec, RequestLimiter,
+    ScenarioRunContext, SingleRequestSpec, StepClients, UrlSource, WorkerContext, Workload,
 };
+pub(super) use execution::execute_request;
+#[cfg(test)]
+pub(super) use extract::extract_vars;
+pub(super) use extract::lookup as json_lookup;
+pub(super) use faker::{FakeBody, FakeData, validate_scenario_placeholders, write_uuid_v4};
+pub(super) use json_template::JsonBody;
 pub(super) use runner::{
-    preflight_request, run_scenario_iteration, run_single_dynamic_iteration, run_single_iteration,
+    preflight_request, probe_first_byte, run_scenario_iteration, run_single_dynamic_iteration,
+    run_single_iteration,
 };
 #[cfg(test)]
 pub(crate) use template::render_template;
@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
 
-use reqwest::{Client, Request, Url};
+use bytes::Bytes;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Request, Url, Version};
 
 use crate::{
     args::{ConnectToMapping, HttpMethod, Scenario, ScenarioStep},
@@ -9,6 +11,7 @@ use crate::{
 
 use super::builders_auth::apply_auth_headers;
 use super::data::{AuthConfig, BodySource, FormFieldSpec, SingleRequestSpec};
+use super::faker::FakeData;
 use super::template::{render_template, resolve_step_url};
 
 fn build_multipart(fields: &[FormFieldSpec]) -> AppResult<reqwest::multipart::Form> {
@@ -18,20 +21,20 @@ fn build_multipart(fields: &[FormFieldSpec]) -> AppResult<reqwest::multipart::Fo
             FormFieldSpec::Text { name, value } => {
                 form = form.text(name.clone(), value.clone());
             }
-            FormFieldSpec::File { name, path } => {
-                let bytes = std::fs::read(path).map_err(|err| {
-                    AppError::http(HttpError::ReadFormFile {
-                        path: path.clone(),
-                        source: err,
-                    })
-                })?;
-                let part = reqwest::multipart::Part::bytes(bytes).file_name(
-                    std::path::Path::new(path)
-                        .file_name()
-                        .and_then(|value| value.to_str())
-                        .unwrap_or("file")
-                        .to_owned(),
-                );
+            FormFieldSpec::File {
+                name,
+                file_name,
+                content_type,
+                bytes,
+            } => {
+                let length = u64::try_from(bytes.len()).unwrap_or(u64::MAX);
+                let mut part = reqwest::multipart::Part::stream_with_length(bytes.clone(), length)
+                    .file_name(file_name.clone());
+                if let Some(content_type) = content_type {
+                    part = part.mime_str(content_type).map_err(|err| {
+                        AppError::http(HttpError::BuildRequestFailed { source: err })
+                    })?;
+                }
                 form = form.part(name.clone(), part);
             }
         }
@@ -39,10 +42,12 @@ fn build_multipart(fields: &[FormFieldSpec]) -> AppResult<reqwest::multipart::Fo
     Ok(form)
 }
 
-pub(super) fn build_request_from_spec(
+/// Also returns the index of the `--connect-to` mapping the URL was rewritten
+/// by, if any.
+pub(in crate::http) fn build_request_from_spec(
     client: &Client,
     spec: &SingleRequestSpec,
-) -> AppResult<Request> {
+) -> AppResult<(Request, Option<usize>)> {
     let url_raw = spec.url.next_url()?;
     let url = Url::parse(&url_raw).map_err(|err| {
         AppError::http(HttpError::InvalidUrl {
@@ -50,7 +55,8 @@ pub(super) fn build_request_from_spec(
             source: err,
         })
     })?;
-    let (url, host_override) = apply_connect_to(&url, &spec.connect_to)?;
+    let (url, connected) = apply_connect_to(&url, &spec.connect_to)?;
+    let (mapping, host_override) = connected.unzip();
 
     let mut request_builder = match spec.method {
         HttpMethod::Get => client.get(url.clone()),
@@ -60,6 +66,9 @@ pub(super) fn build_request_from_spec(
         HttpMethod::Delete => client.delete(url.clone()),
     };
 
+    if let Some(version) = spec.version {
+        request_builder = request_builder.version(version);
+    }
     for (key, value) in &spec.headers {
         request_builder = request_builder.header(key, value);
     }
@@ -69,12 +78,33 @@ pub(super) fn build_request_from_spec(
         request_builder = request_builder.header("Host", host);
     }
 
-    let body = match &spec.body {
-        BodySource::Static(body) => body.clone(),
-        BodySource::Lines(lines) => lines
-            .next()
-            .ok_or_else(|| AppError::http(HttpError::BodyLinesEmpty))?,
+    let (body, file_content_type) = match &spec.body {
+        BodySource::Static(body) => (Bytes::from(body.clone()), None),
+        BodySource::Lines(lines) => (
+            Bytes::from(
+                lines
+                    .next()
+                    .ok_or_else(|| AppError::http(HttpError::BodyLinesEmpty))?,
+            ),
+            None,
+        ),
+        BodySource::Fake(body) => (Bytes::from(body.render()), None),
+        BodySource::Json(body) => (Bytes::from(body.render()?), None),
+        BodySource::Files(files) => {
+            let file = files
+                .next()
+                .ok_or_else(|| AppError::http(HttpError::BodyLinesEmpty))?;
+            (file.bytes.clone(), file.content_type)
+        }
     };
+    if let Some(content_type) = file_content_type
+        && !spec
+            .headers
+            .iter()
+            .any(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+    {
+        request_builder = request_builder.header(CONTENT_TYPE, content_type);
+    }
 
     if let Some(auth) = spec.auth.as_ref() {
         let mut headers_for_sign = spec.headers.clone();
@@ -100,26 +130,33 @@ pub(super) fn build_request_from_spec(
         request_builder = request_builder.body(body);
     }
 
-    request_builder
+    let request = request_builder
         .build()
-        .map_err(|err| AppError::http(HttpError::BuildRequestFailed { source: err }))
+        .map_err(|err| AppError::http(HttpError::BuildRequestFailed { source: err }))?;
+    Ok((request, mapping))
 }
 
 pub(crate) struct StepRequestContext<'ctx> {
     pub connect_to: &'ctx [ConnectToMapping],
     pub host_header: Option<&'ctx str>,
     pub auth: Option<&'ctx AuthConfig>,
+    pub version: Option<Version>,
+    /// Expands `{{name}}`-style fake data in step bodies; `None` leaves them verbatim.
+    pub fake: Option<&'ctx FakeData>,
 }
 
+/// Also returns the index of the `--connect-to` mapping the URL was rewritten
+/// by, if any.
 pub(crate) fn build_step_request(
     client: &Client,
     scenario: &Scenario,
     step: &ScenarioStep,
     vars: &BTreeMap<String, String>,
     context: &StepRequestContext<'_>,
-) -> AppResult<Request> {
+) -> AppResult<(Request, Option<usize>)> {
     let url = resolve_step_url(scenario, step, vars)?;
-    let (url, host_override) = apply_connect_to(&url, context.connect_to)?;
+    let (url, connected) = apply_connect_to(&url, context.connect_to)?;
+    let (mapping, host_override) = connected.unzip();
     let mut request_builder = match step.method {
         HttpMethod::Get => client.get(url.clone()),
         HttpMethod::Post => client.post(url.clone()),
@@ -128,6 +165,10 @@ pub(crate) fn build_step_request(
         HttpMethod::Delete => client.delete(url.clone()),
     };
 
+    if let Some(version) = context.version {
+        request_builder = request_builder.version(version);
+    }
+
     let mut rendered_headers = Vec::with_capacity(step.headers.len());
     for (key, value) in &step.headers {
         let key_rendered = render_template(key, vars);
@@ -146,7 +187,12 @@ pub(crate) fn build_step_request(
     let body_rendered = step
         .body
         .as_ref()
-        .map(|body| render_template(body, vars))
+        .map(|body| {
+            context.fake.map_or_else(
+                || render_template(body, vars),
+                |fake| fake.expand(&render_template(body, vars)),
+            )
+        })
         .unwrap_or_default();
     if let Some(auth) = context.auth {
         let mut headers_for_sign = rendered_headers.clone();
@@ -162,29 +208,32 @@ pub(crate) fn build_step_request(
             step.method,
             &url,
             &headers_for_sign,
-            &body_rendered,
+            body_rendered.as_bytes(),
             auth,
         )?;
     }
 
-    if let Some(body) = step.body.as_ref() {
-        request_builder = request_builder.body(render_template(body, vars));
+    if step.body.is_some() {
+        request_builder = request_builder.body(body_rendered);
     }
 
-    request_builder
+    let request = request_builder
         .build()
-        .map_err(|err| AppError::http(HttpError::BuildRequestFailed { source: err }))
+        .map_err(|err| AppError::http(HttpError::BuildRequestFailed { source: err }))?;
+    Ok((request, mapping))
 }
 
+/// Rewrites `url` by the first matching mapping; returns that mapping's index
+/// and the original authority for the `Host` header.
 fn apply_connect_to(
     url: &Url,
     connect_to: &[ConnectToMapping],
-) -> AppResult<(Url, Option<String>)> {
+) -> AppResult<(Url, Option<(usize, String)>)> {
     let Some(host) = url.host_str() else {
         return Ok((url.clone(), None));
     };
     let port = url.port_or_known_default().unwrap_or(80);
-    for mapping in connect_to {
+    for (index, mapping) in connect_to.iter().enumerate() {
         if mapping.source_host == host && mapping.source_port == port {
             let mut rewritten = url.clone();
             rewritten
@@ -198,12 +247,21 @@ fn apply_connect_to(
             } else {
                 format!("{}:{}", host, port)
             };
-            return Ok((rewritten, Some(host_header)));
+            return Ok((rewritten, Some((index, host_header))));
         }
     }
     Ok((url.clone(), None))
 }
 
+/// Returns the client of the mapping a request was rewritten by, when that
+/// mapping carries its own CA bundle.
+pub(super) fn connect_to_client(
+    clients: &[Option<Client>],
+    mapping: Option<usize>,
+) -> Option<&Client> {
+    clients.get(mapping?)?.as_ref()
+}
+
 fn has_host_header(headers: &[(String, String)]) -> bool {
     headers
         .iter()
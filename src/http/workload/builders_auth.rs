@@ -27,7 +27,7 @@ pub(super) fn apply_auth_headers(
     method: HttpMethod,
     url: &Url,
     headers: &[(String, String)],
-    body: &str,
+    body: &[u8],
     auth: &AuthConfig,
 ) -> AppResult<RequestBuilder> {
     match auth {
@@ -37,6 +37,12 @@ pub(super) fn apply_auth_headers(
             builder = builder.header("Authorization", format!("Basic {}", encoded));
             Ok(builder)
         }
+        // Digest credentials are added after the server's 401 challenge.
+        AuthConfig::Digest { .. } => Ok(builder),
+        AuthConfig::OAuth2 { session } => Ok(builder.header("Authorization", session.bearer())),
+        AuthConfig::Bearer { token } => {
+            Ok(builder.header("Authorization", format!("Bearer {}", token)))
+        }
         AuthConfig::SigV4 {
             access_key,
             secret_key,
@@ -72,7 +78,7 @@ pub(super) fn apply_auth_headers(
                 method_str,
                 url.as_str(),
                 headers.iter().map(|(k, v)| (k.as_str(), v.as_str())),
-                SignableBody::Bytes(body.as_bytes()),
+                SignableBody::Bytes(body),
             )
             .map_err(|err| {
                 AppError::http(HttpError::SigV4Request {
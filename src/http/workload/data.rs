@@ -1,21 +1,39 @@
+use std::collections::BTreeMap;
 use std::sync::{
     Arc,
     atomic::{AtomicU64, AtomicUsize, Ordering},
 };
 
-use rand::distributions::Distribution;
+use bytes::Bytes;
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::thread_rng;
 use rand_regex::Regex as RandRegex;
-use reqwest::{Client, Request};
+use reqwest::{Client, Request, Version};
 use tokio::sync::{Semaphore, mpsc};
 
 use crate::{
-    args::{ConnectToMapping, HttpMethod, Scenario},
+    args::{ConnectToMapping, ExpectedStatus, HttpMethod, Scenario},
     error::{AppError, AppResult, HttpError},
     metrics::{LogSink, Metrics},
     shutdown::ShutdownSender,
 };
 
+use super::super::bandwidth::BandwidthLimiter;
+use super::super::body_assert::BodyAssert;
+use super::super::cookies::CookieJar;
+use super::super::deadline::DeadlineHeader;
+use super::super::digest::DigestAuth;
+use super::super::edge_sim::EdgeSim;
+use super::super::header_gen::GeneratedHeaders;
+use super::super::multiplex::StreamGroup;
+use super::super::oauth2::OAuth2Session;
+use super::super::request_id::RequestIdEcho;
+use super::super::response_size::ResponseSizeAssert;
+use super::super::sticky::StickySession;
+use super::builders::connect_to_client;
+use super::faker::{FakeBody, FakeData};
+use super::json_template::JsonBody;
+
 #[derive(Clone)]
 pub(in crate::http) enum Workload {
     Single(Arc<Request>),
@@ -25,15 +43,57 @@ pub(in crate::http) enum Workload {
         Arc<Vec<ConnectToMapping>>,
         Option<String>,
         Option<AuthConfig>,
+        Option<Version>,
+        Arc<FakeData>,
+        Arc<StepClients>,
+        Arc<ConnectToClients>,
     ),
 }
 
+/// Clients for scenario steps that override the TLS client identity, indexed
+/// by step; `None` entries use the default client.
+pub(in crate::http) type StepClients = Vec<Option<Client>>;
+
+/// Clients for `--connect-to` mappings that carry their own CA bundle,
+/// indexed by mapping; `None` entries use the default client.
+pub(in crate::http) type ConnectToClients = Vec<Option<Client>>;
+
+impl Workload {
+    pub(in crate::http) fn auth(&self) -> Option<&AuthConfig> {
+        match self {
+            Self::Single(_) => None,
+            Self::SingleDynamic(spec) => spec.auth.as_ref(),
+            Self::Scenario(_, _, _, auth, ..) => auth.as_ref(),
+        }
+    }
+
+    /// Returns the CA-specific client of the `--connect-to` mapping a request
+    /// was rewritten by.
+    pub(in crate::http) fn connect_to_client(&self, mapping: Option<usize>) -> Option<&Client> {
+        match self {
+            Self::Single(_) => None,
+            Self::SingleDynamic(spec) => connect_to_client(&spec.connect_to_clients, mapping),
+            Self::Scenario(.., clients) => connect_to_client(clients, mapping),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum AuthConfig {
     Basic {
         username: String,
         password: String,
     },
+    Bearer {
+        token: String,
+    },
+    Digest {
+        username: String,
+        password: String,
+    },
+    OAuth2 {
+        session: OAuth2Session,
+    },
     SigV4 {
         access_key: String,
         secret_key: String,
@@ -96,6 +156,10 @@ impl IndexedList {
         }
     }
 
+    pub(in crate::http) fn items(&self) -> &[String] {
+        &self.items
+    }
+
     pub(super) fn next(&self) -> Option<String> {
         if self.items.is_empty() {
             return None;
@@ -107,22 +171,89 @@ impl IndexedList {
     }
 }
 
+/// One `--data-dir` file, with the Content-Type implied by its extension.
+pub(in crate::http) struct BodyFile {
+    pub(in crate::http) bytes: Bytes,
+    pub(in crate::http) content_type: Option<&'static str>,
+}
+
+/// Request bodies rotated round-robin, one file per request.
+pub(in crate::http) struct FileList {
+    files: Vec<BodyFile>,
+    cursor: AtomicUsize,
+}
+
+impl FileList {
+    const fn new(files: Vec<BodyFile>) -> Self {
+        Self {
+            files,
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    pub(in crate::http) const fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub(in crate::http) fn next(&self) -> Option<&BodyFile> {
+        if self.files.is_empty() {
+            return None;
+        }
+        let idx = self.cursor.fetch_add(1, Ordering::Relaxed);
+        self.files.get(idx.rem_euclid(self.files.len()))
+    }
+}
+
+/// URLs sampled per request in proportion to their weights.
+pub(in crate::http) struct WeightedList {
+    items: Vec<String>,
+    index: WeightedIndex<u64>,
+}
+
+impl WeightedList {
+    fn new(items: Vec<(u32, String)>) -> AppResult<Self> {
+        let index = WeightedIndex::new(items.iter().map(|(weight, _)| u64::from(*weight)))
+            .map_err(|source| AppError::http(HttpError::UrlWeights { source }))?;
+        Ok(Self {
+            items: items.into_iter().map(|(_, url)| url).collect(),
+            index,
+        })
+    }
+
+    pub(in crate::http) fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    fn sample(&self) -> Option<String> {
+        let mut rng = thread_rng();
+        self.items.get(self.index.sample(&mut rng)).cloned()
+    }
+}
+
 #[derive(Clone)]
 pub(in crate::http) enum BodySource {
     Static(String),
     Lines(Arc<IndexedList>),
+    Fake(Arc<FakeBody>),
+    Json(Arc<JsonBody>),
+    Files(Arc<FileList>),
 }
 
 impl BodySource {
     pub(in crate::http) fn from_lines(lines: Vec<String>) -> Self {
         Self::Lines(Arc::new(IndexedList::new(lines)))
     }
+
+    pub(in crate::http) fn from_files(files: Vec<BodyFile>) -> Self {
+        Self::Files(Arc::new(FileList::new(files)))
+    }
 }
 
 #[derive(Clone)]
 pub(in crate::http) enum UrlSource {
     Static(String),
     List(Arc<IndexedList>),
+    Weighted(Arc<WeightedList>),
     Regex(Arc<RandRegex>),
 }
 
@@ -131,12 +262,25 @@ impl UrlSource {
         Self::List(Arc::new(IndexedList::new(urls)))
     }
 
-    pub(super) fn next_url(&self) -> AppResult<String> {
+    /// Builds a weighted source, or a round-robin list when every weight is 1.
+    pub(in crate::http) fn from_weighted(urls: Vec<(u32, String)>) -> AppResult<Self> {
+        if urls.iter().all(|(weight, _)| *weight == 1) {
+            return Ok(Self::from_list(
+                urls.into_iter().map(|(_, url)| url).collect(),
+            ));
+        }
+        Ok(Self::Weighted(Arc::new(WeightedList::new(urls)?)))
+    }
+
+    pub(in crate::http) fn next_url(&self) -> AppResult<String> {
         match self {
             UrlSource::Static(url) => Ok(url.clone()),
             UrlSource::List(list) => list
                 .next()
                 .ok_or_else(|| AppError::http(HttpError::UrlListEmpty)),
+            UrlSource::Weighted(list) => list
+                .sample()
+                .ok_or_else(|| AppError::http(HttpError::UrlListEmpty)),
             UrlSource::Regex(regex) => {
                 let mut rng = thread_rng();
                 Ok(regex.sample(&mut rng))
@@ -147,8 +291,17 @@ impl UrlSource {
 
 #[derive(Clone)]
 pub(in crate::http) enum FormFieldSpec {
-    Text { name: String, value: String },
-    File { name: String, path: String },
+    Text {
+        name: String,
+        value: String,
+    },
+    /// File part read once at startup; `Bytes` keeps per-request clones cheap.
+    File {
+        name: String,
+        file_name: String,
+        content_type: Option<&'static str>,
+        bytes: Bytes,
+    },
 }
 
 #[derive(Clone)]
@@ -159,14 +312,32 @@ pub(in crate::http) struct SingleRequestSpec {
     pub(in crate::http) body: BodySource,
     pub(in crate::http) form: Option<Vec<FormFieldSpec>>,
     pub(in crate::http) connect_to: Vec<ConnectToMapping>,
+    pub(in crate::http) connect_to_clients: ConnectToClients,
     pub(in crate::http) auth: Option<AuthConfig>,
+    pub(in crate::http) version: Option<Version>,
+    /// `${uuid}`/`${seq}` headers rendered per request.
+    pub(in crate::http) generated: Option<Arc<GeneratedHeaders>>,
 }
 
 pub(in crate::http) struct WorkerContext<'ctx> {
     pub(in crate::http) shutdown_tx: &'ctx ShutdownSender,
     pub(in crate::http) rate_limiter: Option<&'ctx Arc<Semaphore>>,
+    pub(in crate::http) bandwidth_limiter: Option<&'ctx Arc<BandwidthLimiter>>,
     pub(in crate::http) request_limiter: Option<&'ctx Arc<RequestLimiter>>,
+    pub(in crate::http) request_id_echo: Option<&'ctx RequestIdEcho>,
+    pub(in crate::http) deadline_header: Option<&'ctx DeadlineHeader>,
+    pub(in crate::http) response_size: Option<&'ctx ResponseSizeAssert>,
+    pub(in crate::http) body_assert: Option<&'ctx BodyAssert>,
     pub(in crate::http) in_flight_counter: &'ctx Arc<AtomicU64>,
+    pub(in crate::http) digest: Option<&'ctx DigestAuth>,
+    pub(in crate::http) sticky: Option<&'ctx StickySession>,
+    pub(in crate::http) edge_sim: Option<&'ctx EdgeSim>,
+    /// The shared HTTP/2 connection this worker's requests are counted on.
+    pub(in crate::http) stream_group: Option<&'ctx StreamGroup>,
+    /// Attach the request URL to each metric, for `--slowest`.
+    pub(in crate::http) record_urls: bool,
+    /// Trace connect and first-byte timing, for `--connection-timing`.
+    pub(in crate::http) connection_timing: bool,
     pub(in crate::http) wait_ongoing: bool,
     pub(in crate::http) latency_correction: bool,
     pub(in crate::http) client: &'ctx Client,
@@ -180,8 +351,43 @@ pub(in crate::http) struct ScenarioRunContext<'ctx> {
     pub(in crate::http) connect_to: &'ctx [ConnectToMapping],
     pub(in crate::http) host_header: Option<&'ctx str>,
     pub(in crate::http) auth: Option<&'ctx AuthConfig>,
-    pub(in crate::http) expected_status_code: u16,
+    pub(in crate::http) version: Option<Version>,
+    pub(in crate::http) fake: &'ctx FakeData,
+    pub(in crate::http) step_clients: &'ctx [Option<Client>],
+    pub(in crate::http) connect_to_clients: &'ctx [Option<Client>],
+    pub(in crate::http) expected_status_code: ExpectedStatus,
     pub(in crate::http) log_sink: &'ctx Option<Arc<LogSink>>,
     pub(in crate::http) metrics_tx: &'ctx mpsc::Sender<Metrics>,
     pub(in crate::http) request_seq: &'ctx mut u64,
+    pub(in crate::http) cookies: Option<&'ctx mut CookieJar>,
+}
+
+/// State carried from one scenario step to the next within an iteration.
+#[derive(Debug)]
+pub(super) struct StepSession<'ctx> {
+    pub(super) vars: BTreeMap<String, String>,
+    pub(super) cookies: Option<&'ctx mut CookieJar>,
+    pub(super) digest: Option<&'ctx DigestAuth>,
+    pub(super) sticky: Option<&'ctx StickySession>,
+    pub(super) edge: Option<&'ctx EdgeSim>,
+}
+
+impl StepSession<'_> {
+    pub(super) fn apply_cookies(&self, request: &mut Request) {
+        if let Some(jar) = self.cookies.as_deref() {
+            jar.apply(request);
+        }
+        if let Some(sticky) = self.sticky {
+            sticky.apply(request);
+        }
+    }
+
+    pub(super) fn store_cookies(&mut self, response: &reqwest::Response) {
+        if let Some(jar) = self.cookies.as_deref_mut() {
+            jar.store(response.url(), response.headers());
+        }
+        if let Some(sticky) = self.sticky {
+            sticky.observe(response.headers());
+        }
+    }
 }
@@ -1,7 +1,25 @@
+use std::sync::Arc;
+
 use futures_util::StreamExt;
-use reqwest::{Client, Request};
+use reqwest::header::COOKIE;
+use reqwest::{Client, Request, Version};
 use tracing::error;
 
+use crate::args::{ExpectedStatus, ScenarioStep};
+use crate::metrics::{CheckFailure, ErrorReason};
+
+use super::super::body_assert::BodyAssert;
+use super::super::conn_timing::mark_first_byte;
+use super::super::cookies::{CookieJar, redirect_request};
+use super::super::digest::DigestAuth;
+use super::super::edge_sim::EdgeSim;
+use super::super::error_reason::classify;
+use super::super::request_id::RequestIdEcho;
+use super::super::response_size::ResponseSizeAssert;
+use super::super::sticky::StickySession;
+use super::data::StepSession;
+use super::extract::extract_vars;
+
 #[derive(Debug)]
 pub(super) struct RequestOutcome {
     pub(super) status: u16,
@@ -9,35 +27,85 @@ pub(super) struct RequestOutcome {
     pub(super) timed_out: bool,
     pub(super) transport_error: bool,
     pub(super) response_bytes: u64,
+    pub(super) error_message: Option<Arc<str>>,
+    pub(super) http_version: Option<&'static str>,
+    pub(super) error_reason: Option<ErrorReason>,
+    pub(super) check_failure: Option<CheckFailure>,
+}
+
+impl RequestOutcome {
+    fn failed(err: &reqwest::Error) -> Self {
+        let timed_out = err.is_timeout();
+        Self {
+            status: 500,
+            success: false,
+            timed_out,
+            transport_error: !timed_out,
+            response_bytes: 0,
+            error_message: Some(Arc::from(err.to_string())),
+            http_version: None,
+            error_reason: Some(classify(err)),
+            check_failure: None,
+        }
+    }
+
+    /// Fails a received response whose body size is outside `response_size` bounds.
+    pub(super) fn check_response_size(
+        mut self,
+        response_size: Option<&ResponseSizeAssert>,
+    ) -> Self {
+        if self.timed_out || self.transport_error {
+            return self;
+        }
+        if let Some(message) = response_size.and_then(|check| check.check(self.response_bytes)) {
+            self.success = false;
+            self.error_message = self.error_message.or(Some(message));
+            self.check_failure = self.check_failure.or(Some(CheckFailure::ResponseSize));
+        }
+        self
+    }
 }
 
 pub(super) async fn execute_request_with_asserts(
     client: &Client,
     request: Request,
-    expected_status_code: u16,
-    assert_status: Option<u16>,
-    assert_body_contains: Option<&str>,
+    expected_status_code: ExpectedStatus,
+    step: &ScenarioStep,
+    session: &mut StepSession<'_>,
+    request_id_echo: Option<&RequestIdEcho>,
 ) -> RequestOutcome {
-    match client.execute(request).await {
+    let sent_request_id = request_id_echo.and_then(|echo| echo.sent_value(&request));
+    match send_step(client, request, session).await {
         Ok(response) => {
             let status = response.status().as_u16();
-            let expected = assert_status.unwrap_or(expected_status_code);
-            let status_ok = status == expected;
+            let http_version = Some(version_label(response.version()));
+            let expected = step
+                .assert_status
+                .map_or(expected_status_code, ExpectedStatus::single);
+            let status_ok = expected.contains(status);
+            let echo_error = request_id_echo
+                .and_then(|echo| echo.verify(sent_request_id.as_ref(), response.headers()));
+            session.store_cookies(&response);
 
+            let assert_body_contains = step.assert_body_contains.as_deref();
             let body_result = match assert_body_contains {
-                Some(fragment) => drain_body_contains(response, fragment).await,
-                None => drain_response_body(response)
+                Some(fragment) => drain_body_contains(response, fragment, session.edge).await,
+                None => drain_response_body(response, session.edge)
                     .await
                     .map(|bytes| (true, bytes)),
             };
             let mut timed_out = false;
             let mut transport_error = false;
+            let mut error_message = None;
+            let mut error_reason = None;
             let (body_ok, response_bytes) = match (assert_body_contains, body_result) {
                 (Some(_), Ok((found, bytes))) => (found, bytes),
                 (Some(_), Err(err)) => {
                     timed_out = err.is_timeout();
                     transport_error = !timed_out;
                     error!("Failed to read response body: {}", err);
+                    error_message = Some(Arc::from(err.to_string()));
+                    error_reason = Some(classify(&err));
                     (false, 0)
                 }
                 (None, Ok((found, bytes))) => (found, bytes),
@@ -45,33 +113,110 @@ pub(super) async fn execute_request_with_asserts(
                     timed_out = err.is_timeout();
                     transport_error = !timed_out;
                     error!("Failed to read response body: {}", err);
+                    error_message = Some(Arc::from(err.to_string()));
+                    error_reason = Some(classify(&err));
                     (false, 0)
                 }
             };
 
+            let echo_ok = echo_error.is_none();
+            let check_failure = mismatch_failure(echo_error.as_ref());
             RequestOutcome {
                 status,
-                success: status_ok && body_ok,
+                success: status_ok && body_ok && echo_ok,
                 timed_out,
                 transport_error,
                 response_bytes,
+                error_message: error_message.or(echo_error),
+                http_version,
+                error_reason,
+                check_failure,
             }
         }
         Err(err) => {
             error!("Request failed: {}", err);
+            RequestOutcome::failed(&err)
+        }
+    }
+}
+
+/// Like [`execute_request_with_asserts`], but buffers the body so the step's
+/// `extract` sources can read it.
+///
+/// Extracted values are written to the session; a miss fails the step as a
+/// transport error.
+pub(super) async fn execute_request_with_extract(
+    client: &Client,
+    request: Request,
+    expected_status_code: ExpectedStatus,
+    step: &ScenarioStep,
+    session: &mut StepSession<'_>,
+    request_id_echo: Option<&RequestIdEcho>,
+) -> RequestOutcome {
+    let sent_request_id = request_id_echo.and_then(|echo| echo.sent_value(&request));
+    let response = match send_step(client, request, session).await {
+        Ok(response) => response,
+        Err(err) => {
+            error!("Request failed: {}", err);
+            return RequestOutcome::failed(&err);
+        }
+    };
+    let status = response.status().as_u16();
+    let http_version = Some(version_label(response.version()));
+    let status_ok = step
+        .assert_status
+        .map_or(expected_status_code, ExpectedStatus::single)
+        .contains(status);
+    let echo_error =
+        request_id_echo.and_then(|echo| echo.verify(sent_request_id.as_ref(), response.headers()));
+    session.store_cookies(&response);
+    let headers = response.headers().clone();
+    let body = match response.bytes().await {
+        Ok(body) => {
+            if let Some(edge) = session.edge {
+                edge.throttle_read(body.len()).await;
+            }
+            body
+        }
+        Err(err) => {
+            error!("Failed to read response body: {}", err);
             let timed_out = err.is_timeout();
-            RequestOutcome {
-                status: 500,
+            return RequestOutcome {
+                status,
                 success: false,
                 timed_out,
                 transport_error: !timed_out,
                 response_bytes: 0,
-            }
+                error_message: Some(Arc::from(err.to_string())),
+                http_version,
+                error_reason: Some(classify(&err)),
+                check_failure: mismatch_failure(echo_error.as_ref()),
+            };
         }
+    };
+
+    let body_ok = step.assert_body_contains.as_deref().is_none_or(|fragment| {
+        fragment.is_empty()
+            || body
+                .windows(fragment.len())
+                .any(|slice| slice == fragment.as_bytes())
+    });
+    let extract_error = extract_vars(&step.extract, &headers, &body, &mut session.vars).err();
+    let check_failure = mismatch_failure(echo_error.as_ref());
+    RequestOutcome {
+        status,
+        success: status_ok && body_ok && echo_error.is_none() && extract_error.is_none(),
+        timed_out: false,
+        transport_error: extract_error.is_some(),
+        response_bytes: u64::try_from(body.len()).unwrap_or(u64::MAX),
+        error_message: extract_error.or(echo_error),
+        http_version,
+        error_reason: None,
+        check_failure,
     }
 }
 
-pub(super) async fn execute_request(
+pub(in crate::http) async fn execute_request(
     client: &Client,
     request: Request,
     drain_body: bool,
@@ -79,50 +224,187 @@ pub(super) async fn execute_request(
     let response = client.execute(request).await?;
     let status = response.status().as_u16();
     if drain_body {
-        let _ = drain_response_body(response).await?;
+        let _ = drain_response_body(response, None).await?;
     }
     Ok(status)
 }
 
+/// Executes a request and drains the body; `success` only reflects transport success.
+///
+/// A missing or mismatched request-id echo keeps the real status and is
+/// reported as [`CheckFailure::RequestIdMismatch`], which fails the request.
+/// With `body_assert` the body of a response with an expected status is
+/// buffered and checked; a failed check is reported as
+/// [`CheckFailure::BodyAssertion`] and takes precedence over a mismatch.
+#[expect(clippy::too_many_arguments)]
 pub(super) async fn execute_request_status(
     client: &Client,
     request: Request,
-) -> (u16, bool, bool, u64) {
-    match client.execute(request).await {
-        Ok(response) => {
-            let status = response.status().as_u16();
-            match drain_response_body(response).await {
-                Ok(bytes) => (status, false, false, bytes),
-                Err(err) => {
-                    let timed_out = err.is_timeout();
-                    (500, timed_out, !timed_out, 0)
-                }
-            }
-        }
-        Err(err) => {
-            let timed_out = err.is_timeout();
-            (500, timed_out, !timed_out, 0)
-        }
+    digest: Option<&DigestAuth>,
+    request_id_echo: Option<&RequestIdEcho>,
+    sticky: Option<&StickySession>,
+    edge: Option<&EdgeSim>,
+    body_assert: Option<&BodyAssert>,
+) -> RequestOutcome {
+    let sent_request_id = request_id_echo.and_then(|echo| echo.sent_value(&request));
+    let response = match send(client, request, digest, edge).await {
+        Ok(response) => response,
+        Err(err) => return RequestOutcome::failed(&err),
+    };
+    if let Some(sticky) = sticky {
+        sticky.observe(response.headers());
+    }
+    let echo_error =
+        request_id_echo.and_then(|echo| echo.verify(sent_request_id.as_ref(), response.headers()));
+    let status = response.status().as_u16();
+    let http_version = Some(version_label(response.version()));
+    let body_assert = body_assert.filter(|check| check.applies_to(status));
+    let (response_bytes, assert_error) = match body_assert {
+        Some(body_assert) => match read_response_body(response, edge).await {
+            Ok(body) => (
+                u64::try_from(body.len()).unwrap_or(u64::MAX),
+                body_assert.check(&body),
+            ),
+            Err(err) => return RequestOutcome::failed(&err),
+        },
+        None => match drain_response_body(response, edge).await {
+            Ok(response_bytes) => (response_bytes, None),
+            Err(err) => return RequestOutcome::failed(&err),
+        },
+    };
+    let check_failure = if assert_error.is_some() {
+        Some(CheckFailure::BodyAssertion)
+    } else {
+        mismatch_failure(echo_error.as_ref())
+    };
+    RequestOutcome {
+        status,
+        success: true,
+        timed_out: false,
+        transport_error: false,
+        response_bytes,
+        error_message: assert_error.or(echo_error),
+        http_version,
+        error_reason: None,
+        check_failure,
+    }
+}
+
+const fn mismatch_failure(echo_error: Option<&Arc<str>>) -> Option<CheckFailure> {
+    if echo_error.is_some() {
+        Some(CheckFailure::RequestIdMismatch)
+    } else {
+        None
+    }
+}
+
+/// Sends a scenario step with the session's cookies.
+///
+/// With a cookie jar the client does not follow redirects, so each hop is
+/// followed here: its `Set-Cookie` is stored and the jar's cookies are sent
+/// on the next hop. The final response is returned; a redirect past the
+/// jar's limit is returned as is.
+async fn send_step(
+    client: &Client,
+    mut request: Request,
+    session: &mut StepSession<'_>,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let base_cookie = request.headers().get(COOKIE).cloned();
+    session.apply_cookies(&mut request);
+    let max_redirects = session
+        .cookies
+        .as_deref()
+        .map_or(0, CookieJar::max_redirects);
+    let mut redirects: u32 = 0;
+    loop {
+        let previous = (redirects < max_redirects)
+            .then(|| request.try_clone())
+            .flatten();
+        let response = send(client, request, session.digest, session.edge).await?;
+        let Some(mut next) = previous
+            .and_then(|previous| redirect_request(previous, &response, base_cookie.as_ref()))
+        else {
+            return Ok(response);
+        };
+        session.store_cookies(&response);
+        session.apply_cookies(&mut next);
+        redirects = redirects.saturating_add(1);
+        request = next;
+    }
+}
+
+/// Sends through the worker's Digest handshake when Digest auth is configured,
+/// after any simulated link latency.
+async fn send(
+    client: &Client,
+    request: Request,
+    digest: Option<&DigestAuth>,
+    edge: Option<&EdgeSim>,
+) -> Result<reqwest::Response, reqwest::Error> {
+    if let Some(edge) = edge {
+        edge.delay_send().await;
+    }
+    let response = match digest {
+        Some(digest) => digest.execute(client, request).await,
+        None => client.execute(request).await,
+    };
+    if response.is_ok() {
+        mark_first_byte();
     }
+    response
 }
 
-async fn drain_response_body(response: reqwest::Response) -> Result<u64, reqwest::Error> {
+const fn version_label(version: Version) -> &'static str {
+    match version {
+        Version::HTTP_09 => "HTTP/0.9",
+        Version::HTTP_10 => "HTTP/1.0",
+        Version::HTTP_11 => "HTTP/1.1",
+        Version::HTTP_2 => "HTTP/2",
+        Version::HTTP_3 => "HTTP/3",
+        _ => "other",
+    }
+}
+
+async fn drain_response_body(
+    response: reqwest::Response,
+    edge: Option<&EdgeSim>,
+) -> Result<u64, reqwest::Error> {
     let mut stream = response.bytes_stream();
     let mut total_bytes: u64 = 0;
     while let Some(chunk) = stream.next().await {
         let bytes = chunk?;
+        if let Some(edge) = edge {
+            edge.throttle_read(bytes.len()).await;
+        }
         total_bytes = total_bytes.saturating_add(u64::try_from(bytes.len()).unwrap_or(u64::MAX));
     }
     Ok(total_bytes)
 }
 
+async fn read_response_body(
+    response: reqwest::Response,
+    edge: Option<&EdgeSim>,
+) -> Result<Vec<u8>, reqwest::Error> {
+    let mut stream = response.bytes_stream();
+    let mut body = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk?;
+        if let Some(edge) = edge {
+            edge.throttle_read(bytes.len()).await;
+        }
+        body.extend_from_slice(&bytes);
+    }
+    Ok(body)
+}
+
 async fn drain_body_contains(
     response: reqwest::Response,
     fragment: &str,
+    edge: Option<&EdgeSim>,
 ) -> Result<(bool, u64), reqwest::Error> {
     let needle = fragment.as_bytes();
     if needle.is_empty() {
-        let bytes = drain_response_body(response).await?;
+        let bytes = drain_response_body(response, edge).await?;
         return Ok((true, bytes));
     }
     let mut found = false;
@@ -131,6 +413,9 @@ async fn drain_body_contains(
     let mut total_bytes: u64 = 0;
     while let Some(chunk) = stream.next().await {
         let bytes = chunk?;
+        if let Some(edge) = edge {
+            edge.throttle_read(bytes.len()).await;
+        }
         total_bytes = total_bytes.saturating_add(u64::try_from(bytes.len()).unwrap_or(u64::MAX));
         if !found {
             let mut window = std::mem::take(&mut carry);
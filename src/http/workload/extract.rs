@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use crate::args::{ExtractSource, JsonPathSegment};
+
+/// Reads every `extract` source from a response into `vars`.
+///
+/// The body is parsed as JSON only when a JSONPath source is present. A
+/// missing header, a path that does not resolve, or a `null` value is a miss.
+///
+/// # Errors
+///
+/// Returns a message naming the first variable that could not be extracted.
+pub(in crate::http) fn extract_vars(
+    extract: &BTreeMap<String, ExtractSource>,
+    headers: &HeaderMap,
+    body: &[u8],
+    vars: &mut BTreeMap<String, String>,
+) -> Result<(), Arc<str>> {
+    let mut json: Option<Value> = None;
+    for (var, source) in extract {
+        let value = match source {
+            ExtractSource::Header(name) => headers
+                .get(name.as_str())
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned),
+            ExtractSource::JsonPath(segments) => {
+                if json.is_none() {
+                    json = serde_json::from_slice(body).ok();
+                }
+                json.as_ref()
+                    .and_then(|root| lookup(root, segments))
+                    .and_then(json_text)
+            }
+        };
+        let Some(value) = value else {
+            return Err(Arc::from(format!(
+                "extract '{}' from {} found no value",
+                var, source
+            )));
+        };
+        vars.insert(var.clone(), value);
+    }
+    Ok(())
+}
+
+pub(in crate::http) fn lookup<'value>(
+    root: &'value Value,
+    segments: &[JsonPathSegment],
+) -> Option<&'value Value> {
+    segments
+        .iter()
+        .try_fold(root, |node, segment| match segment {
+            JsonPathSegment::Key(key) => node.get(key.as_str()),
+            JsonPathSegment::Index(index) => node.get(*index),
+        })
+}
+
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        Value::Bool(_) | Value::Number(_) | Value::Array(_) | Value::Object(_) => {
+            Some(value.to_string())
+        }
+    }
+}
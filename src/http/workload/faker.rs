@@ -0,0 +1,418 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{SecondsFormat, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng, thread_rng};
+use tracing::warn;
+
+use crate::error::{AppError, AppResult, HttpError};
+
+/// Placeholder names accepted in request bodies, shown in validation errors.
+const FAKE_PLACEHOLDERS: &str =
+    "name, first_name, last_name, email, username, uuid, lorem:N, int:MIN:MAX, now, now_ms";
+const MAX_LOREM_WORDS: usize = 10_000;
+
+const FIRST_NAMES: &[&str] = &[
+    "Ada", "Alan", "Amara", "Bruno", "Chen", "Dara", "Elena", "Farid", "Grace", "Hana", "Ivan",
+    "Jonas", "Kira", "Liam", "Maya", "Nia", "Omar", "Priya", "Rosa", "Sven", "Tariq", "Yuki",
+];
+const LAST_NAMES: &[&str] = &[
+    "Abara", "Berg", "Costa", "Dubois", "Eriksen", "Fischer", "Garcia", "Hopper", "Ito", "Jensen",
+    "Kowalski", "Lovelace", "Moreau", "Nakamura", "Okafor", "Patel", "Quinn", "Rossi", "Silva",
+    "Turing", "Varga", "Weber",
+];
+const LOREM_WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FakeField {
+    Name,
+    FirstName,
+    LastName,
+    Email,
+    Username,
+    Uuid,
+    Lorem(usize),
+    Int(i64, i64),
+    Now,
+    NowMs,
+}
+
+impl FakeField {
+    /// Parses a placeholder key; `None` when the name is not a fake-data generator.
+    fn parse(key: &str) -> Option<AppResult<Self>> {
+        let (name, arg) = match key.split_once(':') {
+            Some((name, arg)) => (name.trim(), Some(arg.trim())),
+            None => (key, None),
+        };
+        let invalid = || {
+            AppError::http(HttpError::InvalidFakePlaceholder {
+                placeholder: key.to_owned(),
+            })
+        };
+        let field = match (name, arg) {
+            ("name", None) => Ok(Self::Name),
+            ("first_name", None) => Ok(Self::FirstName),
+            ("last_name", None) => Ok(Self::LastName),
+            ("email", None) => Ok(Self::Email),
+            ("username", None) => Ok(Self::Username),
+            ("uuid", None) => Ok(Self::Uuid),
+            ("now", None) => Ok(Self::Now),
+            ("now_ms", None) => Ok(Self::NowMs),
+            ("lorem", Some(words)) => words
+                .parse::<usize>()
+                .ok()
+                .filter(|words| (1..=MAX_LOREM_WORDS).contains(words))
+                .map(Self::Lorem)
+                .ok_or_else(invalid),
+            ("int", Some(range)) => range
+                .split_once(':')
+                .and_then(|(min, max)| {
+                    Some((
+                        min.trim().parse::<i64>().ok()?,
+                        max.trim().parse::<i64>().ok()?,
+                    ))
+                })
+                .filter(|(min, max)| min <= max)
+                .map(|(min, max)| Self::Int(min, max))
+                .ok_or_else(invalid),
+            (
+                "name" | "first_name" | "last_name" | "email" | "username" | "uuid" | "now"
+                | "now_ms",
+                Some(_),
+            )
+            | ("lorem" | "int", None) => Err(invalid()),
+            _ => return None,
+        };
+        Some(field)
+    }
+
+    /// Like [`FakeField::parse`], but unknown names are an error.
+    pub(super) fn parse_strict(key: &str) -> AppResult<Self> {
+        Self::parse(key).unwrap_or_else(|| {
+            Err(AppError::http(HttpError::UnknownFakePlaceholder {
+                name: key.to_owned(),
+                available: FAKE_PLACEHOLDERS,
+            }))
+        })
+    }
+
+    /// Typed JSON value: `int` and `now_ms` are numbers, everything else a string.
+    pub(super) fn value(self, rng: &mut StdRng) -> serde_json::Value {
+        match self {
+            Self::Int(min, max) => serde_json::Value::from(rng.gen_range(min..=max)),
+            Self::NowMs => serde_json::Value::from(Utc::now().timestamp_millis()),
+            Self::Name
+            | Self::FirstName
+            | Self::LastName
+            | Self::Email
+            | Self::Username
+            | Self::Uuid
+            | Self::Lorem(_)
+            | Self::Now => {
+                let mut output = String::new();
+                self.write(rng, &mut output);
+                serde_json::Value::String(output)
+            }
+        }
+    }
+
+    fn write(self, rng: &mut StdRng, output: &mut String) {
+        match self {
+            Self::Name => {
+                output.push_str(pick(rng, FIRST_NAMES));
+                output.push(' ');
+                output.push_str(pick(rng, LAST_NAMES));
+            }
+            Self::FirstName => output.push_str(pick(rng, FIRST_NAMES)),
+            Self::LastName => output.push_str(pick(rng, LAST_NAMES)),
+            Self::Email => {
+                let first = pick(rng, FIRST_NAMES).to_ascii_lowercase();
+                let last = pick(rng, LAST_NAMES).to_ascii_lowercase();
+                let suffix: u16 = rng.gen_range(1..1000);
+                output.push_str(&first);
+                output.push('.');
+                output.push_str(&last);
+                output.push_str(&suffix.to_string());
+                output.push_str("@example.com");
+            }
+            Self::Username => {
+                let first = pick(rng, FIRST_NAMES).to_ascii_lowercase();
+                let suffix: u16 = rng.gen_range(10..10_000);
+                output.push_str(&first);
+                output.push('_');
+                output.push_str(&suffix.to_string());
+            }
+            Self::Uuid => write_uuid_v4(rng, output),
+            Self::Lorem(words) => {
+                for index in 0..words {
+                    if index > 0 {
+                        output.push(' ');
+                    }
+                    output.push_str(pick(rng, LOREM_WORDS));
+                }
+            }
+            Self::Int(min, max) => output.push_str(&rng.gen_range(min..=max).to_string()),
+            Self::Now => {
+                output.push_str(&Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true));
+            }
+            Self::NowMs => output.push_str(&Utc::now().timestamp_millis().to_string()),
+        }
+    }
+}
+
+/// Appends a random RFC 4122 version 4 UUID to `output`.
+pub(in crate::http) fn write_uuid_v4<R: Rng>(rng: &mut R, output: &mut String) {
+    let mut bytes: [u8; 16] = rng.r#gen();
+    if let Some(byte) = bytes.get_mut(6) {
+        *byte = (*byte & 0x0f) | 0x40;
+    }
+    if let Some(byte) = bytes.get_mut(8) {
+        *byte = (*byte & 0x3f) | 0x80;
+    }
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    for (index, ch) in hex.chars().enumerate() {
+        if matches!(index, 8 | 12 | 16 | 20) {
+            output.push('-');
+        }
+        output.push(ch);
+    }
+}
+
+fn pick<'words>(rng: &mut StdRng, words: &[&'words str]) -> &'words str {
+    words.choose(rng).copied().unwrap_or_default()
+}
+
+/// Per-run source of fake-data RNGs.
+///
+/// Request `n` always draws from the same RNG stream for a given `--seed`,
+/// so a seeded run produces the same sequence of payloads every time.
+#[derive(Debug)]
+pub(in crate::http) struct FakeData {
+    seed: u64,
+    counter: AtomicU64,
+}
+
+impl FakeData {
+    pub(in crate::http) fn new(seed: Option<u64>) -> Self {
+        Self {
+            seed: seed.unwrap_or_else(|| thread_rng().r#gen()),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn next_rng(&self) -> StdRng {
+        let index = self.counter.fetch_add(1, Ordering::Relaxed);
+        StdRng::seed_from_u64(self.seed ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    /// Expands fake-data placeholders left over after scenario variables were applied.
+    ///
+    /// Unknown `{{...}}` keys are kept verbatim, matching `render_template`.
+    pub(in crate::http) fn expand(&self, input: &str) -> String {
+        if !input.contains("{{") {
+            return input.to_owned();
+        }
+        let mut rng = self.next_rng();
+        let mut output = String::with_capacity(input.len());
+        for piece in split_placeholders(input) {
+            match piece {
+                Piece::Text(text) => output.push_str(text),
+                Piece::Key { key, raw } => match FakeField::parse(key) {
+                    Some(Ok(field)) => field.write(&mut rng, &mut output),
+                    Some(Err(_)) | None => output.push_str(raw),
+                },
+            }
+        }
+        output
+    }
+}
+
+/// Checks that fake-data placeholders in a scenario template have valid arguments.
+///
+/// Scenario variables share the `{{...}}` syntax, so unknown names are allowed.
+pub(in crate::http) fn validate_scenario_placeholders(input: &str) -> AppResult<()> {
+    for piece in split_placeholders(input) {
+        if let Piece::Key { key, .. } = piece
+            && let Some(Err(err)) = FakeField::parse(key)
+        {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Segment {
+    Text(String),
+    Fake(FakeField),
+}
+
+/// A request body with fake-data placeholders, parsed once at startup.
+#[derive(Debug)]
+pub(in crate::http) struct FakeBody {
+    segments: Vec<Segment>,
+    data: FakeData,
+}
+
+impl FakeBody {
+    /// Parses `template`; returns `None` when it contains no fake-data
+    /// placeholders. With `literal_unknown`, `{{...}}` text with an unknown
+    /// name is sent as written and listed in a startup warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a fake-data placeholder has invalid arguments,
+    /// or when a name is unknown and `literal_unknown` is not set.
+    pub(in crate::http) fn parse(
+        template: &str,
+        seed: Option<u64>,
+        literal_unknown: bool,
+    ) -> AppResult<Option<Self>> {
+        let (segments, unknown) = parse_known_segments(template)?;
+        if !unknown.is_empty() {
+            let names = unknown
+                .iter()
+                .map(|name| format!("'{}'", name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if !literal_unknown {
+                return Err(AppError::http(HttpError::UnknownBodyPlaceholders {
+                    names,
+                    available: FAKE_PLACEHOLDERS,
+                }));
+            }
+            warn!("Sending unknown body placeholders as written: {}", names);
+        }
+        if !segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Fake(_)))
+        {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            segments,
+            data: FakeData::new(seed),
+        }))
+    }
+
+    pub(in crate::http) fn render(&self) -> String {
+        render_segments(&self.segments, &mut self.data.next_rng())
+    }
+}
+
+/// Splits `template` into text and fake fields, rejecting unknown placeholder names.
+pub(super) fn parse_segments(template: &str) -> AppResult<Vec<Segment>> {
+    let mut segments = Vec::new();
+    for piece in split_placeholders(template) {
+        match piece {
+            Piece::Text(text) => segments.push(Segment::Text(text.to_owned())),
+            Piece::Key { key, .. } => segments.push(Segment::Fake(FakeField::parse_strict(key)?)),
+        }
+    }
+    Ok(segments)
+}
+
+/// Like [`parse_segments`], but keeps `{{...}}` with an unknown name as text
+/// and returns those names, each once, alongside the segments.
+fn parse_known_segments(template: &str) -> AppResult<(Vec<Segment>, Vec<&str>)> {
+    let mut segments = Vec::new();
+    let mut unknown = Vec::new();
+    for piece in split_placeholders(template) {
+        let segment = match piece {
+            Piece::Text(text) => Segment::Text(text.to_owned()),
+            Piece::Key { key, raw } => match FakeField::parse(key) {
+                Some(field) => Segment::Fake(field?),
+                None => {
+                    if !unknown.contains(&key) {
+                        unknown.push(key);
+                    }
+                    Segment::Text(raw.to_owned())
+                }
+            },
+        };
+        segments.push(segment);
+    }
+    Ok((segments, unknown))
+}
+
+pub(super) fn render_segments(segments: &[Segment], rng: &mut StdRng) -> String {
+    let mut output = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => output.push_str(text),
+            Segment::Fake(field) => field.write(rng, &mut output),
+        }
+    }
+    output
+}
+
+enum Piece<'input> {
+    Text(&'input str),
+    /// A placeholder's trimmed name and its full `{{ ... }}` text.
+    Key {
+        key: &'input str,
+        raw: &'input str,
+    },
+}
+
+/// Splits `input` into literal text and `{{key}}` placeholders.
+fn split_placeholders(input: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        let (before, after_start) = rest.split_at(start);
+        let after = after_start.get(2..).unwrap_or_default();
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        if !before.is_empty() {
+            pieces.push(Piece::Text(before));
+        }
+        let raw_end = end.saturating_add(4);
+        pieces.push(Piece::Key {
+            key: after.get(..end).unwrap_or_default().trim(),
+            raw: after_start.get(..raw_end).unwrap_or_default(),
+        });
+        rest = after_start.get(raw_end..).unwrap_or_default();
+    }
+    if !rest.is_empty() {
+        pieces.push(Piece::Text(rest));
+    }
+    pieces
+}
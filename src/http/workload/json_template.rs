@@ -0,0 +1,100 @@
+use rand::rngs::StdRng;
+use serde_json::{Map, Value};
+
+use crate::error::{AppError, AppResult, HttpError};
+
+use super::faker::{FakeData, FakeField, Segment, parse_segments, render_segments};
+
+/// A JSON body template whose placeholders keep their JSON type.
+///
+/// The template is parsed once; each request walks the tree and substitutes
+/// fresh values. A string that is exactly one placeholder (`"{{int:1:100}}"`)
+/// becomes a typed value, so numbers stay numbers. Placeholders embedded in
+/// longer strings are substituted as text. Object keys are emitted in sorted
+/// order.
+#[derive(Debug)]
+pub(in crate::http) struct JsonBody {
+    root: JsonNode,
+    data: FakeData,
+}
+
+#[derive(Debug)]
+enum JsonNode {
+    Literal(Value),
+    Field(FakeField),
+    Text(Vec<Segment>),
+    Array(Vec<JsonNode>),
+    Object(Vec<(String, JsonNode)>),
+}
+
+impl JsonBody {
+    /// Parses and compiles a JSON template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the template is not valid JSON or uses an unknown
+    /// or malformed placeholder.
+    pub(in crate::http) fn parse(template: &str, seed: Option<u64>) -> AppResult<Self> {
+        let value: Value = serde_json::from_str(template)
+            .map_err(|err| AppError::http(HttpError::InvalidJsonTemplate { source: err }))?;
+        Ok(Self {
+            root: compile(value)?,
+            data: FakeData::new(seed),
+        })
+    }
+
+    /// Renders one request body with fresh placeholder values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the rendered value cannot be serialized; the
+    /// request then fails instead of going out with an empty body.
+    pub(in crate::http) fn render(&self) -> AppResult<String> {
+        let value = render_node(&self.root, &mut self.data.next_rng());
+        serde_json::to_string(&value)
+            .map_err(|err| AppError::http(HttpError::RenderJsonTemplate { source: err }))
+    }
+}
+
+fn compile(value: Value) -> AppResult<JsonNode> {
+    match value {
+        Value::String(text) => compile_string(&text),
+        Value::Array(items) => Ok(JsonNode::Array(
+            items.into_iter().map(compile).collect::<AppResult<_>>()?,
+        )),
+        Value::Object(fields) => Ok(JsonNode::Object(
+            fields
+                .into_iter()
+                .map(|(key, field)| Ok((key, compile(field)?)))
+                .collect::<AppResult<_>>()?,
+        )),
+        Value::Null | Value::Bool(_) | Value::Number(_) => Ok(JsonNode::Literal(value)),
+    }
+}
+
+fn compile_string(text: &str) -> AppResult<JsonNode> {
+    let segments = parse_segments(text)?;
+    match segments.as_slice() {
+        [Segment::Fake(field)] => Ok(JsonNode::Field(*field)),
+        [] | [Segment::Text(_)] => Ok(JsonNode::Literal(Value::String(text.to_owned()))),
+        _ => Ok(JsonNode::Text(segments)),
+    }
+}
+
+fn render_node(node: &JsonNode, rng: &mut StdRng) -> Value {
+    match node {
+        JsonNode::Literal(value) => value.clone(),
+        JsonNode::Field(field) => field.value(rng),
+        JsonNode::Text(segments) => Value::String(render_segments(segments, rng)),
+        JsonNode::Array(items) => {
+            Value::Array(items.iter().map(|item| render_node(item, rng)).collect())
+        }
+        JsonNode::Object(fields) => {
+            let mut map = Map::with_capacity(fields.len());
+            for (key, value) in fields {
+                map.insert(key.clone(), render_node(value, rng));
+            }
+            Value::Object(map)
+        }
+    }
+}
@@ -1,19 +1,31 @@
+use std::collections::BTreeMap;
 use std::sync::{Arc, atomic::Ordering};
+use std::time::Duration;
 
+use rand::{Rng, thread_rng};
 use reqwest::{Client, Request};
 use tokio::time::{Instant, sleep};
 use tracing::error;
 
 use crate::{
+    args::{ExpectedStatus, ThinkTime},
     error::{AppError, AppResult, HttpError},
     metrics::Metrics,
     shutdown::ShutdownReceiver,
 };
 
-use super::builders::{StepRequestContext, build_request_from_spec, build_step_request};
-use super::data::{ScenarioRunContext, SingleRequestSpec, WorkerContext, Workload};
-use super::execution::{execute_request, execute_request_status, execute_request_with_asserts};
-use super::runner_common::{InflightGuard, prepare_iteration, run_and_record};
+use super::super::multiplex::StreamGroup;
+use super::builders::{
+    StepRequestContext, build_request_from_spec, build_step_request, connect_to_client,
+};
+use super::data::{ScenarioRunContext, SingleRequestSpec, StepSession, WorkerContext, Workload};
+use super::execution::{
+    RequestOutcome, execute_request, execute_request_status, execute_request_with_asserts,
+    execute_request_with_extract,
+};
+use super::runner_common::{
+    InflightGuard, prepare_iteration, request_body_bytes, run_and_record, throttle_bandwidth,
+};
 use super::template::{build_template_vars, step_label};
 
 /// Synthetic status used when an assert fails before a real HTTP response.
@@ -23,31 +35,70 @@ pub(in crate::http) async fn preflight_request(
     client: &Client,
     workload: &Workload,
 ) -> AppResult<()> {
-    match workload {
-        Workload::Single(request_template) => {
-            let request = request_template
-                .try_clone()
-                .ok_or_else(|| AppError::http(HttpError::CloneRequestFailed))?;
-            execute_request(client, request, true)
-                .await
-                .map_err(|err| AppError::http(HttpError::TestRequestFailed { source: err }))?;
-            Ok(())
+    let (request, mapping) = build_probe_request(client, workload)?;
+    let client = workload.connect_to_client(mapping).unwrap_or(client);
+    let result = execute_request(client, request, true).await;
+    match (workload, result) {
+        (_, Ok(_)) => Ok(()),
+        (Workload::Scenario(..), Err(err)) => {
+            Err(AppError::http(HttpError::ScenarioPreflightFailed {
+                source: Box::new(AppError::from(err)),
+            }))
+        }
+        (Workload::Single(_) | Workload::SingleDynamic(_), Err(err)) => {
+            Err(AppError::http(HttpError::TestRequestFailed { source: err }))
         }
+    }
+}
+
+/// Sends one probe request and measures the time until response headers arrive.
+pub(in crate::http) async fn probe_first_byte(
+    client: &Client,
+    workload: &Workload,
+) -> AppResult<(u16, Duration)> {
+    let (request, mapping) = build_probe_request(client, workload)?;
+    let client = workload.connect_to_client(mapping).unwrap_or(client);
+    let started = Instant::now();
+    let response = client
+        .execute(request)
+        .await
+        .map_err(|err| AppError::http(HttpError::TestRequestFailed { source: err }))?;
+    Ok((response.status().as_u16(), started.elapsed()))
+}
+
+fn build_probe_request(
+    client: &Client,
+    workload: &Workload,
+) -> AppResult<(Request, Option<usize>)> {
+    match workload {
+        Workload::Single(request_template) => request_template
+            .try_clone()
+            .map(|request| (request, None))
+            .ok_or_else(|| AppError::http(HttpError::CloneRequestFailed)),
         Workload::SingleDynamic(spec) => {
-            let request = build_request_from_spec(client, spec)?;
-            execute_request(client, request, true)
-                .await
-                .map_err(|err| AppError::http(HttpError::TestRequestFailed { source: err }))?;
-            Ok(())
+            let (mut request, mapping) = build_request_from_spec(client, spec)?;
+            if let Some(generated) = spec.generated.as_deref() {
+                generated.apply(&mut request);
+            }
+            Ok((request, mapping))
         }
-        Workload::Scenario(scenario, connect_to, host_header, auth) => {
+        Workload::Scenario(
+            scenario,
+            connect_to,
+            host_header,
+            auth,
+            version,
+            fake,
+            step_clients,
+            _,
+        ) => {
             let step = scenario
                 .steps
                 .first()
                 .ok_or_else(|| AppError::http(HttpError::ScenarioHasNoSteps))?;
             let vars = build_template_vars(scenario, step, 0, 0);
-            let request = build_step_request(
-                client,
+            build_step_request(
+                step_client(step_clients, 0).unwrap_or(client),
                 scenario,
                 step,
                 &vars,
@@ -55,16 +106,10 @@ pub(in crate::http) async fn preflight_request(
                     connect_to,
                     host_header: host_header.as_deref(),
                     auth: auth.as_ref(),
+                    version: *version,
+                    fake: Some(fake),
                 },
-            )?;
-            execute_request(client, request, true)
-                .await
-                .map_err(|err| {
-                    AppError::http(HttpError::ScenarioPreflightFailed {
-                        source: Box::new(AppError::from(err)),
-                    })
-                })?;
-            Ok(())
+            )
         }
     }
 }
@@ -87,17 +132,56 @@ pub(in crate::http) async fn run_single_iteration(
         return true;
     };
 
+    let request_bytes = request_body_bytes(request_template);
+    let url = context
+        .record_urls
+        .then(|| Arc::from(request_template.url().as_str()));
     let run_request = async {
         match request_template.try_clone() {
-            Some(req_clone) => execute_request_status(context.client, req_clone).await,
+            Some(mut req_clone) => {
+                if let Some(deadline) = context.deadline_header {
+                    deadline.apply(&mut req_clone, latency_start);
+                }
+                if let Some(sticky) = context.sticky {
+                    sticky.apply(&mut req_clone);
+                }
+                execute_request_status(
+                    context.client,
+                    req_clone,
+                    context.digest,
+                    context.request_id_echo,
+                    context.sticky,
+                    context.edge_sim,
+                    context.body_assert,
+                )
+                .await
+            }
             None => {
                 error!("Failed to clone request template.");
-                (500, false, true, 0)
+                RequestOutcome {
+                    status: 500,
+                    success: false,
+                    timed_out: false,
+                    transport_error: true,
+                    response_bytes: 0,
+                    error_message: Some(Arc::from("failed to clone request template")),
+                    http_version: None,
+                    error_reason: None,
+                    check_failure: None,
+                }
             }
         }
     };
 
-    run_and_record(shutdown_rx, context, latency_start, run_request).await
+    run_and_record(
+        shutdown_rx,
+        context,
+        latency_start,
+        request_bytes,
+        url,
+        run_request,
+    )
+    .await
 }
 
 pub(in crate::http) async fn run_single_dynamic_iteration(
@@ -118,19 +202,53 @@ pub(in crate::http) async fn run_single_dynamic_iteration(
         return true;
     };
 
-    let request = match build_request_from_spec(context.client, spec) {
-        Ok(request) => request,
+    let (mut request, mapping) = match build_request_from_spec(context.client, spec) {
+        Ok(built) => built,
         Err(err) => {
             error!("Failed to build request: {}", err);
             return true;
         }
     };
+    if let Some(deadline) = context.deadline_header {
+        deadline.apply(&mut request, latency_start);
+    }
+    if let Some(sticky) = context.sticky {
+        sticky.apply(&mut request);
+    }
+    let generated = spec
+        .generated
+        .as_deref()
+        .map(|generated| (generated, generated.apply(&mut request)));
 
+    let request_bytes = request_body_bytes(&request);
+    let url = context
+        .record_urls
+        .then(|| Arc::from(request.url().as_str()));
+    let client = connect_to_client(&spec.connect_to_clients, mapping).unwrap_or(context.client);
+    let run_request = async {
+        let start = latency_start.unwrap_or_else(Instant::now);
+        let outcome = execute_request_status(
+            client,
+            request,
+            context.digest,
+            context.request_id_echo,
+            context.sticky,
+            context.edge_sim,
+            context.body_assert,
+        )
+        .await;
+        if let Some((generated, ids)) = generated.as_ref() {
+            generated.record(ids, outcome.status, outcome.success, start.elapsed());
+        }
+        outcome
+    };
     run_and_record(
         shutdown_rx,
         context,
         latency_start,
-        execute_request_status(context.client, request),
+        request_bytes,
+        url,
+        run_request,
     )
     .await
 }
@@ -140,6 +258,17 @@ pub(in crate::http) async fn run_scenario_iteration(
     worker: &WorkerContext<'_>,
     context: &mut ScenarioRunContext<'_>,
 ) -> bool {
+    let mut cookies = context.cookies.as_deref_mut();
+    if let Some(jar) = cookies.as_deref_mut() {
+        jar.clear();
+    }
+    let mut session = StepSession {
+        vars: BTreeMap::new(),
+        cookies,
+        digest: worker.digest,
+        sticky: worker.sticky,
+        edge: worker.edge_sim,
+    };
     for (step_index, step) in context.scenario.steps.iter().enumerate() {
         let Some(latency_start) = prepare_iteration(
             shutdown_rx,
@@ -154,9 +283,17 @@ pub(in crate::http) async fn run_scenario_iteration(
             return true;
         };
 
-        let vars = build_template_vars(context.scenario, step, *context.request_seq, step_index);
-        let request = match build_step_request(
-            context.client,
+        let mut vars =
+            build_template_vars(context.scenario, step, *context.request_seq, step_index);
+        vars.extend(
+            session
+                .vars
+                .iter()
+                .map(|(key, value): (&String, &String)| (key.clone(), value.clone())),
+        );
+        let identity_client = step_client(context.step_clients, step_index);
+        let (mut request, mapping) = match build_step_request(
+            identity_client.unwrap_or(context.client),
             context.scenario,
             step,
             &vars,
@@ -164,27 +301,59 @@ pub(in crate::http) async fn run_scenario_iteration(
                 connect_to: context.connect_to,
                 host_header: context.host_header,
                 auth: context.auth,
+                version: context.version,
+                fake: Some(context.fake),
             },
         ) {
-            Ok(request) => request,
+            Ok(built) => built,
             Err(err) => {
                 error!("Failed to build scenario request: {}", err);
                 return true;
             }
         };
+        // A step's own client identity wins over a `--connect-to` CA bundle.
+        let client = identity_client
+            .or_else(|| connect_to_client(context.connect_to_clients, mapping))
+            .unwrap_or(context.client);
+        if let Some(deadline) = worker.deadline_header {
+            deadline.apply(&mut request, latency_start);
+        }
 
-        let expected = step.assert_status.unwrap_or(context.expected_status_code);
+        if throttle_bandwidth(shutdown_rx, worker, request_body_bytes(&request)).await {
+            return true;
+        }
+        let url = worker
+            .record_urls
+            .then(|| Arc::from(request.url().as_str()));
+
+        let expected = step
+            .assert_status
+            .map_or(context.expected_status_code, ExpectedStatus::single);
         let start = latency_start.unwrap_or_else(Instant::now);
         let in_flight_guard = InflightGuard::acquire(worker.in_flight_counter);
+        let stream_guard = worker.stream_group.map(StreamGroup::begin);
         let run_request = async {
-            execute_request_with_asserts(
-                context.client,
-                request,
-                context.expected_status_code,
-                step.assert_status,
-                step.assert_body_contains.as_deref(),
-            )
-            .await
+            if step.extract.is_empty() {
+                execute_request_with_asserts(
+                    client,
+                    request,
+                    context.expected_status_code,
+                    step,
+                    &mut session,
+                    worker.request_id_echo,
+                )
+                .await
+            } else {
+                execute_request_with_extract(
+                    client,
+                    request,
+                    context.expected_status_code,
+                    step,
+                    &mut session,
+                    worker.request_id_echo,
+                )
+                .await
+            }
         };
         let outcome = if worker.wait_ongoing {
             run_request.await
@@ -194,11 +363,15 @@ pub(in crate::http) async fn run_scenario_iteration(
                 result = run_request => result,
             }
         };
+        drop(stream_guard);
         drop(in_flight_guard);
+        let outcome = outcome.check_response_size(worker.response_size);
 
         if !outcome.success {
             let label = step_label(step, step_index);
-            if let Some(fragment) = step.assert_body_contains.as_deref() {
+            if let Some(message) = outcome.error_message.as_deref() {
+                error!("Scenario step {} failed: {}.", label, message);
+            } else if let Some(fragment) = step.assert_body_contains.as_deref() {
                 error!(
                     "Scenario step {} failed: status {} (expected {}) or body missing '{}'.",
                     label, outcome.status, expected, fragment
@@ -212,7 +385,7 @@ pub(in crate::http) async fn run_scenario_iteration(
         }
 
         let metric_status = if outcome.success {
-            context.expected_status_code
+            context.expected_status_code.first()
         } else {
             ASSERT_FAILED_STATUS
         };
@@ -224,25 +397,53 @@ pub(in crate::http) async fn run_scenario_iteration(
             outcome.transport_error,
             outcome.response_bytes,
             in_flight_ops,
-        );
+        )
+        .with_error_message(outcome.error_message)
+        .with_http_version(outcome.http_version)
+        .with_error_reason(outcome.error_reason)
+        .with_check_failure(outcome.check_failure)
+        .with_url(url);
         if let Some(log_sink) = context.log_sink
-            && !log_sink.send(metric)
+            && !log_sink.send(metric.clone())
         {
             return true;
         }
         if context.metrics_tx.try_send(metric).is_err() {
             // Ignore UI backpressure; summary and charts use log pipeline.
         }
+        if throttle_bandwidth(shutdown_rx, worker, outcome.response_bytes).await {
+            return true;
+        }
 
         *context.request_seq = context.request_seq.saturating_add(1);
 
+        // Later steps depend on extracted values; end the iteration without them.
+        if !step.extract.is_empty() && (outcome.transport_error || outcome.timed_out) {
+            return false;
+        }
+
         if let Some(think_time) = step.think_time {
             tokio::select! {
                 _ = shutdown_rx.recv() => return true,
-                () = sleep(think_time) => {},
+                () = sleep(think_time_pause(think_time)) => {},
             };
         }
     }
 
     false
 }
+
+/// Draws the pause after a step; it runs after the step's metric is recorded,
+/// so it counts toward neither latency nor the request timeout.
+fn think_time_pause(think_time: ThinkTime) -> Duration {
+    if think_time.max > think_time.min {
+        thread_rng().gen_range(think_time.min..=think_time.max)
+    } else {
+        think_time.min
+    }
+}
+
+/// Returns the client for a step that overrides the TLS client identity.
+fn step_client(step_clients: &[Option<Client>], step_index: usize) -> Option<&Client> {
+    step_clients.get(step_index).and_then(Option::as_ref)
+}
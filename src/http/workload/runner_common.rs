@@ -3,6 +3,7 @@ use std::sync::{
     atomic::{AtomicU64, Ordering},
 };
 
+use reqwest::Request;
 use tokio::{sync::Semaphore, time::Instant};
 
 use crate::{
@@ -10,7 +11,10 @@ use crate::{
     shutdown::{ShutdownReceiver, ShutdownSender},
 };
 
+use super::super::conn_timing::traced;
+use super::super::multiplex::StreamGroup;
 use super::data::{RequestLimiter, WorkerContext};
+use super::execution::RequestOutcome;
 
 pub(super) struct InflightGuard<'counter> {
     counter: &'counter AtomicU64,
@@ -47,11 +51,24 @@ pub(super) async fn run_and_record(
     shutdown_rx: &mut ShutdownReceiver,
     worker: &WorkerContext<'_>,
     latency_start: Option<Instant>,
-    run_request: impl std::future::Future<Output = (u16, bool, bool, u64)>,
+    request_bytes: u64,
+    url: Option<Arc<str>>,
+    run_request: impl std::future::Future<Output = RequestOutcome>,
 ) -> bool {
+    if throttle_bandwidth(shutdown_rx, worker, request_bytes).await {
+        return true;
+    }
     let start = latency_start.unwrap_or_else(Instant::now);
     let in_flight_guard = InflightGuard::acquire(worker.in_flight_counter);
-    let (status, timed_out, transport_error, response_bytes) = if worker.wait_ongoing {
+    let stream_guard = worker.stream_group.map(StreamGroup::begin);
+    let run_request = async {
+        if worker.connection_timing {
+            traced(run_request).await
+        } else {
+            (run_request.await, None)
+        }
+    };
+    let (outcome, connection) = if worker.wait_ongoing {
         run_request.await
     } else {
         tokio::select! {
@@ -59,26 +76,57 @@ pub(super) async fn run_and_record(
             result = run_request => result,
         }
     };
+    drop(stream_guard);
     drop(in_flight_guard);
+    let outcome = outcome.check_response_size(worker.response_size);
 
     let in_flight_ops = worker.in_flight_counter.load(Ordering::Relaxed);
     let metric = Metrics::new(
         start,
-        status,
-        timed_out,
-        transport_error,
-        response_bytes,
+        outcome.status,
+        outcome.timed_out,
+        outcome.transport_error,
+        outcome.response_bytes,
         in_flight_ops,
-    );
+    )
+    .with_error_message(outcome.error_message)
+    .with_http_version(outcome.http_version)
+    .with_error_reason(outcome.error_reason)
+    .with_check_failure(outcome.check_failure)
+    .with_url(url)
+    .with_connection(connection);
     if let Some(log_sink) = worker.log_sink
-        && !log_sink.send(metric)
+        && !log_sink.send(metric.clone())
     {
         return true;
     }
     if worker.metrics_tx.try_send(metric).is_err() {
         // Ignore UI backpressure; summary and charts use log pipeline.
     }
-    false
+    throttle_bandwidth(shutdown_rx, worker, outcome.response_bytes).await
+}
+
+/// Charges `bytes` against the shared bandwidth budget, waiting when it is exhausted.
+/// Returns `true` when shutdown was received while waiting.
+pub(super) async fn throttle_bandwidth(
+    shutdown_rx: &mut ShutdownReceiver,
+    worker: &WorkerContext<'_>,
+    bytes: u64,
+) -> bool {
+    let Some(limiter) = worker.bandwidth_limiter else {
+        return false;
+    };
+    tokio::select! {
+        _ = shutdown_rx.recv() => true,
+        () = limiter.consume(bytes) => false,
+    }
+}
+
+pub(super) fn request_body_bytes(request: &Request) -> u64 {
+    request
+        .body()
+        .and_then(reqwest::Body::as_bytes)
+        .map_or(0, |bytes| u64::try_from(bytes.len()).unwrap_or(u64::MAX))
 }
 
 pub(super) async fn prepare_iteration(
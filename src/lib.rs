@@ -12,6 +12,7 @@ pub mod error;
 pub mod http;
 pub mod metrics;
 pub mod protocol;
+pub mod run_context;
 mod shutdown;
 pub mod sinks;
 pub mod ui;
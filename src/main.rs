@@ -11,6 +11,7 @@ mod error;
 mod http;
 mod metrics;
 mod protocol;
+mod run_context;
 mod script;
 mod service;
 mod shutdown;
@@ -26,8 +27,6 @@ mod wasm_runtime;
 #[global_allocator]
 static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 
-use error::AppResult;
-
-fn main() -> AppResult<()> {
+fn main() -> std::process::ExitCode {
     entry::run()
 }
@@ -0,0 +1,102 @@
+use crate::args::Percentage;
+
+/// Aborts the run once cumulative errors reach `--abort-on-errors` or the
+/// error rate exceeds `--abort-on-error-rate`.
+pub(in crate::metrics::collector) struct ErrorBreaker {
+    max_errors: Option<u64>,
+    max_rate_x100: Option<u64>,
+    /// Requests seen before the rate check applies, so one early error does
+    /// not trip it.
+    min_requests: u64,
+}
+
+impl ErrorBreaker {
+    /// Returns `None` when neither threshold is set.
+    pub(in crate::metrics::collector) fn new(
+        max_errors: Option<u64>,
+        max_rate: Option<Percentage>,
+        min_requests: u64,
+    ) -> Option<Self> {
+        (max_errors.is_some() || max_rate.is_some()).then_some(Self {
+            max_errors,
+            max_rate_x100: max_rate.map(Percentage::x100),
+            min_requests,
+        })
+    }
+
+    /// Returns the abort reason when a threshold is breached.
+    pub(in crate::metrics::collector) fn check(
+        &self,
+        total_requests: u64,
+        successful_requests: u64,
+    ) -> Option<String> {
+        let errors = total_requests.saturating_sub(successful_requests);
+        if let Some(max_errors) = self.max_errors
+            && errors >= max_errors
+        {
+            return Some(format!(
+                "{} errors reached --abort-on-errors {}",
+                errors, max_errors
+            ));
+        }
+        let max_rate_x100 = self.max_rate_x100?;
+        if total_requests < self.min_requests {
+            return None;
+        }
+        let rate_x100 = u128::from(errors)
+            .saturating_mul(10_000)
+            .checked_div(u128::from(total_requests))
+            .map_or(0, |rate| u64::try_from(rate).unwrap_or(u64::MAX));
+        (rate_x100 > max_rate_x100).then(|| {
+            format!(
+                "error rate {}.{:02}% exceeded --abort-on-error-rate {}.{:02}% after {} requests",
+                rate_x100 / 100,
+                rate_x100 % 100,
+                max_rate_x100 / 100,
+                max_rate_x100 % 100,
+                total_requests
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AppError, AppResult};
+
+    #[test]
+    fn error_breaker_trips_on_count_and_guarded_rate() -> AppResult<()> {
+        if ErrorBreaker::new(None, None, 100).is_some() {
+            return Err(AppError::validation(
+                "Expected no breaker without thresholds",
+            ));
+        }
+        let breaker = ErrorBreaker::new(Some(50), Some("5".parse()?), 100)
+            .ok_or_else(|| AppError::validation("Expected a breaker"))?;
+
+        let results = [
+            breaker.check(1, 0),
+            breaker.check(99, 90),
+            breaker.check(100, 95),
+            breaker.check(100, 94),
+            breaker.check(10_000, 9_950),
+        ];
+        let expected = [
+            None,
+            None,
+            None,
+            Some("error rate 6.00% exceeded --abort-on-error-rate 5.00% after 100 requests"),
+            Some("50 errors reached --abort-on-errors 50"),
+        ];
+        for (result, want) in results.iter().zip(expected) {
+            if result.as_deref() != want {
+                return Err(AppError::validation(format!(
+                    "Unexpected breaker result: {:?}, expected {:?}",
+                    result, want
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use tokio::time::Instant;
+
+use super::super::super::{DrainReport, Metrics};
+
+/// Classifies the metrics that arrive after the deadline while
+/// `--wait-ongoing-requests-after-deadline` lets in-flight requests finish.
+pub(in crate::metrics::collector) struct DrainTracker {
+    deadline: Instant,
+    in_flight_at_deadline: u64,
+    late_completions: u64,
+    late_timeouts: u64,
+}
+
+impl DrainTracker {
+    /// `in_flight_at_deadline` is the in-flight gauge of the last metric seen
+    /// before the deadline, so it is an estimate.
+    pub(in crate::metrics::collector) const fn new(
+        deadline: Instant,
+        in_flight_at_deadline: u64,
+    ) -> Self {
+        Self {
+            deadline,
+            in_flight_at_deadline,
+            late_completions: 0,
+            late_timeouts: 0,
+        }
+    }
+
+    /// Counts requests that were in flight at the deadline: started before it
+    /// and finished after it. Queued metrics of requests that finished in time
+    /// are not late.
+    pub(in crate::metrics::collector) fn record(&mut self, msg: &Metrics) {
+        let finished_in_time = msg
+            .start
+            .checked_add(msg.response_time)
+            .is_some_and(|finished| finished <= self.deadline);
+        if msg.start >= self.deadline || finished_in_time {
+            return;
+        }
+        let counter = if msg.timed_out {
+            &mut self.late_timeouts
+        } else {
+            &mut self.late_completions
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Requests that timed out during the drain or never reported count as
+    /// abandoned.
+    pub(in crate::metrics::collector) fn finish(self) -> DrainReport {
+        let in_flight_at_deadline = self
+            .in_flight_at_deadline
+            .max(self.late_completions.saturating_add(self.late_timeouts));
+        DrainReport {
+            in_flight_at_deadline,
+            late_completions: self.late_completions,
+            abandoned: in_flight_at_deadline.saturating_sub(self.late_completions),
+        }
+    }
+}
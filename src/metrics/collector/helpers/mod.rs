@@ -1,12 +1,21 @@
+mod breaker;
+mod drain;
 mod processing;
+mod slowest;
+mod spike;
 mod summary;
 mod windows;
 
+pub(in crate::metrics::collector) use breaker::ErrorBreaker;
+pub(in crate::metrics::collector) use drain::DrainTracker;
 pub(in crate::metrics::collector) use processing::process_metric_ui;
+pub(in crate::metrics::collector) use slowest::SlowestTracker;
+pub(in crate::metrics::collector) use spike::SpikeTrigger;
 pub(in crate::metrics::collector) use summary::{
-    build_sink_stats, build_stream_snapshot, resolve_sink_interval, resolve_stream_interval,
+    build_resume_state, build_sink_stats, build_stream_snapshot, resolve_checkpoint_interval,
+    resolve_sink_interval, resolve_stream_interval,
 };
 pub(in crate::metrics::collector) use windows::{
-    compute_percentiles, prune_bytes_window, prune_latency_window, prune_rps_window,
-    record_bytes_sample, record_rps_sample,
+    compute_percentiles, compute_percentiles_extended, prune_bytes_window, prune_latency_window,
+    prune_rps_window, record_bytes_sample, record_rps_sample,
 };
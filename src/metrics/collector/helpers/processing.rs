@@ -3,16 +3,20 @@ use std::time::Duration;
 
 use tokio::time::Instant;
 
-use crate::ui::model::StatusCounts;
+use crate::args::ExpectedStatus;
+use crate::ui::model::{ErrorSample, StatusCounts};
 
 use super::super::super::Metrics;
 use super::super::state::UiAggregationState;
 use super::windows::{prune_bytes_window, prune_latency_window, prune_rps_window};
 
+/// Distinct error messages kept for the TUI error panel.
+pub(in crate::metrics::collector) const ERROR_SAMPLE_LIMIT: usize = 5;
+
 pub(in crate::metrics::collector) fn process_metric_ui(
-    msg: Metrics,
+    msg: &Metrics,
     now: Instant,
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     state: &mut UiAggregationState,
 ) {
     let status_code = msg.status_code;
@@ -21,8 +25,7 @@ pub(in crate::metrics::collector) fn process_metric_ui(
 
     state.current_requests = state.current_requests.saturating_add(1);
 
-    let is_success = status_code == expected_status_code && !msg.timed_out && !msg.transport_error;
-    if is_success {
+    if msg.is_success(expected_status_code) {
         state.successful_requests = state.successful_requests.saturating_add(1);
         state.success_latency_sum_ms = state
             .success_latency_sum_ms
@@ -43,10 +46,39 @@ pub(in crate::metrics::collector) fn process_metric_ui(
         }
     } else if msg.timed_out {
         state.timeout_requests = state.timeout_requests.saturating_add(1);
+        let message = msg.error_message.as_deref().unwrap_or("request timed out");
+        record_error_sample(&mut state.error_samples, message);
     } else if msg.transport_error {
         state.transport_errors = state.transport_errors.saturating_add(1);
-    } else if status_code != expected_status_code {
+        let message = msg.error_message.as_deref().unwrap_or("transport error");
+        record_error_sample(&mut state.error_samples, message);
+    } else if !expected_status_code.contains(status_code) {
         state.non_expected_status = state.non_expected_status.saturating_add(1);
+        match msg.error_message.as_deref() {
+            Some(message) => record_error_sample(&mut state.error_samples, message),
+            None => record_error_sample(&mut state.error_samples, &status_line(status_code)),
+        }
+    } else if let Some(failure) = msg.check_failure {
+        let message = msg
+            .error_message
+            .as_deref()
+            .unwrap_or_else(|| failure.label());
+        record_error_sample(&mut state.error_samples, message);
+    }
+
+    if let Some(version) = msg.http_version {
+        let count = state.http_versions.entry(version).or_insert(0);
+        *count = count.saturating_add(1);
+    }
+    if let Some(reason) = msg.error_reason {
+        let count = state.error_reasons.entry(reason.label()).or_insert(0);
+        *count = count.saturating_add(1);
+    }
+    if let Some(failure) = msg.check_failure {
+        state.check_failures.record(failure);
+    }
+    if let Some(timing) = msg.connection.as_ref() {
+        state.connection_split.record(timing, msg.response_time);
     }
 
     increment_status_counts(
@@ -67,6 +99,9 @@ pub(in crate::metrics::collector) fn process_metric_ui(
     prune_bytes_window(&mut state.bytes_window, now);
 
     state.latency_sum_ms = state.latency_sum_ms.saturating_add(u128::from(latency_ms));
+    if let Some(slowest) = state.slowest.as_mut() {
+        slowest.record(msg, latency_ms);
+    }
     if latency_ms < state.min_latency_ms {
         state.min_latency_ms = latency_ms;
     }
@@ -82,6 +117,40 @@ pub(in crate::metrics::collector) fn process_metric_ui(
     }
 }
 
+/// Moves `message` to the front of the sample list, bumping its count, and
+/// drops the least recently seen message once the list is full.
+pub(in crate::metrics::collector) fn record_error_sample(
+    samples: &mut VecDeque<ErrorSample>,
+    message: &str,
+) {
+    let existing = samples
+        .iter()
+        .position(|sample| sample.message == message)
+        .and_then(|index| samples.remove(index));
+    let sample = existing.map_or_else(
+        || ErrorSample {
+            message: message.to_owned(),
+            count: 1,
+        },
+        |sample| ErrorSample {
+            count: sample.count.saturating_add(1),
+            ..sample
+        },
+    );
+    samples.push_front(sample);
+    samples.truncate(ERROR_SAMPLE_LIMIT);
+}
+
+fn status_line(status_code: u16) -> String {
+    reqwest::StatusCode::from_u16(status_code)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .map_or_else(
+            || format!("HTTP {}", status_code),
+            |reason| format!("HTTP {} {}", status_code, reason),
+        )
+}
+
 fn record_rps(window: &mut VecDeque<(Instant, u64)>, now: Instant) {
     if let Some((ts, count)) = window.back_mut() {
         if now.duration_since(*ts) < Duration::from_millis(100) {
@@ -0,0 +1,128 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use super::super::super::{Metrics, SlowRequest};
+
+/// Keeps the `limit` highest-latency requests in a min-heap, so each metric
+/// costs at most one `O(log limit)` push/pop and memory stays bounded.
+pub(in crate::metrics::collector) struct SlowestTracker {
+    limit: usize,
+    seq: u64,
+    heap: BinaryHeap<Reverse<SlowEntry>>,
+}
+
+struct SlowEntry {
+    latency_ms: u64,
+    /// Arrival order; among equal latencies the earliest request is kept.
+    seq: Reverse<u64>,
+    request: SlowRequest,
+}
+
+impl SlowEntry {
+    const fn key(&self) -> (u64, Reverse<u64>) {
+        (self.latency_ms, self.seq)
+    }
+}
+
+impl PartialEq for SlowEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SlowEntry {}
+
+impl PartialOrd for SlowEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SlowEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl SlowestTracker {
+    pub(in crate::metrics::collector) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            seq: 0,
+            heap: BinaryHeap::with_capacity(limit),
+        }
+    }
+
+    pub(in crate::metrics::collector) fn record(&mut self, msg: &Metrics, latency_ms: u64) {
+        let seq = Reverse(self.seq);
+        self.seq = self.seq.saturating_add(1);
+        if self.heap.len() >= self.limit {
+            let Some(Reverse(fastest)) = self.heap.peek() else {
+                return;
+            };
+            if (latency_ms, seq) <= fastest.key() {
+                return;
+            }
+            self.heap.pop();
+        }
+        self.heap.push(Reverse(SlowEntry {
+            latency_ms,
+            seq,
+            request: SlowRequest {
+                latency_ms,
+                status_code: msg.status_code,
+                timed_out: msg.timed_out,
+                transport_error: msg.transport_error,
+                url: msg.url.clone(),
+            },
+        }));
+    }
+
+    /// Slowest first.
+    pub(in crate::metrics::collector) fn into_sorted(self) -> Vec<SlowRequest> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(entry)| entry.request)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::error::{AppError, AppResult};
+
+    fn metric(url: &str) -> Metrics {
+        Metrics::new(Instant::now(), 200, false, false, 0, 0).with_url(Some(Arc::from(url)))
+    }
+
+    #[test]
+    fn slowest_tracker_keeps_top_latencies_in_order() -> AppResult<()> {
+        let mut tracker = SlowestTracker::new(3);
+        for (index, latency_ms) in [5_u64, 40, 1, 40, 90, 12, 3].into_iter().enumerate() {
+            tracker.record(&metric(&format!("/r{}", index)), latency_ms);
+        }
+        let slowest: Vec<(u64, Option<Arc<str>>)> = tracker
+            .into_sorted()
+            .into_iter()
+            .map(|entry| (entry.latency_ms, entry.url))
+            .collect();
+        let expected = vec![
+            (90, Some(Arc::from("/r4"))),
+            (40, Some(Arc::from("/r1"))),
+            (40, Some(Arc::from("/r3"))),
+        ];
+        if slowest != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected slowest requests: {:?}",
+                slowest
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use super::super::super::{LatencySpike, Metrics};
+
+/// Flags requests whose latency or in-flight count reaches the
+/// `--alloc-profiler-spike-*` thresholds.
+pub(in crate::metrics::collector) struct SpikeTrigger {
+    latency_ms: Option<u64>,
+    in_flight: Option<u64>,
+}
+
+impl SpikeTrigger {
+    /// Returns `None` when neither threshold is set.
+    pub(in crate::metrics::collector) fn new(
+        latency_ms: Option<u64>,
+        in_flight: Option<u64>,
+    ) -> Option<Self> {
+        (latency_ms.is_some() || in_flight.is_some()).then_some(Self {
+            latency_ms,
+            in_flight,
+        })
+    }
+
+    pub(in crate::metrics::collector) fn check(
+        &self,
+        msg: &Metrics,
+        latency_ms: u64,
+    ) -> Option<LatencySpike> {
+        let slow = self
+            .latency_ms
+            .is_some_and(|threshold| latency_ms >= threshold);
+        let crowded = self
+            .in_flight
+            .is_some_and(|threshold| msg.in_flight_ops >= threshold);
+        (slow || crowded).then_some(LatencySpike {
+            latency_ms,
+            in_flight_ops: msg.in_flight_ops,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::error::{AppError, AppResult};
+
+    #[test]
+    fn spike_trigger_fires_on_either_threshold() -> AppResult<()> {
+        if SpikeTrigger::new(None, None).is_some() {
+            return Err(AppError::validation(
+                "Expected no trigger without thresholds",
+            ));
+        }
+        let trigger = SpikeTrigger::new(Some(500), Some(64))
+            .ok_or_else(|| AppError::validation("Expected a trigger"))?;
+        let calm = Metrics::new(Instant::now(), 200, false, false, 0, 10);
+        let crowded = Metrics::new(Instant::now(), 200, false, false, 0, 64);
+
+        let results = [
+            trigger.check(&calm, 120),
+            trigger.check(&calm, 500),
+            trigger.check(&crowded, 3),
+        ];
+        let expected = [
+            None,
+            Some(LatencySpike {
+                latency_ms: 500,
+                in_flight_ops: 10,
+            }),
+            Some(LatencySpike {
+                latency_ms: 3,
+                in_flight_ops: 64,
+            }),
+        ];
+        if results != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected spike checks: {:?}",
+                results
+            )));
+        }
+        Ok(())
+    }
+}
@@ -2,14 +2,18 @@ use std::time::Duration;
 
 use crate::{
     args::PositiveU64,
-    sinks::config::{SinkStats, SinksConfig},
+    sinks::{
+        checkpoint::ResumeState,
+        config::{SinkStats, SinksConfig},
+    },
 };
 
-use super::super::super::StreamSnapshot;
+use super::super::super::{LatencyHistogram, StreamSnapshot};
 use super::super::state::UiAggregationState;
 
 const SINK_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
 const STREAM_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(30);
 
 pub(in crate::metrics::collector) fn build_sink_stats(
     state: &UiAggregationState,
@@ -40,11 +44,12 @@ pub(in crate::metrics::collector) fn build_sink_stats(
         0
     };
 
-    let (p50_latency_ms, p90_latency_ms, p99_latency_ms) = state
+    let (p50_latency_ms, p90_latency_ms, p99_latency_ms, p999_latency_ms, p9999_latency_ms) = state
         .histogram
         .as_ref()
-        .map(|histogram| histogram.percentiles())
-        .unwrap_or((0, 0, 0));
+        .map_or((0, 0, 0, 0, 0), |histogram| {
+            histogram.percentiles_extended()
+        });
 
     let (success_rate_x100, avg_rps_x100, avg_rpm_x100) =
         compute_rate_stats(total_requests, successful_requests, duration);
@@ -61,6 +66,8 @@ pub(in crate::metrics::collector) fn build_sink_stats(
         p50_latency_ms,
         p90_latency_ms,
         p99_latency_ms,
+        p999_latency_ms,
+        p9999_latency_ms,
         success_rate_x100,
         avg_rps_x100,
         avg_rpm_x100,
@@ -119,6 +126,68 @@ pub(in crate::metrics::collector) fn build_stream_snapshot(
         success_max_latency_ms,
         success_latency_sum_ms: state.success_latency_sum_ms,
         histogram_b64,
+        in_flight_ops: state.in_flight_ops,
+        http_versions: state.http_versions.clone(),
+        error_reasons: state.error_reasons.clone(),
+        check_failures: state.check_failures,
+    })
+}
+
+/// Raw totals and histograms for the `resume` block of a checkpoint.
+pub(in crate::metrics::collector) fn build_resume_state(
+    state: &UiAggregationState,
+    duration: Duration,
+) -> Option<ResumeState> {
+    let encode = |histogram: Option<&LatencyHistogram>| {
+        histogram.map(LatencyHistogram::encode_base64).transpose()
+    };
+    let (histogram_b64, success_histogram_b64) = match (
+        encode(state.histogram.as_ref()),
+        encode(state.success_histogram.as_ref()),
+    ) {
+        (Ok(Some(all)), Ok(Some(success))) => (all, success),
+        (Err(err), _) | (_, Err(err)) => {
+            tracing::warn!("Failed to encode histogram for checkpoint: {}", err);
+            return None;
+        }
+        _ => return None,
+    };
+    let has_requests = state.current_requests > 0;
+    let has_successes = state.successful_requests > 0;
+    Some(ResumeState {
+        duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        total_requests: state.current_requests,
+        successful_requests: state.successful_requests,
+        timeout_requests: state.timeout_requests,
+        transport_errors: state.transport_errors,
+        non_expected_status: state.non_expected_status,
+        min_latency_ms: if has_requests {
+            state.min_latency_ms
+        } else {
+            0
+        },
+        max_latency_ms: state.max_latency_ms,
+        latency_sum_ms: state.latency_sum_ms,
+        success_min_latency_ms: if has_successes {
+            state.success_min_latency_ms
+        } else {
+            0
+        },
+        success_max_latency_ms: state.success_max_latency_ms,
+        success_latency_sum_ms: state.success_latency_sum_ms,
+        histogram_b64,
+        success_histogram_b64,
+        http_versions: state
+            .http_versions
+            .iter()
+            .map(|(version, count)| ((*version).to_owned(), *count))
+            .collect(),
+        error_reasons: state
+            .error_reasons
+            .iter()
+            .map(|(reason, count)| ((*reason).to_owned(), *count))
+            .collect(),
+        check_failures: state.check_failures,
     })
 }
 
@@ -177,3 +246,12 @@ pub(in crate::metrics::collector) const fn resolve_stream_interval(
     };
     Duration::from_millis(value.get())
 }
+
+pub(in crate::metrics::collector) const fn resolve_checkpoint_interval(
+    interval_secs: Option<&PositiveU64>,
+) -> Duration {
+    let Some(value) = interval_secs else {
+        return CHECKPOINT_INTERVAL;
+    };
+    Duration::from_secs(value.get())
+}
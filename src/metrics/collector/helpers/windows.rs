@@ -85,25 +85,52 @@ fn prune_bytes_samples(
 pub(in crate::metrics::collector) fn compute_percentiles(
     window: &VecDeque<(Instant, u64)>,
 ) -> (u64, u64, u64) {
+    let (p50, p90, p99, _, _) = compute_percentiles_extended(window);
+    (p50, p90, p99)
+}
+
+/// p50, p90, p99, p99.9 and p99.99 of one window. Like
+/// [`LatencyHistogram::percentiles_extended`](crate::metrics::LatencyHistogram::percentiles_extended),
+/// a tail percentile reports the window's max until it holds 1,000 (p99.9) or
+/// 10,000 (p99.99) samples.
+pub(in crate::metrics::collector) fn compute_percentiles_extended(
+    window: &VecDeque<(Instant, u64)>,
+) -> (u64, u64, u64, u64, u64) {
     if window.is_empty() {
-        return (0, 0, 0);
+        return (0, 0, 0, 0, 0);
     }
 
     let mut values: Vec<u64> = window.iter().map(|(_, latency)| *latency).collect();
     values.sort_unstable();
 
-    let p50 = percentile(&values, 50);
-    let p90 = percentile(&values, 90);
-    let p99 = percentile(&values, 99);
+    let p50 = percentile(&values, 50, 100);
+    let p90 = percentile(&values, 90, 100);
+    let p99 = percentile(&values, 99, 100);
+    let max = values.last().copied().unwrap_or(0);
+    let p999 = if values.len() >= 1_000 {
+        percentile(&values, 999, 1_000)
+    } else {
+        max
+    };
+    let p9999 = if values.len() >= 10_000 {
+        percentile(&values, 9_999, 10_000)
+    } else {
+        max
+    };
 
-    (p50, p90, p99)
+    (p50, p90, p99, p999, p9999)
 }
 
-fn percentile(data: &[u64], percentile: u64) -> u64 {
+/// Value at `parts / of` of the sorted `data`.
+fn percentile(data: &[u64], parts: u64, of: u64) -> u64 {
     if data.is_empty() {
         return 0;
     }
     let count = data.len().saturating_sub(1) as u64;
-    let index = (percentile.saturating_mul(count).saturating_add(50) / 100) as usize;
+    let index = parts
+        .saturating_mul(count)
+        .saturating_add(of / 2)
+        .checked_div(of)
+        .unwrap_or(0) as usize;
     *data.get(index).unwrap_or(&0)
 }
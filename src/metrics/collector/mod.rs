@@ -1,4 +1,5 @@
 mod helpers;
+mod sink_task;
 mod state;
 
 use std::time::Duration;
@@ -9,29 +10,43 @@ use tokio::{
     time::{Instant, MissedTickBehavior},
 };
 
+use crate::run_context::RunContext;
 use crate::shutdown::ShutdownSender;
 use crate::{
-    args::TesterArgs,
-    sinks::writers,
+    args::{TesterArgs, tag_map},
+    sinks::{
+        checkpoint::{self, ResumeState},
+        samples::{SampleStream, sample_from_stats},
+        writers,
+    },
     ui::model::{DataUsage, StatusCounts, UiData},
 };
 
-use super::{Metrics, MetricsReport, MetricsSummary, StreamSnapshot};
+use super::{LatencySpike, Metrics, MetricsReport, MetricsSummary, StreamSnapshot};
 use helpers::{
-    build_sink_stats, build_stream_snapshot, compute_percentiles, process_metric_ui,
+    DrainTracker, ErrorBreaker, SlowestTracker, SpikeTrigger, build_resume_state, build_sink_stats,
+    build_stream_snapshot, compute_percentiles, compute_percentiles_extended, process_metric_ui,
     prune_bytes_window, prune_latency_window, prune_rps_window, record_bytes_sample,
-    record_rps_sample, resolve_sink_interval, resolve_stream_interval,
+    record_rps_sample, resolve_checkpoint_interval, resolve_sink_interval, resolve_stream_interval,
 };
+use sink_task::spawn_sink_writer;
 use state::UiAggregationState;
 
+/// How long queued metrics are drained after the run stops.
+const DRAIN_WINDOW: Duration = Duration::from_millis(200);
+
 #[must_use]
+#[expect(clippy::too_many_arguments)]
 pub fn setup_metrics_collector(
     args: &TesterArgs,
+    run: &RunContext,
+    sample_stream: Option<SampleStream>,
     run_start: Instant,
     shutdown_tx: &ShutdownSender,
     mut metrics_rx: mpsc::Receiver<Metrics>,
     ui_tx: &watch::Sender<UiData>,
     stream_tx: Option<mpsc::UnboundedSender<StreamSnapshot>>,
+    spike_tx: Option<mpsc::Sender<LatencySpike>>,
 ) -> JoinHandle<MetricsReport> {
     let shutdown_tx_main = shutdown_tx.clone();
     let ui_tx = ui_tx.clone();
@@ -41,16 +56,53 @@ pub fn setup_metrics_collector(
     let target_duration = Duration::from_secs(args.target_duration.get());
     let expected_status_code = args.expected_status_code;
     let sinks_config = args.sinks.clone();
+    let sink_run_id = writers::sink_run_id(args.run_id.as_deref());
+    let protocol = args.protocol.as_str();
+    let run_tags = tag_map(&args.tags);
     let stream_summaries = args.distributed_stream_summaries;
     let no_color = args.no_color;
     let sink_interval_duration = resolve_sink_interval(&sinks_config);
     let stream_interval_duration =
         resolve_stream_interval(args.distributed_stream_interval_ms.as_ref());
+    let checkpoint_path = args.checkpoint_out.clone();
+    let slowest = args.slowest.map(|count| count.get());
+    let spike_trigger = spike_tx.and_then(|sender| {
+        SpikeTrigger::new(
+            args.alloc_profiler_spike_ms.map(|value| value.get()),
+            args.alloc_profiler_spike_inflight.map(|value| value.get()),
+        )
+        .map(|trigger| (trigger, sender))
+    });
+    let max_bytes = args.max_bytes.map(|cap| u128::from(cap.bytes()));
+    let error_breaker = ErrorBreaker::new(
+        args.abort_on_errors.map(|count| count.get()),
+        args.abort_on_error_rate,
+        args.abort_min_requests.get(),
+    );
+    let wait_ongoing = args.wait_ongoing_requests_after_deadline;
+    // Requests still in flight finish or time out within one request timeout.
+    let drain_window = if wait_ongoing {
+        args.request_timeout.max(DRAIN_WINDOW)
+    } else {
+        DRAIN_WINDOW
+    };
+    let checkpoint_interval_duration =
+        resolve_checkpoint_interval(args.checkpoint_interval_secs.as_ref());
+    let rate_feedback = run.rate_feedback.clone();
+    let resume_state = run.resume_state.clone();
+    // A resumed run continues the checkpointed run's clock and target.
+    let prior_duration = resume_state
+        .as_deref()
+        .map_or(Duration::ZERO, ResumeState::duration);
 
     tokio::spawn(async move {
         let ui_window = Duration::from_millis(ui_window_ms);
-        let mut state = UiAggregationState::new(ui_window);
+        let mut state = UiAggregationState::new(ui_window, slowest);
+        if let Some(resume) = resume_state.as_deref() {
+            state.resume_from(resume);
+        }
         let start_time = run_start;
+        let run_elapsed = || prior_duration.saturating_add(start_time.elapsed());
         let mut shutdown_rx_inner = shutdown_tx_main.subscribe();
         let ui_tx_clone = ui_tx.clone();
         let interval_ms = 1000u64.checked_div(u64::from(ui_fps)).unwrap_or(1).max(1);
@@ -60,8 +112,29 @@ pub fn setup_metrics_collector(
         sink_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
         let mut stream_interval = tokio::time::interval(stream_interval_duration);
         stream_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
-        let mut last_sink_error: Option<String> = None;
-        let shutdown_timer = tokio::time::sleep(target_duration);
+        let mut checkpoint_interval = tokio::time::interval(checkpoint_interval_duration);
+        checkpoint_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        // The first tick fires immediately; skip it so an empty run is not checkpointed.
+        checkpoint_interval.reset();
+        let sinks_config = sinks_config.filter(|_| !stream_summaries);
+        let (sink_tx, sink_writer) = if sinks_config.is_some() || sample_stream.is_some() {
+            let (sink_tx, sink_rx) = watch::channel(None);
+            let writer = spawn_sink_writer(
+                sinks_config,
+                sink_run_id,
+                protocol,
+                run_tags,
+                sample_stream,
+                sink_rx,
+            );
+            (Some(sink_tx), Some(writer))
+        } else {
+            (None, None)
+        };
+        let mut last_checkpoint_error: Option<String> = None;
+        let mut stop_reason: Option<String> = None;
+        let mut abort_reason: Option<String> = None;
+        let shutdown_timer = tokio::time::sleep(target_duration.saturating_sub(prior_duration));
         tokio::pin!(shutdown_timer);
         let mut ui_enabled = ui_tx
             .send(UiData {
@@ -83,9 +156,12 @@ pub fn setup_metrics_collector(
                     bytes_per_sec: 0,
                     series: Vec::new(),
                 }),
+                error_samples: Vec::new(),
                 p50: 0,
                 p90: 0,
                 p99: 0,
+                p999: 0,
+                p9999: 0,
                 p50_ok: 0,
                 p90_ok: 0,
                 p99_ok: 0,
@@ -111,7 +187,38 @@ pub fn setup_metrics_collector(
                             break;
                         }
                     };
-                    process_metric_ui(msg, Instant::now(), expected_status_code, &mut state);
+                    process_metric_ui(&msg, Instant::now(), expected_status_code, &mut state);
+                    if let Some((trigger, sender)) = spike_trigger.as_ref() {
+                        let latency_ms =
+                            u64::try_from(msg.response_time.as_millis()).unwrap_or(u64::MAX);
+                        // The dump task handles one spike at a time; the rest are dropped.
+                        if let Some(spike) = trigger.check(&msg, latency_ms)
+                            && sender.try_send(spike).is_err()
+                        {
+                            tracing::trace!("alloc-profiler spike dropped while a dump is pending");
+                        }
+                    }
+                    if let Some(cap) = max_bytes
+                        && state.total_bytes >= cap
+                    {
+                        let reason =
+                            format!("byte cap reached ({} of {} bytes)", state.total_bytes, cap);
+                        tracing::info!("Stopping run: {}", reason);
+                        stop_reason = Some(reason);
+                        drop(shutdown_tx_main.send(()));
+                        break;
+                    }
+                    if let Some(reason) = error_breaker
+                        .as_ref()
+                        .and_then(|breaker| {
+                            breaker.check(state.current_requests, state.successful_requests)
+                        })
+                    {
+                        tracing::warn!("Aborting run: {}", reason);
+                        abort_reason = Some(reason);
+                        drop(shutdown_tx_main.send(()));
+                        break;
+                    }
                 },
                 _ = ui_interval.tick() => {
                     let now = Instant::now();
@@ -119,7 +226,7 @@ pub fn setup_metrics_collector(
                     prune_rps_window(&mut state.rps_window, now);
                     prune_bytes_window(&mut state.bytes_window, now);
 
-                    let elapsed_time = start_time.elapsed();
+                    let elapsed_time = run_elapsed();
                     let recent_latencies: Vec<(u64, u64)> = state
                         .latency_window
                         .iter()
@@ -131,7 +238,12 @@ pub fn setup_metrics_collector(
                         })
                         .collect();
 
-                    let (p50, p90, p99) = compute_percentiles(&state.latency_window);
+                    let (p50, p90, p99, p999, p9999) =
+                        compute_percentiles_extended(&state.latency_window);
+                    if let Some(feedback) = rate_feedback.as_ref() {
+                        let samples = u64::try_from(state.latency_window.len()).unwrap_or(u64::MAX);
+                        feedback.publish_p99(p99, samples);
+                    }
                     let (p50_ok, p90_ok, p99_ok) = compute_percentiles(&state.latency_window_ok);
                     let rps: u64 = state
                         .rps_window
@@ -192,9 +304,12 @@ pub fn setup_metrics_collector(
                                     bytes_per_sec,
                                     series: recent_bytes,
                                 }),
+                                error_samples: state.error_samples.iter().cloned().collect(),
                                 p50,
                                 p90,
                                 p99,
+                                p999,
+                                p9999,
                                 p50_ok,
                                 p90_ok,
                                 p99_ok,
@@ -209,26 +324,35 @@ pub fn setup_metrics_collector(
                     }
                 },
                 _ = sink_interval.tick() => {
-                    let duration = start_time.elapsed();
+                    let duration = run_elapsed();
 
-                    if !stream_summaries && let Some(sinks_config) = sinks_config.as_ref() {
-                        let sink_stats = build_sink_stats(&state, duration);
-                        match writers::write_sinks(sinks_config, &sink_stats).await {
+                    if let Some(sink_tx) = sink_tx.as_ref() {
+                        sink_tx.send_replace(Some(build_sink_stats(&state, duration)));
+                    }
+                },
+                _ = checkpoint_interval.tick(), if checkpoint_path.is_some() => {
+                    if let Some(path) = checkpoint_path.as_deref() {
+                        let duration = run_elapsed();
+                        let stats = build_sink_stats(&state, duration);
+                        let resume = build_resume_state(&state, duration);
+                        match checkpoint::write_checkpoint(path, &stats, resume.as_ref(), false)
+                            .await
+                        {
                             Ok(()) => {
-                                last_sink_error = None;
+                                last_checkpoint_error = None;
                             }
                             Err(err) => {
                                 let err_message = err.to_string();
-                                if last_sink_error.as_deref() != Some(err_message.as_str()) {
-                                    tracing::warn!("Failed to write sinks: {}", err);
-                                    last_sink_error = Some(err_message);
+                                if last_checkpoint_error.as_deref() != Some(err_message.as_str()) {
+                                    tracing::warn!("Failed to write checkpoint: {}", err);
+                                    last_checkpoint_error = Some(err_message);
                                 }
                             }
                         }
                     }
                 },
                 _ = stream_interval.tick(), if stream_tx.is_some() => {
-                    let duration = start_time.elapsed();
+                    let duration = run_elapsed();
                     if let Some(stream_tx) = stream_tx.as_ref()
                         && let Some(snapshot) = build_stream_snapshot(&state, duration)
                     {
@@ -238,23 +362,60 @@ pub fn setup_metrics_collector(
             }
         }
 
-        let drain_deadline = Instant::now()
-            .checked_add(Duration::from_millis(200))
-            .unwrap_or_else(Instant::now);
+        let deadline = Instant::now();
+        let drain_deadline = deadline.checked_add(drain_window).unwrap_or(deadline);
+        let mut drain = wait_ongoing.then(|| DrainTracker::new(deadline, state.in_flight_ops));
         loop {
-            if Instant::now() > drain_deadline {
-                break;
-            }
-            match metrics_rx.try_recv() {
-                Ok(msg) => {
-                    process_metric_ui(msg, Instant::now(), expected_status_code, &mut state);
+            // Waiting senders keep the channel open until their last request
+            // resolves; otherwise only already-queued metrics are drained.
+            let msg = if drain.is_some() {
+                match tokio::time::timeout_at(drain_deadline, metrics_rx.recv()).await {
+                    Ok(Some(msg)) => msg,
+                    Ok(None) | Err(_) => break,
+                }
+            } else {
+                if Instant::now() > drain_deadline {
+                    break;
+                }
+                match metrics_rx.try_recv() {
+                    Ok(msg) => msg,
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => break,
                 }
-                Err(mpsc::error::TryRecvError::Empty) => break,
-                Err(mpsc::error::TryRecvError::Disconnected) => break,
+            };
+            if let Some(tracker) = drain.as_mut() {
+                tracker.record(&msg);
             }
+            process_metric_ui(&msg, Instant::now(), expected_status_code, &mut state);
         }
 
-        let duration = start_time.elapsed();
+        // Let an in-flight write finish so it cannot land after the final one.
+        drop(sink_tx);
+        let mut final_stream = match sink_writer {
+            Some(writer) => writer.await.unwrap_or_else(|err| {
+                tracing::warn!("Sink writer task failed: {}", err);
+                None
+            }),
+            None => None,
+        };
+
+        let duration = run_elapsed();
+        // The run is over, so the final sample can be written inline.
+        if let Some(stream) = final_stream.as_mut() {
+            let sample = sample_from_stats(&build_sink_stats(&state, duration));
+            if let Err(err) = stream.append(&sample).await {
+                tracing::warn!("Failed to write final stream sample: {}", err);
+            }
+        }
+        if let Some(path) = checkpoint_path.as_deref() {
+            let stats = build_sink_stats(&state, duration);
+            let resume = build_resume_state(&state, duration);
+            if let Err(err) =
+                checkpoint::write_checkpoint(path, &stats, resume.as_ref(), true).await
+            {
+                tracing::warn!("Failed to write final checkpoint: {}", err);
+            }
+        }
         let avg_latency_ms = if state.current_requests > 0 {
             let avg = state
                 .latency_sum_ms
@@ -307,7 +468,18 @@ pub fn setup_metrics_collector(
                 success_min_latency_ms,
                 success_max_latency_ms,
                 success_avg_latency_ms,
+                http_versions: state.http_versions,
+                error_reasons: state.error_reasons,
+                check_failures: state.check_failures,
             },
+            stop_reason,
+            abort_reason,
+            slowest: state
+                .slowest
+                .map_or_else(Vec::new, SlowestTracker::into_sorted),
+            drain: drain.map(DrainTracker::finish),
+            connection_split: (!state.connection_split.is_empty())
+                .then_some(state.connection_split),
         }
     })
 }
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+
+use tokio::{sync::watch, task::JoinHandle};
+
+use crate::sinks::{
+    config::{SinkStats, SinksConfig},
+    samples::{SampleStream, sample_from_stats},
+    writers,
+};
+
+/// Writes the periodic sink snapshots and stream samples published by the collector.
+///
+/// Pushes and file writes run in their own task so a slow Pushgateway,
+/// Influx endpoint, or disk never stops the collector from draining
+/// metrics. Only the latest snapshot is kept; ticks that arrive during a
+/// slow write are coalesced. The task ends once the collector drops the
+/// sender and hands the sample stream back for the final sample.
+pub(super) fn spawn_sink_writer(
+    config: Option<SinksConfig>,
+    run_id: String,
+    protocol: &'static str,
+    tags: BTreeMap<String, String>,
+    mut sample_stream: Option<SampleStream>,
+    mut stats_rx: watch::Receiver<Option<SinkStats>>,
+) -> JoinHandle<Option<SampleStream>> {
+    tokio::spawn(async move {
+        let mut last_sink_error: Option<String> = None;
+        let mut last_sample_error: Option<String> = None;
+        while stats_rx.changed().await.is_ok() {
+            let Some(stats) = stats_rx.borrow_and_update().clone() else {
+                continue;
+            };
+            if let Some(stream) = sample_stream.as_mut() {
+                let result = stream.append(&sample_from_stats(&stats)).await;
+                warn_on_new_error(result, &mut last_sample_error, "stream sample");
+            }
+            if let Some(config) = config.as_ref() {
+                let result = writers::write_sinks(config, &stats, &run_id, protocol, &tags).await;
+                warn_on_new_error(result, &mut last_sink_error, "sinks");
+            }
+        }
+        sample_stream
+    })
+}
+
+/// Logs `result`'s error once per distinct message.
+fn warn_on_new_error<E: std::fmt::Display>(
+    result: Result<(), E>,
+    last_error: &mut Option<String>,
+    target: &str,
+) {
+    match result {
+        Ok(()) => {
+            *last_error = None;
+        }
+        Err(err) => {
+            let err_message = err.to_string();
+            if last_error.as_deref() != Some(err_message.as_str()) {
+                tracing::warn!("Failed to write {}: {}", target, err);
+                *last_error = Some(err_message);
+            }
+        }
+    }
+}
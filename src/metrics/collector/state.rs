@@ -1,11 +1,13 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::time::Duration;
 
 use tokio::time::Instant;
 
-use crate::ui::model::StatusCounts;
+use crate::sinks::checkpoint::ResumeState;
+use crate::ui::model::{ErrorSample, StatusCounts};
 
-use super::super::LatencyHistogram;
+use super::super::{CheckFailures, ConnectionSplit, LatencyHistogram};
+use super::helpers::SlowestTracker;
 
 pub(super) struct UiAggregationState {
     pub(super) current_requests: u64,
@@ -29,12 +31,18 @@ pub(super) struct UiAggregationState {
     pub(super) bytes_window: VecDeque<(Instant, u64)>,
     pub(super) bytes_samples: VecDeque<(Instant, u64)>,
     pub(super) total_bytes: u128,
+    pub(super) http_versions: BTreeMap<&'static str, u64>,
+    pub(super) error_reasons: BTreeMap<&'static str, u64>,
+    pub(super) check_failures: CheckFailures,
+    pub(super) connection_split: ConnectionSplit,
+    pub(super) error_samples: VecDeque<ErrorSample>,
     pub(super) histogram: Option<LatencyHistogram>,
     pub(super) success_histogram: Option<LatencyHistogram>,
+    pub(super) slowest: Option<SlowestTracker>,
 }
 
 impl UiAggregationState {
-    pub(super) fn new(ui_window: Duration) -> Self {
+    pub(super) fn new(ui_window: Duration, slowest: Option<usize>) -> Self {
         let histogram = match LatencyHistogram::new() {
             Ok(histogram) => Some(histogram),
             Err(err) => {
@@ -72,8 +80,45 @@ impl UiAggregationState {
             bytes_window: VecDeque::new(),
             bytes_samples: VecDeque::new(),
             total_bytes: 0,
+            http_versions: BTreeMap::new(),
+            error_reasons: BTreeMap::new(),
+            check_failures: CheckFailures::default(),
+            connection_split: ConnectionSplit::default(),
+            error_samples: VecDeque::new(),
             histogram,
             success_histogram,
+            slowest: slowest.map(SlowestTracker::new),
+        }
+    }
+
+    /// Seeds the totals with a checkpoint from an earlier run, for `--resume`.
+    pub(super) fn resume_from(&mut self, resume: &ResumeState) {
+        self.current_requests = resume.total_requests;
+        self.successful_requests = resume.successful_requests;
+        self.timeout_requests = resume.timeout_requests;
+        self.transport_errors = resume.transport_errors;
+        self.non_expected_status = resume.non_expected_status;
+        self.latency_sum_ms = resume.latency_sum_ms;
+        self.success_latency_sum_ms = resume.success_latency_sum_ms;
+        if resume.total_requests > 0 {
+            self.min_latency_ms = resume.min_latency_ms;
+            self.max_latency_ms = resume.max_latency_ms;
+        }
+        if resume.successful_requests > 0 {
+            self.success_min_latency_ms = resume.success_min_latency_ms;
+            self.success_max_latency_ms = resume.success_max_latency_ms;
+        }
+        self.http_versions = resume.http_versions();
+        self.error_reasons = resume.error_reasons();
+        self.check_failures = resume.check_failures;
+        match resume.histograms() {
+            Ok((histogram, success_histogram)) => {
+                self.histogram = Some(histogram);
+                self.success_histogram = Some(success_histogram);
+            }
+            Err(err) => {
+                tracing::warn!("Failed to restore checkpoint histograms: {}", err);
+            }
         }
     }
 }
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Run state shared between the collector and the `--find-max-rate` rate
+/// controller.
+///
+/// The collector publishes the p99 of its rolling latency window; the
+/// controller reads it each step and records the highest rate that held the
+/// target so the summary can report it.
+#[derive(Debug, Default)]
+pub struct RateFeedback {
+    p99_ms: AtomicU64,
+    window_samples: AtomicU64,
+    sustainable_rps: AtomicU64,
+}
+
+impl RateFeedback {
+    pub fn publish_p99(&self, p99_ms: u64, window_samples: u64) {
+        self.p99_ms.store(p99_ms, Ordering::Relaxed);
+        self.window_samples.store(window_samples, Ordering::Relaxed);
+    }
+
+    /// Rolling p99 and the number of latencies it was computed from.
+    #[must_use]
+    pub fn p99(&self) -> (u64, u64) {
+        (
+            self.p99_ms.load(Ordering::Relaxed),
+            self.window_samples.load(Ordering::Relaxed),
+        )
+    }
+
+    pub fn set_sustainable_rps(&self, rps: u64) {
+        self.sustainable_rps.store(rps, Ordering::Relaxed);
+    }
+
+    /// Highest rate that held the target; `None` until one has.
+    #[must_use]
+    pub fn sustainable_rps(&self) -> Option<u64> {
+        Some(self.sustainable_rps.load(Ordering::Relaxed)).filter(|rps| *rps > 0)
+    }
+}
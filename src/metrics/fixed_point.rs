@@ -0,0 +1,90 @@
+//! Fixed-point `log2`/`exp2` for statistics that would otherwise need floats.
+
+/// Fractional bits of the fixed-point logarithms.
+pub(crate) const LOG_FRACTION_BITS: u32 = 16;
+/// `1.0` in the fixed-point logarithm format.
+pub(crate) const LOG_ONE: u128 = 1 << LOG_FRACTION_BITS;
+/// Fractional bits of the mantissa while a logarithm or power is computed.
+const MANTISSA_BITS: u32 = 32;
+/// `2^(2^-k)` for `k = 1..=16`, with `MANTISSA_BITS` fractional bits.
+const EXP2_FRACTION_STEPS: [u128; LOG_FRACTION_BITS as usize] = [
+    6_074_001_000,
+    5_107_605_667,
+    4_683_695_048,
+    4_485_121_744,
+    4_389_014_833,
+    4_341_736_423,
+    4_318_288_544,
+    4_306_612_134,
+    4_300_785_774,
+    4_297_875_550,
+    4_296_421_177,
+    4_295_694_175,
+    4_295_330_720,
+    4_295_149_004,
+    4_295_058_149,
+    4_295_012_722,
+];
+
+/// `log2(value)` with `LOG_FRACTION_BITS` fractional bits, via the
+/// bit-by-bit squaring method; `0` for `value == 0`.
+pub(crate) fn log2_fixed(value: u64) -> u128 {
+    let Some(integer) = value.checked_ilog2() else {
+        return 0;
+    };
+    // Normalize to `[1, 2)` with `MANTISSA_BITS` fractional bits.
+    let mut mantissa = u128::from(value)
+        .checked_shl(MANTISSA_BITS)
+        .and_then(|shifted| shifted.checked_shr(integer))
+        .unwrap_or(0);
+    let one = 1u128 << MANTISSA_BITS;
+    let two = one.saturating_mul(2);
+    let mut fraction = 0u128;
+    for bit in (0..LOG_FRACTION_BITS).rev() {
+        mantissa = mantissa
+            .saturating_mul(mantissa)
+            .checked_shr(MANTISSA_BITS)
+            .unwrap_or(0);
+        if mantissa >= two {
+            mantissa = mantissa.checked_shr(1).unwrap_or(0);
+            fraction |= 1u128.checked_shl(bit).unwrap_or(0);
+        }
+    }
+    u128::from(integer)
+        .saturating_mul(LOG_ONE)
+        .saturating_add(fraction)
+}
+
+/// `2^exponent * scale` for a fixed-point `exponent` as produced by
+/// [`log2_fixed`], saturating at `u64::MAX`.
+pub(crate) fn exp2_fixed_scaled(exponent: u128, scale: u64) -> u64 {
+    let integer = exponent.checked_shr(LOG_FRACTION_BITS).unwrap_or(0);
+    let mut power = 1u128 << MANTISSA_BITS;
+    for (step, factor) in EXP2_FRACTION_STEPS.iter().enumerate() {
+        let bit = LOG_FRACTION_BITS
+            .saturating_sub(1)
+            .saturating_sub(u32::try_from(step).unwrap_or(u32::MAX));
+        if exponent & 1u128.checked_shl(bit).unwrap_or(0) != 0 {
+            power = power
+                .saturating_mul(*factor)
+                .checked_shr(MANTISSA_BITS)
+                .unwrap_or(0);
+        }
+    }
+    let scaled = power.saturating_mul(u128::from(scale));
+    let Ok(integer) = u32::try_from(integer) else {
+        return u64::MAX;
+    };
+    let value = if integer >= MANTISSA_BITS {
+        let shift = integer.saturating_sub(MANTISSA_BITS);
+        if shift > scaled.leading_zeros() {
+            return u64::MAX;
+        }
+        scaled.checked_shl(shift).unwrap_or(u128::MAX)
+    } else {
+        scaled
+            .checked_shr(MANTISSA_BITS.saturating_sub(integer))
+            .unwrap_or(0)
+    };
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
@@ -1,10 +1,20 @@
 use base64::{Engine as _, engine::general_purpose::STANDARD as B64};
 use hdrhistogram::Histogram;
-use hdrhistogram::serialization::{Deserializer, Serializer, V2Serializer};
+use hdrhistogram::serialization::interval_log::{
+    IntervalLogIterator, IntervalLogWriterBuilder, LogEntry,
+};
+use hdrhistogram::serialization::{Deserializer, Serializer, V2DeflateSerializer, V2Serializer};
 use std::io::Cursor;
+use std::time::{Duration, SystemTime};
 
 use crate::error::{AppError, AppResult, MetricsError};
 
+use super::fixed_point::{exp2_fixed_scaled, log2_fixed};
+use super::modes::{LatencyMode, detect_modes};
+
+/// Scale of the `_x100` statistics: hundredths of a millisecond.
+const STATS_SCALE: u64 = 100;
+
 #[derive(Debug)]
 pub struct LatencyHistogram {
     hist: Histogram<u64>,
@@ -69,6 +79,118 @@ impl LatencyHistogram {
         )
     }
 
+    /// Returns p50, p90, p99, p99.9 and p99.99.
+    ///
+    /// A tail percentile needs at least 1,000 (p99.9) or 10,000 (p99.99)
+    /// samples to be distinct; with fewer it reports the max observed latency.
+    #[must_use]
+    pub fn percentiles_extended(&self) -> (u64, u64, u64, u64, u64) {
+        let count = self.count();
+        if count == 0 {
+            return (0, 0, 0, 0, 0);
+        }
+
+        let (p50, p90, p99) = self.percentiles();
+        let max = self.hist.max();
+        let p999 = if count >= 1_000 {
+            self.hist.value_at_quantile(0.999)
+        } else {
+            max
+        };
+        let p9999 = if count >= 10_000 {
+            self.hist.value_at_quantile(0.9999)
+        } else {
+            max
+        };
+        (p50, p90, p99, p999, p9999)
+    }
+
+    /// Returns the latency at `quantile` (`0.0..=1.0`), or 0 for an empty histogram.
+    #[must_use]
+    pub fn percentile(&self, quantile: f64) -> u64 {
+        if self.count() == 0 {
+            return 0;
+        }
+        self.hist.value_at_quantile(quantile)
+    }
+
+    /// Returns the highest recorded latency, or 0 for an empty histogram.
+    #[must_use]
+    pub fn max(&self) -> u64 {
+        if self.count() == 0 {
+            return 0;
+        }
+        self.hist.max()
+    }
+
+    /// Returns how many latencies fall between `low_ms` and `high_ms`,
+    /// inclusive, at the histogram's precision.
+    #[must_use]
+    pub fn count_between(&self, low_ms: u64, high_ms: u64) -> u64 {
+        self.hist.count_between(low_ms, high_ms)
+    }
+
+    /// Detects the peaks of the recorded distribution; see [`detect_modes`].
+    #[must_use]
+    pub fn modes(&self) -> Vec<LatencyMode> {
+        detect_modes(
+            self.hist
+                .iter_recorded()
+                .map(|value| (value.value_iterated_to(), value.count_at_value())),
+        )
+    }
+
+    /// Geometric mean latency in hundredths of a millisecond, from bucket
+    /// midpoints; 0 for an empty histogram.
+    #[must_use]
+    pub fn geomean_x100(&self) -> u64 {
+        let count = self.count();
+        if count == 0 {
+            return 0;
+        }
+        let log_sum = self.hist.iter_recorded().fold(0u128, |sum, bucket| {
+            let midpoint = self.hist.median_equivalent(bucket.value_iterated_to());
+            sum.saturating_add(
+                log2_fixed(midpoint).saturating_mul(u128::from(bucket.count_at_value())),
+            )
+        });
+        let mean_log = log_sum.checked_div(u128::from(count)).unwrap_or(0);
+        exp2_fixed_scaled(mean_log, STATS_SCALE)
+    }
+
+    /// Population standard deviation of latency in hundredths of a
+    /// millisecond, from bucket midpoints; 0 for an empty histogram.
+    #[must_use]
+    pub fn stddev_x100(&self) -> u64 {
+        let count = u128::from(self.count());
+        if count == 0 {
+            return 0;
+        }
+        let scaled_midpoints = || {
+            self.hist.iter_recorded().map(|bucket| {
+                let midpoint = self.hist.median_equivalent(bucket.value_iterated_to());
+                (
+                    u128::from(midpoint).saturating_mul(u128::from(STATS_SCALE)),
+                    u128::from(bucket.count_at_value()),
+                )
+            })
+        };
+        let mean = scaled_midpoints()
+            .fold(0u128, |sum, (value, hits)| {
+                sum.saturating_add(value.saturating_mul(hits))
+            })
+            .checked_div(count)
+            .unwrap_or(0);
+        let variance = scaled_midpoints()
+            .fold(0u128, |sum, (value, hits)| {
+                let delta = value.abs_diff(mean);
+                sum.saturating_add(delta.saturating_mul(delta).saturating_mul(hits))
+            })
+            .checked_div(count)
+            .unwrap_or(0);
+        u64::try_from(variance.isqrt()).unwrap_or(u64::MAX)
+    }
+
     #[must_use]
     pub fn count(&self) -> u64 {
         self.hist.len()
@@ -115,4 +237,100 @@ impl LatencyHistogram {
             })?;
         Ok(Self { hist })
     }
+
+    /// Encode the histogram as an HdrHistogram interval log with a single
+    /// interval spanning the run. Values are milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the histogram cannot be serialized.
+    pub fn encode_hdr_log(&self, start: SystemTime, duration: Duration) -> AppResult<Vec<u8>> {
+        let map_err = |err: Box<dyn std::error::Error + Send + Sync>| {
+            AppError::metrics(MetricsError::Histogram {
+                context: "write histogram log",
+                source: err,
+            })
+        };
+        let header = format!(
+            "strest latency histogram (ms): count={} max={}ms",
+            self.count(),
+            self.max()
+        );
+        let mut buffer = Vec::new();
+        let mut serializer = V2DeflateSerializer::new();
+        let mut writer = IntervalLogWriterBuilder::new()
+            .add_comment(&header)
+            .with_start_time(start)
+            .begin_log_with(&mut buffer, &mut serializer)
+            .map_err(|err| map_err(Box::new(err)))?;
+        writer
+            .write_histogram(&self.hist, Duration::ZERO, duration, None)
+            .map_err(|err| map_err(Box::new(err)))?;
+        Ok(buffer)
+    }
+
+    /// Decode an HdrHistogram interval log, merging every interval it holds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be parsed, holds no intervals, or an
+    /// interval cannot be decoded.
+    pub fn decode_hdr_log(bytes: &[u8]) -> AppResult<Self> {
+        let mut merged: Option<Self> = None;
+        for entry in IntervalLogIterator::new(bytes) {
+            let entry = entry.map_err(|err| {
+                AppError::metrics(MetricsError::HistogramLogParse {
+                    detail: format!("{:?}", err),
+                })
+            })?;
+            let LogEntry::Interval(interval) = entry else {
+                continue;
+            };
+            let decoded = Self::decode_base64(interval.encoded_histogram())?;
+            match merged.as_mut() {
+                Some(total) => total.merge(&decoded)?,
+                None => merged = Some(decoded),
+            }
+        }
+        merged.ok_or_else(|| AppError::metrics(MetricsError::HistogramLogEmpty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hdr_log_round_trips_count_and_max() -> AppResult<()> {
+        let mut histogram = LatencyHistogram::new()?;
+        for value in [3, 5, 8, 120] {
+            histogram.record(value)?;
+        }
+        let log = histogram.encode_hdr_log(SystemTime::now(), Duration::from_secs(10))?;
+        let text = String::from_utf8_lossy(&log);
+        if !text.starts_with("#strest latency histogram (ms): count=4 max=120ms") {
+            return Err(AppError::validation(format!("Unexpected header: {}", text)));
+        }
+
+        let decoded = LatencyHistogram::decode_hdr_log(&log)?;
+        if decoded.count() != 4 || decoded.max() != histogram.max() {
+            return Err(AppError::validation(format!(
+                "Unexpected round trip: count={} max={}",
+                decoded.count(),
+                decoded.max()
+            )));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hdr_log_without_intervals_is_rejected() -> AppResult<()> {
+        match LatencyHistogram::decode_hdr_log(b"#just a comment\n") {
+            Err(AppError::Metrics(MetricsError::HistogramLogEmpty)) => Ok(()),
+            other => Err(AppError::validation(format!(
+                "Expected HistogramLogEmpty, got {:?}",
+                other.map(|histogram| histogram.count())
+            ))),
+        }
+    }
 }
@@ -1,12 +1,15 @@
 mod reader;
 mod writer;
 
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 use tokio::sync::mpsc;
 
-use super::{LatencyHistogram, MetricRecord, Metrics, MetricsRange, MetricsSummary};
+use crate::args::ExpectedStatus;
+
+use super::{LatencyHistogram, MetricRecord, Metrics, MetricsRange, MetricsSummary, StatusClass};
 
 #[cfg(any(test, feature = "fuzzing"))]
 pub use reader::read_metrics_log;
@@ -56,14 +59,21 @@ pub struct LogResult {
     pub success_latency_sum_ms: u128,
     pub histogram: LatencyHistogram,
     pub success_histogram: LatencyHistogram,
+    /// Latency per status class; empty unless `per_status_latency` is set.
+    pub status_histograms: BTreeMap<StatusClass, LatencyHistogram>,
+    /// Response body bytes across every logged request.
+    pub response_bytes: u128,
+    /// Responses per status code, excluding timeouts and transport errors.
+    pub status_codes: BTreeMap<u16, u64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct MetricsLoggerConfig {
     pub run_start: tokio::time::Instant,
     pub warmup: Option<Duration>,
-    pub expected_status_code: u16,
+    pub expected_status_code: ExpectedStatus,
     pub metrics_range: Option<MetricsRange>,
     pub metrics_max: usize,
     pub db_url: Option<String>,
+    pub per_status_latency: bool,
 }
@@ -1,4 +1,6 @@
 #[cfg(any(test, feature = "fuzzing"))]
+use std::collections::BTreeMap;
+#[cfg(any(test, feature = "fuzzing"))]
 use std::path::Path;
 #[cfg(any(test, feature = "fuzzing"))]
 use std::time::Duration;
@@ -8,6 +10,8 @@ use tokio::fs::File;
 #[cfg(any(test, feature = "fuzzing"))]
 use tokio::io::{AsyncBufReadExt, BufReader};
 
+#[cfg(any(test, feature = "fuzzing"))]
+use crate::args::ExpectedStatus;
 #[cfg(any(test, feature = "fuzzing"))]
 use crate::error::{AppError, AppResult, MetricsError};
 
@@ -25,7 +29,7 @@ use super::LogResult;
 /// operations fail.
 pub async fn read_metrics_log(
     log_path: &Path,
-    expected_status_code: u16,
+    expected_status_code: ExpectedStatus,
     metrics_range: &Option<MetricsRange>,
     metrics_max: usize,
     warmup: Option<Duration>,
@@ -59,6 +63,8 @@ pub async fn read_metrics_log(
     let mut transport_errors: u64 = 0;
     let mut non_expected_status: u64 = 0;
     let mut max_elapsed_ms: u64 = 0;
+    let mut total_response_bytes: u128 = 0;
+    let mut status_codes: BTreeMap<u16, u64> = BTreeMap::new();
 
     loop {
         line.clear();
@@ -106,9 +112,23 @@ pub async fn read_metrics_log(
             .next()
             .and_then(|value| value.parse::<u64>().ok())
             .unwrap_or(0);
+        let check_failed = parts
+            .next()
+            .and_then(|value| value.parse::<u8>().ok())
+            .is_some_and(|value| value != 0);
+        let record = MetricRecord {
+            elapsed_ms,
+            latency_ms,
+            status_code,
+            timed_out,
+            transport_error,
+            response_bytes,
+            in_flight_ops,
+            check_failed,
+        };
 
         total_requests = total_requests.saturating_add(1);
-        if status_code == expected_status_code && !timed_out && !transport_error {
+        if record.is_success(expected_status_code) {
             successful_requests = successful_requests.saturating_add(1);
             success_latency_sum_ms = success_latency_sum_ms.saturating_add(u128::from(latency_ms));
             if latency_ms < success_min_latency_ms {
@@ -123,9 +143,14 @@ pub async fn read_metrics_log(
             timeout_requests = timeout_requests.saturating_add(1);
         } else if transport_error {
             transport_errors = transport_errors.saturating_add(1);
-        } else if status_code != expected_status_code {
+        } else if !expected_status_code.contains(status_code) {
             non_expected_status = non_expected_status.saturating_add(1);
         }
+        if !timed_out && !transport_error {
+            let count = status_codes.entry(status_code).or_insert(0);
+            *count = count.saturating_add(1);
+        }
+        total_response_bytes = total_response_bytes.saturating_add(u128::from(response_bytes));
         latency_sum_ms = latency_sum_ms.saturating_add(u128::from(latency_ms));
         if latency_ms < min_latency_ms {
             min_latency_ms = latency_ms;
@@ -140,22 +165,13 @@ pub async fn read_metrics_log(
 
         if collect_records {
             let seconds_elapsed = elapsed_ms / 1000;
-            let in_range = match metrics_range {
-                Some(MetricsRange(range)) => range.contains(&seconds_elapsed),
-                None => true,
-            };
+            let in_range = metrics_range
+                .as_ref()
+                .is_none_or(|range| range.contains(seconds_elapsed));
 
             if in_range {
                 if records.len() < metrics_max {
-                    records.push(MetricRecord {
-                        elapsed_ms,
-                        latency_ms,
-                        status_code,
-                        timed_out,
-                        transport_error,
-                        response_bytes,
-                        in_flight_ops,
-                    });
+                    records.push(record);
                 } else {
                     metrics_truncated = true;
                 }
@@ -213,11 +229,17 @@ pub async fn read_metrics_log(
             success_min_latency_ms,
             success_max_latency_ms,
             success_avg_latency_ms,
+            http_versions: BTreeMap::new(),
+            error_reasons: BTreeMap::new(),
+            check_failures: crate::metrics::CheckFailures::default(),
         },
         metrics_truncated,
         latency_sum_ms,
         success_latency_sum_ms,
         histogram,
         success_histogram,
+        status_histograms: BTreeMap::new(),
+        response_bytes: total_response_bytes,
+        status_codes,
     })
 }
@@ -1,5 +1,7 @@
 mod db;
 
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
 use std::fmt::Write as _;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -14,7 +16,9 @@ use tokio_rusqlite::Connection;
 
 use crate::error::{AppError, AppResult, MetricsError};
 
-use super::super::{LatencyHistogram, MetricRecord, Metrics, MetricsRange, MetricsSummary};
+use super::super::{
+    CheckFailures, LatencyHistogram, MetricRecord, Metrics, MetricsSummary, StatusClass,
+};
 use super::{LogResult, MetricsLoggerConfig};
 use db::{DB_FLUSH_SIZE, DbRecord, flush_db_records};
 
@@ -89,6 +93,12 @@ pub fn setup_metrics_logger(
         let mut transport_errors: u64 = 0;
         let mut non_expected_status: u64 = 0;
         let mut max_elapsed_ms: u64 = 0;
+        let mut http_versions: BTreeMap<&'static str, u64> = BTreeMap::new();
+        let mut error_reasons: BTreeMap<&'static str, u64> = BTreeMap::new();
+        let mut check_failures = CheckFailures::default();
+        let mut response_bytes: u128 = 0;
+        let mut status_codes: BTreeMap<u16, u64> = BTreeMap::new();
+        let mut status_histograms: BTreeMap<StatusClass, LatencyHistogram> = BTreeMap::new();
 
         while let Some(msg) = log_rx.recv().await {
             let elapsed_ms_raw = u64::try_from(
@@ -105,14 +115,15 @@ pub fn setup_metrics_logger(
 
             writeln!(
                 &mut buffer,
-                "{},{},{},{},{},{},{}",
+                "{},{},{},{},{},{},{},{}",
                 elapsed_ms,
                 latency_ms,
                 msg.status_code,
                 u8::from(msg.timed_out),
                 u8::from(msg.transport_error),
                 msg.response_bytes,
-                msg.in_flight_ops
+                msg.in_flight_ops,
+                u8::from(msg.check_failure.is_some())
             )
             .map_err(|err| {
                 AppError::metrics(MetricsError::External {
@@ -132,10 +143,7 @@ pub fn setup_metrics_logger(
             }
 
             total_requests = total_requests.saturating_add(1);
-            if msg.status_code == config.expected_status_code
-                && !msg.timed_out
-                && !msg.transport_error
-            {
+            if msg.is_success(config.expected_status_code) {
                 successful_requests = successful_requests.saturating_add(1);
                 success_latency_sum_ms =
                     success_latency_sum_ms.saturating_add(u128::from(latency_ms));
@@ -151,9 +159,14 @@ pub fn setup_metrics_logger(
                 timeout_requests = timeout_requests.saturating_add(1);
             } else if msg.transport_error {
                 transport_errors = transport_errors.saturating_add(1);
-            } else if msg.status_code != config.expected_status_code {
+            } else if !config.expected_status_code.contains(msg.status_code) {
                 non_expected_status = non_expected_status.saturating_add(1);
             }
+            if !msg.timed_out && !msg.transport_error {
+                let count = status_codes.entry(msg.status_code).or_insert(0);
+                *count = count.saturating_add(1);
+            }
+            response_bytes = response_bytes.saturating_add(u128::from(msg.response_bytes));
             latency_sum_ms = latency_sum_ms.saturating_add(u128::from(latency_ms));
             if latency_ms < min_latency_ms {
                 min_latency_ms = latency_ms;
@@ -165,6 +178,29 @@ pub fn setup_metrics_logger(
                 max_elapsed_ms = elapsed_ms;
             }
             histogram.record(latency_ms)?;
+            if let Some(version) = msg.http_version {
+                let count = http_versions.entry(version).or_insert(0);
+                *count = count.saturating_add(1);
+            }
+            if let Some(reason) = msg.error_reason {
+                let count = error_reasons.entry(reason.label()).or_insert(0);
+                *count = count.saturating_add(1);
+            }
+            if let Some(failure) = msg.check_failure {
+                check_failures.record(failure);
+            }
+            if config.per_status_latency
+                && !msg.timed_out
+                && !msg.transport_error
+                && let Some(class) = StatusClass::from_status(msg.status_code)
+            {
+                match status_histograms.entry(class) {
+                    Entry::Occupied(mut entry) => entry.get_mut().record(latency_ms)?,
+                    Entry::Vacant(entry) => {
+                        entry.insert(LatencyHistogram::new()?).record(latency_ms)?
+                    }
+                }
+            }
 
             if let Some(conn) = db_conn.as_ref() {
                 db_buffer.push(DbRecord {
@@ -181,10 +217,10 @@ pub fn setup_metrics_logger(
 
             if collect_records {
                 let seconds_elapsed = elapsed_ms / 1000;
-                let in_range = match &config.metrics_range {
-                    Some(MetricsRange(range)) => range.contains(&seconds_elapsed),
-                    None => true,
-                };
+                let in_range = config
+                    .metrics_range
+                    .as_ref()
+                    .is_none_or(|range| range.contains(seconds_elapsed));
                 if in_range {
                     if records.len() < config.metrics_max {
                         records.push(MetricRecord {
@@ -195,6 +231,7 @@ pub fn setup_metrics_logger(
                             transport_error: msg.transport_error,
                             response_bytes: msg.response_bytes,
                             in_flight_ops: msg.in_flight_ops,
+                            check_failed: msg.check_failure.is_some(),
                         });
                     } else {
                         metrics_truncated = true;
@@ -270,12 +307,18 @@ pub fn setup_metrics_logger(
                 success_min_latency_ms,
                 success_max_latency_ms,
                 success_avg_latency_ms,
+                http_versions,
+                error_reasons,
+                check_failures,
             },
             metrics_truncated,
             latency_sum_ms,
             success_latency_sum_ms,
             histogram,
             success_histogram,
+            status_histograms,
+            response_bytes,
+            status_codes,
         })
     })
 }
@@ -1,18 +1,26 @@
 //! Metrics collection, aggregation, and histogram utilities.
 mod collector;
+mod feedback;
+mod fixed_point;
 mod histogram;
 mod logging;
+mod modes;
 mod types;
 
 #[cfg(test)]
 mod tests;
 
 pub use collector::setup_metrics_collector;
+pub use feedback::RateFeedback;
+pub(crate) use fixed_point::{LOG_ONE, log2_fixed};
 pub use histogram::LatencyHistogram;
 pub use logging::{LogResult, LogSink, MetricsLoggerConfig, setup_metrics_logger};
+pub use modes::LatencyMode;
 pub use types::{
-    AggregatedMetricSample, MetricRecord, Metrics, MetricsRange, MetricsReport, MetricsSummary,
-    StreamSnapshot, StreamingChartData,
+    AggregatedMetricSample, CheckFailure, CheckFailures, ConnectionSplit, ConnectionTiming,
+    DrainReport, ErrorReason, InflightLatencyPoint, LatencySpike, MetricRecord, Metrics,
+    MetricsRange, MetricsReport, MetricsSummary, SlowRequest, StatusClass, StreamSnapshot,
+    StreamingChartData,
 };
 
 #[cfg(any(test, feature = "fuzzing"))]
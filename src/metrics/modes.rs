@@ -0,0 +1,194 @@
+//! Peak detection over the latency histogram, to flag multimodal latency.
+
+/// Sub-buckets per power of two; four gives bins roughly 19% wide.
+const BINS_PER_OCTAVE: u32 = 4;
+/// Fewer samples than this are too noisy to split into modes.
+const MIN_SAMPLES: u64 = 100;
+/// A mode must hold at least this share of samples (in hundredths of a percent).
+const MIN_WEIGHT_X100: u64 = 500;
+/// The valley between two peaks must drop below this percentage of the lower peak.
+const MAX_VALLEY_PERCENT: u64 = 50;
+
+/// One peak of the latency distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyMode {
+    /// Most common latency within the mode, in milliseconds.
+    pub peak_ms: u64,
+    pub low_ms: u64,
+    pub high_ms: u64,
+    pub count: u64,
+    /// Share of all samples, in hundredths of a percent.
+    pub weight_x100: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bin {
+    low_ms: u64,
+    high_ms: u64,
+    count: u64,
+}
+
+/// Finds the modes of a distribution given as `(latency_ms, count)` pairs in
+/// ascending latency order.
+///
+/// Samples are grouped into log-scale bins and smoothed over three bins. A
+/// local maximum only counts as its own mode when the valley separating it
+/// from the neighbouring peak falls below half of the lower peak, and modes
+/// holding less than 5% of the samples are folded into their neighbour.
+/// Returns a single mode for unimodal data and nothing for small samples.
+#[must_use]
+pub fn detect_modes(values: impl IntoIterator<Item = (u64, u64)>) -> Vec<LatencyMode> {
+    let bins = log_bins(values);
+    let total = bins
+        .iter()
+        .fold(0u64, |sum, bin| sum.saturating_add(bin.count));
+    if total < MIN_SAMPLES {
+        return Vec::new();
+    }
+    let smoothed: Vec<u64> = (0..bins.len())
+        .map(|idx| {
+            let start = idx.saturating_sub(1);
+            let end = idx.saturating_add(2).min(bins.len());
+            bins.get(start..end)
+                .unwrap_or(&[])
+                .iter()
+                .fold(0u64, |sum, bin| sum.saturating_add(bin.count))
+        })
+        .collect();
+
+    let mut bounds = split_points(&smoothed);
+    loop {
+        let modes = build_modes(&bins, &bounds, total);
+        let weakest = modes
+            .iter()
+            .enumerate()
+            .filter(|(_, mode)| mode.weight_x100 < MIN_WEIGHT_X100)
+            .min_by_key(|(_, mode)| mode.count)
+            .map(|(idx, _)| idx);
+        match weakest {
+            Some(idx) if !bounds.is_empty() => {
+                // Merge into the neighbour by dropping the nearest boundary.
+                let boundary = idx.min(bounds.len().saturating_sub(1));
+                bounds.remove(boundary);
+            }
+            _ => return modes,
+        }
+    }
+}
+
+/// Groups samples into bins about `2^(1/BINS_PER_OCTAVE)` wide.
+fn log_bins(values: impl IntoIterator<Item = (u64, u64)>) -> Vec<Bin> {
+    let mut bins: Vec<(u32, Bin)> = Vec::new();
+    for (value, count) in values {
+        if count == 0 {
+            continue;
+        }
+        let index = bin_index(value);
+        match bins.last_mut() {
+            Some((last_index, bin)) if *last_index == index => {
+                bin.count = bin.count.saturating_add(count);
+                bin.high_ms = value;
+            }
+            _ => bins.push((
+                index,
+                Bin {
+                    low_ms: value,
+                    high_ms: value,
+                    count,
+                },
+            )),
+        }
+    }
+    // Keep empty bins between occupied ones so gaps form valleys.
+    let mut dense: Vec<Bin> = Vec::new();
+    let mut previous: Option<u32> = None;
+    for (index, bin) in bins {
+        if let Some(prev) = previous {
+            let gap = index.saturating_sub(prev).saturating_sub(1);
+            for _ in 0..gap {
+                dense.push(Bin {
+                    low_ms: bin.low_ms,
+                    high_ms: bin.low_ms,
+                    count: 0,
+                });
+            }
+        }
+        previous = Some(index);
+        dense.push(bin);
+    }
+    dense
+}
+
+fn bin_index(value: u64) -> u32 {
+    let value = value.max(1);
+    let octave = value.ilog2();
+    let sub_bits = BINS_PER_OCTAVE.ilog2();
+    let fraction = if octave >= sub_bits {
+        value >> octave.saturating_sub(sub_bits)
+    } else {
+        value << sub_bits.saturating_sub(octave)
+    };
+    let sub = u32::try_from(fraction & u64::from(BINS_PER_OCTAVE.saturating_sub(1))).unwrap_or(0);
+    octave.saturating_mul(BINS_PER_OCTAVE).saturating_add(sub)
+}
+
+/// Bin indexes where one mode ends and the next begins (the valley minimum).
+fn split_points(smoothed: &[u64]) -> Vec<usize> {
+    let mut bounds = Vec::new();
+    let mut peak: Option<u64> = None;
+    let mut valley: Option<(usize, u64)> = None;
+    for (idx, &count) in smoothed.iter().enumerate() {
+        let Some(peak_count) = peak else {
+            peak = Some(count);
+            continue;
+        };
+        match valley {
+            None if count >= peak_count => peak = Some(count),
+            None => valley = Some((idx, count)),
+            Some((_, valley_count)) if count < valley_count => valley = Some((idx, count)),
+            Some((valley_idx, valley_count)) => {
+                let lower_peak = peak_count.min(count);
+                let separated = valley_count.saturating_mul(100)
+                    < lower_peak.saturating_mul(MAX_VALLEY_PERCENT);
+                if separated {
+                    bounds.push(valley_idx);
+                    peak = Some(count);
+                    valley = None;
+                } else if count >= peak_count {
+                    peak = Some(count);
+                    valley = None;
+                }
+            }
+        }
+    }
+    bounds
+}
+
+fn build_modes(bins: &[Bin], bounds: &[usize], total: u64) -> Vec<LatencyMode> {
+    let mut modes = Vec::with_capacity(bounds.len().saturating_add(1));
+    let mut start = 0usize;
+    for end in bounds.iter().copied().chain(std::iter::once(bins.len())) {
+        let slice = bins.get(start..end).unwrap_or(&[]);
+        let occupied = || slice.iter().filter(|bin| bin.count > 0);
+        if let (Some(first), Some(last), Some(peak)) = (
+            occupied().next(),
+            occupied().next_back(),
+            occupied().max_by_key(|bin| bin.count),
+        ) {
+            let count = occupied().fold(0u64, |sum, bin| sum.saturating_add(bin.count));
+            let weight_x100 = u128::from(count)
+                .saturating_mul(10_000)
+                .checked_div(u128::from(total))
+                .unwrap_or(0);
+            modes.push(LatencyMode {
+                peak_ms: peak.low_ms.midpoint(peak.high_ms),
+                low_ms: first.low_ms,
+                high_ms: last.high_ms,
+                count,
+                weight_x100: u64::try_from(weight_x100).unwrap_or(u64::MAX),
+            });
+        }
+        start = end;
+    }
+    modes
+}
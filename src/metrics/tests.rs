@@ -1,6 +1,10 @@
 use super::*;
-use crate::args::{HttpMethod, LoadMode, PositiveU64, PositiveUsize, Protocol, TesterArgs};
+use crate::args::{
+    ExpectedStatus, HttpMethod, LoadMode, PositiveU64, PositiveUsize, Protocol, TesterArgs,
+};
 use crate::error::{AppError, AppResult};
+use crate::run_context::RunContext;
+use crate::sinks::samples::SampleStream;
 use crate::ui::model::UiData;
 use std::future::Future;
 use std::time::Duration;
@@ -29,14 +33,27 @@ fn base_args() -> AppResult<TesterArgs> {
         replay_snapshot_end: None,
         replay_snapshot_out: None,
         replay_snapshot_format: "json".to_owned(),
+        replay_video: None,
+        replay_export_chart_data: None,
+        replay_export_charts: false,
         method: HttpMethod::Get,
         protocol: Protocol::Http,
+        grpc_mode: None,
+        ws_messages_per_conn: None,
+        ws_message_interval_ms: None,
+        mqtt_mode: crate::args::MqttMode::Publish,
+        mqtt_topic: None,
+        sse_events_per_conn: None,
+        sse_conn_duration: None,
+        sse_resume: false,
+        min_events: None,
         load_mode: LoadMode::Arrival,
         url: Some("http://localhost".to_owned()),
         urls_from_file: false,
         rand_regex_url: false,
         max_repeat: positive_usize(4)?,
         dump_urls: None,
+        dry_run: false,
         headers: vec![],
         accept_header: None,
         content_type: None,
@@ -44,32 +61,59 @@ fn base_args() -> AppResult<TesterArgs> {
         authorized: false,
         data: String::new(),
         form: vec![],
+        form_file: vec![],
         basic_auth: None,
+        basic_auth_file: None,
+        bearer_token: None,
+        bearer_token_file: None,
+        digest_auth: None,
+        oauth2_token_url: None,
+        oauth2_client_id: None,
+        oauth2_client_secret: None,
+        oauth2_scope: None,
         aws_session: None,
         aws_sigv4: None,
         data_file: None,
         data_lines: None,
+        data_dir: None,
+        data_dir_max_bytes: None,
+        seed: None,
+        json_template: false,
+        literal_placeholders: false,
         target_duration: positive_u64(1)?,
         wait_ongoing_requests_after_deadline: false,
+        align_to_second: false,
+        arrival_distribution: None,
         requests: None,
-        expected_status_code: 200,
+        expected_status_code: ExpectedStatus::single(200),
         request_timeout: Duration::from_secs(10),
         redirect_limit: 10,
         disable_keepalive: false,
+        requests_per_connection: None,
         disable_compression: false,
+        cookies: false,
+        session_cookie: None,
+        backend_header: "X-Backend".to_owned(),
         pool_max_idle_per_host: None,
         pool_idle_timeout_ms: None,
         http_version: None,
         connect_timeout: Duration::from_secs(5),
         charts_path: "./charts".to_owned(),
         no_charts: false,
+        no_final_charts: false,
         charts_latency_bucket_ms: positive_u64(100)?,
+        chart_width: 1_600,
+        chart_height: 600,
+        chart_dpi: 96,
+        usl_chart: false,
         verbose: false,
         config: None,
+        config_profile: None,
         tmp_path: "./tmp".to_owned(),
         load_profile: None,
         controller_listen: None,
         controller_mode: crate::args::ControllerMode::Auto,
+        controller_also_runs: false,
         control_listen: None,
         control_auth_token: None,
         agent_join: None,
@@ -82,20 +126,48 @@ fn base_args() -> AppResult<TesterArgs> {
         agent_reconnect_ms: positive_u64(1000)?,
         agent_heartbeat_interval_ms: positive_u64(1000)?,
         agent_heartbeat_timeout_ms: positive_u64(3000)?,
+        agent_health_listen: None,
         keep_tmp: false,
         warmup: None,
+        warmup_requests_file: None,
+        echo_config_on_start: false,
+        echo_config_format: crate::args::EchoConfigFormat::Text,
         output: None,
         output_format: None,
         time_unit: None,
         export_csv: None,
         export_json: None,
         export_jsonl: None,
+        export_histogram: None,
         db_url: None,
+        checkpoint_out: None,
+        checkpoint_interval_secs: None,
+        stream_samples: None,
+        resume: false,
         log_shards: positive_usize(1)?,
         no_ui: true,
         no_splash: true,
+        hold_ui: false,
+        stop_file: None,
         ui_window_ms: positive_u64(10_000)?,
+        baseline: None,
+        baseline_label: None,
         summary: false,
+        summary_template: None,
+        summary_out: None,
+        history_file: "./history.jsonl".to_owned(),
+        no_history: true,
+        notify_webhook: None,
+        notify_on: crate::args::NotifyOn::Always,
+        protocol_version_report: false,
+        connection_timing: false,
+        strict_charts: false,
+        per_status_latency: false,
+        slowest: None,
+        summary_percentile_table: false,
+        stats_extended: false,
+        compat: None,
+        summary_format: None,
         show_selections: false,
         tls_min: None,
         tls_max: None,
@@ -117,11 +189,39 @@ fn base_args() -> AppResult<TesterArgs> {
         rate_limit: None,
         burst_delay: None,
         burst_rate: positive_usize(1)?,
+        find_max_rate: false,
+        target_p99_ms: None,
         latency_correction: false,
+        max_bandwidth: None,
+        sim_downlink: None,
+        sim_latency: None,
+        connect_rate: None,
+        max_bytes: None,
+        abort_on_errors: None,
+        abort_on_error_rate: None,
+        abort_min_requests: positive_u64(100)?,
+        verify_request_id_echo: false,
+        request_id_header: "X-Request-Id".to_owned(),
+        deadline_header: None,
+        deadline_format: crate::args::DeadlineFormat::RelativeMs,
+        correlation_header: None,
+        correlation_log: None,
+        rate_schedule: None,
+        assert_response_bytes_below: None,
+        assert_response_bytes_above: None,
+        assert_body_contains: None,
+        assert_body_regex: None,
+        assert_json: None,
+        assert_success_rate: None,
+        assert_p99_ms: None,
+        assert_max_errors: None,
+        diagnose: false,
+        diagnose_strict: false,
         connect_to: vec![],
         host_header: None,
         ipv6_only: false,
         ipv4_only: false,
+        doh_url: None,
         no_pre_lookup: false,
         no_color: false,
         ui_fps: 16,
@@ -133,6 +233,8 @@ fn base_args() -> AppResult<TesterArgs> {
         alloc_profiler_ms: None,
         alloc_profiler_dump_ms: None,
         alloc_profiler_dump_path: "./alloc-prof".to_owned(),
+        alloc_profiler_spike_ms: None,
+        alloc_profiler_spike_inflight: None,
         scenario: None,
         script: None,
         plugin: vec![],
@@ -140,8 +242,12 @@ fn base_args() -> AppResult<TesterArgs> {
         uninstall_service: false,
         service_name: None,
         sinks: None,
+        tags: Vec::new(),
         distributed_silent: false,
+        run_id: None,
         distributed_stream_summaries: false,
+        agent_breakdown: false,
+        per_agent_summary: false,
         distributed_stream_interval_ms: None,
     })
 }
@@ -167,11 +273,14 @@ fn shuts_down_on_timer_without_metrics() -> AppResult<()> {
 
         let handle = setup_metrics_collector(
             &args,
+            &RunContext::default(),
+            None,
             tokio::time::Instant::now(),
             &shutdown_tx,
             metrics_rx,
             &_ui_tx,
             None,
+            None,
         );
         drop(metrics_tx);
 
@@ -214,8 +323,9 @@ fn read_metrics_log_respects_range() -> AppResult<()> {
             .await
             .map_err(|err| AppError::metrics(format!("Failed to flush log: {}", err)))?;
 
-        let range = Some(MetricsRange(0..=0));
-        let result = read_metrics_log(&log_path, 200, &range, 10, None).await?;
+        let range = Some(MetricsRange(vec![0..=0]));
+        let result =
+            read_metrics_log(&log_path, ExpectedStatus::single(200), &range, 10, None).await?;
 
         if result.records.len() == 1 && result.summary.total_requests == 2 {
             Ok(())
@@ -229,6 +339,32 @@ fn read_metrics_log_respects_range() -> AppResult<()> {
     })
 }
 
+#[test]
+fn read_metrics_log_counts_any_expected_status_as_success() -> AppResult<()> {
+    run_async_test(async {
+        let dir = tempfile::tempdir()
+            .map_err(|err| AppError::metrics(format!("tempdir failed: {}", err)))?;
+        let log_path = dir.path().join("metrics.log");
+        tokio::fs::write(&log_path, b"100,5,200\n200,6,204\n300,7,302\n400,8,500\n")
+            .await
+            .map_err(|err| AppError::metrics(format!("Failed to write log: {}", err)))?;
+
+        let expected = "200,204,301-302"
+            .parse::<ExpectedStatus>()
+            .map_err(AppError::from)?;
+        let result = read_metrics_log(&log_path, expected, &None, 10, None).await?;
+
+        if result.summary.successful_requests == 3 && result.summary.non_expected_status == 1 {
+            Ok(())
+        } else {
+            Err(AppError::metrics(format!(
+                "Expected 3 successes and 1 non-expected status, got {} and {}",
+                result.summary.successful_requests, result.summary.non_expected_status
+            )))
+        }
+    })
+}
+
 #[test]
 fn read_metrics_log_parses_flow_fields() -> AppResult<()> {
     run_async_test(async {
@@ -245,7 +381,8 @@ fn read_metrics_log_parses_flow_fields() -> AppResult<()> {
             .await
             .map_err(|err| AppError::metrics(format!("Failed to flush log: {}", err)))?;
 
-        let result = read_metrics_log(&log_path, 200, &None, 10, None).await?;
+        let result =
+            read_metrics_log(&log_path, ExpectedStatus::single(200), &None, 10, None).await?;
         let record = result
             .records
             .first()
@@ -272,11 +409,14 @@ fn updates_ui_data_on_tick() -> AppResult<()> {
 
         let handle = setup_metrics_collector(
             &args,
+            &RunContext::default(),
+            None,
             tokio::time::Instant::now(),
             &shutdown_tx,
             metrics_rx,
             &ui_tx,
             None,
+            None,
         );
 
         tokio::time::sleep(Duration::from_millis(150)).await;
@@ -288,6 +428,12 @@ fn updates_ui_data_on_tick() -> AppResult<()> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            error_message: None,
+            http_version: None,
+            url: None,
+            connection: None,
+            error_reason: None,
+            check_failure: None,
         }) {
             Ok(()) => {}
             Err(err) => {
@@ -328,6 +474,87 @@ fn updates_ui_data_on_tick() -> AppResult<()> {
     })
 }
 
+#[test]
+fn ui_error_samples_are_deduplicated_and_bounded() -> AppResult<()> {
+    run_async_test(async {
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(5)?;
+
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (ui_tx, mut ui_rx) = watch::channel(UiData::default());
+        let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(32);
+
+        let handle = setup_metrics_collector(
+            &args,
+            &RunContext::default(),
+            None,
+            tokio::time::Instant::now(),
+            &shutdown_tx,
+            metrics_rx,
+            &ui_tx,
+            None,
+            None,
+        );
+
+        let failure = |message: &str| {
+            Metrics::new(tokio::time::Instant::now(), 500, false, true, 0, 0)
+                .with_error_message(Some(std::sync::Arc::from(message)))
+        };
+        let mut batch = vec![failure("connection refused"), failure("connection refused")];
+        for index in 0..4 {
+            batch.push(failure(&format!("dns error {}", index)));
+        }
+        batch.push(failure("connection refused"));
+        batch.push(Metrics::new(
+            tokio::time::Instant::now(),
+            503,
+            false,
+            false,
+            0,
+            0,
+        ));
+        for metric in batch {
+            if metrics_tx.send(metric).await.is_err() {
+                return Err(AppError::metrics("Failed to send metric"));
+            }
+        }
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        ui_rx
+            .changed()
+            .await
+            .map_err(|err| AppError::metrics(format!("UI channel closed: {}", err)))?;
+        let samples = ui_rx.borrow().error_samples.clone();
+        let summary: Vec<(&str, u64)> = samples
+            .iter()
+            .map(|sample| (sample.message.as_str(), sample.count))
+            .collect();
+        let expected = vec![
+            ("HTTP 503 Service Unavailable", 1),
+            ("connection refused", 3),
+            ("dns error 3", 1),
+            ("dns error 2", 1),
+            ("dns error 1", 1),
+        ];
+        if summary != expected {
+            return Err(AppError::metrics(format!(
+                "Unexpected error samples: {:?}",
+                summary
+            )));
+        }
+
+        if shutdown_tx.send(()).is_err() {
+            return Err(AppError::metrics("Failed to send shutdown"));
+        }
+        drop(metrics_tx);
+        tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .map_err(|err| AppError::metrics(format!("Timed out waiting for collector: {}", err)))?
+            .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+        Ok(())
+    })
+}
+
 #[test]
 fn read_metrics_log_respects_metrics_max() -> AppResult<()> {
     run_async_test(async {
@@ -344,7 +571,8 @@ fn read_metrics_log_respects_metrics_max() -> AppResult<()> {
             .await
             .map_err(|err| AppError::metrics(format!("Failed to flush log: {}", err)))?;
 
-        let result = read_metrics_log(&log_path, 200, &None, 0, None).await?;
+        let result =
+            read_metrics_log(&log_path, ExpectedStatus::single(200), &None, 0, None).await?;
         if !result.records.is_empty() {
             return Err(AppError::metrics(
                 "Expected no records when metrics_max is 0",
@@ -376,7 +604,8 @@ fn read_metrics_log_marks_truncated() -> AppResult<()> {
             .await
             .map_err(|err| AppError::metrics(format!("Failed to flush log: {}", err)))?;
 
-        let result = read_metrics_log(&log_path, 200, &None, 2, None).await?;
+        let result =
+            read_metrics_log(&log_path, ExpectedStatus::single(200), &None, 2, None).await?;
         if !result.metrics_truncated {
             return Err(AppError::metrics("Expected metrics_truncated to be true"));
         }
@@ -406,7 +635,8 @@ fn read_metrics_log_empty_file() -> AppResult<()> {
             .await
             .map_err(|err| AppError::metrics(format!("Failed to create log: {}", err)))?;
 
-        let result = read_metrics_log(&log_path, 200, &None, 10, None).await?;
+        let result =
+            read_metrics_log(&log_path, ExpectedStatus::single(200), &None, 10, None).await?;
         if !result.records.is_empty() {
             return Err(AppError::metrics("Expected no records"));
         }
@@ -432,10 +662,11 @@ fn metrics_logger_summarizes_and_limits_records() -> AppResult<()> {
         let logger_config = MetricsLoggerConfig {
             run_start,
             warmup: None,
-            expected_status_code: 200,
+            expected_status_code: ExpectedStatus::single(200),
             metrics_range: None,
             metrics_max: 1,
             db_url: Some(db_path.to_string_lossy().to_string()),
+            per_status_latency: true,
         };
         let handle = setup_metrics_logger(log_path, logger_config, rx);
 
@@ -447,6 +678,12 @@ fn metrics_logger_summarizes_and_limits_records() -> AppResult<()> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            error_message: None,
+            http_version: Some("HTTP/2"),
+            url: None,
+            connection: None,
+            error_reason: None,
+            check_failure: None,
         };
         let second_start = run_start
             .checked_add(Duration::from_millis(10))
@@ -459,6 +696,12 @@ fn metrics_logger_summarizes_and_limits_records() -> AppResult<()> {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            error_message: None,
+            http_version: None,
+            url: None,
+            connection: None,
+            error_reason: None,
+            check_failure: None,
         };
 
         if tx.send(first).await.is_err() {
@@ -492,6 +735,25 @@ fn metrics_logger_summarizes_and_limits_records() -> AppResult<()> {
                 result.summary.timeout_requests
             )));
         }
+        if result.summary.http_versions.get("HTTP/2") != Some(&1)
+            || result.summary.http_versions.len() != 1
+        {
+            return Err(AppError::metrics(format!(
+                "Expected one HTTP/2 response, got {:?}",
+                result.summary.http_versions
+            )));
+        }
+        let status_counts: Vec<(StatusClass, u64)> = result
+            .status_histograms
+            .iter()
+            .map(|(class, histogram)| (*class, histogram.count()))
+            .collect();
+        if status_counts != [(StatusClass::Success, 1)] {
+            return Err(AppError::metrics(format!(
+                "Expected only the 2xx response in status histograms, got {:?}",
+                status_counts
+            )));
+        }
         if result.records.len() != 1 {
             return Err(AppError::metrics(format!(
                 "Expected 1 record due to metrics_max, got {}",
@@ -512,3 +774,803 @@ fn metrics_logger_summarizes_and_limits_records() -> AppResult<()> {
         Ok(())
     })
 }
+
+#[test]
+fn writes_periodic_and_final_checkpoints() -> AppResult<()> {
+    run_async_test(async {
+        let dir = tempfile::tempdir()
+            .map_err(|err| AppError::metrics(format!("tempdir failed: {}", err)))?;
+        let path = dir.path().join("checkpoint.json");
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(5)?;
+        args.checkpoint_out = Some(path.to_string_lossy().into_owned());
+        args.checkpoint_interval_secs = Some(positive_u64(1)?);
+
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (ui_tx, _ui_rx) = watch::channel(UiData::default());
+        let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
+        let handle = setup_metrics_collector(
+            &args,
+            &RunContext::default(),
+            None,
+            tokio::time::Instant::now(),
+            &shutdown_tx,
+            metrics_rx,
+            &ui_tx,
+            None,
+            None,
+        );
+        metrics_tx
+            .send(Metrics {
+                start: tokio::time::Instant::now(),
+                response_time: Duration::from_millis(12),
+                status_code: 200,
+                timed_out: false,
+                transport_error: false,
+                response_bytes: 0,
+                in_flight_ops: 0,
+                error_message: None,
+                http_version: None,
+                url: None,
+                connection: None,
+                error_reason: None,
+                check_failure: None,
+            })
+            .await
+            .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+
+        tokio::time::sleep(Duration::from_millis(1300)).await;
+        let read_checkpoint = || -> AppResult<serde_json::Value> {
+            let bytes = std::fs::read(&path)
+                .map_err(|err| AppError::metrics(format!("read checkpoint: {}", err)))?;
+            Ok(serde_json::from_slice(&bytes)?)
+        };
+        let periodic = read_checkpoint()?;
+        if periodic
+            .get("total_requests")
+            .and_then(serde_json::Value::as_u64)
+            != Some(1)
+            || periodic
+                .get("complete")
+                .and_then(serde_json::Value::as_bool)
+                != Some(false)
+        {
+            return Err(AppError::metrics(format!(
+                "Unexpected periodic checkpoint: {}",
+                periodic
+            )));
+        }
+
+        if shutdown_tx.send(()).is_err() {
+            return Err(AppError::metrics("Failed to send shutdown"));
+        }
+        drop(metrics_tx);
+        tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .map_err(|err| {
+                AppError::metrics(format!(
+                    "Timed out waiting for collector to finish: {}",
+                    err
+                ))
+            })?
+            .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+
+        let final_checkpoint = read_checkpoint()?;
+        if final_checkpoint
+            .get("complete")
+            .and_then(serde_json::Value::as_bool)
+            != Some(true)
+        {
+            return Err(AppError::metrics(
+                "Expected final checkpoint to be complete",
+            ));
+        }
+        if dir.path().join("checkpoint.json.tmp").exists() {
+            return Err(AppError::metrics("Temp checkpoint file was left behind"));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn failed_checks_keep_the_real_status_and_count_as_errors() -> AppResult<()> {
+    run_async_test(async {
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(10)?;
+        let (shutdown_tx, _shutdown_rx) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (ui_tx, ui_rx) = watch::channel(UiData::default());
+        let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
+        let handle = setup_metrics_collector(
+            &args,
+            &RunContext::default(),
+            None,
+            tokio::time::Instant::now(),
+            &shutdown_tx,
+            metrics_rx,
+            &ui_tx,
+            None,
+            None,
+        );
+        let now = tokio::time::Instant::now();
+        let mismatch = Metrics::new(now, 200, false, false, 0, 0)
+            .with_check_failure(Some(CheckFailure::RequestIdMismatch));
+        if mismatch.is_success(args.expected_status_code) {
+            return Err(AppError::metrics("A failed check counted as success"));
+        }
+        let body_assert = Metrics::new(now, 200, false, false, 0, 0)
+            .with_check_failure(Some(CheckFailure::BodyAssertion));
+        for metric in [
+            mismatch,
+            body_assert,
+            Metrics::new(now, 200, false, false, 0, 0),
+        ] {
+            metrics_tx
+                .send(metric)
+                .await
+                .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(metrics_tx);
+        let report = tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .map_err(|err| AppError::metrics(format!("Collector timed out: {}", err)))?
+            .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+
+        let summary = report.summary;
+        let status_2xx = ui_rx
+            .borrow()
+            .status_counts
+            .as_ref()
+            .map_or(0, |counts| counts.status_2xx);
+        if summary.total_requests != 3
+            || summary.successful_requests != 1
+            || summary.error_requests != 2
+            || summary.non_expected_status != 0
+            || summary.check_failures.request_id_mismatches != 1
+            || summary.check_failures.assertion_failures != 1
+            || status_2xx != 3
+        {
+            return Err(AppError::metrics(format!(
+                "Unexpected check failure accounting (2xx {}): {:?}",
+                status_2xx, summary
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn resume_continues_totals_from_a_checkpoint() -> AppResult<()> {
+    run_async_test(async {
+        let dir = tempfile::tempdir()
+            .map_err(|err| AppError::metrics(format!("tempdir failed: {}", err)))?;
+        let path = dir.path().join("checkpoint.json");
+        let path_str = path.to_string_lossy().into_owned();
+        let metric = |latency_ms: u64| Metrics {
+            start: tokio::time::Instant::now(),
+            response_time: Duration::from_millis(latency_ms),
+            status_code: 200,
+            timed_out: false,
+            transport_error: false,
+            response_bytes: 0,
+            in_flight_ops: 0,
+            error_message: None,
+            http_version: Some("HTTP/1.1"),
+            url: None,
+            connection: None,
+            error_reason: None,
+            check_failure: Some(CheckFailure::RequestIdMismatch),
+        };
+        let run_collector = |args: TesterArgs, run: RunContext, latency_ms: u64| async move {
+            let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+            let (ui_tx, _ui_rx) = watch::channel(UiData::default());
+            let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
+            let handle = setup_metrics_collector(
+                &args,
+                &run,
+                None,
+                tokio::time::Instant::now(),
+                &shutdown_tx,
+                metrics_rx,
+                &ui_tx,
+                None,
+                None,
+            );
+            metrics_tx
+                .send(metric(latency_ms))
+                .await
+                .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            if shutdown_tx.send(()).is_err() {
+                return Err(AppError::metrics("Failed to send shutdown"));
+            }
+            drop(metrics_tx);
+            tokio::time::timeout(Duration::from_secs(2), handle)
+                .await
+                .map_err(|err| AppError::metrics(format!("Collector timed out: {}", err)))?
+                .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))
+        };
+
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(60)?;
+        args.checkpoint_out = Some(path_str.clone());
+        run_collector(args.clone(), RunContext::default(), 40).await?;
+
+        let resume = crate::sinks::checkpoint::load_resume_state(&path_str).await?;
+        if resume.total_requests != 1 || resume.latency_sum_ms != 40 {
+            return Err(AppError::metrics(format!(
+                "Unexpected resume state: {:?}",
+                resume
+            )));
+        }
+        let prior = resume.duration();
+        let run = RunContext {
+            resume_state: Some(std::sync::Arc::new(resume)),
+            ..RunContext::default()
+        };
+        let report = run_collector(args, run, 10).await?;
+
+        let summary = report.summary;
+        if summary.total_requests != 2
+            || summary.min_latency_ms != 10
+            || summary.max_latency_ms != 40
+            || summary.avg_latency_ms != 25
+            || summary.http_versions.get("HTTP/1.1") != Some(&2)
+            || summary.check_failures.request_id_mismatches != 2
+            || summary.duration <= prior
+        {
+            return Err(AppError::metrics(format!(
+                "Expected the resumed run to continue the checkpoint: {:?}",
+                summary
+            )));
+        }
+        let continued = crate::sinks::checkpoint::load_resume_state(&path_str).await?;
+        let (histogram, _) = continued.histograms()?;
+        if continued.total_requests != 2 || histogram.count() != 2 {
+            return Err(AppError::metrics(format!(
+                "Expected the new checkpoint to include both runs: {:?}",
+                continued
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn stream_samples_appends_json_lines() -> AppResult<()> {
+    run_async_test(async {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("samples.jsonl");
+        let prior_line = "{\"elapsed_ms\":0}";
+        std::fs::write(&path, format!("{}\n", prior_line))?;
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(10)?;
+        let path_str = path.display().to_string();
+        let sample_stream = SampleStream::open(&path_str).await?;
+        args.stream_samples = Some(path_str);
+
+        let (shutdown_tx, _shutdown_rx) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (ui_tx, _ui_rx) = watch::channel(UiData::default());
+        let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
+        let handle = setup_metrics_collector(
+            &args,
+            &RunContext::default(),
+            Some(sample_stream),
+            tokio::time::Instant::now(),
+            &shutdown_tx,
+            metrics_rx,
+            &ui_tx,
+            None,
+            None,
+        );
+        for status_code in [200, 500] {
+            metrics_tx
+                .send(Metrics::new(
+                    tokio::time::Instant::now(),
+                    status_code,
+                    false,
+                    false,
+                    0,
+                    0,
+                ))
+                .await
+                .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+        }
+        drop(metrics_tx);
+        tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .map_err(|err| {
+                AppError::metrics(format!(
+                    "Timed out waiting for collector to finish: {}",
+                    err
+                ))
+            })?
+            .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+
+        let contents = std::fs::read_to_string(&path)?;
+        if contents.lines().next() != Some(prior_line) {
+            return Err(AppError::metrics(format!(
+                "Expected existing samples to be kept: {}",
+                contents
+            )));
+        }
+        let samples = contents
+            .lines()
+            .map(serde_json::from_str::<serde_json::Value>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let last = samples
+            .last()
+            .ok_or_else(|| AppError::metrics("Expected at least one sample"))?;
+        if last.get("total_requests") != Some(&serde_json::json!(2))
+            || last.get("error_requests") != Some(&serde_json::json!(1))
+            || last.get("p99_latency_ms").is_none()
+        {
+            return Err(AppError::metrics(format!(
+                "Unexpected final sample: {}",
+                last
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn slow_sink_writes_do_not_stall_metric_ingestion() -> AppResult<()> {
+    use crate::sinks::config::{InfluxSinkConfig, PushgatewaySinkConfig, SinksConfig};
+
+    run_async_test(async {
+        for use_influx in [false, true] {
+            // Accepts writes and never answers them, like a hung Pushgateway or InfluxDB.
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+            let url = format!("http://{}", listener.local_addr()?);
+            let server = tokio::spawn(async move {
+                let mut held = Vec::new();
+                while let Ok((socket, _)) = listener.accept().await {
+                    held.push(socket);
+                }
+            });
+
+            let mut sinks = SinksConfig {
+                update_interval_ms: Some(10),
+                prometheus: None,
+                otel: None,
+                influx: None,
+                pushgateway: None,
+            };
+            if use_influx {
+                sinks.influx = Some(InfluxSinkConfig {
+                    path: None,
+                    url: Some(url),
+                    org: None,
+                    bucket: Some("strest".to_owned()),
+                    token: None,
+                    timeout_ms: Some(30_000),
+                });
+            } else {
+                sinks.pushgateway = Some(PushgatewaySinkConfig {
+                    url,
+                    job: "strest".to_owned(),
+                    timeout_ms: Some(30_000),
+                });
+            }
+            let mut args = base_args()?;
+            args.target_duration = positive_u64(10)?;
+            args.sinks = Some(sinks);
+            let dir = tempfile::tempdir()?;
+            let samples_path = dir.path().join("samples.jsonl").display().to_string();
+            let sample_stream = SampleStream::open(&samples_path).await?;
+
+            let (shutdown_tx, _shutdown_rx) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+            let (ui_tx, _ui_rx) = watch::channel(UiData::default());
+            let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(1);
+            let handle = setup_metrics_collector(
+                &args,
+                &RunContext::default(),
+                Some(sample_stream),
+                tokio::time::Instant::now(),
+                &shutdown_tx,
+                metrics_rx,
+                &ui_tx,
+                None,
+                None,
+            );
+            // Let the first write start and hang.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            for _ in 0..20 {
+                let metric = Metrics::new(tokio::time::Instant::now(), 200, false, false, 0, 0);
+                tokio::time::timeout(Duration::from_secs(2), metrics_tx.send(metric))
+                    .await
+                    .map_err(|err| {
+                        AppError::metrics(format!(
+                            "Collector stalled during a sink write (influx: {}): {}",
+                            use_influx, err
+                        ))
+                    })?
+                    .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+            }
+            drop(metrics_tx);
+            // Closing the held sockets fails the pending write so the collector can finish.
+            server.abort();
+            let report = tokio::time::timeout(Duration::from_secs(5), handle)
+                .await
+                .map_err(|err| {
+                    AppError::metrics(format!(
+                        "Timed out waiting for collector to finish: {}",
+                        err
+                    ))
+                })?
+                .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+            let samples = std::fs::read_to_string(&samples_path)?;
+            let last = samples
+                .lines()
+                .last()
+                .map(serde_json::from_str::<serde_json::Value>)
+                .transpose()?;
+            if report.summary.total_requests != 20
+                || last
+                    .as_ref()
+                    .and_then(|sample| sample.get("total_requests"))
+                    != Some(&serde_json::json!(20))
+            {
+                return Err(AppError::metrics(format!(
+                    "Expected 20 requests in the report and final sample, got {} and {:?}",
+                    report.summary.total_requests, last
+                )));
+            }
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn stops_when_byte_cap_is_reached() -> AppResult<()> {
+    run_async_test(async {
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(10)?;
+        args.max_bytes = Some("1KB".parse()?);
+
+        let (shutdown_tx, mut shutdown_rx) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (ui_tx, _ui_rx) = watch::channel(UiData::default());
+        let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
+        let handle = setup_metrics_collector(
+            &args,
+            &RunContext::default(),
+            None,
+            tokio::time::Instant::now(),
+            &shutdown_tx,
+            metrics_rx,
+            &ui_tx,
+            None,
+            None,
+        );
+        for _ in 0..3 {
+            metrics_tx
+                .send(Metrics {
+                    start: tokio::time::Instant::now(),
+                    response_time: Duration::from_millis(5),
+                    status_code: 200,
+                    timed_out: false,
+                    transport_error: false,
+                    response_bytes: 400,
+                    in_flight_ops: 0,
+                    error_message: None,
+                    http_version: None,
+                    url: None,
+                    connection: None,
+                    error_reason: None,
+                    check_failure: None,
+                })
+                .await
+                .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+        }
+
+        tokio::time::timeout(Duration::from_secs(2), shutdown_rx.recv())
+            .await
+            .map_err(|err| AppError::metrics(format!("Byte cap did not stop the run: {}", err)))?
+            .map_err(|err| AppError::metrics(format!("Shutdown recv error: {}", err)))?;
+        let report = tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .map_err(|err| {
+                AppError::metrics(format!(
+                    "Timed out waiting for collector to finish: {}",
+                    err
+                ))
+            })?
+            .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+        if report.summary.total_requests != 3 {
+            return Err(AppError::metrics(format!(
+                "Expected 3 requests, got {}",
+                report.summary.total_requests
+            )));
+        }
+        match report.stop_reason.as_deref() {
+            Some(reason) if reason.contains("byte cap") => Ok(()),
+            other => Err(AppError::metrics(format!(
+                "Unexpected stop reason: {:?}",
+                other
+            ))),
+        }
+    })
+}
+
+#[test]
+fn aborts_when_error_breaker_trips() -> AppResult<()> {
+    run_async_test(async {
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(10)?;
+        args.abort_on_errors = Some(positive_u64(2)?);
+
+        let (shutdown_tx, mut shutdown_rx) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (ui_tx, _ui_rx) = watch::channel(UiData::default());
+        let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
+        let handle = setup_metrics_collector(
+            &args,
+            &RunContext::default(),
+            None,
+            tokio::time::Instant::now(),
+            &shutdown_tx,
+            metrics_rx,
+            &ui_tx,
+            None,
+            None,
+        );
+        for status_code in [200, 500, 200, 503] {
+            metrics_tx
+                .send(Metrics::new(
+                    tokio::time::Instant::now(),
+                    status_code,
+                    false,
+                    false,
+                    0,
+                    0,
+                ))
+                .await
+                .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+        }
+
+        tokio::time::timeout(Duration::from_secs(2), shutdown_rx.recv())
+            .await
+            .map_err(|err| AppError::metrics(format!("Breaker did not stop the run: {}", err)))?
+            .map_err(|err| AppError::metrics(format!("Shutdown recv error: {}", err)))?;
+        let report = tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .map_err(|err| {
+                AppError::metrics(format!(
+                    "Timed out waiting for collector to finish: {}",
+                    err
+                ))
+            })?
+            .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+        if report.stop_reason.is_some() || report.summary.total_requests != 4 {
+            return Err(AppError::metrics(format!(
+                "Unexpected report: stop_reason={:?} total={}",
+                report.stop_reason, report.summary.total_requests
+            )));
+        }
+        match report.abort_reason.as_deref() {
+            Some("2 errors reached --abort-on-errors 2") => Ok(()),
+            other => Err(AppError::metrics(format!(
+                "Unexpected abort reason: {:?}",
+                other
+            ))),
+        }
+    })
+}
+
+#[test]
+fn wait_ongoing_drain_reports_late_and_abandoned_requests() -> AppResult<()> {
+    run_async_test(async {
+        let mut args = base_args()?;
+        args.target_duration = positive_u64(10)?;
+        args.wait_ongoing_requests_after_deadline = true;
+
+        let (shutdown_tx, _shutdown_rx) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (ui_tx, _ui_rx) = watch::channel(UiData::default());
+        let (metrics_tx, metrics_rx) = tokio::sync::mpsc::channel::<Metrics>(10);
+        let handle = setup_metrics_collector(
+            &args,
+            &RunContext::default(),
+            None,
+            tokio::time::Instant::now(),
+            &shutdown_tx,
+            metrics_rx,
+            &ui_tx,
+            None,
+            None,
+        );
+        let started = tokio::time::Instant::now();
+        let metric = |in_flight_ops: u64, timed_out: bool, response_ms: u64| Metrics {
+            start: started,
+            response_time: Duration::from_millis(response_ms),
+            status_code: 200,
+            timed_out,
+            transport_error: false,
+            response_bytes: 0,
+            in_flight_ops,
+            error_message: None,
+            http_version: None,
+            url: None,
+            connection: None,
+            error_reason: None,
+            check_failure: None,
+        };
+        metrics_tx
+            .send(metric(3, false, 5))
+            .await
+            .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(shutdown_tx.send(()));
+        // Arrive after the deadline; the collector waits for them. The last one
+        // finished before the deadline and was only queued, so it is not late.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        for late in [
+            metric(2, false, 300),
+            metric(1, true, 300),
+            metric(1, false, 5),
+        ] {
+            metrics_tx
+                .send(late)
+                .await
+                .map_err(|err| AppError::metrics(format!("Failed to send metric: {}", err)))?;
+        }
+        drop(metrics_tx);
+
+        let report = tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .map_err(|err| {
+                AppError::metrics(format!(
+                    "Timed out waiting for collector to finish: {}",
+                    err
+                ))
+            })?
+            .map_err(|err| AppError::metrics(format!("Collector join error: {}", err)))?;
+        let expected = DrainReport {
+            in_flight_at_deadline: 3,
+            late_completions: 1,
+            abandoned: 2,
+        };
+        if report.drain != Some(expected) || report.summary.total_requests != 4 {
+            return Err(AppError::metrics(format!(
+                "Unexpected drain report: {:?} ({} requests)",
+                report.drain, report.summary.total_requests
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn extended_percentiles_fall_back_to_max_with_few_samples() -> AppResult<()> {
+    let mut histogram = LatencyHistogram::new()?;
+    for latency in 1..=100 {
+        histogram.record(latency)?;
+    }
+    let (_, _, _, p999, p9999) = histogram.percentiles_extended();
+    if p999 != 100 || p9999 != 100 {
+        return Err(AppError::metrics(format!(
+            "Expected max fallback of 100ms, got p999={} p9999={}",
+            p999, p9999
+        )));
+    }
+
+    for _ in 0..10_000 {
+        histogram.record(5)?;
+    }
+    let (p50, _, _, tail_p999, tail_p9999) = histogram.percentiles_extended();
+    if p50 != 5 || tail_p999 >= 100 || tail_p9999 < tail_p999 {
+        return Err(AppError::metrics(format!(
+            "Unexpected percentiles: p50={} p999={} p9999={}",
+            p50, tail_p999, tail_p9999
+        )));
+    }
+
+    if LatencyHistogram::new()?.percentiles_extended() != (0, 0, 0, 0, 0) {
+        return Err(AppError::metrics("Expected zeros for an empty histogram"));
+    }
+    Ok(())
+}
+
+#[test]
+fn latency_modes_separate_cache_hits_from_misses() -> AppResult<()> {
+    let mut histogram = LatencyHistogram::new()?;
+    // Cache hits around 4-6ms, misses around 180-220ms.
+    for latency in [4, 5, 5, 5, 6, 5, 4, 6, 5, 5] {
+        for _ in 0..70 {
+            histogram.record(latency)?;
+        }
+    }
+    for latency in [180, 190, 200, 200, 210, 220] {
+        for _ in 0..50 {
+            histogram.record(latency)?;
+        }
+    }
+    let modes = histogram.modes();
+    let summary: Vec<(u64, u64)> = modes
+        .iter()
+        .map(|mode| (mode.peak_ms, mode.weight_x100))
+        .collect();
+    let [fast, slow] = modes.as_slice() else {
+        return Err(AppError::metrics(format!(
+            "Expected two modes, got {:?}",
+            summary
+        )));
+    };
+    if fast.peak_ms != 5
+        || !(190..=210).contains(&slow.peak_ms)
+        || fast.count != 700
+        || slow.count != 300
+        || fast.weight_x100 != 7_000
+    {
+        return Err(AppError::metrics(format!("Unexpected modes: {:?}", modes)));
+    }
+
+    let mut unimodal = LatencyHistogram::new()?;
+    for (latency, count) in [(40, 10), (45, 40), (50, 100), (55, 40), (60, 10), (90, 3)] {
+        for _ in 0..count {
+            unimodal.record(latency)?;
+        }
+    }
+    let single = unimodal.modes();
+    if single.len() != 1 || single.first().map(|mode| mode.count) != Some(203) {
+        return Err(AppError::metrics(format!(
+            "Expected one mode, got {:?}",
+            single
+        )));
+    }
+
+    let mut sparse = LatencyHistogram::new()?;
+    sparse.record(5)?;
+    sparse.record(500)?;
+    if !sparse.modes().is_empty() {
+        return Err(AppError::metrics("Expected no modes for a tiny sample"));
+    }
+    Ok(())
+}
+
+#[test]
+fn geomean_and_stddev_describe_the_distribution_shape() -> AppResult<()> {
+    let mut bimodal = LatencyHistogram::new()?;
+    let mut uniform = LatencyHistogram::new()?;
+    for _ in 0..500 {
+        bimodal.record(10)?;
+        bimodal.record(1_000)?;
+    }
+    for latency in 10..=1_000 {
+        uniform.record(latency)?;
+    }
+    // sqrt(10 * 1000) = 100ms; |10 - 505| = 495ms.
+    let geomean = bimodal.geomean_x100();
+    let stddev = bimodal.stddev_x100();
+    if !(9_990..=10_010).contains(&geomean) || !(49_400..=49_600).contains(&stddev) {
+        return Err(AppError::metrics(format!(
+            "Unexpected bimodal stats: geomean={} stddev={}",
+            geomean, stddev
+        )));
+    }
+    // Same mean, but a uniform spread has a far smaller deviation (~286ms).
+    let uniform_stddev = uniform.stddev_x100();
+    if !(28_500..=28_700).contains(&uniform_stddev) {
+        return Err(AppError::metrics(format!(
+            "Unexpected uniform stddev: {}",
+            uniform_stddev
+        )));
+    }
+
+    let mut constant = LatencyHistogram::new()?;
+    for _ in 0..10 {
+        constant.record(20)?;
+    }
+    let constant_geomean = constant.geomean_x100();
+    if constant.stddev_x100() != 0 || !(1_999..=2_001).contains(&constant_geomean) {
+        return Err(AppError::metrics(format!(
+            "Unexpected constant stats: geomean={} stddev={}",
+            constant_geomean,
+            constant.stddev_x100()
+        )));
+    }
+    let empty = LatencyHistogram::new()?;
+    if empty.geomean_x100() != 0 || empty.stddev_x100() != 0 {
+        return Err(AppError::metrics("Expected zeros for an empty histogram"));
+    }
+    Ok(())
+}
@@ -1,12 +1,14 @@
 use std::collections::BTreeMap;
 use std::ops::RangeInclusive;
+use std::sync::Arc;
 
+use crate::args::ExpectedStatus;
 use crate::error::ValidationError;
 use std::time::Duration;
 
 use tokio::time::Instant;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct Metrics {
     pub start: Instant,
     pub response_time: Duration,
@@ -15,6 +17,18 @@ pub struct Metrics {
     pub transport_error: bool,
     pub response_bytes: u64,
     pub in_flight_ops: u64,
+    /// Failure detail shown in the TUI error panel (transport error text).
+    pub error_message: Option<Arc<str>>,
+    /// Negotiated HTTP version of the response, e.g. `HTTP/2`; `None` for other protocols.
+    pub http_version: Option<&'static str>,
+    /// Request URL; only populated when `--slowest` is set.
+    pub url: Option<Arc<str>>,
+    /// Connect and first-byte timing; only populated with `--connection-timing`.
+    pub connection: Option<ConnectionTiming>,
+    /// Classified cause of a timeout or transport error.
+    pub error_reason: Option<ErrorReason>,
+    /// First response check that failed even though a response arrived.
+    pub check_failure: Option<CheckFailure>,
 }
 
 impl Metrics {
@@ -35,10 +49,330 @@ impl Metrics {
             transport_error,
             response_bytes,
             in_flight_ops,
+            error_message: None,
+            http_version: None,
+            url: None,
+            connection: None,
+            error_reason: None,
+            check_failure: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_error_message(mut self, error_message: Option<Arc<str>>) -> Self {
+        self.error_message = error_message;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_http_version(mut self, http_version: Option<&'static str>) -> Self {
+        self.http_version = http_version;
+        self
+    }
+
+    #[must_use]
+    pub fn with_url(mut self, url: Option<Arc<str>>) -> Self {
+        self.url = url;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_connection(mut self, connection: Option<ConnectionTiming>) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_error_reason(mut self, error_reason: Option<ErrorReason>) -> Self {
+        self.error_reason = error_reason;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_check_failure(mut self, check_failure: Option<CheckFailure>) -> Self {
+        self.check_failure = check_failure;
+        self
+    }
+
+    /// The request got an expected status and passed every response check.
+    #[must_use]
+    pub fn is_success(&self, expected_status_code: ExpectedStatus) -> bool {
+        expected_status_code.contains(self.status_code)
+            && !self.timed_out
+            && !self.transport_error
+            && self.check_failure.is_none()
+    }
+}
+
+/// Connection phases of one request, measured from when it was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionTiming {
+    /// The request opened a new connection instead of reusing a pooled one.
+    pub new_connection: bool,
+    /// Time spent on DNS, TCP and TLS; zero on a reused connection.
+    pub connect: Duration,
+    /// Time until the response headers arrived, connect included.
+    pub first_byte: Duration,
+}
+
+/// Totals of the requests that went over one kind of connection, in microseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionBucket {
+    pub requests: u64,
+    pub connect_sum_us: u64,
+    pub first_byte_sum_us: u64,
+    pub total_sum_us: u64,
+}
+
+impl ConnectionBucket {
+    fn record(&mut self, timing: &ConnectionTiming, total: Duration) {
+        self.requests = self.requests.saturating_add(1);
+        self.connect_sum_us = self.connect_sum_us.saturating_add(micros(timing.connect));
+        self.first_byte_sum_us = self
+            .first_byte_sum_us
+            .saturating_add(micros(timing.first_byte));
+        self.total_sum_us = self.total_sum_us.saturating_add(micros(total));
+    }
+
+    /// Average time to connect, in hundredths of a millisecond.
+    #[must_use]
+    pub fn avg_connect_ms_x100(&self) -> u64 {
+        self.avg_ms_x100(self.connect_sum_us)
+    }
+
+    /// Average time to first byte, in hundredths of a millisecond.
+    #[must_use]
+    pub fn avg_first_byte_ms_x100(&self) -> u64 {
+        self.avg_ms_x100(self.first_byte_sum_us)
+    }
+
+    /// Average total latency, in hundredths of a millisecond.
+    #[must_use]
+    pub fn avg_total_ms_x100(&self) -> u64 {
+        self.avg_ms_x100(self.total_sum_us)
+    }
+
+    fn avg_ms_x100(&self, sum_us: u64) -> u64 {
+        sum_us
+            .checked_div(self.requests)
+            .and_then(|avg_us| avg_us.checked_div(MICROS_PER_MS_X100))
+            .unwrap_or(0)
+    }
+}
+
+/// Microseconds per hundredth of a millisecond.
+const MICROS_PER_MS_X100: u64 = 10;
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+/// Requests split by whether they opened a new connection or reused one,
+/// for `--connection-timing`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionSplit {
+    pub new: ConnectionBucket,
+    pub reused: ConnectionBucket,
+}
+
+impl ConnectionSplit {
+    /// Adds one request with `total` latency to its bucket.
+    pub fn record(&mut self, timing: &ConnectionTiming, total: Duration) {
+        if timing.new_connection {
+            self.new.record(timing, total);
+        } else {
+            self.reused.record(timing, total);
+        }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.new.requests == 0 && self.reused.requests == 0
+    }
+}
+
+/// HTTP status class used to split latency histograms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatusClass {
+    Success,
+    Redirect,
+    ClientError,
+    ServerError,
+}
+
+impl StatusClass {
+    /// Classifies a real HTTP status; synthetic and informational codes have no class.
+    #[must_use]
+    pub const fn from_status(status_code: u16) -> Option<Self> {
+        match status_code {
+            200..=299 => Some(Self::Success),
+            300..=399 => Some(Self::Redirect),
+            400..=499 => Some(Self::ClientError),
+            500..=599 => Some(Self::ServerError),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Success => "2xx",
+            Self::Redirect => "3xx",
+            Self::ClientError => "4xx",
+            Self::ServerError => "5xx",
         }
     }
 }
 
+/// Why a request failed without a usable response, tallied for the
+/// "Error Reasons" breakdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ErrorReason {
+    Timeout,
+    ConnectionRefused,
+    ConnectionReset,
+    Dns,
+    Tls,
+    Connect,
+    Body,
+    Redirect,
+    Other,
+}
+
+impl ErrorReason {
+    /// Stable label used in summaries, exports and the distributed wire format.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::ConnectionRefused => "connection_refused",
+            Self::ConnectionReset => "connection_reset",
+            Self::Dns => "dns",
+            Self::Tls => "tls",
+            Self::Connect => "connect",
+            Self::Body => "body",
+            Self::Redirect => "redirect",
+            Self::Other => "other",
+        }
+    }
+
+    /// Maps a label back to its reason; unknown labels become [`Self::Other`].
+    #[must_use]
+    pub fn from_label(label: &str) -> Self {
+        match label {
+            "timeout" => Self::Timeout,
+            "connection_refused" => Self::ConnectionRefused,
+            "connection_reset" => Self::ConnectionReset,
+            "dns" => Self::Dns,
+            "tls" => Self::Tls,
+            "connect" => Self::Connect,
+            "body" => Self::Body,
+            "redirect" => Self::Redirect,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Response check that failed on a delivered response. These requests keep
+/// the response's real status but count as failed, and are tallied here per
+/// check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckFailure {
+    /// `--verify-request-id-echo` found a missing or different echo.
+    RequestIdMismatch,
+    /// The body size fell outside `--assert-response-bytes-*`.
+    ResponseSize,
+    /// A streaming connection ended before `--min-events` messages.
+    TooFewMessages,
+    /// The body failed an `--assert-body-*`/`--assert-json` check.
+    BodyAssertion,
+}
+
+impl CheckFailure {
+    /// Short description used when the request carries no error message.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::RequestIdMismatch => "request id mismatch",
+            Self::ResponseSize => "response size violation",
+            Self::TooFewMessages => "too few messages",
+            Self::BodyAssertion => "body assertion failed",
+        }
+    }
+}
+
+/// Totals per [`CheckFailure`] kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CheckFailures {
+    pub request_id_mismatches: u64,
+    pub response_size_violations: u64,
+    pub too_few_messages: u64,
+    pub assertion_failures: u64,
+}
+
+impl CheckFailures {
+    pub const fn record(&mut self, failure: CheckFailure) {
+        match failure {
+            CheckFailure::RequestIdMismatch => {
+                self.request_id_mismatches = self.request_id_mismatches.saturating_add(1);
+            }
+            CheckFailure::ResponseSize => {
+                self.response_size_violations = self.response_size_violations.saturating_add(1);
+            }
+            CheckFailure::TooFewMessages => {
+                self.too_few_messages = self.too_few_messages.saturating_add(1);
+            }
+            CheckFailure::BodyAssertion => {
+                self.assertion_failures = self.assertion_failures.saturating_add(1);
+            }
+        }
+    }
+
+    pub const fn merge(&mut self, other: Self) {
+        self.request_id_mismatches = self
+            .request_id_mismatches
+            .saturating_add(other.request_id_mismatches);
+        self.response_size_violations = self
+            .response_size_violations
+            .saturating_add(other.response_size_violations);
+        self.too_few_messages = self.too_few_messages.saturating_add(other.too_few_messages);
+        self.assertion_failures = self
+            .assertion_failures
+            .saturating_add(other.assertion_failures);
+    }
+
+    /// Non-zero counters as `(export key, summary title, count)`.
+    #[must_use]
+    pub fn counts(self) -> Vec<(&'static str, &'static str, u64)> {
+        [
+            (
+                "request_id_mismatches",
+                "Request ID Mismatches",
+                self.request_id_mismatches,
+            ),
+            (
+                "response_size_violations",
+                "Response Size Violations",
+                self.response_size_violations,
+            ),
+            (
+                "too_few_messages",
+                "Too Few Messages",
+                self.too_few_messages,
+            ),
+            (
+                "assertion_failures",
+                "Assertion Failures",
+                self.assertion_failures,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, _, count)| *count > 0)
+        .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MetricsSummary {
     pub duration: Duration,
@@ -54,11 +388,55 @@ pub struct MetricsSummary {
     pub success_min_latency_ms: u64,
     pub success_max_latency_ms: u64,
     pub success_avg_latency_ms: u64,
+    /// Responses per negotiated HTTP version, e.g. `HTTP/2`.
+    pub http_versions: BTreeMap<&'static str, u64>,
+    /// Failed requests per [`ErrorReason`] label, e.g. `connection_refused`.
+    pub error_reasons: BTreeMap<&'static str, u64>,
+    pub check_failures: CheckFailures,
 }
 
 #[derive(Debug)]
 pub struct MetricsReport {
     pub summary: MetricsSummary,
+    /// Why the collector ended the run early, when a stop condition tripped.
+    pub stop_reason: Option<String>,
+    /// Why `--abort-on-errors`/`--abort-on-error-rate` aborted the run.
+    pub abort_reason: Option<String>,
+    /// Highest-latency requests seen by the collector, slowest first; empty unless `--slowest` is set.
+    pub slowest: Vec<SlowRequest>,
+    /// How requests in flight at the deadline resolved; only set with
+    /// `--wait-ongoing-requests-after-deadline`.
+    pub drain: Option<DrainReport>,
+    /// New vs reused connection timing; only set with `--connection-timing`.
+    pub connection_split: Option<ConnectionSplit>,
+}
+
+/// Requests in flight when the run stopped and how they resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrainReport {
+    /// Estimated from the in-flight gauge of the last metric before the deadline.
+    pub in_flight_at_deadline: u64,
+    /// Requests that started before the deadline and got a response during the drain.
+    pub late_completions: u64,
+    /// Requests that timed out during the drain or never reported.
+    pub abandoned: u64,
+}
+
+/// One entry of the `--slowest` report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowRequest {
+    pub latency_ms: u64,
+    pub status_code: u16,
+    pub timed_out: bool,
+    pub transport_error: bool,
+    pub url: Option<Arc<str>>,
+}
+
+/// Request that crossed an `--alloc-profiler-spike-*` threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySpike {
+    pub latency_ms: u64,
+    pub in_flight_ops: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -77,9 +455,13 @@ pub struct StreamSnapshot {
     pub success_max_latency_ms: u64,
     pub success_latency_sum_ms: u128,
     pub histogram_b64: String,
+    pub in_flight_ops: u64,
+    pub http_versions: BTreeMap<&'static str, u64>,
+    pub error_reasons: BTreeMap<&'static str, u64>,
+    pub check_failures: CheckFailures,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct AggregatedMetricSample {
     pub elapsed_ms: u64,
     pub total_requests: u64,
@@ -100,18 +482,62 @@ pub struct MetricRecord {
     pub transport_error: bool,
     pub response_bytes: u64,
     pub in_flight_ops: u64,
+    /// A response check failed even though the status may be expected.
+    pub check_failed: bool,
+}
+
+impl MetricRecord {
+    /// Same rule as [`Metrics::is_success`], for a logged request.
+    #[must_use]
+    pub fn is_success(&self, expected_status_code: ExpectedStatus) -> bool {
+        expected_status_code.contains(self.status_code)
+            && !self.timed_out
+            && !self.transport_error
+            && !self.check_failed
+    }
 }
 
+/// Ascending, non-overlapping windows (in seconds since run start) of metrics to keep.
 #[derive(Debug, Clone)]
-pub struct MetricsRange(pub RangeInclusive<u64>);
+pub struct MetricsRange(pub Vec<RangeInclusive<u64>>);
 
-#[derive(Debug)]
+impl MetricsRange {
+    /// Returns `true` when `seconds_elapsed` falls inside any window.
+    #[must_use]
+    pub fn contains(&self, seconds_elapsed: u64) -> bool {
+        self.0.iter().any(|range| range.contains(&seconds_elapsed))
+    }
+}
+
+impl std::fmt::Display for MetricsRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, range) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}-{}", range.start(), range.end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Latency percentiles for requests completed at one in-flight concurrency level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct InflightLatencyPoint {
+    pub in_flight: u64,
+    pub samples: u64,
+    pub p50: u64,
+    pub p99: u64,
+}
+
+#[derive(Debug, serde::Serialize)]
 pub struct StreamingChartData {
     pub avg_buckets: BTreeMap<u64, (u128, u64)>,
     pub total_buckets: BTreeMap<u64, u64>,
     pub success_buckets: BTreeMap<u64, u64>,
     pub error_buckets: BTreeMap<u64, u64>,
     pub rps_counts: Vec<u32>,
+    pub response_bytes: Vec<u64>,
     pub timeouts: Vec<u32>,
     pub transports: Vec<u32>,
     pub non_expected: Vec<u32>,
@@ -129,24 +555,42 @@ pub struct StreamingChartData {
     pub p50_ok: Vec<u64>,
     pub p90_ok: Vec<u64>,
     pub p99_ok: Vec<u64>,
+    pub latency_by_inflight: Vec<InflightLatencyPoint>,
 }
 
 impl std::str::FromStr for MetricsRange {
     type Err = ValidationError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start_str, end_str) = s
-            .split_once('-')
-            .ok_or(ValidationError::MetricsRangeFormat)?;
-        let start: u64 = start_str
-            .parse()
-            .map_err(|err| ValidationError::MetricsRangeInvalidStart { source: err })?;
-        let end: u64 = end_str
-            .parse()
-            .map_err(|err| ValidationError::MetricsRangeInvalidEnd { source: err })?;
-        if start > end {
-            return Err(ValidationError::MetricsRangeStartAfterEnd);
+        let mut ranges: Vec<RangeInclusive<u64>> = Vec::new();
+        for part in s.split(',') {
+            let range = parse_single_range(part.trim())?;
+            if let Some(previous) = ranges.last()
+                && *range.start() <= *previous.end()
+            {
+                return Err(ValidationError::MetricsRangeOverlap {
+                    start: *range.start(),
+                    previous_end: *previous.end(),
+                });
+            }
+            ranges.push(range);
         }
-        Ok(MetricsRange(start..=end))
+        Ok(MetricsRange(ranges))
+    }
+}
+
+fn parse_single_range(s: &str) -> Result<RangeInclusive<u64>, ValidationError> {
+    let (start_str, end_str) = s
+        .split_once(['-', ':'])
+        .ok_or(ValidationError::MetricsRangeFormat)?;
+    let start: u64 = start_str
+        .parse()
+        .map_err(|err| ValidationError::MetricsRangeInvalidStart { source: err })?;
+    let end: u64 = end_str
+        .parse()
+        .map_err(|err| ValidationError::MetricsRangeInvalidEnd { source: err })?;
+    if start > end {
+        return Err(ValidationError::MetricsRangeStartAfterEnd);
     }
+    Ok(start..=end)
 }
@@ -7,6 +7,7 @@ use crate::args::TesterArgs;
 use crate::domain::run::{LoadMode, ProtocolKind};
 use crate::error::AppResult;
 use crate::metrics::{LogSink, Metrics};
+use crate::run_context::RunContext;
 use crate::shutdown::ShutdownSender;
 
 use super::{ProtocolAdapter, TransportAdapter};
@@ -19,6 +20,16 @@ const ALL_LOAD_MODES: &[LoadMode] = &[
     LoadMode::Burst,
     LoadMode::Soak,
 ];
+/// HTTP also runs the closed-loop concurrency mode.
+const HTTP_LOAD_MODES: &[LoadMode] = &[
+    LoadMode::Arrival,
+    LoadMode::Step,
+    LoadMode::Ramp,
+    LoadMode::Jitter,
+    LoadMode::Burst,
+    LoadMode::Soak,
+    LoadMode::Concurrency,
+];
 const SOAK_BURST_ONLY: &[LoadMode] = &[LoadMode::Burst, LoadMode::Soak];
 const ARRIVAL_RAMP_ONLY: &[LoadMode] = &[LoadMode::Arrival, LoadMode::Ramp];
 
@@ -34,6 +45,7 @@ pub(super) struct StaticProtocolAdapter {
 
 type SetupRequestSenderFn = fn(
     &TesterArgs,
+    &RunContext,
     &ShutdownSender,
     &mpsc::Sender<Metrics>,
     Option<&Arc<LogSink>>,
@@ -64,7 +76,7 @@ impl StaticProtocolAdapter {
             "HTTP",
             true,
             true,
-            ALL_LOAD_MODES,
+            HTTP_LOAD_MODES,
             crate::http::setup_request_sender,
         )
     }
@@ -178,6 +190,17 @@ impl StaticProtocolAdapter {
             super::runtime::setup_raknet_sender,
         )
     }
+
+    pub(super) const fn sse() -> Self {
+        Self::new(
+            ProtocolKind::Sse,
+            "SSE",
+            true,
+            true,
+            ALL_LOAD_MODES,
+            super::runtime::setup_sse_sender,
+        )
+    }
 }
 
 impl ProtocolAdapter for StaticProtocolAdapter {
@@ -206,15 +229,16 @@ impl TransportAdapter for StaticProtocolAdapter {
     fn setup_request_sender(
         &self,
         args: &TesterArgs,
+        run: &RunContext,
         shutdown_tx: &ShutdownSender,
         metrics_tx: &mpsc::Sender<Metrics>,
         log_sink: Option<&Arc<LogSink>>,
     ) -> AppResult<JoinHandle<()>> {
-        (self.setup_request_sender)(args, shutdown_tx, metrics_tx, log_sink)
+        (self.setup_request_sender)(args, run, shutdown_tx, metrics_tx, log_sink)
     }
 }
 
-pub(super) const fn builtins() -> [StaticProtocolAdapter; 11] {
+pub(super) const fn builtins() -> [StaticProtocolAdapter; 12] {
     [
         StaticProtocolAdapter::http(),
         StaticProtocolAdapter::grpc_unary(),
@@ -227,5 +251,6 @@ pub(super) const fn builtins() -> [StaticProtocolAdapter; 11] {
         StaticProtocolAdapter::enet(),
         StaticProtocolAdapter::kcp(),
         StaticProtocolAdapter::raknet(),
+        StaticProtocolAdapter::sse(),
     ]
 }
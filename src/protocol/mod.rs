@@ -11,5 +11,6 @@ mod tests;
 #[cfg(test)]
 pub(crate) use registry::ProtocolRegistry;
 pub use registry::protocol_registry;
+pub(crate) use runtime::SseStats;
 pub use runtime::setup_request_sender;
 pub use traits::{ProtocolAdapter, ProtocolAdapterError, TransportAdapter};
@@ -3,6 +3,7 @@ mod grpc;
 mod mqtt;
 mod resolve;
 mod spawner;
+mod sse;
 mod transports;
 mod types;
 
@@ -11,22 +12,25 @@ mod tests;
 
 use std::sync::Arc;
 
-use tokio::sync::mpsc;
+use tokio::sync::{Mutex, mpsc};
 use tokio::task::JoinHandle;
 use url::Url;
 
-use crate::args::TesterArgs;
+use crate::args::{GrpcMode, MqttMode, PositiveU64, PositiveUsize, TesterArgs};
 use crate::domain::run::ProtocolKind;
-use crate::error::{AppError, AppResult, ValidationError};
+use crate::error::{AppError, AppResult, HttpError, ValidationError};
 use crate::metrics::{LogSink, Metrics};
+use crate::run_context::RunContext;
 use crate::shutdown::ShutdownSender;
 
 use datagram::{datagram_payload, setup_datagram_sender};
-use grpc::{build_grpc_client, grpc_frame, grpc_request_once};
-use mqtt::{mqtt_request_once, topic_from_path};
-use resolve::{resolve_endpoint, resolve_grpc_url, resolve_websocket_url};
-use spawner::spawn_transport_sender;
-use transports::{tcp_request_once, websocket_request_once};
+use grpc::{GrpcCall, build_grpc_client, grpc_request_once};
+use mqtt::{MqttSubscribeSession, mqtt_request_once, topic_from_path};
+use resolve::{resolve_endpoint, resolve_grpc_url, resolve_sse_url, resolve_websocket_url};
+use spawner::{spawn_transport_sender, spawn_worker_transport_sender};
+pub(crate) use sse::SseStats;
+use sse::{SseSession, SseTarget, build_sse_client};
+use transports::{WebSocketSequence, tcp_request_once, websocket_request_once};
 
 /// Creates protocol-specific request sender task.
 ///
@@ -36,6 +40,7 @@ use transports::{tcp_request_once, websocket_request_once};
 pub fn setup_request_sender(
     protocol: ProtocolKind,
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
@@ -55,11 +60,23 @@ pub fn setup_request_sender(
             supported,
         }));
     }
-    adapter.setup_request_sender(args, shutdown_tx, metrics_tx, log_sink)
+    if args.min_events.is_some()
+        && !matches!(
+            protocol,
+            ProtocolKind::Sse | ProtocolKind::Websocket | ProtocolKind::GrpcStreaming
+        )
+    {
+        tracing::warn!(
+            "--min-events is ignored for the {} protocol.",
+            protocol.as_str()
+        );
+    }
+    adapter.setup_request_sender(args, run, shutdown_tx, metrics_tx, log_sink)
 }
 
 pub(super) fn setup_tcp_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
@@ -68,6 +85,7 @@ pub(super) fn setup_tcp_sender(
     let payload = args.data.clone().into_bytes();
     Ok(spawn_transport_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -83,12 +101,14 @@ pub(super) fn setup_tcp_sender(
 
 pub(super) fn setup_udp_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
     setup_datagram_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -99,12 +119,14 @@ pub(super) fn setup_udp_sender(
 
 pub(super) fn setup_quic_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
     setup_datagram_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -115,12 +137,14 @@ pub(super) fn setup_quic_sender(
 
 pub(super) fn setup_enet_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
     setup_datagram_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -131,12 +155,14 @@ pub(super) fn setup_enet_sender(
 
 pub(super) fn setup_kcp_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
     setup_datagram_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -147,12 +173,14 @@ pub(super) fn setup_kcp_sender(
 
 pub(super) fn setup_raknet_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
     setup_datagram_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -168,6 +196,7 @@ pub(super) fn setup_raknet_sender(
 
 pub(super) fn setup_mqtt_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
@@ -177,14 +206,44 @@ pub(super) fn setup_mqtt_sender(
         .url
         .as_deref()
         .ok_or_else(|| AppError::validation(ValidationError::MissingUrl))?;
-    let topic = Url::parse(raw_url).ok().map_or_else(
-        || "strest/loadtest".to_owned(),
-        |url| topic_from_path(url.path()),
-    );
+    let topic = args.mqtt_topic.clone().unwrap_or_else(|| {
+        Url::parse(raw_url).ok().map_or_else(
+            || "strest/loadtest".to_owned(),
+            |url| topic_from_path(url.path()),
+        )
+    });
     let payload = datagram_payload(args);
 
+    if args.mqtt_mode == MqttMode::Subscribe {
+        return Ok(spawn_worker_transport_sender(
+            args,
+            run,
+            shutdown_tx,
+            metrics_tx,
+            log_sink,
+            move || {
+                let session = Arc::new(Mutex::new(MqttSubscribeSession::new(
+                    endpoint,
+                    topic.clone(),
+                    payload.clone(),
+                )));
+                Arc::new(move |request_timeout, connect_timeout| {
+                    let session = Arc::clone(&session);
+                    Box::pin(async move {
+                        session
+                            .lock()
+                            .await
+                            .round_trip(request_timeout, connect_timeout)
+                            .await
+                    })
+                })
+            },
+        ));
+    }
+
     Ok(spawn_transport_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -202,14 +261,44 @@ pub(super) fn setup_mqtt_sender(
 
 pub(super) fn setup_websocket_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
     let ws_url = resolve_websocket_url(args)?;
+    if let Some(messages_per_conn) = args.ws_messages_per_conn {
+        let messages: Arc<[String]> = Arc::from(message_lines(args)?);
+        return Ok(spawn_worker_transport_sender(
+            args,
+            run,
+            shutdown_tx,
+            metrics_tx,
+            log_sink,
+            move || {
+                let session = Arc::new(Mutex::new(WebSocketSequence::new(
+                    ws_url.clone(),
+                    Arc::clone(&messages),
+                    messages_per_conn.get(),
+                )));
+                Arc::new(move |request_timeout, connect_timeout| {
+                    let session = Arc::clone(&session);
+                    Box::pin(async move {
+                        session
+                            .lock()
+                            .await
+                            .round_trip(request_timeout, connect_timeout)
+                            .await
+                    })
+                })
+            },
+        ));
+    }
     let payload = args.data.clone();
+    let min_messages = args.min_events.map(PositiveU64::get);
     Ok(spawn_transport_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -217,53 +306,161 @@ pub(super) fn setup_websocket_sender(
             let ws_url = ws_url.clone();
             let payload = payload.clone();
             Box::pin(async move {
-                websocket_request_once(&ws_url, &payload, request_timeout, connect_timeout).await
+                websocket_request_once(
+                    &ws_url,
+                    &payload,
+                    request_timeout,
+                    connect_timeout,
+                    min_messages,
+                )
+                .await
             })
         },
     ))
 }
 
+pub(super) fn setup_sse_sender(
+    args: &TesterArgs,
+    run: &RunContext,
+    shutdown_tx: &ShutdownSender,
+    metrics_tx: &mpsc::Sender<Metrics>,
+    log_sink: Option<&Arc<LogSink>>,
+) -> AppResult<JoinHandle<()>> {
+    if let (Some(min_events), Some(events_per_conn)) = (args.min_events, args.sse_events_per_conn)
+        && u64::try_from(events_per_conn.get()).unwrap_or(u64::MAX) < min_events.get()
+    {
+        return Err(AppError::validation(
+            ValidationError::MinEventsExceedsSseEventsPerConn {
+                min_events: min_events.get(),
+                events_per_conn: events_per_conn.get(),
+            },
+        ));
+    }
+    let stats = run.sse_stats.clone().unwrap_or_default();
+    let target = Arc::new(SseTarget {
+        client: build_sse_client(args)?,
+        url: resolve_sse_url(args)?,
+        headers: args.headers.clone(),
+        events_per_conn: args.sse_events_per_conn.map(PositiveUsize::get),
+        min_events: args.min_events.map(PositiveU64::get),
+        conn_duration: args.sse_conn_duration,
+        resume: args.sse_resume,
+        stats: Arc::clone(&stats),
+    });
+    let sender =
+        spawn_worker_transport_sender(args, run, shutdown_tx, metrics_tx, log_sink, move || {
+            let session = Arc::new(Mutex::new(SseSession::new(Arc::clone(&target))));
+            Arc::new(move |request_timeout, connect_timeout| {
+                let session = Arc::clone(&session);
+                Box::pin(async move {
+                    session
+                        .lock()
+                        .await
+                        .next_event(request_timeout, connect_timeout)
+                        .await
+                })
+            })
+        });
+    Ok(tokio::spawn(async move {
+        if sender.await.is_err() {
+            return;
+        }
+        let first_event = stats.avg_first_event();
+        let inter_event = stats.avg_inter_event();
+        tracing::info!(
+            "SSE: {} events over {} connections, avg time-to-first-event {}.{:03}ms, avg inter-event {}.{:03}ms",
+            stats.events(),
+            stats.connections(),
+            first_event.as_millis(),
+            first_event.subsec_micros() % 1000,
+            inter_event.as_millis(),
+            inter_event.subsec_micros() % 1000,
+        );
+    }))
+}
+
 pub(super) fn setup_grpc_unary_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
-    setup_grpc_sender(args, shutdown_tx, metrics_tx, log_sink, false)
+    let mode = args.grpc_mode.unwrap_or(GrpcMode::Unary);
+    setup_grpc_sender(args, run, shutdown_tx, metrics_tx, log_sink, mode)
 }
 
 pub(super) fn setup_grpc_streaming_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
 ) -> AppResult<JoinHandle<()>> {
-    setup_grpc_sender(args, shutdown_tx, metrics_tx, log_sink, true)
+    let mode = args.grpc_mode.unwrap_or(GrpcMode::ServerStream);
+    setup_grpc_sender(args, run, shutdown_tx, metrics_tx, log_sink, mode)
 }
 
 fn setup_grpc_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
-    streaming: bool,
+    mode: GrpcMode,
 ) -> AppResult<JoinHandle<()>> {
     let (grpc_url, prior_knowledge) = resolve_grpc_url(args)?;
     let client = build_grpc_client(args.connect_timeout, prior_knowledge)?;
-    let payload = Arc::<[u8]>::from(grpc_frame(args.data.as_bytes()));
+    let call = Arc::new(GrpcCall::new(
+        mode,
+        &grpc_payloads(args, mode)?,
+        args.min_events.map(PositiveU64::get),
+    ));
 
     Ok(spawn_transport_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
         move |request_timeout, _connect_timeout| {
             let client = client.clone();
             let grpc_url = grpc_url.clone();
-            let payload = Arc::clone(&payload);
-            Box::pin(async move {
-                grpc_request_once(&client, &grpc_url, &payload, request_timeout, streaming).await
-            })
+            let call = Arc::clone(&call);
+            Box::pin(
+                async move { grpc_request_once(&client, &grpc_url, &call, request_timeout).await },
+            )
         },
     ))
 }
+
+/// Request messages: one per `--data-lines` line or `--data` line when the
+/// mode streams requests, otherwise the whole `--data` value.
+fn grpc_payloads(args: &TesterArgs, mode: GrpcMode) -> AppResult<Vec<String>> {
+    if !mode.streams_requests() {
+        return Ok(vec![args.data.clone()]);
+    }
+    message_lines(args)
+}
+
+/// Non-empty lines of the `--data-lines` file, or of `--data` without one.
+fn message_lines(args: &TesterArgs) -> AppResult<Vec<String>> {
+    let content = match args.data_lines.as_ref() {
+        Some(path) => std::fs::read_to_string(path).map_err(|err| {
+            AppError::http(HttpError::ReadFile {
+                path: path.clone().into(),
+                source: err,
+            })
+        })?,
+        None => args.data.clone(),
+    };
+    let lines: Vec<String> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_owned)
+        .collect();
+    if lines.is_empty() {
+        return Err(AppError::http(HttpError::BodyLinesEmpty));
+    }
+    Ok(lines)
+}
@@ -6,14 +6,17 @@ use tokio::task::JoinHandle;
 use crate::args::TesterArgs;
 use crate::error::AppResult;
 use crate::metrics::{LogSink, Metrics};
+use crate::run_context::RunContext;
 use crate::shutdown::ShutdownSender;
 
 use super::resolve::resolve_endpoint;
 use super::spawner::spawn_transport_sender;
 use super::transports::udp_request_once;
 
+#[expect(clippy::too_many_arguments)]
 pub(super) fn setup_datagram_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
@@ -23,6 +26,7 @@ pub(super) fn setup_datagram_sender(
     let endpoint = resolve_endpoint(args, allowed_schemes)?;
     Ok(spawn_transport_sender(
         args,
+        run,
         shutdown_tx,
         metrics_tx,
         log_sink,
@@ -1,13 +1,19 @@
+use std::convert::Infallible;
+use std::sync::Arc;
 use std::time::Duration;
 
-use futures_util::StreamExt;
+use http_body_util::BodyExt;
 use reqwest::header::{CONTENT_TYPE, HeaderMap};
 use tokio::time::timeout;
 use url::Url;
 
+use crate::args::GrpcMode;
 use crate::error::{AppError, AppResult, HttpError};
 
-use super::types::RequestOutcome;
+use super::types::{MessageShortfall, RequestOutcome};
+
+/// Length-prefixed message header: compression flag plus big-endian length.
+const GRPC_FRAME_HEADER_LEN: usize = 5;
 
 pub(super) fn build_grpc_client(
     connect_timeout: Duration,
@@ -26,19 +32,63 @@ pub(super) fn build_grpc_client(
         .map_err(|source| AppError::http(HttpError::BuildClientFailed { source }))
 }
 
+/// Framed request messages for one call.
+///
+/// Streaming-request modes send every message as its own body chunk; the
+/// other modes send the first one. `min_messages` only applies to modes that
+/// stream responses.
+pub(super) struct GrpcCall {
+    pub(super) mode: GrpcMode,
+    pub(super) messages: Arc<[Vec<u8>]>,
+    pub(super) min_messages: Option<u64>,
+}
+
+impl GrpcCall {
+    pub(super) fn new(mode: GrpcMode, payloads: &[String], min_messages: Option<u64>) -> Self {
+        let messages: Vec<Vec<u8>> = if mode.streams_requests() {
+            payloads
+                .iter()
+                .map(|payload| grpc_frame(payload.as_bytes()))
+                .collect()
+        } else {
+            let first = payloads.first().map_or("", String::as_str);
+            vec![grpc_frame(first.as_bytes())]
+        };
+        Self {
+            mode,
+            messages: Arc::from(messages),
+            min_messages: min_messages.filter(|_| mode.streams_responses()),
+        }
+    }
+
+    fn body(&self) -> reqwest::Body {
+        if self.mode.streams_requests() {
+            let chunks: Vec<Result<Vec<u8>, Infallible>> =
+                self.messages.iter().cloned().map(Ok).collect();
+            return reqwest::Body::wrap_stream(futures_util::stream::iter(chunks));
+        }
+        reqwest::Body::from(self.messages.first().cloned().unwrap_or_default())
+    }
+}
+
+/// Runs one call to completion; a completed stream counts as one request.
+///
+/// `response_bytes` covers every received frame. A stream with fewer
+/// messages than `--min-events` is reported as too few messages. A non-zero `grpc-status`,
+/// in the headers or the trailers, is reported as its HTTP equivalent so it
+/// counts as a non-expected status.
 pub(super) async fn grpc_request_once(
     client: &reqwest::Client,
     grpc_url: &Url,
-    payload: &[u8],
+    call: &GrpcCall,
     request_timeout: Duration,
-    streaming: bool,
 ) -> RequestOutcome {
     let request_future = async {
         let response = match client
             .post(grpc_url.as_str())
             .header(CONTENT_TYPE, "application/grpc")
             .header("te", "trailers")
-            .body(payload.to_vec())
+            .body(call.body())
             .send()
             .await
         {
@@ -49,33 +99,53 @@ pub(super) async fn grpc_request_once(
         if !response.status().is_success() {
             return RequestOutcome::transport_error();
         }
-        if grpc_status_non_zero(response.headers()) {
-            return RequestOutcome::transport_error();
+        // Trailers-only responses carry the status in the headers.
+        if let Some(code) = grpc_status(response.headers())
+            && code != 0
+        {
+            return RequestOutcome::status(grpc_status_to_http(code), 0);
         }
 
-        let response_bytes = if streaming {
-            let mut total_bytes: u64 = 0;
-            let mut body_stream = response.bytes_stream();
-            while let Some(item) = body_stream.next().await {
-                let chunk = match item {
-                    Ok(chunk) => chunk,
-                    Err(_) => return RequestOutcome::transport_error(),
-                };
-                let chunk_len = u64::try_from(chunk.len()).unwrap_or(u64::MAX);
-                total_bytes = total_bytes.saturating_add(chunk_len);
-                if total_bytes > 0 {
-                    break;
+        let mut body = reqwest::Body::from(response);
+        let mut frames = GrpcFrameCounter::default();
+        let mut total_bytes: u64 = 0;
+        let mut trailer_status = None;
+        while let Some(frame) = body.frame().await {
+            let Ok(frame) = frame else {
+                return RequestOutcome::transport_error();
+            };
+            match frame.into_data() {
+                Ok(chunk) => {
+                    let chunk_len = u64::try_from(chunk.len()).unwrap_or(u64::MAX);
+                    total_bytes = total_bytes.saturating_add(chunk_len);
+                    frames.feed(&chunk);
+                }
+                Err(frame) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        trailer_status = grpc_status(&trailers);
+                    }
                 }
             }
-            total_bytes
-        } else {
-            match response.bytes().await {
-                Ok(body) => u64::try_from(body.len()).unwrap_or(u64::MAX),
-                Err(_) => return RequestOutcome::transport_error(),
-            }
-        };
+        }
 
-        RequestOutcome::success(response_bytes)
+        if !frames.is_complete() {
+            return RequestOutcome::transport_error();
+        }
+        match trailer_status {
+            Some(code) if code != 0 => {
+                RequestOutcome::status(grpc_status_to_http(code), total_bytes)
+            }
+            Some(_) | None => {
+                // Unary responses must hold exactly one message.
+                if !call.mode.streams_responses() && frames.frames() != 1 {
+                    return RequestOutcome::transport_error();
+                }
+                MessageShortfall::check(frames.frames(), call.min_messages).map_or_else(
+                    || RequestOutcome::success(total_bytes),
+                    |shortfall| RequestOutcome::too_few_messages(shortfall, total_bytes),
+                )
+            }
+        }
     };
 
     timeout(request_timeout, request_future)
@@ -85,19 +155,82 @@ pub(super) async fn grpc_request_once(
 
 pub(super) fn grpc_frame(payload: &[u8]) -> Vec<u8> {
     let payload_len = u32::try_from(payload.len()).map_or(u32::MAX, |value| value);
-    let mut framed = Vec::with_capacity(payload.len().saturating_add(5));
+    let mut framed = Vec::with_capacity(payload.len().saturating_add(GRPC_FRAME_HEADER_LEN));
     framed.push(0);
     framed.extend_from_slice(&payload_len.to_be_bytes());
     framed.extend_from_slice(payload);
     framed
 }
 
-fn grpc_status_non_zero(headers: &HeaderMap) -> bool {
-    let Some(raw) = headers.get("grpc-status") else {
-        return false;
-    };
-    let Ok(value) = raw.to_str() else {
-        return true;
-    };
-    value.trim() != "0"
+/// Counts length-prefixed messages across arbitrarily split body chunks.
+#[derive(Debug, Default)]
+pub(super) struct GrpcFrameCounter {
+    header: Vec<u8>,
+    remaining: usize,
+    frames: u64,
+}
+
+impl GrpcFrameCounter {
+    pub(super) fn feed(&mut self, mut chunk: &[u8]) {
+        while !chunk.is_empty() {
+            if self.remaining > 0 {
+                let take = self.remaining.min(chunk.len());
+                self.remaining = self.remaining.saturating_sub(take);
+                chunk = chunk.get(take..).unwrap_or_default();
+                continue;
+            }
+            let needed = GRPC_FRAME_HEADER_LEN.saturating_sub(self.header.len());
+            let take = needed.min(chunk.len());
+            self.header
+                .extend_from_slice(chunk.get(..take).unwrap_or_default());
+            chunk = chunk.get(take..).unwrap_or_default();
+            if let Some(len_bytes) = self.header.get(1..GRPC_FRAME_HEADER_LEN) {
+                let mut raw = [0_u8; 4];
+                raw.copy_from_slice(len_bytes);
+                self.remaining = usize::try_from(u32::from_be_bytes(raw)).unwrap_or(usize::MAX);
+                self.header.clear();
+                self.frames = self.frames.saturating_add(1);
+            }
+        }
+    }
+
+    #[must_use]
+    pub(super) const fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Whether the stream ended on a message boundary.
+    #[must_use]
+    pub(super) const fn is_complete(&self) -> bool {
+        self.header.is_empty() && self.remaining == 0
+    }
+}
+
+fn grpc_status(headers: &HeaderMap) -> Option<u32> {
+    let raw = headers.get("grpc-status")?;
+    // An unparsable status is treated as UNKNOWN.
+    Some(
+        raw.to_str()
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(2),
+    )
+}
+
+/// Maps a gRPC status code to the HTTP status recorded in metrics.
+pub(super) const fn grpc_status_to_http(code: u32) -> u16 {
+    match code {
+        0 => 200,
+        1 => 499,
+        3 | 9 | 11 => 400,
+        4 => 504,
+        5 => 404,
+        6 | 10 => 409,
+        7 => 403,
+        8 => 429,
+        12 => 501,
+        14 => 503,
+        16 => 401,
+        _ => 500,
+    }
 }
@@ -1,9 +1,10 @@
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
-use tokio::time::timeout;
+use tokio::time::{Instant, timeout, timeout_at};
 
 use super::types::RequestOutcome;
 
@@ -12,8 +13,22 @@ const MQTT_PROTOCOL_LEVEL_3_1_1: u8 = 4;
 const MQTT_CONNECT_PACKET_TYPE: u8 = 0x10;
 const MQTT_PUBLISH_PACKET_TYPE_QOS0: u8 = 0x30;
 const MQTT_CONNACK_PACKET_TYPE: u8 = 0x20;
+const MQTT_SUBSCRIBE_PACKET_TYPE: u8 = 0x82;
+const MQTT_SUBACK_PACKET_TYPE: u8 = 0x90;
+const MQTT_SUBACK_FAILURE: u8 = 0x80;
+const MQTT_PINGREQ_PACKET: [u8; 2] = [0xC0, 0x00];
+const MQTT_PACKET_TYPE_MASK: u8 = 0xF0;
+const MQTT_QOS_MASK: u8 = 0x06;
 const MQTT_CLEAN_SESSION_FLAG: u8 = 0x02;
 const MQTT_KEEPALIVE_SECS: u16 = 60;
+/// The subscriber only reads, so it pings well inside the keepalive window.
+const MQTT_SUBSCRIBER_PING_SECS: u64 = 30;
+/// Session id plus sequence number, both big-endian `u64`.
+const MQTT_CORRELATION_LEN: usize = 16;
+/// Largest remaining length a broker may announce (MQTT 3.1.1 limit).
+const MQTT_MAX_REMAINING_LEN: usize = 268_435_455;
+
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
 
 pub(super) async fn mqtt_request_once(
     endpoint: SocketAddr,
@@ -58,6 +73,245 @@ pub(super) async fn mqtt_request_once(
     RequestOutcome::success(4)
 }
 
+/// One worker's publisher and subscriber connections for `--mqtt-mode subscribe`.
+///
+/// Each publish carries this session's id and a sequence number ahead of the
+/// payload, so the subscriber can match its own message among everything
+/// else on the topic and skip ones that arrive after they timed out.
+pub(super) struct MqttSubscribeSession {
+    endpoint: SocketAddr,
+    topic: String,
+    payload: Vec<u8>,
+    session_id: u64,
+    seq: u64,
+    connections: Option<MqttConnections>,
+}
+
+struct MqttConnections {
+    publisher: TcpStream,
+    subscriber: TcpStream,
+    last_ping: Instant,
+}
+
+impl MqttSubscribeSession {
+    pub(super) fn new(endpoint: SocketAddr, topic: String, payload: Vec<u8>) -> Self {
+        Self {
+            endpoint,
+            topic,
+            payload,
+            session_id: NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed),
+            seq: 0,
+            connections: None,
+        }
+    }
+
+    /// Publishes one message and waits for the subscriber to receive it.
+    ///
+    /// Connecting and subscribing happen on the first call and after any
+    /// failure; their time counts toward that request.
+    pub(super) async fn round_trip(
+        &mut self,
+        request_timeout: Duration,
+        connect_timeout: Duration,
+    ) -> RequestOutcome {
+        let mut connections = match self.connections.take() {
+            Some(connections) => connections,
+            None => match self.connect(request_timeout, connect_timeout).await {
+                Ok(connections) => connections,
+                Err(outcome) => return outcome,
+            },
+        };
+
+        self.seq = self.seq.saturating_add(1);
+        let mut message =
+            Vec::with_capacity(MQTT_CORRELATION_LEN.saturating_add(self.payload.len()));
+        message.extend_from_slice(&self.session_id.to_be_bytes());
+        message.extend_from_slice(&self.seq.to_be_bytes());
+        message.extend_from_slice(&self.payload);
+        let publish_packet = build_publish_packet(&self.topic, &message);
+
+        let deadline = Instant::now()
+            .checked_add(request_timeout)
+            .unwrap_or_else(Instant::now);
+        match timeout_at(deadline, connections.publisher.write_all(&publish_packet)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => return RequestOutcome::transport_error(),
+            Err(_) => return RequestOutcome::timeout(),
+        }
+        if connections.last_ping.elapsed() >= Duration::from_secs(MQTT_SUBSCRIBER_PING_SECS) {
+            if connections
+                .subscriber
+                .write_all(&MQTT_PINGREQ_PACKET)
+                .await
+                .is_err()
+            {
+                return RequestOutcome::transport_error();
+            }
+            connections.last_ping = Instant::now();
+        }
+
+        loop {
+            let packet = match timeout_at(deadline, read_packet(&mut connections.subscriber)).await
+            {
+                Ok(Some(packet)) => packet,
+                Ok(None) => return RequestOutcome::transport_error(),
+                Err(_) => {
+                    // Keep the session; a late delivery is skipped by its sequence number.
+                    self.connections = Some(connections);
+                    return RequestOutcome::timeout();
+                }
+            };
+            let Some(received) = publish_payload(&packet) else {
+                continue;
+            };
+            if received.get(..MQTT_CORRELATION_LEN) == message.get(..MQTT_CORRELATION_LEN) {
+                let payload_len = received.len().saturating_sub(MQTT_CORRELATION_LEN);
+                self.connections = Some(connections);
+                return RequestOutcome::success(u64::try_from(payload_len).unwrap_or(u64::MAX));
+            }
+        }
+    }
+
+    async fn connect(
+        &self,
+        request_timeout: Duration,
+        connect_timeout: Duration,
+    ) -> Result<MqttConnections, RequestOutcome> {
+        let publisher = open_session(
+            self.endpoint,
+            &format!("strest-pub-{}-{}", std::process::id(), self.session_id),
+            request_timeout,
+            connect_timeout,
+        )
+        .await?;
+        let mut subscriber = open_session(
+            self.endpoint,
+            &format!("strest-sub-{}-{}", std::process::id(), self.session_id),
+            request_timeout,
+            connect_timeout,
+        )
+        .await?;
+
+        let subscribe_packet = build_subscribe_packet(&self.topic);
+        match timeout(request_timeout, subscriber.write_all(&subscribe_packet)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => return Err(RequestOutcome::transport_error()),
+            Err(_) => return Err(RequestOutcome::timeout()),
+        }
+        let suback = match timeout(request_timeout, read_packet(&mut subscriber)).await {
+            Ok(Some(packet)) => packet,
+            Ok(None) => return Err(RequestOutcome::transport_error()),
+            Err(_) => return Err(RequestOutcome::timeout()),
+        };
+        let granted = suback.first_byte == MQTT_SUBACK_PACKET_TYPE
+            && suback
+                .body
+                .get(2)
+                .is_some_and(|code| *code != MQTT_SUBACK_FAILURE);
+        if !granted {
+            return Err(RequestOutcome::transport_error());
+        }
+
+        Ok(MqttConnections {
+            publisher,
+            subscriber,
+            last_ping: Instant::now(),
+        })
+    }
+}
+
+async fn open_session(
+    endpoint: SocketAddr,
+    client_id: &str,
+    request_timeout: Duration,
+    connect_timeout: Duration,
+) -> Result<TcpStream, RequestOutcome> {
+    let mut stream = match timeout(connect_timeout, TcpStream::connect(endpoint)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(_)) => return Err(RequestOutcome::transport_error()),
+        Err(_) => return Err(RequestOutcome::timeout()),
+    };
+    match timeout(
+        request_timeout,
+        stream.write_all(&build_connect_packet(client_id)),
+    )
+    .await
+    {
+        Ok(Ok(())) => {}
+        Ok(Err(_)) => return Err(RequestOutcome::transport_error()),
+        Err(_) => return Err(RequestOutcome::timeout()),
+    }
+    let mut connack = [0_u8; 4];
+    match timeout(request_timeout, stream.read_exact(&mut connack)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(_)) => return Err(RequestOutcome::transport_error()),
+        Err(_) => return Err(RequestOutcome::timeout()),
+    }
+    if !is_connack_ok(connack) {
+        return Err(RequestOutcome::transport_error());
+    }
+    Ok(stream)
+}
+
+pub(super) struct MqttPacket {
+    pub(super) first_byte: u8,
+    pub(super) body: Vec<u8>,
+}
+
+/// Reads one packet; `None` when the connection fails or the length is malformed.
+pub(super) async fn read_packet(stream: &mut (impl AsyncRead + Unpin)) -> Option<MqttPacket> {
+    let first_byte = stream.read_u8().await.ok()?;
+    let mut remaining_len: usize = 0;
+    let mut multiplier: usize = 1;
+    loop {
+        let byte = stream.read_u8().await.ok()?;
+        remaining_len =
+            remaining_len.checked_add(usize::from(byte & 0x7F).checked_mul(multiplier)?)?;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        multiplier = multiplier.checked_mul(128)?;
+        if remaining_len > MQTT_MAX_REMAINING_LEN || multiplier > 128 * 128 * 128 {
+            return None;
+        }
+    }
+    let mut body = vec![0_u8; remaining_len];
+    stream.read_exact(&mut body).await.ok()?;
+    Some(MqttPacket { first_byte, body })
+}
+
+/// Application payload of a PUBLISH packet; `None` for other packet types.
+pub(super) fn publish_payload(packet: &MqttPacket) -> Option<&[u8]> {
+    if packet.first_byte & MQTT_PACKET_TYPE_MASK != MQTT_PUBLISH_PACKET_TYPE_QOS0 {
+        return None;
+    }
+    let topic_len = usize::from(u16::from_be_bytes([
+        *packet.body.first()?,
+        *packet.body.get(1)?,
+    ]));
+    let mut offset = topic_len.checked_add(2)?;
+    // QoS 1 and 2 deliveries carry a packet identifier after the topic.
+    if packet.first_byte & MQTT_QOS_MASK != 0 {
+        offset = offset.checked_add(2)?;
+    }
+    packet.body.get(offset..)
+}
+
+fn build_subscribe_packet(topic: &str) -> Vec<u8> {
+    let remaining_len = 2_usize
+        .saturating_add(2)
+        .saturating_add(topic.len())
+        .saturating_add(1);
+    let capacity = 1_usize.saturating_add(remaining_len).saturating_add(4);
+    let mut packet = Vec::with_capacity(capacity);
+    packet.push(MQTT_SUBSCRIBE_PACKET_TYPE);
+    encode_remaining_length(&mut packet, remaining_len);
+    packet.extend_from_slice(&1_u16.to_be_bytes());
+    push_utf8(&mut packet, topic);
+    packet.push(0);
+    packet
+}
+
 pub(super) fn topic_from_path(path: &str) -> String {
     let trimmed = path.trim();
     if trimmed.is_empty() || trimmed == "/" {
@@ -66,7 +320,7 @@ pub(super) fn topic_from_path(path: &str) -> String {
     trimmed.trim_start_matches('/').to_owned()
 }
 
-fn build_connect_packet(client_id: &str) -> Vec<u8> {
+pub(super) fn build_connect_packet(client_id: &str) -> Vec<u8> {
     let mut variable_header = Vec::with_capacity(10);
     push_utf8(&mut variable_header, MQTT_PROTOCOL_NAME);
     variable_header.push(MQTT_PROTOCOL_LEVEL_3_1_1);
@@ -86,7 +340,7 @@ fn build_connect_packet(client_id: &str) -> Vec<u8> {
     packet
 }
 
-fn build_publish_packet(topic: &str, payload: &[u8]) -> Vec<u8> {
+pub(super) fn build_publish_packet(topic: &str, payload: &[u8]) -> Vec<u8> {
     let topic_len = topic.len();
     let remaining_len = 2_usize
         .saturating_add(topic_len)
@@ -158,3 +158,31 @@ pub(super) fn resolve_websocket_url(args: &TesterArgs) -> AppResult<Url> {
         )),
     }
 }
+
+pub(super) fn resolve_sse_url(args: &TesterArgs) -> AppResult<Url> {
+    let raw_url = args
+        .url
+        .as_deref()
+        .ok_or_else(|| AppError::validation(ValidationError::MissingUrl))?;
+    let url = Url::parse(raw_url).map_err(|source| {
+        AppError::validation(ValidationError::InvalidUrl {
+            url: raw_url.to_owned(),
+            source,
+        })
+    })?;
+    match url.scheme() {
+        "http" | "https" => {}
+        other => {
+            return Err(AppError::validation(
+                ValidationError::UnsupportedProtocolUrlScheme {
+                    protocol: args.protocol.as_str().to_owned(),
+                    scheme: other.to_owned(),
+                },
+            ));
+        }
+    }
+    if url.host_str().is_none() {
+        return Err(AppError::validation(ValidationError::UrlMissingHost));
+    }
+    Ok(url)
+}
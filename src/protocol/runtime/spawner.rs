@@ -10,16 +10,21 @@ use tokio::time::{Instant, interval, sleep};
 use tracing::{error, warn};
 
 use crate::args::{Protocol, TesterArgs};
-use crate::http::build_rate_limiter;
-use crate::metrics::{LogSink, Metrics};
+use crate::http::{MaxRateSettings, RatePacing, build_rate_limiter, until_next_wall_second};
+use crate::metrics::{CheckFailure, LogSink, Metrics};
+use crate::run_context::RunContext;
 use crate::shutdown::{ShutdownReceiver, ShutdownSender};
 
 use super::types::{
     InflightGuard, RequestLimiter, RequestOutcome, TransportRequestFn, TransportRunContext,
 };
 
+/// Pause between iterations of a worker that has no rate limiter.
+const IDLE_ITERATION_PAUSE_MS: u64 = 100;
+
 pub(super) fn spawn_transport_sender(
     args: &TesterArgs,
+    run: &RunContext,
     shutdown_tx: &ShutdownSender,
     metrics_tx: &mpsc::Sender<Metrics>,
     log_sink: Option<&Arc<LogSink>>,
@@ -27,11 +32,27 @@ pub(super) fn spawn_transport_sender(
     + Send
     + Sync
     + 'static,
+) -> JoinHandle<()> {
+    let request_fn: Arc<TransportRequestFn> = Arc::new(request_fn);
+    spawn_worker_transport_sender(args, run, shutdown_tx, metrics_tx, log_sink, move || {
+        Arc::clone(&request_fn)
+    })
+}
+
+/// Like [`spawn_transport_sender`], but each worker (and the preflight) gets
+/// its own request function from `make_request_fn`, so it can keep state such
+/// as an open connection across iterations.
+pub(super) fn spawn_worker_transport_sender(
+    args: &TesterArgs,
+    run: &RunContext,
+    shutdown_tx: &ShutdownSender,
+    metrics_tx: &mpsc::Sender<Metrics>,
+    log_sink: Option<&Arc<LogSink>>,
+    make_request_fn: impl Fn() -> Arc<TransportRequestFn> + Send + 'static,
 ) -> JoinHandle<()> {
     let shutdown_tx = shutdown_tx.clone();
     let metrics_tx = metrics_tx.clone();
     let log_sink = log_sink.cloned();
-    let request_fn: Arc<TransportRequestFn> = Arc::new(request_fn);
 
     let skip_preflight = matches!(args.protocol, Protocol::GrpcUnary | Protocol::GrpcStreaming);
 
@@ -46,7 +67,23 @@ pub(super) fn spawn_transport_sender(
     let request_limiter = RequestLimiter::new(args.requests.map(u64::from)).map(Arc::new);
     let burst_delay = args.burst_delay;
     let burst_rate = args.burst_rate.get();
+    let max_rate = MaxRateSettings::from_args(args, run);
     let wait_ongoing = args.wait_ongoing_requests_after_deadline;
+    let align_to_second = args.align_to_second;
+    let pacing = RatePacing::from_args(args);
+    // Paces each worker's WebSocket message sequence; otherwise workers
+    // without a rate limiter back off briefly between iterations.
+    let message_interval = args
+        .ws_message_interval_ms
+        .filter(|_| args.protocol == Protocol::Websocket && args.ws_messages_per_conn.is_some());
+    let paced = message_interval.is_some();
+    // SSE events arrive at the server's pace, so the next read starts right
+    // away and its latency is the gap since the previous event.
+    let server_paced = args.protocol == Protocol::Sse;
+    let iteration_pause = message_interval
+        .map_or(Duration::from_millis(IDLE_ITERATION_PAUSE_MS), |interval| {
+            Duration::from_millis(interval.get())
+        });
     let latency_correction = if args.rate_limit.is_some() {
         args.latency_correction
     } else {
@@ -64,7 +101,7 @@ pub(super) fn spawn_transport_sender(
 
     tokio::spawn(async move {
         if !skip_preflight {
-            let preflight = request_fn(request_timeout, connect_timeout).await;
+            let preflight = make_request_fn()(request_timeout, connect_timeout).await;
             if preflight.timed_out || preflight.transport_error {
                 error!("Protocol preflight request failed");
                 drop(shutdown_tx.send(()));
@@ -73,12 +110,24 @@ pub(super) fn spawn_transport_sender(
         }
 
         let mut shutdown_rx = shutdown_tx.subscribe();
+        if align_to_second {
+            tokio::select! {
+                _ = shutdown_rx.recv() => return,
+                () = sleep(until_next_wall_second()) => {}
+            }
+        }
         let mut spawn_interval = interval(Duration::from_millis(tick_interval));
         let mut total_spawned: usize = 0;
         let permits = Arc::new(Semaphore::new(0));
         let in_flight_counter = Arc::new(AtomicU64::new(0));
-        let rate_limiter =
-            build_rate_limiter(rate_limit, load_profile.as_ref(), burst_delay, burst_rate);
+        let rate_limiter = build_rate_limiter(
+            rate_limit,
+            load_profile.as_ref(),
+            burst_delay,
+            burst_rate,
+            pacing,
+            max_rate,
+        );
         let mut worker_handles = Vec::with_capacity(max_tasks);
 
         for _ in 0..max_tasks {
@@ -89,7 +138,7 @@ pub(super) fn spawn_transport_sender(
             let rate_limiter = rate_limiter.clone();
             let request_limiter = request_limiter.clone();
             let in_flight_counter = in_flight_counter.clone();
-            let request_fn = request_fn.clone();
+            let request_fn = make_request_fn();
 
             let handle = tokio::spawn(async move {
                 let mut shutdown_rx_worker = shutdown_tx.subscribe();
@@ -125,8 +174,8 @@ pub(super) fn spawn_transport_sender(
                         break;
                     }
 
-                    if rate_limiter.is_none() {
-                        sleep(Duration::from_millis(100)).await;
+                    if paced || (rate_limiter.is_none() && !server_paced) {
+                        sleep(iteration_pause).await;
                     }
                 }
             });
@@ -201,7 +250,9 @@ async fn run_transport_iteration(
         let status_code = if outcome.timed_out || outcome.transport_error {
             500
         } else {
-            context.expected_status_code
+            outcome
+                .status_code
+                .unwrap_or_else(|| context.expected_status_code.first())
         };
         let metric = Metrics::new(
             start,
@@ -210,9 +261,15 @@ async fn run_transport_iteration(
             outcome.transport_error,
             outcome.response_bytes,
             in_flight_ops,
-        );
+        )
+        .with_error_message(
+            outcome
+                .shortfall
+                .map(|shortfall| Arc::from(shortfall.message())),
+        )
+        .with_check_failure(outcome.shortfall.map(|_| CheckFailure::TooFewMessages));
         if let Some(sink) = context.log_sink
-            && !sink.send(metric)
+            && !sink.send(metric.clone())
         {
             return true;
         }
@@ -0,0 +1,376 @@
+use std::collections::VecDeque;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::Duration;
+
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use tokio::time::{Instant, timeout};
+use url::Url;
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult, HttpError};
+use crate::http::{apply_proxy_settings, apply_trust_settings};
+
+use super::types::{MessageShortfall, RequestOutcome};
+
+const EVENT_STREAM: &str = "text/event-stream";
+const LAST_EVENT_ID: &str = "Last-Event-ID";
+
+/// Longest line accepted from an event stream; a longer one fails the stream.
+pub(super) const MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// Builds the event-stream client with the sender's `--cacert`, `--cert`/`--key`,
+/// `--insecure`, and `--proxy` settings.
+pub(super) fn build_sse_client(args: &TesterArgs) -> AppResult<reqwest::Client> {
+    let builder = reqwest::Client::builder()
+        .connect_timeout(args.connect_timeout)
+        .tcp_nodelay(true);
+    let builder = apply_proxy_settings(apply_trust_settings(builder, args)?, args)?;
+    builder
+        .build()
+        .map_err(|source| AppError::http(HttpError::BuildClientFailed { source }))
+}
+
+/// Incremental `text/event-stream` parser.
+///
+/// Queues the data size of every dispatched event; events without a `data`
+/// field and `:` comment lines (keepalives) are not dispatched. Lines are
+/// buffered until their newline, up to `MAX_LINE_BYTES`.
+#[derive(Debug, Default)]
+pub(super) struct SseParser {
+    line: Vec<u8>,
+    data_bytes: Option<u64>,
+    last_event_id: Option<String>,
+    events: VecDeque<u64>,
+}
+
+impl SseParser {
+    /// Parses `chunk`; returns `false` once a line grows past `MAX_LINE_BYTES`.
+    pub(super) fn feed(&mut self, chunk: &[u8]) -> bool {
+        for byte in chunk {
+            if *byte == b'\n' {
+                let line = std::mem::take(&mut self.line);
+                self.process_line(line.strip_suffix(b"\r").unwrap_or(&line));
+            } else if self.line.len() >= MAX_LINE_BYTES {
+                return false;
+            } else {
+                self.line.push(*byte);
+            }
+        }
+        true
+    }
+
+    fn process_line(&mut self, line: &[u8]) {
+        if line.is_empty() {
+            if let Some(bytes) = self.data_bytes.take() {
+                self.events.push_back(bytes);
+            }
+            return;
+        }
+        let (field, value) = match line.iter().position(|byte| *byte == b':') {
+            Some(0) => return,
+            Some(idx) => (
+                line.get(..idx).unwrap_or_default(),
+                line.get(idx.saturating_add(1)..).unwrap_or_default(),
+            ),
+            None => (line, &[][..]),
+        };
+        let value = value.strip_prefix(b" ").unwrap_or(value);
+        match field {
+            b"data" => {
+                let len = u64::try_from(value.len()).unwrap_or(u64::MAX);
+                // Multi-line data is joined with '\n'.
+                self.data_bytes = Some(
+                    self.data_bytes
+                        .map_or(len, |bytes| bytes.saturating_add(1).saturating_add(len)),
+                );
+            }
+            b"id" if !value.contains(&0) => {
+                self.last_event_id = Some(String::from_utf8_lossy(value).into_owned());
+            }
+            _ => {}
+        }
+    }
+
+    /// Data size of the next complete event.
+    pub(super) fn next_event(&mut self) -> Option<u64> {
+        self.events.pop_front()
+    }
+
+    pub(super) fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+}
+
+/// Run-wide SSE figures for the end-of-run summary.
+#[derive(Debug, Default)]
+pub(crate) struct SseStats {
+    connections: AtomicU64,
+    events: AtomicU64,
+    first_events: AtomicU64,
+    first_event_us: AtomicU64,
+    gaps: AtomicU64,
+    gap_us: AtomicU64,
+}
+
+impl SseStats {
+    fn record_event(&self, first: bool, elapsed: Duration) {
+        let elapsed_us = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.events.fetch_add(1, Ordering::Relaxed);
+        let (count, sum) = if first {
+            (&self.first_events, &self.first_event_us)
+        } else {
+            (&self.gaps, &self.gap_us)
+        };
+        count.fetch_add(1, Ordering::Relaxed);
+        sum.fetch_add(elapsed_us, Ordering::Relaxed);
+    }
+
+    pub(crate) fn connections(&self) -> u64 {
+        self.connections.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn events(&self) -> u64 {
+        self.events.load(Ordering::Relaxed)
+    }
+
+    /// Average time from opening a stream to its first event.
+    pub(crate) fn avg_first_event(&self) -> Duration {
+        average(&self.first_event_us, &self.first_events)
+    }
+
+    /// Average gap between consecutive events on one stream.
+    pub(crate) fn avg_inter_event(&self) -> Duration {
+        average(&self.gap_us, &self.gaps)
+    }
+}
+
+fn average(sum_us: &AtomicU64, count: &AtomicU64) -> Duration {
+    Duration::from_micros(
+        sum_us
+            .load(Ordering::Relaxed)
+            .checked_div(count.load(Ordering::Relaxed))
+            .unwrap_or(0),
+    )
+}
+
+/// Settings shared by every worker's [`SseSession`].
+#[derive(Debug)]
+pub(super) struct SseTarget {
+    pub(super) client: reqwest::Client,
+    pub(super) url: Url,
+    pub(super) headers: Vec<(String, String)>,
+    pub(super) events_per_conn: Option<usize>,
+    pub(super) min_events: Option<u64>,
+    pub(super) conn_duration: Option<Duration>,
+    pub(super) resume: bool,
+    pub(super) stats: Arc<SseStats>,
+}
+
+struct SseStream {
+    response: reqwest::Response,
+    parser: SseParser,
+    opened_at: Instant,
+    last_event_at: Option<Instant>,
+    events: usize,
+}
+
+enum EventRead {
+    Event(u64),
+    Ended,
+    Failed,
+}
+
+impl SseStream {
+    async fn read_event(&mut self) -> EventRead {
+        loop {
+            if let Some(bytes) = self.parser.next_event() {
+                return EventRead::Event(bytes);
+            }
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => {
+                    if !self.parser.feed(&chunk) {
+                        return EventRead::Failed;
+                    }
+                }
+                Ok(None) => return EventRead::Ended,
+                Err(_) => return EventRead::Failed,
+            }
+        }
+    }
+}
+
+/// One worker's event stream; every received event counts as a request,
+/// except the ones held back until `--min-events` is reached.
+pub(super) struct SseSession {
+    target: Arc<SseTarget>,
+    stream: Option<SseStream>,
+    last_event_id: Option<String>,
+}
+
+impl SseSession {
+    pub(super) const fn new(target: Arc<SseTarget>) -> Self {
+        Self {
+            target,
+            stream: None,
+            last_event_id: None,
+        }
+    }
+
+    /// Waits for the next event, opening a stream first when needed.
+    ///
+    /// The first event on a stream includes the connect in its latency. A
+    /// stream the server closes after delivering events is reopened within
+    /// the same call; any other failure or timeout drops the stream. A stream
+    /// that closes or expires before `--min-events` events is reported as too
+    /// few messages instead of a read. Until a stream reaches `--min-events`,
+    /// its events are held and reported together as one read once the minimum
+    /// arrives, so a stream that falls short never counts as a success.
+    pub(super) async fn next_event(
+        &mut self,
+        request_timeout: Duration,
+        connect_timeout: Duration,
+    ) -> RequestOutcome {
+        if self
+            .stream
+            .as_ref()
+            .is_some_and(|stream| self.is_spent(stream))
+            && let Some(spent) = self.stream.take()
+            && let Some(shortfall) = self.shortfall(&spent)
+        {
+            return RequestOutcome::too_few_messages(shortfall, 0);
+        }
+        let reused = self.stream.is_some();
+        let mut stream = match self.stream.take() {
+            Some(open) => open,
+            None => match self.connect(connect_timeout).await {
+                Ok(opened) => opened,
+                Err(outcome) => return outcome,
+            },
+        };
+
+        let mut read = timeout(request_timeout, stream.read_event()).await;
+        if reused && matches!(read, Ok(EventRead::Ended)) {
+            self.remember_id(&stream);
+            if let Some(shortfall) = self.shortfall(&stream) {
+                return RequestOutcome::too_few_messages(shortfall, 0);
+            }
+            stream = match self.connect(connect_timeout).await {
+                Ok(reopened) => reopened,
+                Err(outcome) => return outcome,
+            };
+            read = timeout(request_timeout, stream.read_event()).await;
+        }
+        self.remember_id(&stream);
+
+        let mut held_bytes = 0_u64;
+        loop {
+            match read {
+                Ok(EventRead::Event(bytes)) => {
+                    let now = Instant::now();
+                    let since = stream.last_event_at.unwrap_or(stream.opened_at);
+                    self.target.stats.record_event(
+                        stream.last_event_at.is_none(),
+                        now.saturating_duration_since(since),
+                    );
+                    stream.last_event_at = Some(now);
+                    stream.events = stream.events.saturating_add(1);
+                    held_bytes = held_bytes.saturating_add(bytes);
+                    if let Some(shortfall) = self.shortfall(&stream) {
+                        if self.is_spent(&stream) {
+                            return RequestOutcome::too_few_messages(shortfall, 0);
+                        }
+                        read = timeout(request_timeout, stream.read_event()).await;
+                        self.remember_id(&stream);
+                        continue;
+                    }
+                    self.stream = Some(stream);
+                    return RequestOutcome::success(held_bytes);
+                }
+                Ok(EventRead::Ended) => {
+                    return self
+                        .shortfall(&stream)
+                        .map_or_else(RequestOutcome::transport_error, |shortfall| {
+                            RequestOutcome::too_few_messages(shortfall, 0)
+                        });
+                }
+                Ok(EventRead::Failed) => return RequestOutcome::transport_error(),
+                Err(_) => return RequestOutcome::timeout(),
+            }
+        }
+    }
+
+    fn is_spent(&self, stream: &SseStream) -> bool {
+        self.target
+            .events_per_conn
+            .is_some_and(|limit| stream.events >= limit)
+            || self
+                .target
+                .conn_duration
+                .is_some_and(|limit| stream.opened_at.elapsed() >= limit)
+    }
+
+    fn shortfall(&self, stream: &SseStream) -> Option<MessageShortfall> {
+        MessageShortfall::check(
+            u64::try_from(stream.events).unwrap_or(u64::MAX),
+            self.target.min_events,
+        )
+    }
+
+    fn remember_id(&mut self, stream: &SseStream) {
+        if let Some(id) = stream.parser.last_event_id() {
+            self.last_event_id = Some(id.to_owned());
+        }
+    }
+
+    async fn connect(&self, connect_timeout: Duration) -> Result<SseStream, RequestOutcome> {
+        let opened_at = Instant::now();
+        let mut request = self
+            .target
+            .client
+            .get(self.target.url.clone())
+            .header(ACCEPT, EVENT_STREAM);
+        for (key, value) in &self.target.headers {
+            request = request.header(key, value);
+        }
+        if self.target.resume
+            && let Some(id) = self.last_event_id.as_deref()
+        {
+            request = request.header(LAST_EVENT_ID, id);
+        }
+        let response = match timeout(connect_timeout, request.send()).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => return Err(RequestOutcome::transport_error()),
+            Err(_) => return Err(RequestOutcome::timeout()),
+        };
+        if !response.status().is_success() {
+            return Err(RequestOutcome::status(response.status().as_u16(), 0));
+        }
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| {
+                value
+                    .trim_start()
+                    .to_ascii_lowercase()
+                    .starts_with(EVENT_STREAM)
+            });
+        if !is_event_stream {
+            return Err(RequestOutcome::transport_error());
+        }
+        self.target
+            .stats
+            .connections
+            .fetch_add(1, Ordering::Relaxed);
+        Ok(SseStream {
+            response,
+            parser: SseParser::default(),
+            opened_at,
+            last_event_at: None,
+            events: 0,
+        })
+    }
+}
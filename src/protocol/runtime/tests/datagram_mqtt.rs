@@ -3,6 +3,7 @@ use tokio::sync::{broadcast, mpsc};
 
 use crate::error::{AppError, AppResult};
 use crate::metrics::Metrics;
+use crate::run_context::RunContext;
 
 use super::{
     SHUTDOWN_CHANNEL_CAPACITY, join_handle, join_result_handle, parse_args, permission_denied,
@@ -45,6 +46,7 @@ fn datagram_protocols_emit_success_metric() -> AppResult<()> {
             let sender_task = setup_request_sender(
                 args.protocol.to_domain(),
                 &args,
+                &RunContext::default(),
                 &shutdown_tx,
                 &metrics_tx,
                 None,
@@ -104,6 +106,7 @@ fn mqtt_protocol_emits_success_metric() -> AppResult<()> {
         let sender_task = setup_request_sender(
             args.protocol.to_domain(),
             &args,
+            &RunContext::default(),
             &shutdown_tx,
             &metrics_tx,
             None,
@@ -0,0 +1,265 @@
+use bytes::Bytes;
+use clap::Parser;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult};
+use crate::metrics::Metrics;
+use crate::run_context::RunContext;
+
+use super::super::grpc::{GrpcFrameCounter, grpc_frame};
+use super::{
+    SHUTDOWN_CHANNEL_CAPACITY, TEST_TIMEOUT, join_handle, join_result_handle, run_async_test,
+    setup_request_sender, wait_metric,
+};
+
+struct GrpcMockReply {
+    expected_request_frames: u64,
+    reply_frames: usize,
+    grpc_status: &'static str,
+}
+
+/// Serves one HTTP/2 connection, checking the request frame count and
+/// replying with `reply_frames` messages followed by a `grpc-status` trailer.
+async fn spawn_grpc_mock_server(
+    reply: GrpcMockReply,
+) -> AppResult<(std::net::SocketAddr, JoinHandle<AppResult<()>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|err| AppError::validation(format!("Failed to bind gRPC server: {}", err)))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|err| AppError::validation(format!("Failed to read gRPC addr: {}", err)))?;
+
+    let task = tokio::spawn(async move {
+        let (socket, _) = timeout(TEST_TIMEOUT, listener.accept())
+            .await
+            .map_err(|_err| AppError::validation("gRPC accept timed out"))?
+            .map_err(|err| AppError::validation(format!("gRPC accept failed: {}", err)))?;
+        let mut connection = h2::server::handshake(socket)
+            .await
+            .map_err(|err| AppError::validation(format!("gRPC handshake failed: {}", err)))?;
+
+        let mut handlers = Vec::new();
+        while let Some(accepted) = connection.accept().await {
+            let (request, mut respond) = accepted
+                .map_err(|err| AppError::validation(format!("gRPC accept stream: {}", err)))?;
+            let expected_request_frames = reply.expected_request_frames;
+            let reply_frames = reply.reply_frames;
+            let grpc_status = reply.grpc_status;
+            handlers.push(tokio::spawn(async move {
+                let mut body = request.into_body();
+                let mut counter = GrpcFrameCounter::default();
+                while let Some(chunk) = body.data().await {
+                    let chunk =
+                        chunk.map_err(|err| AppError::validation(format!("gRPC read: {}", err)))?;
+                    body.flow_control()
+                        .release_capacity(chunk.len())
+                        .map_err(|err| AppError::validation(format!("gRPC capacity: {}", err)))?;
+                    counter.feed(&chunk);
+                }
+                if counter.frames() != expected_request_frames || !counter.is_complete() {
+                    return Err(AppError::validation(format!(
+                        "Expected {} request frames, got {}",
+                        expected_request_frames,
+                        counter.frames()
+                    )));
+                }
+
+                let response = http::Response::builder()
+                    .status(200)
+                    .header("content-type", "application/grpc")
+                    .body(())
+                    .map_err(|err| AppError::validation(format!("gRPC response: {}", err)))?;
+                let mut send = respond
+                    .send_response(response, false)
+                    .map_err(|err| AppError::validation(format!("gRPC headers: {}", err)))?;
+                for _ in 0..reply_frames {
+                    send.send_data(Bytes::from(grpc_frame(b"pong")), false)
+                        .map_err(|err| AppError::validation(format!("gRPC data: {}", err)))?;
+                }
+                let mut trailers = http::HeaderMap::new();
+                trailers.insert("grpc-status", http::HeaderValue::from_static(grpc_status));
+                send.send_trailers(trailers)
+                    .map_err(|err| AppError::validation(format!("gRPC trailers: {}", err)))?;
+                Ok(())
+            }));
+        }
+
+        for handler in handlers {
+            handler
+                .await
+                .map_err(|err| AppError::validation(format!("gRPC handler failed: {}", err)))??;
+        }
+        Ok(())
+    });
+    Ok((addr, task))
+}
+
+fn parse_grpc_args(protocol: &str, url: &str, extra: &[&str]) -> AppResult<TesterArgs> {
+    let mut argv = vec![
+        "strest",
+        "--url",
+        url,
+        "--protocol",
+        protocol,
+        "--requests",
+        "1",
+        "--max-tasks",
+        "1",
+        "--spawn-rate",
+        "1",
+        "--spawn-interval",
+        "1",
+        "--timeout",
+        "3s",
+        "--connect-timeout",
+        "3s",
+    ];
+    argv.extend_from_slice(extra);
+    TesterArgs::try_parse_from(argv)
+        .map_err(|err| AppError::validation(format!("Expected parse success: {}", err)))
+}
+
+async fn run_grpc_case(
+    label: &str,
+    protocol: &str,
+    extra: &[&str],
+    reply: GrpcMockReply,
+) -> AppResult<Metrics> {
+    let (addr, server_task) = spawn_grpc_mock_server(reply).await?;
+    let url = format!("grpc://{addr}/test.Service/Method");
+    let args = parse_grpc_args(protocol, &url, extra)?;
+    let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+    let (metrics_tx, mut metrics_rx) = mpsc::channel::<Metrics>(8);
+
+    let sender_task = setup_request_sender(
+        args.protocol.to_domain(),
+        &args,
+        &RunContext::default(),
+        &shutdown_tx,
+        &metrics_tx,
+        None,
+    )?;
+    let metric = wait_metric(&mut metrics_rx, label).await?;
+    drop(shutdown_tx.send(()));
+    join_handle(sender_task, label).await?;
+    join_result_handle(server_task, label).await?;
+    if metric.timed_out || metric.transport_error {
+        return Err(AppError::validation(format!(
+            "Unexpected timeout or transport error for {}",
+            label
+        )));
+    }
+    Ok(metric)
+}
+
+#[test]
+fn grpc_modes_stream_frames_and_report_trailer_status() -> AppResult<()> {
+    run_async_test(async {
+        let frame_len = u64::try_from(grpc_frame(b"pong").len()).unwrap_or(u64::MAX);
+
+        let client_stream = run_grpc_case(
+            "client-stream",
+            "grpc-unary",
+            &["--grpc-mode", "client-stream", "--data", "a\nb\n\nc"],
+            GrpcMockReply {
+                expected_request_frames: 3,
+                reply_frames: 1,
+                grpc_status: "0",
+            },
+        )
+        .await?;
+        if client_stream.status_code != 200 || client_stream.response_bytes != frame_len {
+            return Err(AppError::validation(format!(
+                "Unexpected client-stream metric: status {} bytes {}",
+                client_stream.status_code, client_stream.response_bytes
+            )));
+        }
+
+        let server_stream = run_grpc_case(
+            "server-stream",
+            "grpc-streaming",
+            &["--data", "ping"],
+            GrpcMockReply {
+                expected_request_frames: 1,
+                reply_frames: 3,
+                grpc_status: "0",
+            },
+        )
+        .await?;
+        if server_stream.status_code != 200
+            || server_stream.response_bytes != frame_len.saturating_mul(3)
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected server-stream metric: status {} bytes {}",
+                server_stream.status_code, server_stream.response_bytes
+            )));
+        }
+
+        let bidi = run_grpc_case(
+            "bidi",
+            "grpc-streaming",
+            &["--grpc-mode", "bidi", "--data", "a\nb"],
+            GrpcMockReply {
+                expected_request_frames: 2,
+                reply_frames: 2,
+                grpc_status: "0",
+            },
+        )
+        .await?;
+        if bidi.response_bytes != frame_len.saturating_mul(2) {
+            return Err(AppError::validation(format!(
+                "Unexpected bidi bytes {}",
+                bidi.response_bytes
+            )));
+        }
+
+        let not_found = run_grpc_case(
+            "unary-not-found",
+            "grpc-unary",
+            &["--data", "ping"],
+            GrpcMockReply {
+                expected_request_frames: 1,
+                reply_frames: 0,
+                grpc_status: "5",
+            },
+        )
+        .await?;
+        if not_found.status_code != 404 {
+            return Err(AppError::validation(format!(
+                "Expected NOT_FOUND to map to 404, got {}",
+                not_found.status_code
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn grpc_frame_counter_handles_split_chunks() -> AppResult<()> {
+    let mut body = grpc_frame(b"hello");
+    body.extend_from_slice(&grpc_frame(b""));
+    body.extend_from_slice(&grpc_frame(b"world!"));
+
+    let mut counter = GrpcFrameCounter::default();
+    for chunk in body.chunks(3) {
+        counter.feed(chunk);
+    }
+    if counter.frames() != 3 || !counter.is_complete() {
+        return Err(AppError::validation(format!(
+            "Expected three complete frames, got {:?}",
+            counter
+        )));
+    }
+
+    let mut truncated = GrpcFrameCounter::default();
+    truncated.feed(body.get(..7).unwrap_or_default());
+    if truncated.is_complete() {
+        return Err(AppError::validation("Expected a truncated frame"));
+    }
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use clap::Parser;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult};
+use crate::metrics::{CheckFailure, Metrics};
+use crate::run_context::RunContext;
+
+use super::sse::spawn_sse_server;
+use super::{
+    SHUTDOWN_CHANNEL_CAPACITY, join_handle, join_result_handle, run_async_test,
+    setup_request_sender, spawn_websocket_mock_server, wait_metric,
+};
+
+fn parse_stream_args(
+    protocol: &str,
+    url: &str,
+    requests: &str,
+    min_messages: &str,
+) -> AppResult<TesterArgs> {
+    TesterArgs::try_parse_from([
+        "strest",
+        "--url",
+        url,
+        "--protocol",
+        protocol,
+        "--requests",
+        requests,
+        "--max-tasks",
+        "1",
+        "--spawn-rate",
+        "1",
+        "--spawn-interval",
+        "1",
+        "--timeout",
+        "1s",
+        "--connect-timeout",
+        "3s",
+        "--data",
+        "ping",
+        "--min-messages",
+        min_messages,
+    ])
+    .map_err(|err| AppError::validation(format!("Expected parse success: {}", err)))
+}
+
+type Outcome = (u16, Option<String>, Option<CheckFailure>);
+
+/// `(status, error, check failure)` of the next `count` metrics.
+async fn collect_outcomes(
+    metrics_rx: &mut mpsc::Receiver<Metrics>,
+    protocol: &str,
+    count: usize,
+) -> AppResult<Vec<Outcome>> {
+    let mut outcomes = Vec::with_capacity(count);
+    for _ in 0..count {
+        let metric = wait_metric(metrics_rx, protocol).await?;
+        if metric.timed_out || metric.transport_error {
+            return Err(AppError::validation(format!(
+                "Unexpected {} failure: {:?}",
+                protocol, metric
+            )));
+        }
+        outcomes.push((
+            metric.status_code,
+            metric.error_message.as_deref().map(str::to_owned),
+            metric.check_failure,
+        ));
+    }
+    Ok(outcomes)
+}
+
+#[test]
+fn sse_stream_closed_before_min_events_is_too_few_messages() -> AppResult<()> {
+    run_async_test(async {
+        // The preflight request uses up the first stream.
+        let (addr, server_task) = spawn_sse_server(3).await?;
+        let url = format!("http://{addr}/events");
+        let args = parse_stream_args("sse", &url, "2", "5")?;
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = mpsc::channel::<Metrics>(16);
+
+        let sender_task = setup_request_sender(
+            args.protocol.to_domain(),
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        let outcomes = collect_outcomes(&mut metrics_rx, "sse", 2).await?;
+        drop(shutdown_tx.send(()));
+        join_handle(sender_task, "sse").await?;
+        server_task.abort();
+
+        // A stream that falls short keeps the expected status but fails its check.
+        let short = (
+            200,
+            Some("too few messages: received 3, expected at least 5".to_owned()),
+            Some(CheckFailure::TooFewMessages),
+        );
+        if outcomes != vec![short.clone(), short] {
+            return Err(AppError::validation(format!(
+                "Unexpected SSE outcomes: {:?}",
+                outcomes
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn sse_events_are_held_until_min_events() -> AppResult<()> {
+    run_async_test(async {
+        // The preflight request uses up the first stream.
+        let (addr, server_task) = spawn_sse_server(3).await?;
+        let url = format!("http://{addr}/events");
+        let args = parse_stream_args("sse", &url, "3", "2")?;
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = mpsc::channel::<Metrics>(16);
+
+        let sender_task = setup_request_sender(
+            args.protocol.to_domain(),
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        let outcomes = collect_outcomes(&mut metrics_rx, "sse", 3).await?;
+        drop(shutdown_tx.send(()));
+        join_handle(sender_task, "sse").await?;
+        server_task.abort();
+
+        // The first two events of each stream count as one request.
+        if outcomes != vec![(200, None, None); 3] {
+            return Err(AppError::validation(format!(
+                "Unexpected SSE outcomes: {:?}",
+                outcomes
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn websocket_reply_below_min_messages_is_too_few_messages() -> AppResult<()> {
+    run_async_test(async {
+        let (addr, server_task) = spawn_websocket_mock_server(2).await?;
+        let url = format!("ws://{addr}");
+        let args = parse_stream_args("websocket", &url, "1", "5")?;
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = mpsc::channel::<Metrics>(16);
+
+        let sender_task = setup_request_sender(
+            args.protocol.to_domain(),
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        let outcomes = collect_outcomes(&mut metrics_rx, "websocket", 1).await?;
+        drop(shutdown_tx.send(()));
+        join_handle(sender_task, "websocket").await?;
+        join_result_handle(server_task, "websocket").await?;
+
+        let short = Some("too few messages: received 1, expected at least 5".to_owned());
+        if outcomes != vec![(200, short, Some(CheckFailure::TooFewMessages))] {
+            return Err(AppError::validation(format!(
+                "Unexpected websocket outcomes: {:?}",
+                outcomes
+            )));
+        }
+        Ok(())
+    })
+}
@@ -18,8 +18,13 @@ use super::resolve::resolve_endpoint;
 use super::setup_request_sender;
 
 mod datagram_mqtt;
+mod grpc_streaming;
+mod min_events;
+mod mqtt_subscribe;
 mod scheme_resolution;
+mod sse;
 mod transport_http_grpc;
+mod websocket_sequence;
 
 const SHUTDOWN_CHANNEL_CAPACITY: usize = 16;
 const TEST_TIMEOUT: Duration = Duration::from_secs(5);
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use clap::Parser;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult};
+use crate::metrics::Metrics;
+use crate::run_context::RunContext;
+
+use super::super::mqtt::{build_publish_packet, publish_payload, read_packet};
+use super::{
+    SHUTDOWN_CHANNEL_CAPACITY, join_handle, run_async_test, setup_request_sender, wait_metric,
+};
+
+/// Publish count (including the preflight) whose delivery is held back past `--timeout`.
+const DELAYED_PUBLISH: usize = 3;
+
+/// Minimal broker: acknowledges CONNECT and SUBSCRIBE and fans every
+/// PUBLISH out to all subscribers, preceded by a message from another client.
+async fn spawn_mqtt_broker() -> AppResult<(std::net::SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|err| AppError::validation(format!("Failed to bind MQTT broker: {}", err)))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|err| AppError::validation(format!("Failed to read MQTT addr: {}", err)))?;
+    let (deliveries, _) = broadcast::channel::<Vec<u8>>(64);
+    let publishes = Arc::new(AtomicUsize::new(0));
+
+    let task = tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve_mqtt_client(
+                stream,
+                deliveries.clone(),
+                Arc::clone(&publishes),
+            ));
+        }
+    });
+    Ok((addr, task))
+}
+
+async fn serve_mqtt_client(
+    stream: TcpStream,
+    deliveries: broadcast::Sender<Vec<u8>>,
+    publishes: Arc<AtomicUsize>,
+) {
+    let (mut reader, mut writer) = stream.into_split();
+    if read_packet(&mut reader).await.is_none()
+        || writer.write_all(&[0x20, 0x02, 0x00, 0x00]).await.is_err()
+    {
+        return;
+    }
+    let mut subscription: Option<broadcast::Receiver<Vec<u8>>> = None;
+    loop {
+        let delivered = async {
+            match subscription.as_mut() {
+                Some(receiver) => receiver.recv().await.ok(),
+                None => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            packet = read_packet(&mut reader) => {
+                let Some(packet) = packet else {
+                    return;
+                };
+                match packet.first_byte & 0xF0 {
+                    0x80 => {
+                        let packet_id = packet.body.get(..2).unwrap_or(&[0, 1]).to_vec();
+                        let mut suback = vec![0x90, 0x03];
+                        suback.extend_from_slice(&packet_id);
+                        suback.push(0x00);
+                        if writer.write_all(&suback).await.is_err() {
+                            return;
+                        }
+                        subscription = Some(deliveries.subscribe());
+                    }
+                    0x30 => {
+                        let Some(payload) = publish_payload(&packet) else {
+                            return;
+                        };
+                        let mut foreign = vec![0xFF; 16];
+                        foreign.extend_from_slice(b"other");
+                        drop(deliveries.send(build_publish_packet("devices/alpha", &foreign)));
+                        let delivery = build_publish_packet("devices/alpha", payload);
+                        let count = publishes.fetch_add(1, Ordering::Relaxed).saturating_add(1);
+                        if count == DELAYED_PUBLISH {
+                            let deliveries = deliveries.clone();
+                            tokio::spawn(async move {
+                                sleep(Duration::from_millis(500)).await;
+                                drop(deliveries.send(delivery));
+                            });
+                        } else {
+                            drop(deliveries.send(delivery));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some(packet) = delivered => {
+                if writer.write_all(&packet).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn mqtt_subscribe_mode_times_publish_to_delivery() -> AppResult<()> {
+    run_async_test(async {
+        let (addr, broker_task) = spawn_mqtt_broker().await?;
+        let url = format!("mqtt://{addr}/ignored");
+        let args = TesterArgs::try_parse_from([
+            "strest",
+            "--url",
+            url.as_str(),
+            "--protocol",
+            "mqtt",
+            "--mqtt-mode",
+            "subscribe",
+            "--mqtt-topic",
+            "devices/alpha",
+            "--requests",
+            "4",
+            "--max-tasks",
+            "1",
+            "--spawn-rate",
+            "1",
+            "--spawn-interval",
+            "1",
+            "--timeout",
+            "300ms",
+            "--connect-timeout",
+            "3s",
+            "--data",
+            "ping",
+        ])
+        .map_err(|err| AppError::validation(format!("Expected parse success: {}", err)))?;
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = mpsc::channel::<Metrics>(16);
+
+        let sender_task = setup_request_sender(
+            args.protocol.to_domain(),
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        let mut outcomes = Vec::new();
+        for _ in 0..4 {
+            let metric = wait_metric(&mut metrics_rx, "mqtt-subscribe").await?;
+            outcomes.push((
+                metric.timed_out,
+                metric.transport_error,
+                metric.response_bytes,
+            ));
+        }
+        drop(shutdown_tx.send(()));
+        join_handle(sender_task, "mqtt-subscribe").await?;
+        broker_task.abort();
+
+        // The delayed delivery times out, and its late arrival is not matched
+        // to the following publish.
+        let expected = vec![
+            (false, false, 4),
+            (true, false, 0),
+            (false, false, 4),
+            (false, false, 4),
+        ];
+        if outcomes != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected MQTT subscribe outcomes: {:?}",
+                outcomes
+            )));
+        }
+        Ok(())
+    })
+}
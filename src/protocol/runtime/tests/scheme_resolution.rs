@@ -2,6 +2,7 @@ use tokio::sync::{broadcast, mpsc};
 
 use crate::error::{AppError, AppResult, ValidationError};
 use crate::metrics::Metrics;
+use crate::run_context::RunContext;
 
 use super::{
     SHUTDOWN_CHANNEL_CAPACITY, parse_args, resolve_endpoint, run_async_test, setup_request_sender,
@@ -71,6 +72,7 @@ fn planned_protocols_reject_unsupported_url_schemes() -> AppResult<()> {
             let err = match setup_request_sender(
                 args.protocol.to_domain(),
                 &args,
+                &RunContext::default(),
                 &shutdown_tx,
                 &metrics_tx,
                 None,
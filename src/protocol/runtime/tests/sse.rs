@@ -0,0 +1,198 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::Parser;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult};
+use crate::metrics::Metrics;
+use crate::run_context::RunContext;
+
+use super::super::sse::{MAX_LINE_BYTES, SseParser};
+use super::{
+    SHUTDOWN_CHANNEL_CAPACITY, TEST_TIMEOUT, join_handle, run_async_test, setup_request_sender,
+    wait_metric,
+};
+
+/// Reads the request head and returns its `Last-Event-ID` header, if any.
+async fn read_last_event_id(stream: &mut TcpStream) -> AppResult<Option<String>> {
+    let mut head = Vec::with_capacity(1024);
+    while !head.windows(4).any(|bytes| bytes == b"\r\n\r\n") {
+        let mut chunk = [0_u8; 1024];
+        let read = timeout(TEST_TIMEOUT, stream.read(&mut chunk))
+            .await
+            .map_err(|_err| AppError::validation("SSE request read timed out"))?
+            .map_err(|err| AppError::validation(format!("SSE request read failed: {}", err)))?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(chunk.get(..read).unwrap_or_default());
+    }
+    let head = String::from_utf8_lossy(&head).into_owned();
+    Ok(head.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("last-event-id")
+            .then(|| value.trim().to_owned())
+    }))
+}
+
+/// Serves `connections` event streams of three events each, then closes
+/// them, and returns the `Last-Event-ID` each connection was opened with.
+pub(super) async fn spawn_sse_server(
+    connections: usize,
+) -> AppResult<(
+    std::net::SocketAddr,
+    JoinHandle<AppResult<Vec<Option<String>>>>,
+)> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|err| AppError::validation(format!("Failed to bind SSE server: {}", err)))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|err| AppError::validation(format!("Failed to read SSE addr: {}", err)))?;
+
+    let task = tokio::spawn(async move {
+        let mut last_event_ids = Vec::with_capacity(connections);
+        for conn in 1..=connections {
+            let (mut stream, _) = timeout(TEST_TIMEOUT, listener.accept())
+                .await
+                .map_err(|_err| AppError::validation("SSE accept timed out"))?
+                .map_err(|err| AppError::validation(format!("SSE accept failed: {}", err)))?;
+            last_event_ids.push(read_last_event_id(&mut stream).await?);
+            let events = [
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n: keepalive\n\nid: c{conn}-1\ndata: hello\n\n"
+                ),
+                format!("id: c{conn}-2\r\ndata: a\r\ndata: bc\r\n\r\n"),
+                format!("event: tick\nid: c{conn}-3\ndata: world\n\n"),
+            ];
+            for event in events {
+                // The client may drop the stream early; that is not a server failure.
+                if stream.write_all(event.as_bytes()).await.is_err() {
+                    break;
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+        }
+        Ok(last_event_ids)
+    });
+    Ok((addr, task))
+}
+
+#[test]
+fn sse_records_each_event_and_resumes_after_reconnect() -> AppResult<()> {
+    run_async_test(async {
+        // Preflight stream, then one worker stream of three events and a
+        // reconnect for the fourth.
+        let (addr, server_task) = spawn_sse_server(3).await?;
+        let url = format!("http://{addr}/events");
+        let args = TesterArgs::try_parse_from([
+            "strest",
+            "--url",
+            url.as_str(),
+            "--protocol",
+            "sse",
+            "--requests",
+            "4",
+            "--max-tasks",
+            "1",
+            "--spawn-rate",
+            "1",
+            "--spawn-interval",
+            "1",
+            "--timeout",
+            "3s",
+            "--connect-timeout",
+            "3s",
+            "--sse-resume",
+        ])
+        .map_err(|err| AppError::validation(format!("Expected parse success: {}", err)))?;
+        let stats = Arc::new(crate::protocol::SseStats::default());
+        let run = RunContext {
+            sse_stats: Some(Arc::clone(&stats)),
+            ..RunContext::default()
+        };
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = mpsc::channel::<Metrics>(16);
+
+        let sender_task = setup_request_sender(
+            args.protocol.to_domain(),
+            &args,
+            &run,
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        let mut sizes = Vec::new();
+        for _ in 0..4 {
+            let metric = wait_metric(&mut metrics_rx, "sse").await?;
+            if metric.timed_out || metric.transport_error {
+                return Err(AppError::validation(format!(
+                    "Unexpected SSE metric: {:?}",
+                    metric
+                )));
+            }
+            sizes.push(metric.response_bytes);
+        }
+        drop(shutdown_tx.send(()));
+        join_handle(sender_task, "sse").await?;
+
+        // Multi-line data is joined with '\n'; comments are not events.
+        if sizes != vec![5, 4, 5, 5] {
+            return Err(AppError::validation(format!(
+                "Unexpected SSE event sizes: {:?}",
+                sizes
+            )));
+        }
+        let last_event_ids = timeout(TEST_TIMEOUT, server_task)
+            .await
+            .map_err(|_err| AppError::validation("SSE server timed out"))?
+            .map_err(|err| AppError::validation(format!("SSE server failed: {}", err)))??;
+        let expected = vec![None, None, Some("c2-3".to_owned())];
+        if last_event_ids != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected Last-Event-ID headers: {:?}",
+                last_event_ids
+            )));
+        }
+        // The server spaces events 20ms apart; the preflight stream counts too.
+        if stats.events() < 4
+            || stats.connections() < 2
+            || stats.avg_inter_event() < Duration::from_millis(10)
+        {
+            return Err(AppError::validation(format!(
+                "Unexpected SSE stats: {:?}",
+                stats
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn sse_parser_fails_on_lines_past_the_cap() -> AppResult<()> {
+    let mut parser = SseParser::default();
+    let at_cap = vec![b'x'; MAX_LINE_BYTES];
+    if !parser.feed(b"data: ok\n\n") || !parser.feed(&at_cap) || !parser.feed(b"\n") {
+        return Err(AppError::validation(
+            "Expected lines up to the cap to be accepted",
+        ));
+    }
+    if parser.next_event() != Some(2) {
+        return Err(AppError::validation(
+            "Expected the event before the long line",
+        ));
+    }
+    if parser.feed(&at_cap) && parser.feed(b"x") {
+        return Err(AppError::validation(
+            "Expected a line past the cap to fail the stream",
+        ));
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@ use tokio::sync::{broadcast, mpsc};
 
 use crate::error::{AppError, AppResult};
 use crate::metrics::Metrics;
+use crate::run_context::RunContext;
 
 use super::{
     SHUTDOWN_CHANNEL_CAPACITY, join_handle, join_result_handle, parse_args, permission_denied,
@@ -55,6 +56,7 @@ fn transport_and_http_protocols_emit_success_metric() -> AppResult<()> {
             let sender_task = setup_request_sender(
                 args.protocol.to_domain(),
                 &args,
+                &RunContext::default(),
                 &shutdown_tx,
                 &metrics_tx,
                 None,
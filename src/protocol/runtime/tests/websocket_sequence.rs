@@ -0,0 +1,152 @@
+use clap::Parser;
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+use crate::args::TesterArgs;
+use crate::error::{AppError, AppResult};
+use crate::metrics::Metrics;
+use crate::run_context::RunContext;
+
+use super::{
+    SHUTDOWN_CHANNEL_CAPACITY, TEST_TIMEOUT, join_handle, run_async_test, setup_request_sender,
+    wait_metric,
+};
+
+/// Serves `connections` WebSocket connections one after another, answering
+/// every text message with a ping followed by `ok`, and returns the messages
+/// received on each connection.
+async fn spawn_websocket_sequence_server(
+    connections: usize,
+) -> AppResult<(
+    std::net::SocketAddr,
+    JoinHandle<AppResult<Vec<Vec<String>>>>,
+)> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|err| AppError::validation(format!("Failed to bind websocket server: {}", err)))?;
+    let addr = listener.local_addr().map_err(|err| {
+        AppError::validation(format!("Failed to read websocket server addr: {}", err))
+    })?;
+
+    let task = tokio::spawn(async move {
+        let mut received = Vec::with_capacity(connections);
+        for _ in 0..connections {
+            let (stream, _) = timeout(TEST_TIMEOUT, listener.accept())
+                .await
+                .map_err(|_err| AppError::validation("Websocket accept timed out"))?
+                .map_err(|err| AppError::validation(format!("Websocket accept failed: {}", err)))?;
+            let mut ws = timeout(TEST_TIMEOUT, accept_async(stream))
+                .await
+                .map_err(|_err| AppError::validation("Websocket handshake timed out"))?
+                .map_err(|err| {
+                    AppError::validation(format!("Websocket handshake failed: {}", err))
+                })?;
+
+            let mut messages = Vec::new();
+            loop {
+                let incoming = timeout(TEST_TIMEOUT, ws.next())
+                    .await
+                    .map_err(|_err| AppError::validation("Websocket recv timed out"))?;
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Pong(_))) => continue,
+                    // The client drops or closes the connection at the end of a sequence.
+                    Some(Ok(_) | Err(_)) | None => break,
+                };
+                messages.push(text);
+                ws.send(Message::Ping(b"keepalive".to_vec()))
+                    .await
+                    .map_err(|err| {
+                        AppError::validation(format!("Websocket ping failed: {}", err))
+                    })?;
+                ws.send(Message::Text("ok".to_owned()))
+                    .await
+                    .map_err(|err| {
+                        AppError::validation(format!("Websocket send failed: {}", err))
+                    })?;
+            }
+            received.push(messages);
+        }
+        Ok(received)
+    });
+    Ok((addr, task))
+}
+
+#[test]
+fn websocket_sequence_reuses_connection_and_skips_keepalives() -> AppResult<()> {
+    run_async_test(async {
+        // Preflight connection, then 7 requests over connections of 3, 3 and 1 messages.
+        let (addr, server_task) = spawn_websocket_sequence_server(4).await?;
+        let url = format!("ws://{addr}");
+        let args = TesterArgs::try_parse_from([
+            "strest",
+            "--url",
+            url.as_str(),
+            "--protocol",
+            "websocket",
+            "--requests",
+            "7",
+            "--max-tasks",
+            "1",
+            "--spawn-rate",
+            "1",
+            "--spawn-interval",
+            "1",
+            "--timeout",
+            "3s",
+            "--connect-timeout",
+            "3s",
+            "--data",
+            "a\nb",
+            "--ws-messages-per-conn",
+            "3",
+            "--ws-message-interval-ms",
+            "1",
+        ])
+        .map_err(|err| AppError::validation(format!("Expected parse success: {}", err)))?;
+        let (shutdown_tx, _) = broadcast::channel::<()>(SHUTDOWN_CHANNEL_CAPACITY);
+        let (metrics_tx, mut metrics_rx) = mpsc::channel::<Metrics>(16);
+
+        let sender_task = setup_request_sender(
+            args.protocol.to_domain(),
+            &args,
+            &RunContext::default(),
+            &shutdown_tx,
+            &metrics_tx,
+            None,
+        )?;
+        for _ in 0..7 {
+            let metric = wait_metric(&mut metrics_rx, "websocket-sequence").await?;
+            if metric.timed_out || metric.transport_error || metric.response_bytes != 2 {
+                return Err(AppError::validation(format!(
+                    "Unexpected websocket sequence metric: {:?}",
+                    metric
+                )));
+            }
+        }
+        drop(shutdown_tx.send(()));
+        join_handle(sender_task, "websocket-sequence").await?;
+
+        let received = timeout(TEST_TIMEOUT, server_task)
+            .await
+            .map_err(|_err| AppError::validation("Websocket server timed out"))?
+            .map_err(|err| AppError::validation(format!("Websocket server failed: {}", err)))??;
+        let expected = vec![
+            vec!["a"],
+            vec!["a", "b", "a"],
+            vec!["b", "a", "b"],
+            vec!["a"],
+        ];
+        if received != expected {
+            return Err(AppError::validation(format!(
+                "Unexpected messages per connection: {:?}",
+                received
+            )));
+        }
+        Ok(())
+    })
+}
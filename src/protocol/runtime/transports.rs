@@ -1,15 +1,16 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures_util::{SinkExt, StreamExt};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpStream, UdpSocket};
 use tokio::time::timeout;
-use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
 use url::Url;
 
-use super::types::RequestOutcome;
+use super::types::{MessageShortfall, RequestOutcome};
 
 pub(super) async fn tcp_request_once(
     endpoint: SocketAddr,
@@ -69,11 +70,15 @@ pub(super) async fn udp_request_once(
     }
 }
 
+/// Opens a connection, sends `payload` and waits for one reply, or for
+/// `min_messages` data frames when set; fewer within `request_timeout` is
+/// reported as too few messages.
 pub(super) async fn websocket_request_once(
     ws_url: &Url,
     payload: &str,
     request_timeout: Duration,
     connect_timeout: Duration,
+    min_messages: Option<u64>,
 ) -> RequestOutcome {
     let connect = timeout(connect_timeout, connect_async(ws_url.as_str())).await;
     let (mut stream, _) = match connect {
@@ -95,6 +100,27 @@ pub(super) async fn websocket_request_once(
         }
     }
 
+    if let Some(min_messages) = min_messages {
+        let mut received: u64 = 0;
+        let mut response_bytes: u64 = 0;
+        let read_messages = async {
+            while received < min_messages {
+                let Some(bytes) = next_data_message(&mut stream).await else {
+                    break;
+                };
+                received = received.saturating_add(1);
+                response_bytes = response_bytes.saturating_add(bytes);
+            }
+        };
+        // Running out of time just stops counting.
+        drop(timeout(request_timeout, read_messages).await);
+        drop(stream.close(None).await);
+        return MessageShortfall::check(received, Some(min_messages)).map_or_else(
+            || RequestOutcome::success(response_bytes),
+            |shortfall| RequestOutcome::too_few_messages(shortfall, response_bytes),
+        );
+    }
+
     let next_message = timeout(request_timeout, stream.next()).await;
     let response_bytes = match next_message {
         Ok(Some(Ok(message))) => message_bytes(&message),
@@ -107,6 +133,103 @@ pub(super) async fn websocket_request_once(
     RequestOutcome::success(response_bytes)
 }
 
+type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// One worker's WebSocket connection, reused for `messages_per_conn`
+/// round-trips before it is closed and reopened.
+pub(super) struct WebSocketSequence {
+    ws_url: Url,
+    messages: Arc<[String]>,
+    messages_per_conn: usize,
+    stream: Option<WebSocket>,
+    sent_on_conn: usize,
+    next_message: usize,
+}
+
+impl WebSocketSequence {
+    pub(super) const fn new(
+        ws_url: Url,
+        messages: Arc<[String]>,
+        messages_per_conn: usize,
+    ) -> Self {
+        Self {
+            ws_url,
+            messages,
+            messages_per_conn,
+            stream: None,
+            sent_on_conn: 0,
+            next_message: 0,
+        }
+    }
+
+    /// Sends the next message and waits for the next data frame in reply.
+    ///
+    /// The first message on a connection includes the handshake in its
+    /// latency. Any failure drops the connection so the next call reconnects.
+    pub(super) async fn round_trip(
+        &mut self,
+        request_timeout: Duration,
+        connect_timeout: Duration,
+    ) -> RequestOutcome {
+        let mut stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => {
+                self.sent_on_conn = 0;
+                match timeout(connect_timeout, connect_async(self.ws_url.as_str())).await {
+                    Ok(Ok((stream, _))) => stream,
+                    Ok(Err(_)) => return RequestOutcome::transport_error(),
+                    Err(_) => return RequestOutcome::timeout(),
+                }
+            }
+        };
+
+        let payload = self
+            .messages
+            .get(self.next_message)
+            .cloned()
+            .unwrap_or_default();
+        self.next_message = self
+            .next_message
+            .saturating_add(1)
+            .checked_rem(self.messages.len())
+            .unwrap_or(0);
+        match timeout(request_timeout, stream.send(Message::Text(payload))).await {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => return RequestOutcome::transport_error(),
+            Err(_) => return RequestOutcome::timeout(),
+        }
+
+        let outcome = match timeout(request_timeout, next_data_message(&mut stream)).await {
+            Ok(Some(response_bytes)) => RequestOutcome::success(response_bytes),
+            Ok(None) => return RequestOutcome::transport_error(),
+            Err(_) => return RequestOutcome::timeout(),
+        };
+
+        self.sent_on_conn = self.sent_on_conn.saturating_add(1);
+        if self.sent_on_conn >= self.messages_per_conn {
+            drop(stream.close(None).await);
+        } else {
+            self.stream = Some(stream);
+        }
+        outcome
+    }
+}
+
+/// Size of the next text or binary frame; ping/pong keepalives are skipped.
+/// `None` when the connection closes or fails first.
+async fn next_data_message(stream: &mut WebSocket) -> Option<u64> {
+    while let Some(message) = stream.next().await {
+        match message.ok()? {
+            message @ (Message::Text(_) | Message::Binary(_)) => {
+                return Some(message_bytes(&message));
+            }
+            Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+            Message::Close(_) => return None,
+        }
+    }
+    None
+}
+
 fn message_bytes(message: &Message) -> u64 {
     match message {
         Message::Text(value) => u64::try_from(value.len()).unwrap_or(u64::MAX),
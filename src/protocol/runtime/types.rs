@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use tokio::sync::{Semaphore, mpsc};
 
+use crate::args::ExpectedStatus;
 use crate::metrics::{LogSink, Metrics};
 use crate::shutdown::ShutdownSender;
 
@@ -14,6 +15,32 @@ pub(super) struct RequestOutcome {
     pub(super) timed_out: bool,
     pub(super) transport_error: bool,
     pub(super) response_bytes: u64,
+    /// Status reported by the protocol itself; `None` means the expected status.
+    pub(super) status_code: Option<u16>,
+    /// Set when a stream fell short of `--min-events`.
+    pub(super) shortfall: Option<MessageShortfall>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct MessageShortfall {
+    pub(super) received: u64,
+    pub(super) expected: u64,
+}
+
+impl MessageShortfall {
+    /// `Some` when `received` is below `min_messages`.
+    pub(super) fn check(received: u64, min_messages: Option<u64>) -> Option<Self> {
+        min_messages
+            .filter(|expected| received < *expected)
+            .map(|expected| Self { received, expected })
+    }
+
+    pub(super) fn message(self) -> String {
+        format!(
+            "too few messages: received {}, expected at least {}",
+            self.received, self.expected
+        )
+    }
 }
 
 impl RequestOutcome {
@@ -22,6 +49,28 @@ impl RequestOutcome {
             timed_out: false,
             transport_error: false,
             response_bytes,
+            status_code: None,
+            shortfall: None,
+        }
+    }
+
+    pub(super) const fn status(status_code: u16, response_bytes: u64) -> Self {
+        Self {
+            timed_out: false,
+            transport_error: false,
+            response_bytes,
+            status_code: Some(status_code),
+            shortfall: None,
+        }
+    }
+
+    pub(super) const fn too_few_messages(shortfall: MessageShortfall, response_bytes: u64) -> Self {
+        Self {
+            timed_out: false,
+            transport_error: false,
+            response_bytes,
+            status_code: None,
+            shortfall: Some(shortfall),
         }
     }
 
@@ -30,6 +79,8 @@ impl RequestOutcome {
             timed_out: true,
             transport_error: false,
             response_bytes: 0,
+            status_code: None,
+            shortfall: None,
         }
     }
 
@@ -38,6 +89,8 @@ impl RequestOutcome {
             timed_out: false,
             transport_error: true,
             response_bytes: 0,
+            status_code: None,
+            shortfall: None,
         }
     }
 }
@@ -125,7 +178,7 @@ pub(super) struct TransportRunContext<'ctx> {
     pub(super) log_sink: &'ctx Option<Arc<LogSink>>,
     pub(super) wait_ongoing: bool,
     pub(super) latency_correction: bool,
-    pub(super) expected_status_code: u16,
+    pub(super) expected_status_code: ExpectedStatus,
     pub(super) request_timeout: Duration,
     pub(super) connect_timeout: Duration,
     pub(super) request_fn: &'ctx TransportRequestFn,
@@ -41,6 +41,8 @@ fn builtins_register_http_as_executable() {
     assert!(registry.adapter(ProtocolKind::Http).is_some());
     assert!(registry.supports_execution(ProtocolKind::Http));
     assert!(registry.supports_load_mode(ProtocolKind::Http, LoadMode::Soak));
+    assert!(registry.supports_load_mode(ProtocolKind::Http, LoadMode::Concurrency));
+    assert!(!registry.supports_load_mode(ProtocolKind::Websocket, LoadMode::Concurrency));
 }
 
 #[test]
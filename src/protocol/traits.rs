@@ -7,6 +7,7 @@ use crate::args::TesterArgs;
 use crate::domain::run::{LoadMode, ProtocolKind};
 use crate::error::{AppError, AppResult, ValidationError};
 use crate::metrics::{LogSink, Metrics};
+use crate::run_context::RunContext;
 use crate::shutdown::ShutdownSender;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,11 +33,12 @@ pub trait TransportAdapter: ProtocolAdapter {
     fn setup_request_sender(
         &self,
         args: &TesterArgs,
+        run: &RunContext,
         shutdown_tx: &ShutdownSender,
         metrics_tx: &mpsc::Sender<Metrics>,
         log_sink: Option<&Arc<LogSink>>,
     ) -> AppResult<JoinHandle<()>> {
-        let _ = (args, shutdown_tx, metrics_tx, log_sink);
+        let _ = (args, run, shutdown_tx, metrics_tx, log_sink);
         Err(AppError::validation(ValidationError::UnsupportedProtocol {
             protocol: self.protocol().as_str().to_owned(),
             supported: String::new(),
@@ -0,0 +1,27 @@
+//! State created once per run and shared by the senders, the metrics
+//! collector and the end-of-run summary.
+
+use std::sync::{Arc, atomic::AtomicU64};
+
+use crate::http::Http2Multiplex;
+use crate::metrics::RateFeedback;
+use crate::protocol::SseStats;
+use crate::sinks::checkpoint::ResumeState;
+
+/// Per-run handles that are not CLI settings; the local runner fills in the
+/// ones its flags need before traffic starts.
+#[derive(Debug, Clone, Default)]
+pub struct RunContext {
+    /// Rolling p99 and discovered rate for `--find-max-rate`.
+    pub(crate) rate_feedback: Option<Arc<RateFeedback>>,
+    /// Connections opened under `--requests-per-connection`.
+    pub(crate) connection_counter: Option<Arc<AtomicU64>>,
+    /// Stream accounting for `--http2-parallel`.
+    pub(crate) http2_multiplex: Option<Arc<Http2Multiplex>>,
+    /// Event timing for `--protocol sse`.
+    pub(crate) sse_stats: Option<Arc<SseStats>>,
+    /// Totals reloaded from `--checkpoint-out` under `--resume`.
+    pub(crate) resume_state: Option<Arc<ResumeState>>,
+    /// URL list read from stdin for `--urls-from-file -u -`.
+    pub(crate) stdin_urls: Option<Arc<str>>,
+}
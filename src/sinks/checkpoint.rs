@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::config::SinkStats;
+use super::format::format_x100;
+use crate::error::{AppError, AppResult, SinkError};
+use crate::metrics::{CheckFailures, ErrorReason, LatencyHistogram};
+
+/// Suffix for the temp file that is renamed over the checkpoint.
+const CHECKPOINT_TMP_SUFFIX: &str = ".tmp";
+
+/// Running totals saved under `resume` in each checkpoint, so `--resume` can
+/// continue the aggregation of a run that crashed or was stopped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeState {
+    pub duration_ms: u64,
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub timeout_requests: u64,
+    pub transport_errors: u64,
+    pub non_expected_status: u64,
+    pub min_latency_ms: u64,
+    pub max_latency_ms: u64,
+    pub latency_sum_ms: u128,
+    pub success_min_latency_ms: u64,
+    pub success_max_latency_ms: u64,
+    pub success_latency_sum_ms: u128,
+    pub histogram_b64: String,
+    pub success_histogram_b64: String,
+    pub http_versions: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub error_reasons: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub check_failures: CheckFailures,
+}
+
+impl ResumeState {
+    /// Time already covered by the checkpointed run.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    /// Decodes the all-request and success-only latency histograms.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either histogram payload is invalid.
+    pub fn histograms(&self) -> AppResult<(LatencyHistogram, LatencyHistogram)> {
+        Ok((
+            LatencyHistogram::decode_base64(&self.histogram_b64)?,
+            LatencyHistogram::decode_base64(&self.success_histogram_b64)?,
+        ))
+    }
+
+    /// Response counts per HTTP version, keyed like the collector's map.
+    #[must_use]
+    pub fn http_versions(&self) -> BTreeMap<&'static str, u64> {
+        let mut versions = BTreeMap::new();
+        for (label, count) in &self.http_versions {
+            let label = match label.as_str() {
+                "HTTP/0.9" => "HTTP/0.9",
+                "HTTP/1.0" => "HTTP/1.0",
+                "HTTP/1.1" => "HTTP/1.1",
+                "HTTP/2" => "HTTP/2",
+                "HTTP/3" => "HTTP/3",
+                _ => "other",
+            };
+            let total = versions.entry(label).or_insert(0u64);
+            *total = total.saturating_add(*count);
+        }
+        versions
+    }
+
+    /// Failed requests per error reason, keyed like the collector's map.
+    #[must_use]
+    pub fn error_reasons(&self) -> BTreeMap<&'static str, u64> {
+        let mut reasons = BTreeMap::new();
+        for (label, count) in &self.error_reasons {
+            let total = reasons
+                .entry(ErrorReason::from_label(label).label())
+                .or_insert(0u64);
+            *total = total.saturating_add(*count);
+        }
+        reasons
+    }
+}
+
+#[derive(Deserialize)]
+struct CheckpointFile {
+    resume: Option<ResumeState>,
+}
+
+/// Reads the resume state from a checkpoint written by an earlier run.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, has no resume
+/// state, or holds invalid histograms.
+pub async fn load_resume_state(path: &str) -> AppResult<ResumeState> {
+    let bytes = tokio::fs::read(path).await.map_err(|err| {
+        AppError::sink(SinkError::ReadCheckpoint {
+            path: path.to_owned(),
+            source: err,
+        })
+    })?;
+    let file: CheckpointFile = serde_json::from_slice(&bytes).map_err(|err| {
+        AppError::sink(SinkError::ParseCheckpoint {
+            path: path.to_owned(),
+            source: err,
+        })
+    })?;
+    let state = file.resume.ok_or_else(|| {
+        AppError::sink(SinkError::CheckpointMissingResume {
+            path: path.to_owned(),
+        })
+    })?;
+    state.histograms()?;
+    Ok(state)
+}
+
+/// Writes the running summary to `path` as JSON.
+///
+/// The payload is written to `<path>.tmp` first and renamed into place, so a
+/// crash mid-write leaves the previous checkpoint intact. `complete` is set on
+/// the last write after the collector has drained. `resume` carries the raw
+/// totals and histograms that `--resume` reloads.
+///
+/// # Errors
+///
+/// Returns an error if the payload fails to serialize or the file cannot be
+/// written or renamed.
+pub async fn write_checkpoint(
+    path: &str,
+    stats: &SinkStats,
+    resume: Option<&ResumeState>,
+    complete: bool,
+) -> AppResult<()> {
+    let payload = serde_json::json!({
+        "complete": complete,
+        "written_at_unix_ms": unix_millis(),
+        "duration_ms": u64::try_from(stats.duration.as_millis()).unwrap_or(u64::MAX),
+        "total_requests": stats.total_requests,
+        "successful_requests": stats.successful_requests,
+        "error_requests": stats.error_requests,
+        "timeout_requests": stats.timeout_requests,
+        "min_latency_ms": stats.min_latency_ms,
+        "max_latency_ms": stats.max_latency_ms,
+        "avg_latency_ms": stats.avg_latency_ms,
+        "p50_latency_ms": stats.p50_latency_ms,
+        "p90_latency_ms": stats.p90_latency_ms,
+        "p99_latency_ms": stats.p99_latency_ms,
+        "p999_latency_ms": stats.p999_latency_ms,
+        "p9999_latency_ms": stats.p9999_latency_ms,
+        "success_rate": format_x100(stats.success_rate_x100),
+        "avg_rps": format_x100(stats.avg_rps_x100),
+        "avg_rpm": format_x100(stats.avg_rpm_x100),
+        "resume": resume,
+    });
+    let json = serde_json::to_vec_pretty(&payload)
+        .map_err(|err| AppError::sink(SinkError::SerializeCheckpoint { source: err }))?;
+
+    let tmp_path = format!("{}{}", path, CHECKPOINT_TMP_SUFFIX);
+    let write_err = |err: std::io::Error| {
+        AppError::sink(SinkError::WriteCheckpoint {
+            path: path.to_owned(),
+            source: err,
+        })
+    };
+    tokio::fs::write(&tmp_path, json).await.map_err(write_err)?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .map_err(write_err)?;
+    Ok(())
+}
+
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        })
+}
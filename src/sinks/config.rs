@@ -7,6 +7,7 @@ pub struct SinksConfig {
     pub prometheus: Option<PrometheusSinkConfig>,
     pub otel: Option<OtelSinkConfig>,
     pub influx: Option<InfluxSinkConfig>,
+    pub pushgateway: Option<PushgatewaySinkConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -19,9 +20,27 @@ pub struct OtelSinkConfig {
     pub path: String,
 }
 
+/// InfluxDB line protocol; written to `path`, POSTed to `<url>/api/v2/write`, or both.
 #[derive(Debug, Clone, Deserialize)]
 pub struct InfluxSinkConfig {
-    pub path: String,
+    pub path: Option<String>,
+    pub url: Option<String>,
+    pub org: Option<String>,
+    /// Required with `url`.
+    pub bucket: Option<String>,
+    /// Sent as `Authorization: Token <token>`.
+    pub token: Option<String>,
+    /// Per-write timeout; defaults to 5000ms.
+    pub timeout_ms: Option<u64>,
+}
+
+/// Prometheus Pushgateway target; receives the same exposition as the textfile sink.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PushgatewaySinkConfig {
+    pub url: String,
+    pub job: String,
+    /// Per-push timeout; defaults to 5000ms.
+    pub timeout_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +56,8 @@ pub struct SinkStats {
     pub p50_latency_ms: u64,
     pub p90_latency_ms: u64,
     pub p99_latency_ms: u64,
+    pub p999_latency_ms: u64,
+    pub p9999_latency_ms: u64,
     pub success_rate_x100: u64,
     pub avg_rps_x100: u64,
     pub avg_rpm_x100: u64,
@@ -1,4 +1,6 @@
 //! Output sinks for run summaries and streaming stats.
+pub mod checkpoint;
 pub mod config;
 mod format;
+pub mod samples;
 pub mod writers;
@@ -0,0 +1,76 @@
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+use super::config::SinkStats;
+use crate::error::{AppError, AppResult, SinkError};
+use crate::metrics::AggregatedMetricSample;
+
+/// `--stream-samples` file that receives one JSON line per sink tick.
+///
+/// Every line is flushed as soon as it is written, so `tail -f` and other
+/// readers see each sample while the run is still going.
+pub struct SampleStream {
+    path: String,
+    writer: BufWriter<File>,
+}
+
+impl SampleStream {
+    /// Opens the file at `path` for appending, creating it if missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub async fn open(path: &str) -> AppResult<Self> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .await
+            .map_err(|err| write_error(path, err))?;
+        Ok(Self {
+            path: path.to_owned(),
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// Appends `sample` as one JSON line and flushes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample cannot be serialized or written.
+    pub async fn append(&mut self, sample: &AggregatedMetricSample) -> AppResult<()> {
+        let mut line = serde_json::to_vec(sample)
+            .map_err(|err| AppError::sink(SinkError::SerializeSample { source: err }))?;
+        line.push(b'\n');
+        self.writer
+            .write_all(&line)
+            .await
+            .map_err(|err| write_error(&self.path, err))?;
+        self.writer
+            .flush()
+            .await
+            .map_err(|err| write_error(&self.path, err))
+    }
+}
+
+/// Aggregated sample for the running totals in `stats`.
+#[must_use]
+pub fn sample_from_stats(stats: &SinkStats) -> AggregatedMetricSample {
+    AggregatedMetricSample {
+        elapsed_ms: u64::try_from(stats.duration.as_millis()).unwrap_or(u64::MAX),
+        total_requests: stats.total_requests,
+        successful_requests: stats.successful_requests,
+        error_requests: stats.error_requests,
+        avg_latency_ms: stats.avg_latency_ms,
+        p50_latency_ms: stats.p50_latency_ms,
+        p90_latency_ms: stats.p90_latency_ms,
+        p99_latency_ms: stats.p99_latency_ms,
+    }
+}
+
+fn write_error(path: &str, err: std::io::Error) -> AppError {
+    AppError::sink(SinkError::WriteSamples {
+        path: path.to_owned(),
+        source: err,
+    })
+}
@@ -1,29 +1,193 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use super::config::{
-    InfluxSinkConfig, OtelSinkConfig, PrometheusSinkConfig, SinkStats, SinksConfig,
+    InfluxSinkConfig, OtelSinkConfig, PrometheusSinkConfig, PushgatewaySinkConfig, SinkStats,
+    SinksConfig,
 };
 use super::format::{format_x100, write_line};
 use crate::error::{AppError, AppResult, SinkError};
 
+/// Suffix for the temp file that is renamed over the Prometheus textfile.
+const PROMETHEUS_TMP_SUFFIX: &str = ".tmp";
+/// Text exposition format accepted by the Pushgateway.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+const PUSHGATEWAY_DEFAULT_TIMEOUT_MS: u64 = 5_000;
+const INFLUX_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+const INFLUX_DEFAULT_TIMEOUT_MS: u64 = 5_000;
+
 /// Write configured sink outputs to their destinations.
 ///
+/// `run_id` and `protocol` tag the Influx line; the Pushgateway groups by `run_id`.
+/// Run `tags` become Prometheus labels, Influx tags, and OTel resource attributes.
+/// Every configured sink is written even when an earlier one fails.
+///
 /// # Errors
 ///
 /// Returns an error if any sink output fails to serialize or write.
-pub async fn write_sinks(config: &SinksConfig, stats: &SinkStats) -> AppResult<()> {
-    if let Some(prom) = config.prometheus.as_ref() {
-        write_prometheus(prom, stats).await?;
+pub async fn write_sinks(
+    config: &SinksConfig,
+    stats: &SinkStats,
+    run_id: &str,
+    protocol: &str,
+    tags: &BTreeMap<String, String>,
+) -> AppResult<()> {
+    let mut errors = Vec::new();
+    if let Some(prom) = config.prometheus.as_ref()
+        && let Err(err) = write_prometheus(prom, stats, tags).await
+    {
+        errors.push(err);
+    }
+    if let Some(pushgateway) = config.pushgateway.as_ref()
+        && let Err(err) = push_pushgateway(pushgateway, stats, run_id, tags).await
+    {
+        errors.push(err);
+    }
+    if let Some(otel) = config.otel.as_ref()
+        && let Err(err) = write_otel(otel, stats, tags).await
+    {
+        errors.push(err);
+    }
+    if let Some(influx) = config.influx.as_ref()
+        && let Err(err) = write_influx(influx, stats, run_id, protocol, tags).await
+    {
+        errors.push(err);
+    }
+    if errors.len() > 1 {
+        return Err(AppError::sink(SinkError::Multiple {
+            messages: errors.iter().map(ToString::to_string).collect(),
+        }));
     }
-    if let Some(otel) = config.otel.as_ref() {
-        write_otel(otel, stats).await?;
+    errors.pop().map_or(Ok(()), Err)
+}
+
+/// HTTP client shared by the Pushgateway and Influx writers.
+///
+/// Built on the first push and reused on every tick so the connection pool
+/// and TLS sessions survive between pushes; timeouts are set per request.
+fn sink_client() -> Result<reqwest::Client, reqwest::Error> {
+    static SINK_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    if let Some(client) = SINK_CLIENT.get() {
+        return Ok(client.clone());
     }
-    if let Some(influx) = config.influx.as_ref() {
-        write_influx(influx, stats).await?;
+    let client = reqwest::Client::builder().build()?;
+    Ok(SINK_CLIENT.get_or_init(|| client).clone())
+}
+
+/// Resolves the run id used to group pushed sink metrics.
+///
+/// Agents pass the controller's run id; local runs get one id per process
+/// (`<unix_ms>-<pid>`, the same shape the controller uses).
+#[must_use]
+pub fn sink_run_id(run_id: Option<&str>) -> String {
+    static LOCAL_RUN_ID: OnceLock<String> = OnceLock::new();
+    run_id.map_or_else(
+        || {
+            LOCAL_RUN_ID
+                .get_or_init(|| {
+                    let now_ms = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |elapsed| elapsed.as_millis());
+                    format!("{}-{}", now_ms, std::process::id())
+                })
+                .clone()
+        },
+        ToOwned::to_owned,
+    )
+}
+
+async fn write_prometheus(
+    config: &PrometheusSinkConfig,
+    stats: &SinkStats,
+    tags: &BTreeMap<String, String>,
+) -> AppResult<()> {
+    let output = render_prometheus(stats, tags)?;
+
+    // Scrapers can read the textfile mid-write, so swap it in with a rename.
+    let tmp_path = format!("{}{}", config.path, PROMETHEUS_TMP_SUFFIX);
+    tokio::fs::write(&tmp_path, output)
+        .await
+        .map_err(|err| AppError::sink(SinkError::WritePrometheus { source: err }))?;
+    tokio::fs::rename(&tmp_path, &config.path)
+        .await
+        .map_err(|err| AppError::sink(SinkError::WritePrometheus { source: err }))?;
+    Ok(())
+}
+
+/// PUTs the Prometheus exposition to `<url>/metrics/job/<job>/run_id/<run_id>`.
+///
+/// PUT replaces every metric in the group, so each tick overwrites the last.
+async fn push_pushgateway(
+    config: &PushgatewaySinkConfig,
+    stats: &SinkStats,
+    run_id: &str,
+    tags: &BTreeMap<String, String>,
+) -> AppResult<()> {
+    let url = pushgateway_url(config, run_id)?;
+    let output = render_prometheus(stats, tags)?;
+    let timeout = Duration::from_millis(
+        config
+            .timeout_ms
+            .unwrap_or(PUSHGATEWAY_DEFAULT_TIMEOUT_MS)
+            .max(1),
+    );
+    let push_err = |err: reqwest::Error| {
+        AppError::sink(SinkError::PushPushgateway {
+            url: config.url.clone(),
+            source: err,
+        })
+    };
+    let response = sink_client()
+        .map_err(push_err)?
+        .put(url)
+        .timeout(timeout)
+        .header(reqwest::header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)
+        .body(output)
+        .send()
+        .await
+        .map_err(push_err)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::sink(SinkError::PushgatewayStatus {
+            url: config.url.clone(),
+            status: status.as_u16(),
+        }));
     }
     Ok(())
 }
 
-async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> AppResult<()> {
+fn pushgateway_url(config: &PushgatewaySinkConfig, run_id: &str) -> AppResult<reqwest::Url> {
+    let invalid = || {
+        AppError::sink(SinkError::InvalidPushgatewayUrl {
+            url: config.url.clone(),
+        })
+    };
+    let mut url = reqwest::Url::parse(&config.url).map_err(|_err| invalid())?;
+    url.path_segments_mut()
+        .map_err(|()| invalid())?
+        .pop_if_empty()
+        .extend(["metrics", "job", config.job.as_str(), "run_id", run_id]);
+    Ok(url)
+}
+
+fn render_prometheus(stats: &SinkStats, tags: &BTreeMap<String, String>) -> AppResult<String> {
     let mut output = String::new();
+    let tag_labels = tags
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_prometheus_label(value)))
+        .collect::<Vec<_>>();
+    let labels = |extra: &str| {
+        let all: Vec<&str> = std::iter::once(extra)
+            .filter(|label| !label.is_empty())
+            .chain(tag_labels.iter().map(String::as_str))
+            .collect();
+        if all.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", all.join(","))
+        }
+    };
 
     write_line(
         &mut output,
@@ -32,7 +196,25 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(&mut output, "# TYPE strest_duration_seconds gauge")?;
     write_line(
         &mut output,
-        &format!("strest_duration_seconds {}", stats.duration.as_secs()),
+        &format!(
+            "strest_duration_seconds{} {}",
+            labels(""),
+            stats.duration.as_secs()
+        ),
+    )?;
+
+    write_line(
+        &mut output,
+        "# HELP strest_start_time_seconds Unix time the measured run started.",
+    )?;
+    write_line(&mut output, "# TYPE strest_start_time_seconds gauge")?;
+    write_line(
+        &mut output,
+        &format!(
+            "strest_start_time_seconds{} {}",
+            labels(""),
+            run_start_unix_secs(stats.duration)
+        ),
     )?;
 
     write_line(
@@ -42,7 +224,11 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(&mut output, "# TYPE strest_requests_total counter")?;
     write_line(
         &mut output,
-        &format!("strest_requests_total {}", stats.total_requests),
+        &format!(
+            "strest_requests_total{} {}",
+            labels(""),
+            stats.total_requests
+        ),
     )?;
 
     write_line(
@@ -53,7 +239,8 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(
         &mut output,
         &format!(
-            "strest_requests_success_total {}",
+            "strest_requests_success_total{} {}",
+            labels(""),
             stats.successful_requests
         ),
     )?;
@@ -65,7 +252,11 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(&mut output, "# TYPE strest_requests_error_total counter")?;
     write_line(
         &mut output,
-        &format!("strest_requests_error_total {}", stats.error_requests),
+        &format!(
+            "strest_requests_error_total{} {}",
+            labels(""),
+            stats.error_requests
+        ),
     )?;
 
     write_line(
@@ -75,7 +266,11 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(&mut output, "# TYPE strest_requests_timeout_total counter")?;
     write_line(
         &mut output,
-        &format!("strest_requests_timeout_total {}", stats.timeout_requests),
+        &format!(
+            "strest_requests_timeout_total{} {}",
+            labels(""),
+            stats.timeout_requests
+        ),
     )?;
 
     write_line(
@@ -86,7 +281,8 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(
         &mut output,
         &format!(
-            "strest_success_rate {}",
+            "strest_success_rate{} {}",
+            labels(""),
             format_x100(stats.success_rate_x100)
         ),
     )?;
@@ -98,7 +294,11 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(&mut output, "# TYPE strest_avg_rps gauge")?;
     write_line(
         &mut output,
-        &format!("strest_avg_rps {}", format_x100(stats.avg_rps_x100)),
+        &format!(
+            "strest_avg_rps{} {}",
+            labels(""),
+            format_x100(stats.avg_rps_x100)
+        ),
     )?;
 
     write_line(
@@ -108,7 +308,11 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(&mut output, "# TYPE strest_avg_rpm gauge")?;
     write_line(
         &mut output,
-        &format!("strest_avg_rpm {}", format_x100(stats.avg_rpm_x100)),
+        &format!(
+            "strest_avg_rpm{} {}",
+            labels(""),
+            format_x100(stats.avg_rpm_x100)
+        ),
     )?;
 
     write_line(
@@ -118,49 +322,92 @@ async fn write_prometheus(config: &PrometheusSinkConfig, stats: &SinkStats) -> A
     write_line(&mut output, "# TYPE strest_latency_ms gauge")?;
     write_line(
         &mut output,
-        &format!("strest_latency_ms{{stat=\"min\"}} {}", stats.min_latency_ms),
+        &format!(
+            "strest_latency_ms{} {}",
+            labels("stat=\"min\""),
+            stats.min_latency_ms
+        ),
     )?;
     write_line(
         &mut output,
-        &format!("strest_latency_ms{{stat=\"avg\"}} {}", stats.avg_latency_ms),
+        &format!(
+            "strest_latency_ms{} {}",
+            labels("stat=\"avg\""),
+            stats.avg_latency_ms
+        ),
     )?;
     write_line(
         &mut output,
-        &format!("strest_latency_ms{{stat=\"max\"}} {}", stats.max_latency_ms),
+        &format!(
+            "strest_latency_ms{} {}",
+            labels("stat=\"max\""),
+            stats.max_latency_ms
+        ),
     )?;
     write_line(
         &mut output,
         &format!(
-            "strest_latency_ms{{quantile=\"0.5\"}} {}",
+            "strest_latency_ms{} {}",
+            labels("quantile=\"0.5\""),
             stats.p50_latency_ms
         ),
     )?;
     write_line(
         &mut output,
         &format!(
-            "strest_latency_ms{{quantile=\"0.9\"}} {}",
+            "strest_latency_ms{} {}",
+            labels("quantile=\"0.9\""),
             stats.p90_latency_ms
         ),
     )?;
     write_line(
         &mut output,
         &format!(
-            "strest_latency_ms{{quantile=\"0.99\"}} {}",
+            "strest_latency_ms{} {}",
+            labels("quantile=\"0.99\""),
             stats.p99_latency_ms
         ),
     )?;
+    write_line(
+        &mut output,
+        &format!(
+            "strest_latency_ms{} {}",
+            labels("quantile=\"0.999\""),
+            stats.p999_latency_ms
+        ),
+    )?;
+    write_line(
+        &mut output,
+        &format!(
+            "strest_latency_ms{} {}",
+            labels("quantile=\"0.9999\""),
+            stats.p9999_latency_ms
+        ),
+    )?;
 
-    tokio::fs::write(&config.path, output)
-        .await
-        .map_err(|err| AppError::sink(SinkError::WritePrometheus { source: err }))?;
-    Ok(())
+    Ok(output)
 }
 
-async fn write_otel(config: &OtelSinkConfig, stats: &SinkStats) -> AppResult<()> {
+/// Unix seconds at which the measured run started, derived from its elapsed duration.
+fn run_start_unix_secs(duration: Duration) -> u64 {
+    SystemTime::now()
+        .checked_sub(duration)
+        .and_then(|start| start.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |start| start.as_secs())
+}
+
+async fn write_otel(
+    config: &OtelSinkConfig,
+    stats: &SinkStats,
+    tags: &BTreeMap<String, String>,
+) -> AppResult<()> {
+    let mut resource = serde_json::Map::new();
+    resource.insert("service.name".to_owned(), serde_json::json!("strest"));
+    for (key, value) in tags {
+        resource.insert(key.clone(), serde_json::json!(value));
+    }
     let payload = serde_json::json!({
-        "resource": {
-            "service.name": "strest"
-        },
+        "resource": resource,
         "metrics": [
             { "name": "strest.duration", "unit": "s", "value": stats.duration.as_secs() },
             { "name": "strest.requests_total", "value": stats.total_requests },
@@ -173,6 +420,8 @@ async fn write_otel(config: &OtelSinkConfig, stats: &SinkStats) -> AppResult<()>
             { "name": "strest.latency_p50_ms", "value": stats.p50_latency_ms },
             { "name": "strest.latency_p90_ms", "value": stats.p90_latency_ms },
             { "name": "strest.latency_p99_ms", "value": stats.p99_latency_ms },
+            { "name": "strest.latency_p999_ms", "value": stats.p999_latency_ms },
+            { "name": "strest.latency_p9999_ms", "value": stats.p9999_latency_ms },
             { "name": "strest.success_rate", "value": format_x100(stats.success_rate_x100) },
             { "name": "strest.avg_rps", "value": format_x100(stats.avg_rps_x100) },
             { "name": "strest.avg_rpm", "value": format_x100(stats.avg_rpm_x100) }
@@ -187,27 +436,283 @@ async fn write_otel(config: &OtelSinkConfig, stats: &SinkStats) -> AppResult<()>
     Ok(())
 }
 
-async fn write_influx(config: &InfluxSinkConfig, stats: &SinkStats) -> AppResult<()> {
-    let line = format!(
-        "strest_summary duration_ms={}i,total_requests={}i,successful_requests={}i,error_requests={}i,timeout_requests={}i,min_latency_ms={}i,max_latency_ms={}i,avg_latency_ms={}i,p50_latency_ms={}i,p90_latency_ms={}i,p99_latency_ms={}i,success_rate={},avg_rps={},avg_rpm={}\n",
+async fn write_influx(
+    config: &InfluxSinkConfig,
+    stats: &SinkStats,
+    run_id: &str,
+    protocol: &str,
+    tags: &BTreeMap<String, String>,
+) -> AppResult<()> {
+    let timestamp_ns = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    let line = render_influx(stats, run_id, protocol, tags, timestamp_ns);
+
+    if let Some(path) = config.path.as_deref() {
+        tokio::fs::write(path, &line)
+            .await
+            .map_err(|err| AppError::sink(SinkError::WriteInflux { source: err }))?;
+    }
+    if let Some(base_url) = config.url.as_deref() {
+        post_influx(config, base_url, line).await?;
+    }
+    Ok(())
+}
+
+/// POSTs one line to `<url>/api/v2/write` with nanosecond precision.
+async fn post_influx(config: &InfluxSinkConfig, base_url: &str, line: String) -> AppResult<()> {
+    let url = influx_write_url(config, base_url)?;
+    let timeout = Duration::from_millis(
+        config
+            .timeout_ms
+            .unwrap_or(INFLUX_DEFAULT_TIMEOUT_MS)
+            .max(1),
+    );
+    let push_err = |err: reqwest::Error| {
+        AppError::sink(SinkError::PushInflux {
+            url: base_url.to_owned(),
+            source: err,
+        })
+    };
+    let mut request = sink_client()
+        .map_err(push_err)?
+        .post(url)
+        .timeout(timeout)
+        .header(reqwest::header::CONTENT_TYPE, INFLUX_CONTENT_TYPE)
+        .body(line);
+    if let Some(token) = config.token.as_deref() {
+        request = request.header(reqwest::header::AUTHORIZATION, format!("Token {}", token));
+    }
+    let response = request.send().await.map_err(push_err)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::sink(SinkError::InfluxStatus {
+            url: base_url.to_owned(),
+            status: status.as_u16(),
+        }));
+    }
+    Ok(())
+}
+
+fn influx_write_url(config: &InfluxSinkConfig, base_url: &str) -> AppResult<reqwest::Url> {
+    let invalid = || {
+        AppError::sink(SinkError::InvalidInfluxUrl {
+            url: base_url.to_owned(),
+        })
+    };
+    let mut url = reqwest::Url::parse(base_url).map_err(|_err| invalid())?;
+    url.path_segments_mut()
+        .map_err(|()| invalid())?
+        .pop_if_empty()
+        .extend(["api", "v2", "write"]);
+    {
+        let mut query = url.query_pairs_mut();
+        if let Some(org) = config.org.as_deref() {
+            query.append_pair("org", org);
+        }
+        if let Some(bucket) = config.bucket.as_deref() {
+            query.append_pair("bucket", bucket);
+        }
+        query.append_pair("precision", "ns");
+    }
+    Ok(url)
+}
+
+/// `strest,run=<id>,protocol=<name>[,<tag>=<value>...] total=..i,...,rps=.. <timestamp_ns>`;
+/// latencies are ms.
+fn render_influx(
+    stats: &SinkStats,
+    run_id: &str,
+    protocol: &str,
+    tags: &BTreeMap<String, String>,
+    timestamp_ns: u128,
+) -> String {
+    let tag_set: String = tags
+        .iter()
+        .map(|(key, value)| format!(",{}={}", key, escape_influx_tag(value)))
+        .collect();
+    format!(
+        "strest,run={},protocol={}{} duration_ms={}i,total={}i,success={}i,errors={}i,timeouts={}i,min={}i,avg={}i,max={}i,p50={}i,p90={}i,p99={}i,p999={}i,p9999={}i,success_rate={},rps={},rpm={} {}\n",
+        escape_influx_tag(run_id),
+        escape_influx_tag(protocol),
+        tag_set,
         stats.duration.as_millis(),
         stats.total_requests,
         stats.successful_requests,
         stats.error_requests,
         stats.timeout_requests,
         stats.min_latency_ms,
-        stats.max_latency_ms,
         stats.avg_latency_ms,
+        stats.max_latency_ms,
         stats.p50_latency_ms,
         stats.p90_latency_ms,
         stats.p99_latency_ms,
+        stats.p999_latency_ms,
+        stats.p9999_latency_ms,
         format_x100(stats.success_rate_x100),
         format_x100(stats.avg_rps_x100),
-        format_x100(stats.avg_rpm_x100)
-    );
+        format_x100(stats.avg_rpm_x100),
+        timestamp_ns
+    )
+}
 
-    tokio::fs::write(&config.path, line)
-        .await
-        .map_err(|err| AppError::sink(SinkError::WriteInflux { source: err }))?;
-    Ok(())
+/// Escapes the characters line protocol treats as delimiters in tag values.
+fn escape_influx_tag(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, ',' | '=' | ' ' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Escapes a Prometheus label value (`\\`, `"`, and newlines).
+fn escape_prometheus_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppResult;
+
+    fn sample_stats() -> SinkStats {
+        SinkStats {
+            duration: Duration::from_millis(2_500),
+            total_requests: 100,
+            successful_requests: 98,
+            error_requests: 2,
+            timeout_requests: 1,
+            min_latency_ms: 3,
+            max_latency_ms: 90,
+            avg_latency_ms: 12,
+            p50_latency_ms: 10,
+            p90_latency_ms: 20,
+            p99_latency_ms: 80,
+            p999_latency_ms: 90,
+            p9999_latency_ms: 90,
+            success_rate_x100: 9_800,
+            avg_rps_x100: 4_000,
+            avg_rpm_x100: 240_000,
+        }
+    }
+
+    #[test]
+    fn influx_line_has_tags_fields_and_ns_timestamp() -> AppResult<()> {
+        let stats = sample_stats();
+        let tags = BTreeMap::from([("env".to_owned(), "eu west".to_owned())]);
+        let line = render_influx(
+            &stats,
+            "run 1,a=b",
+            "http",
+            &tags,
+            1_700_000_000_000_000_000,
+        );
+        let expected = "strest,run=run\\ 1\\,a\\=b,protocol=http,env=eu\\ west duration_ms=2500i,total=100i,success=98i,errors=2i,timeouts=1i,min=3i,avg=12i,max=90i,p50=10i,p90=20i,p99=80i,p999=90i,p9999=90i,success_rate=98.00,rps=40.00,rpm=2400.00 1700000000000000000\n";
+        if line != expected {
+            return Err(AppError::sink(SinkError::TestExpectationValue {
+                message: "Unexpected influx line",
+                value: line,
+            }));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn prometheus_lines_carry_run_tags_as_labels() -> AppResult<()> {
+        let tags = BTreeMap::from([
+            ("build".to_owned(), "1234".to_owned()),
+            ("env".to_owned(), "say \"hi\"".to_owned()),
+        ]);
+        let output = render_prometheus(&sample_stats(), &tags)?;
+        for expected in [
+            "strest_requests_total{build=\"1234\",env=\"say \\\"hi\\\"\"} 100",
+            "strest_latency_ms{stat=\"min\",build=\"1234\",env=\"say \\\"hi\\\"\"} 3",
+        ] {
+            if !output.lines().any(|line| line == expected) {
+                return Err(AppError::sink(SinkError::TestExpectationValue {
+                    message: "Missing tagged Prometheus line",
+                    value: output,
+                }));
+            }
+        }
+        let untagged = render_prometheus(&sample_stats(), &BTreeMap::new())?;
+        if !untagged
+            .lines()
+            .any(|line| line == "strest_requests_total 100")
+        {
+            return Err(AppError::sink(SinkError::TestExpectationValue {
+                message: "Unexpected untagged Prometheus output",
+                value: untagged,
+            }));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn influx_write_url_adds_api_path_and_query() -> AppResult<()> {
+        let config = InfluxSinkConfig {
+            path: None,
+            url: Some("http://influx:8086/".to_owned()),
+            org: Some("team a".to_owned()),
+            bucket: Some("load".to_owned()),
+            token: None,
+            timeout_ms: None,
+        };
+        let url = influx_write_url(&config, "http://influx:8086/")?;
+        if url.as_str() != "http://influx:8086/api/v2/write?org=team+a&bucket=load&precision=ns" {
+            return Err(AppError::sink(SinkError::TestExpectationValue {
+                message: "Unexpected influx write URL",
+                value: url.to_string(),
+            }));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn failing_pushgateway_does_not_skip_file_sinks() -> AppResult<()> {
+        let dir = tempfile::tempdir()
+            .map_err(|err| AppError::sink(SinkError::WritePrometheus { source: err }))?;
+        let otel_path = dir.path().join("otel.json");
+        let config = SinksConfig {
+            update_interval_ms: None,
+            prometheus: None,
+            otel: Some(OtelSinkConfig {
+                path: otel_path.to_string_lossy().into_owned(),
+            }),
+            influx: None,
+            pushgateway: Some(PushgatewaySinkConfig {
+                url: "http://127.0.0.1:1".to_owned(),
+                job: "strest".to_owned(),
+                timeout_ms: Some(500),
+            }),
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| AppError::sink(SinkError::WritePrometheus { source: err }))?;
+        let result = runtime.block_on(write_sinks(
+            &config,
+            &sample_stats(),
+            "run-1",
+            "http",
+            &BTreeMap::new(),
+        ));
+        if result.is_ok() {
+            return Err(AppError::sink(SinkError::TestExpectation {
+                message: "Expected the unreachable Pushgateway to fail",
+            }));
+        }
+        if !otel_path.exists() {
+            return Err(AppError::sink(SinkError::TestExpectation {
+                message: "OTel sink was skipped after the Pushgateway failure",
+            }));
+        }
+        Ok(())
+    }
 }
@@ -2,12 +2,19 @@ use std::time::Duration;
 
 use crate::metrics::MetricRecord;
 
+/// Real-time playback speed, as a percentage.
+pub(crate) const NORMAL_SPEED_PERCENT: u64 = 100;
+/// Speeds reachable with the speed up/down keys, slowest first.
+const PLAYBACK_SPEEDS_PERCENT: [u64; 7] = [25, 50, 100, 200, 400, 800, 1600];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct PlaybackState {
     pub(crate) start_ms: u64,
     pub(crate) cursor_ms: u64,
     pub(crate) end_ms: u64,
     pub(crate) playing: bool,
+    /// Playback speed as a percentage of real time.
+    pub(crate) speed_percent: u64,
 }
 
 impl PlaybackState {
@@ -18,6 +25,7 @@ impl PlaybackState {
             cursor_ms: start_ms,
             end_ms,
             playing: true,
+            speed_percent: NORMAL_SPEED_PERCENT,
         }
     }
 }
@@ -30,6 +38,8 @@ pub(crate) enum PlaybackAction {
     SeekStart,
     SeekEnd,
     Restart,
+    SpeedUp,
+    SpeedDown,
 }
 
 #[must_use]
@@ -65,6 +75,23 @@ pub(crate) fn apply_playback_action(
             state.playing = false;
             state.cursor_ms = state.end_ms;
         }
+        PlaybackAction::SpeedUp => {
+            if let Some(faster) = PLAYBACK_SPEEDS_PERCENT
+                .into_iter()
+                .find(|speed| *speed > state.speed_percent)
+            {
+                state.speed_percent = faster;
+            }
+        }
+        PlaybackAction::SpeedDown => {
+            if let Some(slower) = PLAYBACK_SPEEDS_PERCENT
+                .into_iter()
+                .rev()
+                .find(|speed| *speed < state.speed_percent)
+            {
+                state.speed_percent = slower;
+            }
+        }
     }
     *state != previous
 }
@@ -82,7 +109,12 @@ pub(crate) fn advance_playback(state: &mut PlaybackState, elapsed: Duration, tic
     if steps == 0 {
         return false;
     }
-    let advance_ms = u64::try_from(steps.saturating_mul(tick_ms)).unwrap_or(u64::MAX);
+    let advance_ms = steps
+        .saturating_mul(tick_ms)
+        .saturating_mul(u128::from(state.speed_percent))
+        .checked_div(u128::from(NORMAL_SPEED_PERCENT))
+        .unwrap_or(0);
+    let advance_ms = u64::try_from(advance_ms).unwrap_or(u64::MAX);
     state.cursor_ms = state.cursor_ms.saturating_add(advance_ms).min(state.end_ms);
     if state.cursor_ms >= state.end_ms {
         state.cursor_ms = state.end_ms;
@@ -114,6 +146,7 @@ pub(crate) fn clamp_window_to_records(
         cursor_ms: base.cursor_ms.clamp(min_ms, max_ms),
         end_ms: max_ms,
         playing: base.playing,
+        speed_percent: base.speed_percent,
     }
 }
 
@@ -124,8 +157,8 @@ mod tests {
     use crate::metrics::MetricRecord;
 
     use super::{
-        PlaybackAction, PlaybackState, advance_playback, apply_playback_action,
-        clamp_window_to_records, records_range, resolve_step_ms,
+        NORMAL_SPEED_PERCENT, PlaybackAction, PlaybackState, advance_playback,
+        apply_playback_action, clamp_window_to_records, records_range, resolve_step_ms,
     };
 
     fn metric_record(elapsed_ms: u64) -> MetricRecord {
@@ -137,6 +170,7 @@ mod tests {
             transport_error: false,
             response_bytes: 0,
             in_flight_ops: 0,
+            check_failed: false,
         }
     }
 
@@ -156,6 +190,7 @@ mod tests {
             cursor_ms: 5000,
             end_ms: 9000,
             playing: true,
+            speed_percent: NORMAL_SPEED_PERCENT,
         };
 
         let changed_back = apply_playback_action(&mut state, PlaybackAction::SeekBackward, 2500);
@@ -180,6 +215,7 @@ mod tests {
             cursor_ms: 1000,
             end_ms: 2500,
             playing: true,
+            speed_percent: NORMAL_SPEED_PERCENT,
         };
 
         let changed_short_tick = advance_playback(&mut state, Duration::from_millis(500), 1000);
@@ -193,6 +229,40 @@ mod tests {
         assert!(!state.playing);
     }
 
+    #[test]
+    fn speed_keys_scale_playback_and_stop_at_the_ends() {
+        let mut state = PlaybackState::new(0, 60_000);
+
+        assert!(apply_playback_action(
+            &mut state,
+            PlaybackAction::SpeedUp,
+            1000
+        ));
+        assert_eq!(state.speed_percent, 200);
+        assert!(advance_playback(
+            &mut state,
+            Duration::from_millis(1000),
+            1000
+        ));
+        assert_eq!(state.cursor_ms, 2000);
+
+        for _ in 0..10 {
+            apply_playback_action(&mut state, PlaybackAction::SpeedDown, 1000);
+        }
+        assert_eq!(state.speed_percent, 25);
+        assert!(!apply_playback_action(
+            &mut state,
+            PlaybackAction::SpeedDown,
+            1000
+        ));
+        assert!(advance_playback(
+            &mut state,
+            Duration::from_millis(1000),
+            1000
+        ));
+        assert_eq!(state.cursor_ms, 2250);
+    }
+
     #[test]
     fn records_range_returns_min_and_max_when_present() {
         let records = vec![metric_record(100), metric_record(500)];
@@ -207,6 +277,7 @@ mod tests {
             cursor_ms: 15_000,
             end_ms: 20_000,
             playing: true,
+            speed_percent: NORMAL_SPEED_PERCENT,
         };
         let clamped = clamp_window_to_records(&base, 1000, 10_000);
         assert_eq!(clamped.start_ms, 1000);
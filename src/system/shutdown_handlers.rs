@@ -1,7 +1,8 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, poll, read};
-use tokio::sync::broadcast;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, poll, read};
+use tokio::sync::{broadcast, mpsc};
 
 use crate::shutdown::{ShutdownReceiver, ShutdownSender};
 
@@ -12,6 +13,8 @@ use tokio::signal::unix::{SignalKind, signal};
 const SHUTDOWN_CHANNEL_CAPACITY: usize = 1;
 /// Keyboard polling interval for Ctrl+C detection in TTY mode.
 const KEYBOARD_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Polling interval for `--stop-file` existence checks.
+const STOP_FILE_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
 #[must_use]
 pub fn shutdown_channel() -> (ShutdownSender, ShutdownReceiver) {
@@ -50,6 +53,37 @@ pub fn setup_keyboard_shutdown_handler(
     })
 }
 
+/// Forwards key presses to `key_tx` until shutdown, for UIs that handle their
+/// own keys (including quit). While it runs it is the only stdin reader, so do
+/// not combine it with [`setup_keyboard_shutdown_handler`].
+pub fn setup_key_event_reader(
+    shutdown_tx: &ShutdownSender,
+    key_tx: mpsc::UnboundedSender<KeyEvent>,
+) -> tokio::task::JoinHandle<()> {
+    let mut shutdown_rx = shutdown_tx.subscribe();
+
+    tokio::task::spawn_blocking(move || {
+        loop {
+            match shutdown_rx.try_recv() {
+                Ok(()) => break,
+                Err(broadcast::error::TryRecvError::Closed) => break,
+                Err(broadcast::error::TryRecvError::Empty) => {}
+                Err(broadcast::error::TryRecvError::Lagged(_)) => {}
+            }
+
+            let has_event = poll(KEYBOARD_POLL_INTERVAL).unwrap_or_default();
+
+            if has_event
+                && let Ok(Event::Key(key)) = read()
+                && key.kind == KeyEventKind::Press
+                && key_tx.send(key).is_err()
+            {
+                break;
+            }
+        }
+    })
+}
+
 pub fn setup_signal_shutdown_handler(shutdown_tx: &ShutdownSender) -> tokio::task::JoinHandle<()> {
     let shutdown_tx = shutdown_tx.clone();
     tokio::spawn(async move {
@@ -95,6 +129,42 @@ pub fn setup_signal_shutdown_handler(shutdown_tx: &ShutdownSender) -> tokio::tas
     })
 }
 
+/// Triggers a graceful shutdown once `path` exists.
+///
+/// The watcher polls instead of using filesystem notifications so it behaves
+/// the same on network mounts and container volumes, and it exits as soon as
+/// the run shuts down for any other reason. A file left over from an earlier
+/// run is logged as a warning, since it stops this run on the first poll.
+pub fn setup_stop_file_shutdown_handler(
+    shutdown_tx: &ShutdownSender,
+    path: PathBuf,
+) -> tokio::task::JoinHandle<()> {
+    if path.exists() {
+        tracing::warn!(
+            "Stop file {} already exists; the run will stop immediately. Remove it first to run to completion.",
+            path.display()
+        );
+    }
+    let shutdown_tx = shutdown_tx.clone();
+    tokio::spawn(async move {
+        let mut shutdown_rx = shutdown_tx.subscribe();
+        let mut interval = tokio::time::interval(STOP_FILE_POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => break,
+                _ = interval.tick() => {
+                    if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                        tracing::info!("Stop file {} detected; shutting down.", path.display());
+                        drop(shutdown_tx.send(()));
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,6 +208,42 @@ mod tests {
         })
     }
 
+    #[test]
+    fn stop_file_handler_sends_shutdown_when_file_appears() -> AppResult<()> {
+        run_async_test(async {
+            let dir = tempfile::tempdir()
+                .map_err(|err| AppError::validation(format!("tempdir: {}", err)))?;
+            let path = dir.path().join("stop");
+            let (shutdown_tx, mut shutdown_rx) = shutdown_channel();
+            let handle = setup_stop_file_shutdown_handler(&shutdown_tx, path.clone());
+
+            tokio::time::sleep(SIGNAL_HANDLER_SETTLE).await;
+            if shutdown_rx.try_recv().is_ok() {
+                return Err(AppError::validation(
+                    "Shutdown sent before the stop file existed",
+                ));
+            }
+            std::fs::write(&path, b"")
+                .map_err(|err| AppError::validation(format!("write stop file: {}", err)))?;
+
+            tokio::time::timeout(SHUTDOWN_HANDLER_TIMEOUT, shutdown_rx.recv())
+                .await
+                .map_err(|err| {
+                    AppError::validation(format!("Timed out waiting for shutdown: {}", err))
+                })?
+                .map_err(|err| AppError::validation(format!("Shutdown recv error: {}", err)))?;
+            tokio::time::timeout(SHUTDOWN_HANDLER_TIMEOUT, handle)
+                .await
+                .map_err(|err| {
+                    AppError::validation(format!("Timed out waiting for stop-file task: {}", err))
+                })?
+                .map_err(|err| {
+                    AppError::validation(format!("Stop-file task join error: {}", err))
+                })?;
+            Ok(())
+        })
+    }
+
     #[test]
     fn signal_handler_exits_on_shutdown() -> AppResult<()> {
         run_async_test(async {
@@ -14,6 +14,23 @@ pub(crate) fn selection_lines(args: &TesterArgs, charts_output_path: Option<&str
         "rate_limit_rps: {}",
         format_opt_u64(args.rate_limit)
     ));
+    lines.push(format!(
+        "max_bandwidth: {}",
+        args.max_bandwidth
+            .map_or_else(|| "none".to_owned(), |limit| limit.to_string())
+    ));
+    lines.push(format!(
+        "sim_downlink: {}",
+        args.sim_downlink
+            .map_or_else(|| "none".to_owned(), |rate| rate.to_string())
+    ));
+    lines.push(format!(
+        "sim_latency_ms: {}",
+        args.sim_latency.map_or_else(
+            || "none".to_owned(),
+            |latency| latency.as_millis().to_string()
+        )
+    ));
     lines.push(format!("max_tasks: {}", args.max_tasks.get()));
     lines.push(format!("spawn_rate: {}", args.spawn_rate_per_tick.get()));
     lines.push(format!("spawn_interval_ms: {}", args.tick_interval.get()));
@@ -30,11 +47,16 @@ pub(crate) fn selection_lines(args: &TesterArgs, charts_output_path: Option<&str
     lines.push(format!("no_tui: {}", args.no_ui));
     lines.push(format!("summary: {}", args.summary));
     lines.push(format!("no_charts: {}", args.no_charts));
+    lines.push(format!("no_final_charts: {}", args.no_final_charts));
     lines.push(format!("charts_path: {}", args.charts_path));
     lines.push(format!(
         "charts_latency_bucket_ms: {}",
         args.charts_latency_bucket_ms.get()
     ));
+    lines.push(format!(
+        "chart_size: {}x{} @ {} dpi",
+        args.chart_width, args.chart_height, args.chart_dpi
+    ));
     lines.push(format!("tmp_path: {}", args.tmp_path));
     lines.push(format!("keep_tmp: {}", args.keep_tmp));
     lines.push(format!(
@@ -78,6 +100,9 @@ pub(crate) fn chart_status_line(
     if args.no_charts {
         return "Charts: disabled (--no-charts selected)".to_owned();
     }
+    if args.no_final_charts {
+        return "Charts: skipped (--no-final-charts selected)".to_owned();
+    }
     if let Some(path) = charts_output_path {
         return format!("Charts: saved in {}", path);
     }
@@ -97,10 +122,9 @@ fn format_opt_u64(value: Option<PositiveU64>) -> String {
 }
 
 fn format_metrics_range(range: &Option<MetricsRange>) -> String {
-    range.as_ref().map_or_else(
-        || "none".to_owned(),
-        |range| format!("{}-{}", range.0.start(), range.0.end()),
-    )
+    range
+        .as_ref()
+        .map_or_else(|| "none".to_owned(), ToString::to_string)
 }
 
 const fn format_output_format(format: Option<OutputFormat>) -> &'static str {
@@ -109,6 +133,8 @@ const fn format_output_format(format: Option<OutputFormat>) -> &'static str {
         Some(OutputFormat::Json) => "json",
         Some(OutputFormat::Jsonl) => "jsonl",
         Some(OutputFormat::Csv) => "csv",
+        Some(OutputFormat::Tsv) => "tsv",
+        Some(OutputFormat::Junit) => "junit",
         Some(OutputFormat::Quiet) => "quiet",
         None => "none",
     }
@@ -6,6 +6,8 @@ pub struct ReplayUi {
     pub window_start_ms: u64,
     pub window_end_ms: u64,
     pub cursor_ms: u64,
+    /// Playback speed as a percentage of real time.
+    pub speed_percent: u64,
     pub snapshot_start_ms: Option<u64>,
     pub snapshot_end_ms: Option<u64>,
 }
@@ -26,6 +28,13 @@ pub struct DataUsage {
     pub series: Vec<(u64, u64)>,
 }
 
+/// A distinct failure message and how often it has been seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorSample {
+    pub message: String,
+    pub count: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct UiData {
     pub elapsed_time: Duration,
@@ -42,9 +51,12 @@ pub struct UiData {
     pub rps_series: Vec<(u64, u64)>,
     pub status_counts: Option<StatusCounts>,
     pub data_usage: Option<DataUsage>,
+    pub error_samples: Vec<ErrorSample>,
     pub p50: u64,
     pub p90: u64,
     pub p99: u64,
+    pub p999: u64,
+    pub p9999: u64,
     pub p50_ok: u64,
     pub p90_ok: u64,
     pub p99_ok: u64,
@@ -69,6 +81,8 @@ pub struct CompareOverlay {
     pub p50: u64,
     pub p90: u64,
     pub p99: u64,
+    pub p999: u64,
+    pub p9999: u64,
     pub p50_ok: u64,
     pub p90_ok: u64,
     pub p99_ok: u64,
@@ -93,6 +107,8 @@ impl CompareOverlay {
             p50: data.p50,
             p90: data.p90,
             p99: data.p99,
+            p999: data.p999,
+            p9999: data.p9999,
             p50_ok: data.p50_ok,
             p90_ok: data.p90_ok,
             p99_ok: data.p99_ok,
@@ -118,9 +134,12 @@ pub struct UiRenderData {
     pub rps_series: Vec<(u64, u64)>,
     pub status_counts: Option<StatusCounts>,
     pub data_usage: Option<DataUsage>,
+    pub error_samples: Vec<ErrorSample>,
     pub p50: u64,
     pub p90: u64,
     pub p99: u64,
+    pub p999: u64,
+    pub p9999: u64,
     pub p50_ok: u64,
     pub p90_ok: u64,
     pub p99_ok: u64,
@@ -147,9 +166,12 @@ impl Default for UiData {
             rps_series: Vec::new(),
             status_counts: None,
             data_usage: None,
+            error_samples: Vec::new(),
             p50: 0,
             p90: 0,
             p99: 0,
+            p999: 0,
+            p9999: 0,
             p50_ok: 0,
             p90_ok: 0,
             p99_ok: 0,
@@ -178,9 +200,12 @@ impl From<&UiData> for UiRenderData {
             rps_series: data.rps_series.clone(),
             status_counts: data.status_counts.clone(),
             data_usage: data.data_usage.clone(),
+            error_samples: data.error_samples.clone(),
             p50: data.p50,
             p90: data.p90,
             p99: data.p99,
+            p999: data.p999,
+            p9999: data.p9999,
             p50_ok: data.p50_ok,
             p90_ok: data.p90_ok,
             p99_ok: data.p99_ok,
@@ -2,6 +2,7 @@ mod charts;
 mod charts_status_data;
 mod charts_window;
 mod dashboard;
+mod errors;
 mod formatting;
 mod frame;
 mod lifecycle;
@@ -12,6 +13,6 @@ mod summary_panels_quality;
 mod summary_run;
 mod theme;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "replay-video"))]
 pub use dashboard::{Ui, UiActions};
 pub use lifecycle::{run_splash_screen, setup_render_ui};
@@ -0,0 +1,51 @@
+use ratatui::{
+    layout::Rect,
+    prelude::{Backend, Frame, text},
+    text::Span,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::ui::model::UiRenderData;
+
+use super::formatting::format_count_compact;
+use super::theme::{
+    ACCENT_RED_RGB, panel_block_style, panel_border_style, panel_title_style, rgb, style_color,
+};
+
+/// Height of the error panel for the current samples, or zero when there are none.
+pub fn error_panel_height(data: &UiRenderData) -> u16 {
+    if data.error_samples.is_empty() {
+        return 0;
+    }
+    u16::try_from(data.error_samples.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2)
+}
+
+pub fn render_error_panel<B: Backend>(f: &mut Frame<'_, B>, data: &UiRenderData, chunk: Rect) {
+    let lines: Vec<text::Line<'static>> = data
+        .error_samples
+        .iter()
+        .map(|sample| {
+            text::Line::from(vec![
+                Span::styled(
+                    format!("{:>6}x ", format_count_compact(sample.count)),
+                    style_color(data.no_color, rgb(ACCENT_RED_RGB)),
+                ),
+                Span::from(sample.message.clone()),
+            ])
+        })
+        .collect();
+
+    let panel = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title("Recent Errors")
+                .borders(Borders::ALL)
+                .style(panel_block_style(data.no_color))
+                .border_style(panel_border_style(data.no_color))
+                .title_style(panel_title_style(data.no_color)),
+        )
+        .style(panel_block_style(data.no_color));
+    f.render_widget(panel, chunk);
+}
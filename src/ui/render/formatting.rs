@@ -16,6 +16,19 @@ pub(super) fn format_ms_as_tenths(ms: u128) -> String {
     format!("{}.{}s", secs, tenths)
 }
 
+/// Formats a playback speed percentage as a multiplier, e.g. `2x` or `0.25x`.
+pub(super) fn format_speed_percent(percent: u64) -> String {
+    let whole = percent.checked_div(PERCENT_DIVISOR).unwrap_or(0);
+    let frac = percent.checked_rem(PERCENT_DIVISOR).unwrap_or(0);
+    if frac == 0 {
+        format!("{whole}x")
+    } else if frac.checked_rem(10) == Some(0) {
+        format!("{whole}.{}x", frac.checked_div(10).unwrap_or(0))
+    } else {
+        format!("{whole}.{frac:02}x")
+    }
+}
+
 pub(super) fn format_bytes_compact(bytes: u128) -> String {
     const KB: u128 = 1_000;
     const MB: u128 = 1_000_000;
@@ -7,6 +7,7 @@ use ratatui::{
 use crate::ui::model::UiRenderData;
 
 use super::charts::render_charts;
+use super::errors::{error_panel_height, render_error_panel};
 use super::summary::render_summary;
 use super::theme::{CHART_MIN_HEIGHT, SUMMARY_HEIGHT, UI_MARGIN, app_background_style};
 
@@ -22,15 +23,19 @@ pub fn draw_frame<B: Backend>(f: &mut Frame<'_, B>, data: &UiRenderData) {
         .margin(UI_MARGIN)
         .constraints([
             Constraint::Length(SUMMARY_HEIGHT),
+            Constraint::Length(error_panel_height(data)),
             Constraint::Min(CHART_MIN_HEIGHT),
         ])
         .split(size);
 
-    let (summary_chunk, chart_chunk) = match chunks.as_ref() {
-        [a, b] => (a, b),
+    let (summary_chunk, error_chunk, chart_chunk) = match chunks.as_ref() {
+        [a, b, c] => (a, b, c),
         _ => return,
     };
 
     render_summary(f, data, *summary_chunk);
+    if error_chunk.height > 0 {
+        render_error_panel(f, data, *error_chunk);
+    }
     render_charts(f, data, *chart_chunk);
 }
@@ -19,6 +19,9 @@ use super::theme::{
     SPLASH_SUBTITLE_RGB, app_background_style, rgb, style_color, tri_gradient_color,
 };
 
+/// Key polling interval while holding the final frame.
+const HOLD_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 struct TerminalGuard;
 
 impl Drop for TerminalGuard {
@@ -31,6 +34,7 @@ impl Drop for TerminalGuard {
 pub fn setup_render_ui(
     shutdown_tx: &ShutdownSender,
     ui_tx: &watch::Sender<UiData>,
+    hold_ui: bool,
 ) -> tokio::task::JoinHandle<()> {
     let mut ui_rx = ui_tx.subscribe();
     let mut shutdown_rx = shutdown_tx.subscribe();
@@ -58,9 +62,38 @@ pub fn setup_render_ui(
                 }
             }
         }
+
+        if hold_ui {
+            hold_until_keypress(&mut terminal, &mut ui_rx).await;
+        }
     })
 }
 
+/// Keeps the final frame visible after shutdown until any key (including Ctrl-C) is pressed.
+///
+/// Late UI updates from the collector are still rendered so the held frame shows final totals.
+async fn hold_until_keypress<B: Backend>(
+    terminal: &mut Terminal<B>,
+    ui_rx: &mut watch::Receiver<UiData>,
+) {
+    let mut last = UiRenderData::from(&*ui_rx.borrow_and_update());
+    Ui::render(terminal, &last);
+    loop {
+        if ui_rx.has_changed().unwrap_or(false) {
+            last = UiRenderData::from(&*ui_rx.borrow_and_update());
+            Ui::render(terminal, &last);
+        }
+        while event::poll(Duration::ZERO).unwrap_or(false) {
+            match event::read() {
+                Ok(Event::Resize(..)) => Ui::render(terminal, &last),
+                Ok(Event::Key(_)) | Err(_) => return,
+                Ok(_) => {}
+            }
+        }
+        tokio::time::sleep(HOLD_POLL_INTERVAL).await;
+    }
+}
+
 /// Render a short splash screen before the main UI starts.
 ///
 /// # Errors
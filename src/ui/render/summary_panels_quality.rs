@@ -51,6 +51,18 @@ pub fn latency_panel(data: &UiRenderData) -> Paragraph<'static> {
                         style_color(data.no_color, rgb(ACCENT_RED_RGB)),
                     ),
                 ]),
+                text::Line::from(vec![
+                    Span::from("P999: "),
+                    Span::styled(
+                        format!("{}ms", data.p999),
+                        style_color(data.no_color, rgb(ACCENT_RED_RGB)),
+                    ),
+                    Span::from("   P9999: "),
+                    Span::styled(
+                        format!("{}ms", data.p9999),
+                        style_color(data.no_color, rgb(ACCENT_RED_RGB)),
+                    ),
+                ]),
             ]
         },
         |compare| {
@@ -119,6 +131,28 @@ pub fn latency_panel(data: &UiRenderData) -> Paragraph<'static> {
                         style_color(data.no_color, rgb(ACCENT_REPLAY_RGB)),
                     ),
                 ]),
+                text::Line::from(vec![
+                    Span::from("P999: "),
+                    Span::styled(
+                        format!("{}ms", data.p999),
+                        style_color(data.no_color, rgb(ACCENT_RED_RGB)),
+                    ),
+                    Span::from(" / "),
+                    Span::styled(
+                        format!("{}ms", compare.p999),
+                        style_color(data.no_color, rgb(ACCENT_REPLAY_RGB)),
+                    ),
+                    Span::from("   P9999: "),
+                    Span::styled(
+                        format!("{}ms", data.p9999),
+                        style_color(data.no_color, rgb(ACCENT_RED_RGB)),
+                    ),
+                    Span::from(" / "),
+                    Span::styled(
+                        format!("{}ms", compare.p9999),
+                        style_color(data.no_color, rgb(ACCENT_REPLAY_RGB)),
+                    ),
+                ]),
             ]
         },
     );
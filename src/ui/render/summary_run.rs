@@ -7,7 +7,7 @@ use ratatui::{
 
 use crate::ui::model::UiRenderData;
 
-use super::formatting::{format_count_compact, format_ms_as_tenths};
+use super::formatting::{format_count_compact, format_ms_as_tenths, format_speed_percent};
 use super::progress::progress_bar_line;
 use super::theme::{
     ACCENT_LOAD_RGB, ACCENT_REPLAY_RGB, ACCENT_SERIES_COMPARE_RGB, ACCENT_SERIES_PRIMARY_RGB,
@@ -80,6 +80,11 @@ pub fn render_run_panel<B: Backend>(f: &mut Frame<'_, B>, data: &UiRenderData, r
                 text::Line::from(vec![
                     Span::from("Replay: "),
                     Span::styled(status, style_color(data.no_color, rgb(ACCENT_REPLAY_RGB))),
+                    Span::from("  Speed: "),
+                    Span::styled(
+                        format_speed_percent(replay.speed_percent),
+                        style_color(data.no_color, rgb(PANEL_MUTED_RGB)),
+                    ),
                     Span::from("  Cursor: "),
                     Span::styled(
                         format_ms_as_tenths(u128::from(replay.cursor_ms)),
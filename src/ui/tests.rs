@@ -1,4 +1,4 @@
-use super::model::{DataUsage, StatusCounts, UiData, UiRenderData};
+use super::model::{DataUsage, ErrorSample, StatusCounts, UiData, UiRenderData};
 use super::render::{Ui, UiActions};
 use crate::error::{AppError, AppResult};
 use ratatui::Terminal;
@@ -43,9 +43,15 @@ fn ui_render_does_not_panic() -> AppResult<()> {
             bytes_per_sec: 1024,
             series: vec![(0, 512), (500, 1024), (900, 512)],
         }),
+        error_samples: vec![ErrorSample {
+            message: "request timed out".to_owned(),
+            count: 1,
+        }],
         p50: 15,
         p90: 20,
         p99: 20,
+        p999: 20,
+        p9999: 20,
         p50_ok: 15,
         p90_ok: 20,
         p99_ok: 20,
@@ -86,9 +92,15 @@ fn ui_render_data_from_ui_data() -> AppResult<()> {
             bytes_per_sec: 512,
             series: vec![(0, 256), (100, 256)],
         }),
+        error_samples: vec![ErrorSample {
+            message: "HTTP 503 Service Unavailable".to_owned(),
+            count: 1,
+        }],
         p50: 6,
         p90: 7,
         p99: 7,
+        p999: 7,
+        p9999: 7,
         p50_ok: 6,
         p90_ok: 7,
         p99_ok: 7,
@@ -123,6 +135,9 @@ fn ui_render_data_from_ui_data() -> AppResult<()> {
     if render_data.data_usage.is_none() != ui_data.data_usage.is_none() {
         return Err(AppError::validation("data_usage mismatch"));
     }
+    if render_data.error_samples != ui_data.error_samples {
+        return Err(AppError::validation("error_samples mismatch"));
+    }
     if render_data.p50 != ui_data.p50 {
         return Err(AppError::validation("p50 mismatch"));
     }
@@ -3,6 +3,7 @@ use std::process::{Command, Stdio};
 
 use serde_json::json;
 
+use crate::app::RunStartPayload;
 use crate::args::TesterArgs;
 use crate::error::{AppError, AppResult, ScriptError};
 use crate::metrics::MetricsSummary;
@@ -39,17 +40,16 @@ impl WasmPluginHost {
     }
 
     pub(crate) fn on_run_start(&mut self, args: &TesterArgs) -> AppResult<()> {
-        let payload = json!({
-            "event": "run_start",
-            "protocol": args.protocol.as_str(),
-            "load_mode": args.load_mode.as_str(),
-            "url": args.url,
-            "duration_seconds": args.target_duration.get(),
-            "max_tasks": args.max_tasks.get(),
-            "summary": args.summary,
-            "no_ui": args.no_ui
-        })
-        .to_string();
+        let mut payload =
+            serde_json::to_value(RunStartPayload::from_args(args)).map_err(|err| {
+                AppError::script(ScriptError::WasmPlugin {
+                    message: format!("failed to encode run_start payload: {}", err),
+                })
+            })?;
+        if let Some(fields) = payload.as_object_mut() {
+            fields.insert("event".to_owned(), json!("run_start"));
+        }
+        let payload = payload.to_string();
         self.dispatch(HOOK_RUN_START, &payload)
     }
 
@@ -78,6 +78,7 @@ fn run_distributed(streaming: bool) -> Result<(), String> {
         "1".to_owned(),
         "--spawn-interval".to_owned(),
         "100".to_owned(),
+        "--no-history".to_owned(),
         "--tmp-path".to_owned(),
         tmp_path,
         "--charts-path".to_owned(),
@@ -36,6 +36,7 @@ fn e2e_replay_from_tmp_logs() -> Result<(), String> {
         "--summary".to_owned(),
         "--no-charts".to_owned(),
         "--keep-tmp".to_owned(),
+        "--no-history".to_owned(),
         "--tmp-path".to_owned(),
         tmp_path.clone(),
         "--charts-path".to_owned(),
@@ -93,6 +94,7 @@ fn e2e_replay_from_exports() -> Result<(), String> {
         export_json.to_string_lossy().into_owned(),
         "--export-jsonl".to_owned(),
         export_jsonl.to_string_lossy().into_owned(),
+        "--no-history".to_owned(),
         "--tmp-path".to_owned(),
         tmp_path,
         "--charts-path".to_owned(),
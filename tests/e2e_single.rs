@@ -44,6 +44,7 @@ fn e2e_single_cli_basic() -> Result<(), String> {
         "1".to_owned(),
         "--spawn-interval".to_owned(),
         "100".to_owned(),
+        "--no-history".to_owned(),
         "--tmp-path".to_owned(),
         tmp_path,
         "--charts-path".to_owned(),
@@ -61,6 +62,55 @@ fn e2e_single_cli_basic() -> Result<(), String> {
     Ok(())
 }
 
+#[test]
+fn e2e_single_strict_charts_fails_on_chart_errors() -> Result<(), String> {
+    let Some((url, _server)) = spawn_http_server_or_skip()? else {
+        return Ok(());
+    };
+    let (dir, _charts_path, tmp_path) = prep_paths()?;
+    // A regular file where the charts directory should go makes rendering fail.
+    let blocked_charts = dir.path().join("charts-blocked");
+    fs::write(&blocked_charts, b"").map_err(|err| format!("write file failed: {}", err))?;
+
+    let run = |strict: bool| {
+        let mut args = vec![
+            "-u".to_owned(),
+            url.clone(),
+            "-t".to_owned(),
+            "1".to_owned(),
+            "--no-tui".to_owned(),
+            "--summary".to_owned(),
+            "--rate".to_owned(),
+            "20".to_owned(),
+            "--no-history".to_owned(),
+            "--tmp-path".to_owned(),
+            tmp_path.clone(),
+            "--charts-path".to_owned(),
+            blocked_charts.to_string_lossy().into_owned(),
+        ];
+        if strict {
+            args.push("--strict-charts".to_owned());
+        }
+        run_strest(args)
+    };
+
+    let lenient = run(false)?;
+    if !lenient.status.success() {
+        return Err(format!(
+            "Expected chart errors to be logged only.\nstderr: {}",
+            String::from_utf8_lossy(&lenient.stderr)
+        ));
+    }
+    let strict = run(true)?;
+    if strict.status.success() {
+        return Err(format!(
+            "Expected --strict-charts to fail the run.\nstdout: {}",
+            String::from_utf8_lossy(&strict.stdout)
+        ));
+    }
+    Ok(())
+}
+
 #[test]
 fn e2e_single_exports() -> Result<(), String> {
     let Some((url, _server)) = spawn_http_server_or_skip()? else {
@@ -90,6 +140,7 @@ fn e2e_single_exports() -> Result<(), String> {
         export_json.to_string_lossy().into_owned(),
         "--export-jsonl".to_owned(),
         export_jsonl.to_string_lossy().into_owned(),
+        "--no-history".to_owned(),
         "--tmp-path".to_owned(),
         tmp_path,
         "--charts-path".to_owned(),
@@ -131,6 +182,52 @@ fn e2e_single_exports() -> Result<(), String> {
     Ok(())
 }
 
+#[test]
+fn e2e_single_summary_out_matches_printed_summary() -> Result<(), String> {
+    let Some((url, _server)) = spawn_http_server_or_skip()? else {
+        return Ok(());
+    };
+    let (dir, charts_path, tmp_path) = prep_paths()?;
+    let summary_out = dir.path().join("summary.txt");
+
+    let args = vec![
+        "-u".to_owned(),
+        url,
+        "-t".to_owned(),
+        "1".to_owned(),
+        "--no-tui".to_owned(),
+        "--no-charts".to_owned(),
+        "--summary-format".to_owned(),
+        "plain".to_owned(),
+        "--summary-out".to_owned(),
+        summary_out.to_string_lossy().into_owned(),
+        "--no-history".to_owned(),
+        "--tmp-path".to_owned(),
+        tmp_path,
+        "--charts-path".to_owned(),
+        charts_path,
+    ];
+
+    let output = run_strest(args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        return Err(format!(
+            "stdout: {}\nstderr: {}",
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let written =
+        fs::read_to_string(&summary_out).map_err(|err| format!("read summary failed: {}", err))?;
+    if !written.contains("total_requests: ") || !stdout.contains(written.as_str()) {
+        return Err(format!(
+            "Summary file does not match stdout.\nfile: {}\nstdout: {}",
+            written, stdout
+        ));
+    }
+    Ok(())
+}
+
 #[test]
 fn e2e_single_config_toml_load_and_sinks() -> Result<(), String> {
     let Some((url, _server)) = spawn_http_server_or_skip()? else {
@@ -169,6 +266,7 @@ path = "{sink}"
     let args = vec![
         "--config".to_owned(),
         config_path.to_string_lossy().into_owned(),
+        "--no-history".to_owned(),
         "--tmp-path".to_owned(),
         tmp_path,
         "--charts-path".to_owned(),
@@ -222,6 +320,7 @@ fn e2e_single_config_json_scenario() -> Result<(), String> {
     let args = vec![
         "--config".to_owned(),
         config_path.to_string_lossy().into_owned(),
+        "--no-history".to_owned(),
         "--tmp-path".to_owned(),
         tmp_path,
         "--charts-path".to_owned(),
@@ -238,3 +337,15 @@ fn e2e_single_config_json_scenario() -> Result<(), String> {
     }
     Ok(())
 }
+
+#[test]
+fn e2e_single_invalid_args_exit_code() -> Result<(), String> {
+    let output = run_strest(vec!["--definitely-not-a-flag".to_owned()])?;
+    if output.status.code() != Some(4) {
+        return Err(format!(
+            "Expected exit code 4 for invalid args, got {:?}",
+            output.status.code()
+        ));
+    }
+    Ok(())
+}
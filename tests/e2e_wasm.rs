@@ -66,6 +66,7 @@ fn e2e_wasm_script_runs() -> Result<(), String> {
         "--no-tui".to_owned(),
         "--summary".to_owned(),
         "--no-charts".to_owned(),
+        "--no-history".to_owned(),
         "--max-tasks".to_owned(),
         "1".to_owned(),
     ];